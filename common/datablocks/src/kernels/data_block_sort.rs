@@ -13,6 +13,8 @@ pub struct SortColumnDescription {
     pub column_name: String,
     pub asc: bool,
     pub nulls_first: bool,
+    /// The strings are sorted by their collation keys.
+    pub collation: DataCollation,
 }
 
 impl DataBlock {
@@ -24,8 +26,11 @@ impl DataBlock {
         let order_columns = sort_columns_descriptions
             .iter()
             .map(|f| {
+                let column = f
+                    .collation
+                    .keys(block.try_column_by_name(&f.column_name)?)?;
                 Ok(compute::SortColumn {
-                    values: block.try_array_by_name(&f.column_name)?.get_array_ref(),
+                    values: column.to_array()?.get_array_ref(),
                     options: Some(compute::SortOptions {
                         descending: !f.asc,
                         nulls_first: f.nulls_first,
@@ -56,7 +61,7 @@ impl DataBlock {
         for block in [lhs, rhs].iter() {
             let columns = sort_columns_descriptions
                 .iter()
-                .map(|f| Ok(block.try_column_by_name(&f.column_name)?.clone()))
+                .map(|f| f.collation.keys(block.try_column_by_name(&f.column_name)?))
                 .collect::<Result<Vec<_>>>()?;
             sort_columns.push(columns);
         }
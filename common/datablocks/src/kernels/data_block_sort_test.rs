@@ -24,6 +24,7 @@ fn test_data_block_sort() -> Result<()> {
             column_name: "a".to_owned(),
             asc: true,
             nulls_first: false,
+            collation: DataCollation::Binary,
         }];
         let results = DataBlock::sort_block(&raw, &options, Some(3))?;
         assert_eq!(raw.schema(), results.schema());
@@ -45,6 +46,7 @@ fn test_data_block_sort() -> Result<()> {
             column_name: "a".to_owned(),
             asc: false,
             nulls_first: false,
+            collation: DataCollation::Binary,
         }];
         let results = DataBlock::sort_block(&raw, &options, Some(3))?;
         assert_eq!(raw.schema(), results.schema());
@@ -60,5 +62,58 @@ fn test_data_block_sort() -> Result<()> {
         ];
         crate::assert_blocks_eq(expected, &[results]);
     }
+
+    {
+        // The strings are sorted case-insensitively.
+        let raw = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![1, 2, 3, 4]),
+            Series::new(vec!["b", "A", "a", "B"]),
+        ]);
+        let options = vec![
+            SortColumnDescription {
+                column_name: "b".to_owned(),
+                asc: true,
+                nulls_first: false,
+                collation: DataCollation::Utf8GeneralCi,
+            },
+            SortColumnDescription {
+                column_name: "a".to_owned(),
+                asc: true,
+                nulls_first: false,
+                collation: DataCollation::Binary,
+            },
+        ];
+        let results = DataBlock::sort_block(&raw, &options, None)?;
+
+        let expected = vec![
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 2 | A |",
+            "| 3 | a |",
+            "| 1 | b |",
+            "| 4 | B |",
+            "+---+---+",
+        ];
+        crate::assert_blocks_eq(expected, &[results.clone()]);
+
+        let other = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![5]),
+            Series::new(vec!["a2"]),
+        ]);
+        let merged = DataBlock::merge_sort_block(&results, &other, &options, None)?;
+        let expected = vec![
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 2 | A  |",
+            "| 3 | a  |",
+            "| 5 | a2 |",
+            "| 1 | b  |",
+            "| 4 | B  |",
+            "+---+----+",
+        ];
+        crate::assert_blocks_eq(expected, &[merged]);
+    }
     Ok(())
 }
@@ -41,4 +41,27 @@ impl DataBlock {
 
         Ok(DataBlock::create(raw.schema().clone(), columns))
     }
+
+    /// Takes the rows by the indices, a None index takes a row of nulls, e.g. for the rows of
+    /// the outer joins which match no row.
+    pub fn block_take_by_opt_indices(
+        raw: &DataBlock,
+        indices: &[Option<u32>],
+    ) -> Result<DataBlock> {
+        if indices.is_empty() {
+            return Ok(DataBlock::empty_with_schema(raw.schema().clone()));
+        }
+        let columns = raw
+            .columns()
+            .iter()
+            .map(|column| {
+                let array = column.to_array()?;
+                let mut indices = indices.iter().map(|f| f.map(|f| f as usize));
+                let series = unsafe { array.take_opt_iter_unchecked(&mut indices) }?;
+                Ok(DataColumn::Array(series))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(DataBlock::create(raw.schema().clone(), columns))
+    }
 }
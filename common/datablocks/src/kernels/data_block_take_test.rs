@@ -34,3 +34,40 @@ fn test_data_block_take() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_data_block_take_by_opt_indices() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, true),
+        DataField::new("b", DataType::Utf8, true),
+    ]);
+
+    let raw = DataBlock::create(schema.clone(), vec![
+        DataColumn::Array(Series::new(vec![1i64, 2, 3])),
+        DataColumn::Constant(DataValue::Utf8(Some("b".to_string())), 3),
+    ]);
+
+    // A None index takes nulls.
+    let take = DataBlock::block_take_by_opt_indices(&raw, &[Some(2), None, Some(0)])?;
+    assert_eq!(raw.schema(), take.schema());
+    assert_eq!(take.column(0).to_array()?.null_count(), 1);
+    assert_eq!(take.column(1).to_array()?.null_count(), 1);
+
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "|   |   |",
+        "| 1 | b |",
+        "| 3 | b |",
+        "+---+---+",
+    ];
+    crate::assert_blocks_sorted_eq(expected, &[take]);
+
+    // The rows of an empty block are all nulls.
+    let empty = DataBlock::empty_with_schema(schema);
+    let take = DataBlock::block_take_by_opt_indices(&empty, &[None, None])?;
+    assert_eq!(take.num_rows(), 2);
+    assert_eq!(take.column(0).to_array()?.null_count(), 2);
+    Ok(())
+}
@@ -8,6 +8,7 @@ use common_arrow::arrow::array::Array;
 use common_arrow::arrow::array::ArrayRef;
 use common_arrow::arrow::array::BinaryBuilder;
 use common_arrow::arrow::array::BooleanBuilder;
+use common_arrow::arrow::array::DecimalBuilder;
 use common_arrow::arrow::array::ListBuilder;
 use common_arrow::arrow::array::PrimitiveBuilder;
 use common_arrow::arrow::array::StringBuilder;
@@ -527,3 +528,36 @@ impl BinaryArrayBuilder {
         DFBinaryArray::from_arrow_array(array)
     }
 }
+
+pub struct DecimalArrayBuilder {
+    builder: DecimalBuilder,
+}
+
+impl DecimalArrayBuilder {
+    pub fn new(capacity: usize, precision: usize, scale: usize) -> Self {
+        Self {
+            builder: DecimalBuilder::new(capacity, precision, scale),
+        }
+    }
+
+    /// Appends the value scaled by 10^scale.
+    pub fn append_value(&mut self, value: i128) {
+        self.builder.append_value(value).unwrap();
+    }
+
+    pub fn append_null(&mut self) {
+        self.builder.append_null().unwrap();
+    }
+
+    pub fn append_option(&mut self, value: Option<i128>) {
+        match value {
+            Some(value) => self.append_value(value),
+            None => self.append_null(),
+        }
+    }
+
+    pub fn finish(&mut self) -> DFDecimalArray {
+        let array = self.builder.finish();
+        DFDecimalArray::from_arrow_array(array)
+    }
+}
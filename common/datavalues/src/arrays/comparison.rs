@@ -115,17 +115,26 @@ where T: DFNumericType
 
 macro_rules! impl_eq_missing {
     ($self:ident, $rhs:ident) => {{
-        match ($self.null_count(), $rhs.null_count()) {
-            (0, 0) => $self
-                .into_no_null_iter()
-                .zip($rhs.into_no_null_iter())
-                .map(|(opt_a, opt_b)| opt_a == opt_b)
-                .collect(),
-            (_, _) => $self
-                .downcast_iter()
-                .zip($rhs.downcast_iter())
-                .map(|(opt_a, opt_b)| opt_a == opt_b)
-                .collect(),
+        // broadcast
+        if $rhs.len() == 1 {
+            let value = $rhs.get(0);
+            $self.downcast_iter().map(|opt_a| opt_a == value).collect()
+        } else if $self.len() == 1 {
+            let value = $self.get(0);
+            $rhs.downcast_iter().map(|opt_b| opt_b == value).collect()
+        } else {
+            match ($self.null_count(), $rhs.null_count()) {
+                (0, 0) => $self
+                    .into_no_null_iter()
+                    .zip($rhs.into_no_null_iter())
+                    .map(|(opt_a, opt_b)| opt_a == opt_b)
+                    .collect(),
+                (_, _) => $self
+                    .downcast_iter()
+                    .zip($rhs.downcast_iter())
+                    .map(|(opt_a, opt_b)| opt_a == opt_b)
+                    .collect(),
+            }
         }
     }};
 }
@@ -328,6 +337,67 @@ impl ArrayCompare<&DFUtf8Array> for DFUtf8Array {
     }
 }
 
+impl DFDecimalArray {
+    /// Compares the decimals of the same scale, the array of one value is broadcast.
+    fn comparison(
+        &self,
+        rhs: &DFDecimalArray,
+        operator: impl Fn(Option<i128>, Option<i128>) -> Option<bool>,
+    ) -> DFBooleanArray {
+        let len = if self.len() == 1 {
+            rhs.len()
+        } else {
+            self.len()
+        };
+        let value = |array: &DFDecimalArray, index: usize| match array.len() {
+            1 => array.get(0),
+            _ => array.get(index),
+        };
+        (0..len)
+            .map(|index| operator(value(self, index), value(rhs, index)))
+            .collect()
+    }
+}
+
+macro_rules! impl_cmp_decimal {
+    ($self:ident, $rhs:ident, $operand:tt) => {{
+        Ok($self.comparison($rhs, |lhs, rhs| match (lhs, rhs) {
+            (Some(lhs), Some(rhs)) => Some(lhs $operand rhs),
+            _ => None,
+        }))
+    }};
+}
+
+impl ArrayCompare<&DFDecimalArray> for DFDecimalArray {
+    fn eq_missing(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        Ok(self.comparison(rhs, |lhs, rhs| Some(lhs == rhs)))
+    }
+
+    fn eq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        impl_cmp_decimal! {self, rhs, ==}
+    }
+
+    fn neq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        impl_cmp_decimal! {self, rhs, !=}
+    }
+
+    fn gt(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        impl_cmp_decimal! {self, rhs, >}
+    }
+
+    fn gt_eq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        impl_cmp_decimal! {self, rhs, >=}
+    }
+
+    fn lt(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        impl_cmp_decimal! {self, rhs, <}
+    }
+
+    fn lt_eq(&self, rhs: &DFDecimalArray) -> Result<DFBooleanArray> {
+        impl_cmp_decimal! {self, rhs, <=}
+    }
+}
+
 impl ArrayCompare<&DFNullArray> for DFNullArray {}
 impl ArrayCompare<&DFBinaryArray> for DFBinaryArray {}
 impl ArrayCompare<&DFStructArray> for DFStructArray {}
@@ -561,6 +631,15 @@ impl ArrayEqualElement for DFUtf8Array {
     }
 }
 
+impl ArrayEqualElement for DFDecimalArray {
+    unsafe fn equal_element(&self, idx_self: usize, idx_other: usize, other: &Series) -> bool {
+        let ca_other = other.as_ref().as_ref();
+        debug_assert!(self.data_type() == other.data_type());
+        let ca_other = &*(ca_other as *const DFDecimalArray);
+        self.get(idx_self) == ca_other.get(idx_other)
+    }
+}
+
 impl ArrayEqualElement for DFListArray {}
 impl ArrayEqualElement for DFNullArray {}
 impl ArrayEqualElement for DFStructArray {}
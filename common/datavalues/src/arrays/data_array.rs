@@ -189,6 +189,20 @@ where T: DFDataType
                 downcast_and_pack!(BinaryArray, Binary)
             }
 
+            DataType::Decimal(_, _) => {
+                let array = &*(arr as *const dyn Array as *const DecimalArray);
+                let value = match array.is_null(index) {
+                    true => None,
+                    false => Some(array.value(index)),
+                };
+                match self.data_type() {
+                    DataType::Decimal(precision, scale) => {
+                        Ok(DataValue::Decimal(value, precision, scale))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
             DataType::List(fs) => {
                 let list_array = &*(arr as *const dyn Array as *const ListArray);
                 let value = match list_array.is_null(index) {
@@ -249,7 +263,7 @@ where T: DFDataType
         };
 
         let array_data = ArrayData::new(
-            T::data_type().to_arrow(),
+            data.data_type().clone(),
             data.len(),
             None,
             Some(bitmap_and.into_buffer()),
@@ -14,11 +14,13 @@ use num::Zero;
 use crate::prelude::*;
 use crate::DFBinaryArray;
 use crate::DFBooleanArray;
+use crate::DFDecimalArray;
 use crate::DFListArray;
 use crate::DFNullArray;
 use crate::DFNumericType;
 use crate::DFStructArray;
 use crate::DFUtf8Array;
+use crate::DECIMAL_MAX_PRECISION;
 
 /// Same common aggregators
 pub trait ArrayAgg: Debug {
@@ -314,6 +316,73 @@ impl ArrayAgg for DFUtf8Array {
     }
 }
 
+impl ArrayAgg for DFDecimalArray {
+    /// The sum has the max precision and the same scale.
+    fn sum(&self) -> Result<DataValue> {
+        let (_, scale) = self.precision_and_scale();
+        let mut sum = None;
+        for value in self.downcast_iter().flatten() {
+            let value = sum.unwrap_or(0_i128).checked_add(value).ok_or_else(|| {
+                ErrorCode::BadDataValueType(format!("Decimal overflow in the sum of {:?}", self))
+            })?;
+            sum = Some(value);
+        }
+        Ok(DataValue::Decimal(sum, DECIMAL_MAX_PRECISION, scale))
+    }
+
+    fn min(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_and_scale();
+        let min = self.downcast_iter().flatten().min();
+        Ok(DataValue::Decimal(min, precision, scale))
+    }
+
+    fn max(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_and_scale();
+        let max = self.downcast_iter().flatten().max();
+        Ok(DataValue::Decimal(max, precision, scale))
+    }
+
+    fn arg_min(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_and_scale();
+        let value = self
+            .downcast_iter()
+            .enumerate()
+            .filter_map(|(idx, val)| val.map(|val| (idx, val)))
+            .reduce(|acc, (idx, val)| if acc.1 > val { (idx, val) } else { acc });
+
+        Ok(match value {
+            Some((index, value)) => DataValue::Struct(vec![
+                (index as u64).into(),
+                DataValue::Decimal(Some(value), precision, scale),
+            ]),
+            None => DataValue::Struct(vec![
+                DataValue::UInt64(None),
+                DataValue::Decimal(None, precision, scale),
+            ]),
+        })
+    }
+
+    fn arg_max(&self) -> Result<DataValue> {
+        let (precision, scale) = self.precision_and_scale();
+        let value = self
+            .downcast_iter()
+            .enumerate()
+            .filter_map(|(idx, val)| val.map(|val| (idx, val)))
+            .reduce(|acc, (idx, val)| if acc.1 < val { (idx, val) } else { acc });
+
+        Ok(match value {
+            Some((index, value)) => DataValue::Struct(vec![
+                (index as u64).into(),
+                DataValue::Decimal(Some(value), precision, scale),
+            ]),
+            None => DataValue::Struct(vec![
+                DataValue::UInt64(None),
+                DataValue::Decimal(None, precision, scale),
+            ]),
+        })
+    }
+}
+
 impl ArrayAgg for DFListArray {}
 impl ArrayAgg for DFBinaryArray {}
 impl ArrayAgg for DFNullArray {}
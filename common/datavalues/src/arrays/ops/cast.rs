@@ -8,7 +8,11 @@ use common_exception::Result;
 use num::NumCast;
 
 use crate::arrays::DataArray;
+use crate::cast_to_decimal;
 use crate::data_df_type::*;
+use crate::decimal_to_f64;
+use crate::decimal_to_string;
+use crate::is_integer;
 use crate::series::IntoSeries;
 use crate::series::Series;
 use crate::DFDataType;
@@ -61,6 +65,9 @@ macro_rules! cast_with_type {
             Date64 => ArrayCast::cast::<Date64Type>($self).map(|ca| ca.into_series()),
 
             List(_) => ArrayCast::cast::<ListType>($self).map(|ca| ca.into_series()),
+            Decimal(precision, scale) => {
+                cast_to_decimal(&$self.array.clone().into_series(), *precision, *scale)
+            }
             dt => Err(ErrorCode::IllegalDataType(format!(
                 "Arrow datatype {:?} not supported by Datafuse",
                 dt
@@ -148,3 +155,43 @@ impl ArrayCast for DFBinaryArray {
         todo!()
     }
 }
+
+impl ArrayCast for DFDecimalArray {
+    fn cast<N>(&self) -> Result<DataArray<N>>
+    where N: DFDataType {
+        let series = self.cast_with_type(&N::data_type())?;
+        Ok(series.get_array_ref().into())
+    }
+
+    /// The integers are truncated, the values out of the range of the integers are NULL.
+    fn cast_with_type(&self, data_type: &DataType) -> Result<Series> {
+        let (_, scale) = self.precision_and_scale();
+        match data_type {
+            DataType::Decimal(precision, scale) => {
+                cast_to_decimal(&self.clone().into_series(), *precision, *scale)
+            }
+            DataType::Float32 | DataType::Float64 => self
+                .downcast_iter()
+                .map(|value| value.map(|v| decimal_to_f64(v, scale)))
+                .collect::<DFFloat64Array>()
+                .cast_with_type(data_type),
+            DataType::Utf8 => Ok(self
+                .downcast_iter()
+                .map(|value| value.map(|v| decimal_to_string(v, scale)))
+                .collect::<DFUtf8Array>()
+                .into_series()),
+            data_type if is_integer(data_type) => {
+                let factor = 10_i128.pow(scale as u32);
+                self.downcast_iter()
+                    .map(|value| value.and_then(|v| <i64 as NumCast>::from(v / factor)))
+                    .collect::<DFInt64Array>()
+                    .cast_with_type(data_type)
+            }
+            other => Err(ErrorCode::BadDataValueType(format!(
+                "Unsupported cast from {:?} to {:?}",
+                self.data_type(),
+                other
+            ))),
+        }
+    }
+}
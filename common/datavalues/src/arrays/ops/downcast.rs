@@ -8,6 +8,7 @@ use common_arrow::arrow::array::Array;
 use common_arrow::arrow::array::ArrayRef;
 use common_arrow::arrow::array::BinaryArray;
 use common_arrow::arrow::array::BooleanArray;
+use common_arrow::arrow::array::DecimalArray;
 use common_arrow::arrow::array::ListArray;
 use common_arrow::arrow::array::PrimitiveArray;
 use common_arrow::arrow::array::StringArray;
@@ -18,10 +19,12 @@ use crate::series::IntoSeries;
 use crate::series::Series;
 use crate::DFBinaryArray;
 use crate::DFBooleanArray;
+use crate::DFDecimalArray;
 use crate::DFListArray;
 use crate::DFPrimitiveType;
 use crate::DFStructArray;
 use crate::DFUtf8Array;
+use crate::DataType;
 
 impl<T> AsRef<PrimitiveArray<T>> for DataArray<T>
 where T: DFPrimitiveType
@@ -154,6 +157,49 @@ impl DFBinaryArray {
     }
 }
 
+impl AsRef<DecimalArray> for DFDecimalArray {
+    fn as_ref(&self) -> &DecimalArray {
+        self.downcast_ref()
+    }
+}
+
+impl DFDecimalArray {
+    pub fn downcast_ref(&self) -> &DecimalArray {
+        let arr = &*self.array;
+        unsafe { &*(arr as *const dyn Array as *const DecimalArray) }
+    }
+
+    /// The scaled values of the decimals.
+    pub fn downcast_iter(&self) -> impl Iterator<Item = Option<i128>> + DoubleEndedIterator + '_ {
+        (0..self.len()).map(move |i| self.get(i))
+    }
+
+    pub fn collect_values(&self) -> Vec<Option<i128>> {
+        self.downcast_iter().collect()
+    }
+
+    /// The scaled value of the row, None if it is NULL.
+    pub fn get(&self, index: usize) -> Option<i128> {
+        let arr = self.downcast_ref();
+        match arr.is_null(index) {
+            true => None,
+            false => Some(arr.value(index)),
+        }
+    }
+
+    pub fn precision_and_scale(&self) -> (usize, usize) {
+        match self.data_type() {
+            DataType::Decimal(precision, scale) => (precision, scale),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn from_arrow_array(array: DecimalArray) -> Self {
+        let array_ref = Arc::new(array) as ArrayRef;
+        array_ref.into()
+    }
+}
+
 impl AsRef<StructArray> for DFStructArray {
     fn as_ref(&self) -> &StructArray {
         self.downcast_ref()
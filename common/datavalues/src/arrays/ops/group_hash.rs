@@ -201,5 +201,6 @@ impl GroupHash for DFInt64Array {
 impl GroupHash for DFListArray {}
 impl GroupHash for DFUtf8Array {}
 impl GroupHash for DFBinaryArray {}
+impl GroupHash for DFDecimalArray {}
 impl GroupHash for DFNullArray {}
 impl GroupHash for DFStructArray {}
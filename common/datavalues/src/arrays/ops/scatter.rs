@@ -11,6 +11,7 @@ use crate::arrays::get_list_builder;
 use crate::arrays::BinaryArrayBuilder;
 use crate::arrays::BooleanArrayBuilder;
 use crate::arrays::DataArray;
+use crate::arrays::DecimalArrayBuilder;
 use crate::arrays::PrimitiveArrayBuilder;
 use crate::arrays::Utf8ArrayBuilder;
 use crate::prelude::*;
@@ -236,5 +237,33 @@ impl ArrayScatter for DFBinaryArray {
     }
 }
 
+impl ArrayScatter for DFDecimalArray {
+    unsafe fn scatter_unchecked(
+        &self,
+        indices: &mut dyn Iterator<Item = u64>,
+        scattered_size: usize,
+    ) -> Result<Vec<Self>>
+    where
+        Self: std::marker::Sized,
+    {
+        let (precision, scale) = self.precision_and_scale();
+        let mut builders = Vec::with_capacity(scattered_size);
+        let guess_scattered_len = ((self.len() as f64) * 1.1 / (scattered_size as f64)) as usize;
+        for _i in 0..scattered_size {
+            let builder = DecimalArrayBuilder::new(guess_scattered_len, precision, scale);
+            builders.push(builder);
+        }
+
+        for (value, index) in self.downcast_iter().zip(indices) {
+            builders[index as usize].append_option(value);
+        }
+
+        Ok(builders
+            .iter_mut()
+            .map(|builder| builder.finish())
+            .collect())
+    }
+}
+
 impl ArrayScatter for DFNullArray {}
 impl ArrayScatter for DFStructArray {}
@@ -345,6 +345,39 @@ impl ArrayTake for DFNullArray {}
 impl ArrayTake for DFStructArray {}
 impl ArrayTake for DFBinaryArray {}
 
+impl ArrayTake for DFDecimalArray {
+    unsafe fn take_unchecked<I, INulls>(&self, indices: TakeIdx<I, INulls>) -> Result<Self>
+    where
+        Self: std::marker::Sized,
+        I: Iterator<Item = usize>,
+        INulls: Iterator<Item = Option<usize>>,
+    {
+        self.take(indices)
+    }
+
+    fn take<I, INulls>(&self, indices: TakeIdx<I, INulls>) -> Result<Self>
+    where
+        Self: std::marker::Sized,
+        I: Iterator<Item = usize>,
+        INulls: Iterator<Item = Option<usize>>,
+    {
+        let (precision, scale) = self.precision_and_scale();
+        let value = |index: usize| self.get(index);
+
+        let mut builder = DecimalArrayBuilder::new(self.len(), precision, scale);
+        match indices {
+            TakeIdx::Array(array) => array
+                .iter()
+                .for_each(|index| builder.append_option(index.and_then(|i| value(i as usize)))),
+            TakeIdx::Iter(iter) => iter.for_each(|index| builder.append_option(value(index))),
+            TakeIdx::IterNulls(iter) => {
+                iter.for_each(|index| builder.append_option(index.and_then(value)))
+            }
+        }
+        Ok(builder.finish())
+    }
+}
+
 pub trait AsTakeIndex {
     fn as_take_iter<'a>(&'a self) -> Box<dyn Iterator<Item = usize> + 'a>;
 
@@ -401,3 +434,4 @@ impl ArrayTakeEvery<ListType> for DFListArray {
 impl ArrayTakeEvery<NullType> for DFNullArray {}
 impl ArrayTakeEvery<StructType> for DFStructArray {}
 impl ArrayTakeEvery<BinaryType> for DFBinaryArray {}
+impl ArrayTakeEvery<DecimalType> for DFDecimalArray {}
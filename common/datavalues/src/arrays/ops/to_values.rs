@@ -12,6 +12,7 @@ use crate::arrays::DataArray;
 use crate::prelude::*;
 use crate::DFBinaryArray;
 use crate::DFBooleanArray;
+use crate::DFDecimalArray;
 use crate::DFListArray;
 use crate::DFNullArray;
 use crate::DFStructArray;
@@ -240,6 +241,16 @@ impl ToValues for DFBinaryArray {
     }
 }
 
+impl ToValues for DFDecimalArray {
+    fn to_values(&self) -> Result<Vec<DataValue>> {
+        let (precision, scale) = self.precision_and_scale();
+        Ok(self
+            .downcast_iter()
+            .map(|value| DataValue::Decimal(value, precision, scale))
+            .collect())
+    }
+}
+
 impl ToValues for DFListArray {
     fn to_values(&self) -> Result<Vec<DataValue>> {
         let mut values = Vec::with_capacity(self.len());
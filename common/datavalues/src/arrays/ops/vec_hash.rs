@@ -13,6 +13,7 @@ use crate::arrays::DataArray;
 use crate::prelude::*;
 use crate::DFBinaryArray;
 use crate::DFBooleanArray;
+use crate::DFDecimalArray;
 use crate::DFFloat32Array;
 use crate::DFFloat64Array;
 use crate::DFHasher;
@@ -109,6 +110,23 @@ impl VecHash for DFBinaryArray {
     }
 }
 
+impl VecHash for DFDecimalArray {
+    fn vec_hash(&self, hasher: DFHasher) -> Result<DFUInt64Array> {
+        let mut builder = PrimitiveArrayBuilder::<UInt64Type>::new(self.len());
+
+        self.downcast_iter().for_each(|value| match value {
+            None => builder.append_null(),
+            Some(value) => {
+                let mut h = hasher.clone_initial();
+                h.write(&value.to_le_bytes());
+                builder.append_value(h.finish());
+            }
+        });
+
+        Ok(builder.finish())
+    }
+}
+
 impl VecHash for DFListArray {
     fn vec_hash(&self, _hasher: DFHasher) -> Result<DFUInt64Array> {
         Err(ErrorCode::BadDataValueType(format!(
@@ -157,6 +157,12 @@ impl DataColumn {
                     v.extend_from_slice(&array.value(row.unwrap_or(i)).to_le_bytes());
                 }
             }
+            DataType::Decimal(_, _) => {
+                let array = col.decimal()?.downcast_ref();
+                for (i, v) in vec.iter_mut().enumerate().take(size) {
+                    v.extend_from_slice(&array.value(row.unwrap_or(i)).to_le_bytes());
+                }
+            }
 
             _ => {
                 // This is internal because we should have caught this before.
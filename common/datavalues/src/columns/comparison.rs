@@ -31,6 +31,15 @@ impl DataColumn {
             DataValueComparisonOperator::NotEq => apply_cmp! {self, rhs, neq},
             DataValueComparisonOperator::Like => apply_cmp! {self, rhs, like},
             DataValueComparisonOperator::NotLike => apply_cmp! {self, rhs, nlike},
+            DataValueComparisonOperator::EqNullSafe => apply_cmp! {self, rhs, eq_missing},
+            DataValueComparisonOperator::DistinctFrom => {
+                let lhs = self.to_minimal_array()?;
+                let rhs = rhs.to_minimal_array()?;
+
+                let result = lhs.eq_missing(&rhs)?.not()?;
+                let result: DataColumn = result.into_series().into();
+                Ok(result.resize_constant(self.len()))
+            }
         }
     }
 }
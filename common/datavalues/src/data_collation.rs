@@ -0,0 +1,100 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::borrow::Cow;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::prelude::*;
+
+/// The rules to compare strings, two strings are compared by their collation keys, so the
+/// comparisons, the sorts and the group-by equality of a collation only need the keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DataCollation {
+    /// Compares the bytes of the strings.
+    Binary,
+    /// Compares the strings case-insensitively.
+    Utf8GeneralCi,
+    /// Compares the strings case-insensitively and accent-insensitively, by their canonical
+    /// decompositions without the combining marks.
+    Utf8UnicodeCi,
+}
+
+impl DataCollation {
+    pub fn try_create(name: &str) -> Result<DataCollation> {
+        match name.to_lowercase().as_str() {
+            "binary" | "utf8_bin" | "utf8mb4_bin" => Ok(DataCollation::Binary),
+            "utf8_general_ci" | "utf8mb4_general_ci" => Ok(DataCollation::Utf8GeneralCi),
+            "utf8_unicode_ci" | "utf8mb4_unicode_ci" => Ok(DataCollation::Utf8UnicodeCi),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unknown collation: {}",
+                name
+            ))),
+        }
+    }
+
+    /// The collation of the field, the fields without a collation are binary.
+    pub fn from_field(field: &DataField) -> Result<DataCollation> {
+        match field.collation() {
+            Some(name) => Self::try_create(name),
+            None => Ok(DataCollation::Binary),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self {
+            DataCollation::Binary => "binary",
+            DataCollation::Utf8GeneralCi => "utf8_general_ci",
+            DataCollation::Utf8UnicodeCi => "utf8_unicode_ci",
+        }
+    }
+
+    pub fn is_binary(&self) -> bool {
+        matches!(self, DataCollation::Binary)
+    }
+
+    pub fn key<'a>(&self, value: &'a str) -> Cow<'a, str> {
+        match self {
+            DataCollation::Binary => Cow::Borrowed(value),
+            DataCollation::Utf8GeneralCi => Cow::Owned(value.to_lowercase()),
+            DataCollation::Utf8UnicodeCi => Cow::Owned(
+                value
+                    .nfd()
+                    .filter(|c| !is_combining_mark(*c))
+                    .collect::<String>()
+                    .to_lowercase(),
+            ),
+        }
+    }
+
+    /// The collation keys of the strings of the column, the columns of the other types are
+    /// returned as they are.
+    pub fn keys(&self, column: &DataColumn) -> Result<DataColumn> {
+        if self.is_binary() || column.data_type() != DataType::Utf8 {
+            return Ok(column.clone());
+        }
+
+        let collation = *self;
+        match column {
+            DataColumn::Constant(DataValue::Utf8(Some(value)), size) => Ok(DataColumn::Constant(
+                DataValue::Utf8(Some(collation.key(value).into_owned())),
+                *size,
+            )),
+            DataColumn::Constant(_, _) => Ok(column.clone()),
+            DataColumn::Array(array) => {
+                let keys = array.utf8()?.apply(|value| collation.key(value));
+                Ok(keys.into_series().into())
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for DataCollation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
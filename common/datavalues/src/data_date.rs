@@ -0,0 +1,294 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+//! The values of `Date32` are the days since the epoch, and the values of `Date64`, which is the
+//! type of `DateTime`, are the milliseconds since the epoch, both in UTC.
+
+use std::convert::TryFrom;
+
+use chrono::Datelike;
+use chrono::Duration;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use common_arrow::arrow::datatypes::IntervalUnit;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::prelude::*;
+use crate::DataValueArithmeticOperator;
+
+pub const MILLIS_PER_DAY: i64 = 86_400_000;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATE_TIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S%.f", "%Y-%m-%dT%H:%M:%S%.f"];
+
+fn epoch() -> NaiveDate {
+    NaiveDate::from_ymd(1970, 1, 1)
+}
+
+pub fn date_from_days(days: i32) -> Option<NaiveDate> {
+    epoch().checked_add_signed(Duration::days(days as i64))
+}
+
+pub fn date_to_days(date: &NaiveDate) -> Option<i32> {
+    i32::try_from(date.signed_duration_since(epoch()).num_days()).ok()
+}
+
+pub fn date_time_from_millis(millis: i64) -> Option<NaiveDateTime> {
+    NaiveDateTime::from_timestamp_opt(
+        millis.div_euclid(1000),
+        (millis.rem_euclid(1000) * 1_000_000) as u32,
+    )
+}
+
+pub fn date_time_to_millis(time: &NaiveDateTime) -> i64 {
+    time.timestamp_millis()
+}
+
+/// Formats the days like `2021-01-01`, the days out of the range of chrono are the number.
+pub fn date_to_string(days: i32) -> String {
+    match date_from_days(days) {
+        Some(date) => date.format(DATE_FORMAT).to_string(),
+        None => days.to_string(),
+    }
+}
+
+/// Formats the milliseconds like `2021-01-01 00:00:00.000`.
+pub fn date_time_to_string(millis: i64) -> String {
+    match date_time_from_millis(millis) {
+        Some(time) => time.format("%Y-%m-%d %H:%M:%S%.3f").to_string(),
+        None => millis.to_string(),
+    }
+}
+
+/// Parses the date like `2021-01-01`, the time of a date time string is truncated.
+pub fn date_from_str(s: &str) -> Option<i32> {
+    let s = s.trim();
+    match NaiveDate::parse_from_str(s, DATE_FORMAT) {
+        Ok(date) => date_to_days(&date),
+        Err(_) => date_time_from_str(s).map(|millis| millis.div_euclid(MILLIS_PER_DAY) as i32),
+    }
+}
+
+/// Parses the date time like `2021-01-01 12:00:00[.fff]`, a date is the time at its midnight.
+pub fn date_time_from_str(s: &str) -> Option<i64> {
+    let s = s.trim();
+    DATE_TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+        .map(|time| date_time_to_millis(&time))
+        .or_else(|| {
+            let date = NaiveDate::parse_from_str(s, DATE_FORMAT).ok()?;
+            Some(date_to_days(&date)? as i64 * MILLIS_PER_DAY)
+        })
+}
+
+/// Adds the months to the date, the day is the last day of the month if it is out of the month,
+/// e.g. 2021-01-31 plus one month is 2021-02-28.
+pub fn date_add_months(date: &NaiveDate, months: i64) -> Option<NaiveDate> {
+    let months = (date.year() as i64 * 12 + date.month0() as i64).checked_add(months)?;
+    let year = i32::try_from(months.div_euclid(12)).ok()?;
+    let month = months.rem_euclid(12) as u32 + 1;
+
+    let next_month = match month {
+        12 => NaiveDate::from_ymd_opt(year.checked_add(1)?, 1, 1)?,
+        _ => NaiveDate::from_ymd_opt(year, month + 1, 1)?,
+    };
+    let last_day = next_month.pred_opt()?.day();
+    NaiveDate::from_ymd_opt(year, month, date.day().min(last_day))
+}
+
+/// The days and the milliseconds of `Interval(DayTime)`, which are the high and the low 32 bits.
+pub fn interval_day_time(value: i64) -> (i64, i64) {
+    ((value >> 32) as i32 as i64, value as i32 as i64)
+}
+
+/// Converts the Date32, Date64, the strings and the integers of the days to Date32, the strings
+/// which are not dates are NULL.
+pub fn cast_to_date(series: &Series) -> Result<Series> {
+    match series.data_type() {
+        DataType::Date32 => Ok(series.clone()),
+        DataType::Date64 => {
+            let days = series
+                .cast_with_type(&DataType::Int64)?
+                .i64()?
+                .downcast_iter()
+                .map(|v| v.map(|millis| millis.div_euclid(MILLIS_PER_DAY) as i32))
+                .collect::<Vec<_>>();
+            DFInt32Array::new_from_opt_slice(&days)
+                .into_series()
+                .cast_with_type(&DataType::Date32)
+        }
+        DataType::Utf8 => {
+            let days = series
+                .utf8()?
+                .downcast_iter()
+                .map(|v| v.and_then(date_from_str))
+                .collect::<Vec<_>>();
+            DFInt32Array::new_from_opt_slice(&days)
+                .into_series()
+                .cast_with_type(&DataType::Date32)
+        }
+        data_type if is_integer(&data_type) => series
+            .cast_with_type(&DataType::Int32)?
+            .cast_with_type(&DataType::Date32),
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "Unsupported cast from {:?} to Date32",
+            other
+        ))),
+    }
+}
+
+/// Converts the Date32, Date64, the strings and the integers of the seconds to Date64, the
+/// strings which are not date times are NULL.
+pub fn cast_to_date_time(series: &Series) -> Result<Series> {
+    let millis = match series.data_type() {
+        DataType::Date64 => return Ok(series.clone()),
+        DataType::Date32 => series
+            .cast_with_type(&DataType::Int32)?
+            .i32()?
+            .downcast_iter()
+            .map(|v| v.map(|days| days as i64 * MILLIS_PER_DAY))
+            .collect::<Vec<_>>(),
+        DataType::Utf8 => series
+            .utf8()?
+            .downcast_iter()
+            .map(|v| v.and_then(date_time_from_str))
+            .collect::<Vec<_>>(),
+        data_type if is_integer(&data_type) => series
+            .cast_with_type(&DataType::Int64)?
+            .i64()?
+            .downcast_iter()
+            .map(|v| v.and_then(|seconds| seconds.checked_mul(1000)))
+            .collect::<Vec<_>>(),
+        other => {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Unsupported cast from {:?} to Date64",
+                other
+            )))
+        }
+    };
+    DFInt64Array::new_from_opt_slice(&millis)
+        .into_series()
+        .cast_with_type(&DataType::Date64)
+}
+
+/// The values of the dates or the intervals as i64, the days of Date32, the milliseconds of
+/// Date64, the months of `Interval(YearMonth)` and the raw values of `Interval(DayTime)`.
+fn physical_values(series: &Series) -> Result<Vec<Option<i64>>> {
+    match series.data_type() {
+        DataType::Date32 => Ok(series
+            .cast_with_type(&DataType::Int32)?
+            .i32()?
+            .downcast_iter()
+            .map(|v| v.map(|days| days as i64))
+            .collect()),
+        DataType::Date64 => Ok(series
+            .cast_with_type(&DataType::Int64)?
+            .i64()?
+            .downcast_iter()
+            .collect()),
+        _ => series
+            .to_values()?
+            .iter()
+            .map(|value| match value {
+                DataValue::IntervalYearMonth(v) => Ok(v.map(|months| months as i64)),
+                DataValue::IntervalDayTime(v) => Ok(*v),
+                other => Err(ErrorCode::BadDataValueType(format!(
+                    "Unexpected type:{:?} of the dates or the intervals",
+                    other.data_type()
+                ))),
+            })
+            .collect(),
+    }
+}
+
+/// The sums and the differences of the dates and the intervals, or the differences of the dates,
+/// which are the days of Date32 and the milliseconds of Date64. The length 1 series are
+/// broadcast as the constants.
+pub fn date_arithmetic(
+    op: &DataValueArithmeticOperator,
+    lhs: &Series,
+    rhs: &Series,
+) -> Result<Series> {
+    let result_type = numerical_arithmetic_coercion(op, &lhs.data_type(), &rhs.data_type())?;
+    let (lhs, rhs) = match is_interval(&lhs.data_type()) {
+        true => (rhs, lhs),
+        false => (lhs, rhs),
+    };
+    let (lhs_type, rhs_type) = (lhs.data_type(), rhs.data_type());
+    let negative = matches!(op, DataValueArithmeticOperator::Minus);
+
+    let lhs_values = physical_values(lhs)?;
+    let rhs_values = physical_values(rhs)?;
+    let len = lhs_values.len().max(rhs_values.len());
+    let value = |values: &[Option<i64>], index: usize| match values.len() {
+        1 => values[0],
+        _ => values[index],
+    };
+
+    let mut results = Vec::with_capacity(len);
+    for index in 0..len {
+        let (date, delta) = match (value(&lhs_values, index), value(&rhs_values, index)) {
+            (Some(date), Some(delta)) => (date, delta),
+            _ => {
+                results.push(None);
+                continue;
+            }
+        };
+
+        let result = match (&lhs_type, &rhs_type) {
+            (_, DataType::Interval(IntervalUnit::YearMonth)) => {
+                let months = if negative { -delta } else { delta };
+                match lhs_type {
+                    DataType::Date32 => date_from_days(date as i32)
+                        .and_then(|date| date_add_months(&date, months))
+                        .and_then(|date| date_to_days(&date))
+                        .map(|days| days as i64),
+                    _ => date_time_from_millis(date).and_then(|time| {
+                        let date = date_add_months(&time.date(), months)?;
+                        Some(date_time_to_millis(&date.and_time(time.time())))
+                    }),
+                }
+            }
+            (_, DataType::Interval(IntervalUnit::DayTime)) => {
+                let (days, millis) = interval_day_time(delta);
+                let millis = days * MILLIS_PER_DAY + millis;
+                let delta = match lhs_type {
+                    DataType::Date32 => millis.div_euclid(MILLIS_PER_DAY),
+                    _ => millis,
+                };
+                match negative {
+                    true => date.checked_sub(delta),
+                    false => date.checked_add(delta),
+                }
+            }
+            _ => date.checked_sub(delta),
+        };
+
+        match result {
+            Some(v) if lhs_type != DataType::Date32 || i32::try_from(v).is_ok() => {
+                results.push(Some(v))
+            }
+            _ => {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "{:?} {} {:?} overflow: {:?} and {:?}",
+                    lhs_type,
+                    op,
+                    rhs_type,
+                    value(&lhs_values, index),
+                    value(&rhs_values, index)
+                )))
+            }
+        }
+    }
+
+    let results = DFInt64Array::new_from_opt_slice(&results).into_series();
+    match result_type {
+        DataType::Date32 | DataType::Int32 => results
+            .cast_with_type(&DataType::Int32)?
+            .cast_with_type(&result_type),
+        _ => results.cast_with_type(&result_type),
+    }
+}
@@ -0,0 +1,181 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use chrono::NaiveDate;
+use common_arrow::arrow::datatypes::IntervalUnit;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::cast_to_date;
+use crate::cast_to_date_time;
+use crate::date_add_months;
+use crate::date_arithmetic;
+use crate::date_from_str;
+use crate::date_time_from_str;
+use crate::date_time_to_string;
+use crate::date_to_string;
+use crate::prelude::*;
+use crate::DataValueArithmeticOperator;
+
+fn to_strings(series: &Series) -> Result<Vec<String>> {
+    Ok(series.to_values()?.iter().map(|v| v.to_string()).collect())
+}
+
+#[test]
+fn test_date_from_str() -> Result<()> {
+    assert_eq!(date_from_str("2021-01-01"), Some(18628));
+    assert_eq!(date_from_str(" 1969-12-31 "), Some(-1));
+    assert_eq!(date_from_str("2021-01-01 23:59:59"), Some(18628));
+    assert_eq!(date_from_str("2021-02-30"), None);
+    assert_eq!(
+        date_time_from_str("2021-01-01 12:30:15"),
+        Some(1_609_504_215_000)
+    );
+    assert_eq!(
+        date_time_from_str("2021-01-01T12:30:15.5"),
+        Some(1_609_504_215_500)
+    );
+    assert_eq!(date_time_from_str("2021-01-01"), Some(1_609_459_200_000));
+    assert_eq!(date_time_from_str("12:30:15"), None);
+
+    assert_eq!(date_to_string(18628), "2021-01-01");
+    assert_eq!(
+        date_time_to_string(1_609_504_215_500),
+        "2021-01-01 12:30:15.500"
+    );
+    assert_eq!(date_time_to_string(-1), "1969-12-31 23:59:59.999");
+    Ok(())
+}
+
+#[test]
+fn test_date_add_months() -> Result<()> {
+    let date = |y, m, d| NaiveDate::from_ymd(y, m, d);
+    assert_eq!(
+        date_add_months(&date(2021, 1, 31), 1),
+        Some(date(2021, 2, 28))
+    );
+    assert_eq!(
+        date_add_months(&date(2020, 1, 31), 1),
+        Some(date(2020, 2, 29))
+    );
+    assert_eq!(
+        date_add_months(&date(2021, 1, 15), 12),
+        Some(date(2022, 1, 15))
+    );
+    assert_eq!(
+        date_add_months(&date(2021, 1, 15), -1),
+        Some(date(2020, 12, 15))
+    );
+    assert_eq!(
+        date_add_months(&date(2021, 3, 31), -25),
+        Some(date(2019, 2, 28))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_cast_to_date() -> Result<()> {
+    let strings = Series::new(vec!["2021-01-01", "2021-01-01 12:30:15", "x"]);
+    let dates = cast_to_date(&strings)?;
+    assert_eq!(dates.data_type(), DataType::Date32);
+    assert_eq!(to_strings(&dates)?, vec![
+        "2021-01-01",
+        "2021-01-01",
+        "NULL"
+    ]);
+
+    let times = cast_to_date_time(&strings)?;
+    assert_eq!(times.data_type(), DataType::Date64);
+    assert_eq!(to_strings(&times)?, vec![
+        "2021-01-01 00:00:00.000",
+        "2021-01-01 12:30:15.000",
+        "NULL"
+    ]);
+
+    assert_eq!(to_strings(&cast_to_date(&times)?)?, vec![
+        "2021-01-01",
+        "2021-01-01",
+        "NULL"
+    ]);
+    assert_eq!(to_strings(&cast_to_date_time(&dates)?)?, vec![
+        "2021-01-01 00:00:00.000",
+        "2021-01-01 00:00:00.000",
+        "NULL"
+    ]);
+
+    // The days and the seconds since the epoch.
+    assert_eq!(
+        to_strings(&cast_to_date(&Series::new(vec![18628u16]))?)?,
+        vec!["2021-01-01"]
+    );
+    assert_eq!(
+        to_strings(&cast_to_date_time(&Series::new(vec![1_609_504_215i64]))?)?,
+        vec!["2021-01-01 12:30:15.000"]
+    );
+
+    assert_eq!(
+        cast_to_date(&Series::new(vec![1.5f64]))
+            .unwrap_err()
+            .message(),
+        "Unsupported cast from Float64 to Date32"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_date_arithmetic() -> Result<()> {
+    let dates = cast_to_date(&Series::new(vec!["2021-01-31", "2020-02-29"]))?;
+    let times = cast_to_date_time(&Series::new(vec!["2021-01-31 12:00:00"]))?;
+    let months = DataValue::IntervalYearMonth(Some(13)).to_series_with_size(1)?;
+    // 1 day and 12 hours.
+    let day_time =
+        DataValue::IntervalDayTime(Some((1 << 32) | 43_200_000)).to_series_with_size(1)?;
+
+    let plus = DataValueArithmeticOperator::Plus;
+    let minus = DataValueArithmeticOperator::Minus;
+
+    let result = date_arithmetic(&plus, &dates, &months)?;
+    assert_eq!(result.data_type(), DataType::Date32);
+    assert_eq!(to_strings(&result)?, vec!["2022-02-28", "2021-03-29"]);
+
+    // The interval may be the first one of the sum, the time of it is truncated for the dates.
+    let result = date_arithmetic(&plus, &day_time, &dates)?;
+    assert_eq!(to_strings(&result)?, vec!["2021-02-01", "2020-03-01"]);
+
+    let result = date_arithmetic(&minus, &times, &day_time)?;
+    assert_eq!(result.data_type(), DataType::Date64);
+    assert_eq!(to_strings(&result)?, vec!["2021-01-30 00:00:00.000"]);
+
+    let result = date_arithmetic(&minus, &times, &months)?;
+    assert_eq!(to_strings(&result)?, vec!["2019-12-31 12:00:00.000"]);
+
+    // The differences of the dates are the days.
+    let result = (&dates - &cast_to_date(&Series::new(vec!["2021-01-01"]))?)?;
+    assert_eq!(result.data_type(), DataType::Int32);
+    assert_eq!(to_strings(&result)?, vec!["30", "-307"]);
+
+    assert_eq!(
+        (&months - &dates).unwrap_err().message(),
+        "DataValue Error: Unsupported (Interval(YearMonth)) minus (Date32)"
+    );
+    assert_eq!(
+        (&dates * &months).unwrap_err().message(),
+        "DataValue Error: Unsupported (Date32) multiply (Interval(YearMonth))"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_date_comparison() -> Result<()> {
+    let dates = cast_to_date(&Series::new(vec!["2021-01-01", "2021-01-02"]))?;
+    let times = cast_to_date_time(&Series::new(vec!["2021-01-01 12:00:00"]))?;
+
+    let result = dates.gt(&Series::new(vec!["2021-01-01"]))?;
+    assert_eq!(Vec::from(&result), vec![Some(false), Some(true)]);
+    let result = dates.lt(&times)?;
+    assert_eq!(Vec::from(&result), vec![Some(true), Some(false)]);
+    let result = dates.eq(&dates)?;
+    assert_eq!(Vec::from(&result), vec![Some(true), Some(true)]);
+    Ok(())
+}
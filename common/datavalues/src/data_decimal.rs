@@ -0,0 +1,169 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+//! The values of `Decimal(precision, scale)` are the i128 scaled by 10^scale, e.g. 12.34 of
+//! `Decimal(10, 2)` is 1234.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::arrays::DecimalArrayBuilder;
+use crate::prelude::*;
+
+/// The max number of the digits in the i128.
+pub const DECIMAL_MAX_PRECISION: usize = 38;
+/// The precision and the scale of `DECIMAL` without the arguments, as in MySQL.
+pub const DECIMAL_DEFAULT_PRECISION: usize = 10;
+pub const DECIMAL_DEFAULT_SCALE: usize = 0;
+
+pub fn check_decimal_type(precision: usize, scale: usize) -> Result<()> {
+    if precision == 0 || precision > DECIMAL_MAX_PRECISION || scale > precision {
+        return Err(ErrorCode::IllegalDataType(format!(
+            "Invalid type Decimal({}, {}), the precision must be in [1, {}] and the scale must not be greater than the precision",
+            precision, scale, DECIMAL_MAX_PRECISION
+        )));
+    }
+    Ok(())
+}
+
+/// 10^exp, None if it overflows the i128.
+#[inline]
+pub fn decimal_scale_factor(exp: usize) -> Option<i128> {
+    10_i128.checked_pow(exp as u32)
+}
+
+/// Whether the value has no more digits than the precision.
+#[inline]
+pub fn decimal_fits(value: i128, precision: usize) -> bool {
+    match decimal_scale_factor(precision) {
+        Some(max) => value > -max && value < max,
+        None => true,
+    }
+}
+
+/// Changes the scale of the value, the dropped digits are truncated. None if it overflows.
+pub fn decimal_rescale(value: i128, from_scale: usize, to_scale: usize) -> Option<i128> {
+    if to_scale >= from_scale {
+        value.checked_mul(decimal_scale_factor(to_scale - from_scale)?)
+    } else {
+        Some(value / decimal_scale_factor(from_scale - to_scale)?)
+    }
+}
+
+pub fn decimal_to_string(value: i128, scale: usize) -> String {
+    if scale == 0 {
+        return value.to_string();
+    }
+
+    let digits = format!("{:0>width$}", value.unsigned_abs(), width = scale + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - scale);
+    let sign = if value < 0 { "-" } else { "" };
+    format!("{}{}.{}", sign, integer, fraction)
+}
+
+/// Parses the decimal like `-12.345`, the digits beyond the scale are truncated. None if the
+/// string is not a decimal or it does not fit the precision.
+pub fn decimal_from_str(s: &str, precision: usize, scale: usize) -> Option<i128> {
+    let s = s.trim();
+    let (negative, s) = match s.as_bytes().first() {
+        Some(b'-') => (true, &s[1..]),
+        Some(b'+') => (false, &s[1..]),
+        _ => (false, s),
+    };
+    let (integer, fraction) = match s.find('.') {
+        Some(pos) => (&s[..pos], &s[pos + 1..]),
+        None => (s, ""),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return None;
+    }
+
+    let fraction = fraction.bytes().chain(std::iter::repeat(b'0')).take(scale);
+    let mut value: i128 = 0;
+    for c in integer.bytes().chain(fraction) {
+        if !c.is_ascii_digit() {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_add((c - b'0') as i128)?;
+    }
+
+    let value = if negative { -value } else { value };
+    if decimal_fits(value, precision) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+/// Converts the float to the decimal, rounded half away from zero at the scale. None if it is
+/// not finite or does not fit the precision.
+pub fn decimal_from_f64(value: f64, precision: usize, scale: usize) -> Option<i128> {
+    let scaled = (value * 10_f64.powi(scale as i32)).round();
+    if !scaled.is_finite() || scaled.abs() >= 1e38 {
+        return None;
+    }
+
+    let value = scaled as i128;
+    if decimal_fits(value, precision) {
+        Some(value)
+    } else {
+        None
+    }
+}
+
+#[inline]
+pub fn decimal_to_f64(value: i128, scale: usize) -> f64 {
+    value as f64 / 10_f64.powi(scale as i32)
+}
+
+/// Casts the decimals, the integers, the floats and the strings to `Decimal(precision, scale)`,
+/// the values which cannot be converted or do not fit the precision are NULL.
+pub fn cast_to_decimal(series: &Series, precision: usize, scale: usize) -> Result<Series> {
+    check_decimal_type(precision, scale)?;
+
+    let mut builder = DecimalArrayBuilder::new(series.len(), precision, scale);
+    let fits = |value: Option<i128>| value.filter(|v| decimal_fits(*v, precision));
+    match series.data_type() {
+        DataType::Decimal(_, from_scale) => {
+            for value in series.decimal()?.downcast_iter() {
+                builder.append_option(fits(
+                    value.and_then(|v| decimal_rescale(v, from_scale, scale)),
+                ));
+            }
+        }
+        DataType::Utf8 => {
+            for value in series.utf8()?.downcast_iter() {
+                builder.append_option(value.and_then(|v| decimal_from_str(v, precision, scale)));
+            }
+        }
+        DataType::UInt64 => {
+            for value in series.u64()?.downcast_iter() {
+                builder.append_option(fits(
+                    value.and_then(|v| decimal_rescale(v as i128, 0, scale)),
+                ));
+            }
+        }
+        DataType::Float32 | DataType::Float64 => {
+            let series = series.cast_with_type(&DataType::Float64)?;
+            for value in series.f64()?.downcast_iter() {
+                builder.append_option(value.and_then(|v| decimal_from_f64(v, precision, scale)));
+            }
+        }
+        data_type if is_integer(&data_type) || data_type == DataType::Boolean => {
+            let series = series.cast_with_type(&DataType::Int64)?;
+            for value in series.i64()?.downcast_iter() {
+                builder.append_option(fits(
+                    value.and_then(|v| decimal_rescale(v as i128, 0, scale)),
+                ));
+            }
+        }
+        other => {
+            return Err(ErrorCode::BadDataValueType(format!(
+                "Unsupported cast from {:?} to Decimal({}, {})",
+                other, precision, scale
+            )))
+        }
+    }
+    Ok(builder.finish().into_series())
+}
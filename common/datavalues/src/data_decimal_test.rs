@@ -0,0 +1,185 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::cast_to_decimal;
+use crate::decimal_from_f64;
+use crate::decimal_from_str;
+use crate::decimal_rescale;
+use crate::decimal_to_string;
+use crate::prelude::*;
+
+#[test]
+fn test_decimal_from_str() -> Result<()> {
+    assert_eq!(decimal_from_str("12.345", 10, 2), Some(1234));
+    assert_eq!(decimal_from_str("-0.5", 10, 2), Some(-50));
+    assert_eq!(decimal_from_str(" 7 ", 10, 2), Some(700));
+    assert_eq!(decimal_from_str(".25", 10, 2), Some(25));
+    assert_eq!(decimal_from_str("123.4", 4, 2), None);
+    assert_eq!(decimal_from_str("1.2.3", 10, 2), None);
+    assert_eq!(decimal_from_str("abc", 10, 2), None);
+    assert_eq!(decimal_from_str("-", 10, 2), None);
+    Ok(())
+}
+
+#[test]
+fn test_decimal_to_string() -> Result<()> {
+    assert_eq!(decimal_to_string(1234, 2), "12.34");
+    assert_eq!(decimal_to_string(-5, 2), "-0.05");
+    assert_eq!(decimal_to_string(0, 3), "0.000");
+    assert_eq!(decimal_to_string(42, 0), "42");
+    assert_eq!(
+        decimal_to_string(i128::MIN, 38),
+        "-1.70141183460469231731687303715884105728"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decimal_rescale() -> Result<()> {
+    assert_eq!(decimal_rescale(1234, 2, 4), Some(123400));
+    assert_eq!(decimal_rescale(1239, 2, 1), Some(123));
+    assert_eq!(decimal_rescale(-1239, 2, 0), Some(-12));
+    assert_eq!(decimal_rescale(i128::MAX, 0, 1), None);
+    assert_eq!(decimal_from_f64(1.005, 10, 1), Some(10));
+    assert_eq!(decimal_from_f64(-2.25, 10, 1), Some(-23));
+    assert_eq!(decimal_from_f64(f64::NAN, 10, 1), None);
+    Ok(())
+}
+
+#[test]
+fn test_cast_to_decimal() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        series: Series,
+        precision: usize,
+        scale: usize,
+        expect: Vec<&'static str>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "strings",
+            series: Series::new(vec!["1.5", "-0.25", "x", "99999"]),
+            precision: 5,
+            scale: 2,
+            expect: vec!["1.50", "-0.25", "NULL", "NULL"],
+        },
+        Test {
+            name: "integers",
+            series: Series::new(vec![1_i32, -2, 300]),
+            precision: 4,
+            scale: 1,
+            expect: vec!["1.0", "-2.0", "300.0"],
+        },
+        Test {
+            name: "unsigned integers",
+            series: Series::new(vec![1_u64, 100000]),
+            precision: 5,
+            scale: 0,
+            expect: vec!["1", "NULL"],
+        },
+        Test {
+            name: "floats",
+            series: Series::new(vec![1.25_f64, -0.125]),
+            precision: 10,
+            scale: 2,
+            expect: vec!["1.25", "-0.13"],
+        },
+        Test {
+            name: "decimals",
+            series: cast_to_decimal(&Series::new(vec!["1.239", "-4.5"]), 10, 3)?,
+            precision: 10,
+            scale: 1,
+            expect: vec!["1.2", "-4.5"],
+        },
+    ];
+
+    for t in tests {
+        let result = cast_to_decimal(&t.series, t.precision, t.scale)?;
+        assert_eq!(
+            result.data_type(),
+            DataType::Decimal(t.precision, t.scale),
+            "{}",
+            t.name
+        );
+
+        let values = result
+            .to_values()?
+            .iter()
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(values, t.expect, "{}", t.name);
+    }
+
+    let result = cast_to_decimal(&Series::new(vec![1_i32]), 39, 2);
+    assert_eq!(
+        result.unwrap_err().message(),
+        "Invalid type Decimal(39, 2), the precision must be in [1, 38] and the scale must not be greater than the precision"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_decimal_arithmetic() -> Result<()> {
+    let lhs = cast_to_decimal(&Series::new(vec!["1.25", "-3.5"]), 10, 2)?;
+    let rhs = cast_to_decimal(&Series::new(vec!["2.5", "0.5"]), 10, 1)?;
+
+    let to_strings = |series: Series| -> Result<(DataType, Vec<String>)> {
+        let values = series.to_values()?.iter().map(|v| v.to_string()).collect();
+        Ok((series.data_type(), values))
+    };
+
+    assert_eq!(
+        to_strings((&lhs + &rhs)?)?,
+        (DataType::Decimal(38, 2), vec![
+            "3.75".to_string(),
+            "-3.00".to_string()
+        ])
+    );
+    assert_eq!(
+        to_strings((&lhs - &rhs)?)?,
+        (DataType::Decimal(38, 2), vec![
+            "-1.25".to_string(),
+            "-4.00".to_string()
+        ])
+    );
+    assert_eq!(
+        to_strings((&lhs * &rhs)?)?,
+        (DataType::Decimal(38, 3), vec![
+            "3.125".to_string(),
+            "-1.750".to_string()
+        ])
+    );
+    assert_eq!(
+        to_strings((&lhs / &rhs)?)?,
+        (DataType::Decimal(38, 2), vec![
+            "0.50".to_string(),
+            "-7.00".to_string()
+        ])
+    );
+    assert_eq!(
+        to_strings((&lhs * &Series::new(vec![2_i32, 3]))?)?,
+        (DataType::Decimal(38, 2), vec![
+            "2.50".to_string(),
+            "-10.50".to_string()
+        ])
+    );
+    assert_eq!(
+        (&lhs + &Series::new(vec![1.0_f64, 2.0]))?.data_type(),
+        DataType::Float64
+    );
+
+    let zero = cast_to_decimal(&Series::new(vec![0_i32, 0]), 10, 0)?;
+    assert!((&lhs / &zero).is_err());
+    assert!((&lhs % &rhs).is_err());
+
+    let gt = lhs.gt(&rhs)?;
+    assert_eq!(Vec::from(&gt), vec![Some(false), Some(false)]);
+    let lt = lhs.lt(&Series::new(vec![2_i32, 0]))?;
+    assert_eq!(Vec::from(&lt), vec![Some(true), Some(true)]);
+    Ok(())
+}
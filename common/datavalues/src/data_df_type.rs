@@ -56,6 +56,7 @@ pub struct ListType;
 
 pub struct StructType;
 pub struct BinaryType;
+pub struct DecimalType;
 
 pub type DFNullArray = DataArray<NullType>;
 pub type DFInt8Array = DataArray<Int8Type>;
@@ -76,6 +77,7 @@ pub type DFUtf8Array = DataArray<Utf8Type>;
 pub type DFListArray = DataArray<ListType>;
 pub type DFStructArray = DataArray<StructType>;
 pub type DFBinaryArray = DataArray<BinaryType>;
+pub type DFDecimalArray = DataArray<DecimalType>;
 
 pub type DFDate32Array = DataArray<Date32Type>;
 pub type DFDate64Array = DataArray<Date64Type>;
@@ -163,6 +165,13 @@ impl DFDataType for BinaryType {
     }
 }
 
+impl DFDataType for DecimalType {
+    fn data_type() -> DataType {
+        // the max precision as we cannot know the precision and the scale without self.
+        DataType::Decimal(38, 0)
+    }
+}
+
 impl DFDataType for StructType {
     fn data_type() -> DataType {
         // null as we cannot no anything without self.
@@ -2,9 +2,18 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
+
 use common_arrow::arrow::datatypes::Field as ArrowField;
 
 use crate::DataType;
+
+/// The keys of arrow field metadata to keep the default expression, the comment and the
+/// collation of a column.
+const DEFAULT_EXPR_KEY: &str = "default_expr";
+const COMMENT_KEY: &str = "comment";
+const COLLATION_KEY: &str = "collation";
+
 #[derive(
     serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq, Hash, Eq, PartialOrd, Ord,
 )]
@@ -12,6 +21,15 @@ pub struct DataField {
     name: String,
     data_type: DataType,
     nullable: bool,
+    /// The SQL text of the DEFAULT expression of a table column.
+    #[serde(default)]
+    default_expr: Option<String>,
+    /// The COMMENT of a table column.
+    #[serde(default)]
+    comment: Option<String>,
+    /// The COLLATE of a string column, see `DataCollation`.
+    #[serde(default)]
+    collation: Option<String>,
 }
 
 impl DataField {
@@ -20,8 +38,32 @@ impl DataField {
             name: name.to_string(),
             data_type,
             nullable,
+            default_expr: None,
+            comment: None,
+            collation: None,
         }
     }
+
+    pub fn with_default_expr(mut self, default_expr: Option<String>) -> Self {
+        self.default_expr = default_expr;
+        self
+    }
+
+    pub fn with_comment(mut self, comment: Option<String>) -> Self {
+        self.comment = comment;
+        self
+    }
+
+    pub fn with_collation(mut self, collation: Option<String>) -> Self {
+        self.collation = collation;
+        self
+    }
+
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
     pub fn name(&self) -> &String {
         &self.name
     }
@@ -34,6 +76,18 @@ impl DataField {
         self.nullable
     }
 
+    pub fn default_expr(&self) -> Option<&String> {
+        self.default_expr.as_ref()
+    }
+
+    pub fn comment(&self) -> Option<&String> {
+        self.comment.as_ref()
+    }
+
+    pub fn collation(&self) -> Option<&String> {
+        self.collation.as_ref()
+    }
+
     /// Check to see if `self` is a superset of `other` field. Superset is defined as:
     ///
     /// * if nullability doesn't match, self needs to be nullable
@@ -51,13 +105,31 @@ impl DataField {
     }
 
     pub fn to_arrow(&self) -> ArrowField {
-        ArrowField::new(&self.name, self.data_type.to_arrow(), self.nullable)
+        let mut field = ArrowField::new(&self.name, self.data_type.to_arrow(), self.nullable);
+        let mut metadata = BTreeMap::new();
+        if let Some(expr) = &self.default_expr {
+            metadata.insert(DEFAULT_EXPR_KEY.to_string(), expr.clone());
+        }
+        if let Some(comment) = &self.comment {
+            metadata.insert(COMMENT_KEY.to_string(), comment.clone());
+        }
+        if let Some(collation) = &self.collation {
+            metadata.insert(COLLATION_KEY.to_string(), collation.clone());
+        }
+        if !metadata.is_empty() {
+            field.set_metadata(Some(metadata));
+        }
+        field
     }
 }
 
 impl From<&ArrowField> for DataField {
     fn from(f: &ArrowField) -> Self {
+        let metadata = |key: &str| f.metadata().as_ref().and_then(|m| m.get(key)).cloned();
         DataField::new(f.name(), f.data_type().into(), f.is_nullable())
+            .with_default_expr(metadata(DEFAULT_EXPR_KEY))
+            .with_comment(metadata(COMMENT_KEY))
+            .with_collation(metadata(COLLATION_KEY))
     }
 }
 
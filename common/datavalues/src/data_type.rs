@@ -38,6 +38,9 @@ pub enum DataType {
     List(Box<DataField>),
     Struct(Vec<DataField>),
     Binary,
+    /// An exact number of the precision (the number of the digits) and the scale (the number of
+    /// the digits after the point), stored as an i128 scaled by 10^scale.
+    Decimal(usize, usize),
 }
 
 impl DataType {
@@ -67,6 +70,7 @@ impl DataType {
                 ArrowDataType::Struct(arrows_fields)
             }
             Binary => ArrowDataType::Binary,
+            Decimal(precision, scale) => ArrowDataType::Decimal(*precision, *scale),
         }
     }
 }
@@ -122,6 +126,7 @@ impl From<&ArrowDataType> for DataType {
 
             ArrowDataType::Utf8 => DataType::Utf8,
             ArrowDataType::Binary => DataType::Binary,
+            ArrowDataType::Decimal(precision, scale) => DataType::Decimal(*precision, *scale),
 
             // this is safe, because we define the datatype firstly
             _ => unimplemented!(),
@@ -9,6 +9,7 @@ use common_exception::Result;
 
 use crate::DataType;
 use crate::DataValueArithmeticOperator;
+use crate::DECIMAL_MAX_PRECISION;
 
 /// Determine if a DataType is signed numeric or not
 pub fn is_signed_numeric(dt: &DataType) -> bool {
@@ -47,6 +48,27 @@ pub fn is_integer(dt: &DataType) -> bool {
     is_numeric(dt) && !is_floating(dt)
 }
 
+pub fn is_decimal(dt: &DataType) -> bool {
+    matches!(dt, DataType::Decimal(_, _))
+}
+
+pub fn is_date_or_date_time(dt: &DataType) -> bool {
+    matches!(dt, DataType::Date32 | DataType::Date64)
+}
+
+pub fn is_interval(dt: &DataType) -> bool {
+    matches!(dt, DataType::Interval(_))
+}
+
+/// The scale of the decimals and the integers, which are the decimals of the scale 0.
+pub fn decimal_scale(dt: &DataType) -> Option<usize> {
+    match dt {
+        DataType::Decimal(_, scale) => Some(*scale),
+        dt if is_integer(dt) => Some(0),
+        _ => None,
+    }
+}
+
 pub fn numeric_byte_size(dt: &DataType) -> Result<usize> {
     match dt {
         DataType::Int8 | DataType::UInt8 => Ok(1),
@@ -111,6 +133,10 @@ pub fn string_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataT
 /// can be casted to for numerical calculation, while maintaining
 /// maximum precision
 pub fn numerical_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType> {
+    if is_decimal(lhs_type) || is_decimal(rhs_type) {
+        return decimal_coercion(lhs_type, rhs_type);
+    }
+
     let has_float = is_floating(lhs_type) || is_floating(rhs_type);
     let has_integer = is_integer(lhs_type) || is_integer(rhs_type);
     let has_signed = is_signed_numeric(lhs_type) || is_signed_numeric(rhs_type);
@@ -187,12 +213,126 @@ pub fn numerical_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<Da
     )
 }
 
+/// Coercion rule for the decimals with the decimals or the integers: the decimal of the max
+/// precision and the larger scale. The decimals with the floats are Float64.
+fn decimal_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType> {
+    if is_floating(lhs_type) || is_floating(rhs_type) {
+        return Ok(DataType::Float64);
+    }
+
+    match (decimal_scale(lhs_type), decimal_scale(rhs_type)) {
+        (Some(lhs_scale), Some(rhs_scale)) => Ok(DataType::Decimal(
+            DECIMAL_MAX_PRECISION,
+            cmp::max(lhs_scale, rhs_scale),
+        )),
+        _ => Result::Err(ErrorCode::BadDataValueType(format!(
+            "Can't construct type from {} and {}",
+            lhs_type, rhs_type
+        ))),
+    }
+}
+
+/// Coercion rule for the comparison of the dates with the dates or the strings: the date time
+/// if there is any, otherwise the date.
+pub fn date_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Result<DataType> {
+    match (lhs_type, rhs_type) {
+        (DataType::Date64, DataType::Date32 | DataType::Date64 | DataType::Utf8)
+        | (DataType::Date32 | DataType::Utf8, DataType::Date64) => Ok(DataType::Date64),
+        (DataType::Date32, DataType::Date32 | DataType::Utf8)
+        | (DataType::Utf8, DataType::Date32) => Ok(DataType::Date32),
+        _ => Result::Err(ErrorCode::BadDataValueType(format!(
+            "Can't construct type from {} and {}",
+            lhs_type, rhs_type
+        ))),
+    }
+}
+
+/// Coercion rule for the arithmetic of the dates: the date plus or minus the interval is the
+/// date of the same type, and the difference of the dates of the same type is the days of Int32
+/// or the milliseconds of Int64.
+fn date_arithmetic_coercion(
+    op: &DataValueArithmeticOperator,
+    lhs_type: &DataType,
+    rhs_type: &DataType,
+) -> Result<DataType> {
+    match (op, lhs_type, rhs_type) {
+        (DataValueArithmeticOperator::Plus, DataType::Interval(_), date)
+        | (
+            DataValueArithmeticOperator::Plus | DataValueArithmeticOperator::Minus,
+            date,
+            DataType::Interval(_),
+        ) if is_date_or_date_time(date) => Ok(date.clone()),
+        (DataValueArithmeticOperator::Minus, DataType::Date32, DataType::Date32) => {
+            Ok(DataType::Int32)
+        }
+        (DataValueArithmeticOperator::Minus, DataType::Date64, DataType::Date64) => {
+            Ok(DataType::Int64)
+        }
+        _ => Result::Err(ErrorCode::BadDataValueType(format!(
+            "DataValue Error: Unsupported ({:?}) {} ({:?})",
+            lhs_type, op, rhs_type
+        ))),
+    }
+}
+
+/// Coercion rule for the arithmetic of the decimals, the result has the max precision and the
+/// scale of the sum or the difference or the quotient is the larger scale, while the one of the
+/// product is the sum of the scales.
+fn decimal_arithmetic_coercion(
+    op: &DataValueArithmeticOperator,
+    lhs_type: &DataType,
+    rhs_type: &DataType,
+) -> Result<DataType> {
+    if is_floating(lhs_type) || is_floating(rhs_type) {
+        return Ok(DataType::Float64);
+    }
+
+    let (lhs_scale, rhs_scale) = match (decimal_scale(lhs_type), decimal_scale(rhs_type)) {
+        (Some(lhs_scale), Some(rhs_scale)) => (lhs_scale, rhs_scale),
+        _ => {
+            return Result::Err(ErrorCode::BadDataValueType(format!(
+                "DataValue Error: Unsupported ({:?}) {} ({:?})",
+                lhs_type, op, rhs_type
+            )))
+        }
+    };
+
+    let scale = match op {
+        DataValueArithmeticOperator::Plus
+        | DataValueArithmeticOperator::Minus
+        | DataValueArithmeticOperator::Div => cmp::max(lhs_scale, rhs_scale),
+        DataValueArithmeticOperator::Mul => lhs_scale + rhs_scale,
+        DataValueArithmeticOperator::Modulo => {
+            return Result::Err(ErrorCode::BadDataValueType(format!(
+                "DataValue Error: Unsupported ({:?}) {} ({:?})",
+                lhs_type, op, rhs_type
+            )))
+        }
+    };
+
+    if scale > DECIMAL_MAX_PRECISION {
+        return Result::Err(ErrorCode::BadDataValueType(format!(
+            "DataValue Error: The scale of ({:?}) {} ({:?}) exceeds {}",
+            lhs_type, op, rhs_type, DECIMAL_MAX_PRECISION
+        )));
+    }
+    Ok(DataType::Decimal(DECIMAL_MAX_PRECISION, scale))
+}
+
 #[inline]
 pub fn numerical_arithmetic_coercion(
     op: &DataValueArithmeticOperator,
     lhs_type: &DataType,
     rhs_type: &DataType,
 ) -> Result<DataType> {
+    if is_decimal(lhs_type) || is_decimal(rhs_type) {
+        return decimal_arithmetic_coercion(op, lhs_type, rhs_type);
+    }
+
+    if is_date_or_date_time(lhs_type) || is_date_or_date_time(rhs_type) {
+        return date_arithmetic_coercion(op, lhs_type, rhs_type);
+    }
+
     // error on any non-numeric type
     if !is_numeric(lhs_type) || !is_numeric(rhs_type) {
         return Result::Err(ErrorCode::BadDataValueType(format!(
@@ -235,6 +375,10 @@ pub fn numerical_arithmetic_coercion(
 
 #[inline]
 pub fn numerical_signed_coercion(val_type: &DataType) -> Result<DataType> {
+    if is_decimal(val_type) {
+        return Ok(val_type.clone());
+    }
+
     // error on any non-numeric type
     if !is_numeric(val_type) {
         return Result::Err(ErrorCode::BadDataValueType(format!(
@@ -20,6 +20,9 @@ use common_exception::Result;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::date_time_to_string;
+use crate::date_to_string;
+use crate::decimal_to_string;
 use crate::series::IntoSeries;
 use crate::series::Series;
 use crate::DataField;
@@ -43,6 +46,8 @@ pub enum DataValue {
     Float64(Option<f64>),
     Binary(Option<Vec<u8>>),
     Utf8(Option<String>),
+    /// Decimal stored as the i128 scaled by 10^scale, with the precision and the scale.
+    Decimal(Option<i128>, usize, usize),
 
     /// Datetime.
     /// Date stored as a signed 32bit int
@@ -86,6 +91,7 @@ impl DataValue {
                 | DataValue::Float64(None)
                 | DataValue::Binary(None)
                 | DataValue::Utf8(None)
+                | DataValue::Decimal(None, _, _)
                 | DataValue::Date32(None)
                 | DataValue::Date64(None)
                 | DataValue::Null
@@ -111,6 +117,7 @@ impl DataValue {
             DataValue::Float32(_) => DataType::Float32,
             DataValue::Float64(_) => DataType::Float64,
             DataValue::Utf8(_) => DataType::Utf8,
+            DataValue::Decimal(_, precision, scale) => DataType::Decimal(*precision, *scale),
             DataValue::Date32(_) => DataType::Date32,
             DataValue::Date64(_) => DataType::Date64,
             DataValue::TimestampSecond(_) => DataType::Timestamp(TimeUnit::Second, None),
@@ -196,6 +203,16 @@ impl DataValue {
                 Some(v) => Ok(Arc::new(BinaryArray::from(vec![v.deref(); size]))),
                 None => Ok(new_null_array_by_type(&DataType::Binary, size)),
             },
+            DataValue::Decimal(e, precision, scale) => {
+                let mut builder = DecimalBuilder::new(size, *precision, *scale);
+                for _ in 0..size {
+                    match e {
+                        Some(v) => builder.append_value(*v)?,
+                        None => builder.append_null()?,
+                    }
+                }
+                Ok(Arc::new(builder.finish()))
+            }
             DataValue::Date32(e) => match e {
                 Some(value) => Ok(Arc::new(Date32Array::from_value(*value, size))),
                 None => Ok(new_null_array_by_type(&DataType::Date32, size)),
@@ -392,14 +409,15 @@ impl From<&DataType> for DataValue {
             DataType::Float32 => DataValue::Float32(None),
             DataType::Float64 => DataValue::Float64(None),
             DataType::Utf8 => DataValue::Utf8(None),
-            DataType::Date32 => DataValue::UInt32(None),
-            DataType::Date64 => DataValue::UInt64(None),
+            DataType::Date32 => DataValue::Date32(None),
+            DataType::Date64 => DataValue::Date64(None),
             DataType::Timestamp(_, _) => DataValue::UInt64(None),
             DataType::Interval(IntervalUnit::YearMonth) => DataValue::UInt32(None),
             DataType::Interval(IntervalUnit::DayTime) => DataValue::UInt64(None),
             DataType::List(f) => DataValue::List(None, f.data_type().clone()),
             DataType::Struct(_) => DataValue::Struct(vec![]),
             DataType::Binary => DataValue::Binary(None),
+            DataType::Decimal(precision, scale) => DataValue::Decimal(None, *precision, *scale),
         }
     }
 }
@@ -429,6 +447,8 @@ impl fmt::Display for DataValue {
             DataValue::UInt32(v) => format_data_value_with_option!(f, v),
             DataValue::UInt64(v) => format_data_value_with_option!(f, v),
             DataValue::Utf8(v) => format_data_value_with_option!(f, v),
+            DataValue::Decimal(None, _, _) => write!(f, "NULL"),
+            DataValue::Decimal(Some(v), _, scale) => write!(f, "{}", decimal_to_string(*v, *scale)),
             DataValue::Binary(None) => write!(f, "NULL"),
             DataValue::Binary(Some(v)) => {
                 for c in v {
@@ -436,8 +456,9 @@ impl fmt::Display for DataValue {
                 }
                 Ok(())
             }
-            DataValue::Date32(v) => format_data_value_with_option!(f, v),
-            DataValue::Date64(v) => format_data_value_with_option!(f, v),
+            DataValue::Date32(None) | DataValue::Date64(None) => write!(f, "NULL"),
+            DataValue::Date32(Some(v)) => write!(f, "{}", date_to_string(*v)),
+            DataValue::Date64(Some(v)) => write!(f, "{}", date_time_to_string(*v)),
             DataValue::TimestampSecond(v) => format_data_value_with_option!(f, v),
             DataValue::TimestampMillisecond(v) => format_data_value_with_option!(f, v),
             DataValue::TimestampMicrosecond(v) => format_data_value_with_option!(f, v),
@@ -481,6 +502,9 @@ impl fmt::Debug for DataValue {
             DataValue::Utf8(v) => format_data_value_with_option!(f, v),
             DataValue::Binary(None) => write!(f, "{}", self),
             DataValue::Binary(Some(_)) => write!(f, "\"{}\"", self),
+            DataValue::Decimal(_, precision, scale) => {
+                write!(f, "Decimal({}, {}, {})", self, precision, scale)
+            }
             DataValue::Date32(_) => write!(f, "Date32(\"{}\")", self),
             DataValue::Date64(_) => write!(f, "Date64(\"{}\")", self),
             DataValue::IntervalDayTime(_) => {
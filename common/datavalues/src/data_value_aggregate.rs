@@ -7,6 +7,7 @@ use common_exception::Result;
 
 use crate::DataValue;
 use crate::DataValueAggregateOperator;
+use crate::DataValueArithmeticOperator;
 
 impl DataValue {
     #[inline]
@@ -178,6 +179,35 @@ impl DataValue {
                     ))
                 }
             },
+            (
+                DataValue::Decimal(lhs, precision, scale),
+                DataValue::Decimal(rhs, _, rhs_scale),
+            ) if scale == rhs_scale => match op {
+                DataValueAggregateOperator::Min | DataValueAggregateOperator::Max => {
+                    let value = match (lhs, rhs) {
+                        (Some(lhs), Some(rhs)) if op == DataValueAggregateOperator::Min => {
+                            Some(*lhs.min(rhs))
+                        }
+                        (Some(lhs), Some(rhs)) => Some(*lhs.max(rhs)),
+                        (value, None) | (None, value) => *value,
+                    };
+                    Result::Ok(DataValue::Decimal(value, *precision, *scale))
+                }
+                DataValueAggregateOperator::Sum => {
+                    DataValue::arithmetic(DataValueArithmeticOperator::Plus, left.clone(), right.clone())
+                }
+                DataValueAggregateOperator::Count => Result::Ok(DataValue::UInt64(Some(1))),
+                _ => {
+                    Result::Err(ErrorCode::BadDataValueType(
+                        format!(
+                            "DataValue Error: Unsupported data_value_{} for data type: left:{:?}, right:{:?}",
+                            op,
+                            left.data_type(),
+                            right.data_type()
+                        )
+                    ))
+                }
+            },
             (DataValue::Utf8(lhs), DataValue::Utf8(rhs)) => match op {
                 DataValueAggregateOperator::Min => typed_data_value_min_max_string!(lhs, rhs, Utf8, min),
                 DataValueAggregateOperator::Max => typed_data_value_min_max_string!(lhs, rhs, Utf8, max),
@@ -102,6 +102,20 @@ impl DataValue {
             (DataValue::Null, _) => Ok(right),
             (_, DataValue::Null) => Ok(left),
             _ => match (&left, &right) {
+                // Decimal, with the scales of the series arithmetic.
+                (DataValue::Decimal(..), _) | (_, DataValue::Decimal(..)) => {
+                    let lhs = left.to_series_with_size(1)?;
+                    let rhs = right.to_series_with_size(1)?;
+                    let result = match op {
+                        Plus => &lhs + &rhs,
+                        Minus => &lhs - &rhs,
+                        Mul => &lhs * &rhs,
+                        Div => &lhs / &rhs,
+                        Modulo => &lhs % &rhs,
+                    }?;
+                    result.try_get(0)
+                }
+
                 // Float.
                 (DataValue::Float64(lhs), DataValue::Float64(rhs)) => {
                     typed_data_value_operator!(op, lhs, rhs, Float64, f64)
@@ -34,6 +34,10 @@ pub enum DataValueComparisonOperator {
     NotEq,
     Like,
     NotLike,
+    /// The equality which regards two NULLs as equal and NULL as not equal to any value.
+    EqNullSafe,
+    /// The negation of `EqNullSafe`.
+    DistinctFrom,
 }
 
 impl std::fmt::Display for DataValueComparisonOperator {
@@ -47,6 +51,8 @@ impl std::fmt::Display for DataValueComparisonOperator {
             DataValueComparisonOperator::NotEq => "!=",
             DataValueComparisonOperator::Like => "LIKE",
             DataValueComparisonOperator::NotLike => "NOT LIKE",
+            DataValueComparisonOperator::EqNullSafe => "<=>",
+            DataValueComparisonOperator::DistinctFrom => "IS DISTINCT FROM",
         };
         write!(f, "{}", display)
     }
@@ -65,6 +65,37 @@ impl DataValue {
             }
             DataType::Boolean => try_build_array! {values},
             DataType::Utf8 => try_build_array! {Utf8, values},
+            DataType::Binary => {
+                let mut builder = BinaryArrayBuilder::new(values.len());
+                for value in values.iter() {
+                    match value {
+                        DataValue::Binary(Some(v)) => builder.append_value(v),
+                        DataValue::Binary(None) => builder.append_null(),
+                        _ => unreachable!(),
+                    }
+                }
+                Ok(builder.finish().into_series())
+            }
+            DataType::Decimal(precision, scale) => {
+                let mut builder = DecimalArrayBuilder::new(values.len(), *precision, *scale);
+                for value in values.iter() {
+                    match value {
+                        DataValue::Decimal(v, _, _) => builder.append_option(*v),
+                        _ => unreachable!(),
+                    }
+                }
+                Ok(builder.finish().into_series())
+            }
+            DataType::List(field) if values.is_empty() => {
+                DataValue::List(None, field.data_type().clone()).to_series_with_size(0)
+            }
+            DataType::List(_) => {
+                let columns = values
+                    .iter()
+                    .map(|v| Ok(DataColumn::from(v.to_series_with_size(1)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                DataColumnCommon::concat(&columns)?.to_array()
+            }
             other => Result::Err(ErrorCode::BadDataValueType(format!(
                 "Unexpected type:{} for DataValue List",
                 other
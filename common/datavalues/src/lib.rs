@@ -11,10 +11,17 @@ mod macros;
 
 #[cfg(test)]
 mod data_array_filter_test;
+#[cfg(test)]
+mod data_date_test;
+#[cfg(test)]
+mod data_decimal_test;
 
 #[allow(dead_code)]
 mod bit_util;
 mod data_array_filter;
+mod data_collation;
+mod data_date;
+mod data_decimal;
 mod data_df_type;
 mod data_field;
 mod data_group_value;
@@ -40,6 +47,9 @@ pub mod prelude;
 pub mod series;
 
 pub use data_array_filter::*;
+pub use data_collation::DataCollation;
+pub use data_date::*;
+pub use data_decimal::*;
 pub use data_df_type::*;
 pub use data_field::DataField;
 pub use data_group_value::DataGroupValue;
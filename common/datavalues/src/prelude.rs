@@ -17,12 +17,14 @@ pub use crate::arrays::ArrayFullNull;
 pub use crate::arrays::ArrayScatter;
 pub use crate::arrays::ArrayTake;
 pub use crate::arrays::ArrayTakeEvery;
+pub use crate::arrays::BinaryArrayBuilder;
 pub use crate::arrays::BooleanArrayBuilder;
 pub use crate::arrays::DFUInt16ArrayBuilder;
 pub use crate::arrays::DFUInt32ArrayBuilder;
 pub use crate::arrays::DFUInt64ArrayBuilder;
 pub use crate::arrays::DFUInt8ArrayBuilder;
 pub use crate::arrays::DataArray;
+pub use crate::arrays::DecimalArrayBuilder;
 pub use crate::arrays::GetValues;
 pub use crate::arrays::IntoTakeRandom;
 pub use crate::arrays::IsNull;
@@ -46,6 +48,7 @@ pub use crate::DFHasher;
 pub use crate::DFNumericType;
 pub use crate::DFPrimitiveType;
 // common structs
+pub use crate::DataCollation;
 pub use crate::DataField;
 pub use crate::DataGroupValue;
 pub use crate::DataSchema;
@@ -15,23 +15,33 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::arrays::DataArray;
+use crate::arrays::DecimalArrayBuilder;
+use crate::date_arithmetic;
+use crate::decimal_scale;
+use crate::decimal_scale_factor;
 use crate::numerical_arithmetic_coercion;
 use crate::numerical_coercion;
 use crate::numerical_signed_coercion;
 use crate::prelude::*;
 use crate::DFBinaryArray;
 use crate::DFBooleanArray;
+use crate::DFDecimalArray;
 use crate::DFListArray;
 use crate::DFNullArray;
 use crate::DFNumericType;
 use crate::DFStructArray;
 use crate::DFUtf8Array;
 use crate::DataValueArithmeticOperator;
+use crate::DECIMAL_MAX_PRECISION;
 
 impl Add for &Series {
     type Output = Result<Series>;
 
     fn add(self, rhs: Self) -> Self::Output {
+        if is_date_or_date_time(&self.data_type()) || is_date_or_date_time(&rhs.data_type()) {
+            return date_arithmetic(&DataValueArithmeticOperator::Plus, self, rhs);
+        }
+
         let (lhs, rhs) = coerce_lhs_rhs(&DataValueArithmeticOperator::Plus, self, rhs)?;
         lhs.add_to(&rhs)
     }
@@ -41,6 +51,10 @@ impl Sub for &Series {
     type Output = Result<Series>;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        if is_date_or_date_time(&self.data_type()) || is_date_or_date_time(&rhs.data_type()) {
+            return date_arithmetic(&DataValueArithmeticOperator::Minus, self, rhs);
+        }
+
         let (lhs, rhs) = coerce_lhs_rhs(&DataValueArithmeticOperator::Minus, self, rhs)?;
         lhs.subtract(&rhs)
     }
@@ -191,6 +205,89 @@ impl NumOpsDispatch for DFUtf8Array {
         Ok(out.into_series())
     }
 }
+impl DFDecimalArray {
+    /// Applies the operator on the scaled values, the array of one value is broadcast. The
+    /// overflows and the divisions by zero are errors rather than the wrong decimals.
+    fn arithmetic(
+        &self,
+        rhs: &Series,
+        scale: usize,
+        operator: impl Fn(i128, i128) -> Option<i128>,
+        op: &str,
+    ) -> Result<Series> {
+        let rhs = rhs.decimal()?;
+        let len = if self.len() == 1 {
+            rhs.len()
+        } else {
+            self.len()
+        };
+        let value = |array: &DFDecimalArray, index: usize| match array.len() {
+            1 => array.get(0),
+            _ => array.get(index),
+        };
+
+        let mut builder = DecimalArrayBuilder::new(len, DECIMAL_MAX_PRECISION, scale);
+        for index in 0..len {
+            match (value(self, index), value(rhs, index)) {
+                (Some(lhs), Some(rhs)) => match operator(lhs, rhs) {
+                    Some(result) => builder.append_value(result),
+                    None => {
+                        return Err(ErrorCode::BadDataValueType(format!(
+                            "Decimal {} overflow or division by zero: {:?} and {:?}",
+                            op,
+                            self.data_type(),
+                            rhs
+                        )))
+                    }
+                },
+                _ => builder.append_null(),
+            }
+        }
+        Ok(builder.finish().into_series())
+    }
+
+    fn scale(&self) -> usize {
+        self.precision_and_scale().1
+    }
+}
+
+impl NumOpsDispatch for DFDecimalArray {
+    fn subtract(&self, rhs: &Series) -> Result<Series> {
+        self.arithmetic(rhs, self.scale(), |l, r| l.checked_sub(r), "subtraction")
+    }
+
+    fn add_to(&self, rhs: &Series) -> Result<Series> {
+        self.arithmetic(rhs, self.scale(), |l, r| l.checked_add(r), "addition")
+    }
+
+    fn multiply(&self, rhs: &Series) -> Result<Series> {
+        let scale = self.scale() + rhs.decimal()?.scale();
+        self.arithmetic(rhs, scale, |l, r| l.checked_mul(r), "multiplication")
+    }
+
+    // l / r of the scale s is l * 10^(s - lhs_scale + rhs_scale) / r, truncated.
+    fn divide(&self, rhs: &Series) -> Result<Series> {
+        let (lhs_scale, rhs_scale) = (self.scale(), rhs.decimal()?.scale());
+        let scale = std::cmp::max(lhs_scale, rhs_scale);
+        let factor = decimal_scale_factor(scale - lhs_scale + rhs_scale);
+        self.arithmetic(
+            rhs,
+            scale,
+            |l, r| l.checked_mul(factor?)?.checked_div(r),
+            "division",
+        )
+    }
+
+    fn negative(&self) -> Result<Series> {
+        let (precision, scale) = self.precision_and_scale();
+        let mut builder = DecimalArrayBuilder::new(self.len(), precision, scale);
+        for value in self.downcast_iter() {
+            builder.append_option(value.map(|v| -v));
+        }
+        Ok(builder.finish().into_series())
+    }
+}
+
 impl NumOpsDispatch for DFBooleanArray {}
 impl NumOpsDispatch for DFListArray {}
 impl NumOpsDispatch for DFBinaryArray {}
@@ -204,6 +301,23 @@ fn coerce_lhs_rhs(
 ) -> Result<(Series, Series)> {
     let dtype = numerical_arithmetic_coercion(op, &lhs.data_type(), &rhs.data_type())?;
 
+    // The product and the quotient of the decimals depend on the scales of both sides.
+    if let DataType::Decimal(precision, _) = dtype {
+        if matches!(
+            op,
+            DataValueArithmeticOperator::Mul | DataValueArithmeticOperator::Div
+        ) {
+            let to_decimal = |series: &Series| match decimal_scale(&series.data_type()) {
+                Some(scale) if series.data_type() == DataType::Decimal(precision, scale) => {
+                    Ok(series.clone())
+                }
+                Some(scale) => series.cast_with_type(&DataType::Decimal(precision, scale)),
+                None => unreachable!(),
+            };
+            return Ok((to_decimal(lhs)?, to_decimal(rhs)?));
+        }
+    }
+
     let mut left = lhs.clone();
     if lhs.data_type() != dtype {
         left = lhs.cast_with_type(&dtype)?;
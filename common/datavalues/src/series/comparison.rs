@@ -7,6 +7,10 @@ use common_exception::Result;
 
 use super::Series;
 use crate::arrays::ArrayCompare;
+use crate::cast_to_date;
+use crate::cast_to_date_time;
+use crate::date_coercion;
+use crate::is_date_or_date_time;
 use crate::numerical_coercion;
 use crate::DFBooleanArray;
 use crate::DataType;
@@ -28,6 +32,7 @@ macro_rules! impl_compare {
             DataType::Float64 => $self.f64().unwrap().$method($rhs.f64().unwrap()),
             DataType::Date32 => $self.date32().unwrap().$method($rhs.date32().unwrap()),
             DataType::Date64 => $self.date64().unwrap().$method($rhs.date64().unwrap()),
+            DataType::Decimal(_, _) => $self.decimal().unwrap().$method($rhs.decimal().unwrap()),
             _ => unimplemented!(),
         }
     }};
@@ -40,6 +45,15 @@ fn coerce_cmp_lhs_rhs(lhs: &Series, rhs: &Series) -> Result<(Series, Series)> {
         return Ok((lhs.clone(), rhs.clone()));
     }
 
+    // The strings are parsed as the dates.
+    if is_date_or_date_time(&lhs.data_type()) || is_date_or_date_time(&rhs.data_type()) {
+        let cast = match date_coercion(&lhs.data_type(), &rhs.data_type())? {
+            DataType::Date32 => cast_to_date,
+            _ => cast_to_date_time,
+        };
+        return Ok((cast(lhs)?, cast(rhs)?));
+    }
+
     let dtype = numerical_coercion(&lhs.data_type(), &rhs.data_type())?;
 
     let mut left = lhs.clone();
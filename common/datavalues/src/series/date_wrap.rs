@@ -203,6 +203,13 @@ macro_rules! impl_dyn_arrays {
                 try_physical_dispatch!(self, take_iter_unchecked, iter.into())
             }
 
+            unsafe fn take_opt_iter_unchecked(
+                &self,
+                iter: &mut dyn Iterator<Item = Option<usize>>,
+            ) -> Result<Series> {
+                try_physical_dispatch!(self, take_opt_iter_unchecked, iter)
+            }
+
             /// scatter the arrays by indices, the size of indices must be equal to the size of array
             unsafe fn scatter_unchecked(
                 &self,
@@ -194,6 +194,14 @@ pub trait SeriesTrait: Send + Sync + fmt::Debug {
         )))
     }
 
+    /// Unpack to DFArray of data_type decimal
+    fn decimal(&self) -> Result<&DFDecimalArray> {
+        Err(ErrorCode::IllegalDataType(format!(
+            "{:?} != decimal",
+            self.data_type()
+        )))
+    }
+
     /// Take by index from an iterator. This operation clones the data.
     ///
     /// # Safety
@@ -208,6 +216,16 @@ pub trait SeriesTrait: Send + Sync + fmt::Debug {
     /// This doesn't check any bounds or null validity.
     unsafe fn take_iter_unchecked(&self, _iter: &mut dyn Iterator<Item = usize>) -> Result<Series>;
 
+    /// Take by index from an iterator, a None index takes a null. This operation clones the data.
+    ///
+    /// # Safety
+    ///
+    /// This doesn't check any bounds or null validity.
+    unsafe fn take_opt_iter_unchecked(
+        &self,
+        _iter: &mut dyn Iterator<Item = Option<usize>>,
+    ) -> Result<Series>;
+
     /// scatter the arrays by indices, the size of indices must be equal to the size of array
     /// # Safety
     /// Note this doesn't do any bound checking, for performance reason.
@@ -224,7 +242,9 @@ where T: 'static + DFDataType
     fn as_ref(&self) -> &DataArray<T> {
         if T::data_type() == self.data_type() ||
             // needed because we want to get ref of List no matter what the inner type is.
-            (matches!(T::data_type(), DataType::List(_)) && matches!(self.data_type(), DataType::List(_)) )
+            (matches!(T::data_type(), DataType::List(_)) && matches!(self.data_type(), DataType::List(_)) ) ||
+            // and Decimal no matter what the precision and the scale are.
+            (matches!(T::data_type(), DataType::Decimal(_, _)) && matches!(self.data_type(), DataType::Decimal(_, _)) )
         {
             unsafe { &*(self as *const dyn SeriesTrait as *const DataArray<T>) }
         } else {
@@ -316,6 +336,7 @@ impl IntoSeries for ArrayRef {
             DataType::List(_) => DFListArray::new(self).into_series(),
             DataType::Struct(_) => DFStructArray::new(self).into_series(),
             DataType::Binary => DFBinaryArray::new(self).into_series(),
+            DataType::Decimal(_, _) => DFDecimalArray::new(self).into_series(),
 
             _ => unreachable!(),
         }
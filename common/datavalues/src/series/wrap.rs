@@ -353,6 +353,18 @@ macro_rules! impl_dyn_array {
                 }
             }
 
+            fn decimal(&self) -> Result<&DFDecimalArray> {
+                if matches!(self.0.data_type(), DataType::Decimal(_, _)) {
+                    unsafe { Ok(&*(self as *const dyn SeriesTrait as *const DFDecimalArray)) }
+                } else {
+                    Err(ErrorCode::IllegalDataType(format!(
+                        "cannot unpack Series: {:?} of type {:?} into decimal",
+                        self.name(),
+                        self.data_type(),
+                    )))
+                }
+            }
+
             fn take_iter(&self, iter: &mut dyn Iterator<Item = usize>) -> Result<Series> {
                 Ok(ArrayTake::take(&self.0, iter.into())?.into_series())
             }
@@ -364,6 +376,13 @@ macro_rules! impl_dyn_array {
                 Ok(ArrayTake::take_unchecked(&self.0, iter.into())?.into_series())
             }
 
+            unsafe fn take_opt_iter_unchecked(
+                &self,
+                iter: &mut dyn Iterator<Item = Option<usize>>,
+            ) -> Result<Series> {
+                Ok(ArrayTake::take_unchecked(&self.0, SeriesWrap(iter).into())?.into_series())
+            }
+
             /// scatter the arrays by indices, the size of indices must be equal to the size of array
             unsafe fn scatter_unchecked(
                 &self,
@@ -396,3 +415,4 @@ impl_dyn_array!(DFListArray);
 impl_dyn_array!(DFBooleanArray);
 impl_dyn_array!(DFBinaryArray);
 impl_dyn_array!(DFStructArray);
+impl_dyn_array!(DFDecimalArray);
@@ -153,6 +153,16 @@ build_exceptions! {
     BadBytes(46),
     InitPrometheusFailure(47),
     ScalarSubqueryBadRows(48),
+    UnknownTableEngine(49),
+    DuplicateTableEngine(50),
+    TenantAlreadyBound(51),
+    BadSettingValue(52),
+    ReadOnlySetting(53),
+    ReadLimitExceeded(54),
+    ResultLimitExceeded(55),
+    UnknownQuery(56),
+    MemoryLimitExceeded(57),
+    JoinLimitExceeded(58),
 
 
     // uncategorized
@@ -180,6 +190,8 @@ build_exceptions! {
     MetaServiceShutdown(2202),
     // meta service is unavailable for now.
     MetaServiceUnavailable(2203),
+    // meta service is read-only, e.g. during an upgrade or a backup.
+    MetaServiceReadOnly(2204),
 
     // config errors
 
@@ -11,9 +11,17 @@ pub use common_store_api::CreateDatabaseActionResult;
 pub use common_store_api::CreateTableActionResult;
 pub use common_store_api::DropDatabaseActionResult;
 pub use common_store_api::DropTableActionResult;
+pub use common_store_api::ExportMetaActionResult;
 pub use common_store_api::GetDatabaseActionResult;
+pub use common_store_api::GetMetaStatusActionResult;
+pub use common_store_api::GetMetaVersionActionResult;
+pub use common_store_api::GetPlacementPolicyActionResult;
 pub use common_store_api::GetTableActionResult;
+pub use common_store_api::ImportMetaActionResult;
 use common_store_api::MetaApi;
+pub use common_store_api::PlacementPolicy;
+pub use common_store_api::SetMetaReadOnlyActionResult;
+pub use common_store_api::SetPlacementPolicyActionResult;
 
 use crate::action_declare;
 use crate::store_do_action::StoreDoAction;
@@ -70,6 +78,52 @@ impl MetaApi for StoreClient {
     ) -> common_exception::Result<GetTableActionResult> {
         self.do_action(GetTableAction { db, table }).await
     }
+
+    /// Get catalog version.
+    async fn get_meta_version(&mut self) -> common_exception::Result<GetMetaVersionActionResult> {
+        self.do_action(GetMetaVersionAction {}).await
+    }
+
+    /// Export meta data for backup.
+    async fn export_meta(&mut self) -> common_exception::Result<ExportMetaActionResult> {
+        self.do_action(ExportMetaAction {}).await
+    }
+
+    /// Import meta data from a backup.
+    async fn import_meta(
+        &mut self,
+        data: Vec<u8>,
+    ) -> common_exception::Result<ImportMetaActionResult> {
+        self.do_action(ImportMetaAction { data }).await
+    }
+
+    /// Set the placement policy of the store cluster.
+    async fn set_placement_policy(
+        &mut self,
+        policy: PlacementPolicy,
+    ) -> common_exception::Result<SetPlacementPolicyActionResult> {
+        self.do_action(SetPlacementPolicyAction { policy }).await
+    }
+
+    /// Get the placement policy of the store cluster.
+    async fn get_placement_policy(
+        &mut self,
+    ) -> common_exception::Result<GetPlacementPolicyActionResult> {
+        self.do_action(GetPlacementPolicyAction {}).await
+    }
+
+    /// Get the raft status of the store node.
+    async fn get_meta_status(&mut self) -> common_exception::Result<GetMetaStatusActionResult> {
+        self.do_action(GetMetaStatusAction {}).await
+    }
+
+    /// Put the meta service of the store cluster into the read-only mode or out of it.
+    async fn set_meta_read_only(
+        &mut self,
+        read_only: bool,
+    ) -> common_exception::Result<SetMetaReadOnlyActionResult> {
+        self.do_action(SetMetaReadOnlyAction { read_only }).await
+    }
 }
 
 // == database actions ==
@@ -139,3 +193,75 @@ action_declare!(
     GetTableActionResult,
     StoreDoAction::GetTable
 );
+
+// - get meta version
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetMetaVersionAction {}
+action_declare!(
+    GetMetaVersionAction,
+    GetMetaVersionActionResult,
+    StoreDoAction::GetMetaVersion
+);
+
+// == backup actions ==
+// - export meta
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ExportMetaAction {}
+action_declare!(
+    ExportMetaAction,
+    ExportMetaActionResult,
+    StoreDoAction::ExportMeta
+);
+
+// - import meta
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ImportMetaAction {
+    pub data: Vec<u8>,
+}
+action_declare!(
+    ImportMetaAction,
+    ImportMetaActionResult,
+    StoreDoAction::ImportMeta
+);
+
+// == placement actions ==
+// - set placement policy
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetPlacementPolicyAction {
+    pub policy: PlacementPolicy,
+}
+action_declare!(
+    SetPlacementPolicyAction,
+    SetPlacementPolicyActionResult,
+    StoreDoAction::SetPlacementPolicy
+);
+
+// - get placement policy
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetPlacementPolicyAction {}
+action_declare!(
+    GetPlacementPolicyAction,
+    GetPlacementPolicyActionResult,
+    StoreDoAction::GetPlacementPolicy
+);
+
+// == status actions ==
+// - get meta status
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetMetaStatusAction {}
+action_declare!(
+    GetMetaStatusAction,
+    GetMetaStatusActionResult,
+    StoreDoAction::GetMetaStatus
+);
+
+// - set meta read only
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetMetaReadOnlyAction {
+    pub read_only: bool,
+}
+action_declare!(
+    SetMetaReadOnlyAction,
+    SetMetaReadOnlyActionResult,
+    StoreDoAction::SetMetaReadOnly
+);
@@ -5,6 +5,7 @@
 
 use std::convert::TryFrom;
 
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::datatypes::SchemaRef as ArrowSchemaRef;
 use common_arrow::arrow::ipc::writer::IpcWriteOptions;
 use common_arrow::arrow::record_batch::RecordBatch;
@@ -18,7 +19,9 @@ use common_exception::ErrorCode;
 use common_planners::ScanPlan;
 use common_runtime::tokio;
 pub use common_store_api::AppendResult;
+pub use common_store_api::AppendTxResult;
 pub use common_store_api::BlockStream;
+pub use common_store_api::ColumnStatistics;
 pub use common_store_api::DataPartInfo;
 pub use common_store_api::ReadAction;
 pub use common_store_api::ReadPlanResult;
@@ -28,8 +31,14 @@ use futures::SinkExt;
 use futures::StreamExt;
 use tonic::Request;
 
+use crate::action_declare;
 use crate::impls::storage_api_impl_utils;
+pub use crate::impls::storage_api_impl_utils::get_bucket_meta;
+pub use crate::impls::storage_api_impl_utils::get_deduplicate_meta;
 pub use crate::impls::storage_api_impl_utils::get_meta;
+pub use crate::impls::storage_api_impl_utils::get_part_bucket;
+pub use crate::impls::storage_api_impl_utils::get_tx_meta;
+pub use crate::impls::storage_api_impl_utils::part_file_name;
 use crate::RequestFor;
 use crate::StoreClient;
 use crate::StoreDoAction;
@@ -50,6 +59,28 @@ impl From<ReadPlanAction> for StoreDoAction {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct CommitAppendAction {
+    pub tx_id: String,
+}
+
+action_declare!(
+    CommitAppendAction,
+    AppendTxResult,
+    StoreDoAction::CommitAppend
+);
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct AbortAppendAction {
+    pub tx_id: String,
+}
+
+action_declare!(
+    AbortAppendAction,
+    AppendTxResult,
+    StoreDoAction::AbortAppend
+);
+
 #[async_trait::async_trait]
 impl StorageApi for StoreClient {
     async fn read_plan(
@@ -72,8 +103,17 @@ impl StorageApi for StoreClient {
         let cmd = StoreDoGet::Read(read_action.clone());
         let mut req = tonic::Request::<Ticket>::from(&cmd);
         req.set_timeout(self.timeout);
-        let res = self.client.do_get(req).await?.into_inner();
-        let arrow_schema: ArrowSchemaRef = Arc::new(schema.to_arrow());
+        let mut res = self.client.do_get(req).await?.into_inner();
+
+        // The first message is the schema of the partition, which is a subset of `schema`
+        // if the partition is written before some columns are added.
+        let arrow_schema: ArrowSchemaRef = match res.next().await {
+            Some(item) => {
+                let item = item.map_err(|status| ErrorCode::TokioError(status.to_string()))?;
+                Arc::new(ArrowSchema::try_from(&item).map_err(ErrorCode::from)?)
+            }
+            None => Arc::new(schema.to_arrow()),
+        };
         let res_stream = res.map(move |item| {
             item.map_err(|status| ErrorCode::TokioError(status.to_string()))
                 .and_then(|item| {
@@ -90,6 +130,9 @@ impl StorageApi for StoreClient {
         db_name: String,
         tbl_name: String,
         scheme_ref: DataSchemaRef,
+        bucket: Option<u64>,
+        tx_id: Option<String>,
+        deduplicate: bool,
         mut block_stream: BlockStream,
     ) -> common_exception::Result<AppendResult> {
         let ipc_write_opt = IpcWriteOptions::default();
@@ -127,6 +170,15 @@ impl StorageApi for StoreClient {
         let mut req = Request::new(flight_stream);
         let meta = req.metadata_mut();
         storage_api_impl_utils::put_meta(meta, &db_name, &tbl_name);
+        if let Some(bucket) = bucket {
+            storage_api_impl_utils::put_bucket_meta(meta, bucket);
+        }
+        if let Some(tx_id) = &tx_id {
+            storage_api_impl_utils::put_tx_meta(meta, tx_id);
+        }
+        if deduplicate {
+            storage_api_impl_utils::put_deduplicate_meta(meta);
+        }
 
         let res = self.client.do_put(req).await?;
 
@@ -135,4 +187,12 @@ impl StorageApi for StoreClient {
         let vec = serde_json::from_slice(&put_result.app_metadata)?;
         Ok(vec)
     }
+
+    async fn commit_append(&mut self, tx_id: String) -> common_exception::Result<AppendTxResult> {
+        self.do_action(CommitAppendAction { tx_id }).await
+    }
+
+    async fn abort_append(&mut self, tx_id: String) -> common_exception::Result<AppendTxResult> {
+        self.do_action(AbortAppendAction { tx_id }).await
+    }
 }
@@ -35,3 +35,81 @@ pub fn get_meta(meta: &MetadataMap) -> anyhow::Result<(String, String)> {
     let tbl_name = fetch_string(meta, META_KEY_TBL_NAME, "invalid tbl_name meta data")?;
     Ok((db_name, tbl_name))
 }
+
+pub const META_KEY_BUCKET: &str = "fq-bucket-bin";
+pub const BUCKET_PART_PREFIX: &str = "bucket-";
+
+/// The rows of an append are all in one bucket of a bucketed table.
+pub fn put_bucket_meta(meta: &mut MetadataMap, bucket: u64) {
+    meta.insert_bin(
+        META_KEY_BUCKET,
+        MetadataValue::from_bytes(bucket.to_string().as_bytes()),
+    );
+}
+
+pub fn get_bucket_meta(meta: &MetadataMap) -> anyhow::Result<Option<u64>> {
+    match meta.get_bin(META_KEY_BUCKET) {
+        None => Ok(None),
+        Some(v) => {
+            let bytes = v
+                .to_bytes()
+                .map_err(|_| anyhow::anyhow!("invalid bucket meta data"))?;
+            Ok(Some(String::from_utf8(bytes.to_vec())?.parse()?))
+        }
+    }
+}
+
+pub const META_KEY_TX_ID: &str = "fq-tx-id-bin";
+
+/// The parts of an append are staged in the transaction, instead of being visible at once.
+pub fn put_tx_meta(meta: &mut MetadataMap, tx_id: &str) {
+    meta.insert_bin(META_KEY_TX_ID, MetadataValue::from_bytes(tx_id.as_bytes()));
+}
+
+pub fn get_tx_meta(meta: &MetadataMap) -> anyhow::Result<Option<String>> {
+    match meta.get_bin(META_KEY_TX_ID) {
+        None => Ok(None),
+        Some(v) => {
+            let bytes = v
+                .to_bytes()
+                .map_err(|_| anyhow::anyhow!("invalid tx id meta data"))?;
+            Ok(Some(String::from_utf8(bytes.to_vec())?))
+        }
+    }
+}
+
+pub const META_KEY_DEDUPLICATE: &str = "fq-deduplicate-bin";
+
+/// The blocks of an append which are appended before are skipped, see `StorageApi::append_data`.
+pub fn put_deduplicate_meta(meta: &mut MetadataMap) {
+    meta.insert_bin(META_KEY_DEDUPLICATE, MetadataValue::from_bytes(b"1"));
+}
+
+pub fn get_deduplicate_meta(meta: &MetadataMap) -> anyhow::Result<bool> {
+    match meta.get_bin(META_KEY_DEDUPLICATE) {
+        None => Ok(false),
+        Some(v) => {
+            let bytes = v
+                .to_bytes()
+                .map_err(|_| anyhow::anyhow!("invalid deduplicate meta data"))?;
+            Ok(bytes.as_ref() == b"1")
+        }
+    }
+}
+
+/// The file name of a part, the parts of a bucket are named with the bucket as prefix.
+pub fn part_file_name(bucket: Option<u64>, id: &str) -> String {
+    match bucket {
+        None => format!("{}.parquet", id),
+        Some(bucket) => format!("{}{}-{}.parquet", BUCKET_PART_PREFIX, bucket, id),
+    }
+}
+
+/// Returns the bucket of a part by its location, None if the part is not in a bucket.
+pub fn get_part_bucket(location: &str) -> Option<u64> {
+    let file_name = location.rsplit('/').next()?;
+    let (bucket, _) = file_name
+        .strip_prefix(BUCKET_PART_PREFIX)?
+        .split_once('-')?;
+    bucket.parse().ok()
+}
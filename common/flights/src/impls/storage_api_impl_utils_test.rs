@@ -7,8 +7,16 @@
 mod test {
     use tonic::metadata::MetadataMap;
 
+    use crate::impls::storage_api_impl_utils::get_bucket_meta;
+    use crate::impls::storage_api_impl_utils::get_deduplicate_meta;
     use crate::impls::storage_api_impl_utils::get_meta;
+    use crate::impls::storage_api_impl_utils::get_part_bucket;
+    use crate::impls::storage_api_impl_utils::get_tx_meta;
+    use crate::impls::storage_api_impl_utils::part_file_name;
+    use crate::impls::storage_api_impl_utils::put_bucket_meta;
+    use crate::impls::storage_api_impl_utils::put_deduplicate_meta;
     use crate::impls::storage_api_impl_utils::put_meta;
+    use crate::impls::storage_api_impl_utils::put_tx_meta;
 
     #[test]
     fn test_get_set_meta() {
@@ -20,4 +28,39 @@ mod test {
         assert_eq!(test_db, db);
         assert_eq!(test_tbl, tbl);
     }
+
+    #[test]
+    fn test_get_set_bucket_meta() {
+        let mut meta = MetadataMap::new();
+        assert_eq!(None, get_bucket_meta(&meta).unwrap());
+        put_bucket_meta(&mut meta, 3);
+        assert_eq!(Some(3), get_bucket_meta(&meta).unwrap());
+    }
+
+    #[test]
+    fn test_get_set_tx_meta() {
+        let mut meta = MetadataMap::new();
+        assert_eq!(None, get_tx_meta(&meta).unwrap());
+        put_tx_meta(&mut meta, "tx-1");
+        assert_eq!(Some("tx-1".to_string()), get_tx_meta(&meta).unwrap());
+    }
+
+    #[test]
+    fn test_get_set_deduplicate_meta() {
+        let mut meta = MetadataMap::new();
+        assert!(!get_deduplicate_meta(&meta).unwrap());
+        put_deduplicate_meta(&mut meta);
+        assert!(get_deduplicate_meta(&meta).unwrap());
+    }
+
+    #[test]
+    fn test_part_bucket() {
+        let name = part_file_name(None, "abc");
+        assert_eq!("abc.parquet", name);
+        assert_eq!(None, get_part_bucket(&format!("db/tbl/{}", name)));
+
+        let name = part_file_name(Some(12), "abc");
+        assert_eq!("bucket-12-abc.parquet", name);
+        assert_eq!(Some(12), get_part_bucket(&format!("db/tbl/{}", name)));
+    }
 }
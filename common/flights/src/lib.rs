@@ -35,5 +35,11 @@ pub mod protobuf {
     tonic::include_proto!("storeflight");
 }
 
+// The Substrait plans executed by FuseQuery.
+#[allow(clippy::all)]
+pub mod substrait {
+    tonic::include_proto!("substrait");
+}
+
 #[cfg(test)]
 mod dns_resolver_test;
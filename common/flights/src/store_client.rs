@@ -9,6 +9,7 @@ use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
 use common_arrow::arrow_flight::Action;
 use common_arrow::arrow_flight::BasicAuth;
 use common_arrow::arrow_flight::HandshakeRequest;
+use common_arrow::arrow_flight::Ticket;
 use common_exception::ErrorCode;
 use futures::stream;
 use futures::StreamExt;
@@ -22,6 +23,8 @@ use tonic::Request;
 use crate::flight_result_to_str;
 use crate::store_do_action::RequestFor;
 use crate::store_do_action::StoreDoAction;
+use crate::store_do_get::PullAction;
+use crate::store_do_get::StoreDoGet;
 use crate::ConnectionFactory;
 
 #[derive(Clone)]
@@ -94,6 +97,24 @@ impl StoreClient {
         Ok(token)
     }
 
+    /// Pull a file from a store server, which is only used to replicate data between the store
+    /// servers.
+    pub async fn pull_file(&mut self, key: &str) -> common_exception::Result<Vec<u8>> {
+        let cmd = StoreDoGet::Pull(PullAction {
+            key: key.to_string(),
+        });
+        let mut req = Request::<Ticket>::from(&cmd);
+        req.set_timeout(self.timeout);
+        let mut res = self.client.do_get(req).await?.into_inner();
+
+        let mut buf = vec![];
+        while let Some(item) = res.next().await {
+            let item = item.map_err(|status| ErrorCode::TokioError(status.to_string()))?;
+            buf.extend_from_slice(&item.data_body);
+        }
+        Ok(buf)
+    }
+
     pub(crate) async fn do_action<T, R>(&mut self, v: T) -> common_exception::Result<R>
     where
         T: RequestFor<Reply = R>,
@@ -18,8 +18,17 @@ use crate::impls::meta_api_impl::CreateDatabaseAction;
 use crate::impls::meta_api_impl::CreateTableAction;
 use crate::impls::meta_api_impl::DropDatabaseAction;
 use crate::impls::meta_api_impl::DropTableAction;
+use crate::impls::meta_api_impl::ExportMetaAction;
 use crate::impls::meta_api_impl::GetDatabaseAction;
+use crate::impls::meta_api_impl::GetMetaStatusAction;
+use crate::impls::meta_api_impl::GetMetaVersionAction;
+use crate::impls::meta_api_impl::GetPlacementPolicyAction;
 use crate::impls::meta_api_impl::GetTableAction;
+use crate::impls::meta_api_impl::ImportMetaAction;
+use crate::impls::meta_api_impl::SetMetaReadOnlyAction;
+use crate::impls::meta_api_impl::SetPlacementPolicyAction;
+use crate::impls::storage_api_impl::AbortAppendAction;
+use crate::impls::storage_api_impl::CommitAppendAction;
 use crate::impls::storage_api_impl::ReadPlanAction;
 use crate::protobuf::FlightStoreRequest;
 
@@ -53,8 +62,20 @@ pub enum StoreDoAction {
     CreateTable(CreateTableAction),
     DropTable(DropTableAction),
     GetTable(GetTableAction),
+    GetMetaVersion(GetMetaVersionAction),
+    // meta-backup
+    ExportMeta(ExportMetaAction),
+    ImportMeta(ImportMetaAction),
+    // meta-placement
+    SetPlacementPolicy(SetPlacementPolicyAction),
+    GetPlacementPolicy(GetPlacementPolicyAction),
+    // meta-status
+    GetMetaStatus(GetMetaStatusAction),
+    SetMetaReadOnly(SetMetaReadOnlyAction),
     // storage
     ReadPlan(ReadPlanAction),
+    CommitAppend(CommitAppendAction),
+    AbortAppend(AbortAppendAction),
 
     // general purpose kv
     UpsertKV(UpsertKVAction),
@@ -0,0 +1,153 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::AggregateSingeValueState;
+use crate::aggregates::GetState;
+use crate::aggregates::StateAddr;
+
+/// `any(x)` returns the first non-null value it meets, and `anyLast(x)` returns the last one.
+/// Neither of them compares the values, so the result depends on the order of the rows and the
+/// blocks, which is not deterministic if the query is run in parallel.
+#[derive(Clone)]
+pub struct AggregateAnyFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    last: bool,
+}
+
+impl AggregateAnyFunction {
+    fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        last: bool,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+
+        Ok(Arc::new(AggregateAnyFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            last,
+        }))
+    }
+
+    pub fn try_create_any(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        Self::try_create(display_name, arguments, false)
+    }
+
+    pub fn try_create_any_last(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        Self::try_create(display_name, arguments, true)
+    }
+
+    /// Keeps the first non-null value, or replaces it with any later non-null value.
+    fn update(&self, state: &mut AggregateSingeValueState, value: DataValue) {
+        if !value.is_null() && (self.last || state.value.is_null()) {
+            state.value = value;
+        }
+    }
+}
+
+impl AggregateFunction for AggregateAnyFunction {
+    fn name(&self) -> &str {
+        "AggregateAnyFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(self.arguments[0].data_type().clone())
+    }
+
+    // The result of no rows or only NULL values is NULL.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateSingeValueState {
+            value: DataValue::from(self.arguments[0].data_type()),
+        });
+
+        (state as *mut AggregateSingeValueState) as StateAddr
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<()> {
+        let state = AggregateSingeValueState::get(place);
+        if !self.last && !state.value.is_null() {
+            return Ok(());
+        }
+
+        // Only the first or the last non-null row is fetched.
+        let row = match &columns[0] {
+            DataColumn::Constant(_, _) if input_rows > 0 => Some(0),
+            DataColumn::Constant(_, _) => None,
+            DataColumn::Array(array) => match self.last {
+                true => (0..array.len()).rev().find(|row| !array.is_null(*row)),
+                false => (0..array.len()).find(|row| !array.is_null(*row)),
+            },
+        };
+
+        if let Some(row) = row {
+            self.update(state, columns[0].try_get(row)?);
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateSingeValueState::get(place);
+        if self.last || state.value.is_null() {
+            self.update(state, columns[0].try_get(row)?);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateSingeValueState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateSingeValueState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateSingeValueState::get(place);
+        let rhs = AggregateSingeValueState::get(rhs);
+        self.update(state, rhs.value.clone());
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateSingeValueState::get(place);
+        Ok(state.value.clone())
+    }
+}
+
+impl fmt::Display for AggregateAnyFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,195 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::GetState;
+use crate::aggregates::StateAddr;
+
+/// The candidate of the majority vote and its counter, the counter is increased by the same
+/// value and decreased by a different one, and the candidate is replaced when it reaches zero.
+pub struct AggregateAnyHeavyState {
+    value: DataValue,
+    counter: u64,
+}
+
+impl<'a> GetState<'a, AggregateAnyHeavyState> for AggregateAnyHeavyState {}
+
+impl AggregateAnyHeavyState {
+    fn add(&mut self, value: DataValue) {
+        if value.is_null() {
+            return;
+        }
+
+        if self.value == value {
+            self.counter += 1;
+        } else if self.counter == 0 {
+            self.value = value;
+            self.counter = 1;
+        } else {
+            self.counter -= 1;
+        }
+    }
+
+    fn merge(&mut self, rhs: &Self) {
+        if rhs.value.is_null() {
+            return;
+        }
+
+        if self.value == rhs.value {
+            self.counter += rhs.counter;
+        } else if self.counter < rhs.counter {
+            self.value = rhs.value.clone();
+            self.counter = rhs.counter - self.counter;
+        } else {
+            self.counter -= rhs.counter;
+        }
+    }
+
+    /// The array of the value and the counter in JSON.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        serde_json::to_writer(writer, &(&self.value, self.counter))?;
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let (value, counter) = serde_json::from_slice(reader)?;
+        self.value = value;
+        self.counter = counter;
+        Ok(())
+    }
+}
+
+/// `anyHeavy(x)` returns a frequently occurring value by the heavy hitters algorithm, the value
+/// is the one which occurs in more than half of the rows if there is such a value, otherwise it
+/// is any value.
+#[derive(Clone)]
+pub struct AggregateAnyHeavyFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+}
+
+impl AggregateAnyHeavyFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+
+        Ok(Arc::new(AggregateAnyHeavyFunction {
+            display_name: display_name.to_string(),
+            arguments,
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateAnyHeavyFunction {
+    fn name(&self) -> &str {
+        "AggregateAnyHeavyFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(self.arguments[0].data_type().clone())
+    }
+
+    // The result of no rows or only NULL values is NULL.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateAnyHeavyState {
+            value: DataValue::from(self.arguments[0].data_type()),
+            counter: 0,
+        });
+
+        (state as *mut AggregateAnyHeavyState) as StateAddr
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<()> {
+        let state = AggregateAnyHeavyState::get(place);
+        match &columns[0] {
+            // The constant is the candidate of all the rows at once.
+            DataColumn::Constant(value, _) if input_rows > 0 => {
+                let rhs = AggregateAnyHeavyState {
+                    value: value.clone(),
+                    counter: input_rows as u64,
+                };
+                state.merge(&rhs);
+            }
+            DataColumn::Constant(_, _) => {}
+            DataColumn::Array(array) => {
+                for value in array.to_values()? {
+                    state.add(value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateAnyHeavyState::get(place);
+        state.add(columns[0].try_get(row)?);
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let values = columns[0].to_values()?;
+        for (value, offset) in values.into_iter().zip(offsets.iter()) {
+            let state = AggregateAnyHeavyState::get(places[*offset]);
+            state.add(value);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateAnyHeavyState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateAnyHeavyState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateAnyHeavyState::get(place);
+        let rhs = AggregateAnyHeavyState::get(rhs);
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateAnyHeavyState::get(place);
+        Ok(state.value.clone())
+    }
+}
+
+impl fmt::Display for AggregateAnyHeavyFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
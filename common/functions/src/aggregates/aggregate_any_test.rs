@@ -0,0 +1,125 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+#[test]
+fn test_aggregate_any_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![DataField::new("a", DataType::Int64, true)];
+    let columns: Vec<DataColumn> = vec![Series::new(vec![None, Some(1_i64), Some(2), None]).into()];
+
+    struct Test {
+        name: &'static str,
+        expect: DataValue,
+    }
+
+    let tests = vec![
+        Test {
+            name: "any",
+            expect: DataValue::Int64(Some(1)),
+        },
+        Test {
+            name: "anyLast",
+            expect: DataValue::Int64(Some(2)),
+        },
+    ];
+
+    for t in tests {
+        let func = AggregateFunctionFactory::get(t.name, args.clone())?;
+        assert_eq!(DataType::Int64, func.return_type()?, "{}", t.name);
+
+        // The batch and the rows agree on the first or the last non-null value.
+        let place1 = func.allocate_state(&arena);
+        func.accumulate(place1, &columns, 4)?;
+        assert_eq!(t.expect, func.merge_result(place1)?, "{}", t.name);
+
+        let place2 = func.allocate_state(&arena);
+        for row in 0..4 {
+            func.accumulate_row(place2, row, &columns)?;
+        }
+        assert_eq!(t.expect, func.merge_result(place2)?, "{}", t.name);
+
+        // The state of no rows is NULL, and it is replaced by the merged state.
+        let place3 = func.allocate_state(&arena);
+        assert_eq!(
+            DataValue::Int64(None),
+            func.merge_result(place3)?,
+            "{}",
+            t.name
+        );
+
+        let mut state = vec![];
+        func.serialize_state(place1, &mut state)?;
+        let place4 = func.allocate_state(&arena);
+        func.deserialize_state(place4, &state)?;
+        func.merge(place3, place4)?;
+        assert_eq!(t.expect, func.merge_result(place3)?, "{}", t.name);
+    }
+
+    // The constant is the value of all the rows.
+    let func = AggregateFunctionFactory::get("any", args)?;
+    let place = func.allocate_state(&arena);
+    let columns = vec![DataColumn::Constant(DataValue::Int64(Some(7)), 3)];
+    func.accumulate(place, &columns, 3)?;
+    assert_eq!(DataValue::Int64(Some(7)), func.merge_result(place)?);
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_any_heavy_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![DataField::new("a", DataType::Utf8, true)];
+    let func = AggregateFunctionFactory::get("anyHeavy", args)?;
+    assert_eq!(DataType::Utf8, func.return_type()?);
+
+    // The majority value wins regardless of its positions.
+    let columns: Vec<DataColumn> = vec![Series::new(vec![
+        Some("a"),
+        Some("b"),
+        Some("b"),
+        None,
+        Some("c"),
+        Some("b"),
+    ])
+    .into()];
+    let place1 = func.allocate_state(&arena);
+    func.accumulate(place1, &columns, 6)?;
+    assert_eq!(
+        DataValue::Utf8(Some("b".to_string())),
+        func.merge_result(place1)?
+    );
+
+    let place2 = func.allocate_state(&arena);
+    let offsets = vec![0; 6];
+    func.accumulate_keys(&[place2], &offsets, &columns, 6)?;
+    assert_eq!(
+        DataValue::Utf8(Some("b".to_string())),
+        func.merge_result(place2)?
+    );
+
+    // The merged counters keep the majority of both states.
+    let place3 = func.allocate_state(&arena);
+    let columns = vec![DataColumn::Constant(
+        DataValue::Utf8(Some("c".to_string())),
+        5,
+    )];
+    func.accumulate(place3, &columns, 5)?;
+
+    let mut state = vec![];
+    func.serialize_state(place3, &mut state)?;
+    let place4 = func.allocate_state(&arena);
+    func.deserialize_state(place4, &state)?;
+    func.merge(place1, place4)?;
+    assert_eq!(
+        DataValue::Utf8(Some("c".to_string())),
+        func.merge_result(place1)?
+    );
+    Ok(())
+}
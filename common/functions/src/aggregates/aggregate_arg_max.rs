@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
+use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::fmt;
 
@@ -10,6 +11,7 @@ use common_datavalues::prelude::*;
 use common_exception::Result;
 
 use crate::aggregates::aggregator_common::assert_binary_arguments;
+use crate::aggregates::AggregateArgMinMaxState;
 use crate::aggregates::AggregateFunction;
 use crate::aggregates::AggregateSingeValueState;
 use crate::aggregates::GetState;
@@ -44,22 +46,23 @@ impl AggregateFunction for AggregateArgMaxFunction {
         Ok(self.arguments[0].data_type().clone())
     }
 
+    // The arg is NULL if there are no rows or the arg of the max is NULL.
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
-        Ok(false)
+        Ok(true)
     }
 
     fn as_any(&self) -> &dyn Any {
         self
     }
     fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
-        let state = arena.alloc(AggregateSingeValueState {
-            value: DataValue::Struct(vec![
-                DataValue::from(self.arguments[0].data_type()),
-                DataValue::from(self.arguments[1].data_type()),
-            ]),
+        let state = arena.alloc(AggregateArgMinMaxState {
+            arg: DataValue::from(self.arguments[0].data_type()),
+            value: AggregateSingeValueState {
+                value: DataValue::from(self.arguments[1].data_type()),
+            },
         });
 
-        (state as *mut AggregateSingeValueState) as StateAddr
+        (state as *mut AggregateArgMinMaxState) as StateAddr
     }
 
     fn accumulate(
@@ -72,103 +75,55 @@ impl AggregateFunction for AggregateArgMaxFunction {
             return Ok(());
         }
 
-        let value = match &columns[1] {
-            DataColumn::Constant(value, _) => Ok(DataValue::Struct(vec![
-                DataValue::UInt64(Some(0)),
-                value.clone(),
-            ])),
-            DataColumn::Array(array) => array.arg_max(),
-        }?;
-
-        if let DataValue::Struct(max_arg_val) = value {
-            if max_arg_val[0].is_null() {
-                return Ok(());
-            }
-            let index: u64 = max_arg_val[0].clone().try_into()?;
-            let max_val = max_arg_val[1].clone();
-            let max_arg = columns[0].try_get(index as usize)?;
-
-            let state = AggregateSingeValueState::get(place);
-
-            if let DataValue::Struct(old_max_arg_val) = state.value.clone() {
-                let old_max_arg = old_max_arg_val[0].clone();
-                let old_max_val = old_max_arg_val[1].clone();
-
-                let new_max_val = DataValue::agg(Max, old_max_val.clone(), max_val)?;
-
-                state.value = DataValue::Struct(vec![
-                    if new_max_val == old_max_val {
-                        old_max_arg
-                    } else {
-                        max_arg
-                    },
-                    new_max_val,
-                ]);
-            }
+        let (max_index, max_val) = match &columns[1] {
+            DataColumn::Constant(value, _) => (0, value.clone()),
+            DataColumn::Array(array) => match array.arg_max()? {
+                DataValue::Struct(mut max_arg_val) if !max_arg_val[0].is_null() => {
+                    let index: u64 = max_arg_val[0].clone().try_into()?;
+                    (index as usize, max_arg_val.swap_remove(1))
+                }
+                _ => return Ok(()),
+            },
+        };
+
+        // The arg is only fetched if the value of the batch replaces the value of the state.
+        let state = AggregateArgMinMaxState::get(place);
+        if state.value.update(max_val, Ordering::Greater)? {
+            state.arg = columns[0].try_get(max_index)?;
         }
-
         Ok(())
     }
 
     fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-
-        if let DataValue::Struct(old_max_arg_val) = state.value.clone() {
-            let old_max_arg = old_max_arg_val[0].clone();
-            let old_max_val = old_max_arg_val[1].clone();
-
-            let new_max_val = DataValue::agg(Max, old_max_val.clone(), columns[1].try_get(row)?)?;
-
-            state.value = DataValue::Struct(vec![
-                if new_max_val == old_max_val {
-                    old_max_arg
-                } else {
-                    columns[0].try_get(row)?
-                },
-                new_max_val,
-            ]);
+        let state = AggregateArgMinMaxState::get(place);
+        if state
+            .value
+            .update(columns[1].try_get(row)?, Ordering::Greater)?
+        {
+            state.arg = columns[0].try_get(row)?;
         }
         Ok(())
     }
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateArgMinMaxState::get(place);
         state.serialize(writer)
     }
 
     fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateArgMinMaxState::get(place);
         state.deserialize(reader)
     }
 
     fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let rhs = AggregateSingeValueState::get(rhs);
-
-        if let (DataValue::Struct(current), DataValue::Struct(other)) =
-            (state.value.clone(), rhs.value.clone())
-        {
-            let new_max_val = DataValue::agg(Max, current[1].clone(), other[1].clone())?;
-
-            state.value = DataValue::Struct(vec![
-                if new_max_val == other[1] {
-                    other[0].clone()
-                } else {
-                    current[0].clone()
-                },
-                new_max_val,
-            ]);
-        }
-        Ok(())
+        let state = AggregateArgMinMaxState::get(place);
+        let rhs = AggregateArgMinMaxState::get(rhs);
+        state.merge(rhs, Ordering::Greater)
     }
 
     fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
-        let state = AggregateSingeValueState::get(place);
-        Ok(if let DataValue::Struct(state) = state.value.clone() {
-            state[0].clone()
-        } else {
-            state.value.clone()
-        })
+        let state = AggregateArgMinMaxState::get(place);
+        Ok(state.arg.clone())
     }
 }
 
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
+use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::fmt;
 
@@ -11,6 +12,7 @@ use common_exception::Result;
 
 use super::StateAddr;
 use crate::aggregates::assert_binary_arguments;
+use crate::aggregates::AggregateArgMinMaxState;
 use crate::aggregates::AggregateFunction;
 use crate::aggregates::AggregateSingeValueState;
 use crate::aggregates::GetState;
@@ -44,8 +46,9 @@ impl AggregateFunction for AggregateArgMinFunction {
         Ok(self.arguments[0].data_type().clone())
     }
 
+    // The arg is NULL if there are no rows or the arg of the min is NULL.
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
-        Ok(false)
+        Ok(true)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -53,14 +56,14 @@ impl AggregateFunction for AggregateArgMinFunction {
     }
 
     fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
-        let state = arena.alloc(AggregateSingeValueState {
-            value: DataValue::Struct(vec![
-                DataValue::from(self.arguments[0].data_type()),
-                DataValue::from(self.arguments[1].data_type()),
-            ]),
+        let state = arena.alloc(AggregateArgMinMaxState {
+            arg: DataValue::from(self.arguments[0].data_type()),
+            value: AggregateSingeValueState {
+                value: DataValue::from(self.arguments[1].data_type()),
+            },
         });
 
-        (state as *mut AggregateSingeValueState) as StateAddr
+        (state as *mut AggregateArgMinMaxState) as StateAddr
     }
 
     fn accumulate(
@@ -73,103 +76,55 @@ impl AggregateFunction for AggregateArgMinFunction {
             return Ok(());
         }
 
-        let value = match &columns[1] {
-            DataColumn::Constant(value, _) => Ok(DataValue::Struct(vec![
-                DataValue::UInt64(Some(0)),
-                value.clone(),
-            ])),
-            DataColumn::Array(array) => array.arg_min(),
-        }?;
-
-        if let DataValue::Struct(min_arg_val) = value {
-            if min_arg_val[0].is_null() {
-                return Ok(());
-            }
-            let index: u64 = min_arg_val[0].clone().try_into()?;
-            let min_val = min_arg_val[1].clone();
-
-            let min_arg = columns[0].try_get(index as usize)?;
-
-            let state = AggregateSingeValueState::get(place);
-
-            if let DataValue::Struct(old_min_arg_val) = state.value.clone() {
-                let old_min_arg = old_min_arg_val[0].clone();
-                let old_min_val = old_min_arg_val[1].clone();
-
-                let new_min_val = DataValue::agg(Min, old_min_val.clone(), min_val)?;
-
-                state.value = DataValue::Struct(vec![
-                    if new_min_val == old_min_val {
-                        old_min_arg
-                    } else {
-                        min_arg
-                    },
-                    new_min_val,
-                ]);
-            }
+        let (min_index, min_val) = match &columns[1] {
+            DataColumn::Constant(value, _) => (0, value.clone()),
+            DataColumn::Array(array) => match array.arg_min()? {
+                DataValue::Struct(mut min_arg_val) if !min_arg_val[0].is_null() => {
+                    let index: u64 = min_arg_val[0].clone().try_into()?;
+                    (index as usize, min_arg_val.swap_remove(1))
+                }
+                _ => return Ok(()),
+            },
+        };
+
+        // The arg is only fetched if the value of the batch replaces the value of the state.
+        let state = AggregateArgMinMaxState::get(place);
+        if state.value.update(min_val, Ordering::Less)? {
+            state.arg = columns[0].try_get(min_index)?;
         }
         Ok(())
     }
 
     fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-
-        if let DataValue::Struct(old_min_arg_val) = state.value.clone() {
-            let old_min_arg = old_min_arg_val[0].clone();
-            let old_min_val = old_min_arg_val[1].clone();
-
-            let new_min_val = DataValue::agg(Min, old_min_val.clone(), columns[1].try_get(row)?)?;
-
-            state.value = DataValue::Struct(vec![
-                if new_min_val == old_min_val {
-                    old_min_arg
-                } else {
-                    columns[0].try_get(row)?
-                },
-                new_min_val,
-            ]);
+        let state = AggregateArgMinMaxState::get(place);
+        if state
+            .value
+            .update(columns[1].try_get(row)?, Ordering::Less)?
+        {
+            state.arg = columns[0].try_get(row)?;
         }
         Ok(())
     }
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateArgMinMaxState::get(place);
         state.serialize(writer)
     }
 
     fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateArgMinMaxState::get(place);
         state.deserialize(reader)
     }
 
     fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let rhs = AggregateSingeValueState::get(rhs);
-
-        if let (DataValue::Struct(current), DataValue::Struct(other)) =
-            (state.value.clone(), rhs.value.clone())
-        {
-            let new_min_val = DataValue::agg(Min, current[1].clone(), other[1].clone())?;
-
-            state.value = DataValue::Struct(vec![
-                if new_min_val == other[1] {
-                    other[0].clone()
-                } else {
-                    current[0].clone()
-                },
-                new_min_val,
-            ]);
-        }
-        Ok(())
+        let state = AggregateArgMinMaxState::get(place);
+        let rhs = AggregateArgMinMaxState::get(rhs);
+        state.merge(rhs, Ordering::Less)
     }
 
     fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
-        let state = AggregateSingeValueState::get(place);
-        Ok(if let DataValue::Struct(state) = state.value.clone() {
-            state[0].clone()
-        } else {
-            state.value.clone()
-        })
+        let state = AggregateArgMinMaxState::get(place);
+        Ok(state.arg.clone())
     }
 }
 
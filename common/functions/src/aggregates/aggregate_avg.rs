@@ -4,52 +4,72 @@
 
 use std::any::Any;
 use std::fmt;
+use std::marker::PhantomData;
 
 use common_datavalues::prelude::*;
 use common_exception::Result;
 
-use super::AggregateSingeValueState;
 use super::GetState;
 use super::StateAddr;
 use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::sum_batch;
+use crate::aggregates::sum_return_type;
+use crate::aggregates::AggregateAvgState;
 use crate::aggregates::AggregateFunction;
 use crate::aggregates::AggregateFunctionRef;
-use crate::aggregates::AggregateSumFunction;
+use crate::aggregates::StateNative;
 
+/// AVG of the values, the state is the sum in the native of the sum type and the count. The
+/// AVG of the decimals is the decimal of the scale of the values, truncated.
 #[derive(Clone)]
-pub struct AggregateAvgFunction {
+pub struct AggregateAvgFunction<T> {
     display_name: String,
     arguments: Vec<DataField>,
-    sum_type: DataType,
+    return_type: DataType,
+    _native: PhantomData<T>,
 }
 
-impl AggregateAvgFunction {
-    pub fn try_create(
-        display_name: &str,
-        arguments: Vec<DataField>,
-    ) -> Result<AggregateFunctionRef> {
-        assert_unary_arguments(display_name, arguments.len())?;
-
-        let sum_type = AggregateSumFunction::sum_return_type(arguments[0].data_type())?;
-        Ok(Arc::new(AggregateAvgFunction {
-            display_name: display_name.to_string(),
-            arguments,
-            sum_type,
-        }))
+pub fn try_create_aggregate_avg_function(
+    display_name: &str,
+    arguments: Vec<DataField>,
+) -> Result<AggregateFunctionRef> {
+    assert_unary_arguments(display_name, arguments.len())?;
+
+    match sum_return_type(arguments[0].data_type())? {
+        DataType::Int64 => create_typed::<i64>(display_name, arguments, DataType::Float64),
+        DataType::UInt64 => create_typed::<u64>(display_name, arguments, DataType::Float64),
+        return_type @ DataType::Decimal(_, _) => {
+            create_typed::<i128>(display_name, arguments, return_type)
+        }
+        _ => create_typed::<f64>(display_name, arguments, DataType::Float64),
     }
 }
 
-impl AggregateFunction for AggregateAvgFunction {
+fn create_typed<T: StateNative>(
+    display_name: &str,
+    arguments: Vec<DataField>,
+    return_type: DataType,
+) -> Result<AggregateFunctionRef> {
+    Ok(Arc::new(AggregateAvgFunction::<T> {
+        display_name: display_name.to_string(),
+        arguments,
+        return_type,
+        _native: PhantomData,
+    }))
+}
+
+impl<T: StateNative> AggregateFunction for AggregateAvgFunction<T> {
     fn name(&self) -> &str {
         "AggregateAvgFunction"
     }
 
     fn return_type(&self) -> Result<DataType> {
-        Ok(DataType::Float64)
+        Ok(self.return_type.clone())
     }
 
+    // The result of no rows or only NULL values is NULL.
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
-        Ok(false)
+        Ok(true)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -57,14 +77,12 @@ impl AggregateFunction for AggregateAvgFunction {
     }
 
     fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
-        let state = arena.alloc(AggregateSingeValueState {
-            value: DataValue::Struct(vec![
-                DataValue::from(&self.sum_type),
-                DataValue::UInt64(Some(0)),
-            ]),
+        let state = arena.alloc(AggregateAvgState::<T> {
+            sum: T::default(),
+            count: 0,
         });
 
-        (state as *mut AggregateSingeValueState) as StateAddr
+        (state as *mut AggregateAvgState<T>) as StateAddr
     }
 
     fn accumulate(
@@ -73,81 +91,73 @@ impl AggregateFunction for AggregateAvgFunction {
         columns: &[DataColumn],
         input_rows: usize,
     ) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-
-        if let DataValue::Struct(values) = state.value.clone() {
-            let sum = match &columns[0] {
-                DataColumn::Constant(value, size) => {
-                    DataValue::arithmetic(Mul, value.clone(), DataValue::UInt64(Some(*size as u64)))
-                }
-                DataColumn::Array(array) => array.sum(),
-            }?;
-
-            let sum = (&sum + &values[0])?;
-
-            let count = DataValue::UInt64(Some(input_rows as u64));
-            let count = (&count + &values[1])?;
-
-            state.value = DataValue::Struct(vec![sum, count]);
-        }
+        let state = AggregateAvgState::<T>::get(place);
+        let sum = T::from_data_value(&sum_batch(&columns[0])?)?;
+
+        // The NULLs are not counted.
+        let count = match &columns[0] {
+            DataColumn::Constant(value, _) if value.is_null() => 0,
+            DataColumn::Constant(_, _) => input_rows,
+            DataColumn::Array(array) => array.len() - array.null_count(),
+        };
+        state.add(sum, count as u64);
         Ok(())
     }
 
     fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let value = columns[0].try_get(row)?;
-
-        if let DataValue::Struct(values) = state.value.clone() {
-            let sum = (&value + &values[0])?;
-            let count = DataValue::UInt64(Some(1_u64));
-            let count = (&count + &values[1])?;
+        let state = AggregateAvgState::<T>::get(place);
+        state.add(T::from_data_value(&columns[0].try_get(row)?)?, 1);
+        Ok(())
+    }
 
-            state.value = DataValue::Struct(vec![sum, count]);
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        // The column is cast to the native of the state once, not per row.
+        let values = T::column_values(&columns[0])?;
+        for (value, offset) in values.into_iter().zip(offsets.iter()) {
+            let state = AggregateAvgState::<T>::get(places[*offset]);
+            state.add(value, 1);
         }
         Ok(())
     }
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateAvgState::<T>::get(place);
         state.serialize(writer)
     }
 
     fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateAvgState::<T>::get(place);
         state.deserialize(reader)
     }
 
     fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let rhs = AggregateSingeValueState::get(rhs);
-
-        if let (DataValue::Struct(current), DataValue::Struct(other)) =
-            (state.value.clone(), rhs.value.clone())
-        {
-            let sum = (&current[0] + &other[0])?;
-            let count = (&current[1] + &other[1])?;
-
-            state.value = DataValue::Struct(vec![sum, count]);
-        }
+        let state = AggregateAvgState::<T>::get(place);
+        let rhs = AggregateAvgState::<T>::get(rhs);
+        state.add(Some(rhs.sum), rhs.count);
         Ok(())
     }
 
     fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
-        let state = AggregateSingeValueState::get(place);
-
-        Ok(if let DataValue::Struct(states) = state.value.clone() {
-            if states[1].eq(&DataValue::UInt64(Some(0))) {
-                DataValue::Float64(None)
-            } else {
-                (&states[0] / &states[1])?
-            }
-        } else {
-            state.value.clone()
-        })
+        let state = AggregateAvgState::<T>::get(place);
+        match (state.count, &self.return_type) {
+            (0, return_type) => Ok(DataValue::from(return_type)),
+            (count, DataType::Decimal(_, _)) => DataValue::arithmetic(
+                Div,
+                T::to_data_value(Some(state.sum), &self.return_type),
+                DataValue::UInt64(Some(count)),
+            ),
+            (count, _) => Ok(DataValue::Float64(Some(state.sum.as_f64() / count as f64))),
+        }
     }
 }
 
-impl fmt::Display for AggregateAvgFunction {
+impl<T> fmt::Display for AggregateAvgFunction<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.display_name)
     }
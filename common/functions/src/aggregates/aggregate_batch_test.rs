@@ -96,7 +96,7 @@ fn test_aggregate_batch() {
                 Series::new(vec![4.0_f32, 3.0, 2.0, 1.0]),
                 Series::new(vec![4.0_f64, 3.0, 2.0, 1.0]),
             ],
-            func: Box::new(AggregateSumFunction::sum_batch),
+            func: Box::new(sum_batch),
             expect: vec![
                 DataValue::Utf8(Some("xx".to_string())),
                 DataValue::Int64(Some(10)),
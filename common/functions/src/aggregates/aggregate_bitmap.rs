@@ -0,0 +1,275 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use roaring::RoaringTreemap;
+
+use super::GetState;
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::scalars::bitmap_from_bytes;
+use crate::scalars::bitmap_to_bytes;
+
+/// The bitmap of the aggregated values or bitmaps, None if nothing is aggregated yet, which is
+/// not the empty bitmap for the intersection.
+pub struct AggregateBitmapState {
+    bitmap: Option<RoaringTreemap>,
+}
+
+impl<'a> GetState<'a, AggregateBitmapState> for AggregateBitmapState {}
+
+impl AggregateBitmapState {
+    fn merge(&mut self, rhs: RoaringTreemap, input: BitmapInput) {
+        self.bitmap = Some(match self.bitmap.take() {
+            None => rhs,
+            Some(bitmap) => match input {
+                BitmapInput::And => &bitmap & &rhs,
+                BitmapInput::Values | BitmapInput::Or => &bitmap | &rhs,
+            },
+        });
+    }
+
+    /// A byte of whether there is a bitmap, then the bitmap in the portable format of roaring.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        match &self.bitmap {
+            None => writer.push(0),
+            Some(bitmap) => {
+                writer.push(1);
+                writer.extend_from_slice(&bitmap_to_bytes(bitmap)?);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        self.bitmap = match reader.split_first() {
+            Some((0, _)) => None,
+            Some((1, bytes)) => Some(bitmap_from_bytes(bytes)?),
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Cannot deserialize the state of bitmap: {:?}",
+                    reader
+                )))
+            }
+        };
+        Ok(())
+    }
+}
+
+// The aggregated values are unsigned integers, or the bitmaps which are intersected or united.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BitmapInput {
+    Values,
+    And,
+    Or,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BitmapResult {
+    Bitmap,
+    Cardinality,
+}
+
+/// `bitmapBuild(x)` returns the bitmap of the values, `groupBitmap(x)` returns the number of
+/// the distinct values, `groupBitmapAnd(bitmap)` and `groupBitmapOr(bitmap)` return the number
+/// of the values of the intersection and the union of the bitmaps.
+#[derive(Clone)]
+pub struct AggregateBitmapFunction {
+    display_name: String,
+    input: BitmapInput,
+    result: BitmapResult,
+}
+
+impl AggregateBitmapFunction {
+    fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        input: BitmapInput,
+        result: BitmapResult,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        assert_unary_arguments(display_name, arguments.len())?;
+
+        let data_type = arguments[0].data_type();
+        let supported = match input {
+            BitmapInput::Values => matches!(
+                data_type,
+                DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64
+            ),
+            BitmapInput::And | BitmapInput::Or => data_type == &DataType::Binary,
+        };
+        if !supported {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} does not support {} type parameters",
+                display_name, data_type
+            )));
+        }
+
+        Ok(Arc::new(AggregateBitmapFunction {
+            display_name: display_name.to_string(),
+            input,
+            result,
+        }))
+    }
+
+    pub fn try_create_build(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        Self::try_create(
+            display_name,
+            arguments,
+            BitmapInput::Values,
+            BitmapResult::Bitmap,
+        )
+    }
+
+    pub fn try_create_group(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        Self::try_create(
+            display_name,
+            arguments,
+            BitmapInput::Values,
+            BitmapResult::Cardinality,
+        )
+    }
+
+    pub fn try_create_group_and(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        Self::try_create(
+            display_name,
+            arguments,
+            BitmapInput::And,
+            BitmapResult::Cardinality,
+        )
+    }
+
+    pub fn try_create_group_or(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        Self::try_create(
+            display_name,
+            arguments,
+            BitmapInput::Or,
+            BitmapResult::Cardinality,
+        )
+    }
+}
+
+impl AggregateFunction for AggregateBitmapFunction {
+    fn name(&self) -> &str {
+        "AggregateBitmapFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        match self.result {
+            BitmapResult::Bitmap => Ok(DataType::Binary),
+            BitmapResult::Cardinality => Ok(DataType::UInt64),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateBitmapState { bitmap: None });
+        (state as *mut AggregateBitmapState) as StateAddr
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<()> {
+        if self.input != BitmapInput::Values {
+            return (0..input_rows).try_for_each(|row| self.accumulate_row(place, row, columns));
+        }
+
+        let series = columns[0].to_array()?.cast_with_type(&DataType::UInt64)?;
+        let mut bitmap = RoaringTreemap::new();
+        for value in series.u64()?.downcast_iter().flatten() {
+            bitmap.insert(value);
+        }
+        AggregateBitmapState::get(place).merge(bitmap, self.input);
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateBitmapState::get(place);
+        match (self.input, columns[0].try_get(row)?) {
+            (_, value) if value.is_null() => {}
+            (BitmapInput::Values, value) => {
+                let value = value.as_u64()?;
+                match &mut state.bitmap {
+                    Some(bitmap) => {
+                        bitmap.insert(value);
+                    }
+                    None => state.bitmap = Some(std::iter::once(value).collect()),
+                }
+            }
+            (_, DataValue::Binary(Some(bytes))) => {
+                state.merge(bitmap_from_bytes(&bytes)?, self.input)
+            }
+            (_, value) => {
+                return Err(ErrorCode::BadDataValueType(format!(
+                    "{} expects bitmaps, but got {:?}",
+                    self.display_name,
+                    value.data_type()
+                )))
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateBitmapState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateBitmapState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateBitmapState::get(place);
+        let rhs = AggregateBitmapState::get(rhs);
+        if let Some(bitmap) = &rhs.bitmap {
+            state.merge(bitmap.clone(), self.input);
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateBitmapState::get(place);
+        let empty = RoaringTreemap::new();
+        let bitmap = state.bitmap.as_ref().unwrap_or(&empty);
+        match self.result {
+            BitmapResult::Bitmap => Ok(DataValue::Binary(Some(bitmap_to_bytes(bitmap)?))),
+            BitmapResult::Cardinality => Ok(DataValue::UInt64(Some(bitmap.len()))),
+        }
+    }
+}
+
+impl fmt::Display for AggregateBitmapFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
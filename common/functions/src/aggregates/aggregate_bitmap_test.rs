@@ -0,0 +1,107 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+use crate::scalars::bitmap_from_bytes;
+
+#[test]
+fn test_aggregate_bitmap_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![DataField::new("a", DataType::UInt64, false)];
+    let columns: Vec<DataColumn> =
+        vec![Series::new(vec![Some(3u64), Some(1), None, Some(3)]).into()];
+
+    // groupBitmap counts the distinct values.
+    let func = AggregateFunctionFactory::get("groupBitmap", args.clone())?;
+    assert_eq!(DataType::UInt64, func.return_type()?);
+    let place = func.allocate_state(&arena);
+    func.accumulate(place, &columns, 4)?;
+    assert_eq!(DataValue::UInt64(Some(2)), func.merge_result(place)?);
+
+    // bitmapBuild returns the bitmap, the states are merged after the serialization.
+    let func = AggregateFunctionFactory::get("bitmapBuild", args.clone())?;
+    assert_eq!(DataType::Binary, func.return_type()?);
+    let place1 = func.allocate_state(&arena);
+    func.accumulate(place1, &columns, 4)?;
+    let place2 = func.allocate_state(&arena);
+    func.accumulate_row(place2, 0, &[Series::new(vec![7u64]).into()])?;
+    let mut state = vec![];
+    func.serialize_state(place2, &mut state)?;
+    let place3 = func.allocate_state(&arena);
+    func.deserialize_state(place3, &state)?;
+    func.merge(place1, place3)?;
+    match func.merge_result(place1)? {
+        DataValue::Binary(Some(bytes)) => {
+            let bitmap = bitmap_from_bytes(&bytes)?;
+            assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![1, 3, 7]);
+        }
+        other => panic!("Unexpected result of bitmapBuild: {:?}", other),
+    }
+
+    // The values must be unsigned integers.
+    let result = AggregateFunctionFactory::get("bitmapBuild", vec![DataField::new(
+        "a",
+        DataType::Int64,
+        false,
+    )]);
+    assert_eq!(
+        "Code: 6, displayText = bitmapBuild does not support Int64 type parameters.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_group_bitmap_and_or_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![DataField::new("a", DataType::UInt64, false)];
+    let columns: Vec<DataColumn> = vec![Series::new(vec![1u64, 2, 3, 2, 3, 4]).into()];
+
+    // The bitmaps of {1, 2, 3} and {2, 3, 4}.
+    let build = AggregateFunctionFactory::get("bitmapBuild", args)?;
+    let mut bitmaps = vec![];
+    for rows in &[0..3, 3..6] {
+        let place = build.allocate_state(&arena);
+        for row in rows.clone() {
+            build.accumulate_row(place, row, &columns)?;
+        }
+        bitmaps.push(build.merge_result(place)?);
+    }
+    let bitmaps: DataColumn = DataValue::try_into_data_array(&bitmaps, &DataType::Binary)?.into();
+
+    let args = vec![DataField::new("bitmap", DataType::Binary, false)];
+    for (name, expect) in &[("groupBitmapAnd", 2u64), ("groupBitmapOr", 4)] {
+        let func = AggregateFunctionFactory::get(name, args.clone())?;
+        let place = func.allocate_state(&arena);
+        func.accumulate(place, &[bitmaps.clone()], 2)?;
+        assert_eq!(
+            DataValue::UInt64(Some(*expect)),
+            func.merge_result(place)?,
+            "{}",
+            name
+        );
+
+        // A state without any bitmap is merged as nothing.
+        let empty = func.allocate_state(&arena);
+        func.merge(place, empty)?;
+        assert_eq!(
+            DataValue::UInt64(Some(*expect)),
+            func.merge_result(place)?,
+            "{}",
+            name
+        );
+        assert_eq!(
+            DataValue::UInt64(Some(0)),
+            func.merge_result(empty)?,
+            "{}",
+            name
+        );
+    }
+    Ok(())
+}
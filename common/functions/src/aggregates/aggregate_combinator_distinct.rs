@@ -29,6 +29,7 @@ pub struct AggregateDistinctState {
 }
 
 impl AggregateDistinctState {
+    /// The array of the distinct values in JSON.
     pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
         let mut vs = Vec::with_capacity(self.set.len());
         for entry in self.set.iter() {
@@ -139,7 +139,30 @@ impl AggregateFunction for AggregateIfCombinator {
     }
 
     fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
-        self.nested.accumulate_row(place, row, columns)
+        match columns[self.argument_len - 1].try_get(row)? {
+            DataValue::Boolean(Some(true)) => self.nested.accumulate_row(place, row, columns),
+            _ => Ok(()),
+        }
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<()> {
+        let boolean_array = columns[self.argument_len - 1].to_array()?;
+        let boolean_array = boolean_array.bool()?;
+
+        // The rows whose condition is false or NULL are skipped.
+        for (row, condition) in boolean_array.into_iter().enumerate().take(input_rows) {
+            if condition == Some(true) {
+                self.nested
+                    .accumulate_row(places[offsets[row]], row, columns)?;
+            }
+        }
+        Ok(())
     }
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
@@ -150,6 +173,14 @@ impl AggregateFunction for AggregateIfCombinator {
         self.nested.deserialize(place, reader)
     }
 
+    fn state_version(&self) -> u8 {
+        self.nested.state_version()
+    }
+
+    fn deserialize_version(&self, place: StateAddr, version: u8, reader: &[u8]) -> Result<()> {
+        self.nested.deserialize_version(place, version, reader)
+    }
+
     fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
         self.nested.merge(place, rhs)
     }
@@ -7,6 +7,7 @@ use std::convert::TryInto;
 use std::fmt;
 
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::StateAddr;
@@ -79,6 +80,21 @@ impl AggregateFunction for AggregateCountFunction {
         Ok(())
     }
 
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        _columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<()> {
+        for offset in offsets.iter().take(input_rows) {
+            let state = AggregateCountState::get(places[*offset]);
+            state.count += 1;
+        }
+        Ok(())
+    }
+
+    // the u64 count in big endian
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
         let state = AggregateCountState::get(place);
         let bs = state.count.to_be_bytes();
@@ -88,7 +104,13 @@ impl AggregateFunction for AggregateCountFunction {
 
     fn deserialize(&self, place: StateAddr, value: &[u8]) -> Result<()> {
         let state = AggregateCountState::get(place);
-        state.count = u64::from_be_bytes(value.try_into().unwrap());
+        let bytes = value.try_into().map_err(|_| {
+            ErrorCode::BadBytes(format!(
+                "Cannot deserialize the state of count: {:?}",
+                value
+            ))
+        })?;
+        state.count = u64::from_be_bytes(bytes);
         Ok(())
     }
 
@@ -10,6 +10,7 @@ use common_datavalues::columns::DataColumn;
 use common_datavalues::DataSchema;
 use common_datavalues::DataType;
 use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use super::StateAddr;
@@ -45,10 +46,58 @@ pub trait AggregateFunction: fmt::Display + Sync + Send {
         Ok(())
     }
 
+    // accumulate_keys is to accumulate the columns of many groups in one pass,
+    // the state of the row is places[offsets[row]], used by group by
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<()> {
+        (0..input_rows).try_for_each(|row| self.accumulate_row(places[offsets[row]], row, columns))
+    }
+
     // serialize  the state into binary array
     fn serialize(&self, _place: StateAddr, _writer: &mut Vec<u8>) -> Result<()>;
     fn deserialize(&self, _place: StateAddr, _value: &[u8]) -> Result<()>;
 
+    // the version of the binary layout of serialize, it must be bumped when the layout changes,
+    // and the old layouts are translated by deserialize_version
+    fn state_version(&self) -> u8 {
+        1
+    }
+
+    // deserialize the state of an older layout, which is sent by the nodes not upgraded yet
+    fn deserialize_version(&self, _place: StateAddr, version: u8, _value: &[u8]) -> Result<()> {
+        Err(ErrorCode::BadBytes(format!(
+            "Cannot deserialize the state of {} of version {}, the version of this node is {}",
+            self,
+            version,
+            self.state_version()
+        )))
+    }
+
+    // serialize the state with its version as the first byte, the states exchanged between
+    // the nodes are always serialized by this
+    fn serialize_state(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        writer.push(self.state_version());
+        self.serialize(place, writer)
+    }
+
+    fn deserialize_state(&self, place: StateAddr, value: &[u8]) -> Result<()> {
+        match value.split_first() {
+            Some((&version, value)) if version == self.state_version() => {
+                self.deserialize(place, value)
+            }
+            Some((&version, value)) => self.deserialize_version(place, version, value),
+            None => Err(ErrorCode::BadBytes(format!(
+                "Cannot deserialize the state of {} from empty bytes",
+                self
+            ))),
+        }
+    }
+
     fn merge(&self, _place: StateAddr, _rhs: StateAddr) -> Result<()>;
 
     // TODO append the value into the column builder
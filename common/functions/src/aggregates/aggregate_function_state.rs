@@ -2,7 +2,11 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
-use common_datavalues::DataValue;
+use std::cmp::Ordering;
+
+use common_datavalues::prelude::*;
+use common_datavalues::DECIMAL_MAX_PRECISION;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 pub type StateAddr = usize;
@@ -19,6 +23,7 @@ pub struct AggregateSingeValueState {
 impl<'a> GetState<'a, AggregateSingeValueState> for AggregateSingeValueState {}
 
 impl AggregateSingeValueState {
+    /// The value in JSON.
     pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
         serde_json::to_writer(writer, &self.value)?;
         Ok(())
@@ -28,4 +33,316 @@ impl AggregateSingeValueState {
         self.value = serde_json::from_slice(reader)?;
         Ok(())
     }
+
+    /// Replaces the value of MIN (Less) or MAX (Greater) in place, the value is moved into the
+    /// state only if it is less or greater than the current value. NULL is ignored.
+    pub fn update(&mut self, value: DataValue, ordering: Ordering) -> Result<bool> {
+        if value.is_null() {
+            return Ok(false);
+        }
+
+        let replace =
+            self.value.is_null() || compare_values(&value, &self.value)? == Some(ordering);
+        if replace {
+            self.value = value;
+        }
+        Ok(replace)
+    }
+}
+
+/// The state of ARGMIN and ARGMAX, the arg of the min or max value. The arg is cloned only
+/// when the value is replaced.
+pub struct AggregateArgMinMaxState {
+    pub arg: DataValue,
+    pub value: AggregateSingeValueState,
+}
+
+impl<'a> GetState<'a, AggregateArgMinMaxState> for AggregateArgMinMaxState {}
+
+impl AggregateArgMinMaxState {
+    pub fn merge(&mut self, rhs: &Self, ordering: Ordering) -> Result<()> {
+        if self.value.update(rhs.value.value.clone(), ordering)? {
+            self.arg = rhs.arg.clone();
+        }
+        Ok(())
+    }
+
+    /// The array of the arg and the value in JSON.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        serde_json::to_writer(writer, &(&self.arg, &self.value.value))?;
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let (arg, value) = serde_json::from_slice(reader)?;
+        self.arg = arg;
+        self.value.value = value;
+        Ok(())
+    }
+}
+
+/// Compares the non-NULL values of the same type by reference, None if they are not comparable.
+pub(crate) fn compare_values(lhs: &DataValue, rhs: &DataValue) -> Result<Option<Ordering>> {
+    macro_rules! compare {
+        ($($SCALAR: ident),*) => {
+            match (lhs, rhs) {
+                $((DataValue::$SCALAR(Some(l)), DataValue::$SCALAR(Some(r))) => Ok(l.partial_cmp(r)),)*
+                (DataValue::Decimal(Some(l), _, l_scale), DataValue::Decimal(Some(r), _, r_scale))
+                    if l_scale == r_scale =>
+                {
+                    Ok(l.partial_cmp(r))
+                }
+                _ => Err(ErrorCode::BadDataValueType(format!(
+                    "Cannot compare the values of the aggregate state: left:{:?}, right:{:?}",
+                    lhs.data_type(),
+                    rhs.data_type()
+                ))),
+            }
+        };
+    }
+
+    compare!(
+        Boolean,
+        Int8,
+        Int16,
+        Int32,
+        Int64,
+        UInt8,
+        UInt16,
+        UInt32,
+        UInt64,
+        Float32,
+        Float64,
+        Binary,
+        Utf8,
+        Date32,
+        Date64,
+        TimestampSecond,
+        TimestampMillisecond,
+        TimestampMicrosecond,
+        TimestampNanosecond
+    )
+}
+
+/// The native value of a typed aggregate state, which is stored in the arena and updated in
+/// place. The arena does not drop its values, so the natives must not own heap memory.
+pub trait StateNative: Copy + PartialOrd + Default + Send + Sync + 'static {
+    const SIZE: usize;
+
+    /// Casts the numeric value to the native, None if the value is NULL.
+    fn from_data_value(value: &DataValue) -> Result<Option<Self>>;
+    /// The value of the result type, which is needed by the natives of several types.
+    fn to_data_value(value: Option<Self>, data_type: &DataType) -> DataValue;
+    /// Casts the column to the native, the values of the rows in one pass.
+    fn column_values(column: &DataColumn) -> Result<Vec<Option<Self>>>;
+    fn add(self, other: Self) -> Self;
+    fn as_f64(self) -> f64;
+    fn write_to(self, writer: &mut Vec<u8>);
+    fn read_from(reader: &[u8]) -> Self;
+}
+
+macro_rules! impl_state_native {
+    ($NATIVE: ident, $SCALAR: ident, $DOWNCAST: ident, $ADD: ident) => {
+        impl StateNative for $NATIVE {
+            const SIZE: usize = std::mem::size_of::<$NATIVE>();
+
+            fn from_data_value(value: &DataValue) -> Result<Option<Self>> {
+                Ok(match value {
+                    DataValue::Int8(v) => v.map(|v| v as $NATIVE),
+                    DataValue::Int16(v) => v.map(|v| v as $NATIVE),
+                    DataValue::Int32(v) => v.map(|v| v as $NATIVE),
+                    DataValue::Int64(v) => v.map(|v| v as $NATIVE),
+                    DataValue::UInt8(v) => v.map(|v| v as $NATIVE),
+                    DataValue::UInt16(v) => v.map(|v| v as $NATIVE),
+                    DataValue::UInt32(v) => v.map(|v| v as $NATIVE),
+                    DataValue::UInt64(v) => v.map(|v| v as $NATIVE),
+                    DataValue::Float32(v) => v.map(|v| v as $NATIVE),
+                    DataValue::Float64(v) => v.map(|v| v as $NATIVE),
+                    DataValue::Null => None,
+                    other => {
+                        return Err(ErrorCode::BadDataValueType(format!(
+                            "Unexpected type:{:?} to get {} number",
+                            other.data_type(),
+                            stringify!($NATIVE)
+                        )))
+                    }
+                })
+            }
+
+            fn to_data_value(value: Option<Self>, _data_type: &DataType) -> DataValue {
+                DataValue::$SCALAR(value)
+            }
+
+            fn column_values(column: &DataColumn) -> Result<Vec<Option<Self>>> {
+                let array = column.cast_with_type(&DataType::$SCALAR)?.to_array()?;
+                Ok(array.$DOWNCAST()?.into_iter().collect())
+            }
+
+            #[inline]
+            fn add(self, other: Self) -> Self {
+                self.$ADD(other)
+            }
+
+            fn as_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn write_to(self, writer: &mut Vec<u8>) {
+                writer.extend_from_slice(&self.to_le_bytes());
+            }
+
+            fn read_from(reader: &[u8]) -> Self {
+                let mut bytes = [0u8; std::mem::size_of::<$NATIVE>()];
+                bytes.copy_from_slice(&reader[..Self::SIZE]);
+                $NATIVE::from_le_bytes(bytes)
+            }
+        }
+    };
+}
+
+trait FloatAdd {
+    fn float_add(self, other: Self) -> Self;
+}
+
+impl FloatAdd for f32 {
+    #[inline]
+    fn float_add(self, other: Self) -> Self {
+        self + other
+    }
+}
+
+impl FloatAdd for f64 {
+    #[inline]
+    fn float_add(self, other: Self) -> Self {
+        self + other
+    }
+}
+
+impl_state_native!(i64, Int64, i64, wrapping_add);
+impl_state_native!(u64, UInt64, u64, wrapping_add);
+impl_state_native!(f32, Float32, f32, float_add);
+impl_state_native!(f64, Float64, f64, float_add);
+
+/// The scaled values of the decimals, the integers are the decimals of the scale 0.
+impl StateNative for i128 {
+    const SIZE: usize = std::mem::size_of::<i128>();
+
+    fn from_data_value(value: &DataValue) -> Result<Option<Self>> {
+        Ok(match value {
+            DataValue::Decimal(v, _, _) => *v,
+            value if value.is_null() => None,
+            value => Some(value.as_i64()? as i128),
+        })
+    }
+
+    fn to_data_value(value: Option<Self>, data_type: &DataType) -> DataValue {
+        match data_type {
+            DataType::Decimal(precision, scale) => DataValue::Decimal(value, *precision, *scale),
+            _ => DataValue::Decimal(value, DECIMAL_MAX_PRECISION, 0),
+        }
+    }
+
+    fn column_values(column: &DataColumn) -> Result<Vec<Option<Self>>> {
+        let array = column.to_array()?;
+        Ok(array.decimal()?.collect_values())
+    }
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        self.wrapping_add(other)
+    }
+
+    fn as_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn write_to(self, writer: &mut Vec<u8>) {
+        writer.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn read_from(reader: &[u8]) -> Self {
+        let mut bytes = [0u8; std::mem::size_of::<i128>()];
+        bytes.copy_from_slice(&reader[..Self::SIZE]);
+        i128::from_le_bytes(bytes)
+    }
+}
+
+/// The state of SUM, NULL until a value is added.
+pub struct AggregateSumState<T: StateNative> {
+    pub value: Option<T>,
+}
+
+impl<'a, T: StateNative> GetState<'a, AggregateSumState<T>> for AggregateSumState<T> {}
+
+impl<T: StateNative> AggregateSumState<T> {
+    #[inline]
+    pub fn add(&mut self, value: Option<T>) {
+        if let Some(value) = value {
+            self.value = Some(self.value.unwrap_or_default().add(value));
+        }
+    }
+
+    /// A byte of the NULL flag, and the value if it is not NULL.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        match self.value {
+            None => writer.push(0),
+            Some(value) => {
+                writer.push(1);
+                value.write_to(writer);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        self.value = match reader {
+            [0] => None,
+            [1, value @ ..] if value.len() == T::SIZE => Some(T::read_from(value)),
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Cannot deserialize the state of sum: {:?}",
+                    reader
+                )))
+            }
+        };
+        Ok(())
+    }
+}
+
+/// The state of AVG, the sum and the count of the values.
+pub struct AggregateAvgState<T: StateNative> {
+    pub sum: T,
+    pub count: u64,
+}
+
+impl<'a, T: StateNative> GetState<'a, AggregateAvgState<T>> for AggregateAvgState<T> {}
+
+impl<T: StateNative> AggregateAvgState<T> {
+    #[inline]
+    pub fn add(&mut self, value: Option<T>, count: u64) {
+        if let Some(value) = value {
+            self.sum = self.sum.add(value);
+            self.count += count;
+        }
+    }
+
+    /// The sum and the u64 count in little endian.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        self.sum.write_to(writer);
+        writer.extend_from_slice(&self.count.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        if reader.len() != T::SIZE + 8 {
+            return Err(ErrorCode::BadBytes(format!(
+                "Cannot deserialize the state of avg: {:?}",
+                reader
+            )));
+        }
+
+        self.sum = T::read_from(reader);
+        self.count = u64::read_from(&reader[T::SIZE..]);
+        Ok(())
+    }
 }
@@ -296,3 +296,230 @@ fn test_aggregate_function_on_empty_data() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_aggregate_function_states() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        func_name: &'static str,
+        args: Vec<DataField>,
+        columns: Vec<DataColumn>,
+        expect: DataValue,
+    }
+
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec![Some(3i64), None, Some(5), Some(1)]).into(),
+        Series::new(vec![Some("c"), Some("a"), None, Some("b")]).into(),
+    ];
+
+    let args = vec![
+        DataField::new("a", DataType::Int64, true),
+        DataField::new("b", DataType::Utf8, true),
+    ];
+
+    let tests = vec![
+        Test {
+            name: "sum-with-null",
+            func_name: "sum",
+            args: vec![args[0].clone()],
+            columns: vec![columns[0].clone()],
+            expect: DataValue::Int64(Some(9)),
+        },
+        Test {
+            name: "avg-with-null",
+            func_name: "avg",
+            args: vec![args[0].clone()],
+            columns: vec![columns[0].clone()],
+            expect: DataValue::Float64(Some(3.0)),
+        },
+        Test {
+            name: "max-utf8",
+            func_name: "max",
+            args: vec![args[1].clone()],
+            columns: vec![columns[1].clone()],
+            expect: DataValue::Utf8(Some("c".to_string())),
+        },
+        Test {
+            name: "min-utf8",
+            func_name: "min",
+            args: vec![args[1].clone()],
+            columns: vec![columns[1].clone()],
+            expect: DataValue::Utf8(Some("a".to_string())),
+        },
+        Test {
+            name: "argmax-with-null",
+            func_name: "argmax",
+            args: vec![args[1].clone(), args[0].clone()],
+            columns: vec![columns[1].clone(), columns[0].clone()],
+            expect: DataValue::Utf8(None),
+        },
+        Test {
+            name: "argmin-with-null",
+            func_name: "argmin",
+            args: vec![args[1].clone(), args[0].clone()],
+            columns: vec![columns[1].clone(), columns[0].clone()],
+            expect: DataValue::Utf8(Some("b".to_string())),
+        },
+    ];
+
+    for t in tests {
+        let arena = Bump::new();
+        let func = AggregateFunctionFactory::get(t.func_name, t.args.clone())?;
+
+        // Accumulates the rows one by one, the states are updated in place.
+        let place = func.allocate_state(&arena);
+        for row in 0..t.columns[0].len() {
+            func.accumulate_row(place, row, &t.columns)?;
+        }
+
+        // The state is the same after the serialization.
+        let mut bytes = vec![];
+        func.serialize_state(place, &mut bytes)?;
+        assert_eq!(func.state_version(), bytes[0], "{}", t.name);
+        let deserialized = func.allocate_state(&arena);
+        func.deserialize_state(deserialized, &bytes)?;
+        assert_eq!(&t.expect, &func.merge_result(deserialized)?, "{}", t.name);
+
+        // Merges into an empty state.
+        let merged = func.allocate_state(&arena);
+        func.merge(merged, deserialized)?;
+        assert_eq!(&t.expect, &func.merge_result(merged)?, "{}", t.name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_function_accumulate_keys() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        func_name: &'static str,
+        args: Vec<DataField>,
+        columns: Vec<DataColumn>,
+        expect: Vec<DataValue>,
+    }
+
+    // The groups of the rows, [0, 1, 0, 1, 2].
+    let offsets = vec![0, 1, 0, 1, 2];
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec![Some(1i64), Some(2), None, Some(4), Some(5)]).into(),
+        Series::new(vec![true, false, true, true, false]).into(),
+    ];
+
+    let args = vec![
+        DataField::new("a", DataType::Int64, true),
+        DataField::new("b", DataType::Boolean, false),
+    ];
+
+    let tests = vec![
+        Test {
+            name: "count",
+            func_name: "count",
+            args: vec![args[0].clone()],
+            columns: vec![columns[0].clone()],
+            expect: vec![2u64.into(), 2u64.into(), 1u64.into()],
+        },
+        Test {
+            name: "sum",
+            func_name: "sum",
+            args: vec![args[0].clone()],
+            columns: vec![columns[0].clone()],
+            expect: vec![1i64.into(), 6i64.into(), 5i64.into()],
+        },
+        Test {
+            name: "avg",
+            func_name: "avg",
+            args: vec![args[0].clone()],
+            columns: vec![columns[0].clone()],
+            expect: vec![1f64.into(), 3f64.into(), 5f64.into()],
+        },
+        Test {
+            name: "max",
+            func_name: "max",
+            args: vec![args[0].clone()],
+            columns: vec![columns[0].clone()],
+            expect: vec![1i64.into(), 4i64.into(), 5i64.into()],
+        },
+        Test {
+            name: "sumIf",
+            func_name: "sumif",
+            args: args.clone(),
+            columns: columns.clone(),
+            expect: vec![1i64.into(), 4i64.into(), DataValue::Int64(None)],
+        },
+    ];
+
+    for t in tests {
+        let arena = Bump::new();
+        let func = AggregateFunctionFactory::get(t.func_name, t.args.clone())?;
+        let places = (0..3)
+            .map(|_| func.allocate_state(&arena))
+            .collect::<Vec<_>>();
+
+        func.accumulate_keys(&places, &offsets, &t.columns, offsets.len())?;
+
+        let result = places
+            .iter()
+            .map(|place| func.merge_result(*place))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(t.expect, result, "{}", t.name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_function_state_versions() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![DataField::new("a", DataType::Int64, false)];
+    let column: DataColumn = Series::new(vec![1i64, 2, 3]).into();
+
+    for func_name in &["count", "sum", "avg", "max", "sumif", "uniq"] {
+        let args = match *func_name {
+            "sumif" => vec![
+                args[0].clone(),
+                DataField::new("b", DataType::Boolean, false),
+            ],
+            _ => args.clone(),
+        };
+        let columns = match *func_name {
+            "sumif" => vec![column.clone(), Series::new(vec![true, true, false]).into()],
+            _ => vec![column.clone()],
+        };
+        let func = AggregateFunctionFactory::get(func_name, args)?;
+        let place = func.allocate_state(&arena);
+        func.accumulate(place, &columns, 3)?;
+
+        let mut bytes = vec![];
+        func.serialize_state(place, &mut bytes)?;
+
+        // The state of another version fails cleanly instead of being misread.
+        bytes[0] = func.state_version() + 1;
+        let result = func.deserialize_state(func.allocate_state(&arena), &bytes);
+        assert_eq!(
+            format!(
+                "Code: 46, displayText = Cannot deserialize the state of {} of version {}, the version of this node is {}.",
+                func,
+                func.state_version() + 1,
+                func.state_version()
+            ),
+            result.unwrap_err().to_string()
+        );
+
+        let result = func.deserialize_state(func.allocate_state(&arena), &[]);
+        assert_eq!(
+            format!(
+                "Code: 46, displayText = Cannot deserialize the state of {} from empty bytes.",
+                func
+            ),
+            result.unwrap_err().to_string()
+        );
+    }
+
+    // The truncated state of count.
+    let func = AggregateFunctionFactory::get("count", args)?;
+    let result = func.deserialize_state(func.allocate_state(&arena), &[1, 0, 0]);
+    assert_eq!(
+        "Code: 46, displayText = Cannot deserialize the state of count: [0, 0].",
+        result.unwrap_err().to_string()
+    );
+    Ok(())
+}
@@ -0,0 +1,184 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+use std::io::Cursor;
+
+use bytes::Buf;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::GetState;
+use crate::aggregates::StateAddr;
+
+/// The values in the order they are inserted. There are at most `limit` values if the limit is
+/// known.
+pub struct AggregateGroupArrayState {
+    limit: Option<u64>,
+    values: Vec<DataValue>,
+}
+
+impl<'a> GetState<'a, AggregateGroupArrayState> for AggregateGroupArrayState {}
+
+impl AggregateGroupArrayState {
+    fn push(&mut self, value: DataValue) {
+        match self.limit {
+            Some(limit) if self.values.len() as u64 >= limit => {}
+            _ => self.values.push(value),
+        }
+    }
+
+    /// The limit and the array of the values in JSON.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        serde_json::to_writer(writer, &(self.limit, &self.values))?;
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let reader = Cursor::new(reader).reader();
+        let (limit, values) = serde_json::from_reader(reader)?;
+        self.limit = limit;
+        self.values = values;
+        Ok(())
+    }
+}
+
+/// `groupArray(x[, N])` returns the list of the values of x, at most the first N values if N is
+/// given. The rows whose x is NULL are skipped. The values are in the order of the rows within a
+/// block, the order of the values of different blocks depends on the order the states are merged.
+#[derive(Clone)]
+pub struct AggregateGroupArrayFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+}
+
+impl AggregateGroupArrayFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_variadic_arguments(display_name, arguments.len(), (1, 2))?;
+
+        // The values are built into a list, which takes the numbers and the strings.
+        let value_type = arguments[0].data_type();
+        if !is_numeric(value_type) && value_type != &DataType::Utf8 {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} does not support {} type values",
+                display_name, value_type
+            )));
+        }
+
+        if arguments.len() == 2 && !is_integer(arguments[1].data_type()) {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} expects the limit of integer, but got {}",
+                display_name,
+                arguments[1].data_type()
+            )));
+        }
+
+        Ok(Arc::new(AggregateGroupArrayFunction {
+            display_name: display_name.to_string(),
+            arguments,
+        }))
+    }
+
+    fn limit(&self, columns: &[DataColumn]) -> Result<Option<u64>> {
+        let limit = match columns.get(1) {
+            None => return Ok(None),
+            Some(DataColumn::Constant(value, _)) if !value.is_null() => value.as_i64()?,
+            Some(_) => -1,
+        };
+
+        if limit < 0 {
+            return Err(ErrorCode::BadArguments(format!(
+                "The limit of {} must be a constant non-negative integer",
+                self.display_name
+            )));
+        }
+        Ok(Some(limit as u64))
+    }
+}
+
+impl AggregateFunction for AggregateGroupArrayFunction {
+    fn name(&self) -> &str {
+        "AggregateGroupArrayFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::List(Box::new(DataField::new(
+            "item",
+            self.arguments[0].data_type().clone(),
+            true,
+        ))))
+    }
+
+    // The result of no rows is an empty list.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateGroupArrayState {
+            limit: None,
+            values: vec![],
+        });
+        (state as *mut AggregateGroupArrayState) as StateAddr
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateGroupArrayState::get(place);
+        state.limit = self.limit(columns)?;
+
+        let value = columns[0].try_get(row)?;
+        if !value.is_null() {
+            state.push(value);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateGroupArrayState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateGroupArrayState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateGroupArrayState::get(place);
+        let rhs = AggregateGroupArrayState::get(rhs);
+
+        // The state of no rows has not seen the limit yet.
+        state.limit = state.limit.or(rhs.limit);
+        for value in rhs.values.iter() {
+            state.push(value.clone());
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateGroupArrayState::get(place);
+        Ok(DataValue::List(
+            Some(state.values.clone()),
+            self.arguments[0].data_type().clone(),
+        ))
+    }
+}
+
+impl fmt::Display for AggregateGroupArrayFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
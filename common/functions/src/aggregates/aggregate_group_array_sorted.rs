@@ -0,0 +1,243 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::Cursor;
+
+use bytes::Buf;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::aggregates::aggregate_function_state::compare_values;
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::GetState;
+use crate::aggregates::StateAddr;
+
+/// The (key, value) entries sorted by the keys, the entries of the equal keys are in the order
+/// they are inserted. There are at most `limit` entries if the limit is known.
+pub struct AggregateGroupArraySortedState {
+    limit: Option<u64>,
+    entries: Vec<(DataValue, DataValue)>,
+}
+
+impl<'a> GetState<'a, AggregateGroupArraySortedState> for AggregateGroupArraySortedState {}
+
+impl AggregateGroupArraySortedState {
+    /// Inserts the entry after the entries whose keys are not after the key, `ordering` is the
+    /// ordering of a key before the keys after it.
+    fn insert(&mut self, key: DataValue, value: DataValue, ordering: Ordering) -> Result<()> {
+        let (mut low, mut high) = (0, self.entries.len());
+        while low < high {
+            let mid = (low + high) / 2;
+            match compare_values(&key, &self.entries[mid].0)? == Some(ordering) {
+                true => high = mid,
+                false => low = mid + 1,
+            }
+        }
+
+        match self.limit {
+            Some(limit) if low as u64 >= limit => {}
+            _ => {
+                self.entries.insert(low, (key, value));
+                if let Some(limit) = self.limit {
+                    self.entries.truncate(limit as usize);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The limit and the array of the entries in JSON.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        serde_json::to_writer(writer, &(self.limit, &self.entries))?;
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let reader = Cursor::new(reader).reader();
+        let (limit, entries) = serde_json::from_reader(reader)?;
+        self.limit = limit;
+        self.entries = entries;
+        Ok(())
+    }
+}
+
+/// `groupArraySorted(x, key[, N])` returns the list of the values of x ordered by the key
+/// ascending, and `groupArraySortedDesc(x, key[, N])` orders them by the key descending, at most
+/// the first N values if N is given. The rows whose x or key is NULL are skipped. They are
+/// `groupArray(x ORDER BY key [DESC] LIMIT N)` in other dialects, the key and N are arguments as
+/// the SQL parser does not take ORDER BY and LIMIT inside the function calls.
+#[derive(Clone)]
+pub struct AggregateGroupArraySortedFunction {
+    display_name: String,
+    arguments: Vec<DataField>,
+    desc: bool,
+}
+
+impl AggregateGroupArraySortedFunction {
+    fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        desc: bool,
+    ) -> Result<AggregateFunctionRef> {
+        assert_variadic_arguments(display_name, arguments.len(), (2, 3))?;
+
+        // The values are built into a list, which takes the numbers and the strings.
+        let value_type = arguments[0].data_type();
+        if !is_numeric(value_type) && value_type != &DataType::Utf8 {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} does not support {} type values",
+                display_name, value_type
+            )));
+        }
+
+        let key_type = arguments[1].data_type();
+        if matches!(key_type, DataType::List(_) | DataType::Struct(_)) {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} does not support {} type keys",
+                display_name, key_type
+            )));
+        }
+
+        if arguments.len() == 3 && !is_integer(arguments[2].data_type()) {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} expects the limit of integer, but got {}",
+                display_name,
+                arguments[2].data_type()
+            )));
+        }
+
+        Ok(Arc::new(AggregateGroupArraySortedFunction {
+            display_name: display_name.to_string(),
+            arguments,
+            desc,
+        }))
+    }
+
+    pub fn try_create_asc(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        Self::try_create(display_name, arguments, false)
+    }
+
+    pub fn try_create_desc(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        Self::try_create(display_name, arguments, true)
+    }
+
+    fn ordering(&self) -> Ordering {
+        match self.desc {
+            true => Ordering::Greater,
+            false => Ordering::Less,
+        }
+    }
+
+    fn limit(&self, columns: &[DataColumn]) -> Result<Option<u64>> {
+        let limit = match columns.get(2) {
+            None => return Ok(None),
+            Some(DataColumn::Constant(value, _)) if !value.is_null() => value.as_i64()?,
+            Some(_) => -1,
+        };
+
+        if limit < 0 {
+            return Err(ErrorCode::BadArguments(format!(
+                "The limit of {} must be a constant non-negative integer",
+                self.display_name
+            )));
+        }
+        Ok(Some(limit as u64))
+    }
+}
+
+impl AggregateFunction for AggregateGroupArraySortedFunction {
+    fn name(&self) -> &str {
+        "AggregateGroupArraySortedFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::List(Box::new(DataField::new(
+            "item",
+            self.arguments[0].data_type().clone(),
+            true,
+        ))))
+    }
+
+    // The result of no rows is an empty list.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateGroupArraySortedState {
+            limit: None,
+            entries: vec![],
+        });
+        (state as *mut AggregateGroupArraySortedState) as StateAddr
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateGroupArraySortedState::get(place);
+        state.limit = self.limit(columns)?;
+
+        let value = columns[0].try_get(row)?;
+        let key = columns[1].try_get(row)?;
+        if value.is_null() || key.is_null() {
+            return Ok(());
+        }
+        state.insert(key, value, self.ordering())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateGroupArraySortedState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateGroupArraySortedState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateGroupArraySortedState::get(place);
+        let rhs = AggregateGroupArraySortedState::get(rhs);
+
+        // The state of no rows has not seen the limit yet.
+        state.limit = state.limit.or(rhs.limit);
+        for (key, value) in rhs.entries.iter() {
+            state.insert(key.clone(), value.clone(), self.ordering())?;
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateGroupArraySortedState::get(place);
+        let values = state
+            .entries
+            .iter()
+            .map(|(_, value)| value.clone())
+            .collect();
+        Ok(DataValue::List(
+            Some(values),
+            self.arguments[0].data_type().clone(),
+        ))
+    }
+}
+
+impl fmt::Display for AggregateGroupArraySortedFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
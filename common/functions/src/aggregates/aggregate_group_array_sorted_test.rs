@@ -0,0 +1,124 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+fn events(values: &[&str]) -> DataValue {
+    let values = values
+        .iter()
+        .map(|v| DataValue::Utf8(Some(v.to_string())))
+        .collect();
+    DataValue::List(Some(values), DataType::Utf8)
+}
+
+#[test]
+fn test_aggregate_group_array_sorted_function() -> Result<()> {
+    let arena = Bump::new();
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec![
+            Some("b"),
+            Some("d"),
+            None,
+            Some("a"),
+            Some("c"),
+            Some("e"),
+        ])
+        .into(),
+        Series::new(vec![Some(2_i64), Some(4), Some(1), Some(1), Some(2), None]).into(),
+        DataColumn::Constant(DataValue::UInt8(Some(3)), 6),
+    ];
+
+    struct Test {
+        name: &'static str,
+        args: usize,
+        expect: DataValue,
+    }
+
+    let tests = vec![
+        Test {
+            name: "groupArraySorted",
+            args: 2,
+            expect: events(&["a", "b", "c", "d"]),
+        },
+        Test {
+            name: "groupArraySorted",
+            args: 3,
+            expect: events(&["a", "b", "c"]),
+        },
+        Test {
+            name: "groupArraySortedDesc",
+            args: 3,
+            expect: events(&["d", "b", "c"]),
+        },
+    ];
+
+    for t in tests {
+        let args = vec![
+            DataField::new("x", DataType::Utf8, true),
+            DataField::new("key", DataType::Int64, true),
+            DataField::new("n", DataType::UInt8, false),
+        ];
+        let name = format!("{} of {} arguments", t.name, t.args);
+        let func = AggregateFunctionFactory::get(t.name, args[..t.args].to_vec())?;
+        assert_eq!(
+            DataType::List(Box::new(DataField::new("item", DataType::Utf8, true))),
+            func.return_type()?,
+            "{}",
+            name
+        );
+
+        let place1 = func.allocate_state(&arena);
+        func.accumulate(place1, &columns[..t.args], 6)?;
+        assert_eq!(t.expect, func.merge_result(place1)?, "{}", name);
+
+        // The rows are split into two states, which are merged after the serialization, and
+        // the state of no rows is an empty list.
+        let place2 = func.allocate_state(&arena);
+        assert_eq!(events(&[]), func.merge_result(place2)?, "{}", name);
+        let place3 = func.allocate_state(&arena);
+        for row in 0..6 {
+            let place = if row % 2 == 0 { place2 } else { place3 };
+            func.accumulate_row(place, row, &columns[..t.args])?;
+        }
+
+        let mut state = vec![];
+        func.serialize_state(place3, &mut state)?;
+        let place4 = func.allocate_state(&arena);
+        func.deserialize_state(place4, &state)?;
+        func.merge(place2, place4)?;
+        assert_eq!(t.expect, func.merge_result(place2)?, "{}", name);
+    }
+
+    let args = vec![
+        DataField::new("x", DataType::Boolean, false),
+        DataField::new("key", DataType::Int64, false),
+    ];
+    let result = AggregateFunctionFactory::get("groupArraySorted", args);
+    assert_eq!(
+        "Code: 6, displayText = groupArraySorted does not support Boolean type values.",
+        result.err().unwrap().to_string()
+    );
+
+    let func = AggregateFunctionFactory::get("groupArraySorted", vec![
+        DataField::new("x", DataType::Utf8, false),
+        DataField::new("key", DataType::Int64, false),
+        DataField::new("n", DataType::UInt8, false),
+    ])?;
+    let place = func.allocate_state(&arena);
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec!["a"]).into(),
+        Series::new(vec![1_i64]).into(),
+        Series::new(vec![1_u8]).into(),
+    ];
+    assert_eq!(
+        "Code: 6, displayText = The limit of groupArraySorted must be a constant non-negative integer.",
+        func.accumulate(place, &columns, 1).unwrap_err().to_string()
+    );
+    Ok(())
+}
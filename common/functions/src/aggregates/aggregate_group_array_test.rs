@@ -0,0 +1,100 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+fn values(values: &[i64]) -> DataValue {
+    let values = values.iter().map(|v| DataValue::Int64(Some(*v))).collect();
+    DataValue::List(Some(values), DataType::Int64)
+}
+
+#[test]
+fn test_aggregate_group_array_function() -> Result<()> {
+    let arena = Bump::new();
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec![Some(4_i64), None, Some(3), Some(1), Some(2), Some(5)]).into(),
+        DataColumn::Constant(DataValue::UInt8(Some(3)), 6),
+    ];
+
+    struct Test {
+        args: usize,
+        expect: DataValue,
+        // The rows of the even states followed by the rows of the odd states.
+        merged: DataValue,
+    }
+
+    let tests = vec![
+        Test {
+            args: 1,
+            expect: values(&[4, 3, 1, 2, 5]),
+            merged: values(&[4, 3, 2, 1, 5]),
+        },
+        Test {
+            args: 2,
+            expect: values(&[4, 3, 1]),
+            merged: values(&[4, 3, 2]),
+        },
+    ];
+
+    for t in tests {
+        let args = vec![
+            DataField::new("x", DataType::Int64, true),
+            DataField::new("n", DataType::UInt8, false),
+        ];
+        let name = format!("groupArray of {} arguments", t.args);
+        let func = AggregateFunctionFactory::get("groupArray", args[..t.args].to_vec())?;
+        assert_eq!(
+            DataType::List(Box::new(DataField::new("item", DataType::Int64, true))),
+            func.return_type()?,
+            "{}",
+            name
+        );
+
+        let place1 = func.allocate_state(&arena);
+        func.accumulate(place1, &columns[..t.args], 6)?;
+        assert_eq!(t.expect, func.merge_result(place1)?, "{}", name);
+
+        // The rows are split into two states, which are merged after the serialization, and
+        // the state of no rows is an empty list.
+        let place2 = func.allocate_state(&arena);
+        assert_eq!(values(&[]), func.merge_result(place2)?, "{}", name);
+        let place3 = func.allocate_state(&arena);
+        for row in 0..6 {
+            let place = if row % 2 == 0 { place2 } else { place3 };
+            func.accumulate_row(place, row, &columns[..t.args])?;
+        }
+
+        let mut state = vec![];
+        func.serialize_state(place3, &mut state)?;
+        let place4 = func.allocate_state(&arena);
+        func.deserialize_state(place4, &state)?;
+        func.merge(place2, place4)?;
+        assert_eq!(t.merged, func.merge_result(place2)?, "{}", name);
+    }
+
+    let result = AggregateFunctionFactory::get("groupArray", vec![DataField::new(
+        "x",
+        DataType::Boolean,
+        false,
+    )]);
+    assert_eq!(
+        "Code: 6, displayText = groupArray does not support Boolean type values.",
+        result.err().unwrap().to_string()
+    );
+
+    let result = AggregateFunctionFactory::get("groupArray", vec![
+        DataField::new("x", DataType::Int64, false),
+        DataField::new("n", DataType::Utf8, false),
+    ]);
+    assert_eq!(
+        "Code: 6, displayText = groupArray expects the limit of integer, but got Utf8.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
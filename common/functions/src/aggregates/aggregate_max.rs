@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
+use std::cmp::Ordering;
 use std::fmt;
 
 use common_datavalues::prelude::*;
@@ -44,8 +45,9 @@ impl AggregateFunction for AggregateMaxFunction {
         Ok(self.arguments[0].data_type().clone())
     }
 
+    // The result of no rows or only NULL values is NULL.
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
-        Ok(false)
+        Ok(true)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -68,7 +70,7 @@ impl AggregateFunction for AggregateMaxFunction {
     ) -> Result<()> {
         let state = AggregateSingeValueState::get(place);
         let value = Self::max_batch(&columns[0])?;
-        state.value = DataValue::agg(Max, state.value.clone(), value)?;
+        state.update(value, Ordering::Greater)?;
         Ok(())
     }
 
@@ -76,7 +78,22 @@ impl AggregateFunction for AggregateMaxFunction {
         let state = AggregateSingeValueState::get(place);
         let value = columns[0].try_get(row)?;
 
-        state.value = DataValue::agg(Max, state.value.clone(), value)?;
+        state.update(value, Ordering::Greater)?;
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let values = columns[0].to_values()?;
+        for (value, offset) in values.into_iter().zip(offsets.iter()) {
+            let state = AggregateSingeValueState::get(places[*offset]);
+            state.update(value, Ordering::Greater)?;
+        }
         Ok(())
     }
 
@@ -94,7 +111,7 @@ impl AggregateFunction for AggregateMaxFunction {
         let state = AggregateSingeValueState::get(place);
         let rhs = AggregateSingeValueState::get(rhs);
 
-        state.value = DataValue::agg(Max, state.value.clone(), rhs.value.clone())?;
+        state.update(rhs.value.clone(), Ordering::Greater)?;
         Ok(())
     }
 
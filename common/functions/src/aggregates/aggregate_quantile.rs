@@ -0,0 +1,372 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+use std::io::Cursor;
+
+use bytes::Buf;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::GetState;
+use crate::aggregates::StateAddr;
+
+/// The compression of the t-digest, the digest keeps about this many centroids, and the error
+/// of a quantile is about 1% of the values at the median, and less towards the tails.
+const COMPRESSION: f64 = 100.0;
+
+/// The values buffered before they are merged into the centroids.
+const BUFFER_SIZE: usize = 500;
+
+/// A t-digest: the values are clustered into centroids of their mean and their number, the
+/// centroids near the tails hold fewer values, so the extreme quantiles are more accurate. The
+/// digests of the states are merged by merging their centroids.
+#[derive(Default)]
+pub struct TDigest {
+    /// The centroids of (mean, weight) sorted by the means.
+    centroids: Vec<(f64, f64)>,
+    /// The centroids not merged yet.
+    buffer: Vec<(f64, f64)>,
+    min: f64,
+    max: f64,
+}
+
+impl TDigest {
+    fn is_empty(&self) -> bool {
+        self.centroids.is_empty() && self.buffer.is_empty()
+    }
+
+    fn add(&mut self, value: f64) {
+        if value.is_nan() {
+            return;
+        }
+        self.add_centroid(value, 1.0, value, value);
+    }
+
+    fn add_centroid(&mut self, mean: f64, weight: f64, min: f64, max: f64) {
+        if self.is_empty() {
+            self.min = min;
+            self.max = max;
+        }
+        self.min = self.min.min(min);
+        self.max = self.max.max(max);
+        self.buffer.push((mean, weight));
+        if self.buffer.len() >= BUFFER_SIZE {
+            self.compress();
+        }
+    }
+
+    fn merge(&mut self, rhs: &TDigest) {
+        for (mean, weight) in rhs.centroids.iter().chain(rhs.buffer.iter()) {
+            self.add_centroid(*mean, *weight, rhs.min, rhs.max);
+        }
+    }
+
+    // Merges the buffer into the centroids, the adjacent centroids are merged while the merged
+    // centroid spans at most one unit of the scale function k(q) = c / 2π * asin(2q - 1).
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+
+        let mut centroids = std::mem::take(&mut self.centroids);
+        centroids.append(&mut self.buffer);
+        centroids.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        let total = centroids.iter().map(|(_, weight)| weight).sum::<f64>();
+        let k = |q: f64| COMPRESSION / (2.0 * std::f64::consts::PI) * (2.0 * q - 1.0).asin();
+        let mut merged = Vec::with_capacity(COMPRESSION as usize * 2);
+        let mut current = centroids[0];
+        let mut weight_before = 0.0;
+        for centroid in centroids.into_iter().skip(1) {
+            let q_left = weight_before / total;
+            let q_right = ((weight_before + current.1 + centroid.1) / total).min(1.0);
+            if k(q_right) - k(q_left) <= 1.0 {
+                let weight = current.1 + centroid.1;
+                current.0 += (centroid.0 - current.0) * centroid.1 / weight;
+                current.1 = weight;
+            } else {
+                weight_before += current.1;
+                merged.push(current);
+                current = centroid;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// The quantile of the level in [0, 1] interpolated between the centers of the centroids,
+    /// it is exact if every centroid holds one value.
+    fn quantile(&mut self, level: f64) -> Option<f64> {
+        self.compress();
+        let centroids = &self.centroids;
+        let (first, last) = match (centroids.first(), centroids.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return None,
+        };
+
+        let total = centroids.iter().map(|(_, weight)| weight).sum::<f64>();
+        let target = level * total;
+        if target <= first.1 / 2.0 {
+            let ratio = if first.1 > 1.0 {
+                target / (first.1 / 2.0)
+            } else {
+                1.0
+            };
+            return Some(self.min + (first.0 - self.min) * ratio);
+        }
+        if target >= total - last.1 / 2.0 {
+            let ratio = if last.1 > 1.0 {
+                (total - target) / (last.1 / 2.0)
+            } else {
+                1.0
+            };
+            return Some(self.max - (self.max - last.0) * ratio);
+        }
+
+        let mut center = first.1 / 2.0;
+        for pair in centroids.windows(2) {
+            let next_center = center + (pair[0].1 + pair[1].1) / 2.0;
+            if target <= next_center {
+                let ratio = (target - center) / (next_center - center);
+                return Some(pair[0].0 + (pair[1].0 - pair[0].0) * ratio);
+            }
+            center = next_center;
+        }
+        Some(last.0)
+    }
+}
+
+/// The levels of the quantiles and the digest of the values.
+pub struct AggregateQuantileState {
+    levels: Vec<f64>,
+    digest: TDigest,
+}
+
+impl<'a> GetState<'a, AggregateQuantileState> for AggregateQuantileState {}
+
+impl AggregateQuantileState {
+    /// The levels, the min, the max and the centroids in JSON.
+    pub fn serialize(&mut self, writer: &mut Vec<u8>) -> Result<()> {
+        self.digest.compress();
+        let digest = &self.digest;
+        serde_json::to_writer(
+            writer,
+            &(&self.levels, digest.min, digest.max, &digest.centroids),
+        )?;
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let reader = Cursor::new(reader).reader();
+        let (levels, min, max, centroids) = serde_json::from_reader(reader)?;
+        self.levels = levels;
+        self.digest = TDigest {
+            centroids,
+            buffer: vec![],
+            min,
+            max,
+        };
+        Ok(())
+    }
+}
+
+/// `quantile(level, x)` returns the approximate quantile of the non-null values of x by a
+/// t-digest, `median(x)` is `quantile(0.5, x)`, and `quantiles(level1, level2, ..., x)` returns
+/// the list of the quantiles of the levels. A level is a constant number in [0, 1], it is an
+/// argument instead of a parameter as in `quantile(level)(x)` of ClickHouse.
+///
+/// The quantiles are exact for the small sets, the digest of a state is about a hundred
+/// centroids whatever the number of the values, so the states of a cluster are cheap to merge.
+#[derive(Clone)]
+pub struct AggregateQuantileFunction {
+    display_name: String,
+    // The arguments before x which are the levels, the median has none.
+    level_args: usize,
+    // Whether the result is the list of the quantiles.
+    returns_list: bool,
+}
+
+impl AggregateQuantileFunction {
+    pub fn try_create_quantile(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_variadic_arguments(display_name, arguments.len(), (2, 2))?;
+        Self::try_create(display_name, arguments, false)
+    }
+
+    pub fn try_create_median(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_variadic_arguments(display_name, arguments.len(), (1, 1))?;
+        Self::try_create(display_name, arguments, false)
+    }
+
+    pub fn try_create_quantiles(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_variadic_arguments(display_name, arguments.len(), (2, 32))?;
+        Self::try_create(display_name, arguments, true)
+    }
+
+    fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        returns_list: bool,
+    ) -> Result<AggregateFunctionRef> {
+        let level_args = arguments.len() - 1;
+        for argument in &arguments[..level_args] {
+            if !is_numeric(argument.data_type()) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "{} expects the level of number, but got {}",
+                    display_name,
+                    argument.data_type()
+                )));
+            }
+        }
+
+        let value_type = arguments[level_args].data_type();
+        if !is_numeric(value_type) {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} does not support {} type values",
+                display_name, value_type
+            )));
+        }
+
+        Ok(Arc::new(AggregateQuantileFunction {
+            display_name: display_name.to_string(),
+            level_args,
+            returns_list,
+        }))
+    }
+
+    fn levels(&self, columns: &[DataColumn]) -> Result<Vec<f64>> {
+        if self.level_args == 0 {
+            return Ok(vec![0.5]);
+        }
+
+        columns[..self.level_args]
+            .iter()
+            .map(|column| {
+                let level = match column {
+                    DataColumn::Constant(value, _) => Self::value_as_f64(value)?,
+                    DataColumn::Array(_) => None,
+                };
+                match level {
+                    Some(level) if (0.0..=1.0).contains(&level) => Ok(level),
+                    _ => Err(ErrorCode::BadArguments(format!(
+                        "The level of {} must be a constant number in [0, 1]",
+                        self.display_name
+                    ))),
+                }
+            })
+            .collect()
+    }
+
+    fn value_as_f64(value: &DataValue) -> Result<Option<f64>> {
+        match value {
+            value if value.is_null() => Ok(None),
+            DataValue::Float32(Some(v)) => Ok(Some(*v as f64)),
+            DataValue::Float64(Some(v)) => Ok(Some(*v)),
+            DataValue::UInt64(Some(v)) => Ok(Some(*v as f64)),
+            value => Ok(Some(value.as_i64()? as f64)),
+        }
+    }
+}
+
+impl AggregateFunction for AggregateQuantileFunction {
+    fn name(&self) -> &str {
+        "AggregateQuantileFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        match self.returns_list {
+            true => Ok(DataType::List(Box::new(DataField::new(
+                "item",
+                DataType::Float64,
+                true,
+            )))),
+            false => Ok(DataType::Float64),
+        }
+    }
+
+    // The quantile of no rows is NULL, the quantiles of no rows are an empty list.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(!self.returns_list)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateQuantileState {
+            levels: vec![],
+            digest: TDigest::default(),
+        });
+        (state as *mut AggregateQuantileState) as StateAddr
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateQuantileState::get(place);
+        if state.levels.is_empty() {
+            state.levels = self.levels(columns)?;
+        }
+
+        let value = columns[self.level_args].try_get(row)?;
+        if let Some(value) = Self::value_as_f64(&value)? {
+            state.digest.add(value);
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateQuantileState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateQuantileState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateQuantileState::get(place);
+        let rhs = AggregateQuantileState::get(rhs);
+
+        // The state of no rows has not seen the levels yet.
+        if state.levels.is_empty() {
+            state.levels = rhs.levels.clone();
+        }
+        state.digest.merge(&rhs.digest);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateQuantileState::get(place);
+        let mut quantiles = Vec::with_capacity(state.levels.len());
+        for level in state.levels.iter() {
+            quantiles.push(DataValue::Float64(state.digest.quantile(*level)));
+        }
+
+        match self.returns_list {
+            true => Ok(DataValue::List(Some(quantiles), DataType::Float64)),
+            false => Ok(quantiles.pop().unwrap_or(DataValue::Float64(None))),
+        }
+    }
+}
+
+impl fmt::Display for AggregateQuantileFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+fn quantiles(values: &[f64]) -> DataValue {
+    let values = values
+        .iter()
+        .map(|v| DataValue::Float64(Some(*v)))
+        .collect();
+    DataValue::List(Some(values), DataType::Float64)
+}
+
+fn level(level: f64, rows: usize) -> DataColumn {
+    DataColumn::Constant(DataValue::Float64(Some(level)), rows)
+}
+
+#[test]
+fn test_aggregate_quantile_function() -> Result<()> {
+    let arena = Bump::new();
+    let x: DataColumn =
+        Series::new(vec![Some(4_i64), None, Some(3), Some(1), Some(2), Some(5)]).into();
+
+    struct Test {
+        name: &'static str,
+        columns: Vec<DataColumn>,
+        expect: DataValue,
+        empty: DataValue,
+    }
+
+    let tests = vec![
+        Test {
+            name: "quantile",
+            columns: vec![level(0.25, 6), x.clone()],
+            expect: DataValue::Float64(Some(1.75)),
+            empty: DataValue::Float64(None),
+        },
+        Test {
+            name: "median",
+            columns: vec![x.clone()],
+            expect: DataValue::Float64(Some(3.0)),
+            empty: DataValue::Float64(None),
+        },
+        Test {
+            name: "quantiles",
+            columns: vec![level(0.0, 6), level(0.5, 6), level(1.0, 6), x.clone()],
+            expect: quantiles(&[1.0, 3.0, 5.0]),
+            empty: quantiles(&[]),
+        },
+    ];
+
+    for t in tests {
+        let mut args = vec![DataField::new("level", DataType::Float64, false); t.columns.len()];
+        *args.last_mut().unwrap() = DataField::new("x", DataType::Int64, true);
+        let func = AggregateFunctionFactory::get(t.name, args)?;
+
+        let place1 = func.allocate_state(&arena);
+        func.accumulate(place1, &t.columns, 6)?;
+        assert_eq!(t.expect, func.merge_result(place1)?, "{}", t.name);
+
+        // The rows are split into two states, which are merged after the serialization.
+        let place2 = func.allocate_state(&arena);
+        assert_eq!(t.empty, func.merge_result(place2)?, "{}", t.name);
+        let place3 = func.allocate_state(&arena);
+        for row in 0..6 {
+            let place = if row % 2 == 0 { place2 } else { place3 };
+            func.accumulate_row(place, row, &t.columns)?;
+        }
+
+        let mut state = vec![];
+        func.serialize_state(place3, &mut state)?;
+        let place4 = func.allocate_state(&arena);
+        func.deserialize_state(place4, &state)?;
+        func.merge(place2, place4)?;
+        assert_eq!(t.expect, func.merge_result(place2)?, "{}", t.name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_quantile_accuracy() -> Result<()> {
+    let arena = Bump::new();
+    let rows = 100000;
+    let levels = [0.0, 0.01, 0.25, 0.5, 0.9, 0.99, 1.0];
+    let mut columns: Vec<DataColumn> = levels.iter().map(|l| level(*l, rows / 2)).collect();
+    let mut args = vec![DataField::new("level", DataType::Float64, false); levels.len()];
+    args.push(DataField::new("x", DataType::UInt64, false));
+    let func = AggregateFunctionFactory::get("quantiles", args)?;
+
+    // The values of 0..rows in a shuffled order, split into two states.
+    let values = (0..rows as u64)
+        .map(|v| v * 7919 % rows as u64)
+        .collect::<Vec<_>>();
+    let place1 = func.allocate_state(&arena);
+    let place2 = func.allocate_state(&arena);
+    for (place, values) in [place1, place2].iter().zip(values.chunks(rows / 2)) {
+        columns.push(Series::new(values.to_vec()).into());
+        func.accumulate(*place, &columns, rows / 2)?;
+        columns.pop();
+    }
+
+    let mut state = vec![];
+    func.serialize_state(place2, &mut state)?;
+    let place3 = func.allocate_state(&arena);
+    func.deserialize_state(place3, &state)?;
+    func.merge(place1, place3)?;
+
+    let result = match func.merge_result(place1)? {
+        DataValue::List(Some(values), _) => values,
+        other => panic!("quantiles returns a list, but got {:?}", other),
+    };
+    assert_eq!(levels.len(), result.len());
+    for (level, value) in levels.iter().zip(result.iter()) {
+        let value = match value {
+            DataValue::Float64(Some(v)) => *v,
+            other => panic!("quantile returns a Float64, but got {:?}", other),
+        };
+        // The error is within 0.5% of the rows.
+        let expect = level * (rows - 1) as f64;
+        assert!(
+            (value - expect).abs() <= rows as f64 * 0.005,
+            "The quantile of {} is {}, but the exact one is {}",
+            level,
+            value,
+            expect
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_quantile_arguments() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![
+        DataField::new("level", DataType::Float64, false),
+        DataField::new("x", DataType::Int64, false),
+    ];
+    let func = AggregateFunctionFactory::get("quantile", args)?;
+    let place = func.allocate_state(&arena);
+    let x: DataColumn = Series::new(vec![1_i64, 2]).into();
+    let result = func.accumulate(place, &[level(1.5, 2), x], 2);
+    assert_eq!(
+        "Code: 6, displayText = The level of quantile must be a constant number in [0, 1].",
+        result.err().unwrap().to_string()
+    );
+
+    let result =
+        AggregateFunctionFactory::get("median", vec![DataField::new("x", DataType::Utf8, false)]);
+    assert_eq!(
+        "Code: 6, displayText = median does not support Utf8 type values.",
+        result.err().unwrap().to_string()
+    );
+
+    let result = AggregateFunctionFactory::get("quantiles", vec![
+        DataField::new("level", DataType::Utf8, false),
+        DataField::new("x", DataType::Int64, false),
+    ]);
+    assert_eq!(
+        "Code: 6, displayText = quantiles expects the level of number, but got Utf8.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
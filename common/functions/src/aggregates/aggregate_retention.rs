@@ -0,0 +1,169 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::convert::TryInto;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::GetState;
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+
+/// The max number of the conditions, each of them is a bit of the state.
+const MAX_EVENTS: usize = 32;
+
+/// The bit i is set if the condition i is true for any row.
+pub struct AggregateRetentionState {
+    events: u32,
+}
+
+impl<'a> GetState<'a, AggregateRetentionState> for AggregateRetentionState {}
+
+impl AggregateRetentionState {
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        writer.extend_from_slice(&self.events.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let bytes: [u8; 4] = reader.try_into().map_err(|_| {
+            ErrorCode::BadBytes(format!(
+                "Cannot deserialize the state of retention: {:?}",
+                reader
+            ))
+        })?;
+        self.events = u32::from_le_bytes(bytes);
+        Ok(())
+    }
+}
+
+/// `retention(cond1, cond2, ..., condN)` returns the list of N UInt8, the first one is whether
+/// cond1 is true for any row, the i-th one is whether both cond1 and condi are true for any rows.
+#[derive(Clone)]
+pub struct AggregateRetentionFunction {
+    display_name: String,
+    events_size: usize,
+}
+
+impl AggregateRetentionFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        assert_variadic_arguments(display_name, arguments.len(), (1, MAX_EVENTS))?;
+
+        for argument in arguments.iter() {
+            if argument.data_type() != &DataType::Boolean {
+                return Err(ErrorCode::BadArguments(format!(
+                    "{} does not support {} type parameters",
+                    display_name,
+                    argument.data_type()
+                )));
+            }
+        }
+
+        Ok(Arc::new(AggregateRetentionFunction {
+            display_name: display_name.to_string(),
+            events_size: arguments.len(),
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateRetentionFunction {
+    fn name(&self) -> &str {
+        "AggregateRetentionFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::List(Box::new(DataField::new(
+            "item",
+            DataType::UInt8,
+            true,
+        ))))
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateRetentionState { events: 0 });
+        (state as *mut AggregateRetentionState) as StateAddr
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = AggregateRetentionState::get(place);
+        for (i, column) in columns.iter().enumerate() {
+            let matched = match column {
+                DataColumn::Constant(value, size) => {
+                    *size > 0 && value == &DataValue::Boolean(Some(true))
+                }
+                DataColumn::Array(array) => array.bool()?.downcast_iter().any(|v| v == Some(true)),
+            };
+            if matched {
+                state.events |= 1 << i;
+            }
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateRetentionState::get(place);
+        for (i, column) in columns.iter().enumerate() {
+            if column.try_get(row)? == DataValue::Boolean(Some(true)) {
+                state.events |= 1 << i;
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateRetentionState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateRetentionState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateRetentionState::get(place);
+        let rhs = AggregateRetentionState::get(rhs);
+        state.events |= rhs.events;
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateRetentionState::get(place);
+        let first = state.events & 1 == 1;
+        let values = (0..self.events_size)
+            .map(|i| {
+                let retained = first && state.events & (1 << i) != 0;
+                DataValue::UInt8(Some(retained as u8))
+            })
+            .collect();
+        Ok(DataValue::List(Some(values), DataType::UInt8))
+    }
+}
+
+impl fmt::Display for AggregateRetentionFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
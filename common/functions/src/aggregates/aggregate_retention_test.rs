@@ -0,0 +1,131 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+fn retained(values: &[u8]) -> DataValue {
+    let values = values.iter().map(|v| DataValue::UInt8(Some(*v))).collect();
+    DataValue::List(Some(values), DataType::UInt8)
+}
+
+#[test]
+fn test_aggregate_retention_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![
+        DataField::new("a", DataType::Boolean, false),
+        DataField::new("b", DataType::Boolean, false),
+        DataField::new("c", DataType::Boolean, false),
+    ];
+    let func = AggregateFunctionFactory::get("retention", args)?;
+    assert_eq!(
+        DataType::List(Box::new(DataField::new("item", DataType::UInt8, true))),
+        func.return_type()?
+    );
+
+    // The first event happens, so does the second one, but the third one never happens.
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec![true, false, false]).into(),
+        Series::new(vec![false, false, true]).into(),
+        DataColumn::Constant(DataValue::Boolean(Some(false)), 3),
+    ];
+    let place1 = func.allocate_state(&arena);
+    func.accumulate(place1, &columns, 3)?;
+    assert_eq!(retained(&[1, 1, 0]), func.merge_result(place1)?);
+
+    // Without the first event, nothing is retained.
+    let place2 = func.allocate_state(&arena);
+    func.accumulate_row(place2, 2, &columns)?;
+    assert_eq!(retained(&[0, 0, 0]), func.merge_result(place2)?);
+
+    // The states are merged after the serialization.
+    let place3 = func.allocate_state(&arena);
+    let columns: Vec<DataColumn> = vec![
+        Series::new(vec![false]).into(),
+        Series::new(vec![false]).into(),
+        Series::new(vec![true]).into(),
+    ];
+    func.accumulate_row(place3, 0, &columns)?;
+    let mut state = vec![];
+    func.serialize_state(place3, &mut state)?;
+    let place4 = func.allocate_state(&arena);
+    func.deserialize_state(place4, &state)?;
+    func.merge(place1, place4)?;
+    assert_eq!(retained(&[1, 1, 1]), func.merge_result(place1)?);
+
+    let result = AggregateFunctionFactory::get("retention", vec![DataField::new(
+        "a",
+        DataType::UInt64,
+        false,
+    )]);
+    assert_eq!(
+        "Code: 6, displayText = retention does not support UInt64 type parameters.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_uniq_up_to_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![
+        DataField::new("n", DataType::UInt8, false),
+        DataField::new("a", DataType::Int64, true),
+    ];
+    let func = AggregateFunctionFactory::get("uniqUpTo", args)?;
+    assert_eq!(DataType::UInt64, func.return_type()?);
+
+    let values: DataColumn = Series::new(vec![Some(1i64), Some(2), None, Some(2), Some(3)]).into();
+    let threshold = |n: u8| DataColumn::Constant(DataValue::UInt8(Some(n)), 5);
+
+    // Not greater than the threshold.
+    let place = func.allocate_state(&arena);
+    func.accumulate(place, &[threshold(3), values.clone()], 5)?;
+    assert_eq!(DataValue::UInt64(Some(3)), func.merge_result(place)?);
+
+    // Greater than the threshold.
+    let place1 = func.allocate_state(&arena);
+    func.accumulate(place1, &[threshold(1), values.clone()], 5)?;
+    assert_eq!(DataValue::UInt64(Some(2)), func.merge_result(place1)?);
+
+    // The threshold is kept in the state, an empty state has none yet.
+    let place2 = func.allocate_state(&arena);
+    func.accumulate_row(place2, 4, &[threshold(1), values.clone()])?;
+    let mut state = vec![];
+    func.serialize_state(place2, &mut state)?;
+    let place3 = func.allocate_state(&arena);
+    func.deserialize_state(place3, &state)?;
+    let empty = func.allocate_state(&arena);
+    func.merge(empty, place3)?;
+    assert_eq!(DataValue::UInt64(Some(1)), func.merge_result(empty)?);
+    func.merge(empty, place1)?;
+    assert_eq!(DataValue::UInt64(Some(2)), func.merge_result(empty)?);
+
+    assert_eq!(
+        "Code: 6, displayText = The threshold of uniqUpTo must be a constant integer in [0, 100].",
+        func.accumulate(place, &[threshold(101), values.clone()], 5)
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        "Code: 6, displayText = The threshold of uniqUpTo must be a constant integer in [0, 100].",
+        func.accumulate(place, &[values.clone(), values], 5)
+            .unwrap_err()
+            .to_string()
+    );
+
+    let result = AggregateFunctionFactory::get("uniqUpTo", vec![
+        DataField::new("n", DataType::Float64, false),
+        DataField::new("a", DataType::Int64, false),
+    ]);
+    assert_eq!(
+        "Code: 6, displayText = uniqUpTo expects the threshold of integer, but got Float64.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
@@ -0,0 +1,333 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::aggregates::aggregator_common::assert_binary_arguments;
+use crate::aggregates::aggregator_common::assert_unary_arguments;
+use crate::aggregates::AggregateFunction;
+use crate::aggregates::AggregateFunctionRef;
+use crate::aggregates::GetState;
+use crate::aggregates::StateAddr;
+use crate::aggregates::StateNative;
+
+/// The statistics of the moments of the state.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Statistic {
+    VarPop,
+    VarSamp,
+    StddevPop,
+    StddevSamp,
+    CovarPop,
+    CovarSamp,
+    Corr,
+}
+
+/// The count, the means and the co-moments of the pairs (x, y) by Welford's algorithm: the sums
+/// of the squared deviations from the means are updated as the means move, so they keep their
+/// precision for the values far from zero, unlike the sums of the squares. The variance of x is
+/// the covariance of the pairs (x, x).
+#[derive(Clone, Copy, Default)]
+pub struct AggregateStatisticsState {
+    count: u64,
+    mean_x: f64,
+    mean_y: f64,
+    m2_x: f64,
+    m2_y: f64,
+    c_xy: f64,
+}
+
+impl<'a> GetState<'a, AggregateStatisticsState> for AggregateStatisticsState {}
+
+impl AggregateStatisticsState {
+    const SIZE: usize = 8 + 5 * f64::SIZE;
+
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        self.count += 1;
+        let count = self.count as f64;
+        let delta_x = x - self.mean_x;
+        self.mean_x += delta_x / count;
+        let delta_y = y - self.mean_y;
+        self.mean_y += delta_y / count;
+        self.m2_x += delta_x * (x - self.mean_x);
+        self.m2_y += delta_y * (y - self.mean_y);
+        self.c_xy += delta_x * (y - self.mean_y);
+    }
+
+    /// Merges the moments of two sets of pairs by the parallel algorithm of Chan et al.
+    pub fn merge(&mut self, rhs: &AggregateStatisticsState) {
+        if rhs.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            *self = *rhs;
+            return;
+        }
+
+        let (lhs_count, rhs_count) = (self.count as f64, rhs.count as f64);
+        let count = lhs_count + rhs_count;
+        let delta_x = rhs.mean_x - self.mean_x;
+        let delta_y = rhs.mean_y - self.mean_y;
+        let weight = lhs_count * rhs_count / count;
+        self.mean_x += delta_x * rhs_count / count;
+        self.mean_y += delta_y * rhs_count / count;
+        self.m2_x += rhs.m2_x + delta_x * delta_x * weight;
+        self.m2_y += rhs.m2_y + delta_y * delta_y * weight;
+        self.c_xy += rhs.c_xy + delta_x * delta_y * weight;
+        self.count += rhs.count;
+    }
+
+    /// The u64 count, then the f64 means and co-moments in little endian.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        self.count.write_to(writer);
+        for value in [self.mean_x, self.mean_y, self.m2_x, self.m2_y, self.c_xy].iter() {
+            value.write_to(writer);
+        }
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        if reader.len() != Self::SIZE {
+            return Err(ErrorCode::BadBytes(format!(
+                "Cannot deserialize the state of statistics: {:?}",
+                reader
+            )));
+        }
+
+        self.count = u64::read_from(reader);
+        let values = reader[8..]
+            .chunks(f64::SIZE)
+            .map(f64::read_from)
+            .collect::<Vec<_>>();
+        self.mean_x = values[0];
+        self.mean_y = values[1];
+        self.m2_x = values[2];
+        self.m2_y = values[3];
+        self.c_xy = values[4];
+        Ok(())
+    }
+
+    fn result(&self, statistic: Statistic) -> Option<f64> {
+        let count = self.count as f64;
+        let population = self.count > 0;
+        let sample = self.count > 1;
+        match statistic {
+            Statistic::VarPop if population => Some(self.m2_x / count),
+            Statistic::VarSamp if sample => Some(self.m2_x / (count - 1.0)),
+            Statistic::StddevPop if population => Some((self.m2_x / count).sqrt()),
+            Statistic::StddevSamp if sample => Some((self.m2_x / (count - 1.0)).sqrt()),
+            Statistic::CovarPop if population => Some(self.c_xy / count),
+            Statistic::CovarSamp if sample => Some(self.c_xy / (count - 1.0)),
+            Statistic::Corr if population && self.m2_x > 0.0 && self.m2_y > 0.0 => {
+                Some(self.c_xy / (self.m2_x * self.m2_y).sqrt())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The variances and the standard deviations of x, the covariances and the correlation of x and
+/// y: `varPop(x)`, `varSamp(x)`, `stddevPop(x)`, `stddevSamp(x)`, `covarPop(x, y)`,
+/// `covarSamp(x, y)` and `corr(x, y)`. The rows where any argument is NULL are skipped.
+///
+/// The state is the Welford moments of a fixed size, which are merged exactly, so the partial
+/// states of the nodes of a cluster are cheap to send and merge.
+#[derive(Clone)]
+pub struct AggregateStatisticsFunction {
+    display_name: String,
+    statistic: Statistic,
+}
+
+impl AggregateStatisticsFunction {
+    pub fn try_create_var_pop(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::VarPop)
+    }
+
+    pub fn try_create_var_samp(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::VarSamp)
+    }
+
+    pub fn try_create_stddev_pop(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::StddevPop)
+    }
+
+    pub fn try_create_stddev_samp(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_unary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::StddevSamp)
+    }
+
+    pub fn try_create_covar_pop(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_binary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::CovarPop)
+    }
+
+    pub fn try_create_covar_samp(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_binary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::CovarSamp)
+    }
+
+    pub fn try_create_corr(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<AggregateFunctionRef> {
+        assert_binary_arguments(display_name, arguments.len())?;
+        Self::try_create(display_name, arguments, Statistic::Corr)
+    }
+
+    fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+        statistic: Statistic,
+    ) -> Result<AggregateFunctionRef> {
+        for argument in &arguments {
+            if !is_numeric(argument.data_type()) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "{} does not support {} type values",
+                    display_name,
+                    argument.data_type()
+                )));
+            }
+        }
+
+        Ok(Arc::new(AggregateStatisticsFunction {
+            display_name: display_name.to_string(),
+            statistic,
+        }))
+    }
+
+    // The pairs of the rows cast to f64, None if any value is NULL, y is x for a single column.
+    fn pairs(columns: &[DataColumn]) -> Result<Vec<Option<(f64, f64)>>> {
+        let xs = f64::column_values(&columns[0])?;
+        if columns.len() == 1 {
+            return Ok(xs.into_iter().map(|x| x.map(|x| (x, x))).collect());
+        }
+
+        let ys = f64::column_values(&columns[1])?;
+        Ok(xs.into_iter().zip(ys).map(|(x, y)| x.zip(y)).collect())
+    }
+}
+
+impl AggregateFunction for AggregateStatisticsFunction {
+    fn name(&self) -> &str {
+        "AggregateStatisticsFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    // The result of no rows is NULL, and of one row for the sample statistics, the correlation
+    // is NULL if x or y is constant.
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateStatisticsState::default());
+        (state as *mut AggregateStatisticsState) as StateAddr
+    }
+
+    fn accumulate(
+        &self,
+        place: StateAddr,
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        let state = AggregateStatisticsState::get(place);
+        for (x, y) in Self::pairs(columns)?.into_iter().flatten() {
+            state.add(x, y);
+        }
+        Ok(())
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateStatisticsState::get(place);
+        let x = f64::from_data_value(&columns[0].try_get(row)?)?;
+        let y = match columns.len() {
+            1 => x,
+            _ => f64::from_data_value(&columns[1].try_get(row)?)?,
+        };
+        if let Some((x, y)) = x.zip(y) {
+            state.add(x, y);
+        }
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        // The columns are cast to f64 once, not per row.
+        let pairs = Self::pairs(columns)?;
+        for (pair, offset) in pairs.into_iter().zip(offsets.iter()) {
+            if let Some((x, y)) = pair {
+                let state = AggregateStatisticsState::get(places[*offset]);
+                state.add(x, y);
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateStatisticsState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateStatisticsState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateStatisticsState::get(place);
+        let rhs = AggregateStatisticsState::get(rhs);
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateStatisticsState::get(place);
+        Ok(DataValue::Float64(state.result(self.statistic)))
+    }
+}
+
+impl fmt::Display for AggregateStatisticsFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
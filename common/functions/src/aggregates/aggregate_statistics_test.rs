@@ -0,0 +1,163 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+fn assert_approx_eq(expect: Option<f64>, actual: DataValue, name: &str) {
+    match (expect, actual) {
+        (None, DataValue::Float64(None)) => {}
+        (Some(expect), DataValue::Float64(Some(actual))) => assert!(
+            (expect - actual).abs() <= 1e-9 * expect.abs().max(1.0),
+            "{}: expect {}, but got {}",
+            name,
+            expect,
+            actual
+        ),
+        (expect, actual) => panic!("{}: expect {:?}, but got {:?}", name, expect, actual),
+    }
+}
+
+#[test]
+fn test_aggregate_statistics_function() -> Result<()> {
+    let arena = Bump::new();
+    let x: DataColumn =
+        Series::new(vec![Some(4_i64), None, Some(3), Some(1), Some(2), Some(5)]).into();
+    let y: DataColumn = Series::new(vec![1.0_f64, 7.0, 2.0, 5.0, 3.0, 0.0]).into();
+
+    struct Test {
+        name: &'static str,
+        columns: Vec<DataColumn>,
+        expect: Option<f64>,
+    }
+
+    // The row where x is NULL is skipped, also by the functions of x and y.
+    let tests = vec![
+        Test {
+            name: "varPop",
+            columns: vec![x.clone()],
+            expect: Some(2.0),
+        },
+        Test {
+            name: "varSamp",
+            columns: vec![x.clone()],
+            expect: Some(2.5),
+        },
+        Test {
+            name: "stddevPop",
+            columns: vec![x.clone()],
+            expect: Some(2.0_f64.sqrt()),
+        },
+        Test {
+            name: "stddev",
+            columns: vec![x.clone()],
+            expect: Some(2.5_f64.sqrt()),
+        },
+        Test {
+            name: "covarPop",
+            columns: vec![x.clone(), y.clone()],
+            expect: Some(-2.4),
+        },
+        Test {
+            name: "covar_samp",
+            columns: vec![x.clone(), y.clone()],
+            expect: Some(-3.0),
+        },
+        Test {
+            name: "corr",
+            columns: vec![x.clone(), y.clone()],
+            expect: Some(-3.0 / (2.5_f64 * 3.7).sqrt()),
+        },
+    ];
+
+    for t in tests {
+        let args = t
+            .columns
+            .iter()
+            .map(|column| DataField::new("x", column.data_type(), true))
+            .collect::<Vec<_>>();
+        let func = AggregateFunctionFactory::get(t.name, args)?;
+
+        let place1 = func.allocate_state(&arena);
+        func.accumulate(place1, &t.columns, 6)?;
+        assert_approx_eq(t.expect, func.merge_result(place1)?, t.name);
+
+        // The rows are split into two states, which are merged after the serialization.
+        let place2 = func.allocate_state(&arena);
+        assert_eq!(DataValue::Float64(None), func.merge_result(place2)?);
+        let place3 = func.allocate_state(&arena);
+        for row in 0..6 {
+            let place = if row % 2 == 0 { place2 } else { place3 };
+            func.accumulate_row(place, row, &t.columns)?;
+        }
+
+        let mut state = vec![];
+        func.serialize_state(place3, &mut state)?;
+        let place4 = func.allocate_state(&arena);
+        func.deserialize_state(place4, &state)?;
+        func.merge(place2, place4)?;
+        assert_approx_eq(t.expect, func.merge_result(place2)?, t.name);
+
+        // The rows of the groups are added to the states of the groups by their offsets.
+        let place5 = func.allocate_state(&arena);
+        let place6 = func.allocate_state(&arena);
+        let offsets = [0, 0, 1, 0, 1, 1];
+        func.accumulate_keys(&[place5, place6], &offsets, &t.columns, 6)?;
+        func.merge(place5, place6)?;
+        assert_approx_eq(t.expect, func.merge_result(place5)?, t.name);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_aggregate_statistics_corner_cases() -> Result<()> {
+    // The squares of the values exceed the precision of f64, but their deviations do not.
+    let arena = Bump::new();
+    let values: Vec<i64> = vec![4, 7, 13, 16]
+        .into_iter()
+        .map(|v| 1_000_000_000 + v)
+        .collect();
+    let x: DataColumn = Series::new(values).into();
+    let args = vec![DataField::new("x", DataType::Int64, false)];
+    let func = AggregateFunctionFactory::get("varSamp", args)?;
+    let place = func.allocate_state(&arena);
+    func.accumulate(place, &[x], 4)?;
+    assert_eq!(DataValue::Float64(Some(30.0)), func.merge_result(place)?);
+
+    // The sample statistics of one row, and the correlation of a constant are NULL.
+    let x: DataColumn = Series::new(vec![1_u64, 1, 1]).into();
+    let args = vec![
+        DataField::new("x", DataType::UInt64, false),
+        DataField::new("y", DataType::UInt64, false),
+    ];
+    let func = AggregateFunctionFactory::get("corr", args)?;
+    let place = func.allocate_state(&arena);
+    func.accumulate(place, &[x.clone(), x.clone()], 3)?;
+    assert_eq!(DataValue::Float64(None), func.merge_result(place)?);
+
+    let args = vec![DataField::new("x", DataType::UInt64, false)];
+    let func = AggregateFunctionFactory::get("stddevSamp", args)?;
+    let place = func.allocate_state(&arena);
+    func.accumulate_row(place, 0, &[x])?;
+    assert_eq!(DataValue::Float64(None), func.merge_result(place)?);
+
+    let result =
+        AggregateFunctionFactory::get("corr", vec![DataField::new("x", DataType::Int64, false)]);
+    assert_eq!(
+        "Code: 28, displayText = corr expect to have two arguments, but got 1.",
+        result.err().unwrap().to_string()
+    );
+
+    let result =
+        AggregateFunctionFactory::get("stddev", vec![DataField::new("x", DataType::Utf8, false)]);
+    assert_eq!(
+        "Code: 6, displayText = stddev does not support Utf8 type values.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
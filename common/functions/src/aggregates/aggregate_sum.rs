@@ -4,8 +4,10 @@
 
 use std::any::Any;
 use std::fmt;
+use std::marker::PhantomData;
 
 use common_datavalues::prelude::*;
+use common_datavalues::DECIMAL_MAX_PRECISION;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -13,16 +15,19 @@ use super::GetState;
 use super::StateAddr;
 use crate::aggregates::aggregator_common::assert_unary_arguments;
 use crate::aggregates::AggregateFunction;
-use crate::aggregates::AggregateSingeValueState;
+use crate::aggregates::AggregateSumState;
+use crate::aggregates::StateNative;
 
+/// SUM of the values, the state is the native of the sum type in the arena.
 #[derive(Clone)]
-pub struct AggregateSumFunction {
+pub struct AggregateSumFunction<T> {
     display_name: String,
     arguments: Vec<DataField>,
     return_type: DataType,
+    _native: PhantomData<T>,
 }
 
-impl AggregateFunction for AggregateSumFunction {
+impl<T: StateNative> AggregateFunction for AggregateSumFunction<T> {
     fn name(&self) -> &str {
         "AggregateSumFunction"
     }
@@ -31,8 +36,9 @@ impl AggregateFunction for AggregateSumFunction {
         Ok(self.return_type.clone())
     }
 
+    // The result of no rows or only NULL values is NULL.
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
-        Ok(false)
+        Ok(true)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -40,11 +46,8 @@ impl AggregateFunction for AggregateSumFunction {
     }
 
     fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
-        let state = arena.alloc(AggregateSingeValueState {
-            value: DataValue::from(&self.return_type),
-        });
-
-        (state as *mut AggregateSingeValueState) as StateAddr
+        let state = arena.alloc(AggregateSumState::<T> { value: None });
+        (state as *mut AggregateSumState<T>) as StateAddr
     }
 
     fn accumulate(
@@ -53,94 +56,117 @@ impl AggregateFunction for AggregateSumFunction {
         columns: &[DataColumn],
         _input_rows: usize,
     ) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let value = Self::sum_batch(&columns[0])?;
-        state.value = (&state.value + &value)?;
+        let state = AggregateSumState::<T>::get(place);
+        let value = sum_batch(&columns[0])?;
+        state.add(T::from_data_value(&value)?);
         Ok(())
     }
 
     fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let value = columns[0].try_get(row)?;
-        state.value = (&state.value + &value)?;
+        let state = AggregateSumState::<T>::get(place);
+        state.add(T::from_data_value(&columns[0].try_get(row)?)?);
+        Ok(())
+    }
+
+    fn accumulate_keys(
+        &self,
+        places: &[StateAddr],
+        offsets: &[usize],
+        columns: &[DataColumn],
+        _input_rows: usize,
+    ) -> Result<()> {
+        // The column is cast to the native of the state once, not per row.
+        let values = T::column_values(&columns[0])?;
+        for (value, offset) in values.into_iter().zip(offsets.iter()) {
+            let state = AggregateSumState::<T>::get(places[*offset]);
+            state.add(value);
+        }
         Ok(())
     }
 
     fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateSumState::<T>::get(place);
         state.serialize(writer)
     }
 
     fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
+        let state = AggregateSumState::<T>::get(place);
         state.deserialize(reader)
     }
 
     fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
-        let state = AggregateSingeValueState::get(place);
-        let rhs = AggregateSingeValueState::get(rhs);
-
-        state.value = (&state.value + &rhs.value)?;
+        let state = AggregateSumState::<T>::get(place);
+        let rhs = AggregateSumState::<T>::get(rhs);
+        state.add(rhs.value);
         Ok(())
     }
 
     fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
-        let state = AggregateSingeValueState::get(place);
-
-        Ok(state.value.clone())
+        let state = AggregateSumState::<T>::get(place);
+        Ok(T::to_data_value(state.value, &self.return_type))
     }
 }
 
-impl fmt::Display for AggregateSumFunction {
+impl<T> fmt::Display for AggregateSumFunction<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.display_name)
     }
 }
 
-impl AggregateSumFunction {
-    pub fn try_create(
-        display_name: &str,
-        arguments: Vec<DataField>,
-    ) -> Result<Arc<dyn AggregateFunction>> {
-        assert_unary_arguments(display_name, arguments.len())?;
-        let return_type = Self::sum_return_type(arguments[0].data_type())?;
-
-        Ok(Arc::new(Self {
-            display_name: display_name.to_owned(),
-            arguments,
-            return_type,
-        }))
-    }
-
-    pub fn sum_return_type(arg_type: &DataType) -> Result<DataType> {
-        match arg_type {
-            DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => {
-                Ok(DataType::Int64)
-            }
-            DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
-                Ok(DataType::UInt64)
-            }
-            DataType::Float32 => Ok(DataType::Float32),
-            DataType::Float64 => Ok(DataType::Float64),
-
-            other => Err(ErrorCode::BadDataValueType(format!(
-                "SUM does not support type '{:?}'",
-                other
-            ))),
-        }
+pub fn try_create_aggregate_sum_function(
+    display_name: &str,
+    arguments: Vec<DataField>,
+) -> Result<Arc<dyn AggregateFunction>> {
+    assert_unary_arguments(display_name, arguments.len())?;
+    let return_type = sum_return_type(arguments[0].data_type())?;
+
+    match return_type {
+        DataType::Int64 => create_typed::<i64>(display_name, arguments, return_type),
+        DataType::UInt64 => create_typed::<u64>(display_name, arguments, return_type),
+        DataType::Float32 => create_typed::<f32>(display_name, arguments, return_type),
+        DataType::Decimal(_, _) => create_typed::<i128>(display_name, arguments, return_type),
+        _ => create_typed::<f64>(display_name, arguments, return_type),
     }
+}
 
-    pub fn sum_batch(column: &DataColumn) -> Result<DataValue> {
-        if column.is_empty() {
-            return Ok(DataValue::from(&Self::sum_return_type(
-                &column.data_type(),
-            )?));
+fn create_typed<T: StateNative>(
+    display_name: &str,
+    arguments: Vec<DataField>,
+    return_type: DataType,
+) -> Result<Arc<dyn AggregateFunction>> {
+    Ok(Arc::new(AggregateSumFunction::<T> {
+        display_name: display_name.to_owned(),
+        arguments,
+        return_type,
+        _native: PhantomData,
+    }))
+}
+
+pub fn sum_return_type(arg_type: &DataType) -> Result<DataType> {
+    match arg_type {
+        DataType::Int8 | DataType::Int16 | DataType::Int32 | DataType::Int64 => Ok(DataType::Int64),
+        DataType::UInt8 | DataType::UInt16 | DataType::UInt32 | DataType::UInt64 => {
+            Ok(DataType::UInt64)
         }
-        match column {
-            DataColumn::Constant(value, size) => {
-                DataValue::arithmetic(Mul, value.clone(), DataValue::UInt64(Some(*size as u64)))
-            }
-            DataColumn::Array(array) => array.sum(),
+        DataType::Float32 => Ok(DataType::Float32),
+        DataType::Float64 => Ok(DataType::Float64),
+        DataType::Decimal(_, scale) => Ok(DataType::Decimal(DECIMAL_MAX_PRECISION, *scale)),
+
+        other => Err(ErrorCode::BadDataValueType(format!(
+            "SUM does not support type '{:?}'",
+            other
+        ))),
+    }
+}
+
+pub fn sum_batch(column: &DataColumn) -> Result<DataValue> {
+    if column.is_empty() {
+        return Ok(DataValue::from(&sum_return_type(&column.data_type())?));
+    }
+    match column {
+        DataColumn::Constant(value, size) => {
+            DataValue::arithmetic(Mul, value.clone(), DataValue::UInt64(Some(*size as u64)))
         }
+        DataColumn::Array(array) => array.sum(),
     }
 }
@@ -0,0 +1,193 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::GetState;
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+
+/// The bits of the hash which choose the register, there are 2^12 registers of one byte, the
+/// standard error of the estimate is about 1.04 / sqrt(2^12) = 1.6%.
+const PRECISION: u32 = 12;
+const REGISTERS: usize = 1 << PRECISION;
+
+/// The HyperLogLog sketch of the hashes of the values: every register keeps the max rank, the
+/// position of the first 1 bit, of the hashes which choose it. The registers are allocated by
+/// the first value, so the state of no rows is empty.
+pub struct AggregateUniqHLLState {
+    registers: Vec<u8>,
+}
+
+impl<'a> GetState<'a, AggregateUniqHLLState> for AggregateUniqHLLState {}
+
+impl AggregateUniqHLLState {
+    fn insert(&mut self, hash: u64) {
+        if self.registers.is_empty() {
+            self.registers = vec![0; REGISTERS];
+        }
+
+        let index = (hash >> (64 - PRECISION)) as usize;
+        // The bit after the hash bits bounds the rank if they are all 0.
+        let rank = ((hash << PRECISION) | (1 << (PRECISION - 1))).leading_zeros() as u8 + 1;
+        if self.registers[index] < rank {
+            self.registers[index] = rank;
+        }
+    }
+
+    fn merge(&mut self, rhs: &AggregateUniqHLLState) {
+        if self.registers.is_empty() {
+            self.registers = rhs.registers.clone();
+            return;
+        }
+
+        for (register, rhs) in self.registers.iter_mut().zip(rhs.registers.iter()) {
+            if *register < *rhs {
+                *register = *rhs;
+            }
+        }
+    }
+
+    fn estimate(&self) -> u64 {
+        if self.registers.is_empty() {
+            return 0;
+        }
+
+        let m = REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+        let sum = self
+            .registers
+            .iter()
+            .map(|register| 2f64.powi(-(*register as i32)))
+            .sum::<f64>();
+        let estimate = alpha * m * m / sum;
+
+        // The linear counting of the empty registers is more accurate for the small sets.
+        let zeros = self.registers.iter().filter(|r| **r == 0).count();
+        if estimate <= 2.5 * m && zeros > 0 {
+            return (m * (m / zeros as f64).ln()).round() as u64;
+        }
+        estimate.round() as u64
+    }
+
+    /// The registers, or nothing if the state is empty.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        writer.extend_from_slice(&self.registers);
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        if !reader.is_empty() && reader.len() != REGISTERS {
+            return Err(ErrorCode::BadBytes(format!(
+                "The state of uniqHLL must have {} registers, but got {}",
+                REGISTERS,
+                reader.len()
+            )));
+        }
+        self.registers = reader.to_vec();
+        Ok(())
+    }
+}
+
+/// `uniqHLL(x, ...)` returns the approximate number of the distinct non-null values by the
+/// HyperLogLog sketch, whose state is 4 KiB whatever the number of the values, so the states of
+/// the nodes of a cluster are merged instead of their distinct values. `approx_count_distinct`
+/// is the same function.
+#[derive(Clone)]
+pub struct AggregateUniqHLLFunction {
+    display_name: String,
+}
+
+impl AggregateUniqHLLFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        assert_variadic_arguments(display_name, arguments.len(), (1, 32))?;
+
+        Ok(Arc::new(AggregateUniqHLLFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+}
+
+impl AggregateFunction for AggregateUniqHLLFunction {
+    fn name(&self) -> &str {
+        "AggregateUniqHLLFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateUniqHLLState { registers: vec![] });
+        (state as *mut AggregateUniqHLLState) as StateAddr
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let values = columns
+            .iter()
+            .map(|c| c.try_get(row))
+            .collect::<Result<Vec<_>>>()?;
+        if values.iter().any(|v| v.is_null()) {
+            return Ok(());
+        }
+
+        // The hasher has the fixed keys, so the nodes hash the same values to the same hashes.
+        let mut hasher = DefaultHasher::new();
+        for value in values.iter() {
+            DataGroupValue::try_from(value)?.hash(&mut hasher);
+        }
+        let state = AggregateUniqHLLState::get(place);
+        state.insert(hasher.finish());
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateUniqHLLState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateUniqHLLState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateUniqHLLState::get(place);
+        let rhs = AggregateUniqHLLState::get(rhs);
+        state.merge(rhs);
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateUniqHLLState::get(place);
+        Ok(DataValue::UInt64(Some(state.estimate())))
+    }
+}
+
+impl fmt::Display for AggregateUniqHLLFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
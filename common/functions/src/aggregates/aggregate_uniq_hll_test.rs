@@ -0,0 +1,78 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use bumpalo::Bump;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::aggregates::*;
+
+fn estimate(func: &AggregateFunctionRef, place: StateAddr) -> Result<u64> {
+    match func.merge_result(place)? {
+        DataValue::UInt64(Some(v)) => Ok(v),
+        other => panic!("uniqHLL returns UInt64, but got {:?}", other),
+    }
+}
+
+#[test]
+fn test_aggregate_uniq_hll_function() -> Result<()> {
+    let arena = Bump::new();
+    let args = vec![DataField::new("x", DataType::Int64, true)];
+
+    for name in ["uniqHLL", "approx_count_distinct"].iter() {
+        let func = AggregateFunctionFactory::get(name, args.clone())?;
+        assert_eq!(DataType::UInt64, func.return_type()?, "{}", name);
+
+        // The small sets are counted exactly, the nulls are skipped.
+        let columns: Vec<DataColumn> =
+            vec![Series::new(vec![Some(1_i64), None, Some(2), Some(1), Some(3), None]).into()];
+        let place = func.allocate_state(&arena);
+        func.accumulate(place, &columns, 6)?;
+        assert_eq!(3, estimate(&func, place)?, "{}", name);
+
+        // The state of no rows is 0.
+        let place = func.allocate_state(&arena);
+        assert_eq!(0, estimate(&func, place)?, "{}", name);
+    }
+
+    // The large sets are estimated within a few standard errors.
+    let func = AggregateFunctionFactory::get("uniqHLL", args.clone())?;
+    let values = (0..100_000_i64).map(|v| v % 50_000).collect::<Vec<_>>();
+    let columns: Vec<DataColumn> = vec![Series::new(values).into()];
+    let place = func.allocate_state(&arena);
+    func.accumulate(place, &columns, 100_000)?;
+    let count = estimate(&func, place)?;
+    assert!((47_500..52_500).contains(&count), "estimate {}", count);
+
+    // The rows are split into two states, which are merged after the serialization, to the
+    // same estimate.
+    let place1 = func.allocate_state(&arena);
+    let place2 = func.allocate_state(&arena);
+    for row in 0..100_000 {
+        let place = if row % 2 == 0 { place1 } else { place2 };
+        func.accumulate_row(place, row, &columns)?;
+    }
+    let mut state = vec![];
+    func.serialize_state(place2, &mut state)?;
+    let place3 = func.allocate_state(&arena);
+    func.deserialize_state(place3, &state)?;
+    func.merge(place1, place3)?;
+    assert_eq!(count, estimate(&func, place1)?);
+
+    // The state of no rows merges to nothing.
+    let place4 = func.allocate_state(&arena);
+    let mut state = vec![];
+    func.serialize(place4, &mut state)?;
+    assert!(state.is_empty());
+    func.merge(place4, place1)?;
+    assert_eq!(count, estimate(&func, place4)?);
+
+    let result = func.deserialize(place4, &[1, 2, 3]);
+    assert_eq!(
+        "Code: 46, displayText = The state of uniqHLL must have 4096 registers, but got 3.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
@@ -0,0 +1,199 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fmt;
+use std::io::Cursor;
+
+use bytes::Buf;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use super::GetState;
+use super::StateAddr;
+use crate::aggregates::aggregator_common::assert_variadic_arguments;
+use crate::aggregates::AggregateFunction;
+
+/// The max threshold, as the state keeps all the distinct values up to it.
+const MAX_THRESHOLD: u64 = 100;
+
+/// At most threshold + 1 distinct values, which is enough to tell whether there are more
+/// values than the threshold.
+pub struct AggregateUniqUpToState {
+    threshold: u64,
+    set: HashSet<Vec<DataGroupValue>>,
+}
+
+impl<'a> GetState<'a, AggregateUniqUpToState> for AggregateUniqUpToState {}
+
+impl AggregateUniqUpToState {
+    fn is_full(&self) -> bool {
+        self.set.len() as u64 > self.threshold
+    }
+
+    fn insert(&mut self, values: Vec<DataGroupValue>) {
+        if !self.is_full() {
+            self.set.insert(values);
+        }
+    }
+
+    /// The threshold and the array of the distinct values in JSON.
+    pub fn serialize(&self, writer: &mut Vec<u8>) -> Result<()> {
+        let values = self
+            .set
+            .iter()
+            .map(|entry| entry.iter().map(DataValue::from).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        serde_json::to_writer(writer, &(self.threshold, values))?;
+        Ok(())
+    }
+
+    pub fn deserialize(&mut self, reader: &[u8]) -> Result<()> {
+        let reader = Cursor::new(reader).reader();
+        let (threshold, values): (u64, Vec<Vec<DataValue>>) = serde_json::from_reader(reader)?;
+
+        self.threshold = threshold;
+        self.set.clear();
+        for array in values.iter() {
+            let entry = array
+                .iter()
+                .map(DataGroupValue::try_from)
+                .collect::<Result<Vec<_>>>()?;
+            self.set.insert(entry);
+        }
+        Ok(())
+    }
+}
+
+/// `uniqUpTo(N, x, ...)` returns the number of the distinct non-null values if it is not greater
+/// than N, otherwise N + 1. N must be a constant integer not greater than 100, it is the first
+/// argument instead of a parameter as in `uniqUpTo(N)(x)` of ClickHouse.
+#[derive(Clone)]
+pub struct AggregateUniqUpToFunction {
+    display_name: String,
+}
+
+impl AggregateUniqUpToFunction {
+    pub fn try_create(
+        display_name: &str,
+        arguments: Vec<DataField>,
+    ) -> Result<Arc<dyn AggregateFunction>> {
+        assert_variadic_arguments(display_name, arguments.len(), (2, 32))?;
+
+        let threshold_type = arguments[0].data_type();
+        if !is_integer(threshold_type) {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} expects the threshold of integer, but got {}",
+                display_name, threshold_type
+            )));
+        }
+
+        Ok(Arc::new(AggregateUniqUpToFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    fn threshold(&self, column: &DataColumn) -> Result<u64> {
+        let threshold = match column {
+            DataColumn::Constant(value, _) if !value.is_null() => value.as_i64()?,
+            _ => -1,
+        };
+
+        if threshold < 0 || threshold as u64 > MAX_THRESHOLD {
+            return Err(ErrorCode::BadArguments(format!(
+                "The threshold of {} must be a constant integer in [0, {}]",
+                self.display_name, MAX_THRESHOLD
+            )));
+        }
+        Ok(threshold as u64)
+    }
+}
+
+impl AggregateFunction for AggregateUniqUpToFunction {
+    fn name(&self) -> &str {
+        "AggregateUniqUpToFunction"
+    }
+
+    fn return_type(&self) -> Result<DataType> {
+        Ok(DataType::UInt64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn allocate_state(&self, arena: &bumpalo::Bump) -> StateAddr {
+        let state = arena.alloc(AggregateUniqUpToState {
+            threshold: 0,
+            set: HashSet::new(),
+        });
+        (state as *mut AggregateUniqUpToState) as StateAddr
+    }
+
+    fn accumulate_row(&self, place: StateAddr, row: usize, columns: &[DataColumn]) -> Result<()> {
+        let state = AggregateUniqUpToState::get(place);
+        state.threshold = self.threshold(&columns[0])?;
+        if state.is_full() {
+            return Ok(());
+        }
+
+        let values = columns[1..]
+            .iter()
+            .map(|c| c.try_get(row))
+            .collect::<Result<Vec<_>>>()?;
+        if !values.iter().any(|v| v.is_null()) {
+            state.insert(
+                values
+                    .iter()
+                    .map(DataGroupValue::try_from)
+                    .collect::<Result<Vec<_>>>()?,
+            );
+        }
+        Ok(())
+    }
+
+    fn serialize(&self, place: StateAddr, writer: &mut Vec<u8>) -> Result<()> {
+        let state = AggregateUniqUpToState::get(place);
+        state.serialize(writer)
+    }
+
+    fn deserialize(&self, place: StateAddr, reader: &[u8]) -> Result<()> {
+        let state = AggregateUniqUpToState::get(place);
+        state.deserialize(reader)
+    }
+
+    fn merge(&self, place: StateAddr, rhs: StateAddr) -> Result<()> {
+        let state = AggregateUniqUpToState::get(place);
+        let rhs = AggregateUniqUpToState::get(rhs);
+
+        // The state of no rows has not seen the threshold yet.
+        state.threshold = state.threshold.max(rhs.threshold);
+        for values in rhs.set.iter() {
+            if state.is_full() {
+                break;
+            }
+            state.insert(values.clone());
+        }
+        Ok(())
+    }
+
+    fn merge_result(&self, place: StateAddr) -> Result<DataValue> {
+        let state = AggregateUniqUpToState::get(place);
+        Ok(DataValue::UInt64(Some(state.set.len() as u64)))
+    }
+}
+
+impl fmt::Display for AggregateUniqUpToFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -6,15 +6,25 @@ use common_exception::Result;
 
 use crate::aggregates::aggregate_function_factory::FactoryCombinatorFuncRef;
 use crate::aggregates::aggregate_function_factory::FactoryFuncRef;
+use crate::aggregates::try_create_aggregate_avg_function;
+use crate::aggregates::try_create_aggregate_sum_function;
+use crate::aggregates::AggregateAnyFunction;
+use crate::aggregates::AggregateAnyHeavyFunction;
 use crate::aggregates::AggregateArgMaxFunction;
 use crate::aggregates::AggregateArgMinFunction;
-use crate::aggregates::AggregateAvgFunction;
+use crate::aggregates::AggregateBitmapFunction;
 use crate::aggregates::AggregateCountFunction;
 use crate::aggregates::AggregateDistinctCombinator;
+use crate::aggregates::AggregateGroupArrayFunction;
+use crate::aggregates::AggregateGroupArraySortedFunction;
 use crate::aggregates::AggregateIfCombinator;
 use crate::aggregates::AggregateMaxFunction;
 use crate::aggregates::AggregateMinFunction;
-use crate::aggregates::AggregateSumFunction;
+use crate::aggregates::AggregateQuantileFunction;
+use crate::aggregates::AggregateRetentionFunction;
+use crate::aggregates::AggregateStatisticsFunction;
+use crate::aggregates::AggregateUniqHLLFunction;
+use crate::aggregates::AggregateUniqUpToFunction;
 
 pub struct Aggregators;
 
@@ -23,14 +33,120 @@ impl Aggregators {
         let mut map = map.write();
         // FuseQuery always uses lowercase function names to get functions.
         map.insert("count".into(), AggregateCountFunction::try_create);
-        map.insert("sum".into(), AggregateSumFunction::try_create);
+        map.insert("sum".into(), try_create_aggregate_sum_function);
         map.insert("min".into(), AggregateMinFunction::try_create);
         map.insert("max".into(), AggregateMaxFunction::try_create);
-        map.insert("avg".into(), AggregateAvgFunction::try_create);
+        map.insert("avg".into(), try_create_aggregate_avg_function);
         map.insert("argmin".into(), AggregateArgMinFunction::try_create);
         map.insert("argmax".into(), AggregateArgMaxFunction::try_create);
+        map.insert(
+            "bitmapbuild".into(),
+            AggregateBitmapFunction::try_create_build,
+        );
+        map.insert(
+            "groupbitmap".into(),
+            AggregateBitmapFunction::try_create_group,
+        );
+        map.insert(
+            "groupbitmapand".into(),
+            AggregateBitmapFunction::try_create_group_and,
+        );
+        map.insert(
+            "groupbitmapor".into(),
+            AggregateBitmapFunction::try_create_group_or,
+        );
 
         map.insert("uniq".into(), AggregateDistinctCombinator::try_create_uniq);
+        map.insert("uniqupto".into(), AggregateUniqUpToFunction::try_create);
+        map.insert("uniqhll".into(), AggregateUniqHLLFunction::try_create);
+        map.insert(
+            "approx_count_distinct".into(),
+            AggregateUniqHLLFunction::try_create,
+        );
+        map.insert("retention".into(), AggregateRetentionFunction::try_create);
+        map.insert("any".into(), AggregateAnyFunction::try_create_any);
+        map.insert("anylast".into(), AggregateAnyFunction::try_create_any_last);
+        map.insert("anyheavy".into(), AggregateAnyHeavyFunction::try_create);
+        map.insert("grouparray".into(), AggregateGroupArrayFunction::try_create);
+        map.insert(
+            "grouparraysorted".into(),
+            AggregateGroupArraySortedFunction::try_create_asc,
+        );
+        map.insert(
+            "grouparraysorteddesc".into(),
+            AggregateGroupArraySortedFunction::try_create_desc,
+        );
+        map.insert(
+            "quantile".into(),
+            AggregateQuantileFunction::try_create_quantile,
+        );
+        map.insert(
+            "median".into(),
+            AggregateQuantileFunction::try_create_median,
+        );
+        map.insert(
+            "quantiles".into(),
+            AggregateQuantileFunction::try_create_quantiles,
+        );
+
+        // The statistics by the names of ClickHouse and of the SQL standard.
+        map.insert(
+            "varpop".into(),
+            AggregateStatisticsFunction::try_create_var_pop,
+        );
+        map.insert(
+            "var_pop".into(),
+            AggregateStatisticsFunction::try_create_var_pop,
+        );
+        map.insert(
+            "varsamp".into(),
+            AggregateStatisticsFunction::try_create_var_samp,
+        );
+        map.insert(
+            "var_samp".into(),
+            AggregateStatisticsFunction::try_create_var_samp,
+        );
+        map.insert(
+            "variance".into(),
+            AggregateStatisticsFunction::try_create_var_samp,
+        );
+        map.insert(
+            "stddevpop".into(),
+            AggregateStatisticsFunction::try_create_stddev_pop,
+        );
+        map.insert(
+            "stddev_pop".into(),
+            AggregateStatisticsFunction::try_create_stddev_pop,
+        );
+        map.insert(
+            "stddevsamp".into(),
+            AggregateStatisticsFunction::try_create_stddev_samp,
+        );
+        map.insert(
+            "stddev_samp".into(),
+            AggregateStatisticsFunction::try_create_stddev_samp,
+        );
+        map.insert(
+            "stddev".into(),
+            AggregateStatisticsFunction::try_create_stddev_samp,
+        );
+        map.insert(
+            "covarpop".into(),
+            AggregateStatisticsFunction::try_create_covar_pop,
+        );
+        map.insert(
+            "covar_pop".into(),
+            AggregateStatisticsFunction::try_create_covar_pop,
+        );
+        map.insert(
+            "covarsamp".into(),
+            AggregateStatisticsFunction::try_create_covar_samp,
+        );
+        map.insert(
+            "covar_samp".into(),
+            AggregateStatisticsFunction::try_create_covar_samp,
+        );
+        map.insert("corr".into(), AggregateStatisticsFunction::try_create_corr);
 
         Ok(())
     }
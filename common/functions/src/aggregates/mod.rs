@@ -2,42 +2,92 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod aggregate_any_test;
 #[cfg(test)]
 mod aggregate_batch_test;
 #[cfg(test)]
+mod aggregate_bitmap_test;
+#[cfg(test)]
 mod aggregate_combinator_test;
 #[cfg(test)]
 mod aggregate_function_test;
+#[cfg(test)]
+mod aggregate_group_array_sorted_test;
+#[cfg(test)]
+mod aggregate_group_array_test;
+#[cfg(test)]
+mod aggregate_quantile_test;
+#[cfg(test)]
+mod aggregate_retention_test;
+#[cfg(test)]
+mod aggregate_statistics_test;
+#[cfg(test)]
+mod aggregate_uniq_hll_test;
 
+mod aggregate_any;
+mod aggregate_any_heavy;
 mod aggregate_arg_max;
 mod aggregate_arg_min;
 mod aggregate_avg;
+mod aggregate_bitmap;
 mod aggregate_combinator_distinct;
 mod aggregate_combinator_if;
 mod aggregate_count;
 mod aggregate_function;
 mod aggregate_function_factory;
 mod aggregate_function_state;
+mod aggregate_group_array;
+mod aggregate_group_array_sorted;
 mod aggregate_max;
 mod aggregate_min;
+mod aggregate_quantile;
+mod aggregate_retention;
+mod aggregate_statistics;
 mod aggregate_sum;
+mod aggregate_uniq_hll;
+mod aggregate_uniq_up_to;
 mod aggregator;
 mod aggregator_common;
 
+pub use aggregate_any::AggregateAnyFunction;
+pub use aggregate_any_heavy::AggregateAnyHeavyFunction;
+pub use aggregate_any_heavy::AggregateAnyHeavyState;
 pub use aggregate_arg_max::AggregateArgMaxFunction;
 pub use aggregate_arg_min::AggregateArgMinFunction;
+pub use aggregate_avg::try_create_aggregate_avg_function;
 pub use aggregate_avg::AggregateAvgFunction;
+pub use aggregate_bitmap::AggregateBitmapFunction;
 pub use aggregate_combinator_distinct::AggregateDistinctCombinator;
 pub use aggregate_combinator_if::AggregateIfCombinator;
 pub use aggregate_count::AggregateCountFunction;
 pub use aggregate_function::AggregateFunction;
 pub use aggregate_function::AggregateFunctionRef;
 pub use aggregate_function_factory::AggregateFunctionFactory;
+pub use aggregate_function_state::AggregateArgMinMaxState;
+pub use aggregate_function_state::AggregateAvgState;
 pub use aggregate_function_state::AggregateSingeValueState;
+pub use aggregate_function_state::AggregateSumState;
 pub use aggregate_function_state::GetState;
 pub use aggregate_function_state::StateAddr;
+pub use aggregate_function_state::StateNative;
+pub use aggregate_group_array::AggregateGroupArrayFunction;
+pub use aggregate_group_array::AggregateGroupArrayState;
+pub use aggregate_group_array_sorted::AggregateGroupArraySortedFunction;
+pub use aggregate_group_array_sorted::AggregateGroupArraySortedState;
 pub use aggregate_max::AggregateMaxFunction;
 pub use aggregate_min::AggregateMinFunction;
+pub use aggregate_quantile::AggregateQuantileFunction;
+pub use aggregate_quantile::AggregateQuantileState;
+pub use aggregate_retention::AggregateRetentionFunction;
+pub use aggregate_statistics::AggregateStatisticsFunction;
+pub use aggregate_statistics::AggregateStatisticsState;
+pub use aggregate_sum::sum_batch;
+pub use aggregate_sum::sum_return_type;
+pub use aggregate_sum::try_create_aggregate_sum_function;
 pub use aggregate_sum::AggregateSumFunction;
+pub use aggregate_uniq_hll::AggregateUniqHLLFunction;
+pub use aggregate_uniq_hll::AggregateUniqHLLState;
+pub use aggregate_uniq_up_to::AggregateUniqUpToFunction;
 pub use aggregator::Aggregators;
 pub use aggregator_common::*;
@@ -0,0 +1,59 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_arrow::arrow::array::Array;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use roaring::RoaringTreemap;
+
+use crate::scalars::BitmapCardinalityFunction;
+use crate::scalars::BitmapOperatorFunction;
+use crate::scalars::FactoryFuncRef;
+
+/// The bitmaps are Binary values of the serialized roaring bitmaps of u64, in the portable
+/// format of roaring, so they are exchanged between the nodes and returned to the clients as
+/// they are.
+#[derive(Clone)]
+pub struct BitmapFunction;
+
+impl BitmapFunction {
+    pub fn register(map: FactoryFuncRef) -> Result<()> {
+        let mut map = map.write();
+        map.insert(
+            "bitmapCardinality".into(),
+            BitmapCardinalityFunction::try_create,
+        );
+        map.insert("bitmapAnd".into(), BitmapOperatorFunction::try_create_and);
+        map.insert("bitmapOr".into(), BitmapOperatorFunction::try_create_or);
+        map.insert("bitmapXor".into(), BitmapOperatorFunction::try_create_xor);
+        map.insert(
+            "bitmapAndnot".into(),
+            BitmapOperatorFunction::try_create_andnot,
+        );
+        Ok(())
+    }
+}
+
+pub fn bitmap_from_bytes(bytes: &[u8]) -> Result<RoaringTreemap> {
+    RoaringTreemap::deserialize_from(bytes)
+        .map_err(|e| ErrorCode::BadBytes(format!("Cannot deserialize the bitmap: {}", e)))
+}
+
+pub fn bitmap_to_bytes(bitmap: &RoaringTreemap) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(bitmap.serialized_size());
+    bitmap.serialize_into(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// The bitmaps of the Binary series, the nulls are None.
+pub fn bitmaps_from_series(series: &Series) -> Result<Vec<Option<RoaringTreemap>>> {
+    let array = series.binary()?.downcast_ref();
+    (0..array.len())
+        .map(|i| match array.is_null(i) {
+            true => Ok(None),
+            false => bitmap_from_bytes(array.value(i)).map(Some),
+        })
+        .collect()
+}
@@ -0,0 +1,66 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::bitmaps_from_series;
+use crate::scalars::Function;
+
+/// `bitmapCardinality(bitmap)` returns the number of the values of the bitmap.
+#[derive(Clone)]
+pub struct BitmapCardinalityFunction {
+    display_name: String,
+}
+
+impl BitmapCardinalityFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(BitmapCardinalityFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+}
+
+impl Function for BitmapCardinalityFunction {
+    fn name(&self) -> &str {
+        "bitmapCardinality"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match args[0] {
+            DataType::Binary => Ok(DataType::UInt64),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Function Error: {} does not support {} type parameters",
+                self.display_name, args[0]
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let series = columns[0].to_minimal_array()?;
+        let array = bitmaps_from_series(&series)?
+            .iter()
+            .map(|bitmap| bitmap.as_ref().map(|bitmap| bitmap.len()))
+            .collect::<DFUInt64Array>();
+        let column: DataColumn = array.into_series().into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for BitmapCardinalityFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bitmapCardinality")
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use roaring::RoaringTreemap;
+
+use crate::scalars::bitmap_to_bytes;
+use crate::scalars::bitmaps_from_series;
+use crate::scalars::Function;
+
+#[derive(Clone, Copy, Debug)]
+enum BitmapOperator {
+    And,
+    Or,
+    Xor,
+    AndNot,
+}
+
+/// `bitmapAnd(a, b)`, `bitmapOr(a, b)`, `bitmapXor(a, b)` and `bitmapAndnot(a, b)` return the
+/// bitmap of the intersection, the union, the symmetric difference and the difference of the
+/// bitmaps.
+#[derive(Clone)]
+pub struct BitmapOperatorFunction {
+    display_name: String,
+    op: BitmapOperator,
+}
+
+impl BitmapOperatorFunction {
+    fn try_create(display_name: &str, op: BitmapOperator) -> Result<Box<dyn Function>> {
+        Ok(Box::new(BitmapOperatorFunction {
+            display_name: display_name.to_string(),
+            op,
+        }))
+    }
+
+    pub fn try_create_and(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, BitmapOperator::And)
+    }
+
+    pub fn try_create_or(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, BitmapOperator::Or)
+    }
+
+    pub fn try_create_xor(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, BitmapOperator::Xor)
+    }
+
+    pub fn try_create_andnot(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, BitmapOperator::AndNot)
+    }
+
+    fn apply(&self, lhs: &RoaringTreemap, rhs: &RoaringTreemap) -> RoaringTreemap {
+        match self.op {
+            BitmapOperator::And => lhs & rhs,
+            BitmapOperator::Or => lhs | rhs,
+            BitmapOperator::Xor => lhs ^ rhs,
+            BitmapOperator::AndNot => lhs - rhs,
+        }
+    }
+}
+
+impl Function for BitmapOperatorFunction {
+    fn name(&self) -> &str {
+        "BitmapOperatorFunction"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match (&args[0], &args[1]) {
+            (DataType::Binary, DataType::Binary) => Ok(DataType::Binary),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Function Error: {} does not support {} and {} type parameters",
+                self.display_name, args[0], args[1]
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let lhs = bitmaps_from_series(&columns[0].to_array()?)?;
+        let rhs = bitmaps_from_series(&columns[1].to_array()?)?;
+
+        let mut builder = BinaryArrayBuilder::new(input_rows);
+        for (lhs, rhs) in lhs.iter().zip(rhs.iter()) {
+            match (lhs, rhs) {
+                (Some(lhs), Some(rhs)) => {
+                    builder.append_value(bitmap_to_bytes(&self.apply(lhs, rhs))?)
+                }
+                _ => builder.append_null(),
+            }
+        }
+        Ok(builder.finish().into_series().into())
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+}
+
+impl fmt::Display for BitmapOperatorFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+use roaring::RoaringTreemap;
+
+use crate::scalars::*;
+
+fn bitmap_column(bitmaps: &[Option<Vec<u64>>]) -> Result<DataColumn> {
+    let mut builder = BinaryArrayBuilder::new(bitmaps.len());
+    for bitmap in bitmaps {
+        match bitmap {
+            Some(values) => {
+                let bitmap = values.iter().copied().collect::<RoaringTreemap>();
+                builder.append_value(bitmap_to_bytes(&bitmap)?);
+            }
+            None => builder.append_null(),
+        }
+    }
+    Ok(builder.finish().into_series().into())
+}
+
+fn bitmap_values(column: &DataColumn) -> Result<Vec<Option<Vec<u64>>>> {
+    Ok(bitmaps_from_series(&column.to_array()?)?
+        .into_iter()
+        .map(|bitmap| bitmap.map(|bitmap| bitmap.iter().collect()))
+        .collect())
+}
+
+#[test]
+fn test_bitmap_cardinality_function() -> Result<()> {
+    let func = BitmapCardinalityFunction::try_create("bitmapCardinality")?;
+    assert_eq!("bitmapCardinality", format!("{}", func));
+    assert_eq!(DataType::UInt64, func.return_type(&[DataType::Binary])?);
+    assert_eq!(
+        "Code: 6, displayText = Function Error: bitmapCardinality does not support UInt64 type parameters.",
+        func.return_type(&[DataType::UInt64])
+            .err()
+            .unwrap()
+            .to_string()
+    );
+
+    let column = bitmap_column(&[
+        Some(vec![1, 2, 3]),
+        Some(vec![]),
+        None,
+        Some(vec![u64::MAX]),
+    ])?;
+    let result = func.eval(&[column], 4)?;
+    assert_eq!(result.to_values()?, vec![
+        DataValue::UInt64(Some(3)),
+        DataValue::UInt64(Some(0)),
+        DataValue::UInt64(None),
+        DataValue::UInt64(Some(1)),
+    ]);
+
+    // The bytes which are not a bitmap.
+    let mut builder = BinaryArrayBuilder::new(1);
+    builder.append_value(b"bitmap");
+    let column: DataColumn = builder.finish().into_series().into();
+    assert!(func.eval(&[column], 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_bitmap_operator_function() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        func: Box<dyn Function>,
+        expect: Vec<Option<Vec<u64>>>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "bitmap-and-passed",
+            func: BitmapOperatorFunction::try_create_and("bitmapAnd")?,
+            expect: vec![Some(vec![2, 3]), None],
+        },
+        Test {
+            name: "bitmap-or-passed",
+            func: BitmapOperatorFunction::try_create_or("bitmapOr")?,
+            expect: vec![Some(vec![1, 2, 3, 4]), None],
+        },
+        Test {
+            name: "bitmap-xor-passed",
+            func: BitmapOperatorFunction::try_create_xor("bitmapXor")?,
+            expect: vec![Some(vec![1, 4]), None],
+        },
+        Test {
+            name: "bitmap-andnot-passed",
+            func: BitmapOperatorFunction::try_create_andnot("bitmapAndnot")?,
+            expect: vec![Some(vec![1]), None],
+        },
+    ];
+
+    let lhs = bitmap_column(&[Some(vec![1, 2, 3]), Some(vec![1])])?;
+    let rhs = bitmap_column(&[Some(vec![2, 3, 4]), None])?;
+    for t in tests {
+        assert_eq!(
+            DataType::Binary,
+            t.func.return_type(&[DataType::Binary, DataType::Binary])?,
+            "{}",
+            t.name
+        );
+        let result = t.func.eval(&[lhs.clone(), rhs.clone()], 2)?;
+        assert_eq!(bitmap_values(&result)?, t.expect, "{}", t.name);
+    }
+    Ok(())
+}
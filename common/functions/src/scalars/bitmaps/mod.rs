@@ -0,0 +1,17 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod bitmap_test;
+
+mod bitmap;
+mod bitmap_cardinality;
+mod bitmap_operator;
+
+pub use bitmap::bitmap_from_bytes;
+pub use bitmap::bitmap_to_bytes;
+pub use bitmap::bitmaps_from_series;
+pub use bitmap::BitmapFunction;
+pub use bitmap_cardinality::BitmapCardinalityFunction;
+pub use bitmap_operator::BitmapOperatorFunction;
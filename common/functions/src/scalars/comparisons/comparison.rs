@@ -9,7 +9,9 @@ use common_datavalues::prelude::*;
 use common_datavalues::DataValueComparisonOperator;
 use common_exception::Result;
 
+use crate::scalars::ComparisonDistinctFromFunction;
 use crate::scalars::ComparisonEqFunction;
+use crate::scalars::ComparisonEqNullSafeFunction;
 use crate::scalars::ComparisonGtEqFunction;
 use crate::scalars::ComparisonGtFunction;
 use crate::scalars::ComparisonLikeFunction;
@@ -41,6 +43,15 @@ impl ComparisonFunction {
             "not like".into(),
             ComparisonNotLikeFunction::try_create_func,
         );
+        map.insert("<=>".into(), ComparisonEqNullSafeFunction::try_create_func);
+        map.insert(
+            "is not distinct from".into(),
+            ComparisonEqNullSafeFunction::try_create_func,
+        );
+        map.insert(
+            "is distinct from".into(),
+            ComparisonDistinctFromFunction::try_create_func,
+        );
         Ok(())
     }
 
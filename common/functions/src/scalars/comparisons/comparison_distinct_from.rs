@@ -0,0 +1,17 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataValueComparisonOperator;
+use common_exception::Result;
+
+use crate::scalars::ComparisonFunction;
+use crate::scalars::Function;
+
+pub struct ComparisonDistinctFromFunction;
+
+impl ComparisonDistinctFromFunction {
+    pub fn try_create_func(_display_name: &str) -> Result<Box<dyn Function>> {
+        ComparisonFunction::try_create_func(DataValueComparisonOperator::DistinctFrom)
+    }
+}
@@ -132,6 +132,45 @@ fn test_comparison_function() -> Result<()> {
             expect: Series::new(vec![false, false, false, true]),
             error: "",
         },
+        Test {
+            name: "eq-null-safe-passed",
+            display: "<=>",
+            nullable: false,
+            func: ComparisonEqNullSafeFunction::try_create_func("")?,
+            arg_names: vec!["a", "b"],
+            columns: vec![
+                Series::new(vec![Some(4i64), None, None, Some(4)]).into(),
+                Series::new(vec![Some(1i64), Some(2), None, Some(4)]).into(),
+            ],
+            expect: Series::new(vec![false, false, true, true]),
+            error: "",
+        },
+        Test {
+            name: "eq-null-safe-null-constant-passed",
+            display: "<=>",
+            nullable: false,
+            func: ComparisonEqNullSafeFunction::try_create_func("")?,
+            arg_names: vec!["a", "b"],
+            columns: vec![
+                Series::new(vec![Some(4i64), None, None, Some(4)]).into(),
+                DataColumn::Constant(DataValue::Int64(None), 4),
+            ],
+            expect: Series::new(vec![false, true, true, false]),
+            error: "",
+        },
+        Test {
+            name: "distinct-from-passed",
+            display: "IS DISTINCT FROM",
+            nullable: false,
+            func: ComparisonDistinctFromFunction::try_create_func("")?,
+            arg_names: vec!["a", "b"],
+            columns: vec![
+                Series::new(vec![Some("a"), None, None, Some("b")]).into(),
+                Series::new(vec![Some("a"), Some("b"), None, Some("c")]).into(),
+            ],
+            expect: Series::new(vec![false, true, false, true]),
+            error: "",
+        },
     ];
 
     for t in tests {
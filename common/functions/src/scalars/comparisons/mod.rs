@@ -6,7 +6,9 @@
 mod comparison_test;
 
 mod comparison;
+mod comparison_distinct_from;
 mod comparison_eq;
+mod comparison_eq_null_safe;
 mod comparison_gt;
 mod comparison_gt_eq;
 mod comparison_like;
@@ -16,7 +18,9 @@ mod comparison_not_eq;
 mod comparison_not_like;
 
 pub use comparison::ComparisonFunction;
+pub use comparison_distinct_from::ComparisonDistinctFromFunction;
 pub use comparison_eq::ComparisonEqFunction;
+pub use comparison_eq_null_safe::ComparisonEqNullSafeFunction;
 pub use comparison_gt::ComparisonGtFunction;
 pub use comparison_gt_eq::ComparisonGtEqFunction;
 pub use comparison_like::ComparisonLikeFunction;
@@ -0,0 +1,23 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+
+use crate::scalars::FactoryFuncRef;
+use crate::scalars::NowFunction;
+use crate::scalars::ToDateFunction;
+
+#[derive(Clone)]
+pub struct DateFunction;
+
+impl DateFunction {
+    pub fn register(map: FactoryFuncRef) -> Result<()> {
+        let mut map = map.write();
+        map.insert("toDate".into(), ToDateFunction::try_create_date);
+        map.insert("toDateTime".into(), ToDateFunction::try_create_date_time);
+        map.insert("now".into(), NowFunction::try_create_now);
+        map.insert("today".into(), NowFunction::try_create_today);
+        Ok(())
+    }
+}
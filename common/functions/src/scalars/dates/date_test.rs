@@ -0,0 +1,84 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::*;
+
+#[test]
+fn test_to_date_function() -> Result<()> {
+    let columns: Vec<DataColumn> = vec![Series::new(vec![
+        Some("2021-01-01"),
+        Some("2021-01-01 12:30:15"),
+        Some("x"),
+        None,
+    ])
+    .into()];
+
+    let func = ToDateFunction::try_create_date("toDate")?;
+    assert_eq!("toDate", format!("{}", func));
+    assert_eq!(DataType::Date32, func.return_type(&[DataType::Utf8])?);
+    let result = func.eval(&columns, 4)?.to_array()?;
+    let values = result.to_values()?;
+    assert_eq!(
+        values.iter().map(|v| v.to_string()).collect::<Vec<_>>(),
+        vec!["2021-01-01", "2021-01-01", "NULL", "NULL"]
+    );
+
+    let func = ToDateFunction::try_create_date_time("toDateTime")?;
+    assert_eq!(DataType::Date64, func.return_type(&[DataType::Date32])?);
+    let result = func.eval(&columns, 4)?.to_array()?;
+    assert_eq!(result.to_values()?, vec![
+        DataValue::Date64(Some(1_609_459_200_000)),
+        DataValue::Date64(Some(1_609_504_215_000)),
+        DataValue::Date64(None),
+        DataValue::Date64(None),
+    ]);
+
+    // The constants are a constant.
+    let columns = vec![DataColumn::Constant(DataValue::UInt32(Some(18628)), 3)];
+    let func = ToDateFunction::try_create_date("toDate")?;
+    let result = func.eval(&columns, 3)?;
+    assert!(
+        matches!(
+            result,
+            DataColumn::Constant(DataValue::Date32(Some(18628)), 3)
+        ),
+        "{:?}",
+        result
+    );
+
+    assert_eq!(
+        "Code: 6, displayText = Function Error: toDateTime does not support Float64 type parameters.",
+        ToDateFunction::try_create_date_time("toDateTime")?
+            .return_type(&[DataType::Float64])
+            .unwrap_err()
+            .to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_now_function() -> Result<()> {
+    let now = NowFunction::try_create_now("now")?;
+    assert_eq!(DataType::Date64, now.return_type(&[])?);
+    assert!(!now.is_deterministic());
+
+    let today = NowFunction::try_create_today("today")?;
+    assert_eq!(DataType::Date32, today.return_type(&[])?);
+
+    // Today is the date of now, unless it is midnight between the calls.
+    let now = now.eval(&[], 2)?;
+    let today = today.eval(&[], 2)?;
+    match (&now, &today) {
+        (
+            DataColumn::Constant(DataValue::Date64(Some(millis)), 2),
+            DataColumn::Constant(DataValue::Date32(Some(days)), 2),
+        ) => assert!((*days as i64 - millis / 86_400_000).abs() <= 1),
+        _ => panic!("Unexpected now {:?} and today {:?}", now, today),
+    }
+    Ok(())
+}
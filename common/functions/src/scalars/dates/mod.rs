@@ -0,0 +1,14 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod date_test;
+
+mod date;
+mod now;
+mod to_date;
+
+pub use date::DateFunction;
+pub use now::NowFunction;
+pub use to_date::ToDateFunction;
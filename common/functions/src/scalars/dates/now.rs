@@ -0,0 +1,74 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use chrono::Utc;
+use common_datavalues::date_to_days;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::Function;
+
+/// `now()` returns the current date time of Date64, `today()` returns the current date of
+/// Date32, both in UTC.
+#[derive(Clone)]
+pub struct NowFunction {
+    display_name: String,
+    data_type: DataType,
+}
+
+impl NowFunction {
+    fn try_create(display_name: &str, data_type: DataType) -> Result<Box<dyn Function>> {
+        Ok(Box::new(NowFunction {
+            display_name: display_name.to_string(),
+            data_type,
+        }))
+    }
+
+    pub fn try_create_now(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, DataType::Date64)
+    }
+
+    pub fn try_create_today(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, DataType::Date32)
+    }
+}
+
+impl Function for NowFunction {
+    fn name(&self) -> &str {
+        "NowFunction"
+    }
+
+    fn return_type(&self, _args: &[DataType]) -> Result<DataType> {
+        Ok(self.data_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, _columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let now = Utc::now().naive_utc();
+        let value = match self.data_type {
+            DataType::Date32 => DataValue::Date32(date_to_days(&now.date())),
+            _ => DataValue::Date64(Some(now.timestamp_millis())),
+        };
+        Ok(DataColumn::Constant(value, input_rows))
+    }
+
+    fn num_arguments(&self) -> usize {
+        0
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+}
+
+impl fmt::Display for NowFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::cast_to_date;
+use common_datavalues::cast_to_date_time;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::Function;
+
+/// `toDate(x)` converts the dates, the date times, the strings like `2021-01-01` and the days
+/// since the epoch to Date32. `toDateTime(x)` converts the dates, the date times, the strings
+/// like `2021-01-01 12:00:00` and the seconds since the epoch to Date64. The strings which
+/// cannot be parsed are NULL.
+#[derive(Clone)]
+pub struct ToDateFunction {
+    display_name: String,
+    data_type: DataType,
+}
+
+impl ToDateFunction {
+    fn try_create(display_name: &str, data_type: DataType) -> Result<Box<dyn Function>> {
+        Ok(Box::new(ToDateFunction {
+            display_name: display_name.to_string(),
+            data_type,
+        }))
+    }
+
+    pub fn try_create_date(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, DataType::Date32)
+    }
+
+    pub fn try_create_date_time(display_name: &str) -> Result<Box<dyn Function>> {
+        Self::try_create(display_name, DataType::Date64)
+    }
+}
+
+impl Function for ToDateFunction {
+    fn name(&self) -> &str {
+        "ToDateFunction"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match &args[0] {
+            DataType::Date32 | DataType::Date64 | DataType::Utf8 => Ok(self.data_type.clone()),
+            data_type if is_integer(data_type) => Ok(self.data_type.clone()),
+            data_type => Err(ErrorCode::BadArguments(format!(
+                "Function Error: {} does not support {} type parameters",
+                self.display_name, data_type
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let series = columns[0].to_minimal_array()?;
+        let result = match self.data_type {
+            DataType::Date32 => cast_to_date(&series)?,
+            _ => cast_to_date_time(&series)?,
+        };
+        let column: DataColumn = result.into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for ToDateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
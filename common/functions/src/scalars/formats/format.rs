@@ -0,0 +1,24 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+
+use crate::scalars::FactoryFuncRef;
+use crate::scalars::FormatDateTimeFunction;
+use crate::scalars::FormatReadableSizeFunction;
+
+#[derive(Clone)]
+pub struct FormatFunction;
+
+impl FormatFunction {
+    pub fn register(map: FactoryFuncRef) -> Result<()> {
+        let mut map = map.write();
+        map.insert("formatDateTime".into(), FormatDateTimeFunction::try_create);
+        map.insert(
+            "formatReadableSize".into(),
+            FormatReadableSizeFunction::try_create,
+        );
+        Ok(())
+    }
+}
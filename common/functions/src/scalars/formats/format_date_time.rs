@@ -0,0 +1,143 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+use std::fmt::Write;
+
+use chrono::format::Item;
+use chrono::format::StrftimeItems;
+use chrono::NaiveDateTime;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::Function;
+
+/// `formatDateTime(time, format)` formats the time by the strftime specifiers of the format,
+/// e.g. `%Y-%m-%d %H:%M:%S`, in UTC. The times are the days of Date32, the milliseconds of
+/// Date64 or the seconds of the integers since the epoch.
+#[derive(Clone)]
+pub struct FormatDateTimeFunction {
+    display_name: String,
+}
+
+impl FormatDateTimeFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(FormatDateTimeFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    fn format_items<'a>(&self, format: &'a str) -> Result<Vec<Item<'a>>> {
+        let items = StrftimeItems::new(format).collect::<Vec<_>>();
+        let invalid = items.iter().any(|item| matches!(item, Item::Error))
+            || Self::format(&items, &NaiveDateTime::from_timestamp(0, 0)).is_none();
+        match invalid {
+            true => Err(ErrorCode::BadArguments(format!(
+                "Invalid format of {}: {}",
+                self.display_name, format
+            ))),
+            false => Ok(items),
+        }
+    }
+
+    // The specifiers of the time zone have no value for the times in UTC, they are errors.
+    fn format(items: &[Item], time: &NaiveDateTime) -> Option<String> {
+        let mut buf = String::new();
+        write!(buf, "{}", time.format_with_items(items.iter())).ok()?;
+        Some(buf)
+    }
+
+    fn date_times(series: &Series) -> Result<Vec<Option<NaiveDateTime>>> {
+        let date_times = match series.data_type() {
+            DataType::Date32 => series
+                .date32()?
+                .downcast_iter()
+                .map(|days| days.and_then(|days| Self::from_seconds(days as i64 * 86400, 0)))
+                .collect(),
+            DataType::Date64 => series
+                .date64()?
+                .downcast_iter()
+                .map(|millis| {
+                    millis.and_then(|millis| {
+                        let nanos = millis.rem_euclid(1000) as u32 * 1_000_000;
+                        Self::from_seconds(millis.div_euclid(1000), nanos)
+                    })
+                })
+                .collect(),
+            _ => series
+                .cast_with_type(&DataType::Int64)?
+                .i64()?
+                .downcast_iter()
+                .map(|seconds| seconds.and_then(|seconds| Self::from_seconds(seconds, 0)))
+                .collect(),
+        };
+        Ok(date_times)
+    }
+
+    // The times out of the range of chrono are nulls.
+    fn from_seconds(seconds: i64, nanos: u32) -> Option<NaiveDateTime> {
+        NaiveDateTime::from_timestamp_opt(seconds, nanos)
+    }
+}
+
+impl Function for FormatDateTimeFunction {
+    fn name(&self) -> &str {
+        "formatDateTime"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match args[0] {
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Date32
+            | DataType::Date64 => Ok(DataType::Utf8),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Function Error: {} does not support {} type parameters",
+                self.display_name, args[0]
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let format = match &columns[1] {
+            DataColumn::Constant(DataValue::Utf8(Some(format)), _) => format,
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The format of {} must be a constant string",
+                    self.display_name
+                )))
+            }
+        };
+        let items = self.format_items(format)?;
+
+        let series = columns[0].to_minimal_array()?;
+        let array = Self::date_times(&series)?
+            .into_iter()
+            .map(|time| time.and_then(|time| Self::format(&items, &time)))
+            .collect::<DFUtf8Array>();
+        let column: DataColumn = array.into_series().into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+}
+
+impl fmt::Display for FormatDateTimeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "formatDateTime")
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::FormatDateTimeFunction;
+
+#[test]
+fn test_format_date_time_function() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        column: DataColumn,
+        format: &'static str,
+        expect: Vec<Option<&'static str>>,
+        error: &'static str,
+    }
+
+    let seconds: DataColumn = Series::new(vec![Some(0i64), Some(1_000_000_000), None]).into();
+    let days = Series::new(vec![18628i32]).cast_with_type(&DataType::Date32)?;
+    let millis = Series::new(vec![1_609_459_201_500i64]).cast_with_type(&DataType::Date64)?;
+
+    let tests = vec![
+        Test {
+            name: "format-seconds-passed",
+            column: seconds.clone(),
+            format: "%Y-%m-%d %H:%M:%S",
+            expect: vec![
+                Some("1970-01-01 00:00:00"),
+                Some("2001-09-09 01:46:40"),
+                None,
+            ],
+            error: "",
+        },
+        Test {
+            name: "format-names-passed",
+            column: seconds.clone(),
+            format: "%a %d %b %Y",
+            expect: vec![Some("Thu 01 Jan 1970"), Some("Sun 09 Sep 2001"), None],
+            error: "",
+        },
+        Test {
+            name: "format-date32-passed",
+            column: days.into(),
+            format: "%Y-%m-%d",
+            expect: vec![Some("2021-01-01")],
+            error: "",
+        },
+        Test {
+            name: "format-date64-passed",
+            column: millis.into(),
+            format: "%Y-%m-%d %H:%M:%S%.3f",
+            expect: vec![Some("2021-01-01 00:00:01.500")],
+            error: "",
+        },
+        Test {
+            name: "format-unknown-specifier",
+            column: seconds.clone(),
+            format: "%Q",
+            expect: vec![],
+            error: "Code: 6, displayText = Invalid format of formatDateTime: %Q.",
+        },
+        Test {
+            name: "format-time-zone-specifier",
+            column: seconds,
+            format: "%H:%M %z",
+            expect: vec![],
+            error: "Code: 6, displayText = Invalid format of formatDateTime: %H:%M %z.",
+        },
+    ];
+
+    let func = FormatDateTimeFunction::try_create("formatDateTime")?;
+    assert_eq!("formatDateTime", format!("{}", func));
+    assert_eq!(
+        DataType::Utf8,
+        func.return_type(&[DataType::UInt32, DataType::Utf8])?
+    );
+    assert!(func.return_type(&[DataType::Utf8, DataType::Utf8]).is_err());
+
+    for t in tests {
+        let rows = t.column.len();
+        let format = DataColumn::Constant(DataValue::Utf8(Some(t.format.to_string())), rows);
+        match func.eval(&[t.column, format], rows) {
+            Ok(v) => {
+                let expect = t
+                    .expect
+                    .iter()
+                    .map(|v| DataValue::Utf8(v.map(|v| v.to_string())))
+                    .collect::<Vec<_>>();
+                assert_eq!(v.to_values()?, expect, "{}", t.name);
+            }
+            Err(e) => assert_eq!(t.error, e.to_string(), "{}", t.name),
+        }
+    }
+
+    // The format must be a constant.
+    let format: DataColumn = Series::new(vec!["%Y"]).into();
+    let result = func.eval(&[Series::new(vec![0i64]).into(), format], 1);
+    assert_eq!(
+        "Code: 6, displayText = The format of formatDateTime must be a constant string.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
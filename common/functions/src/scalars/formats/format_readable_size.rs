@@ -0,0 +1,90 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::Function;
+
+const UNITS: [&str; 9] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB", "ZiB", "YiB"];
+
+/// `formatReadableSize(bytes)` formats the bytes in the binary units with two decimals,
+/// e.g. `1.50 KiB` for 1536.
+#[derive(Clone)]
+pub struct FormatReadableSizeFunction {
+    display_name: String,
+}
+
+impl FormatReadableSizeFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(FormatReadableSizeFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    fn format(bytes: f64) -> String {
+        let mut size = bytes;
+        let mut unit = 0;
+        while size.abs() >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        format!("{:.2} {}", size, UNITS[unit])
+    }
+}
+
+impl Function for FormatReadableSizeFunction {
+    fn name(&self) -> &str {
+        "formatReadableSize"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        match args[0] {
+            DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64 => Ok(DataType::Utf8),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Function Error: {} does not support {} type parameters",
+                self.display_name, args[0]
+            ))),
+        }
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let series = columns[0]
+            .to_minimal_array()?
+            .cast_with_type(&DataType::Float64)?;
+        let array = series
+            .f64()?
+            .downcast_iter()
+            .map(|bytes| bytes.map(Self::format))
+            .collect::<DFUtf8Array>();
+        let column: DataColumn = array.into_series().into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for FormatReadableSizeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "formatReadableSize")
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::FormatReadableSizeFunction;
+
+#[test]
+fn test_format_readable_size_function() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        column: DataColumn,
+        expect: DataColumn,
+    }
+
+    let tests = vec![
+        Test {
+            name: "format-integers-passed",
+            column: Series::new(vec![0i64, 1, 1023, 1024, 1536, 1048576, -2048]).into(),
+            expect: Series::new(vec![
+                "0.00 B",
+                "1.00 B",
+                "1023.00 B",
+                "1.00 KiB",
+                "1.50 KiB",
+                "1.00 MiB",
+                "-2.00 KiB",
+            ])
+            .into(),
+        },
+        Test {
+            name: "format-large-integers-passed",
+            column: Series::new(vec![u64::MAX]).into(),
+            expect: Series::new(vec!["16.00 EiB"]).into(),
+        },
+        Test {
+            name: "format-floats-passed",
+            column: Series::new(vec![1.5e12f64, 0.5]).into(),
+            expect: Series::new(vec!["1.36 TiB", "0.50 B"]).into(),
+        },
+        Test {
+            name: "format-constant-passed",
+            column: DataColumn::Constant(DataValue::UInt32(Some(4096)), 3),
+            expect: DataColumn::Constant(DataValue::Utf8(Some("4.00 KiB".to_string())), 3),
+        },
+    ];
+
+    let func = FormatReadableSizeFunction::try_create("formatReadableSize")?;
+    assert_eq!("formatReadableSize", format!("{}", func));
+    assert_eq!(DataType::Utf8, func.return_type(&[DataType::UInt64])?);
+    assert!(func.return_type(&[DataType::Utf8]).is_err());
+
+    for t in tests {
+        let rows = t.column.len();
+        let v = func.eval(&[t.column], rows)?;
+        assert_eq!(&v, &t.expect, "{}", t.name);
+    }
+    Ok(())
+}
@@ -0,0 +1,16 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod format_date_time_test;
+#[cfg(test)]
+mod format_readable_size_test;
+
+mod format;
+mod format_date_time;
+mod format_readable_size;
+
+pub use format::FormatFunction;
+pub use format_date_time::FormatDateTimeFunction;
+pub use format_readable_size::FormatReadableSizeFunction;
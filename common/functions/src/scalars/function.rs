@@ -25,6 +25,13 @@ pub trait Function: fmt::Display + Sync + Send + DynClone {
 
     fn return_type(&self, args: &[DataType]) -> Result<DataType>;
     fn nullable(&self, _input_schema: &DataSchema) -> Result<bool>;
+
+    // If the result is NULL when any argument is NULL, the result is nullable if any argument
+    // is nullable, even if the function itself never returns NULL.
+    fn passthrough_null(&self) -> bool {
+        true
+    }
+
     fn eval(&self, columns: &[DataColumn], _input_rows: usize) -> Result<DataColumn>;
 
     // If function returns the same result when same arguments, it is deterministic function.
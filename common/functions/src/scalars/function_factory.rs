@@ -12,10 +12,15 @@ use lazy_static::lazy_static;
 use unicase::UniCase;
 
 use crate::scalars::ArithmeticFunction;
+use crate::scalars::BitmapFunction;
 use crate::scalars::ComparisonFunction;
+use crate::scalars::DateFunction;
+use crate::scalars::FormatFunction;
 use crate::scalars::Function;
+use crate::scalars::GeoFunction;
 use crate::scalars::HashesFunction;
 use crate::scalars::LogicFunction;
+use crate::scalars::NullableFunction;
 use crate::scalars::StringFunction;
 use crate::scalars::ToCastFunction;
 use crate::scalars::UdfFunction;
@@ -36,6 +41,11 @@ lazy_static! {
         UdfFunction::register(map.clone()).unwrap();
         HashesFunction::register(map.clone()).unwrap();
         ToCastFunction::register(map.clone()).unwrap();
+        FormatFunction::register(map.clone()).unwrap();
+        BitmapFunction::register(map.clone()).unwrap();
+        GeoFunction::register(map.clone()).unwrap();
+        DateFunction::register(map.clone()).unwrap();
+        NullableFunction::register(map.clone()).unwrap();
 
         map
     };
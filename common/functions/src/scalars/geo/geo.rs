@@ -0,0 +1,73 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::FactoryFuncRef;
+use crate::scalars::GeohashEncodeFunction;
+use crate::scalars::GreatCircleDistanceFunction;
+use crate::scalars::PointInPolygonFunction;
+
+/// The coordinates are the numbers in degrees, which are cast to Float64, the longitude is
+/// always before the latitude.
+#[derive(Clone)]
+pub struct GeoFunction;
+
+impl GeoFunction {
+    pub fn register(map: FactoryFuncRef) -> Result<()> {
+        let mut map = map.write();
+        map.insert(
+            "greatCircleDistance".into(),
+            GreatCircleDistanceFunction::try_create,
+        );
+        map.insert("pointInPolygon".into(), PointInPolygonFunction::try_create);
+        map.insert("geohashEncode".into(), GeohashEncodeFunction::try_create);
+        Ok(())
+    }
+
+    pub fn check_numeric_arguments(display_name: &str, args: &[DataType]) -> Result<()> {
+        match args.iter().find(|arg| !is_numeric(arg)) {
+            None => Ok(()),
+            Some(arg) => Err(ErrorCode::BadArguments(format!(
+                "Function Error: {} does not support {} type parameters",
+                display_name, arg
+            ))),
+        }
+    }
+
+    pub fn check_coordinate(display_name: &str, longitude: f64, latitude: f64) -> Result<()> {
+        if !(-180.0..=180.0).contains(&longitude) || !(-90.0..=90.0).contains(&latitude) {
+            return Err(ErrorCode::BadArguments(format!(
+                "{} expects the longitude in [-180, 180] and the latitude in [-90, 90], but got ({}, {})",
+                display_name, longitude, latitude
+            )));
+        }
+        Ok(())
+    }
+
+    /// The Float64 values of the columns. The columns are of one row if all of them are
+    /// constants, so that the result of the constants is a constant.
+    pub fn float64_values(
+        columns: &[DataColumn],
+        input_rows: usize,
+    ) -> Result<Vec<Vec<Option<f64>>>> {
+        let all_constants = columns
+            .iter()
+            .all(|column| matches!(column, DataColumn::Constant(_, _)));
+        let rows = if all_constants { 1 } else { input_rows };
+
+        columns
+            .iter()
+            .map(|column| {
+                let series = column
+                    .resize_constant(rows)
+                    .to_array()?
+                    .cast_with_type(&DataType::Float64)?;
+                Ok(series.f64()?.downcast_iter().collect())
+            })
+            .collect()
+    }
+}
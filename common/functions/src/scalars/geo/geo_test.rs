@@ -0,0 +1,201 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::*;
+
+fn constant(value: f64, rows: usize) -> DataColumn {
+    DataColumn::Constant(DataValue::Float64(Some(value)), rows)
+}
+
+#[test]
+fn test_great_circle_distance_function() -> Result<()> {
+    let func = GreatCircleDistanceFunction::try_create("greatCircleDistance")?;
+    assert_eq!("greatCircleDistance", format!("{}", func));
+    assert_eq!(
+        DataType::Float64,
+        func.return_type(&[
+            DataType::Float64,
+            DataType::Float64,
+            DataType::Int32,
+            DataType::UInt8
+        ])?
+    );
+    assert_eq!(
+        "Code: 6, displayText = Function Error: greatCircleDistance does not support Utf8 type parameters.",
+        func.return_type(&[
+            DataType::Float64,
+            DataType::Utf8,
+            DataType::Float64,
+            DataType::Float64
+        ])
+        .unwrap_err()
+        .to_string()
+    );
+
+    let columns = vec![
+        Series::new(vec![Some(0.0_f64), Some(0.0), None]).into(),
+        Series::new(vec![0.0_f64, 0.0, 0.0]).into(),
+        Series::new(vec![0.0_f64, 180.0, 1.0]).into(),
+        Series::new(vec![1.0_f64, 0.0, 1.0]).into(),
+    ];
+    let result = func.eval(&columns, 3)?.to_array()?;
+    let distances = result.f64()?.downcast_iter().collect::<Vec<_>>();
+    assert!((distances[0].unwrap() - 111226.3).abs() < 1e-3);
+    assert!((distances[1].unwrap() - 20020734.0).abs() < 1e-3);
+    assert_eq!(distances[2], None);
+
+    // The constants are a constant.
+    let columns = vec![
+        constant(10.0, 5),
+        constant(20.0, 5),
+        constant(10.0, 5),
+        constant(20.0, 5),
+    ];
+    let result = func.eval(&columns, 5)?;
+    assert!(
+        matches!(result, DataColumn::Constant(DataValue::Float64(Some(v)), 5) if v == 0.0),
+        "{:?}",
+        result
+    );
+
+    let columns = vec![
+        constant(0.0, 1),
+        constant(91.0, 1),
+        constant(0.0, 1),
+        constant(0.0, 1),
+    ];
+    assert_eq!(
+        "Code: 6, displayText = greatCircleDistance expects the longitude in [-180, 180] and the latitude in [-90, 90], but got (0, 91).",
+        func.eval(&columns, 1).unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_point_in_polygon_function() -> Result<()> {
+    let func = PointInPolygonFunction::try_create("pointInPolygon")?;
+    assert_eq!("pointInPolygon", format!("{}", func));
+    assert_eq!(Some((8, usize::MAX)), func.variadic_arguments());
+    assert_eq!(
+        DataType::Boolean,
+        func.return_type(&vec![DataType::Float64; 8])?
+    );
+    assert_eq!(
+        "Code: 28, displayText = pointInPolygon expects the point and the vertices of the polygon in pairs of coordinates, but got 9 arguments.",
+        func.return_type(&vec![DataType::Float64; 9])
+            .unwrap_err()
+            .to_string()
+    );
+
+    // The square of (0, 0), (4, 0), (4, 4) and (0, 4).
+    let square = |rows: usize| -> Vec<DataColumn> {
+        [0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 0.0, 4.0]
+            .iter()
+            .map(|v| constant(*v, rows))
+            .collect()
+    };
+
+    let mut columns: Vec<DataColumn> = vec![
+        Series::new(vec![Some(2.0_f64), Some(5.0), Some(4.0), Some(0.0), None]).into(),
+        Series::new(vec![2.0_f64, 2.0, 2.0, 0.0, 1.0]).into(),
+    ];
+    columns.extend(square(5));
+    let result = func.eval(&columns, 5)?.to_array()?;
+    assert_eq!(Vec::from(result.bool()?), vec![
+        Some(true),
+        Some(false),
+        Some(true),
+        Some(true),
+        None
+    ]);
+
+    // The concave polygon of (0, 0), (4, 0), (4, 4), (2, 1) and (0, 4).
+    let mut columns = vec![constant(2.0, 2), constant(3.0, 2)];
+    columns.extend(
+        [0.0, 0.0, 4.0, 0.0, 4.0, 4.0, 2.0, 1.0, 0.0, 4.0]
+            .iter()
+            .map(|v| constant(*v, 2)),
+    );
+    let result = func.eval(&columns, 2)?;
+    assert!(
+        matches!(
+            result,
+            DataColumn::Constant(DataValue::Boolean(Some(false)), 2)
+        ),
+        "{:?}",
+        result
+    );
+
+    let mut columns = vec![constant(2.0, 1), constant(2.0, 1)];
+    columns.push(Series::new(vec![0.0_f64]).into());
+    columns.extend(square(1).into_iter().skip(1));
+    assert_eq!(
+        "Code: 6, displayText = The vertices of pointInPolygon must be the constant numbers.",
+        func.eval(&columns, 1).unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_geohash_encode_function() -> Result<()> {
+    let func = GeohashEncodeFunction::try_create("geohashEncode")?;
+    assert_eq!("geohashEncode", format!("{}", func));
+    assert_eq!(Some((2, 3)), func.variadic_arguments());
+    assert_eq!(
+        DataType::Utf8,
+        func.return_type(&[DataType::Float64, DataType::Float64])?
+    );
+
+    struct Test {
+        name: &'static str,
+        precision: Option<DataValue>,
+        expect: Vec<Option<&'static str>>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "geohash-default-precision-passed",
+            precision: None,
+            expect: vec![Some("ezs42d000000"), Some("wx4g09nj42fs"), None],
+        },
+        Test {
+            name: "geohash-precision-passed",
+            precision: Some(DataValue::UInt8(Some(5))),
+            expect: vec![Some("ezs42"), Some("wx4g0"), None],
+        },
+        Test {
+            name: "geohash-zero-precision-passed",
+            precision: Some(DataValue::UInt8(Some(0))),
+            expect: vec![Some("ezs42d000000"), Some("wx4g09nj42fs"), None],
+        },
+    ];
+
+    for t in tests {
+        let mut columns: Vec<DataColumn> = vec![
+            Series::new(vec![Some(-5.60302734375_f64), Some(116.3975), None]).into(),
+            Series::new(vec![42.593994140625_f64, 39.9087, 0.0]).into(),
+        ];
+        if let Some(precision) = t.precision {
+            columns.push(DataColumn::Constant(precision, 3));
+        }
+
+        let result = func.eval(&columns, 3)?.to_array()?;
+        assert_eq!(Vec::from(result.utf8()?), t.expect, "{}", t.name);
+    }
+
+    let columns = vec![
+        constant(1.0, 1),
+        constant(1.0, 1),
+        Series::new(vec![5_u8]).into(),
+    ];
+    assert_eq!(
+        "Code: 6, displayText = The precision of geohashEncode must be a constant integer.",
+        func.eval(&columns, 1).unwrap_err().to_string()
+    );
+    Ok(())
+}
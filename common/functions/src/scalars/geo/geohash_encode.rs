@@ -0,0 +1,122 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::Function;
+use crate::scalars::GeoFunction;
+
+const BASE32: &[u8] = b"0123456789bcdefghjkmnpqrstuvwxyz";
+const MAX_PRECISION: usize = 12;
+
+/// `geohashEncode(longitude, latitude[, precision])` returns the geohash of the point in the
+/// characters of the precision, which is a constant in [1, 12]. The precision out of the range
+/// is 12, as the one by default.
+#[derive(Clone)]
+pub struct GeohashEncodeFunction {
+    display_name: String,
+}
+
+impl GeohashEncodeFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(GeohashEncodeFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    fn precision(&self, columns: &[DataColumn]) -> Result<usize> {
+        let precision = match columns.get(2) {
+            None => return Ok(MAX_PRECISION),
+            Some(DataColumn::Constant(value, _)) if !value.is_null() => value.as_u64().ok(),
+            Some(_) => None,
+        };
+
+        match precision {
+            Some(precision) if (1..=MAX_PRECISION as u64).contains(&precision) => {
+                Ok(precision as usize)
+            }
+            Some(_) => Ok(MAX_PRECISION),
+            None => Err(ErrorCode::BadArguments(format!(
+                "The precision of {} must be a constant integer",
+                self.display_name
+            ))),
+        }
+    }
+
+    // The bits of the longitude and the latitude are interleaved from the longitude, which
+    // halve their ranges in turn, five bits for a character.
+    fn encode(longitude: f64, latitude: f64, precision: usize) -> String {
+        let mut ranges = [(-180.0, 180.0), (-90.0, 90.0)];
+        let coordinates = [longitude, latitude];
+
+        let mut hash = String::with_capacity(precision);
+        let mut bit = 0;
+        while hash.len() < precision {
+            let mut index = 0;
+            for _ in 0..5 {
+                let (range, value) = (&mut ranges[bit % 2], coordinates[bit % 2]);
+                let mid = (range.0 + range.1) / 2.0;
+                index <<= 1;
+                if value >= mid {
+                    index |= 1;
+                    range.0 = mid;
+                } else {
+                    range.1 = mid;
+                }
+                bit += 1;
+            }
+            hash.push(BASE32[index] as char);
+        }
+        hash
+    }
+}
+
+impl Function for GeohashEncodeFunction {
+    fn name(&self) -> &str {
+        "geohashEncode"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        GeoFunction::check_numeric_arguments(&self.display_name, args)?;
+        Ok(DataType::Utf8)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let precision = self.precision(columns)?;
+        let values = GeoFunction::float64_values(&columns[..2], input_rows)?;
+
+        let mut hashes = Vec::with_capacity(values[0].len());
+        for point in values[0].iter().zip(values[1].iter()) {
+            hashes.push(match point {
+                (Some(longitude), Some(latitude)) => {
+                    GeoFunction::check_coordinate(&self.display_name, *longitude, *latitude)?;
+                    Some(Self::encode(*longitude, *latitude, precision))
+                }
+                _ => None,
+            });
+        }
+
+        let array = hashes.into_iter().collect::<DFUtf8Array>();
+        let column: DataColumn = array.into_series().into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        Some((2, 3))
+    }
+}
+
+impl fmt::Display for GeohashEncodeFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "geohashEncode")
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::Function;
+use crate::scalars::GeoFunction;
+
+/// The radius of the earth in meters, as a sphere.
+const EARTH_RADIUS: f64 = 6372797.560856;
+
+/// `greatCircleDistance(lon1, lat1, lon2, lat2)` returns the distance in meters between the
+/// two points on the earth by the haversine formula.
+#[derive(Clone)]
+pub struct GreatCircleDistanceFunction {
+    display_name: String,
+}
+
+impl GreatCircleDistanceFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(GreatCircleDistanceFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    fn distance(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+        let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+        let half_lat = (lat2 - lat1) / 2.0;
+        let half_lon = (lon2 - lon1).to_radians() / 2.0;
+        let a = half_lat.sin().powi(2) + lat1.cos() * lat2.cos() * half_lon.sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().asin()
+    }
+}
+
+impl Function for GreatCircleDistanceFunction {
+    fn name(&self) -> &str {
+        "greatCircleDistance"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        GeoFunction::check_numeric_arguments(&self.display_name, args)?;
+        Ok(DataType::Float64)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let values = GeoFunction::float64_values(columns, input_rows)?;
+        let points = values[0]
+            .iter()
+            .zip(values[1].iter())
+            .zip(values[2].iter().zip(values[3].iter()));
+
+        let mut distances = Vec::with_capacity(values[0].len());
+        for ((lon1, lat1), (lon2, lat2)) in points {
+            distances.push(match (lon1, lat1, lon2, lat2) {
+                (Some(lon1), Some(lat1), Some(lon2), Some(lat2)) => {
+                    GeoFunction::check_coordinate(&self.display_name, *lon1, *lat1)?;
+                    GeoFunction::check_coordinate(&self.display_name, *lon2, *lat2)?;
+                    Some(Self::distance(*lon1, *lat1, *lon2, *lat2))
+                }
+                _ => None,
+            });
+        }
+
+        let array = DFFloat64Array::new_from_opt_slice(&distances);
+        let column: DataColumn = array.into_series().into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn num_arguments(&self) -> usize {
+        4
+    }
+}
+
+impl fmt::Display for GreatCircleDistanceFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "greatCircleDistance")
+    }
+}
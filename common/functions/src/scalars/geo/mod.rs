@@ -0,0 +1,16 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod geo_test;
+
+mod geo;
+mod geohash_encode;
+mod great_circle_distance;
+mod point_in_polygon;
+
+pub use geo::GeoFunction;
+pub use geohash_encode::GeohashEncodeFunction;
+pub use great_circle_distance::GreatCircleDistanceFunction;
+pub use point_in_polygon::PointInPolygonFunction;
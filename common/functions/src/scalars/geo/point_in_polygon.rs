@@ -0,0 +1,128 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::Function;
+use crate::scalars::GeoFunction;
+
+/// `pointInPolygon(x, y, x1, y1, x2, y2, x3, y3, ...)` returns whether the point (x, y) is
+/// inside the polygon of the constant vertices, the points on the edges are inside.
+#[derive(Clone)]
+pub struct PointInPolygonFunction {
+    display_name: String,
+}
+
+impl PointInPolygonFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(PointInPolygonFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+
+    fn polygon(&self, columns: &[DataColumn]) -> Result<Vec<(f64, f64)>> {
+        let mut coordinates = Vec::with_capacity(columns.len());
+        for column in columns {
+            let value = match column {
+                DataColumn::Constant(value, _) if !value.is_null() => value,
+                _ => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "The vertices of {} must be the constant numbers",
+                        self.display_name
+                    )))
+                }
+            };
+            let series = value.to_array()?.cast_with_type(&DataType::Float64)?;
+            coordinates.push(
+                series
+                    .f64()?
+                    .downcast_iter()
+                    .next()
+                    .flatten()
+                    .unwrap_or(0.0),
+            );
+        }
+        Ok(coordinates
+            .chunks(2)
+            .map(|vertex| (vertex[0], vertex[1]))
+            .collect())
+    }
+
+    // The crossings of the ray to the right of the point with the edges, odd if it is inside.
+    fn contains(polygon: &[(f64, f64)], (x, y): (f64, f64)) -> bool {
+        let mut inside = false;
+        let mut prev = polygon[polygon.len() - 1];
+        for &vertex in polygon {
+            if Self::on_edge((x, y), prev, vertex) {
+                return true;
+            }
+
+            let ((x1, y1), (x2, y2)) = (vertex, prev);
+            if (y1 > y) != (y2 > y) && x < (x2 - x1) * (y - y1) / (y2 - y1) + x1 {
+                inside = !inside;
+            }
+            prev = vertex;
+        }
+        inside
+    }
+
+    fn on_edge((x, y): (f64, f64), (x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> bool {
+        let cross = (x2 - x1) * (y - y1) - (y2 - y1) * (x - x1);
+        cross == 0.0 && x >= x1.min(x2) && x <= x1.max(x2) && y >= y1.min(y2) && y <= y1.max(y2)
+    }
+}
+
+impl Function for PointInPolygonFunction {
+    fn name(&self) -> &str {
+        "pointInPolygon"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        if args.len() % 2 != 0 {
+            return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                "{} expects the point and the vertices of the polygon in pairs of coordinates, but got {} arguments",
+                self.display_name,
+                args.len()
+            )));
+        }
+
+        GeoFunction::check_numeric_arguments(&self.display_name, args)?;
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], input_rows: usize) -> Result<DataColumn> {
+        let polygon = self.polygon(&columns[2..])?;
+        let points = GeoFunction::float64_values(&columns[..2], input_rows)?;
+
+        let array = points[0]
+            .iter()
+            .zip(points[1].iter())
+            .map(|point| match point {
+                (Some(x), Some(y)) => Some(Self::contains(&polygon, (*x, *y))),
+                _ => None,
+            })
+            .collect::<DFBooleanArray>();
+        let column: DataColumn = array.into_series().into();
+        Ok(column.resize_constant(input_rows))
+    }
+
+    fn variadic_arguments(&self) -> Option<(usize, usize)> {
+        // The point and three vertices at least.
+        Some((8, usize::MAX))
+    }
+}
+
+impl fmt::Display for PointInPolygonFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pointInPolygon")
+    }
+}
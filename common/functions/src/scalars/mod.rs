@@ -6,28 +6,38 @@
 mod function_column_test;
 
 mod arithmetics;
+mod bitmaps;
 mod comparisons;
+mod dates;
 mod expressions;
+mod formats;
 mod function;
 mod function_alias;
 mod function_column;
 mod function_factory;
 mod function_literal;
+mod geo;
 mod hashes;
 mod logics;
+mod nullables;
 mod strings;
 mod udfs;
 
 pub use arithmetics::*;
+pub use bitmaps::*;
 pub use comparisons::*;
+pub use dates::*;
 pub use expressions::*;
+pub use formats::*;
 pub use function::Function;
 pub use function_alias::AliasFunction;
 pub use function_column::ColumnFunction;
 pub use function_factory::FactoryFuncRef;
 pub use function_factory::FunctionFactory;
 pub use function_literal::LiteralFunction;
+pub use geo::*;
 pub use hashes::*;
 pub use logics::*;
+pub use nullables::*;
 pub use strings::*;
 pub use udfs::*;
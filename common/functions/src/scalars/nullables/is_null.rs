@@ -0,0 +1,80 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_arrow::arrow::compute;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+use crate::scalars::Function;
+
+/// `isNull(x)`, which is `x IS NULL`, and `isNotNull(x)`, which is `x IS NOT NULL`. The results
+/// are computed from the validity bitmap of the column, and they are never NULL.
+#[derive(Clone)]
+pub struct IsNullFunction {
+    display_name: String,
+    negated: bool,
+}
+
+impl IsNullFunction {
+    pub fn try_create_is_null(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(IsNullFunction {
+            display_name: display_name.to_string(),
+            negated: false,
+        }))
+    }
+
+    pub fn try_create_is_not_null(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(IsNullFunction {
+            display_name: display_name.to_string(),
+            negated: true,
+        }))
+    }
+}
+
+impl Function for IsNullFunction {
+    fn name(&self) -> &str {
+        "IsNullFunction"
+    }
+
+    fn return_type(&self, _args: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn passthrough_null(&self) -> bool {
+        false
+    }
+
+    fn eval(&self, columns: &[DataColumn], _input_rows: usize) -> Result<DataColumn> {
+        match &columns[0] {
+            DataColumn::Constant(value, size) => Ok(DataColumn::Constant(
+                DataValue::Boolean(Some(value.is_null() != self.negated)),
+                *size,
+            )),
+            DataColumn::Array(series) => {
+                let array = series.get_array_ref();
+                let result = match self.negated {
+                    true => compute::is_not_null(array.as_ref())?,
+                    false => compute::is_null(array.as_ref())?,
+                };
+                Ok(DFBooleanArray::from_arrow_array(result).into())
+            }
+        }
+    }
+
+    fn num_arguments(&self) -> usize {
+        1
+    }
+}
+
+impl fmt::Display for IsNullFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.display_name)
+    }
+}
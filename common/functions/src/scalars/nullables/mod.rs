@@ -0,0 +1,12 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod nullable_test;
+
+mod is_null;
+mod nullable;
+
+pub use is_null::IsNullFunction;
+pub use nullable::NullableFunction;
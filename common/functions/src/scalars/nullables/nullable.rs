@@ -0,0 +1,20 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+
+use crate::scalars::FactoryFuncRef;
+use crate::scalars::IsNullFunction;
+
+#[derive(Clone)]
+pub struct NullableFunction;
+
+impl NullableFunction {
+    pub fn register(map: FactoryFuncRef) -> Result<()> {
+        let mut map = map.write();
+        map.insert("isNull".into(), IsNullFunction::try_create_is_null);
+        map.insert("isNotNull".into(), IsNullFunction::try_create_is_not_null);
+        Ok(())
+    }
+}
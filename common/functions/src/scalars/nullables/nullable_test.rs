@@ -0,0 +1,48 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::*;
+
+#[test]
+fn test_is_null_function() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Utf8, true)]);
+    let columns: Vec<DataColumn> = vec![Series::new(vec![Some("a"), None, Some("c")]).into()];
+
+    let func = IsNullFunction::try_create_is_null("isNull")?;
+    assert_eq!("isNull", format!("{}", func));
+    assert_eq!(DataType::Boolean, func.return_type(&[DataType::Utf8])?);
+    assert!(!func.nullable(&schema)?);
+    assert!(!func.passthrough_null());
+    let result = func.eval(&columns, 3)?.to_array()?;
+    assert_eq!(Vec::from(result.bool()?), vec![
+        Some(false),
+        Some(true),
+        Some(false)
+    ]);
+
+    let func = IsNullFunction::try_create_is_not_null("isNotNull")?;
+    let result = func.eval(&columns, 3)?.to_array()?;
+    assert_eq!(Vec::from(result.bool()?), vec![
+        Some(true),
+        Some(false),
+        Some(true)
+    ]);
+
+    // The constants are a constant.
+    let columns = vec![DataColumn::Constant(DataValue::Utf8(None), 2)];
+    let result = func.eval(&columns, 2)?;
+    assert!(
+        matches!(
+            result,
+            DataColumn::Constant(DataValue::Boolean(Some(false)), 2)
+        ),
+        "{:?}",
+        result
+    );
+    Ok(())
+}
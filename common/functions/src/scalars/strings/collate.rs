@@ -0,0 +1,65 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::scalars::Function;
+
+/// `collate(str, collation)` returns the collation keys of the strings, two strings are equal or
+/// ordered by the collation as their keys are.
+#[derive(Clone)]
+pub struct CollateFunction {
+    display_name: String,
+}
+
+impl CollateFunction {
+    pub fn try_create(display_name: &str) -> Result<Box<dyn Function>> {
+        Ok(Box::new(CollateFunction {
+            display_name: display_name.to_string(),
+        }))
+    }
+}
+
+impl Function for CollateFunction {
+    fn name(&self) -> &str {
+        "collate"
+    }
+
+    fn return_type(&self, args: &[DataType]) -> Result<DataType> {
+        Ok(args[0].clone())
+    }
+
+    fn nullable(&self, _input_schema: &DataSchema) -> Result<bool> {
+        Ok(false)
+    }
+
+    fn eval(&self, columns: &[DataColumn], _input_rows: usize) -> Result<DataColumn> {
+        let collation = match &columns[1] {
+            DataColumn::Constant(DataValue::Utf8(Some(name)), _) => {
+                DataCollation::try_create(name)?
+            }
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The collation of {} must be a constant string",
+                    self.display_name
+                )))
+            }
+        };
+        collation.keys(&columns[0])
+    }
+
+    fn num_arguments(&self) -> usize {
+        2
+    }
+}
+
+impl fmt::Display for CollateFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "COLLATE")
+    }
+}
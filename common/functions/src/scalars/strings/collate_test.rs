@@ -0,0 +1,79 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::scalars::CollateFunction;
+
+#[test]
+fn test_collate_function() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        collation: &'static str,
+        expect: Vec<&'static str>,
+        error: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "collate-binary-passed",
+            collation: "binary",
+            expect: vec!["Abc", "aBC", "Été", "ete"],
+            error: "",
+        },
+        Test {
+            name: "collate-general-ci-passed",
+            collation: "utf8_general_ci",
+            expect: vec!["abc", "abc", "été", "ete"],
+            error: "",
+        },
+        Test {
+            name: "collate-unicode-ci-passed",
+            collation: "UTF8MB4_UNICODE_CI",
+            expect: vec!["abc", "abc", "ete", "ete"],
+            error: "",
+        },
+        Test {
+            name: "collate-unknown-collation",
+            collation: "latin1_swedish_ci",
+            expect: vec![],
+            error: "Code: 6, displayText = Unknown collation: latin1_swedish_ci.",
+        },
+    ];
+
+    let func = CollateFunction::try_create("collate")?;
+    assert_eq!("COLLATE", format!("{}", func));
+    assert_eq!(
+        DataType::Utf8,
+        func.return_type(&[DataType::Utf8, DataType::Utf8])?
+    );
+
+    let column: DataColumn = Series::new(vec!["Abc", "aBC", "Été", "ete"]).into();
+    for t in tests {
+        let collation = DataColumn::Constant(DataValue::Utf8(Some(t.collation.to_string())), 4);
+        match func.eval(&[column.clone(), collation], 4) {
+            Ok(v) => {
+                let expect: DataColumn = Series::new(t.expect).into();
+                assert_eq!(&v, &expect, "{}", t.name);
+            }
+            Err(e) => assert_eq!(t.error, e.to_string(), "{}", t.name),
+        }
+    }
+
+    // The collation must be a constant.
+    let result = func.eval(&[column.clone(), column], 4);
+    assert_eq!(
+        "Code: 6, displayText = The collation of collate must be a constant string.",
+        result.err().unwrap().to_string()
+    );
+
+    // The other types are not collated.
+    let numbers: DataColumn = Series::new(vec![1i64, 2]).into();
+    let collation = DataColumn::Constant(DataValue::Utf8(Some("utf8_general_ci".to_string())), 2);
+    let v = func.eval(&[numbers.clone(), collation], 2)?;
+    assert_eq!(&v, &numbers);
+    Ok(())
+}
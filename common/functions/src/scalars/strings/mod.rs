@@ -2,11 +2,15 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod collate_test;
 #[cfg(test)]
 mod substring_test;
 
+mod collate;
 mod string;
 mod substring;
 
+pub use collate::CollateFunction;
 pub use string::StringFunction;
 pub use substring::SubstringFunction;
@@ -4,6 +4,7 @@
 
 use common_exception::Result;
 
+use crate::scalars::CollateFunction;
 use crate::scalars::FactoryFuncRef;
 use crate::scalars::SubstringFunction;
 
@@ -14,6 +15,7 @@ impl StringFunction {
     pub fn register(map: FactoryFuncRef) -> Result<()> {
         let mut map = map.write();
         map.insert("substring".into(), SubstringFunction::try_create);
+        map.insert("collate".into(), CollateFunction::try_create);
 
         Ok(())
     }
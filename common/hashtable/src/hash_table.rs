@@ -0,0 +1,173 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::mem;
+
+use crate::HashTableKey;
+
+const MIN_SLOTS: usize = 16;
+
+#[derive(Clone, Copy)]
+struct Slot {
+    hash: u64,
+    index: usize,
+}
+
+impl Slot {
+    const EMPTY: Slot = Slot {
+        hash: 0,
+        index: usize::MAX,
+    };
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.index == usize::MAX
+    }
+}
+
+/// An open addressing hash table with linear probing, shared by the group by and the hash join.
+///
+/// The entries are kept one after another in the order they are inserted, and the slots only
+/// hold the hashes and the indexes of the entries. So an entry is known by its index for its
+/// lifetime, which the group by uses as the offset of the states of the group in the arena, and
+/// growing the table only moves the slots.
+pub struct HashTable<Key: HashTableKey, Value> {
+    slots: Vec<Slot>,
+    entries: Vec<(Key, Value)>,
+}
+
+impl<Key: HashTableKey, Value> HashTable<Key, Value> {
+    pub fn create() -> Self {
+        Self::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        // The table grows once it is half full.
+        let slots = (capacity * 2).next_power_of_two().max(MIN_SLOTS);
+        HashTable {
+            slots: vec![Slot::EMPTY; slots],
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The bytes of the slots and the entries, not counting the memory which the keys and the
+    /// values own on the heap.
+    pub fn memory_size(&self) -> usize {
+        self.slots.capacity() * mem::size_of::<Slot>()
+            + self.entries.capacity() * mem::size_of::<(Key, Value)>()
+    }
+
+    /// The index of the entry of the key.
+    #[inline]
+    pub fn find(&self, key: &Key) -> Option<usize> {
+        let (pos, found) = self.find_slot(key.hash_key(), key);
+        match found {
+            true => Some(self.slots[pos].index),
+            false => None,
+        }
+    }
+
+    #[inline]
+    pub fn get(&self, key: &Key) -> Option<&Value> {
+        self.find(key).map(|index| &self.entries[index].1)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, key: &Key) -> Option<&mut Value> {
+        match self.find(key) {
+            Some(index) => Some(&mut self.entries[index].1),
+            None => None,
+        }
+    }
+
+    /// Inserts the entry if the key is not in the table yet, returns the index of the entry of
+    /// the key and whether it is inserted.
+    #[inline]
+    pub fn insert(&mut self, key: Key, value: Value) -> (usize, bool) {
+        self.insert_with(key, || value)
+    }
+
+    /// Same as `insert`, but the value is only made if the key is new.
+    pub fn insert_with<F>(&mut self, key: Key, make_value: F) -> (usize, bool)
+    where F: FnOnce() -> Value {
+        let hash = key.hash_key();
+        let (pos, found) = self.find_slot(hash, &key);
+        if found {
+            return (self.slots[pos].index, false);
+        }
+
+        let index = self.entries.len();
+        self.entries.push((key, make_value()));
+        self.slots[pos] = Slot { hash, index };
+        if self.entries.len() * 2 > self.slots.len() {
+            self.resize(self.slots.len() * 2);
+        }
+        (index, true)
+    }
+
+    #[inline]
+    pub fn key(&self, index: usize) -> &Key {
+        &self.entries[index].0
+    }
+
+    #[inline]
+    pub fn value(&self, index: usize) -> &Value {
+        &self.entries[index].1
+    }
+
+    #[inline]
+    pub fn value_mut(&mut self, index: usize) -> &mut Value {
+        &mut self.entries[index].1
+    }
+
+    /// The entries in the order they are inserted.
+    pub fn iter(&self) -> impl Iterator<Item = (&Key, &Value)> {
+        self.entries.iter().map(|(key, value)| (key, value))
+    }
+
+    /// The slot of the key if it is in the table, or else the empty slot the key goes into.
+    #[inline]
+    fn find_slot(&self, hash: u64, key: &Key) -> (usize, bool) {
+        let mask = self.slots.len() - 1;
+        let mut pos = hash as usize & mask;
+        loop {
+            let slot = &self.slots[pos];
+            if slot.is_empty() {
+                return (pos, false);
+            }
+            if slot.hash == hash && self.entries[slot.index].0 == *key {
+                return (pos, true);
+            }
+            pos = (pos + 1) & mask;
+        }
+    }
+
+    fn resize(&mut self, slots: usize) {
+        let mask = slots - 1;
+        let old_slots = mem::replace(&mut self.slots, vec![Slot::EMPTY; slots]);
+        for slot in old_slots.into_iter().filter(|slot| !slot.is_empty()) {
+            let mut pos = slot.hash as usize & mask;
+            while !self.slots[pos].is_empty() {
+                pos = (pos + 1) & mask;
+            }
+            self.slots[pos] = slot;
+        }
+    }
+}
+
+impl<Key: HashTableKey, Value> Default for HashTable<Key, Value> {
+    fn default() -> Self {
+        Self::create()
+    }
+}
@@ -0,0 +1,50 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::hash::Hasher;
+
+use ahash::AHasher;
+
+/// The keys of the hash table: the fixed keys are the group keys packed into one integer, the
+/// serialized keys are the bytes of the group keys one after another.
+pub trait HashTableKey: Eq {
+    fn hash_key(&self) -> u64;
+}
+
+/// The fixed keys are mixed by the finalizer of murmur3, which spreads every bit of the key into
+/// the low bits used as the slot.
+#[inline]
+fn mix(key: u64) -> u64 {
+    let mut hash = key;
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    hash ^ (hash >> 33)
+}
+
+macro_rules! impl_fixed_key {
+    ($ty: ty) => {
+        impl HashTableKey for $ty {
+            #[inline]
+            fn hash_key(&self) -> u64 {
+                mix(*self as u64)
+            }
+        }
+    };
+}
+
+impl_fixed_key!(u8);
+impl_fixed_key!(u16);
+impl_fixed_key!(u32);
+impl_fixed_key!(u64);
+
+impl HashTableKey for Vec<u8> {
+    #[inline]
+    fn hash_key(&self) -> u64 {
+        let mut hasher = AHasher::default();
+        hasher.write(self);
+        hasher.finish()
+    }
+}
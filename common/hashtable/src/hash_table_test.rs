@@ -0,0 +1,61 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use pretty_assertions::assert_eq;
+
+use crate::HashTable;
+use crate::HashTableKey;
+
+#[test]
+fn test_hash_table_fixed_keys() {
+    let mut table = HashTable::<u64, u64>::create();
+    assert!(table.is_empty());
+
+    // Enough keys to grow the table several times.
+    for key in 0..10000_u64 {
+        assert_eq!((key as usize, true), table.insert(key * 7, key));
+    }
+    assert_eq!(10000, table.len());
+    // The 32768 slots of the hash and the index, and the entries of the key and the value.
+    assert!(table.memory_size() >= 32768 * 16 + 10000 * 16);
+
+    // The keys already in the table keep their entries.
+    assert_eq!((3, false), table.insert(21, 100));
+    assert_eq!(Some(&3), table.get(&21));
+    assert_eq!(None, table.get(&22));
+
+    *table.get_mut(&21).unwrap() += 1;
+    assert_eq!(&4, table.value(3));
+    assert_eq!(&21, table.key(3));
+
+    let keys = table
+        .iter()
+        .take(3)
+        .map(|(key, _)| *key)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![0, 7, 14], keys);
+}
+
+#[test]
+fn test_hash_table_serialized_keys() {
+    let mut table = HashTable::<Vec<u8>, Vec<u32>>::with_capacity(2);
+    for (row, key) in ["a", "b", "a", "", "b", "a"].iter().enumerate() {
+        let (index, _) = table.insert_with(key.as_bytes().to_vec(), Vec::new);
+        table.value_mut(index).push(row as u32);
+    }
+
+    assert_eq!(3, table.len());
+    assert_eq!(Some(&vec![0, 2, 5]), table.get(&b"a".to_vec()));
+    assert_eq!(Some(&vec![1, 4]), table.get(&b"b".to_vec()));
+    assert_eq!(Some(2), table.find(&vec![]));
+    assert_eq!(None, table.find(&b"c".to_vec()));
+}
+
+#[test]
+fn test_hash_table_key_hash() {
+    // The fixed keys of the same value hash the same whatever their width.
+    assert_eq!(7_u64.hash_key(), 7_u8.hash_key());
+    assert_eq!(b"ab".to_vec().hash_key(), b"ab".to_vec().hash_key());
+    assert_ne!(1_u32.hash_key(), 2_u32.hash_key());
+}
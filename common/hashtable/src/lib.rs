@@ -0,0 +1,12 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod hash_table_test;
+
+mod hash_table;
+mod hash_table_key;
+
+pub use hash_table::HashTable;
+pub use hash_table_key::HashTableKey;
@@ -47,6 +47,18 @@ pub struct Table {
 
     /// name of parts that belong to this table.
     pub parts: HashSet<String>,
+
+    /// the COMMENT of this table.
+    #[serde(default)]
+    pub comment: String,
+
+    /// name of the table engine, e.g., `Parquet`.
+    #[serde(default)]
+    pub engine: String,
+
+    /// engine specific options of this table, e.g., `location`.
+    #[serde(default)]
+    pub options: HashMap<String, String>,
 }
 
 impl fmt::Display for Table {
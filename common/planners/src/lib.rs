@@ -21,6 +21,8 @@ mod plan_filter_test;
 #[cfg(test)]
 mod plan_having_test;
 #[cfg(test)]
+mod plan_join_test;
+#[cfg(test)]
 mod plan_limit_test;
 #[cfg(test)]
 mod plan_projection_test;
@@ -31,16 +33,20 @@ mod plan_scan_test;
 #[cfg(test)]
 mod plan_select_test;
 #[cfg(test)]
+mod plan_union_test;
+#[cfg(test)]
 mod test;
 
 mod plan_aggregator_final;
 mod plan_aggregator_partial;
 mod plan_broadcast;
 mod plan_builder;
+mod plan_copy;
 mod plan_database_create;
 mod plan_database_drop;
 mod plan_describe_table;
 mod plan_display;
+mod plan_display_graphviz;
 mod plan_display_indent;
 mod plan_empty;
 mod plan_explain;
@@ -59,10 +65,13 @@ mod plan_extras;
 mod plan_filter;
 mod plan_having;
 mod plan_insert_into;
+mod plan_join;
+mod plan_kill_query;
 mod plan_limit;
 mod plan_limit_by;
 mod plan_node;
 mod plan_partition;
+mod plan_partition_drop;
 mod plan_projection;
 mod plan_read_datasource;
 mod plan_remote;
@@ -77,6 +86,8 @@ mod plan_statistics;
 mod plan_subqueries_set;
 mod plan_table_create;
 mod plan_table_drop;
+mod plan_table_truncate;
+mod plan_union;
 mod plan_use_database;
 mod plan_visitor;
 
@@ -84,6 +95,7 @@ pub use plan_aggregator_final::AggregatorFinalPlan;
 pub use plan_aggregator_partial::AggregatorPartialPlan;
 pub use plan_broadcast::BroadcastPlan;
 pub use plan_builder::PlanBuilder;
+pub use plan_copy::CopyPlan;
 pub use plan_database_create::CreateDatabasePlan;
 pub use plan_database_create::DatabaseEngineType;
 pub use plan_database_create::DatabaseOptions;
@@ -103,10 +115,12 @@ pub use plan_expression_common::expand_wildcard;
 pub use plan_expression_common::expr_as_column_expr;
 pub use plan_expression_common::extract_aliases;
 pub use plan_expression_common::find_aggregate_exprs;
+pub use plan_expression_common::find_column_exprs;
 pub use plan_expression_common::find_columns_not_satisfy_exprs;
 pub use plan_expression_common::rebase_expr;
 pub use plan_expression_common::rebase_expr_from_input;
 pub use plan_expression_common::resolve_aliases_to_exprs;
+pub use plan_expression_common::resolve_grouping_exprs;
 pub use plan_expression_common::sort_to_inner_expr;
 pub use plan_expression_common::unwrap_alias_exprs;
 pub use plan_expression_function::add;
@@ -123,12 +137,17 @@ pub use plan_expression_visitor::Recursion;
 pub use plan_extras::Extras;
 pub use plan_filter::FilterPlan;
 pub use plan_having::HavingPlan;
+pub use plan_insert_into::BlockStream;
 pub use plan_insert_into::InsertIntoPlan;
+pub use plan_join::JoinPlan;
+pub use plan_join::JoinType;
+pub use plan_kill_query::KillQueryPlan;
 pub use plan_limit::LimitPlan;
 pub use plan_limit_by::LimitByPlan;
 pub use plan_node::PlanNode;
 pub use plan_partition::Part;
 pub use plan_partition::Partitions;
+pub use plan_partition_drop::DropPartitionPlan;
 pub use plan_projection::ProjectionPlan;
 pub use plan_read_datasource::ReadDataSourcePlan;
 pub use plan_remote::RemotePlan;
@@ -145,8 +164,9 @@ pub use plan_stage::StagePlan;
 pub use plan_statistics::Statistics;
 pub use plan_subqueries_set::SubQueriesSetPlan;
 pub use plan_table_create::CreateTablePlan;
-pub use plan_table_create::TableEngineType;
 pub use plan_table_create::TableOptions;
 pub use plan_table_drop::DropTablePlan;
+pub use plan_table_truncate::TruncateTablePlan;
+pub use plan_union::UnionPlan;
 pub use plan_use_database::UseDatabasePlan;
 pub use plan_visitor::PlanVisitor;
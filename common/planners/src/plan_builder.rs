@@ -5,11 +5,13 @@
 use std::sync::Arc;
 
 use common_datablocks::DataBlock;
+use common_datavalues::equal_coercion;
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
+use common_exception::ErrorCode;
 use common_exception::Result;
 
 use crate::col;
@@ -25,6 +27,8 @@ use crate::ExpressionPlan;
 use crate::Extras;
 use crate::FilterPlan;
 use crate::HavingPlan;
+use crate::JoinPlan;
+use crate::JoinType;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::PlanNode;
@@ -33,6 +37,7 @@ use crate::RewriteHelper;
 use crate::ScanPlan;
 use crate::SelectPlan;
 use crate::SortPlan;
+use crate::UnionPlan;
 
 pub enum AggregateMode {
     Partial,
@@ -202,7 +207,7 @@ impl PlanBuilder {
         _table_name: &str,
         table_schema: &DataSchema,
         projection: Option<Vec<usize>>,
-        table_args: Option<Expression>,
+        table_args: Vec<Expression>,
         limit: Option<usize>,
     ) -> Result<Self> {
         let table_schema = DataSchemaRef::new(table_schema.clone());
@@ -225,6 +230,7 @@ impl PlanBuilder {
                 projection,
                 filters: vec![],
                 limit,
+                ..Extras::default()
             },
         })))
     }
@@ -249,11 +255,241 @@ impl PlanBuilder {
         })))
     }
 
+    /// Apply a join with the right input, the keys at the same position of `left_keys` and
+    /// `right_keys` are compared, they must have the same type. The join is a merge join if both
+    /// inputs are sorted on the keys, whose order may then differ from the given one.
+    pub fn join(
+        &self,
+        join_type: JoinType,
+        right: &PlanNode,
+        left_keys: &[Expression],
+        right_keys: &[Expression],
+    ) -> Result<Self> {
+        if left_keys.is_empty() || left_keys.len() != right_keys.len() {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "JOIN must have the same number of left and right keys, left: {:?}, right: {:?}",
+                left_keys, right_keys
+            )));
+        }
+
+        let left_schema = self.plan.schema();
+        let right_schema = right.schema();
+        for (left_key, right_key) in left_keys.iter().zip(right_keys.iter()) {
+            validate_expression(left_key)?;
+            validate_expression(right_key)?;
+
+            let left_type = left_key.to_data_type(&left_schema)?;
+            let right_type = right_key.to_data_type(&right_schema)?;
+            if left_type != right_type {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "The keys of JOIN must have the same type, left: {:?} {:?}, right: {:?} {:?}",
+                    left_key, left_type, right_key, right_type
+                )));
+            }
+        }
+
+        let (left_keys, right_keys, sorted) =
+            match Self::sorted_keys(&self.plan, right, left_keys, right_keys) {
+                Some((left_keys, right_keys)) => (left_keys, right_keys, true),
+                None => (left_keys.to_vec(), right_keys.to_vec(), false),
+            };
+        Ok(Self::from(&PlanNode::Join(JoinPlan {
+            join_type,
+            left_keys,
+            right_keys,
+            condition: None,
+            sorted,
+            left: Arc::new(self.plan.clone()),
+            right: Arc::new(right.clone()),
+            schema: self.join_schema(join_type, right)?,
+        })))
+    }
+
+    /// Apply a join with the right input without keys, the pairs of the rows of both inputs are
+    /// joined if the condition is true for them, or all the pairs without condition, e.g. a
+    /// CROSS JOIN. The condition references the columns of both inputs.
+    pub fn nested_loop_join(
+        &self,
+        join_type: JoinType,
+        right: &PlanNode,
+        condition: Option<Expression>,
+    ) -> Result<Self> {
+        if let Some(condition) = &condition {
+            validate_expression(condition)?;
+
+            let mut fields = self.plan.schema().fields().clone();
+            fields.extend_from_slice(right.schema().fields());
+            let condition_type = condition.to_data_type(&DataSchemaRefExt::create(fields))?;
+            if condition_type != DataType::Boolean {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "The condition of JOIN must be Boolean, but got {:?} {:?}",
+                    condition, condition_type
+                )));
+            }
+        }
+
+        Ok(Self::from(&PlanNode::Join(JoinPlan {
+            join_type,
+            left_keys: vec![],
+            right_keys: vec![],
+            condition,
+            sorted: false,
+            left: Arc::new(self.plan.clone()),
+            right: Arc::new(right.clone()),
+            schema: self.join_schema(join_type, right)?,
+        })))
+    }
+
+    // The keys in the order which both inputs are sorted on, if the first columns of the sort
+    // order of each input are its keys, e.g. both inputs are sorted on (b, a) for the keys a, b.
+    fn sorted_keys(
+        left: &PlanNode,
+        right: &PlanNode,
+        left_keys: &[Expression],
+        right_keys: &[Expression],
+    ) -> Option<(Vec<Expression>, Vec<Expression>)> {
+        let left_order = left.sort_order();
+        let right_order = right.sort_order();
+        let mut unsorted = (0..left_keys.len()).collect::<Vec<_>>();
+        let mut sorted = (vec![], vec![]);
+        for (left_name, right_name) in left_order.iter().zip(right_order.iter()) {
+            if unsorted.is_empty() {
+                break;
+            }
+            let position = unsorted.iter().position(|key| {
+                &left_keys[*key].column_name() == left_name
+                    && &right_keys[*key].column_name() == right_name
+            })?;
+            let key = unsorted.remove(position);
+            sorted.0.push(left_keys[key].clone());
+            sorted.1.push(right_keys[key].clone());
+        }
+        match unsorted.is_empty() {
+            true => Some(sorted),
+            false => None,
+        }
+    }
+
+    fn join_schema(&self, join_type: JoinType, right: &PlanNode) -> Result<DataSchemaRef> {
+        let left_schema = self.plan.schema();
+        let right_schema = right.schema();
+
+        // The columns of a side are null for the unmatched rows of the other side.
+        let outer_field = |field: &DataField, outer: bool| {
+            let nullable = field.is_nullable() || outer;
+            field.clone().with_nullable(nullable)
+        };
+        let mut fields = left_schema
+            .fields()
+            .iter()
+            .map(|field| outer_field(field, join_type.keeps_right()))
+            .collect::<Vec<_>>();
+        // The semi and the anti joins output the columns of the left side only.
+        let right_fields: &[DataField] = match join_type.outputs_right() {
+            true => right_schema.fields().as_slice(),
+            false => &[],
+        };
+        for field in right_fields {
+            if fields.iter().any(|x| x.name() == field.name()) {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "Duplicate column {} in the inputs of JOIN",
+                    field.name()
+                )));
+            }
+            fields.push(outer_field(field, join_type.keeps_left()));
+        }
+        Ok(DataSchemaRefExt::create(fields))
+    }
+
+    /// Apply a UNION ALL with the other inputs, the columns at the same position of the inputs
+    /// are cast to their common type, and named after the columns of this input.
+    pub fn union_all(&self, others: &[PlanNode]) -> Result<Self> {
+        if others.is_empty() {
+            return Ok(Self::from(&self.plan));
+        }
+
+        let schema = self.plan.schema();
+        let mut fields = schema.fields().clone();
+        for other in others {
+            let other_schema = other.schema();
+            if other_schema.fields().len() != fields.len() {
+                return Result::Err(ErrorCode::BadArguments(format!(
+                    "The inputs of UNION must have the same number of columns, but {} != {}",
+                    fields.len(),
+                    other_schema.fields().len()
+                )));
+            }
+
+            for (field, other_field) in fields.iter_mut().zip(other_schema.fields()) {
+                let data_type = equal_coercion(field.data_type(), other_field.data_type())
+                    .map_err(|_| {
+                        ErrorCode::BadArguments(format!(
+                            "The columns {} and {} of UNION have no common type, {:?} and {:?}",
+                            field.name(),
+                            other_field.name(),
+                            field.data_type(),
+                            other_field.data_type()
+                        ))
+                    })?;
+                *field = DataField::new(
+                    field.name(),
+                    data_type,
+                    field.is_nullable() || other_field.is_nullable(),
+                )
+                .with_collation(field.collation().cloned());
+            }
+        }
+
+        let mut inputs = Vec::with_capacity(others.len() + 1);
+        for input in std::iter::once(&self.plan).chain(others.iter()) {
+            inputs.push(Arc::new(Self::coerce_union_input(input, &fields)?));
+        }
+
+        Ok(Self::from(&PlanNode::Union(UnionPlan {
+            inputs,
+            schema: DataSchemaRefExt::create(fields),
+        })))
+    }
+
+    /// Casts the columns of the input of UNION to the types of the union.
+    fn coerce_union_input(input: &PlanNode, fields: &[DataField]) -> Result<PlanNode> {
+        let input_schema = input.schema();
+        let coerced = input_schema
+            .fields()
+            .iter()
+            .zip(fields)
+            .all(|(x, field)| x.data_type() == field.data_type());
+        if coerced {
+            return Ok(input.clone());
+        }
+
+        let exprs = input_schema
+            .fields()
+            .iter()
+            .zip(fields)
+            .map(|(x, field)| match x.data_type() == field.data_type() {
+                true => col(x.name()),
+                false => Expression::Cast {
+                    expr: Box::new(col(x.name())),
+                    data_type: field.data_type().clone(),
+                }
+                .alias(x.name()),
+            })
+            .collect::<Vec<_>>();
+        Self::from(input).project(&exprs)?.build()
+    }
+
     pub fn sort(&self, exprs: &[Expression]) -> Result<Self> {
+        self.sort_with_limit(exprs, None)
+    }
+
+    /// Apply a sort which only keeps the first `limit` rows
+    pub fn sort_with_limit(&self, exprs: &[Expression], limit: Option<usize>) -> Result<Self> {
         Ok(Self::from(&PlanNode::Sort(SortPlan {
             order_by: exprs.to_vec(),
             schema: self.plan.schema(),
             input: self.wrap_subquery_plan(exprs)?,
+            limit,
         })))
     }
 
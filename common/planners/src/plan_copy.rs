@@ -0,0 +1,25 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+
+/// COPY INTO table FROM 'location' FORMAT format
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CopyPlan {
+    pub db_name: String,
+    pub tbl_name: String,
+    /// The columns of the table in the file, in the order of the file.
+    pub schema: DataSchemaRef,
+    pub location: String,
+    pub format: String,
+}
+
+impl CopyPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        Arc::new(DataSchema::empty())
+    }
+}
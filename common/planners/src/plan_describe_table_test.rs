@@ -26,9 +26,9 @@ fn test_describe_table_plan() -> Result<()> {
 
     let expect = "\
     DataSchema { fields: [\
-        DataField { name: \"Field\", data_type: Utf8, nullable: false }, \
-        DataField { name: \"Type\", data_type: Utf8, nullable: false }, \
-        DataField { name: \"Null\", data_type: Utf8, nullable: false }\
+        DataField { name: \"Field\", data_type: Utf8, nullable: false, default_expr: None, comment: None, collation: None }, \
+        DataField { name: \"Type\", data_type: Utf8, nullable: false, default_expr: None, comment: None, collation: None }, \
+        DataField { name: \"Null\", data_type: Utf8, nullable: false, default_expr: None, comment: None, collation: None }\
     ] }";
     let actual = format!("{:?}", describe.schema());
     assert_eq!(expect, actual);
@@ -7,6 +7,7 @@ use std::fmt::Display;
 
 use common_datavalues::DataSchema;
 
+use crate::plan_display_graphviz::PlanNodeGraphvizDisplay;
 use crate::plan_display_indent::PlanNodeIndentFormatDisplay;
 use crate::PlanNode;
 
@@ -15,22 +16,30 @@ impl PlanNode {
         PlanNodeIndentFormatDisplay::create(0, self, false)
     }
 
+    /// The indent format with the estimated rows and bytes of every node.
+    pub fn display_indent_with_statistics(&self) -> impl fmt::Display + '_ {
+        PlanNodeIndentFormatDisplay::create(0, self, false).with_statistics(true)
+    }
+
+    /// The indent format with the text of every node by `annotate`, e.g. the runtime statistics
+    /// of EXPLAIN ANALYZE.
+    pub fn display_indent_with<'a>(
+        &'a self,
+        annotate: &'a dyn Fn(&PlanNode) -> Option<String>,
+    ) -> impl fmt::Display + 'a {
+        PlanNodeIndentFormatDisplay::create(0, self, false).with_annotate(Some(annotate))
+    }
+
     pub fn display_graphviz(&self) -> impl fmt::Display + '_ {
-        struct Wrapper<'a>(&'a PlanNode);
-        impl<'a> fmt::Display for Wrapper<'a> {
-            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-                writeln!(
-                    f,
-                    "// Begin DataFuse GraphViz Plan (see https://graphviz.org)"
-                )?;
-                writeln!(f, "digraph {{")?;
-                // TODO()
-                writeln!(f, "}}")?;
-                writeln!(f, "// End DataFuse GraphViz Plan")?;
-                Ok(())
-            }
-        }
-        Wrapper(self)
+        PlanNodeGraphvizDisplay::create(self, &[])
+    }
+
+    /// The graphviz format with the stages on the cluster labelled with the names of its nodes.
+    pub fn display_graphviz_in_cluster<'a>(
+        &'a self,
+        cluster_nodes: &'a [String],
+    ) -> impl fmt::Display + 'a {
+        PlanNodeGraphvizDisplay::create(self, cluster_nodes)
     }
 
     pub fn display_schema(schema: &DataSchema) -> impl fmt::Display + '_ {
@@ -0,0 +1,162 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::plan_display_indent::PlanNodeIndentFormatDisplay;
+use crate::PlanNode;
+use crate::StageKind;
+
+#[derive(Clone, Copy, PartialEq)]
+enum StageLocation {
+    Local,
+    Cluster,
+}
+
+/// The plan in the DOT language of graphviz, the data flows along the edges from the inputs to
+/// the nodes.
+///
+/// The nodes are grouped by the stages split by the RedistributeStage and the Broadcast nodes,
+/// the stage of the root is the first one. Every stage is labelled with where it runs as the
+/// PlanScheduler places it: the sources run on the local node, the stage above an Expansive
+/// stage, a Normal stage or a Broadcast runs on all the nodes of the cluster, and the stage
+/// above a Convergent stage runs on the local node again.
+pub struct PlanNodeGraphvizDisplay<'a> {
+    node: &'a PlanNode,
+    cluster_nodes: &'a [String],
+}
+
+#[derive(Default)]
+struct Graph {
+    stages: Vec<StageLocation>,
+    // The stage and the label of the nodes.
+    nodes: Vec<(usize, String)>,
+    // From the input to the node.
+    edges: Vec<(usize, usize)>,
+}
+
+impl<'a> PlanNodeGraphvizDisplay<'a> {
+    /// The stages on the cluster are labelled with the names of `cluster_nodes`, or as all the
+    /// nodes if it is empty.
+    pub fn create(node: &'a PlanNode, cluster_nodes: &'a [String]) -> Self {
+        PlanNodeGraphvizDisplay {
+            node,
+            cluster_nodes,
+        }
+    }
+
+    fn stage_label(&self, stage: usize, location: StageLocation) -> String {
+        match location {
+            StageLocation::Local => format!("Stage {} on the local node", stage),
+            StageLocation::Cluster if self.cluster_nodes.is_empty() => {
+                format!("Stage {} on all the nodes", stage)
+            }
+            StageLocation::Cluster => {
+                format!("Stage {} on {}", stage, self.cluster_nodes.join(", "))
+            }
+        }
+    }
+}
+
+impl Graph {
+    /// Adds the node and its inputs to the stage, returns where the output of the node is.
+    fn visit(&mut self, node: &PlanNode, stage: usize, parent: Option<usize>) -> StageLocation {
+        let (stage, output) = match node {
+            PlanNode::Stage(plan) => (self.add_stage(), match plan.kind {
+                StageKind::Convergent => Some(StageLocation::Local),
+                StageKind::Normal | StageKind::Expansive => Some(StageLocation::Cluster),
+            }),
+            PlanNode::Broadcast(_) => (self.add_stage(), Some(StageLocation::Cluster)),
+            _ => (stage, None),
+        };
+
+        // The nodes only showing their inputs, e.g. Select, are skipped.
+        let label = format!(
+            "{}",
+            PlanNodeIndentFormatDisplay::create(0, node, false).with_inputs(false)
+        );
+        let parent = match label.is_empty() {
+            true => parent,
+            false => {
+                let id = self.nodes.len();
+                self.nodes.push((stage, label));
+                if let Some(parent) = parent {
+                    self.edges.push((id, parent));
+                }
+                Some(id)
+            }
+        };
+
+        let mut location = StageLocation::Local;
+        for input in node.inputs() {
+            if matches!(input.as_ref(), PlanNode::Empty(_)) {
+                continue;
+            }
+            if self.visit(input.as_ref(), stage, parent) == StageLocation::Cluster {
+                location = StageLocation::Cluster;
+            }
+        }
+
+        match output {
+            None => location,
+            Some(output) => {
+                self.stages[stage] = location;
+                output
+            }
+        }
+    }
+
+    fn add_stage(&mut self) -> usize {
+        self.stages.push(StageLocation::Local);
+        self.stages.len() - 1
+    }
+}
+
+impl<'a> fmt::Display for PlanNodeGraphvizDisplay<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut graph = Graph::default();
+        let root_stage = graph.add_stage();
+        let location = graph.visit(self.node, root_stage, None);
+        graph.stages[root_stage] = location;
+
+        writeln!(
+            f,
+            "// Begin DataFuse GraphViz Plan (see https://graphviz.org)"
+        )?;
+        writeln!(f, "digraph {{")?;
+        for (stage, location) in graph.stages.iter().enumerate() {
+            writeln!(f, "  subgraph cluster_{} {{", stage)?;
+            writeln!(
+                f,
+                "    label = \"{}\";",
+                escape(&self.stage_label(stage, *location))
+            )?;
+            for (id, (_, label)) in graph
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, (node_stage, _))| *node_stage == stage)
+            {
+                writeln!(
+                    f,
+                    "    node_{} [shape=box, label=\"{}\"];",
+                    id,
+                    escape(label)
+                )?;
+            }
+            writeln!(f, "  }}")?;
+        }
+        for (from, to) in graph.edges.iter() {
+            writeln!(f, "  node_{} -> node_{};", from, to)?;
+        }
+        writeln!(f, "}}")?;
+        writeln!(f, "// End DataFuse GraphViz Plan")?;
+        Ok(())
+    }
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -8,24 +8,32 @@ use std::fmt::Formatter;
 use crate::plan_broadcast::BroadcastPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DropDatabasePlan;
+use crate::DropPartitionPlan;
 use crate::DropTablePlan;
 use crate::Expression;
 use crate::ExpressionPlan;
+use crate::JoinPlan;
 use crate::LimitPlan;
 use crate::PlanNode;
 use crate::ProjectionPlan;
 use crate::ReadDataSourcePlan;
 use crate::SortPlan;
 use crate::StagePlan;
+use crate::Statistics;
 use crate::SubQueriesSetPlan;
+use crate::TruncateTablePlan;
 
 pub struct PlanNodeIndentFormatDisplay<'a> {
     indent: usize,
     node: &'a PlanNode,
     printed_indent: bool,
+    with_statistics: bool,
+    with_inputs: bool,
+    annotate: Option<&'a dyn Fn(&PlanNode) -> Option<String>>,
 }
 
 impl<'a> PlanNodeIndentFormatDisplay<'a> {
@@ -34,8 +42,33 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
             indent,
             node,
             printed_indent: printed,
+            with_statistics: false,
+            with_inputs: true,
+            annotate: None,
         }
     }
+
+    /// Appends the estimated statistics of every node, see `PlanNode::estimate_statistics`.
+    pub fn with_statistics(mut self, with_statistics: bool) -> Self {
+        self.with_statistics = with_statistics;
+        self
+    }
+
+    /// Formats the inputs of the node, the node alone is formatted without them, and the nodes
+    /// only showing their inputs, e.g. Select, are empty.
+    pub fn with_inputs(mut self, with_inputs: bool) -> Self {
+        self.with_inputs = with_inputs;
+        self
+    }
+
+    /// Appends the text of every node by `annotate` in brackets, if any.
+    pub fn with_annotate(
+        mut self,
+        annotate: Option<&'a dyn Fn(&PlanNode) -> Option<String>>,
+    ) -> Self {
+        self.annotate = annotate;
+        self
+    }
 }
 
 impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
@@ -53,6 +86,8 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::AggregatorFinal(plan) => Self::format_aggregator_final(f, plan),
             PlanNode::Filter(plan) => write!(f, "Filter: {:?}", plan.predicate),
             PlanNode::Having(plan) => write!(f, "Having: {:?}", plan.predicate),
+            PlanNode::Join(plan) => Self::format_join(f, plan),
+            PlanNode::Union(_) => write!(f, "Union All"),
             PlanNode::Sort(plan) => Self::format_sort(f, plan),
             PlanNode::Limit(plan) => Self::format_limit(f, plan),
             PlanNode::SubQueryExpression(plan) => Self::format_subquery_expr(f, plan),
@@ -61,6 +96,10 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             PlanNode::DropDatabase(plan) => Self::format_drop_database(f, plan),
             PlanNode::CreateTable(plan) => Self::format_create_table(f, plan),
             PlanNode::DropTable(plan) => Self::format_drop_table(f, plan),
+            PlanNode::TruncateTable(plan) => Self::format_truncate_table(f, plan),
+            PlanNode::DropPartition(plan) => Self::format_drop_partition(f, plan),
+            PlanNode::Copy(plan) => Self::format_copy(f, plan),
+            _ if !self.with_inputs => return fmt::Result::Ok(()),
             _ => {
                 let mut printed = true;
 
@@ -74,6 +113,8 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
                     }
 
                     PlanNodeIndentFormatDisplay::create(self.indent, input.as_ref(), printed)
+                        .with_statistics(self.with_statistics)
+                        .with_annotate(self.annotate)
                         .fmt(f)?;
                     printed = true;
                 }
@@ -82,6 +123,17 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
         }?;
 
+        if self.with_statistics {
+            Self::format_statistics(f, &self.node.estimate_statistics())?;
+        }
+        if let Some(text) = self.annotate.and_then(|annotate| annotate(self.node)) {
+            write!(f, " [{}]", text)?;
+        }
+
+        if !self.with_inputs {
+            return fmt::Result::Ok(());
+        }
+
         let new_indent = self.indent + 1;
         for input in self.node.inputs() {
             if matches!(input.as_ref(), PlanNode::Empty(_)) {
@@ -89,7 +141,10 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
             }
 
             writeln!(f)?;
-            PlanNodeIndentFormatDisplay::create(new_indent, &input, false).fmt(f)?;
+            PlanNodeIndentFormatDisplay::create(new_indent, &input, false)
+                .with_statistics(self.with_statistics)
+                .with_annotate(self.annotate)
+                .fmt(f)?;
         }
 
         fmt::Result::Ok(())
@@ -97,6 +152,19 @@ impl<'a> fmt::Display for PlanNodeIndentFormatDisplay<'a> {
 }
 
 impl<'a> PlanNodeIndentFormatDisplay<'a> {
+    fn format_statistics(f: &mut Formatter, statistics: &Statistics) -> fmt::Result {
+        let kind = if statistics.is_exact {
+            "exact"
+        } else {
+            "estimated"
+        };
+        write!(
+            f,
+            " [{} rows: {}, bytes: {}]",
+            kind, statistics.read_rows, statistics.read_bytes
+        )
+    }
+
     fn format_stage(f: &mut Formatter, plan: &StagePlan) -> fmt::Result {
         write!(f, "RedistributeStage[expr: {:?}]", plan.scatters_expr)
     }
@@ -122,6 +190,37 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         fmt::Result::Ok(())
     }
 
+    fn format_join(f: &mut Formatter, plan: &JoinPlan) -> fmt::Result {
+        // A join without keys and condition joins all the pairs of the rows.
+        if plan.is_nested_loop() {
+            return match &plan.condition {
+                Some(condition) => {
+                    write!(f, "Join: {}, condition: {:?}", plan.join_type, condition)
+                }
+                None => write!(f, "Join: {}, condition: true", plan.join_type),
+            };
+        }
+
+        write!(f, "Join: {}, on: [", plan.join_type)?;
+        for (i, (left, right)) in plan
+            .left_keys
+            .iter()
+            .zip(plan.right_keys.iter())
+            .enumerate()
+        {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{:?} = {:?}", left, right)?;
+        }
+        write!(f, "]")?;
+        // Both inputs are sorted on the keys.
+        if plan.sorted {
+            write!(f, ", merge")?;
+        }
+        Ok(())
+    }
+
     fn format_expression(f: &mut Formatter, plan: &ExpressionPlan) -> fmt::Result {
         write!(f, "Expression: ")?;
         for i in 0..plan.exprs.len() {
@@ -169,6 +268,9 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
                 expr.to_data_type(&plan.schema()).unwrap()
             )?;
         }
+        if let Some(limit) = plan.limit {
+            write!(f, " (limit: {})", limit)?;
+        }
 
         fmt::Result::Ok(())
     }
@@ -201,7 +303,23 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
             PlanNode::display_schema(plan.schema.as_ref()),
             plan.statistics.read_rows,
             plan.statistics.read_bytes,
-        )
+        )?;
+
+        // The nodes the store executes above the read of every part, the outermost first.
+        if let Some(store_plan) = &plan.scan_plan.push_downs.store_plan {
+            let mut nodes = vec![];
+            let mut node = store_plan.as_ref().clone();
+            while !matches!(node, PlanNode::ReadSource(_)) && !node.inputs().is_empty() {
+                nodes.push(
+                    PlanNodeIndentFormatDisplay::create(0, &node, true)
+                        .with_inputs(false)
+                        .to_string(),
+                );
+                node = node.input(0).as_ref().clone();
+            }
+            write!(f, ", store plan: [{}]", nodes.join(", "))?;
+        }
+        Ok(())
     }
 
     fn format_create_database(f: &mut Formatter, plan: &CreateDatabasePlan) -> fmt::Result {
@@ -219,8 +337,7 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
     fn format_create_table(f: &mut Formatter, plan: &CreateTablePlan) -> fmt::Result {
         write!(f, "Create table {:}.{:}", plan.db, plan.table)?;
         write!(f, " {:},", plan.schema)?;
-        // need engine to impl Display
-        write!(f, " engine: {},", plan.engine.to_string())?;
+        write!(f, " engine: {},", plan.engine)?;
         write!(f, " if_not_exists:{:},", plan.if_not_exists)?;
         write!(f, " option: {:?}", plan.options)
     }
@@ -229,4 +346,23 @@ impl<'a> PlanNodeIndentFormatDisplay<'a> {
         write!(f, "Drop table {:}.{:},", plan.db, plan.table)?;
         write!(f, " if_exists:{:}", plan.if_exists)
     }
+
+    fn format_truncate_table(f: &mut Formatter, plan: &TruncateTablePlan) -> fmt::Result {
+        write!(f, "Truncate table {:}.{:}", plan.db, plan.table)
+    }
+
+    fn format_drop_partition(f: &mut Formatter, plan: &DropPartitionPlan) -> fmt::Result {
+        write!(
+            f,
+            "Drop partition {:} of table {:}.{:}",
+            plan.partition, plan.db, plan.table
+        )
+    }
+
+    fn format_copy(f: &mut Formatter, plan: &CopyPlan) -> fmt::Result {
+        write!(f, "Copy into {:}.{:}", plan.db_name, plan.tbl_name)?;
+        write!(f, " {:},", plan.schema)?;
+        write!(f, " from: {:},", plan.location)?;
+        write!(f, " format: {:}", plan.format)
+    }
 }
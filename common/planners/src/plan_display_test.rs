@@ -3,12 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRefExt;
 use common_datavalues::DataType;
 use common_exception::Result;
 
+use crate::test::Test;
 use crate::*;
 
 #[test]
@@ -26,14 +28,135 @@ fn test_plan_display_indent() -> Result<()> {
         db: "foo".into(),
         table: "bar".into(),
         schema,
-        engine: TableEngineType::JsonEachRaw,
+        engine: "JSON".to_string(),
         options,
     });
 
     assert_eq!(
-        "Create table foo.bar DataField { name: \"a\", data_type: Int64, nullable: false }, engine: JSON, if_not_exists:true, option: {\"opt_foo\": \"opt_bar\"}",
+        "Create table foo.bar DataField { name: \"a\", data_type: Int64, nullable: false, default_expr: None, comment: None, collation: None }, engine: JSON, if_not_exists:true, option: {\"opt_foo\": \"opt_bar\"}",
         format!("{:?}", plan_create)
     );
 
     Ok(())
 }
+
+#[test]
+fn test_plan_display_indent_with_statistics() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+
+    let plan = PlanBuilder::from(&source)
+        .filter(col("number").eq(lit(1i64)))?
+        .limit(10)?
+        .build()?;
+    assert_eq!(
+        "Limit: 10 [estimated rows: 10, bytes: 80]\
+        \n  Filter: (number = 1) [estimated rows: 5000, bytes: 40000]\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000] [exact rows: 10000, bytes: 80000]",
+        format!("{}", plan.display_indent_with_statistics())
+    );
+
+    let plan = PlanBuilder::from(&source)
+        .project(&[col("number")])?
+        .limit_offset(Some(10), 9995)?
+        .build()?;
+    assert_eq!(
+        "Limit: 10, 9995 [exact rows: 5, bytes: 40]\
+        \n  Projection: number:UInt64 [exact rows: 10000, bytes: 80000]\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000] [exact rows: 10000, bytes: 80000]",
+        format!("{}", plan.display_indent_with_statistics())
+    );
+
+    // The indent format without the statistics is unchanged.
+    assert_eq!(
+        "Limit: 10, 9995\
+        \n  Projection: number:UInt64\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]",
+        format!("{:?}", plan)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_plan_display_indent_with() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+
+    let plan = PlanBuilder::from(&source)
+        .filter(col("number").eq(lit(1i64)))?
+        .limit(10)?
+        .build()?;
+    let annotate = |node: &PlanNode| match node {
+        PlanNode::ReadSource(_) => None,
+        node => Some(format!("{} columns", node.schema().fields().len())),
+    };
+    assert_eq!(
+        "Limit: 10 [1 columns]\
+        \n  Filter: (number = 1) [1 columns]\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]",
+        format!("{}", plan.display_indent_with(&annotate))
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_plan_display_graphviz() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let source = Test::create().generate_source_plan_for_test(10000)?;
+    let expansive = PlanNode::Stage(StagePlan {
+        kind: StageKind::Expansive,
+        input: Arc::new(source),
+        scatters_expr: lit(0u64),
+    });
+    let filter = PlanBuilder::from(&expansive)
+        .filter(col("number").eq(lit(1i64)))?
+        .build()?;
+    let convergent = PlanNode::Stage(StagePlan {
+        kind: StageKind::Convergent,
+        input: Arc::new(filter),
+        scatters_expr: lit(0u64),
+    });
+    let plan = PlanBuilder::from(&convergent)
+        .limit(10)?
+        .select()?
+        .build()?;
+
+    let cluster_nodes = vec!["node1".to_string(), "node2".to_string()];
+    assert_eq!(
+        "// Begin DataFuse GraphViz Plan (see https://graphviz.org)\
+        \ndigraph {\
+        \n  subgraph cluster_0 {\
+        \n    label = \"Stage 0 on the local node\";\
+        \n    node_0 [shape=box, label=\"Limit: 10\"];\
+        \n  }\
+        \n  subgraph cluster_1 {\
+        \n    label = \"Stage 1 on node1, node2\";\
+        \n    node_1 [shape=box, label=\"RedistributeStage[expr: 0]\"];\
+        \n    node_2 [shape=box, label=\"Filter: (number = 1)\"];\
+        \n  }\
+        \n  subgraph cluster_2 {\
+        \n    label = \"Stage 2 on the local node\";\
+        \n    node_3 [shape=box, label=\"RedistributeStage[expr: 0]\"];\
+        \n    node_4 [shape=box, label=\"ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\"];\
+        \n  }\
+        \n  node_1 -> node_0;\
+        \n  node_2 -> node_1;\
+        \n  node_3 -> node_2;\
+        \n  node_4 -> node_3;\
+        \n}\
+        \n// End DataFuse GraphViz Plan\
+        \n",
+        format!("{}", plan.display_graphviz_in_cluster(&cluster_nodes))
+    );
+
+    // The stages on the cluster are on all the nodes without their names.
+    let actual = format!("{}", plan.display_graphviz());
+    assert!(actual.contains("label = \"Stage 1 on all the nodes\";"));
+
+    Ok(())
+}
@@ -16,6 +16,13 @@ pub enum ExplainType {
     Syntax,
     Graph,
     Pipeline,
+    /// The processors of the pipeline in the DOT language of graphviz.
+    PipelineGraph,
+    /// The plan with the estimated statistics after every optimizer.
+    Statistics,
+    /// The plan with the rows, the bytes and the elapsed time of the blocks produced by every
+    /// node, the query is run to collect them.
+    Analyze,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
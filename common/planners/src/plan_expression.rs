@@ -14,6 +14,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
 use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::scalars::Function;
 use common_functions::scalars::FunctionFactory;
 use lazy_static::lazy_static;
 
@@ -158,13 +159,88 @@ impl Expression {
     pub fn to_data_field(&self, input_schema: &DataSchemaRef) -> Result<DataField> {
         let name = self.column_name();
         self.to_data_type(input_schema).and_then(|return_type| {
-            self.nullable(input_schema)
-                .map(|nullable| DataField::new(&name, return_type, nullable))
+            self.nullable(input_schema).map(|nullable| {
+                DataField::new(&name, return_type, nullable)
+                    .with_collation(self.collation(input_schema))
+            })
         })
     }
 
-    // TODO
-    pub fn nullable(&self, _input_schema: &DataSchemaRef) -> Result<bool> {
+    /// The collation of a column is kept by the expressions which output the column as it is.
+    pub fn collation(&self, input_schema: &DataSchemaRef) -> Option<String> {
+        match self {
+            Expression::Column(name) => input_schema
+                .field_with_name(name)
+                .ok()
+                .and_then(|field| field.collation().cloned()),
+            Expression::Alias(_, expr) | Expression::Sort { expr, .. } => {
+                expr.collation(input_schema)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the expression may be NULL, the function of a nullable argument is nullable unless
+    /// the function handles the NULL itself, e.g. `isNull(x)`.
+    pub fn nullable(&self, input_schema: &DataSchemaRef) -> Result<bool> {
+        match self {
+            Expression::Alias(_, expr) | Expression::Sort { expr, .. } => {
+                expr.nullable(input_schema)
+            }
+            Expression::Column(s) => Ok(input_schema.field_with_name(s)?.is_nullable()),
+            Expression::Literal { value, .. } => Ok(value.is_null()),
+            Expression::Subquery { .. } => Ok(false),
+            // The scalar subquery of no rows is NULL.
+            Expression::ScalarSubquery { .. } => Ok(true),
+            Expression::BinaryExpression { op, left, right } => {
+                let func = FunctionFactory::get(op)?;
+                Self::function_nullable(
+                    func.as_ref(),
+                    &[*left.clone(), *right.clone()],
+                    input_schema,
+                )
+            }
+            Expression::UnaryExpression { op, expr } => {
+                let func = FunctionFactory::get(op)?;
+                Self::function_nullable(func.as_ref(), &[*expr.clone()], input_schema)
+            }
+            Expression::ScalarFunction { op, args } => {
+                let func = FunctionFactory::get(op)?;
+                Self::function_nullable(func.as_ref(), args, input_schema)
+            }
+            Expression::AggregateFunction { .. } => {
+                let func = self.to_aggregate_function(input_schema)?;
+                func.nullable(input_schema)
+            }
+            Expression::Wildcard => Result::Err(ErrorCode::IllegalDataType(
+                "Wildcard expressions are not valid to get nullable",
+            )),
+            // The strings which cannot be parsed are cast to NULL.
+            Expression::Cast { expr, .. } => {
+                Ok(expr.nullable(input_schema)?
+                    || expr.to_data_type(input_schema)? == DataType::Utf8)
+            }
+        }
+    }
+
+    fn function_nullable(
+        func: &dyn Function,
+        args: &[Expression],
+        input_schema: &DataSchemaRef,
+    ) -> Result<bool> {
+        if func.nullable(input_schema)? {
+            return Ok(true);
+        }
+
+        if !func.passthrough_null() {
+            return Ok(false);
+        }
+
+        for arg in args {
+            if arg.nullable(input_schema)? {
+                return Ok(true);
+            }
+        }
         Ok(false)
     }
 
@@ -2,7 +2,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use common_datavalues::DataCollation;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::scalars::FunctionFactory;
 
@@ -101,8 +104,9 @@ impl ExpressionChain {
             }
 
             Expression::BinaryExpression { op, left, right } => {
-                self.add_expr(left)?;
-                self.add_expr(right)?;
+                let (left, right) = self.collated_operands(op, left, right)?;
+                self.add_expr(&left)?;
+                self.add_expr(&right)?;
 
                 let func = FunctionFactory::get(op)?;
                 let arg_types = vec![
@@ -191,4 +195,55 @@ impl ExpressionChain {
         }
         Ok(())
     }
+
+    /// The operands of a comparison are compared by their collation keys if any of them is a
+    /// column with a collation.
+    fn collated_operands(
+        &self,
+        op: &str,
+        left: &Expression,
+        right: &Expression,
+    ) -> Result<(Expression, Expression)> {
+        let uncollated = Ok((left.clone(), right.clone()));
+        let is_comparison = matches!(
+            op.to_lowercase().as_str(),
+            "=" | "!="
+                | "<>"
+                | "<"
+                | "<="
+                | ">"
+                | ">="
+                | "<=>"
+                | "is distinct from"
+                | "is not distinct from"
+                | "like"
+                | "not like"
+        );
+        if !is_comparison {
+            return uncollated;
+        }
+
+        let collation = match (left.collation(&self.schema), right.collation(&self.schema)) {
+            (Some(left), Some(right)) if left != right => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Illegal mix of collations {} and {} for operation {}",
+                    left, right, op
+                )));
+            }
+            (Some(collation), _) | (None, Some(collation)) => collation,
+            (None, None) => return uncollated,
+        };
+        if DataCollation::try_create(&collation)?.is_binary() {
+            return uncollated;
+        }
+
+        let collate = |expr: &Expression| Expression::ScalarFunction {
+            op: "collate".to_string(),
+            args: vec![
+                expr.clone(),
+                Expression::create_literal(DataValue::Utf8(Some(collation.clone()))),
+            ],
+        };
+        Ok((collate(left), collate(right)))
+    }
 }
@@ -5,6 +5,7 @@
 use std::collections::HashMap;
 
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -342,3 +343,59 @@ pub fn unwrap_alias_exprs(expr: &Expression) -> Result<Expression> {
         _ => Ok(None),
     })
 }
+
+/// Rebuilds an `expr` with `GROUPING(a, ...)` and `GROUPING_ID()` replaced by their values. The
+/// bit of an argument is 1 if the row is a super-aggregate row over it, and the first argument is
+/// the most significant bit, `GROUPING_ID()` is the bits of all the GROUP BY expressions. Without
+/// ROLLUP or CUBE every row is a genuine group, so the values are 0.
+pub fn resolve_grouping_exprs(
+    expr: &Expression,
+    group_by_exprs: &[Expression],
+    aliases: &HashMap<String, Expression>,
+) -> Result<Expression> {
+    clone_with_replacement(expr, &|nest_exprs| match nest_exprs {
+        Expression::ScalarFunction { op, args }
+            if op.eq_ignore_ascii_case("grouping") || op.eq_ignore_ascii_case("grouping_id") =>
+        {
+            if group_by_exprs.is_empty() {
+                return Err(ErrorCode::IllegalAggregateExp(format!(
+                    "{} is only allowed with GROUP BY",
+                    nest_exprs.column_name()
+                )));
+            }
+
+            let keys = match args.is_empty() {
+                true if op.eq_ignore_ascii_case("grouping") => {
+                    return Err(ErrorCode::NumberArgumentsNotMatch(
+                        "grouping expect to have at least 1 arguments, but got 0",
+                    ))
+                }
+                true => group_by_exprs.to_vec(),
+                false => args.clone(),
+            };
+            if keys.len() > 64 {
+                return Err(ErrorCode::NumberArgumentsNotMatch(format!(
+                    "{} expect to have at most 64 arguments, but got {}",
+                    op,
+                    keys.len()
+                )));
+            }
+
+            for key in keys.iter() {
+                let key = resolve_aliases_to_exprs(key, aliases)?;
+                if !group_by_exprs.contains(&key) {
+                    return Err(ErrorCode::IllegalAggregateExp(format!(
+                        "Argument `{:?}` of {} is not in GROUP BY",
+                        key, op
+                    )));
+                }
+            }
+
+            Ok(Some(Expression::Literal {
+                value: DataValue::UInt64(Some(0)),
+                column_name: Some(nest_exprs.column_name()),
+            }))
+        }
+        _ => Ok(None),
+    })
+}
@@ -89,6 +89,22 @@ impl Expression {
         binary_expr(self.clone(), "<=", other)
     }
 
+    /// Is null.
+    pub fn is_null(&self) -> Expression {
+        Expression::ScalarFunction {
+            op: "isnull".to_string(),
+            args: vec![self.clone()],
+        }
+    }
+
+    /// Is not null.
+    pub fn is_not_null(&self) -> Expression {
+        Expression::ScalarFunction {
+            op: "isnotnull".to_string(),
+            args: vec![self.clone()],
+        }
+    }
+
     /// Alias.
     pub fn alias(&self, alias: &str) -> Expression {
         Expression::Alias(alias.to_string(), Box::from(self.clone()))
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_datavalues::prelude::*;
@@ -111,3 +112,184 @@ fn test_expression_validate() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn test_expression_collation() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Utf8, false)
+            .with_collation(Some("utf8_general_ci".to_string())),
+        DataField::new("b", DataType::Utf8, false),
+        DataField::new("c", DataType::Utf8, false)
+            .with_collation(Some("utf8_unicode_ci".to_string())),
+    ]);
+
+    // The columns output as they are keep their collations.
+    let field = col("a").alias("x").to_data_field(&schema)?;
+    assert_eq!(Some(&"utf8_general_ci".to_string()), field.collation());
+    let field = add(col("a"), col("a")).to_data_field(&schema)?;
+    assert_eq!(None, field.collation());
+
+    // The comparisons of a collated column compare the collation keys.
+    let chain = ExpressionChain::try_create(schema.clone(), &[col("b").eq(col("a"))])?;
+    let functions = chain
+        .actions
+        .iter()
+        .filter_map(|action| match action {
+            ExpressionAction::Function(function) => Some(function.name.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "collate(b, utf8_general_ci)",
+            "collate(a, utf8_general_ci)",
+            "(b = a)",
+        ],
+        functions
+    );
+
+    let chain = ExpressionChain::try_create(schema.clone(), &[col("b").eq(lit("x"))])?;
+    assert_eq!(chain.actions.len(), 3);
+
+    let result = ExpressionChain::try_create(schema, &[col("a").lt(col("c"))]);
+    assert_eq!(
+        "Code: 6, displayText = Illegal mix of collations utf8_general_ci and utf8_unicode_ci for operation <.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_expression_nullable() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Int64, true),
+        DataField::new("c", DataType::Utf8, false),
+    ]);
+
+    struct Test {
+        desc: &'static str,
+        expression: Expression,
+        nullable: bool,
+    }
+
+    let cases = vec![
+        Test {
+            desc: "column",
+            expression: col("a"),
+            nullable: false,
+        },
+        Test {
+            desc: "nullable-column",
+            expression: col("b").alias("x"),
+            nullable: true,
+        },
+        Test {
+            desc: "null-literal",
+            expression: Expression::create_literal(DataValue::Null),
+            nullable: true,
+        },
+        Test {
+            desc: "function-of-columns",
+            expression: add(col("a"), lit(1)),
+            nullable: false,
+        },
+        Test {
+            desc: "function-of-nullable-column",
+            expression: add(col("a"), col("b")).gt(lit(1)),
+            nullable: true,
+        },
+        Test {
+            desc: "is-null-of-nullable-column",
+            expression: col("b").is_null(),
+            nullable: false,
+        },
+        Test {
+            desc: "is-not-null-of-nullable-column",
+            expression: not(col("b").is_not_null()),
+            nullable: false,
+        },
+        Test {
+            desc: "cast-of-string",
+            expression: Expression::Cast {
+                expr: Box::new(col("c")),
+                data_type: DataType::Int64,
+            },
+            nullable: true,
+        },
+        Test {
+            desc: "sum-of-column",
+            expression: sum(col("a")),
+            nullable: true,
+        },
+        Test {
+            desc: "count-of-nullable-column",
+            expression: Expression::AggregateFunction {
+                op: "count".to_string(),
+                distinct: false,
+                args: vec![col("b")],
+            },
+            nullable: false,
+        },
+    ];
+
+    for t in cases {
+        assert_eq!(t.nullable, t.expression.nullable(&schema)?, "{}", t.desc);
+        assert_eq!(
+            t.nullable,
+            t.expression.to_data_field(&schema)?.is_nullable(),
+            "{}",
+            t.desc
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_resolve_grouping_exprs() -> Result<()> {
+    let group_by_exprs = vec![modular(col("number"), lit(3)), col("name")];
+    let mut aliases = HashMap::new();
+    aliases.insert("id".to_string(), modular(col("number"), lit(3)));
+
+    let grouping = |op: &str, args: Vec<Expression>| Expression::ScalarFunction {
+        op: op.to_string(),
+        args,
+    };
+
+    // Every row is a genuine group, the name of the value is the name of the function.
+    let expr = add(grouping("GROUPING", vec![col("id"), col("name")]), lit(1));
+    let resolved = resolve_grouping_exprs(&expr, &group_by_exprs, &aliases)?;
+    assert_eq!(
+        add(
+            Expression::Literal {
+                value: DataValue::UInt64(Some(0)),
+                column_name: Some("GROUPING(id, name)".to_string()),
+            },
+            lit(1)
+        ),
+        resolved
+    );
+
+    let expr = grouping("grouping_id", vec![]);
+    let resolved = resolve_grouping_exprs(&expr, &group_by_exprs, &aliases)?;
+    assert_eq!("grouping_id()", resolved.column_name());
+
+    let expr = grouping("grouping", vec![]);
+    assert_eq!(
+        "Code: 28, displayText = grouping expect to have at least 1 arguments, but got 0.",
+        resolve_grouping_exprs(&expr, &group_by_exprs, &aliases)
+            .unwrap_err()
+            .to_string()
+    );
+
+    let expr = grouping("grouping", vec![col("number")]);
+    assert_eq!(
+        "Code: 26, displayText = Argument `number` of grouping is not in GROUP BY.",
+        resolve_grouping_exprs(&expr, &group_by_exprs, &aliases)
+            .unwrap_err()
+            .to_string()
+    );
+    Ok(())
+}
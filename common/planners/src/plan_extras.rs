@@ -2,7 +2,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use common_datavalues::DataValue;
+
 use crate::Expression;
+use crate::PlanNode;
 
 /// Extras is a wrapper for push down items.
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
@@ -13,6 +16,19 @@ pub struct Extras {
     pub filters: Vec<Expression>,
     /// Optional limit to skip read
     pub limit: Option<usize>,
+    /// Optional MIN/MAX aggregates of the columns without filters, the parts which can not hold
+    /// their results may be skipped
+    #[serde(default)]
+    pub aggregates: Vec<Expression>,
+    /// Optional plan executed by the store on the rows of every part, e.g. the filters and the
+    /// partial aggregates above the read, the store sends the results of the plan instead of the
+    /// rows
+    #[serde(default)]
+    pub store_plan: Option<Box<PlanNode>>,
+    /// The default values of the columns the parts written before the columns were added do not
+    /// have
+    #[serde(default)]
+    pub default_values: Vec<(String, DataValue)>,
 }
 
 impl Extras {
@@ -21,6 +37,9 @@ impl Extras {
             projection: None,
             filters: vec![],
             limit: None,
+            aggregates: vec![],
+            store_plan: None,
+            default_values: vec![],
         }
     }
 }
@@ -6,11 +6,18 @@ use std::sync::Arc;
 
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_exception::Result;
 use common_infallible::Mutex;
 
+use crate::Expression;
+use crate::PlanNode;
+
 /// please do not keep this, this code is just for test purpose
-type BlockStream =
-    std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
+/// The input of an insert may fail in the middle, e.g. the SELECT of an INSERT SELECT,
+/// the table must not keep the data appended by a failed input.
+pub type BlockStream = std::pin::Pin<
+    Box<dyn futures::stream::Stream<Item = Result<DataBlock>> + Sync + Send + 'static>,
+>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone)]
 pub struct InsertIntoPlan {
@@ -20,6 +27,17 @@ pub struct InsertIntoPlan {
 
     #[serde(skip, default = "InsertIntoPlan::empty_stream")]
     pub input_stream: Arc<Mutex<Option<BlockStream>>>,
+
+    /// The SELECT of an INSERT SELECT, whose output columns are the inserted columns,
+    /// the input stream is built from it at execution.
+    #[serde(default)]
+    pub select_plan: Option<Box<PlanNode>>,
+
+    /// The writable table function inserted into instead of a table, e.g. INSERT INTO FUNCTION file(...).
+    #[serde(default)]
+    pub table_function: Option<String>,
+    #[serde(default)]
+    pub table_args: Vec<Expression>,
 }
 
 impl PartialEq for InsertIntoPlan {
@@ -27,6 +45,9 @@ impl PartialEq for InsertIntoPlan {
         self.db_name == other.db_name
             && self.tbl_name == other.tbl_name
             && self.schema == other.schema
+            && self.select_plan == other.select_plan
+            && self.table_function == other.table_function
+            && self.table_args == other.table_args
     }
 }
 
@@ -0,0 +1,108 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::Expression;
+use crate::PlanNode;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum JoinType {
+    /// The pairs of the rows whose keys are equal.
+    Inner,
+    /// The inner join, and the rows of the left input which match no row with nulls.
+    Left,
+    /// The inner join, and the rows of the right input which match no row with nulls.
+    Right,
+    /// The inner join, and the rows of both inputs which match no row with nulls.
+    Full,
+    /// The rows of the left input which match any row, once, e.g. `x IN (subquery)`.
+    Semi,
+    /// The rows of the left input which match no row, e.g. `x NOT IN (subquery)`.
+    Anti,
+}
+
+impl JoinType {
+    /// Whether the rows of the left input which match no row are output.
+    pub fn keeps_left(&self) -> bool {
+        matches!(self, JoinType::Left | JoinType::Full)
+    }
+
+    /// Whether the rows of the right input which match no row are output.
+    pub fn keeps_right(&self) -> bool {
+        matches!(self, JoinType::Right | JoinType::Full)
+    }
+
+    /// Whether the output has the columns of the right input, the semi and the anti joins only
+    /// tell whether the rows of the left input match.
+    pub fn outputs_right(&self) -> bool {
+        !matches!(self, JoinType::Semi | JoinType::Anti)
+    }
+}
+
+impl fmt::Display for JoinType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JoinType::Inner => write!(f, "INNER"),
+            JoinType::Left => write!(f, "LEFT OUTER"),
+            JoinType::Right => write!(f, "RIGHT OUTER"),
+            JoinType::Full => write!(f, "FULL OUTER"),
+            JoinType::Semi => write!(f, "LEFT SEMI"),
+            JoinType::Anti => write!(f, "LEFT ANTI"),
+        }
+    }
+}
+
+/// Joins the rows of two inputs by the equality of their keys, the keys at the same position
+/// of `left_keys` and `right_keys` have the same type. The output has the columns of the left
+/// input followed by the columns of the right input, the columns of an input are nullable if
+/// the unmatched rows of the other input are output. The output of the semi and the anti joins
+/// has the columns of the left input only.
+///
+/// A join without keys joins every pair of the rows for which `condition` is true, or every
+/// pair without condition, it is executed by a nested loop.
+///
+/// If both inputs are sorted on the keys, see `PlanNode::sort_order`, the join is executed by a
+/// merge of the inputs, which holds the rows of one key of the right input at a time instead of
+/// a hash table of all its rows.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct JoinPlan {
+    pub join_type: JoinType,
+    /// The keys of the left input, which is probed.
+    pub left_keys: Vec<Expression>,
+    /// The keys of the right input, which builds the hash table.
+    pub right_keys: Vec<Expression>,
+    /// The predicate of the pairs of the rows of a join without keys, on the columns of both
+    /// inputs.
+    pub condition: Option<Expression>,
+    /// Whether both inputs are sorted ascending on the keys in their order, then the join is a
+    /// merge join.
+    #[serde(default)]
+    pub sorted: bool,
+    /// The left input of the join.
+    pub left: Arc<PlanNode>,
+    /// The right input of the join.
+    pub right: Arc<PlanNode>,
+    /// The output schema.
+    pub schema: DataSchemaRef,
+}
+
+impl JoinPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    /// Whether the join is executed by a nested loop, as it has no keys.
+    pub fn is_nested_loop(&self) -> bool {
+        self.left_keys.is_empty()
+    }
+
+    pub fn set_inputs(&mut self, left: &PlanNode, right: &PlanNode) {
+        self.left = Arc::new(left.clone());
+        self.right = Arc::new(right.clone());
+    }
+}
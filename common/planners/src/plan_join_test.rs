@@ -0,0 +1,280 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+
+use crate::test::Test;
+use crate::*;
+
+#[test]
+fn test_join_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[col("number").alias("b.number")])?
+        .build()?;
+    let plan = PlanBuilder::from(&left)
+        .join(JoinType::Inner, &right, &[col("number")], &[col(
+            "b.number",
+        )])?
+        .project(&[col("number"), col("b.number")])?
+        .build()?;
+
+    let expect = "\
+    Projection: number:UInt64, b.number:UInt64\
+    \n  Join: INNER, on: [number = b.number]\
+    \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+    \n    Projection: number as b.number:UInt64\
+    \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]";
+    let actual = format!("{:?}", plan);
+    assert_eq!(expect, actual);
+
+    let join = plan.input(0);
+    assert_eq!(join.name(), "JoinPlan");
+    assert_eq!(join.inputs().len(), 2);
+    assert_eq!(
+        join.schema().field_with_name("b.number")?.data_type(),
+        &DataType::UInt64
+    );
+    Ok(())
+}
+
+#[test]
+fn test_semi_join_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = Test::create().generate_source_plan_for_test(10)?;
+
+    // The output has the columns of the left input only, so the inputs can have the same columns.
+    for (join_type, name) in vec![(JoinType::Semi, "LEFT SEMI"), (JoinType::Anti, "LEFT ANTI")] {
+        let plan = PlanBuilder::from(&left)
+            .join(join_type, &right, &[col("number")], &[col("number")])?
+            .build()?;
+
+        let expect = format!("\
+        Join: {}, on: [number = number]\
+        \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+        \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]", name);
+        assert_eq!(expect, format!("{:?}", plan));
+        assert_eq!(left.schema(), plan.schema());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_outer_join_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[col("number").alias("b.number")])?
+        .build()?;
+
+    // The columns of an input are nullable if the unmatched rows of the other input are output.
+    let tests = vec![
+        (JoinType::Left, "LEFT OUTER", false, true),
+        (JoinType::Right, "RIGHT OUTER", true, false),
+        (JoinType::Full, "FULL OUTER", true, true),
+    ];
+    for (join_type, name, left_nullable, right_nullable) in tests {
+        let plan = PlanBuilder::from(&left)
+            .join(join_type, &right, &[col("number")], &[col("b.number")])?
+            .build()?;
+
+        let expect = format!("\
+        Join: {}, on: [number = b.number]\
+        \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+        \n  Projection: number as b.number:UInt64\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]", name);
+        assert_eq!(expect, format!("{:?}", plan));
+
+        let schema = plan.schema();
+        assert_eq!(
+            schema.field_with_name("number")?.is_nullable(),
+            left_nullable
+        );
+        assert_eq!(
+            schema.field_with_name("b.number")?.is_nullable(),
+            right_nullable
+        );
+    }
+
+    // The nullable columns keep their DEFAULT expressions and comments.
+    let right = PlanNode::Empty(EmptyPlan::create_with_schema(DataSchemaRefExt::create(
+        vec![DataField::new("b.number", DataType::UInt64, false)
+            .with_default_expr(Some("1".to_string()))
+            .with_comment(Some("the number".to_string()))],
+    )));
+    let plan = PlanBuilder::from(&left)
+        .join(JoinType::Left, &right, &[col("number")], &[col("b.number")])?
+        .build()?;
+    let field = plan.schema().field_with_name("b.number")?.clone();
+    assert!(field.is_nullable());
+    assert_eq!(field.default_expr(), Some(&"1".to_string()));
+    assert_eq!(field.comment(), Some(&"the number".to_string()));
+    Ok(())
+}
+
+#[test]
+fn test_merge_join_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10000)?)
+        .sort(&[sort("number", true, false)])?
+        .build()?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .sort(&[sort("number", true, false)])?
+        .project(&[col("number").alias("b.number")])?
+        .build()?;
+    assert_eq!(vec!["b.number".to_string()], right.sort_order());
+
+    // Both inputs are sorted on the keys, the output is sorted on the keys of the left input
+    // unless the unmatched rows of the right input are output.
+    let plan = PlanBuilder::from(&left)
+        .join(JoinType::Inner, &right, &[col("number")], &[col(
+            "b.number",
+        )])?
+        .build()?;
+    let expect = "\
+    Join: INNER, on: [number = b.number], merge\
+    \n  Sort: number:UInt64\
+    \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+    \n  Projection: number as b.number:UInt64\
+    \n    Sort: number:UInt64\
+    \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]";
+    assert_eq!(expect, format!("{:?}", plan));
+    assert_eq!(vec!["number".to_string()], plan.sort_order());
+
+    let plan = PlanBuilder::from(&left)
+        .join(JoinType::Full, &right, &[col("number")], &[col("b.number")])?
+        .build()?;
+    assert!(matches!(&plan, PlanNode::Join(join) if join.sorted));
+    assert!(plan.sort_order().is_empty());
+
+    // An input sorted descending is hash joined.
+    let unsorted = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .sort(&[sort("number", false, false)])?
+        .project(&[col("number").alias("b.number")])?
+        .build()?;
+    assert!(unsorted.sort_order().is_empty());
+    let plan = PlanBuilder::from(&left)
+        .join(JoinType::Inner, &unsorted, &[col("number")], &[col(
+            "b.number",
+        )])?
+        .build()?;
+    assert!(matches!(&plan, PlanNode::Join(join) if !join.sorted));
+
+    // The keys are reordered as the inputs are sorted on them.
+    let sorted_input = |prefix: &str| -> Result<PlanNode> {
+        PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+            .project(&[
+                col("number").alias(&format!("{}number", prefix)),
+                add(col("number"), lit(1u64)).alias(&format!("{}m", prefix)),
+            ])?
+            .sort(&[
+                sort(&format!("{}m", prefix), true, false),
+                sort(&format!("{}number", prefix), true, false),
+            ])?
+            .build()
+    };
+    let plan = PlanBuilder::from(&sorted_input("")?)
+        .join(
+            JoinType::Inner,
+            &sorted_input("b.")?,
+            &[col("number"), col("m")],
+            &[col("b.number"), col("b.m")],
+        )?
+        .build()?;
+    match plan {
+        PlanNode::Join(join) => {
+            assert!(join.sorted);
+            assert_eq!(vec![col("m"), col("number")], join.left_keys);
+            assert_eq!(vec![col("b.m"), col("b.number")], join.right_keys);
+        }
+        _ => panic!("The plan must be a join"),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_nested_loop_join_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[col("number").alias("b.number")])?
+        .build()?;
+
+    let tests = vec![
+        (None, "Join: LEFT OUTER, condition: true"),
+        (
+            Some(col("number").lt(col("b.number"))),
+            "Join: LEFT OUTER, condition: (number < b.number)",
+        ),
+    ];
+    for (condition, name) in tests {
+        let plan = PlanBuilder::from(&left)
+            .nested_loop_join(JoinType::Left, &right, condition)?
+            .build()?;
+
+        let expect = format!("\
+        {}\
+        \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+        \n  Projection: number as b.number:UInt64\
+        \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]", name);
+        assert_eq!(expect, format!("{:?}", plan));
+        assert!(plan.schema().field_with_name("b.number")?.is_nullable());
+    }
+
+    // The condition must be Boolean.
+    let result = PlanBuilder::from(&left).nested_loop_join(
+        JoinType::Inner,
+        &right,
+        Some(add(col("number"), col("b.number"))),
+    );
+    assert_eq!(
+        "Code: 6, displayText = The condition of JOIN must be Boolean, but got (number + b.number) UInt64.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_join_plan_error() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = Test::create().generate_source_plan_for_test(10)?;
+
+    // The columns of the inputs must have different names.
+    let result =
+        PlanBuilder::from(&left).join(JoinType::Inner, &right, &[col("number")], &[col("number")]);
+    let actual = format!("{}", result.err().unwrap());
+    assert_eq!(
+        "Code: 6, displayText = Duplicate column number in the inputs of JOIN.",
+        actual
+    );
+
+    // The keys must have the same type.
+    let right = PlanBuilder::from(&right)
+        .project(&[col("number").alias("b.number")])?
+        .build()?;
+    let result = PlanBuilder::from(&left).join(JoinType::Inner, &right, &[col("number")], &[
+        Expression::Cast {
+            expr: Box::new(col("b.number")),
+            data_type: DataType::Int64,
+        },
+    ]);
+    assert!(result.is_err());
+
+    // A join must have keys.
+    let result = PlanBuilder::from(&left).join(JoinType::Inner, &right, &[], &[]);
+    assert!(result.is_err());
+    Ok(())
+}
@@ -4,6 +4,7 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataCollation;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -12,17 +13,22 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
 use crate::DropDatabasePlan;
+use crate::DropPartitionPlan;
 use crate::DropTablePlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
+use crate::Expression;
 use crate::ExpressionPlan;
 use crate::FilterPlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
+use crate::JoinPlan;
+use crate::KillQueryPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::ProjectionPlan;
@@ -34,6 +40,8 @@ use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
 use crate::SortPlan;
 use crate::StagePlan;
+use crate::TruncateTablePlan;
+use crate::UnionPlan;
 use crate::UseDatabasePlan;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
@@ -48,6 +56,8 @@ pub enum PlanNode {
     AggregatorFinal(AggregatorFinalPlan),
     Filter(FilterPlan),
     Having(HavingPlan),
+    Join(JoinPlan),
+    Union(UnionPlan),
     Sort(SortPlan),
     Limit(LimitPlan),
     LimitBy(LimitByPlan),
@@ -60,10 +70,14 @@ pub enum PlanNode {
     CreateTable(CreateTablePlan),
     DescribeTable(DescribeTablePlan),
     DropTable(DropTablePlan),
+    TruncateTable(TruncateTablePlan),
+    DropPartition(DropPartitionPlan),
     UseDatabase(UseDatabasePlan),
     SetVariable(SettingPlan),
     InsertInto(InsertIntoPlan),
+    Copy(CopyPlan),
     ShowCreateTable(ShowCreateTablePlan),
+    KillQuery(KillQueryPlan),
     SubQueryExpression(SubQueriesSetPlan),
 }
 
@@ -82,6 +96,8 @@ impl PlanNode {
             PlanNode::AggregatorFinal(v) => v.schema(),
             PlanNode::Filter(v) => v.schema(),
             PlanNode::Having(v) => v.schema(),
+            PlanNode::Join(v) => v.schema(),
+            PlanNode::Union(v) => v.schema(),
             PlanNode::Limit(v) => v.schema(),
             PlanNode::LimitBy(v) => v.schema(),
             PlanNode::ReadSource(v) => v.schema(),
@@ -91,12 +107,16 @@ impl PlanNode {
             PlanNode::DropDatabase(v) => v.schema(),
             PlanNode::CreateTable(v) => v.schema(),
             PlanNode::DropTable(v) => v.schema(),
+            PlanNode::TruncateTable(v) => v.schema(),
+            PlanNode::DropPartition(v) => v.schema(),
             PlanNode::DescribeTable(v) => v.schema(),
             PlanNode::SetVariable(v) => v.schema(),
             PlanNode::Sort(v) => v.schema(),
             PlanNode::UseDatabase(v) => v.schema(),
             PlanNode::InsertInto(v) => v.schema(),
+            PlanNode::Copy(v) => v.schema(),
             PlanNode::ShowCreateTable(v) => v.schema(),
+            PlanNode::KillQuery(v) => v.schema(),
             PlanNode::SubQueryExpression(v) => v.schema(),
         }
     }
@@ -114,6 +134,8 @@ impl PlanNode {
             PlanNode::AggregatorFinal(_) => "AggregatorFinalPlan",
             PlanNode::Filter(_) => "FilterPlan",
             PlanNode::Having(_) => "HavingPlan",
+            PlanNode::Join(_) => "JoinPlan",
+            PlanNode::Union(_) => "UnionPlan",
             PlanNode::Limit(_) => "LimitPlan",
             PlanNode::LimitBy(_) => "LimitByPlan",
             PlanNode::ReadSource(_) => "ReadSourcePlan",
@@ -124,11 +146,15 @@ impl PlanNode {
             PlanNode::CreateTable(_) => "CreateTablePlan",
             PlanNode::DescribeTable(_) => "DescribeTablePlan",
             PlanNode::DropTable(_) => "DropTablePlan",
+            PlanNode::TruncateTable(_) => "TruncateTablePlan",
+            PlanNode::DropPartition(_) => "DropPartitionPlan",
             PlanNode::SetVariable(_) => "SetVariablePlan",
             PlanNode::Sort(_) => "SortPlan",
             PlanNode::UseDatabase(_) => "UseDatabasePlan",
             PlanNode::InsertInto(_) => "InsertIntoPlan",
+            PlanNode::Copy(_) => "CopyPlan",
             PlanNode::ShowCreateTable(_) => "ShowCreateTablePlan",
+            PlanNode::KillQuery(_) => "KillQueryPlan",
             PlanNode::SubQueryExpression(_) => "CreateSubQueriesSets",
         }
     }
@@ -143,6 +169,8 @@ impl PlanNode {
             PlanNode::AggregatorFinal(v) => vec![v.input.clone()],
             PlanNode::Filter(v) => vec![v.input.clone()],
             PlanNode::Having(v) => vec![v.input.clone()],
+            PlanNode::Join(v) => vec![v.left.clone(), v.right.clone()],
+            PlanNode::Union(v) => v.inputs.clone(),
             PlanNode::Limit(v) => vec![v.input.clone()],
             PlanNode::Explain(v) => vec![v.input.clone()],
             PlanNode::Select(v) => vec![v.input.clone()],
@@ -171,6 +199,8 @@ impl PlanNode {
             PlanNode::AggregatorFinal(v) => v.set_input(inputs[0]),
             PlanNode::Filter(v) => v.set_input(inputs[0]),
             PlanNode::Having(v) => v.set_input(inputs[0]),
+            PlanNode::Join(v) if inputs.len() == 2 => v.set_inputs(inputs[0], inputs[1]),
+            PlanNode::Union(v) => v.set_inputs(inputs),
             PlanNode::Limit(v) => v.set_input(inputs[0]),
             PlanNode::Explain(v) => v.set_input(inputs[0]),
             PlanNode::Select(v) => v.set_input(inputs[0]),
@@ -186,4 +216,71 @@ impl PlanNode {
 
         Ok(())
     }
+
+    /// The columns which the output of the plan is known to be sorted ascending on, the first
+    /// column first, e.g. the columns of ORDER BY a, b under the nodes which keep the order of
+    /// their rows. The output is then one stream, as a sort merges its input into one.
+    ///
+    /// The strings of a collated column are sorted by their collation keys rather than their
+    /// bytes, so the order stops before such a column.
+    pub fn sort_order(&self) -> Vec<String> {
+        match self {
+            PlanNode::Sort(plan) => {
+                let input_schema = plan.input.schema();
+                let mut names = vec![];
+                for expr in &plan.order_by {
+                    let expr = match expr {
+                        Expression::Sort {
+                            expr, asc: true, ..
+                        } => expr,
+                        _ => break,
+                    };
+                    let binary = match expr.to_data_field(&input_schema) {
+                        Ok(field) => matches!(
+                            DataCollation::from_field(&field),
+                            Ok(collation) if collation.is_binary()
+                        ),
+                        Err(_) => false,
+                    };
+                    if !binary {
+                        break;
+                    }
+                    names.push(expr.column_name());
+                }
+                names
+            }
+            PlanNode::Select(plan) => plan.input.sort_order(),
+            PlanNode::Filter(plan) => plan.input.sort_order(),
+            PlanNode::Having(plan) => plan.input.sort_order(),
+            PlanNode::Limit(plan) => plan.input.sort_order(),
+            PlanNode::LimitBy(plan) => plan.input.sort_order(),
+            // The expressions are added to the columns of the input.
+            PlanNode::Expression(plan) => plan.input.sort_order(),
+            // The columns are kept by their names, or renamed by the aliases.
+            PlanNode::Projection(plan) => {
+                let mut names = vec![];
+                for name in plan.input.sort_order() {
+                    let output_name = plan.expr.iter().find_map(|expr| match expr {
+                        Expression::Alias(alias, expr) if expr.column_name() == name => {
+                            Some(alias.clone())
+                        }
+                        Expression::Alias(_, _) => None,
+                        expr if expr.column_name() == name => Some(name.clone()),
+                        _ => None,
+                    });
+                    match output_name {
+                        Some(output_name) => names.push(output_name),
+                        None => break,
+                    }
+                }
+                names
+            }
+            // A merge join outputs the rows in the order of the left input, the unmatched rows of
+            // the right input have no keys of the left input.
+            PlanNode::Join(plan) if plan.sorted && !plan.join_type.keeps_right() => {
+                plan.left_keys.iter().map(|key| key.column_name()).collect()
+            }
+            _ => vec![],
+        }
+    }
 }
@@ -2,10 +2,16 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
+
 pub type Partitions = Vec<Part>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct Part {
     pub name: String,
     pub version: u64,
+    /// The labels of the nodes which hold the copies of the part, e.g. `host` and `zone`, the
+    /// part is read by the query node nearest to one of them. Empty if the part has no place.
+    #[serde(default)]
+    pub locations: Vec<BTreeMap<String, String>>,
 }
@@ -8,6 +8,7 @@ use std::sync::Arc;
 
 use common_datavalues::DataField;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
 use common_exception::ErrorCode;
 use common_exception::Result;
 
@@ -15,10 +16,12 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
 use crate::DropDatabasePlan;
+use crate::DropPartitionPlan;
 use crate::DropTablePlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
@@ -28,6 +31,8 @@ use crate::Expressions;
 use crate::FilterPlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
+use crate::JoinPlan;
+use crate::KillQueryPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::PlanBuilder;
@@ -41,6 +46,8 @@ use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
 use crate::SortPlan;
 use crate::StagePlan;
+use crate::TruncateTablePlan;
+use crate::UnionPlan;
 use crate::UseDatabasePlan;
 
 /// `PlanRewriter` is a visitor that can help to rewrite `PlanNode`
@@ -85,12 +92,18 @@ pub trait PlanRewriter {
             PlanNode::Broadcast(plan) => self.rewrite_broadcast(plan),
             PlanNode::Remote(plan) => self.rewrite_remote(plan),
             PlanNode::Having(plan) => self.rewrite_having(plan),
+            PlanNode::Join(plan) => self.rewrite_join(plan),
+            PlanNode::Union(plan) => self.rewrite_union(plan),
             PlanNode::Expression(plan) => self.rewrite_expression(plan),
             PlanNode::DescribeTable(plan) => self.rewrite_describe_table(plan),
             PlanNode::DropTable(plan) => self.rewrite_drop_table(plan),
+            PlanNode::TruncateTable(plan) => self.rewrite_truncate_table(plan),
+            PlanNode::DropPartition(plan) => self.rewrite_drop_partition(plan),
             PlanNode::DropDatabase(plan) => self.rewrite_drop_database(plan),
             PlanNode::InsertInto(plan) => self.rewrite_insert_into(plan),
+            PlanNode::Copy(plan) => self.rewrite_copy(plan),
             PlanNode::ShowCreateTable(plan) => self.rewrite_show_create_table(plan),
+            PlanNode::KillQuery(plan) => self.rewrite_kill_query(plan),
             PlanNode::SubQueryExpression(plan) => self.rewrite_sub_queries_sets(plan),
         }
     }
@@ -233,10 +246,45 @@ pub trait PlanRewriter {
         PlanBuilder::from(&new_input).having(new_predicate)?.build()
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        let new_left = self.rewrite_plan_node(plan.left.as_ref())?;
+        let new_right = self.rewrite_plan_node(plan.right.as_ref())?;
+        if plan.is_nested_loop() {
+            let mut fields = new_left.schema().fields().clone();
+            fields.extend_from_slice(new_right.schema().fields());
+            let schema = DataSchemaRefExt::create(fields);
+            let new_condition = match &plan.condition {
+                Some(condition) => Some(self.rewrite_expr(&schema, condition)?),
+                None => None,
+            };
+            return PlanBuilder::from(&new_left)
+                .nested_loop_join(plan.join_type, &new_right, new_condition)?
+                .build();
+        }
+
+        let new_left_keys = self.rewrite_exprs(&new_left.schema(), &plan.left_keys)?;
+        let new_right_keys = self.rewrite_exprs(&new_right.schema(), &plan.right_keys)?;
+        PlanBuilder::from(&new_left)
+            .join(plan.join_type, &new_right, &new_left_keys, &new_right_keys)?
+            .build()
+    }
+
+    fn rewrite_union(&mut self, plan: &UnionPlan) -> Result<PlanNode> {
+        let mut new_inputs = Vec::with_capacity(plan.inputs.len());
+        for input in &plan.inputs {
+            new_inputs.push(self.rewrite_plan_node(input.as_ref())?);
+        }
+        PlanBuilder::from(&new_inputs[0])
+            .union_all(&new_inputs[1..])?
+            .build()
+    }
+
     fn rewrite_sort(&mut self, plan: &SortPlan) -> Result<PlanNode> {
         let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
         let new_order_by = self.rewrite_exprs(&new_input.schema(), &plan.order_by)?;
-        PlanBuilder::from(&new_input).sort(&new_order_by)?.build()
+        PlanBuilder::from(&new_input)
+            .sort_with_limit(&new_order_by, plan.limit)?
+            .build()
     }
 
     fn rewrite_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
@@ -309,6 +357,14 @@ pub trait PlanRewriter {
         Ok(PlanNode::DropTable(plan.clone()))
     }
 
+    fn rewrite_truncate_table(&mut self, plan: &TruncateTablePlan) -> Result<PlanNode> {
+        Ok(PlanNode::TruncateTable(plan.clone()))
+    }
+
+    fn rewrite_drop_partition(&mut self, plan: &DropPartitionPlan) -> Result<PlanNode> {
+        Ok(PlanNode::DropPartition(plan.clone()))
+    }
+
     fn rewrite_drop_database(&mut self, plan: &DropDatabasePlan) -> Result<PlanNode> {
         Ok(PlanNode::DropDatabase(plan.clone()))
     }
@@ -317,9 +373,17 @@ pub trait PlanRewriter {
         Ok(PlanNode::InsertInto(plan.clone()))
     }
 
+    fn rewrite_copy(&mut self, plan: &CopyPlan) -> Result<PlanNode> {
+        Ok(PlanNode::Copy(plan.clone()))
+    }
+
     fn rewrite_show_create_table(&mut self, plan: &ShowCreateTablePlan) -> Result<PlanNode> {
         Ok(PlanNode::ShowCreateTable(plan.clone()))
     }
+
+    fn rewrite_kill_query(&mut self, plan: &KillQueryPlan) -> Result<PlanNode> {
+        Ok(PlanNode::KillQuery(plan.clone()))
+    }
 }
 
 pub struct RewriteHelper {}
@@ -531,6 +595,18 @@ impl RewriteHelper {
         Ok(map)
     }
 
+    /// Splits the predicate into the conjunctions which are joined by AND.
+    pub fn split_conjunctions(expr: &Expression) -> Vec<Expression> {
+        match expr {
+            Expression::BinaryExpression { op, left, right } if op.to_lowercase() == "and" => {
+                let mut conjunctions = Self::split_conjunctions(left);
+                conjunctions.extend(Self::split_conjunctions(right));
+                conjunctions
+            }
+            other => vec![other.clone()],
+        }
+    }
+
     /// Get the expression children.
     pub fn expression_plan_children(expr: &Expression) -> Result<Vec<Expression>> {
         Ok(match expr {
@@ -218,3 +218,21 @@ fn test_plan_rewriter_1() -> Result<()> {
     assert_eq!(before_rewrite, after_rewrite);
     Ok(())
 }
+
+#[test]
+fn test_split_conjunctions() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    use crate::*;
+
+    let a = col("a").eq(lit(1i64));
+    let b = col("b").gt(lit(2i64));
+    let c = col("c").lt(lit(3i64)).or(col("d").eq(lit(4i64)));
+
+    // The disjunctions are not split.
+    let conjunctions = RewriteHelper::split_conjunctions(&a.and(b.clone()).and(c.clone()));
+    assert_eq!(conjunctions, vec![a.clone(), b, c.clone()]);
+    assert_eq!(RewriteHelper::split_conjunctions(&c), vec![c]);
+    assert_eq!(RewriteHelper::split_conjunctions(&a), vec![a]);
+    Ok(())
+}
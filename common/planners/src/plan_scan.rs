@@ -16,7 +16,7 @@ pub struct ScanPlan {
     pub schema_name: String,
     // The schema of the source data
     pub table_schema: DataSchemaRef,
-    pub table_args: Option<Expression>,
+    pub table_args: Vec<Expression>,
     pub projected_schema: DataSchemaRef,
     // Extras.
     pub push_downs: Extras,
@@ -32,7 +32,7 @@ impl ScanPlan {
             schema_name: "".to_string(),
             table_schema: Arc::new(DataSchema::empty()),
             projected_schema: Arc::new(DataSchema::empty()),
-            table_args: None,
+            table_args: vec![],
             push_downs: Extras::default(),
         }
     }
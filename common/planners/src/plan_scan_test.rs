@@ -14,7 +14,7 @@ fn test_scan_plan() -> Result<()> {
     let scan = PlanNode::Scan(ScanPlan {
         schema_name: "scan_test".to_string(),
         table_schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::Utf8, false)]),
-        table_args: None,
+        table_args: vec![],
         projected_schema: DataSchemaRefExt::create(vec![DataField::new(
             "a",
             DataType::Utf8,
@@ -17,6 +17,9 @@ pub struct SortPlan {
     pub input: Arc<PlanNode>,
     /// Output data schema
     pub schema: DataSchemaRef,
+    /// Only the first rows are kept if the sort is under a limit, the sort is then a top N.
+    #[serde(default)]
+    pub limit: Option<usize>,
 }
 
 impl SortPlan {
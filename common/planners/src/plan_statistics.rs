@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use crate::PlanNode;
+
 #[derive(serde::Serialize, serde::Deserialize, PartialEq, Eq, Clone, Debug, Default)]
 pub struct Statistics {
     /// Total rows of the query read.
@@ -33,3 +35,83 @@ impl Statistics {
         *self = Self::default();
     }
 }
+
+impl PlanNode {
+    /// The selectivity of the predicates of Filter, Having and LimitBy, as the data of the
+    /// tables is not analyzed.
+    const ESTIMATED_SELECTIVITY: f64 = 0.5;
+
+    /// Estimates the rows and bytes which the plan outputs, from the statistics of the sources.
+    /// The estimation is exact only if the statistics of the sources are exact, and no predicate
+    /// or GROUP BY reduces the rows.
+    pub fn estimate_statistics(&self) -> Statistics {
+        match self {
+            PlanNode::ReadSource(plan) => plan.statistics.clone(),
+            PlanNode::Filter(plan) => Self::estimate_selected(&plan.input),
+            PlanNode::Having(plan) => Self::estimate_selected(&plan.input),
+            PlanNode::LimitBy(plan) => Self::estimate_selected(&plan.input),
+            PlanNode::Limit(plan) => {
+                let input = plan.input.estimate_statistics();
+                let rows = input.read_rows.saturating_sub(plan.offset);
+                let rows = plan.n.map_or(rows, |n| rows.min(n));
+                Self::estimate_rows(&input, rows, input.is_exact)
+            }
+            // The aggregation without GROUP BY outputs one row.
+            PlanNode::AggregatorPartial(plan) if plan.group_expr.is_empty() => {
+                let input = plan.input.estimate_statistics();
+                Self::estimate_rows(&input, 1, input.is_exact)
+            }
+            PlanNode::AggregatorFinal(plan) if plan.group_expr.is_empty() => {
+                let input = plan.input.estimate_statistics();
+                Self::estimate_rows(&input, 1, input.is_exact)
+            }
+            // GROUP BY outputs at most the rows of the input.
+            PlanNode::AggregatorPartial(plan) => {
+                let input = plan.input.estimate_statistics();
+                Self::estimate_rows(&input, input.read_rows, false)
+            }
+            PlanNode::AggregatorFinal(plan) => {
+                let input = plan.input.estimate_statistics();
+                Self::estimate_rows(&input, input.read_rows, false)
+            }
+            PlanNode::SubQueryExpression(plan) => plan.input.estimate_statistics(),
+            // The union outputs the rows of all the inputs.
+            PlanNode::Union(plan) => {
+                plan.inputs
+                    .iter()
+                    .fold(Statistics::new_exact(0, 0), |statistics, input| {
+                        let input = input.estimate_statistics();
+                        Statistics {
+                            read_rows: statistics.read_rows + input.read_rows,
+                            read_bytes: statistics.read_bytes + input.read_bytes,
+                            is_exact: statistics.is_exact && input.is_exact,
+                        }
+                    })
+            }
+            _ => match self.inputs().first() {
+                Some(input) => input.estimate_statistics(),
+                None => Statistics::default(),
+            },
+        }
+    }
+
+    fn estimate_selected(input: &PlanNode) -> Statistics {
+        let input = input.estimate_statistics();
+        let rows = (input.read_rows as f64 * Self::ESTIMATED_SELECTIVITY).ceil() as usize;
+        Self::estimate_rows(&input, rows, false)
+    }
+
+    /// The bytes of the rows are in proportion to the bytes of the input.
+    fn estimate_rows(input: &Statistics, rows: usize, is_exact: bool) -> Statistics {
+        let bytes = match input.read_rows {
+            0 => 0,
+            input_rows => (input.read_bytes as f64 * rows as f64 / input_rows as f64) as usize,
+        };
+
+        Statistics {
+            read_rows: rows,
+            read_bytes: bytes,
+            is_exact,
+        }
+    }
+}
@@ -28,8 +28,9 @@ impl SubQueriesSetPlan {
                     schema_fields.push(DataField::new(name, subquery_field_type, false));
                 }
                 Expression::ScalarSubquery { name, query_plan } => {
+                    // The scalar subquery of no rows is NULL.
                     let subquery_field_type = Expression::to_scalar_subquery_type(query_plan);
-                    schema_fields.push(DataField::new(name, subquery_field_type, false));
+                    schema_fields.push(DataField::new(name, subquery_field_type, true));
                 }
                 _ => panic!("Logical error, expressions must be Subquery or ScalarSubquery"),
             };
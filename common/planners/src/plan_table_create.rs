@@ -6,32 +6,6 @@ use std::collections::HashMap;
 
 use common_datavalues::DataSchemaRef;
 
-/// Types of files to parse as DataFrames
-#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
-pub enum TableEngineType {
-    /// Newline-delimited JSON
-    JsonEachRaw,
-    /// Apache Parquet columnar store
-    Parquet,
-    /// Comma separated values
-    Csv,
-    /// Null ENGINE
-    Null,
-    Memory,
-}
-
-impl ToString for TableEngineType {
-    fn to_string(&self) -> String {
-        match self {
-            TableEngineType::JsonEachRaw => "JSON".into(),
-            TableEngineType::Parquet => "Parquet".into(),
-            TableEngineType::Csv => "CSV".into(),
-            TableEngineType::Null => "Null".into(),
-            TableEngineType::Memory => "Memory".into(),
-        }
-    }
-}
-
 pub type TableOptions = HashMap<String, String>;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
@@ -42,8 +16,8 @@ pub struct CreateTablePlan {
     pub table: String,
     /// The table schema
     pub schema: DataSchemaRef,
-    /// The file type of physical file
-    pub engine: TableEngineType,
+    /// The name of the table engine, e.g., `Parquet`, resolved by the table engine registry
+    pub engine: String,
     pub options: TableOptions,
 }
 
@@ -0,0 +1,33 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+
+use crate::PlanNode;
+
+/// Concatenates the rows of the inputs, which have the same number of columns with the same
+/// types. The output has the column names of the first input, a column is nullable if it is
+/// nullable in any input.
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq)]
+pub struct UnionPlan {
+    /// The inputs of the union, two at least.
+    pub inputs: Vec<Arc<PlanNode>>,
+    /// The output schema.
+    pub schema: DataSchemaRef,
+}
+
+impl UnionPlan {
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    pub fn set_inputs(&mut self, inputs: Vec<&PlanNode>) {
+        self.inputs = inputs
+            .into_iter()
+            .map(|input| Arc::new(input.clone()))
+            .collect();
+    }
+}
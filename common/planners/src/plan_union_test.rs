@@ -0,0 +1,73 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataType;
+use common_exception::Result;
+
+use crate::test::Test;
+use crate::*;
+
+#[test]
+fn test_union_plan() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[Expression::Cast {
+            expr: Box::new(col("number")),
+            data_type: DataType::UInt32,
+        }
+        .alias("b")])?
+        .build()?;
+
+    // The inputs are cast to the common type, and the union has the names of the first input.
+    let plan = PlanBuilder::from(&left).union_all(&[right])?.build()?;
+    let expect = "\
+    Union All\
+    \n  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10000, read_bytes: 80000]\
+    \n  Projection: cast(b as UInt64) as b:UInt64\
+    \n    Projection: cast(number as UInt32) as b:UInt32\
+    \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]";
+    assert_eq!(expect, format!("{:?}", plan));
+
+    assert_eq!(plan.name(), "UnionPlan");
+    assert_eq!(plan.inputs().len(), 2);
+    let field = plan.schema().field_with_name("number")?.clone();
+    assert_eq!(field.data_type(), &DataType::UInt64);
+    assert!(!field.is_nullable());
+
+    // The rows of all the inputs.
+    let statistics = plan.estimate_statistics();
+    assert_eq!(statistics.read_rows, 10010);
+    assert!(statistics.is_exact);
+    Ok(())
+}
+
+#[test]
+fn test_union_plan_error() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    let left = Test::create().generate_source_plan_for_test(10000)?;
+
+    // The inputs must have the same number of columns.
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[col("number"), col("number").alias("b")])?
+        .build()?;
+    let result = PlanBuilder::from(&left).union_all(&[right]);
+    assert_eq!(
+        "Code: 6, displayText = The inputs of UNION must have the same number of columns, but 1 != 2.",
+        result.err().unwrap().to_string()
+    );
+
+    // The columns must have a common type.
+    let right = PlanBuilder::from(&Test::create().generate_source_plan_for_test(10)?)
+        .project(&[lit("a").alias("b")])?
+        .build()?;
+    let result = PlanBuilder::from(&left).union_all(&[right]);
+    assert_eq!(
+        "Code: 6, displayText = The columns number and b of UNION have no common type, UInt64 and Utf8.",
+        result.err().unwrap().to_string()
+    );
+    Ok(())
+}
@@ -8,10 +8,12 @@ use crate::plan_broadcast::BroadcastPlan;
 use crate::plan_subqueries_set::SubQueriesSetPlan;
 use crate::AggregatorFinalPlan;
 use crate::AggregatorPartialPlan;
+use crate::CopyPlan;
 use crate::CreateDatabasePlan;
 use crate::CreateTablePlan;
 use crate::DescribeTablePlan;
 use crate::DropDatabasePlan;
+use crate::DropPartitionPlan;
 use crate::DropTablePlan;
 use crate::EmptyPlan;
 use crate::ExplainPlan;
@@ -20,6 +22,8 @@ use crate::ExpressionPlan;
 use crate::FilterPlan;
 use crate::HavingPlan;
 use crate::InsertIntoPlan;
+use crate::JoinPlan;
+use crate::KillQueryPlan;
 use crate::LimitByPlan;
 use crate::LimitPlan;
 use crate::PlanNode;
@@ -32,6 +36,8 @@ use crate::SettingPlan;
 use crate::ShowCreateTablePlan;
 use crate::SortPlan;
 use crate::StagePlan;
+use crate::TruncateTablePlan;
+use crate::UnionPlan;
 use crate::UseDatabasePlan;
 
 /// `PlanVisitor` implements visitor pattern(reference [syn](https://docs.rs/syn/1.0.72/syn/visit/trait.Visit.html)) for `PlanNode`.
@@ -94,6 +100,8 @@ pub trait PlanVisitor {
             PlanNode::DropDatabase(plan) => self.visit_drop_database(plan),
             PlanNode::CreateTable(plan) => self.visit_create_table(plan),
             PlanNode::DropTable(plan) => self.visit_drop_table(plan),
+            PlanNode::TruncateTable(plan) => self.visit_truncate_table(plan),
+            PlanNode::DropPartition(plan) => self.visit_drop_partition(plan),
             PlanNode::DescribeTable(plan) => self.visit_describe_table(plan),
             PlanNode::UseDatabase(plan) => self.visit_use_database(plan),
             PlanNode::SetVariable(plan) => self.visit_set_variable(plan),
@@ -101,9 +109,13 @@ pub trait PlanVisitor {
             PlanNode::Broadcast(plan) => self.visit_broadcast(plan),
             PlanNode::Remote(plan) => self.visit_remote(plan),
             PlanNode::Having(plan) => self.visit_having(plan),
+            PlanNode::Join(plan) => self.visit_join(plan),
+            PlanNode::Union(plan) => self.visit_union(plan),
             PlanNode::Expression(plan) => self.visit_expression(plan),
             PlanNode::InsertInto(plan) => self.visit_insert_into(plan),
+            PlanNode::Copy(plan) => self.visit_copy(plan),
             PlanNode::ShowCreateTable(plan) => self.visit_show_create_table(plan),
+            PlanNode::KillQuery(plan) => self.visit_kill_query(plan),
             PlanNode::SubQueryExpression(plan) => self.visit_sub_queries_sets(plan),
         }
     }
@@ -187,6 +199,24 @@ pub trait PlanVisitor {
         self.visit_expr(&plan.predicate)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan) -> Result<()> {
+        self.visit_plan_node(plan.left.as_ref())?;
+        self.visit_plan_node(plan.right.as_ref())?;
+        self.visit_exprs(&plan.left_keys)?;
+        self.visit_exprs(&plan.right_keys)?;
+        match &plan.condition {
+            Some(condition) => self.visit_expr(condition),
+            None => Ok(()),
+        }
+    }
+
+    fn visit_union(&mut self, plan: &UnionPlan) -> Result<()> {
+        for input in &plan.inputs {
+            self.visit_plan_node(input.as_ref())?;
+        }
+        Ok(())
+    }
+
     fn visit_sort(&mut self, plan: &SortPlan) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref())?;
         self.visit_exprs(&plan.order_by)
@@ -236,6 +266,14 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_truncate_table(&mut self, _: &TruncateTablePlan) -> Result<()> {
+        Ok(())
+    }
+
+    fn visit_drop_partition(&mut self, _: &DropPartitionPlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_use_database(&mut self, _: &UseDatabasePlan) -> Result<()> {
         Ok(())
     }
@@ -248,7 +286,15 @@ pub trait PlanVisitor {
         Ok(())
     }
 
+    fn visit_copy(&mut self, _: &CopyPlan) -> Result<()> {
+        Ok(())
+    }
+
     fn visit_show_create_table(&mut self, _: &ShowCreateTablePlan) -> Result<()> {
         Ok(())
     }
+
+    fn visit_kill_query(&mut self, _: &KillQueryPlan) -> Result<()> {
+        Ok(())
+    }
 }
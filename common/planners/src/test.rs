@@ -57,6 +57,7 @@ impl Test {
             partitions.push(Part {
                 name: format!("{}-{}-{}", total, 0, total,),
                 version: 0,
+                locations: vec![],
             })
         } else {
             for part in 0..workers {
@@ -68,6 +69,7 @@ impl Test {
                 partitions.push(Part {
                     name: format!("{}-{}-{}", total, part_begin, part_end,),
                     version: 0,
+                    locations: vec![],
                 })
             }
         }
@@ -4,17 +4,43 @@
 
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 /// Progress callback is called with progress about the stream read progress.
 pub type ProgressCallback = Box<dyn FnMut(&ProgressValues) + Send + Sync + 'static>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ProgressValues {
     pub read_rows: usize,
     pub read_bytes: usize,
     pub total_rows_to_read: usize,
 }
 
+impl ProgressValues {
+    pub fn is_empty(&self) -> bool {
+        self.read_rows == 0 && self.read_bytes == 0 && self.total_rows_to_read == 0
+    }
+
+    /// The percentage of the rows read of the approx total rows, None if the total rows are
+    /// unknown. The total is approx, so the percentage is at most 100.
+    pub fn percent(&self) -> Option<f64> {
+        match self.total_rows_to_read {
+            0 => None,
+            total => Some((self.read_rows as f64 * 100.0 / total as f64).min(100.0)),
+        }
+    }
+
+    /// Estimates the remaining time of the reading from the elapsed time, assuming the rows are
+    /// read at the same speed. None if the total rows are unknown or nothing is read yet.
+    pub fn estimated_remaining(&self, elapsed: Duration) -> Option<Duration> {
+        match (self.read_rows, self.total_rows_to_read) {
+            (0, _) | (_, 0) => None,
+            (read, total) if read >= total => Some(Duration::from_secs(0)),
+            (read, total) => Some(elapsed.mul_f64((total - read) as f64 / read as f64)),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Progress {
     read_rows: AtomicUsize,
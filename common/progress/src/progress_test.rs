@@ -25,3 +25,38 @@ fn test_progress() -> Result<()> {
     assert_eq!(0, progress.get_values().read_bytes);
     Ok(())
 }
+
+#[test]
+fn test_progress_estimation() -> Result<()> {
+    use std::time::Duration;
+
+    use crate::*;
+
+    let mut values = ProgressValues {
+        read_rows: 0,
+        read_bytes: 0,
+        total_rows_to_read: 0,
+    };
+    assert_eq!(None, values.percent());
+    assert_eq!(None, values.estimated_remaining(Duration::from_secs(1)));
+
+    values.total_rows_to_read = 100;
+    assert_eq!(Some(0.0), values.percent());
+    assert_eq!(None, values.estimated_remaining(Duration::from_secs(1)));
+
+    values.read_rows = 25;
+    assert_eq!(Some(25.0), values.percent());
+    assert_eq!(
+        Some(Duration::from_secs(3)),
+        values.estimated_remaining(Duration::from_secs(1))
+    );
+
+    // The total rows are approx, more rows than the total may be read.
+    values.read_rows = 120;
+    assert_eq!(Some(100.0), values.percent());
+    assert_eq!(
+        Some(Duration::from_secs(0)),
+        values.estimated_remaining(Duration::from_secs(1))
+    );
+    Ok(())
+}
@@ -2,11 +2,15 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod numa_test;
 #[cfg(test)]
 mod runtime_test;
 
+mod numa;
 mod runtime;
 
+pub use numa::NumaTopology;
 pub use runtime::Dropper;
 pub use runtime::Runtime;
 pub use tokio;
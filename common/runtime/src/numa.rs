@@ -0,0 +1,147 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+/// The CPU cores of the NUMA nodes which the threads of the process may run on.
+///
+/// The cores of a node share its memory controller, and Linux places a page on the node of the
+/// thread which touches it first. The threads pinned to the cores of one node therefore allocate
+/// and fill the memory of their arenas, hash tables and blocks on that node, and read it without
+/// crossing to the other socket.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NumaTopology {
+    // The allowed cores of every node, the nodes without any allowed core are skipped.
+    nodes: Vec<Vec<usize>>,
+}
+
+impl NumaTopology {
+    pub fn create(nodes: Vec<Vec<usize>>) -> Self {
+        NumaTopology {
+            nodes: nodes
+                .into_iter()
+                .filter(|cores| !cores.is_empty())
+                .collect(),
+        }
+    }
+
+    /// Loads the nodes of the machine, keeping the cores the current thread is allowed to run
+    /// on. All the cores are of one node if the machine does not report its nodes.
+    pub fn load() -> Self {
+        let allowed = core_affinity::get_core_ids()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|core| core.id)
+            .collect::<Vec<_>>();
+
+        let nodes = Self::node_cores()
+            .into_iter()
+            .map(|cores| {
+                cores
+                    .into_iter()
+                    .filter(|core| allowed.contains(core))
+                    .collect::<Vec<_>>()
+            })
+            .filter(|cores| !cores.is_empty())
+            .collect::<Vec<_>>();
+
+        if nodes.is_empty() {
+            Self::create(vec![allowed])
+        } else {
+            Self::create(nodes)
+        }
+    }
+
+    pub fn nodes(&self) -> &[Vec<usize>] {
+        &self.nodes
+    }
+
+    /// The cores of the `workers` threads of a runtime, from the `first` of the cores ordered
+    /// node by node. The threads are kept on one node when they fit in it: a runtime which would
+    /// cross the end of a node starts at the next node instead. More threads than cores share the
+    /// cores.
+    pub fn runtime_cores(&self, first: usize, workers: usize) -> Vec<usize> {
+        let cores = self.nodes.iter().flatten().copied().collect::<Vec<_>>();
+        if cores.is_empty() {
+            return vec![];
+        }
+
+        let mut first = first % cores.len();
+        let mut node_start = 0;
+        for node in &self.nodes {
+            let node_end = node_start + node.len();
+            if first < node_end {
+                if workers <= node.len() && first + workers > node_end {
+                    first = node_end % cores.len();
+                }
+                break;
+            }
+            node_start = node_end;
+        }
+
+        (0..workers)
+            .map(|worker| cores[(first + worker) % cores.len()])
+            .collect()
+    }
+
+    #[cfg(target_os = "linux")]
+    fn node_cores() -> Vec<Vec<usize>> {
+        let entries = match std::fs::read_dir("/sys/devices/system/node") {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut nodes = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                let node = name.strip_prefix("node")?.parse::<usize>().ok()?;
+                let cpu_list = std::fs::read_to_string(entry.path().join("cpulist")).ok()?;
+                Some((node, parse_cpu_list(&cpu_list)))
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by_key(|(node, _)| *node);
+        nodes.into_iter().map(|(_, cores)| cores).collect()
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn node_cores() -> Vec<Vec<usize>> {
+        vec![]
+    }
+}
+
+/// Parses a cpu list of the kernel, e.g. `0-3,8-11`, the malformed ranges are skipped.
+pub fn parse_cpu_list(cpu_list: &str) -> Vec<usize> {
+    cpu_list
+        .trim()
+        .split(',')
+        .filter(|range| !range.is_empty())
+        .flat_map(|range| {
+            let mut bounds = range
+                .splitn(2, '-')
+                .map(|bound| bound.trim().parse::<usize>());
+            match (bounds.next(), bounds.next()) {
+                (Some(Ok(start)), None) => start..start + 1,
+                (Some(Ok(start)), Some(Ok(end))) if start <= end => start..end + 1,
+                _ => 0..0,
+            }
+        })
+        .collect()
+}
+
+/// Lets the current thread run on the cores. A thread inherits the cores of the thread which
+/// spawns it, so the threads spawned by a pinned thread are pinned too until they are reset.
+#[cfg(target_os = "linux")]
+pub fn set_current_thread_cores(cores: &[usize]) -> bool {
+    if cores.is_empty() {
+        return false;
+    }
+
+    // Safe: the set is a plain bitmap which outlives the call.
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        for core in cores {
+            libc::CPU_SET(*core, &mut set);
+        }
+        libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) == 0
+    }
+}
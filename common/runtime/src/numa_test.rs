@@ -0,0 +1,84 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use crate::numa::parse_cpu_list;
+use crate::NumaTopology;
+
+#[test]
+fn test_parse_cpu_list() {
+    assert_eq!(vec![0, 1, 2, 3, 8, 9], parse_cpu_list("0-3,8-9\n"));
+    assert_eq!(vec![5], parse_cpu_list("5"));
+    assert_eq!(vec![1, 4], parse_cpu_list("1,x,3-2,4"));
+    assert_eq!(Vec::<usize>::new(), parse_cpu_list("\n"));
+}
+
+#[test]
+fn test_numa_runtime_cores() {
+    struct Test {
+        name: &'static str,
+        first: usize,
+        workers: usize,
+        expect: Vec<usize>,
+    }
+
+    let topology = NumaTopology::create(vec![vec![0, 1, 2, 3], vec![], vec![4, 5, 6, 7]]);
+    assert_eq!(&[vec![0, 1, 2, 3], vec![4, 5, 6, 7]], topology.nodes());
+
+    let tests = vec![
+        Test {
+            name: "in the first node",
+            first: 0,
+            workers: 2,
+            expect: vec![0, 1],
+        },
+        Test {
+            name: "moved to the next node",
+            first: 2,
+            workers: 4,
+            expect: vec![4, 5, 6, 7],
+        },
+        Test {
+            name: "moved from the last node to the first one",
+            first: 14,
+            workers: 3,
+            expect: vec![0, 1, 2],
+        },
+        Test {
+            name: "more workers than the cores of a node",
+            first: 2,
+            workers: 6,
+            expect: vec![2, 3, 4, 5, 6, 7],
+        },
+        Test {
+            name: "more workers than cores",
+            first: 0,
+            workers: 10,
+            expect: vec![0, 1, 2, 3, 4, 5, 6, 7, 0, 1],
+        },
+    ];
+
+    for t in tests {
+        assert_eq!(
+            t.expect,
+            topology.runtime_cores(t.first, t.workers),
+            "{}",
+            t.name
+        );
+    }
+
+    // No cores, no pinning.
+    let topology = NumaTopology::create(vec![vec![]]);
+    assert_eq!(Vec::<usize>::new(), topology.runtime_cores(0, 2));
+}
+
+#[test]
+fn test_numa_topology_load() {
+    // Every node has cores and every core is of one node.
+    let topology = NumaTopology::load();
+    let mut cores = topology.nodes().concat();
+    cores.sort_unstable();
+    cores.dedup();
+    assert_eq!(topology.nodes().concat().len(), cores.len());
+    assert!(topology.nodes().iter().all(|node| !node.is_empty()));
+}
@@ -3,15 +3,26 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::mpsc;
+use std::sync::Arc;
+use std::sync::Barrier;
 use std::thread;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use core_affinity::CoreId;
 use tokio::runtime::Handle;
 use tokio::sync::oneshot;
 use tokio::task::JoinHandle;
 
+use crate::NumaTopology;
+
+// The core which the next pinned runtime of the process starts at, of the cores ordered node by
+// node.
+static NEXT_PINNED_CORE: AtomicUsize = AtomicUsize::new(0);
+
 /// Tokio Runtime wrapper.
 /// If a runtime is in an asynchronous context, shutdown it first.
 pub struct Runtime {
@@ -68,6 +79,58 @@ impl Runtime {
         Self::create(builder)
     }
 
+    /// Same as `with_worker_threads`, but the worker threads are pinned to the CPU cores one by
+    /// one, the cores of one NUMA node when the workers fit in it. The memory which the workers
+    /// allocate and touch first, e.g. the arenas and the hash tables of the aggregations and the
+    /// blocks of the shuffles, is placed on the node of their cores by the first-touch policy of
+    /// Linux. The runtimes of the process take the cores in turn, so the runtimes running at the
+    /// same time are spread over the cores and the nodes.
+    ///
+    /// The threads of the blocking tasks are not pinned, they run on the cores of the thread
+    /// which creates the runtime.
+    pub fn with_pinned_worker_threads(workers: usize) -> Result<Self> {
+        let topology = NumaTopology::load();
+        let first = NEXT_PINNED_CORE.fetch_add(workers, Ordering::Relaxed);
+        let cores = topology.runtime_cores(first, workers);
+        let unpinned = topology.nodes().concat();
+        let started = Arc::new(AtomicUsize::new(0));
+        let pinned = Arc::new(Barrier::new(workers + 1));
+
+        let mut runtime = tokio::runtime::Builder::new_multi_thread();
+        let builder = runtime
+            .enable_all()
+            .worker_threads(workers)
+            .on_thread_start({
+                let pinned = pinned.clone();
+                move || {
+                    // The workers are the first threads of the runtime, started by its build.
+                    let thread = started.fetch_add(1, Ordering::Relaxed);
+                    if thread < workers {
+                        if let Some(core) = cores.get(thread) {
+                            core_affinity::set_for_current(CoreId { id: *core });
+                        }
+                        pinned.wait();
+                    } else {
+                        Self::unpin_current_thread(&unpinned);
+                    }
+                }
+            });
+        let runtime = Self::create(builder)?;
+
+        // No blocking task is spawned before all the workers are started and pinned.
+        pinned.wait();
+        Ok(runtime)
+    }
+
+    // The blocking threads are spawned by the workers and inherit their cores on Linux.
+    #[cfg(target_os = "linux")]
+    fn unpin_current_thread(cores: &[usize]) {
+        crate::numa::set_current_thread_cores(cores);
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn unpin_current_thread(_cores: &[usize]) {}
+
     /// Spawns a new asynchronous task, returning a tokio::JoinHandle for it.
     /// Same as tokio::runtime.spawn.
     pub fn spawn<T>(&self, task: T) -> JoinHandle<T::Output>
@@ -21,3 +21,86 @@ async fn test_runtime() -> Result<()> {
     });
     Ok(())
 }
+
+#[test]
+fn test_pinned_runtime() -> Result<()> {
+    use std::sync::mpsc;
+
+    use crate::*;
+
+    // The tasks run on the pinned threads as on the others.
+    let runtime = Runtime::with_pinned_worker_threads(2)?;
+    let (tx, rx) = mpsc::channel();
+    for i in 0..4 {
+        let tx = tx.clone();
+        runtime.spawn(async move { tx.send(i).unwrap() });
+    }
+
+    let mut received = (0..4).map(|_| rx.recv().unwrap()).collect::<Vec<_>>();
+    received.sort_unstable();
+    assert_eq!(vec![0, 1, 2, 3], received);
+    Ok(())
+}
+
+// The cores the current thread is allowed to run on.
+#[cfg(target_os = "linux")]
+fn current_thread_cores() -> Vec<usize> {
+    unsafe {
+        let mut set: libc::cpu_set_t = std::mem::zeroed();
+        assert_eq!(
+            0,
+            libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set)
+        );
+        (0..libc::CPU_SETSIZE as usize)
+            .filter(|core| libc::CPU_ISSET(*core, &set))
+            .collect()
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_pinned_runtime_affinity() -> Result<()> {
+    use std::sync::mpsc;
+
+    use crate::*;
+
+    let unpinned = current_thread_cores();
+    let topology = NumaTopology::load();
+    let node_of = |core: usize| {
+        topology
+            .nodes()
+            .iter()
+            .position(|node| node.contains(&core))
+    };
+
+    // Every task runs on a worker pinned to one core, the workers fit in a node so they are of
+    // one node.
+    let runtime = Runtime::with_pinned_worker_threads(2)?;
+    let (tx, rx) = mpsc::channel();
+    for _ in 0..16 {
+        let tx = tx.clone();
+        runtime.spawn(async move { tx.send(current_thread_cores()).unwrap() });
+    }
+    let mut nodes = vec![];
+    for _ in 0..16 {
+        let cores = rx.recv().unwrap();
+        assert_eq!(1, cores.len(), "{:?}", cores);
+        assert!(unpinned.contains(&cores[0]), "{:?}", cores);
+        nodes.push(node_of(cores[0]));
+    }
+    if topology.nodes().iter().all(|node| node.len() >= 2) {
+        nodes.dedup();
+        assert_eq!(1, nodes.len(), "{:?}", nodes);
+    }
+
+    // The blocking threads are spawned by the pinned workers, but run on all the cores.
+    let (tx, rx) = mpsc::channel();
+    runtime.spawn(async move {
+        let cores = tokio::task::spawn_blocking(current_thread_cores)
+            .await
+            .unwrap();
+        tx.send(cores).unwrap()
+    });
+    assert_eq!(unpinned, rx.recv().unwrap());
+    Ok(())
+}
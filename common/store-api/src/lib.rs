@@ -15,11 +15,21 @@ pub use meta_api::CreateDatabaseActionResult;
 pub use meta_api::CreateTableActionResult;
 pub use meta_api::DropDatabaseActionResult;
 pub use meta_api::DropTableActionResult;
+pub use meta_api::ExportMetaActionResult;
 pub use meta_api::GetDatabaseActionResult;
+pub use meta_api::GetMetaStatusActionResult;
+pub use meta_api::GetMetaVersionActionResult;
+pub use meta_api::GetPlacementPolicyActionResult;
 pub use meta_api::GetTableActionResult;
+pub use meta_api::ImportMetaActionResult;
 pub use meta_api::MetaApi;
+pub use meta_api::PlacementPolicy;
+pub use meta_api::SetMetaReadOnlyActionResult;
+pub use meta_api::SetPlacementPolicyActionResult;
 pub use storage_api::AppendResult;
+pub use storage_api::AppendTxResult;
 pub use storage_api::BlockStream;
+pub use storage_api::ColumnStatistics;
 pub use storage_api::DataPartInfo;
 pub use storage_api::PartitionInfo;
 pub use storage_api::ReadAction;
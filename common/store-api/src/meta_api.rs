@@ -3,11 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0.
 //
 
+use std::collections::BTreeMap;
+
 use common_datavalues::DataSchemaRef;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
+use common_planners::TableOptions;
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
 pub struct CreateDatabaseActionResult {
@@ -37,6 +40,75 @@ pub struct GetTableActionResult {
     pub db: String,
     pub name: String,
     pub schema: DataSchemaRef,
+    pub comment: String,
+    pub engine: String,
+    pub options: TableOptions,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetMetaVersionActionResult {
+    pub meta_ver: u64,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ExportMetaActionResult {
+    /// The serialized meta dump, opaque to client.
+    pub data: Vec<u8>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct ImportMetaActionResult {
+    pub databases: u64,
+    pub tables: u64,
+}
+
+/// The rules to place the copies of the files on the store nodes, stored in the meta of the
+/// store cluster.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct PlacementPolicy {
+    /// The label of the store nodes whose values are the failure domains, e.g. `zone` or `rack`.
+    /// The copies of a file are placed on the nodes of different values of it, as long as there
+    /// are healthy nodes of such values. Empty if the nodes are not in failure domains.
+    pub failure_domain: String,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetPlacementPolicyActionResult {
+    /// The policy before it is set.
+    pub prev: PlacementPolicy,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct GetPlacementPolicyActionResult {
+    pub policy: PlacementPolicy,
+}
+
+/// The raft status of the meta service of a store node.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GetMetaStatusActionResult {
+    pub id: u64,
+    /// The raft state of the node, e.g. `Leader` or `Follower`.
+    pub state: String,
+    pub current_term: u64,
+    pub current_leader: Option<u64>,
+    pub last_log_index: u64,
+    pub last_applied: u64,
+    /// The index of the last log in the current snapshot, None if there is no snapshot.
+    pub snapshot_last_log_index: Option<u64>,
+    /// The milli seconds since the current snapshot is built or installed.
+    pub snapshot_age_ms: Option<u64>,
+    /// The number of logs the other nodes are behind the last log of this node, by the last
+    /// applied logs in their latest heartbeats.
+    pub peer_lags: BTreeMap<u64, u64>,
+    /// Whether the meta service rejects the DDL, see `MetaApi::set_meta_read_only`.
+    #[serde(default)]
+    pub read_only: bool,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct SetMetaReadOnlyActionResult {
+    /// Whether the meta service was read-only before it is set.
+    pub prev: bool,
 }
 
 #[async_trait::async_trait]
@@ -69,4 +141,39 @@ pub trait MetaApi {
         db: String,
         table: String,
     ) -> common_exception::Result<GetTableActionResult>;
+
+    /// Get the catalog version, which changes whenever a database or table is created or dropped.
+    async fn get_meta_version(&mut self) -> common_exception::Result<GetMetaVersionActionResult>;
+
+    /// Export the catalog(databases, tables and their parts) to a portable dump.
+    async fn export_meta(&mut self) -> common_exception::Result<ExportMetaActionResult>;
+
+    /// Restore the catalog from a dump produced by `export_meta`.
+    /// The target cluster must have an empty catalog.
+    async fn import_meta(
+        &mut self,
+        data: Vec<u8>,
+    ) -> common_exception::Result<ImportMetaActionResult>;
+
+    /// Set the placement policy of the cluster, the slots are moved to the failure domains of
+    /// the policy by the leader of the store cluster.
+    async fn set_placement_policy(
+        &mut self,
+        policy: PlacementPolicy,
+    ) -> common_exception::Result<SetPlacementPolicyActionResult>;
+
+    async fn get_placement_policy(
+        &mut self,
+    ) -> common_exception::Result<GetPlacementPolicyActionResult>;
+
+    /// Get the raft status of the meta service of the store node.
+    async fn get_meta_status(&mut self) -> common_exception::Result<GetMetaStatusActionResult>;
+
+    /// Put the meta service of the store cluster into the read-only mode or out of it, e.g.
+    /// during an upgrade or a backup. In the read-only mode the DDL is rejected with
+    /// `MetaServiceReadOnly`, the reads are still served.
+    async fn set_meta_read_only(
+        &mut self,
+        read_only: bool,
+    ) -> common_exception::Result<SetMetaReadOnlyActionResult>;
 }
@@ -3,18 +3,32 @@
 // SPDX-License-Identifier: Apache-2.0.
 //
 
+use std::collections::HashMap;
+
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_planners::Part;
 use common_planners::PlanNode;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
 use common_streams::SendableDataBlockStream;
 
-#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Eq, PartialEq)]
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
 pub struct DataPartInfo {
     pub part: Part,
     pub stats: Statistics,
+    /// The statistics of the columns of the part, by the column names.
+    #[serde(default)]
+    pub col_stats: HashMap<String, ColumnStatistics>,
+}
+
+/// The minimum and the maximum of the non-null values of a column in a part, a column of only
+/// null values has no statistics.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ColumnStatistics {
+    pub min: DataValue,
+    pub max: DataValue,
 }
 pub type ReadPlanResult = Option<Vec<DataPartInfo>>;
 
@@ -22,6 +36,12 @@ pub type ReadPlanResult = Option<Vec<DataPartInfo>>;
 pub struct ReadAction {
     pub part: Part,
     pub push_down: PlanNode,
+    /// The rows of the blocks the reader prefers, the store decides if it is None.
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+    /// The blocks the store may send ahead of the reader, the store decides if it is None.
+    #[serde(default)]
+    pub window: Option<usize>,
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default)]
@@ -45,6 +65,12 @@ pub struct PartitionInfo {
     pub wire_bytes: usize,
     pub disk_bytes: usize,
     pub location: String,
+    /// The content hash of the appended block, by which a retried append is deduplicated.
+    #[serde(default)]
+    pub block_hash: String,
+    /// The statistics of the columns of the part, by the column names.
+    #[serde(default)]
+    pub col_stats: HashMap<String, ColumnStatistics>,
 }
 
 impl AppendResult {
@@ -55,16 +81,22 @@ impl AppendResult {
         cols: usize,
         wire_bytes: usize,
         disk_bytes: usize,
-    ) {
+        block_hash: &str,
+    ) -> &mut PartitionInfo {
         let part = PartitionInfo {
             rows,
             cols,
             wire_bytes,
             disk_bytes,
             location: location.to_string(),
+            block_hash: block_hash.to_string(),
+            col_stats: HashMap::new(),
         };
         self.parts.push(part);
         self.summary.increase(rows, wire_bytes, disk_bytes);
+        // The part has just been pushed.
+        let last = self.parts.len() - 1;
+        &mut self.parts[last]
     }
 }
 
@@ -72,10 +104,24 @@ impl AppendResult {
 pub struct AppendResult {
     pub summary: Summary,
     pub parts: Vec<PartitionInfo>,
+    /// The number of blocks skipped by the deduplication since they are appended before, the
+    /// appends in a transaction are deduplicated when it commits.
+    #[serde(default)]
+    pub deduplicated_blocks: usize,
     pub session_id: String,
     pub tx_id: String,
 }
 
+/// The result of committing or aborting the appends of a transaction.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct AppendTxResult {
+    /// The number of parts committed or discarded.
+    pub parts: usize,
+    /// The number of blocks of the committed appends skipped by the deduplication.
+    #[serde(default)]
+    pub deduplicated_blocks: usize,
+}
+
 // TODO A better name, we already have a SendableDataBlockStream
 pub type BlockStream =
     std::pin::Pin<Box<dyn futures::stream::Stream<Item = DataBlock> + Sync + Send + 'static>>;
@@ -96,11 +142,28 @@ pub trait StorageApi {
         read_action: &ReadAction,
     ) -> common_exception::Result<SendableDataBlockStream>;
 
+    /// Append data to a table, `bucket` is the bucket of all the rows if the table is bucketed.
+    ///
+    /// If `tx_id` is provided, the appended parts are staged in the transaction and are not
+    /// visible until `commit_append` is called with it.
+    ///
+    /// With `deduplicate`, the blocks equal to a block among the latest appended blocks of the
+    /// table are skipped, so that a retried append does not duplicate the data. The blocks are
+    /// checked and recorded at once when the parts become visible.
     async fn append_data(
         &mut self,
         db_name: String,
         tbl_name: String,
         scheme_ref: DataSchemaRef,
+        bucket: Option<u64>,
+        tx_id: Option<String>,
+        deduplicate: bool,
         mut block_stream: BlockStream,
     ) -> common_exception::Result<AppendResult>;
+
+    /// Makes all the parts staged in a transaction visible at once.
+    async fn commit_append(&mut self, tx_id: String) -> common_exception::Result<AppendTxResult>;
+
+    /// Discards the parts staged in a transaction.
+    async fn abort_append(&mut self, tx_id: String) -> common_exception::Result<AppendTxResult>;
 }
@@ -5,6 +5,8 @@
 #[cfg(test)]
 mod stream_datablock_test;
 
+#[cfg(test)]
+mod stream_limit_result_test;
 #[cfg(test)]
 mod stream_progress_test;
 
@@ -13,6 +15,7 @@ mod stream_abort;
 mod stream_correct_with_schema;
 mod stream_datablock;
 mod stream_limit_by;
+mod stream_limit_result;
 mod stream_parquet;
 mod stream_progress;
 mod stream_skip;
@@ -25,6 +28,8 @@ pub use stream_abort::AbortStream;
 pub use stream_correct_with_schema::CorrectWithSchemaStream;
 pub use stream_datablock::DataBlockStream;
 pub use stream_limit_by::LimitByStream;
+pub use stream_limit_result::LimitResultStream;
+pub use stream_limit_result::OverflowMode;
 pub use stream_parquet::ParquetStream;
 pub use stream_progress::ProgressStream;
 pub use stream_skip::SkipStream;
@@ -0,0 +1,109 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::pin::Pin;
+use std::task::Context;
+use std::task::Poll;
+
+use common_datablocks::DataBlock;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::Stream;
+use futures::StreamExt;
+
+use crate::SendableDataBlockStream;
+
+/// What to do when the result exceeds the limits.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OverflowMode {
+    /// Fail the query.
+    Throw,
+    /// Return the result read so far, the result is truncated to the max rows. The block which
+    /// exceeds the max bytes is the last block of the result.
+    Break,
+}
+
+/// Limits the rows and bytes of the result of a query, 0 is unlimited.
+pub struct LimitResultStream {
+    input: SendableDataBlockStream,
+    max_rows: usize,
+    max_bytes: usize,
+    mode: OverflowMode,
+    rows: usize,
+    bytes: usize,
+    finished: bool,
+}
+
+impl LimitResultStream {
+    pub fn try_create(
+        input: SendableDataBlockStream,
+        max_rows: usize,
+        max_bytes: usize,
+        mode: OverflowMode,
+    ) -> Result<Self> {
+        Ok(LimitResultStream {
+            input,
+            max_rows,
+            max_bytes,
+            mode,
+            rows: 0,
+            bytes: 0,
+            finished: false,
+        })
+    }
+
+    fn limit_block(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let rows = self.rows + block.num_rows();
+        let bytes = self.bytes + block.memory_size();
+
+        if self.max_rows != 0 && rows > self.max_rows {
+            return match self.mode {
+                OverflowMode::Throw => Err(ErrorCode::ResultLimitExceeded(format!(
+                    "Limit for result exceeded, max rows: {}, current rows: {}",
+                    self.max_rows, rows
+                ))),
+                OverflowMode::Break => {
+                    self.finished = true;
+                    Ok(block.slice(0, self.max_rows - self.rows))
+                }
+            };
+        }
+
+        if self.max_bytes != 0 && bytes > self.max_bytes {
+            return match self.mode {
+                OverflowMode::Throw => Err(ErrorCode::ResultLimitExceeded(format!(
+                    "Limit for result exceeded, max bytes: {}, current bytes: {}",
+                    self.max_bytes, bytes
+                ))),
+                OverflowMode::Break => {
+                    self.finished = true;
+                    Ok(block)
+                }
+            };
+        }
+
+        self.rows = rows;
+        self.bytes = bytes;
+        // No more rows can be returned.
+        if self.mode == OverflowMode::Break && self.max_rows != 0 && rows == self.max_rows {
+            self.finished = true;
+        }
+        Ok(block)
+    }
+}
+
+impl Stream for LimitResultStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.finished {
+            return Poll::Ready(None);
+        }
+
+        self.input.poll_next_unpin(ctx).map(|x| match x {
+            Some(Ok(block)) => Some(self.limit_block(block)),
+            other => other,
+        })
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datablocks::*;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::*;
+
+fn create_input() -> SendableDataBlockStream {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]);
+    let block =
+        DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i64, 2, 3]).into()]);
+
+    Box::pin(DataBlockStream::create(
+        Arc::new(DataSchema::empty()),
+        None,
+        vec![block.clone(), block.clone(), block],
+    ))
+}
+
+#[tokio::test]
+async fn test_limit_result_stream() -> Result<()> {
+    // Unlimited.
+    let stream = LimitResultStream::try_create(create_input(), 0, 0, OverflowMode::Throw)?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.iter().map(|b| b.num_rows()).sum::<usize>(), 9);
+
+    // The result is truncated to the max rows.
+    let stream = LimitResultStream::try_create(create_input(), 4, 0, OverflowMode::Break)?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 1 |", "+---+",
+    ];
+    assert_blocks_eq(expected, result.as_slice());
+
+    // The stream ends at the max rows.
+    let stream = LimitResultStream::try_create(create_input(), 3, 0, OverflowMode::Break)?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 1);
+
+    // The block which exceeds the max bytes is the last block.
+    let stream = LimitResultStream::try_create(create_input(), 0, 1, OverflowMode::Break)?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_eq!(result.len(), 1);
+
+    let stream = LimitResultStream::try_create(create_input(), 4, 0, OverflowMode::Throw)?;
+    match stream.try_collect::<Vec<_>>().await {
+        Ok(_) => assert!(false, "The result limit must be exceeded"),
+        Err(e) => assert_eq!(
+            "Code: 55, displayText = Limit for result exceeded, max rows: 4, current rows: 6.",
+            e.to_string()
+        ),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryFrom;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::SchemaRef;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use fuse_query::datasources::MemoryTable;
+use fuse_query::interpreters::InterpreterFactory;
+use fuse_query::sessions::SessionRef;
+use fuse_query::sql::PlanParser;
+use futures::Stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use crate::DataFrame;
+
+pub type SendableRecordBatchStream = Pin<Box<dyn Stream<Item = Result<RecordBatch>> + Send>>;
+
+/// The context to execute the queries of an application, the queries are executed one by one.
+pub struct Context {
+    session: Arc<SessionRef>,
+}
+
+impl Context {
+    pub(crate) fn create(session: SessionRef) -> Context {
+        Context {
+            session: Arc::new(session),
+        }
+    }
+
+    /// Register the batches as a table of the current database, the table is kept in memory.
+    pub fn register_record_batches(
+        &self,
+        table: &str,
+        schema: SchemaRef,
+        batches: Vec<RecordBatch>,
+    ) -> Result<()> {
+        let blocks = batches
+            .into_iter()
+            .map(|batch| match batch.schema() == schema {
+                true => DataBlock::try_from(batch),
+                false => Err(ErrorCode::BadArguments(format!(
+                    "The schema of the batches of table {} mismatch",
+                    table
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let ctx = self.session.create_context();
+        let database = ctx.get_current_database();
+        let table = MemoryTable::create_with_blocks(
+            database.clone(),
+            table.to_string(),
+            Arc::new(DataSchema::from(schema)),
+            blocks,
+        );
+        ctx.get_datasource()
+            .register_table(&database, Arc::new(table))
+    }
+
+    /// Execute the SQL, the results are streamed as the query is executed.
+    pub async fn sql(&self, query: &str) -> Result<SendableRecordBatchStream> {
+        let ctx = self.session.create_context();
+        ctx.attach_query_info(query);
+
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let interpreter = InterpreterFactory::get(ctx, plan)?;
+        let stream = interpreter.execute().await?;
+
+        Ok(Box::pin(stream.map(|block| RecordBatch::try_from(block?))))
+    }
+
+    /// The schema of the results of the SQL, the query is planned but not executed.
+    pub fn sql_schema(&self, query: &str) -> Result<SchemaRef> {
+        let ctx = self.session.create_context();
+        let plan = PlanParser::create(ctx).build_from_sql(query)?;
+        Ok(Arc::new(plan.schema().to_arrow()))
+    }
+
+    /// A `DataFrame` which reads the table of the current database.
+    pub fn table(&self, table: &str) -> Result<DataFrame> {
+        let ctx = self.session.create_context();
+        let database = ctx.get_current_database();
+        let table = ctx.get_table(&database, table)?;
+
+        let scan = PlanBuilder::scan(
+            &database,
+            table.name(),
+            &table.schema()?,
+            None,
+            vec![],
+            None,
+        )?
+        .build()?;
+        let plan = match scan {
+            PlanNode::Scan(ref scan) => {
+                let partitions = ctx.get_settings().get_max_threads()? as usize;
+                PlanNode::ReadSource(table.read_plan(ctx.clone(), scan, partitions)?)
+            }
+            _unreachable_plan => panic!("Logical error: Cannot downcast to scan plan"),
+        };
+        Ok(DataFrame::create(self.session.clone(), plan))
+    }
+
+    /// Execute the SQL and collect all the results.
+    pub async fn sql_collect(&self, query: &str) -> Result<Vec<RecordBatch>> {
+        self.sql(query).await?.try_collect().await
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::UInt64Array;
+use common_arrow::arrow::datatypes::DataType;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::datatypes::Schema;
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_runtime::tokio;
+
+use crate::Engine;
+use crate::RecordBatch;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_context_sql() -> Result<()> {
+    let engine = Engine::create()?;
+    let ctx = engine.create_context()?;
+
+    let batches = ctx
+        .sql_collect("SELECT number, number * 2 AS doubled FROM numbers(3)")
+        .await?;
+    let expected = vec![
+        "+--------+---------+",
+        "| number | doubled |",
+        "+--------+---------+",
+        "| 0      | 0       |",
+        "| 1      | 2       |",
+        "| 2      | 4       |",
+        "+--------+---------+",
+    ];
+    assert_blocks_eq(expected, &to_blocks(batches)?);
+
+    let schema = ctx.sql_schema("SELECT number, number * 2 AS doubled FROM numbers(3)")?;
+    assert_eq!("doubled", schema.field(1).name());
+
+    // The syntax errors are returned.
+    assert!(ctx.sql("SELEC 1").await.is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_context_register_record_batches() -> Result<()> {
+    let engine = Engine::create()?;
+    let ctx = engine.create_context()?;
+
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt64, false)]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![Arc::new(UInt64Array::from(vec![
+        1, 2, 3,
+    ]))])?;
+    ctx.register_record_batches("t1", schema.clone(), vec![batch.clone(), batch])?;
+
+    // The tables are visible to the other contexts of the engine.
+    let other = engine.create_context()?;
+    let batches = other.sql_collect("SELECT sum(a) AS s FROM t1").await?;
+    let expected = vec!["+----+", "| s  |", "+----+", "| 12 |", "+----+"];
+    assert_blocks_eq(expected, &to_blocks(batches)?);
+
+    // The name of a table is unique in the database.
+    assert!(ctx.register_record_batches("t1", schema, vec![]).is_err());
+
+    // The batches must be of the schema.
+    let other_schema = Arc::new(Schema::new(vec![Field::new("b", DataType::UInt64, false)]));
+    let batch = RecordBatch::try_new(other_schema, vec![Arc::new(UInt64Array::from(vec![1]))])?;
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::UInt64, false)]));
+    assert!(ctx
+        .register_record_batches("t2", schema, vec![batch])
+        .is_err());
+
+    Ok(())
+}
+
+fn to_blocks(batches: Vec<RecordBatch>) -> Result<Vec<DataBlock>> {
+    batches.into_iter().map(DataBlock::try_from).collect()
+}
@@ -0,0 +1,230 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::SchemaRef;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::expand_aggregate_arg_exprs;
+use common_planners::expr_as_column_expr;
+use common_planners::find_aggregate_exprs;
+use common_planners::find_columns_not_satisfy_exprs;
+use common_planners::rebase_expr;
+use common_planners::rebase_expr_from_input;
+use common_planners::sort_to_inner_expr;
+use common_planners::unwrap_alias_exprs;
+use common_planners::Expression;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::SelectPlan;
+use fuse_query::interpreters::InterpreterFactory;
+use fuse_query::sessions::SessionRef;
+use futures::StreamExt;
+use futures::TryStreamExt;
+
+use crate::SendableRecordBatchStream;
+
+/// A query built by methods instead of SQL text, each method returns a new `DataFrame`:
+///
+/// ```ignore
+/// let batches = ctx
+///     .table("t1")?
+///     .filter(col("a").gt(lit(1u64)))?
+///     .aggregate(&[col("b")], &[sum(col("a")).alias("s")])?
+///     .sort(&[sort("s", false, false)])?
+///     .limit(10)?
+///     .collect()
+///     .await?;
+/// ```
+///
+/// The plan is the same as the plan of the equivalent SELECT, the expressions are validated
+/// when the method is called and the query is executed by `execute` or `collect`.
+#[derive(Clone)]
+pub struct DataFrame {
+    session: Arc<SessionRef>,
+    plan: PlanNode,
+}
+
+impl DataFrame {
+    pub(crate) fn create(session: Arc<SessionRef>, plan: PlanNode) -> DataFrame {
+        DataFrame { session, plan }
+    }
+
+    /// Keep the rows which the predicate is true.
+    pub fn filter(&self, predicate: Expression) -> Result<DataFrame> {
+        let predicate = rebase_expr_from_input(&predicate, &self.plan.schema())?;
+        // Resolve the type of the predicate, for the unknown columns.
+        predicate.to_data_field(&self.plan.schema())?;
+        self.with_plan(PlanBuilder::from(&self.plan).filter(predicate)?.build()?)
+    }
+
+    /// Evaluate the expressions for each row, the results are the columns of the new `DataFrame`.
+    pub fn select(&self, exprs: &[Expression]) -> Result<DataFrame> {
+        let plan = Self::expression(&self.plan, exprs, "Before Projection")?;
+        self.with_plan(Self::project(&plan, exprs)?)
+    }
+
+    /// Group the rows by `group_by` and evaluate the aggregate expressions for each group,
+    /// the columns of the new `DataFrame` are the `group_by` columns followed by the aggregates.
+    pub fn aggregate(&self, group_by: &[Expression], aggr: &[Expression]) -> Result<DataFrame> {
+        let aggr_exprs = find_aggregate_exprs(aggr);
+        let group_by_exprs = group_by
+            .iter()
+            .map(unwrap_alias_exprs)
+            .collect::<Result<Vec<_>>>()?;
+
+        let aggr_projection_exprs = group_by_exprs
+            .iter()
+            .chain(aggr_exprs.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        let before_aggr_exprs = expand_aggregate_arg_exprs(&aggr_projection_exprs);
+        let input = Self::expression(&self.plan, &before_aggr_exprs, "Before GroupBy")?;
+
+        let rebase = |exprs: &[Expression]| {
+            exprs
+                .iter()
+                .map(|expr| rebase_expr_from_input(expr, &input.schema()))
+                .collect::<Result<Vec<_>>>()
+        };
+        let plan = PlanBuilder::from(&input)
+            .aggregate_partial(&rebase(&aggr_exprs)?, &rebase(&group_by_exprs)?)?
+            .aggregate_final(
+                input.schema(),
+                &rebase(&aggr_exprs)?,
+                &rebase(&group_by_exprs)?,
+            )?
+            .build()?;
+
+        // The expressions out of the aggregate functions must be computed by the group by columns.
+        let column_exprs_post_aggr = aggr_projection_exprs
+            .iter()
+            .map(expr_as_column_expr)
+            .collect::<Result<Vec<_>>>()?;
+        let exprs_post_aggr = group_by
+            .iter()
+            .chain(aggr.iter())
+            .map(|expr| rebase_expr(expr, &aggr_projection_exprs))
+            .collect::<Result<Vec<_>>>()?;
+        if let Ok(Some(expr)) =
+            find_columns_not_satisfy_exprs(&column_exprs_post_aggr, &exprs_post_aggr)
+        {
+            return Err(ErrorCode::IllegalAggregateExp(format!(
+                "Column `{:?}` is not under aggregate function and not in group by: While processing {:?}",
+                expr, exprs_post_aggr
+            )));
+        }
+
+        let plan = Self::expression(&plan, &exprs_post_aggr, "Before Projection")?;
+        self.with_plan(Self::project(&plan, &exprs_post_aggr)?)
+    }
+
+    /// Sort the rows by the sort expressions, which are created by `sort(name, asc, nulls_first)`.
+    pub fn sort(&self, exprs: &[Expression]) -> Result<DataFrame> {
+        if let Some(expr) = exprs
+            .iter()
+            .find(|expr| !matches!(expr, Expression::Sort { .. }))
+        {
+            return Err(ErrorCode::BadArguments(format!(
+                "DataFrame can only be sorted by sort expressions, but got {:?}",
+                expr
+            )));
+        }
+
+        let plan = Self::expression(&self.plan, exprs, "Before OrderBy")?;
+        let order_by = exprs
+            .iter()
+            .map(|expr| rebase_expr_from_input(expr, &plan.schema()))
+            .collect::<Result<Vec<_>>>()?;
+        let plan = PlanBuilder::from(&plan).sort(&order_by)?.build()?;
+
+        // Remove the columns added for the sort expressions.
+        let columns = self
+            .plan
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expression::Column(field.name().clone()))
+            .collect::<Vec<_>>();
+        self.with_plan(Self::project(&plan, &columns)?)
+    }
+
+    /// Keep the first n rows.
+    pub fn limit(&self, n: usize) -> Result<DataFrame> {
+        self.with_plan(PlanBuilder::from(&self.plan).limit(n)?.build()?)
+    }
+
+    /// The schema of the results.
+    pub fn schema(&self) -> SchemaRef {
+        Arc::new(self.plan.schema().to_arrow())
+    }
+
+    /// The plan of the query, in the format of EXPLAIN.
+    pub fn explain(&self) -> String {
+        self.plan.display_indent_format().to_string()
+    }
+
+    /// Execute the query, the results are streamed as the query is executed.
+    pub async fn execute(&self) -> Result<SendableRecordBatchStream> {
+        let ctx = self.session.create_context();
+        ctx.attach_query_info(&self.explain());
+
+        let plan = PlanNode::Select(SelectPlan {
+            input: Arc::new(self.plan.clone()),
+        });
+        let interpreter = InterpreterFactory::get(ctx, plan)?;
+        let stream = interpreter.execute().await?;
+
+        Ok(Box::pin(stream.map(|block| RecordBatch::try_from(block?))))
+    }
+
+    /// Execute the query and collect all the results.
+    pub async fn collect(&self) -> Result<Vec<RecordBatch>> {
+        self.execute().await?.try_collect().await
+    }
+
+    fn with_plan(&self, plan: PlanNode) -> Result<DataFrame> {
+        Ok(DataFrame::create(self.session.clone(), plan))
+    }
+
+    /// Compute the expressions which are not columns of the input, the same as the expression
+    /// stages of a SELECT.
+    fn expression(input: &PlanNode, exprs: &[Expression], desc: &str) -> Result<PlanNode> {
+        let mut dedup_exprs = vec![];
+        for expr in exprs {
+            let rebased_expr = unwrap_alias_exprs(expr)
+                .and_then(|e| rebase_expr_from_input(&e, &input.schema()))?;
+            let rebased_expr = sort_to_inner_expr(&rebased_expr);
+
+            if !dedup_exprs.contains(&rebased_expr) {
+                dedup_exprs.push(rebased_expr);
+            }
+        }
+
+        if dedup_exprs
+            .iter()
+            .all(|expr| matches!(expr, Expression::Column(_)))
+        {
+            return Ok(input.clone());
+        }
+
+        PlanBuilder::from(input)
+            .expression(&dedup_exprs, desc)
+            .and_then(|builder| builder.build())
+    }
+
+    fn project(input: &PlanNode, exprs: &[Expression]) -> Result<PlanNode> {
+        let exprs = exprs
+            .iter()
+            .map(|expr| rebase_expr_from_input(expr, &input.schema()))
+            .collect::<Result<Vec<_>>>()?;
+
+        PlanBuilder::from(input)
+            .project(&exprs)
+            .and_then(|builder| builder.build())
+    }
+}
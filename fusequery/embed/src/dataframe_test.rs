@@ -0,0 +1,115 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::UInt64Array;
+use common_arrow::arrow::datatypes::DataType;
+use common_arrow::arrow::datatypes::Field;
+use common_arrow::arrow::datatypes::Schema;
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_runtime::tokio;
+
+use crate::*;
+
+fn create_context() -> Result<(Engine, Context)> {
+    let engine = Engine::create()?;
+    let ctx = engine.create_context()?;
+
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("a", DataType::UInt64, false),
+        Field::new("b", DataType::UInt64, false),
+    ]));
+    let batch = RecordBatch::try_new(schema.clone(), vec![
+        Arc::new(UInt64Array::from(vec![1, 2, 3, 4, 5])),
+        Arc::new(UInt64Array::from(vec![1, 2, 1, 2, 1])),
+    ])?;
+    ctx.register_record_batches("t1", schema, vec![batch])?;
+    Ok((engine, ctx))
+}
+
+fn to_blocks(batches: Vec<RecordBatch>) -> Result<Vec<DataBlock>> {
+    batches.into_iter().map(DataBlock::try_from).collect()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_dataframe_select_filter_limit() -> Result<()> {
+    let (_engine, ctx) = create_context()?;
+
+    let df = ctx
+        .table("t1")?
+        .filter(col("a").gt(lit(1u64)))?
+        .select(&[col("a"), add(col("a"), col("b")).alias("c")])?
+        .limit(3)?;
+    assert_eq!(
+        vec!["a", "c"],
+        df.schema()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let expected = vec![
+        "+---+---+",
+        "| a | c |",
+        "+---+---+",
+        "| 2 | 4 |",
+        "| 3 | 4 |",
+        "| 4 | 6 |",
+        "+---+---+",
+    ];
+    assert_blocks_eq(expected, &to_blocks(df.collect().await?)?);
+
+    // A DataFrame can be executed more than once.
+    assert_eq!(3, df.collect().await?[0].num_rows());
+
+    // Unknown columns are rejected when the DataFrame is built.
+    assert!(ctx.table("t1")?.filter(col("x").gt(lit(1u64))).is_err());
+    assert!(ctx.table("t2").is_err());
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_dataframe_aggregate_sort() -> Result<()> {
+    let (_engine, ctx) = create_context()?;
+
+    let df = ctx
+        .table("t1")?
+        .aggregate(&[col("b")], &[
+            sum(col("a")).alias("s"),
+            sum(add(col("a"), lit(1u64))).alias("v"),
+        ])?
+        .sort(&[sort("s", false, false)])?;
+
+    let expected = vec![
+        "+---+---+----+",
+        "| b | s | v  |",
+        "+---+---+----+",
+        "| 1 | 9 | 12 |",
+        "| 2 | 6 | 8  |",
+        "+---+---+----+",
+    ];
+    assert_blocks_eq(expected, &to_blocks(df.collect().await?)?);
+
+    // Without group by.
+    let df = ctx
+        .table("t1")?
+        .aggregate(&[], &[sum(col("a")).alias("s")])?;
+    let expected = vec!["+----+", "| s  |", "+----+", "| 15 |", "+----+"];
+    assert_blocks_eq(expected, &to_blocks(df.collect().await?)?);
+
+    // The columns out of the aggregate functions must be grouped by.
+    assert!(ctx
+        .table("t1")?
+        .aggregate(&[col("b")], &[add(col("a"), sum(col("b")))])
+        .is_err());
+
+    // Only the sort expressions can sort the rows.
+    assert!(ctx.table("t1")?.sort(&[col("a")]).is_err());
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use fuse_query::clusters::Cluster;
+use fuse_query::configs::Config;
+use fuse_query::sessions::SessionManager;
+use fuse_query::sessions::SessionManagerRef;
+
+use crate::Context;
+
+/// The engine of an application, the contexts created by it share the catalog of the engine.
+pub struct Engine {
+    sessions: SessionManagerRef,
+}
+
+impl Engine {
+    pub fn create() -> Result<Engine> {
+        // A standalone engine, the queries are never distributed.
+        let sessions = SessionManager::from_conf(Config::default(), Cluster::empty())?;
+        Ok(Engine { sessions })
+    }
+
+    /// A context is like a connection of a client, it has its own current database and settings.
+    pub fn create_context(&self) -> Result<Context> {
+        let session = self.sessions.create_session("Embedded")?;
+        Ok(Context::create(session))
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+//! Embed the FuseQuery engine in Rust applications, without running any server:
+//!
+//! ```ignore
+//! let engine = Engine::create()?;
+//! let ctx = engine.create_context()?;
+//! ctx.register_record_batches("t1", schema, batches)?;
+//! let batches = ctx.sql_collect("SELECT sum(a) FROM t1").await?;
+//!
+//! // The same query built by a DataFrame.
+//! let batches = ctx.table("t1")?.aggregate(&[], &[sum(col("a"))])?.collect().await?;
+//! ```
+//!
+//! The items of this crate follow semver by the version of the crate, the internal modules of
+//! fuse-query may change in any release and are not exposed here.
+
+#[cfg(test)]
+mod context_test;
+#[cfg(test)]
+mod dataframe_test;
+
+mod context;
+mod dataframe;
+mod engine;
+
+pub use common_arrow::arrow::datatypes::SchemaRef;
+pub use common_arrow::arrow::record_batch::RecordBatch;
+pub use common_exception::ErrorCode;
+pub use common_exception::Result;
+pub use common_planners::add;
+pub use common_planners::avg;
+pub use common_planners::col;
+pub use common_planners::lit;
+pub use common_planners::modular;
+pub use common_planners::not;
+pub use common_planners::sort;
+pub use common_planners::sum;
+pub use common_planners::Expression;
+pub use context::Context;
+pub use context::SendableRecordBatchStream;
+pub use dataframe::DataFrame;
+pub use engine::Engine;
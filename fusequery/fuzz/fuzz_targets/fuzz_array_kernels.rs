@@ -0,0 +1,185 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_arrow::arrow::alloc;
+use common_datavalues::prelude::*;
+use common_datavalues::AlignedVec;
+use honggfuzz::arbitrary;
+use honggfuzz::arbitrary::Arbitrary;
+use honggfuzz::arbitrary::Unstructured;
+use honggfuzz::fuzz;
+
+#[derive(Debug)]
+enum FuzzArray {
+    Boolean(Vec<Option<bool>>),
+    Int64(Vec<Option<i64>>),
+    Float64(Vec<Option<f64>>),
+    Utf8(Vec<Option<String>>),
+}
+
+#[derive(Debug)]
+enum VecOperation {
+    Push(i64),
+    ExtendFromSlice(Vec<i64>),
+    Extend(Vec<i64>),
+    Resize(u8, i64),
+    Reserve(u8),
+    ShrinkToFit,
+}
+
+#[derive(Debug)]
+struct Input {
+    array: FuzzArray,
+    // Indices of take, in the bounds of the array.
+    take: Vec<u16>,
+    // Indices of scatter, in the bounds of the scattered size.
+    scatter: Vec<u8>,
+    scattered_size: u8,
+    operations: Vec<VecOperation>,
+}
+
+impl<'a> Arbitrary<'a> for FuzzArray {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3u8)? {
+            0 => FuzzArray::Boolean(u.arbitrary()?),
+            1 => FuzzArray::Int64(u.arbitrary()?),
+            2 => FuzzArray::Float64(u.arbitrary()?),
+            _ => FuzzArray::Utf8(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for VecOperation {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5u8)? {
+            0 => VecOperation::Push(u.arbitrary()?),
+            1 => VecOperation::ExtendFromSlice(u.arbitrary()?),
+            2 => VecOperation::Extend(u.arbitrary()?),
+            3 => VecOperation::Resize(u.arbitrary()?, u.arbitrary()?),
+            4 => VecOperation::Reserve(u.arbitrary()?),
+            _ => VecOperation::ShrinkToFit,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Input {
+            array: u.arbitrary()?,
+            take: u.arbitrary()?,
+            scatter: u.arbitrary()?,
+            scattered_size: u.arbitrary()?,
+            operations: u.arbitrary()?,
+        })
+    }
+}
+
+// Compared by the formats, NaN is not equal to NaN.
+fn value(series: &Series, index: usize) -> String {
+    format!("{:?}", series.try_get(index).unwrap())
+}
+
+/// The unsafe kernels are called with the inputs which satisfy their contracts,
+/// so the results must be the same as the results of the safe ones.
+fn check_kernels(series: Series, take: &[u16], scatter: &[u8], scattered_size: u8) {
+    let rows = series.len();
+    if rows == 0 {
+        return;
+    }
+
+    let indices = take
+        .iter()
+        .map(|index| *index as usize % rows)
+        .collect::<Vec<_>>();
+    let taken = series.take_iter(&mut indices.iter().copied()).unwrap();
+    let taken_unchecked = unsafe {
+        series
+            .take_iter_unchecked(&mut indices.iter().copied())
+            .unwrap()
+    };
+    assert_eq!(taken.len(), indices.len());
+    assert_eq!(taken_unchecked.len(), indices.len());
+    for (i, index) in indices.iter().enumerate() {
+        let expected = value(&series, *index);
+        assert_eq!(value(&taken, i), expected);
+        assert_eq!(value(&taken_unchecked, i), expected);
+    }
+
+    let scattered_size = scattered_size as usize % 16 + 1;
+    let mut indices = scatter
+        .iter()
+        .map(|index| (*index as usize % scattered_size) as u64)
+        .collect::<Vec<_>>();
+    indices.resize(rows, 0);
+    let scattered = unsafe {
+        series
+            .scatter_unchecked(&mut indices.iter().copied(), scattered_size)
+            .unwrap()
+    };
+    assert_eq!(scattered.len(), scattered_size);
+    for (part, scattered) in scattered.iter().enumerate() {
+        let rows_of_part = (0..rows)
+            .filter(|row| indices[*row] as usize == part)
+            .collect::<Vec<_>>();
+        assert_eq!(scattered.len(), rows_of_part.len());
+        for (i, row) in rows_of_part.iter().enumerate() {
+            assert_eq!(value(scattered, i), value(&series, *row));
+        }
+    }
+}
+
+/// The AlignedVec must behave as a Vec, and keep the alignment of Arrow.
+fn check_aligned_vec(operations: Vec<VecOperation>) {
+    let mut vec = AlignedVec::<i64>::with_capacity_aligned(0);
+    let mut expected = Vec::<i64>::new();
+
+    for operation in operations {
+        match operation {
+            VecOperation::Push(v) => {
+                vec.push(v);
+                expected.push(v);
+            }
+            VecOperation::ExtendFromSlice(v) => {
+                vec.extend_from_slice(&v);
+                expected.extend_from_slice(&v);
+            }
+            VecOperation::Extend(v) => {
+                vec.extend(v.iter().copied());
+                expected.extend(v.iter().copied());
+            }
+            VecOperation::Resize(len, v) => {
+                vec.resize(len as usize, v);
+                expected.resize(len as usize, v);
+            }
+            VecOperation::Reserve(additional) => {
+                vec.reserve(additional as usize);
+                assert!(vec.capacity() >= vec.len() + additional as usize);
+            }
+            VecOperation::ShrinkToFit => vec.shrink_to_fit(),
+        }
+
+        assert_eq!(vec.as_mut_slice(), expected.as_slice());
+        if vec.capacity() > 0 {
+            assert_eq!((vec.as_ptr() as usize) % alloc::ALIGNMENT, 0);
+        }
+    }
+
+    let array = vec.into_primitive_array::<Int64Type>(None);
+    assert_eq!(array.values(), expected.as_slice());
+}
+
+fn main() {
+    loop {
+        fuzz!(|input: Input| {
+            let series = match input.array {
+                FuzzArray::Boolean(v) => Series::new(v),
+                FuzzArray::Int64(v) => Series::new(v),
+                FuzzArray::Float64(v) => Series::new(v),
+                FuzzArray::Utf8(v) => Series::new(v),
+            };
+            check_kernels(series, &input.take, &input.scatter, input.scattered_size);
+            check_aligned_vec(input.operations);
+        });
+    }
+}
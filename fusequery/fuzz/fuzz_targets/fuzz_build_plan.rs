@@ -0,0 +1,25 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use fuse_query::sessions::SessionManager;
+use fuse_query::sql::PlanParser;
+use honggfuzz::fuzz;
+
+fn main() {
+    let session_manager = SessionManager::try_create(1).unwrap();
+    let session = session_manager.create_session("Fuzz").unwrap();
+
+    loop {
+        fuzz!(|data: String| {
+            // The table functions of the remote endpoints connect to the endpoints when the
+            // plan is built.
+            let lowercase = data.to_lowercase();
+            if lowercase.contains("remote") || lowercase.contains("flight") {
+                return;
+            }
+
+            let _ = PlanParser::create(session.create_context()).build_from_sql(&data);
+        });
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::columns::DataColumn;
+use common_datavalues::prelude::*;
+use common_functions::scalars::FunctionFactory;
+use honggfuzz::arbitrary;
+use honggfuzz::arbitrary::Arbitrary;
+use honggfuzz::arbitrary::Unstructured;
+use honggfuzz::fuzz;
+
+#[derive(Debug)]
+enum FuzzValue {
+    Null,
+    Boolean(bool),
+    Int64(i64),
+    UInt64(u64),
+    Float64(f64),
+    Utf8(String),
+}
+
+#[derive(Debug)]
+enum FuzzColumn {
+    Boolean(Vec<Option<bool>>),
+    UInt8(Vec<Option<u8>>),
+    Int32(Vec<Option<i32>>),
+    Int64(Vec<Option<i64>>),
+    UInt64(Vec<Option<u64>>),
+    Float64(Vec<Option<f64>>),
+    Utf8(Vec<Option<String>>),
+    Constant(FuzzValue),
+}
+
+#[derive(Debug)]
+struct Input {
+    function: u16,
+    rows: u8,
+    columns: Vec<FuzzColumn>,
+}
+
+impl<'a> Arbitrary<'a> for FuzzValue {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=5u8)? {
+            0 => FuzzValue::Null,
+            1 => FuzzValue::Boolean(u.arbitrary()?),
+            2 => FuzzValue::Int64(u.arbitrary()?),
+            3 => FuzzValue::UInt64(u.arbitrary()?),
+            4 => FuzzValue::Float64(u.arbitrary()?),
+            _ => FuzzValue::Utf8(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for FuzzColumn {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=7u8)? {
+            0 => FuzzColumn::Boolean(u.arbitrary()?),
+            1 => FuzzColumn::UInt8(u.arbitrary()?),
+            2 => FuzzColumn::Int32(u.arbitrary()?),
+            3 => FuzzColumn::Int64(u.arbitrary()?),
+            4 => FuzzColumn::UInt64(u.arbitrary()?),
+            5 => FuzzColumn::Float64(u.arbitrary()?),
+            6 => FuzzColumn::Utf8(u.arbitrary()?),
+            _ => FuzzColumn::Constant(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Input {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Input {
+            function: u.arbitrary()?,
+            rows: u.arbitrary()?,
+            columns: u.arbitrary()?,
+        })
+    }
+}
+
+impl FuzzColumn {
+    fn into_column(self, rows: usize) -> DataColumn {
+        fn series<T: Clone>(mut values: Vec<Option<T>>, rows: usize) -> Vec<Option<T>> {
+            values.resize(rows, None);
+            values
+        }
+
+        match self {
+            FuzzColumn::Boolean(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::UInt8(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::Int32(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::Int64(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::UInt64(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::Float64(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::Utf8(v) => Series::new(series(v, rows)).into(),
+            FuzzColumn::Constant(v) => {
+                let value = match v {
+                    FuzzValue::Null => DataValue::Null,
+                    FuzzValue::Boolean(v) => DataValue::Boolean(Some(v)),
+                    FuzzValue::Int64(v) => DataValue::Int64(Some(v)),
+                    FuzzValue::UInt64(v) => DataValue::UInt64(Some(v)),
+                    FuzzValue::Float64(v) => DataValue::Float64(Some(v)),
+                    FuzzValue::Utf8(v) => DataValue::Utf8(Some(v)),
+                };
+                DataColumn::Constant(value, rows)
+            }
+        }
+    }
+}
+
+fn main() {
+    // The functions which panic or sleep by design.
+    let mut names = FunctionFactory::registered_names()
+        .into_iter()
+        .filter(|name| name != "crashme" && name != "sleep")
+        .collect::<Vec<_>>();
+    names.sort();
+
+    loop {
+        fuzz!(|input: Input| {
+            let name = &names[input.function as usize % names.len()];
+            let func = FunctionFactory::get(name).unwrap();
+
+            // The arguments are checked as the planner does, before the function is evaluated.
+            let arguments = input.columns.len();
+            let (min, max) = func
+                .variadic_arguments()
+                .unwrap_or_else(|| (func.num_arguments(), func.num_arguments()));
+            if arguments < min || arguments > max {
+                return;
+            }
+
+            let rows = input.rows as usize;
+            let columns = input
+                .columns
+                .into_iter()
+                .map(|column| column.into_column(rows))
+                .collect::<Vec<_>>();
+            let types = columns
+                .iter()
+                .map(|column| column.data_type())
+                .collect::<Vec<_>>();
+
+            if func.return_type(&types).is_ok() {
+                if let Ok(result) = func.eval(&columns, rows) {
+                    assert_eq!(result.len(), rows, "{}{:?}", name, types);
+                }
+            }
+        });
+    }
+}
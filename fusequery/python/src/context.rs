@@ -0,0 +1,59 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::Runtime;
+use fuse_query_embed::Context;
+use fuse_query_embed::Engine;
+use pyo3::prelude::*;
+
+use crate::utils::to_py_err;
+use crate::utils::to_py_table;
+
+/// `datafuse.Context()`, an engine of its own and a context of the engine.
+#[pyclass(name = "Context", module = "datafuse")]
+pub struct PyContext {
+    context: Arc<Context>,
+    runtime: Runtime,
+}
+
+#[pymethods]
+impl PyContext {
+    #[new]
+    fn new() -> PyResult<Self> {
+        let engine = Engine::create().map_err(to_py_err)?;
+        let context = engine.create_context().map_err(to_py_err)?;
+        let runtime = Runtime::with_default_worker_threads().map_err(to_py_err)?;
+        Ok(PyContext {
+            context: Arc::new(context),
+            runtime,
+        })
+    }
+
+    /// Execute the SQL and return the results as a `pyarrow.Table`.
+    fn sql(&self, py: Python, query: &str) -> PyResult<PyObject> {
+        let context = self.context.clone();
+        let query = query.to_string();
+        let task = self.runtime.spawn(async move {
+            let batches = context.sql_collect(&query).await?;
+            let schema = match batches.first() {
+                Some(batch) => batch.schema(),
+                None => context.sql_schema(&query)?,
+            };
+            Result::Ok((schema, batches))
+        });
+
+        // Other python threads can run while the query is executed.
+        let (schema, batches) = py
+            .allow_threads(|| {
+                futures::executor::block_on(task)
+                    .map_err(|e| ErrorCode::TokioError(e.to_string()))?
+            })
+            .map_err(to_py_err)?;
+        to_py_table(py, schema, &batches)
+    }
+}
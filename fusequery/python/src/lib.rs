@@ -0,0 +1,29 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+//! The python module `datafuse`, executes SQL by the embedded FuseQuery engine:
+//!
+//! ```python
+//! import datafuse
+//!
+//! ctx = datafuse.Context()
+//! table = ctx.sql("SELECT number, number * 2 AS doubled FROM numbers(10)")
+//! df = table.to_pandas()
+//! ```
+//!
+//! The results are `pyarrow.Table`, the arrays are exported by the Arrow C data interface
+//! without copying the buffers.
+
+mod context;
+mod utils;
+
+use pyo3::prelude::*;
+
+use crate::context::PyContext;
+
+#[pymodule]
+fn datafuse(_py: Python, m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyContext>()?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_arrow::arrow::array::ArrayRef;
+use common_arrow::arrow::datatypes::SchemaRef;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_exception::ErrorCode;
+use pyo3::exceptions::PyException;
+use pyo3::ffi::Py_uintptr_t;
+use pyo3::prelude::*;
+use pyo3::types::PyList;
+
+pub fn to_py_err(error: ErrorCode) -> PyErr {
+    PyException::new_err(error.to_string())
+}
+
+/// Export the array by the Arrow C data interface, pyarrow takes the ownership of the buffers.
+fn to_py_array(array: &ArrayRef, pyarrow: &PyModule) -> PyResult<PyObject> {
+    let (array_ptr, schema_ptr) = array.to_raw().map_err(|e| to_py_err(e.into()))?;
+    let array = pyarrow.getattr("Array")?.call_method1(
+        "_import_from_c",
+        (array_ptr as Py_uintptr_t, schema_ptr as Py_uintptr_t),
+    )?;
+    Ok(array.to_object(pyarrow.py()))
+}
+
+fn to_py_batch(batch: &RecordBatch, pyarrow: &PyModule) -> PyResult<PyObject> {
+    let arrays = batch
+        .columns()
+        .iter()
+        .map(|array| to_py_array(array, pyarrow))
+        .collect::<PyResult<Vec<_>>>()?;
+    let names = batch
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect::<Vec<_>>();
+
+    let batch = pyarrow
+        .getattr("RecordBatch")?
+        .call_method1("from_arrays", (arrays, names))?;
+    Ok(batch.to_object(pyarrow.py()))
+}
+
+/// Convert the batches to a `pyarrow.Table`, the table of no batches has the columns of the schema.
+pub fn to_py_table(py: Python, schema: SchemaRef, batches: &[RecordBatch]) -> PyResult<PyObject> {
+    let pyarrow = py.import("pyarrow")?;
+    let batches = match batches.is_empty() {
+        true => vec![to_py_batch(&RecordBatch::new_empty(schema), pyarrow)?],
+        false => batches
+            .iter()
+            .map(|batch| to_py_batch(batch, pyarrow))
+            .collect::<PyResult<Vec<_>>>()?,
+    };
+
+    let table = pyarrow
+        .getattr("Table")?
+        .call_method1("from_batches", (PyList::new(py, batches),))?;
+    Ok(table.to_object(py))
+}
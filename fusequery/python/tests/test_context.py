@@ -0,0 +1,32 @@
+# Copyright 2020-2021 The Datafuse Authors.
+#
+# SPDX-License-Identifier: Apache-2.0.
+
+import pyarrow as pa
+import pytest
+
+import datafuse
+
+
+def test_sql():
+    ctx = datafuse.Context()
+    table = ctx.sql("SELECT number, number * 2 AS doubled FROM numbers(3)")
+
+    assert isinstance(table, pa.Table)
+    assert table.column_names == ["number", "doubled"]
+    assert table.column("number").to_pylist() == [0, 1, 2]
+    assert table.column("doubled").to_pylist() == [0, 2, 4]
+
+
+def test_sql_empty_result():
+    ctx = datafuse.Context()
+    table = ctx.sql("SELECT number FROM numbers(3) WHERE number > 5")
+
+    assert table.num_rows == 0
+    assert table.column_names == ["number"]
+
+
+def test_sql_error():
+    ctx = datafuse.Context()
+    with pytest.raises(Exception, match="Code: "):
+        ctx.sql("SELEC 1")
@@ -8,7 +8,10 @@ mod suites;
 
 criterion_main! {
     suites::bench_aggregate_query_sql::benches,
+    suites::bench_clickbench_query_sql::benches,
     suites::bench_filter_query_sql::benches,
     suites::bench_limit_query_sql::benches,
     suites::bench_sort_query_sql::benches,
+    suites::bench_thread_pinning_query_sql::benches,
+    suites::bench_tpch_query_sql::benches,
 }
@@ -0,0 +1,12 @@
+SELECT COUNT(*) FROM hits
+SELECT COUNT(*) FROM hits WHERE AdvEngineID != 0
+SELECT SUM(AdvEngineID), COUNT(*), AVG(ResolutionWidth) FROM hits
+SELECT AVG(UserID) FROM hits
+SELECT uniq(UserID) FROM hits
+SELECT AdvEngineID, COUNT(*) AS c FROM hits WHERE AdvEngineID != 0 GROUP BY AdvEngineID ORDER BY c DESC
+SELECT RegionID, uniq(UserID) AS u FROM hits GROUP BY RegionID ORDER BY u DESC LIMIT 10
+SELECT SearchPhrase, COUNT(*) AS c FROM hits WHERE SearchPhrase != '' GROUP BY SearchPhrase ORDER BY c DESC LIMIT 10
+SELECT UserID, COUNT(*) AS c FROM hits GROUP BY UserID ORDER BY c DESC LIMIT 10
+SELECT UserID, SearchPhrase, COUNT(*) AS c FROM hits GROUP BY UserID, SearchPhrase ORDER BY c DESC LIMIT 10
+SELECT CounterID, COUNT(*) AS c FROM hits WHERE URL LIKE '%google%' GROUP BY CounterID ORDER BY c DESC LIMIT 10
+SELECT WatchID, ResolutionWidth FROM hits WHERE IsRefresh = 0 ORDER BY WatchID DESC LIMIT 10
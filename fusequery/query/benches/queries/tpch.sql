@@ -0,0 +1,10 @@
+-- Q1, pricing summary report.
+SELECT l_returnflag, l_linestatus, sum(l_quantity) AS sum_qty, sum(l_extendedprice) AS sum_base_price, sum(l_extendedprice * (1 - l_discount)) AS sum_disc_price, sum(l_extendedprice * (1 - l_discount) * (1 + l_tax)) AS sum_charge, avg(l_quantity) AS avg_qty, avg(l_extendedprice) AS avg_price, avg(l_discount) AS avg_disc, count(*) AS count_order FROM lineitem WHERE l_shipdate <= '1998-09-02' GROUP BY l_returnflag, l_linestatus ORDER BY l_returnflag, l_linestatus
+-- Q6, forecasting revenue change.
+SELECT sum(l_extendedprice * l_discount) AS revenue FROM lineitem WHERE l_shipdate >= '1994-01-01' AND l_shipdate < '1995-01-01' AND l_discount >= 0.05 AND l_discount <= 0.07 AND l_quantity < 24
+-- Q12 without the join of orders, shipping modes.
+SELECT l_shipmode, count(*) AS line_count FROM lineitem WHERE (l_shipmode = 'MAIL' OR l_shipmode = 'SHIP') AND l_shipdate >= '1994-01-01' AND l_shipdate < '1995-01-01' GROUP BY l_shipmode ORDER BY l_shipmode
+-- Q18 without the joins, the orders of the largest volume.
+SELECT l_orderkey, sum(l_quantity) AS quantity FROM lineitem GROUP BY l_orderkey ORDER BY quantity DESC LIMIT 100
+-- The revenue of the orders, the top N of a high cardinality aggregation.
+SELECT l_orderkey, sum(l_extendedprice * (1 - l_discount)) AS revenue FROM lineitem WHERE l_shipdate > '1995-03-15' GROUP BY l_orderkey ORDER BY revenue DESC LIMIT 10
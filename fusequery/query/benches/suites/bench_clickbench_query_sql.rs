@@ -0,0 +1,22 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+use crate::suites::criterion_benchmark_workload;
+use crate::suites::datagen::TableGenerator;
+
+fn criterion_benchmark_clickbench_query(c: &mut Criterion) {
+    criterion_benchmark_workload(
+        c,
+        "clickbench",
+        |rows| vec![TableGenerator::hits(rows)],
+        include_str!("../queries/clickbench.sql"),
+    );
+}
+
+criterion_group!(benches, criterion_benchmark_clickbench_query);
+criterion_main!(benches);
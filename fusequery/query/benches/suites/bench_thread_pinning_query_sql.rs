@@ -0,0 +1,52 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_runtime::tokio;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+use fuse_query::sessions::SessionManager;
+
+use crate::suites::execute;
+
+/// Runs the aggregations and the joins with and without pinning the threads of the queries to the
+/// cores of a NUMA node, the last queries build hash tables larger than the caches, so the
+/// pinned runs read them from the memory of their own node on the machines of several nodes.
+fn criterion_benchmark_thread_pinning_query(c: &mut Criterion) {
+    let queries = vec![
+        "SELECT COUNT(number) FROM numbers_mt(10000000) GROUP BY number%1000",
+        "SELECT COUNT(number) FROM numbers_mt(10000000) GROUP BY number%100000",
+        "SELECT SUM(number) FROM numbers_mt(10000000)",
+        "SELECT COUNT(*) FROM numbers_mt(1000000) AS a JOIN numbers_mt(100000) AS b ON a.number = b.number",
+        "SELECT COUNT(number) FROM numbers_mt(10000000) GROUP BY number%5000000",
+        "SELECT COUNT(*) FROM numbers_mt(10000000) AS a JOIN numbers_mt(5000000) AS b ON a.number = b.number",
+    ];
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let session_manager = SessionManager::try_create(1).unwrap();
+    let session = session_manager.create_session("Benches").unwrap();
+
+    let mut group = c.benchmark_group("runtime_thread_pinning");
+    group.sample_size(10);
+    for pinning in &[0, 1] {
+        let set = format!("SET enable_thread_pinning = {}", pinning);
+        runtime
+            .block_on(execute(session.create_context(), &set))
+            .unwrap();
+
+        for (i, query) in queries.iter().enumerate() {
+            group.bench_function(format!("pinning_{}_q{:02}", pinning, i), |b| {
+                b.iter(|| {
+                    runtime
+                        .block_on(execute(session.create_context(), query))
+                        .unwrap()
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark_thread_pinning_query);
+criterion_main!(benches);
@@ -0,0 +1,169 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write;
+use std::path::Path;
+
+use common_exception::Result;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+const SHIP_MODES: [&str; 7] = ["AIR", "FOB", "MAIL", "RAIL", "REG AIR", "SHIP", "TRUCK"];
+const SEARCH_PHRASES: [&str; 8] = [
+    "datafuse",
+    "rust arrow",
+    "olap database",
+    "clickhouse",
+    "vectorized execution",
+    "cloud warehouse",
+    "flight rpc",
+    "mysql protocol",
+];
+const DOMAINS: [&str; 5] = [
+    "google.com",
+    "github.com",
+    "datafuse.rs",
+    "yandex.ru",
+    "example.org",
+];
+
+/// A table of the benchmarks whose rows are generated with a fixed seed, every run reads the
+/// same data. The rows are written to a CSV file once, and loaded into a Memory table, so the
+/// queries do not measure the parsing of CSV.
+pub struct TableGenerator {
+    pub name: &'static str,
+    pub columns: &'static str,
+    pub rows: usize,
+    generate_row: fn(&mut StdRng, usize) -> String,
+}
+
+impl TableGenerator {
+    /// A subset of the TPC-H lineitem table, the dates are 'YYYY-MM-DD' strings.
+    pub fn lineitem(rows: usize) -> Self {
+        TableGenerator {
+            name: "lineitem",
+            columns: "l_orderkey bigint, l_partkey bigint, l_suppkey bigint, l_linenumber int, \
+                      l_quantity double, l_extendedprice double, l_discount double, l_tax double, \
+                      l_returnflag varchar, l_linestatus varchar, l_shipdate varchar, \
+                      l_shipmode varchar",
+            rows,
+            generate_row: |rng, i| {
+                // 1992-01-02 plus up to 2526 days, as dbgen.
+                let ship_days = 8036 + rng.gen_range(0..2526);
+                // 1995-06-17 is the current date of dbgen.
+                let shipped = ship_days <= 9298;
+
+                let part_key = rng.gen_range(1..200000u64);
+                let quantity = rng.gen_range(1..=50u64);
+                let price = quantity as f64 * (900.0 + (part_key % 1000) as f64) / 10.0;
+                format!(
+                    "{},{},{},{},{},{:.2},{:.2},{:.2},{},{},{},{}",
+                    i / 4 + 1,
+                    part_key,
+                    rng.gen_range(1..10000u64),
+                    i % 4 + 1,
+                    quantity,
+                    price,
+                    rng.gen_range(0..=10u64) as f64 / 100.0,
+                    rng.gen_range(0..=8u64) as f64 / 100.0,
+                    match shipped {
+                        true if rng.gen_bool(0.5) => "R",
+                        true => "A",
+                        false => "N",
+                    },
+                    if shipped { "F" } else { "O" },
+                    format_date(ship_days),
+                    SHIP_MODES[rng.gen_range(0..SHIP_MODES.len())],
+                )
+            },
+        }
+    }
+
+    /// A subset of the ClickBench hits table.
+    pub fn hits(rows: usize) -> Self {
+        TableGenerator {
+            name: "hits",
+            columns: "WatchID bigint, UserID bigint, CounterID int, EventDate varchar, \
+                      RegionID int, AdvEngineID tinyint, ResolutionWidth smallint, \
+                      SearchPhrase varchar, URL varchar, IsRefresh tinyint",
+            rows,
+            generate_row: |rng, i| {
+                // Most of the hits have no search phrase and no adv engine, as the real data.
+                let search_phrase = match rng.gen_range(0..10) {
+                    0 => SEARCH_PHRASES[rng.gen_range(0..SEARCH_PHRASES.len())],
+                    _ => "",
+                };
+                let adv_engine = match rng.gen_range(0..50) {
+                    0 => rng.gen_range(1..30u8),
+                    _ => 0,
+                };
+                format!(
+                    "{},{},{},{},{},{},{},{},https://{}/page/{},{}",
+                    i,
+                    rng.gen_range(0..100000u64),
+                    rng.gen_range(0..1000u32),
+                    format_date(15887 + rng.gen_range(0..31)),
+                    rng.gen_range(0..300u32),
+                    adv_engine,
+                    [1024, 1280, 1366, 1440, 1920][rng.gen_range(0..5)],
+                    search_phrase,
+                    DOMAINS[rng.gen_range(0..DOMAINS.len())],
+                    rng.gen_range(0..1000u32),
+                    rng.gen_range(0..2u8),
+                )
+            },
+        }
+    }
+
+    /// Returns the statements which load the table into memory, the CSV file is written to
+    /// the directory if it does not exist.
+    pub fn setup_statements(&self, dir: &Path) -> Result<Vec<String>> {
+        let path = dir.join(format!("{}_{}.csv", self.name, self.rows));
+        if !path.exists() {
+            std::fs::create_dir_all(dir)?;
+            let tmp_path = path.with_extension("tmp");
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            let mut rng = StdRng::seed_from_u64(42);
+            for i in 0..self.rows {
+                writeln!(writer, "{}", (self.generate_row)(&mut rng, i))?;
+            }
+            writer.flush()?;
+            std::fs::rename(&tmp_path, &path)?;
+        }
+
+        Ok(vec![
+            format!("DROP TABLE IF EXISTS {}_csv", self.name),
+            format!(
+                "CREATE TABLE {}_csv ({}) ENGINE = CSV location = '{}'",
+                self.name,
+                self.columns,
+                path.display()
+            ),
+            format!("DROP TABLE IF EXISTS {}", self.name),
+            format!(
+                "CREATE TABLE {} ({}) ENGINE = Memory",
+                self.name, self.columns
+            ),
+            format!("INSERT INTO {} SELECT * FROM {}_csv", self.name, self.name),
+        ])
+    }
+}
+
+/// Formats the days since 1970-01-01 as 'YYYY-MM-DD'.
+fn format_date(days: i64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
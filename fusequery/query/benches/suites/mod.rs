@@ -6,17 +6,23 @@ use common_exception::Result;
 use common_planners::PlanNode;
 use common_runtime::tokio;
 use criterion::Criterion;
+use fuse_query::interpreters::InterpreterFactory;
 use fuse_query::interpreters::SelectInterpreter;
-use fuse_query::servers::MySQLConnection;
-use fuse_query::sessions::session_ref::SessionRef;
+use fuse_query::sessions::FuseQueryContextRef;
 use fuse_query::sessions::SessionManager;
 use fuse_query::sql::PlanParser;
 use futures::StreamExt;
 
+use crate::suites::datagen::TableGenerator;
+
 pub mod bench_aggregate_query_sql;
+pub mod bench_clickbench_query_sql;
 pub mod bench_filter_query_sql;
 pub mod bench_limit_query_sql;
 pub mod bench_sort_query_sql;
+pub mod bench_thread_pinning_query_sql;
+pub mod bench_tpch_query_sql;
+pub mod datagen;
 
 pub async fn select_executor(sql: &str) -> Result<()> {
     let session_manager = SessionManager::try_create(1)?;
@@ -42,3 +48,56 @@ pub fn criterion_benchmark_suite(c: &mut Criterion, sql: &str) {
         })
     });
 }
+
+pub async fn execute(ctx: FuseQueryContextRef, sql: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(sql)?;
+    let mut stream = InterpreterFactory::get(ctx, plan)?.execute().await?;
+    while let Some(block) = stream.next().await {
+        block?;
+    }
+    Ok(())
+}
+
+/// Benchmarks the queries of the workload against the generated tables, one query per line,
+/// the lines of `--` are comments. The tables are loaded once, in the session of all the queries,
+/// the rows of every table are FUSE_BENCH_ROWS (1000000 by default).
+pub fn criterion_benchmark_workload(
+    c: &mut Criterion,
+    workload: &str,
+    tables: fn(usize) -> Vec<TableGenerator>,
+    queries: &str,
+) {
+    let rows = std::env::var("FUSE_BENCH_ROWS")
+        .ok()
+        .and_then(|rows| rows.parse::<usize>().ok())
+        .unwrap_or(1000000);
+
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let session_manager = SessionManager::try_create(1).unwrap();
+    let session = session_manager.create_session("Benches").unwrap();
+
+    let dir = std::env::temp_dir().join("fuse-query-benches");
+    for table in tables(rows) {
+        for statement in table.setup_statements(&dir).unwrap() {
+            runtime
+                .block_on(execute(session.create_context(), &statement))
+                .unwrap();
+        }
+    }
+
+    let mut group = c.benchmark_group(workload);
+    group.sample_size(10);
+    let queries = queries
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.starts_with("--"));
+    for (i, query) in queries.enumerate() {
+        group.bench_function(format!("{}_q{:02}", workload, i), |b| {
+            b.iter(|| {
+                runtime
+                    .block_on(execute(session.create_context(), query))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
@@ -0,0 +1,107 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::time::Duration;
+
+use warp::Filter;
+
+use crate::clusters::ClusterRef;
+use crate::configs::Config;
+use crate::sessions::SessionManagerRef;
+
+const STORE_CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The checks of the readiness, the node is ready if all of them pass.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ReadyChecks {
+    pub ready: bool,
+    // All the services are bound.
+    pub started: bool,
+    // The node is shutting down.
+    pub draining: bool,
+    // The node is standalone, or it is in the nodes of the cluster.
+    pub cluster: bool,
+    // The store is reachable, None if it is not checked by the readiness_check_store config.
+    pub store: Option<bool>,
+}
+
+pub fn health_handler(
+    cfg: Config,
+    cluster: ClusterRef,
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    healthz().or(readyz(cfg, cluster, sessions))
+}
+
+/// GET /healthz, OK as long as the process serves the requests.
+fn healthz() -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("healthz").and(warp::get()).map(|| "ok")
+}
+
+/// GET /readyz, OK if the node is ready for the queries, otherwise 503 Service Unavailable, with
+/// the checks in JSON.
+fn readyz(
+    cfg: Config,
+    cluster: ClusterRef,
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("readyz")
+        .and(warp::get())
+        .and(warp::any().map(move || cfg.clone()))
+        .and(warp::any().map(move || cluster.clone()))
+        .and(warp::any().map(move || sessions.clone()))
+        .and_then(handlers::readyz)
+}
+
+mod handlers {
+    use common_runtime::tokio;
+    use warp::http::StatusCode;
+
+    use crate::api::http::health::ReadyChecks;
+    use crate::api::http::health::STORE_CONNECT_TIMEOUT;
+    use crate::clusters::ClusterRef;
+    use crate::configs::Config;
+    use crate::sessions::SessionManagerRef;
+
+    pub async fn readyz(
+        cfg: Config,
+        cluster: ClusterRef,
+        sessions: SessionManagerRef,
+    ) -> Result<impl warp::Reply, std::convert::Infallible> {
+        let health = sessions.get_health();
+        let mut checks = ReadyChecks {
+            ready: false,
+            started: health.is_started(),
+            draining: health.is_draining(),
+            cluster: in_cluster(&cluster),
+            store: None,
+        };
+        if cfg.readiness_check_store {
+            checks.store = Some(store_reachable(&cfg.store_api_address).await);
+        }
+
+        checks.ready =
+            checks.started && !checks.draining && checks.cluster && checks.store != Some(false);
+        let status = match checks.ready {
+            true => StatusCode::OK,
+            false => StatusCode::SERVICE_UNAVAILABLE,
+        };
+        Ok(warp::reply::with_status(warp::reply::json(&checks), status))
+    }
+
+    fn in_cluster(cluster: &ClusterRef) -> bool {
+        match cluster.get_nodes() {
+            Ok(nodes) => nodes.is_empty() || nodes.iter().any(|node| node.is_local()),
+            Err(_) => false,
+        }
+    }
+
+    async fn store_reachable(address: &str) -> bool {
+        let connect = tokio::net::TcpStream::connect(address);
+        matches!(
+            tokio::time::timeout(STORE_CONNECT_TIMEOUT, connect).await,
+            Ok(Ok(_))
+        )
+    }
+}
@@ -0,0 +1,115 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_runtime::tokio;
+use warp::Filter;
+use warp::Reply;
+
+use crate::api::http::health::ReadyChecks;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_health() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    use crate::api::http::health::health_handler;
+    use crate::clusters::Cluster;
+    use crate::configs::Config;
+
+    let sessions = crate::tests::try_create_sessions()?;
+    let cluster = Cluster::empty();
+    let filter = health_handler(Config::default(), cluster.clone(), sessions.clone());
+
+    // Health.
+    {
+        let res = warp::test::request().path("/healthz").reply(&filter).await;
+        assert_eq!(200, res.status());
+        assert_eq!("ok", String::from_utf8_lossy(res.body()));
+    }
+
+    // Not ready before the services are started.
+    {
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(503, status);
+        assert!(!checks.ready);
+        assert!(!checks.started);
+    }
+
+    // Ready.
+    {
+        sessions.get_health().set_started();
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(200, status);
+        assert_eq!(
+            ReadyChecks {
+                ready: true,
+                started: true,
+                draining: false,
+                cluster: true,
+                store: None,
+            },
+            checks
+        );
+    }
+
+    // Not ready if the store is not reachable.
+    {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let mut conf = Config::default();
+        conf.readiness_check_store = true;
+        conf.store_api_address = listener.local_addr()?.to_string();
+        let filter = health_handler(conf.clone(), cluster.clone(), sessions.clone());
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(200, status);
+        assert_eq!(Some(true), checks.store);
+
+        drop(listener);
+        let filter = health_handler(conf, cluster.clone(), sessions.clone());
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(503, status);
+        assert_eq!(Some(false), checks.store);
+    }
+
+    // Not ready if the cluster does not have the node.
+    {
+        cluster.add_node("remote", 5, "1.2.3.4:9090").await?;
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(503, status);
+        assert!(!checks.cluster);
+
+        cluster.add_node("local", 5, "127.0.0.1:9090").await?;
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(200, status);
+        assert!(checks.cluster);
+    }
+
+    // Not ready while draining, and the new sessions are rejected.
+    {
+        sessions.get_health().set_draining();
+        let (status, checks) = readyz(&filter).await;
+        assert_eq!(503, status);
+        assert!(checks.draining);
+
+        let res = warp::test::request().path("/healthz").reply(&filter).await;
+        assert_eq!(200, res.status());
+
+        let session = sessions.create_session("TestSession");
+        assert_eq!(
+            "Code: 42, displayText = The server is shutting down, no new sessions are accepted.",
+            session.unwrap_err().to_string()
+        );
+    }
+
+    Ok(())
+}
+
+async fn readyz<F>(filter: &F) -> (u16, ReadyChecks)
+where
+    F: Filter + 'static,
+    F::Extract: Reply + Send,
+{
+    let res = warp::test::request().path("/readyz").reply(filter).await;
+    let checks = serde_json::from_slice(res.body()).unwrap();
+    (res.status().as_u16(), checks)
+}
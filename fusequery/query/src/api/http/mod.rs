@@ -2,6 +2,10 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod health_test;
+
 pub mod debug;
+pub mod health;
 pub mod router;
 pub mod v1;
@@ -7,15 +7,21 @@ use warp::Filter;
 
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
+use crate::sessions::SessionManagerRef;
 
 pub struct Router {
     cfg: Config,
     cluster: ClusterRef,
+    sessions: SessionManagerRef,
 }
 
 impl Router {
-    pub fn create(cfg: Config, cluster: ClusterRef) -> Self {
-        Router { cfg, cluster }
+    pub fn create(cfg: Config, cluster: ClusterRef, sessions: SessionManagerRef) -> Self {
+        Router {
+            cfg,
+            cluster,
+            sessions,
+        }
     }
 
     pub fn router(
@@ -24,7 +30,14 @@ impl Router {
         let v1 = super::v1::hello::hello_handler(self.cfg.clone())
             .or(super::v1::config::config_handler(self.cfg.clone()))
             .or(super::v1::cluster::cluster_handler(self.cluster.clone()))
-            .or(super::debug::home::debug_handler(self.cfg.clone()));
+            .or(super::v1::sessions::sessions_handler(self.sessions.clone()))
+            .or(super::v1::query::query_handler(self.sessions.clone()))
+            .or(super::debug::home::debug_handler(self.cfg.clone()))
+            .or(super::health::health_handler(
+                self.cfg.clone(),
+                self.cluster.clone(),
+                self.sessions.clone(),
+            ));
         let routes = v1.with(warp::log("v1"));
         Ok(routes)
     }
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
@@ -19,6 +20,9 @@ pub struct ClusterNodeRequest {
     // priority
     pub priority: u8,
     pub address: String,
+    // The labels of the node, e.g. `host` and `zone`
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
 }
 
 pub fn cluster_handler(
@@ -92,7 +96,7 @@ mod handlers {
     ) -> Result<impl warp::Reply, warp::Rejection> {
         info!("Cluster add node: {:?}", req);
         match cluster
-            .add_node(&req.name, req.priority, &req.address)
+            .add_node_with_labels(&req.name, req.priority, &req.address, req.labels)
             .await
         {
             Ok(_) => Ok(warp::reply::with_status(
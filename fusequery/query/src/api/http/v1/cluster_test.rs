@@ -26,6 +26,9 @@ async fn test_cluster() -> Result<()> {
                 name: "9090".to_string(),
                 priority: 8,
                 address: "127.0.0.1:9090".to_string(),
+                labels: vec![("zone".to_string(), "a".to_string())]
+                    .into_iter()
+                    .collect(),
             })
             .reply(&filter);
         assert_eq!(200, res.await.status());
@@ -38,6 +41,7 @@ async fn test_cluster() -> Result<()> {
                 name: "9091".to_string(),
                 priority: 4,
                 address: "127.0.0.1:9091".to_string(),
+                labels: Default::default(),
             })
             .reply(&filter);
         assert_eq!(200, res.await.status());
@@ -53,6 +57,7 @@ async fn test_cluster() -> Result<()> {
                 name: "9091".to_string(),
                 priority: 4,
                 address: "127.0.0.1:9091".to_string(),
+                labels: Default::default(),
             })
             .reply(&filter);
         assert_eq!(200, res.await.status());
@@ -64,7 +69,7 @@ async fn test_cluster() -> Result<()> {
             .path("/v1/cluster/list")
             .reply(&filter);
         assert_eq!(
-            "[{\"name\":\"9090\",\"priority\":8,\"address\":\"127.0.0.1:9090\",\"local\":true,\"sequence\":0}]",
+            "[{\"name\":\"9090\",\"priority\":8,\"address\":\"127.0.0.1:9090\",\"local\":true,\"sequence\":0,\"labels\":{\"zone\":\"a\"}}]",
             res.await.body()
         );
     }
@@ -4,7 +4,13 @@
 
 #[cfg(test)]
 mod cluster_test;
+#[cfg(test)]
+mod query_test;
+#[cfg(test)]
+mod sessions_test;
 
 pub mod cluster;
 pub mod config;
 pub mod hello;
+pub mod query;
+pub mod sessions;
@@ -0,0 +1,412 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+
+use warp::hyper::body::Bytes;
+use warp::Filter;
+
+use crate::sessions::SessionManagerRef;
+
+/// The media type of the results in the Arrow IPC stream format.
+pub const ARROW_STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+/// The media type of the results in JSON lines, a JSON object of each row.
+pub const JSON_LINES_CONTENT_TYPE: &str = "application/x-ndjson";
+/// The response header of the id of the query, which is used to kill the query.
+pub const QUERY_ID_HEADER: &str = "x-fuse-query-id";
+/// The prefix of the request headers of the settings of the query, the rest of the header name
+/// is the name of the setting, in which `-` is `_`, e.g. `X-Fuse-Setting-Max-Threads: 2`.
+pub const SETTING_HEADER_PREFIX: &str = "x-fuse-setting-";
+/// The request header of the current database of the query.
+pub const DATABASE_HEADER: &str = "x-fuse-database";
+
+pub fn query_handler(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    query(sessions.clone()).or(query_kill(sessions))
+}
+
+/// POST /v1/query?format=arrow|json&query_id=xxx
+///
+/// The body is the SQL of the query, which runs in a new session. The results are streamed in
+/// the Arrow IPC stream format or in JSON lines as the blocks are produced, programmatic clients
+/// read the Arrow ones without parsing the texts of the values.
+fn query(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "query")
+        .and(warp::post())
+        .and(warp::query::<HashMap<String, String>>())
+        .and(warp::header::headers_cloned())
+        .and(sql_body())
+        .and(with_sessions(sessions))
+        .and_then(handlers::query)
+}
+
+/// POST /v1/query/{query_id}/kill
+fn query_kill(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "query" / String / "kill")
+        .and(warp::post())
+        .and(with_sessions(sessions))
+        .and_then(handlers::kill_query)
+}
+
+fn with_sessions(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = (SessionManagerRef,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || sessions.clone())
+}
+
+fn sql_body() -> impl Filter<Extract = (Bytes,), Error = warp::Rejection> + Clone {
+    warp::body::content_length_limit(1024 * 1024).and(warp::body::bytes())
+}
+
+mod handlers {
+    use std::collections::HashMap;
+    use std::convert::TryInto;
+    use std::io;
+    use std::io::Write;
+    use std::sync::Arc;
+
+    use common_arrow::arrow::ipc::writer::StreamWriter;
+    use common_arrow::arrow::record_batch::RecordBatch;
+    use common_datablocks::DataBlock;
+    use common_datavalues::prelude::*;
+    use common_exception::ErrorCode;
+    use common_exception::Result;
+    use common_infallible::Mutex;
+    use common_streams::AbortStream;
+    use futures::StreamExt;
+    use log::info;
+    use serde_json::Value as JsonValue;
+    use warp::http::HeaderMap;
+    use warp::http::HeaderValue;
+    use warp::http::StatusCode;
+    use warp::hyper::body::Bytes;
+    use warp::hyper::Body;
+    use warp::Reply;
+
+    use crate::api::http::v1::query::*;
+    use crate::interpreters::InterpreterFactory;
+    use crate::sessions::FuseQueryContextRef;
+    use crate::sessions::SessionManagerRef;
+    use crate::sessions::SessionRef;
+    use crate::sql::PlanParser;
+
+    pub async fn query(
+        params: HashMap<String, String>,
+        headers: HeaderMap,
+        body: Bytes,
+        sessions: SessionManagerRef,
+    ) -> Result<warp::reply::Response, std::convert::Infallible> {
+        match execute(&params, &headers, &body, sessions).await {
+            Ok(response) => Ok(response),
+            Err(error) => Ok(
+                warp::reply::with_status(error.to_string(), StatusCode::BAD_REQUEST)
+                    .into_response(),
+            ),
+        }
+    }
+
+    pub async fn kill_query(
+        query_id: String,
+        sessions: SessionManagerRef,
+    ) -> Result<impl warp::Reply, std::convert::Infallible> {
+        info!("Kill query: {}", query_id);
+        match sessions.kill_query(&query_id) {
+            Ok(_) => Ok(warp::reply::with_status("".to_string(), StatusCode::OK)),
+            Err(error) => Ok(warp::reply::with_status(
+                error.message(),
+                StatusCode::NOT_FOUND,
+            )),
+        }
+    }
+
+    /// Starts the query and returns the response streaming its results. The errors before the
+    /// first block are the responses of the status 400, the errors after it abort the stream.
+    async fn execute(
+        params: &HashMap<String, String>,
+        headers: &HeaderMap,
+        body: &Bytes,
+        sessions: SessionManagerRef,
+    ) -> Result<warp::reply::Response> {
+        let format = params.get("format").map(String::as_str).unwrap_or("arrow");
+        let format = ResultFormat::try_create(format)?;
+        let sql = std::str::from_utf8(body)
+            .map_err(|e| ErrorCode::BadBytes(format!("The query is not UTF-8: {}", e)))?;
+
+        let session = sessions.create_session("HTTPSession")?;
+        let context = session.create_context();
+        if let Some(query_id) = params.get("query_id") {
+            context.set_id(query_id.clone())?;
+        }
+        apply_headers(&context, headers)?;
+        context.attach_query_info(sql);
+        let (schema, first, stream) = match start(&context, sql).await {
+            Ok(started) => started,
+            Err(error) => {
+                context.set_query_error(&error);
+                return Err(error);
+            }
+        };
+        let query_id = context.get_id();
+        let results = ResultStream {
+            encoder: ResultEncoder::try_create(format, &schema)?,
+            first,
+            stream,
+            finished: false,
+            _context: context,
+            _session: session,
+        };
+
+        let response = warp::http::Response::builder()
+            .header("content-type", format.content_type())
+            .header(QUERY_ID_HEADER, query_id)
+            .body(Body::wrap_stream(results.into_stream()))
+            .map_err(|e| ErrorCode::LogicalError(e.to_string()))?;
+        Ok(response)
+    }
+
+    async fn start(
+        context: &FuseQueryContextRef,
+        sql: &str,
+    ) -> Result<(DataSchemaRef, Option<DataBlock>, AbortStream)> {
+        context.wait_in_query_queue().await?;
+
+        let plan = PlanParser::create(context.clone()).build_from_sql(sql)?;
+        let interpreter = InterpreterFactory::get(context.clone(), plan)?;
+        let mut stream = context.try_create_abortable(interpreter.execute().await?)?;
+        let first = stream.next().await.transpose()?;
+
+        // The statements without results have no blocks, nor the schema of the interpreter.
+        let schema = match &first {
+            Some(block) => block.schema().clone(),
+            None => interpreter.schema(),
+        };
+        Ok((schema, first, stream))
+    }
+
+    fn apply_headers(context: &FuseQueryContextRef, headers: &HeaderMap) -> Result<()> {
+        let header_value = |name: &str, value: &HeaderValue| -> Result<String> {
+            match value.to_str() {
+                Ok(value) => Ok(value.to_string()),
+                Err(e) => Err(ErrorCode::BadArguments(format!(
+                    "Invalid header {}: {}",
+                    name, e
+                ))),
+            }
+        };
+
+        for (name, value) in headers {
+            let name = name.as_str();
+            if name == DATABASE_HEADER {
+                context.set_current_database(header_value(name, value)?)?;
+            } else if let Some(setting) = name.strip_prefix(SETTING_HEADER_PREFIX) {
+                let setting = setting.replace('-', "_");
+                context
+                    .get_settings()
+                    .update_settings(&setting, header_value(name, value)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    #[derive(Clone, Copy)]
+    enum ResultFormat {
+        Arrow,
+        JsonLines,
+    }
+
+    impl ResultFormat {
+        fn try_create(name: &str) -> Result<ResultFormat> {
+            match name.to_lowercase().as_str() {
+                "arrow" => Ok(ResultFormat::Arrow),
+                "json" => Ok(ResultFormat::JsonLines),
+                _ => Err(ErrorCode::BadArguments(format!(
+                    "Unknown format {}, expects arrow or json",
+                    name
+                ))),
+            }
+        }
+
+        fn content_type(&self) -> &'static str {
+            match self {
+                ResultFormat::Arrow => ARROW_STREAM_CONTENT_TYPE,
+                ResultFormat::JsonLines => JSON_LINES_CONTENT_TYPE,
+            }
+        }
+    }
+
+    /// The results of the query, the session and the context are kept until the results are
+    /// sent, so the query is running and can be killed by its id.
+    struct ResultStream {
+        encoder: ResultEncoder,
+        first: Option<DataBlock>,
+        stream: AbortStream,
+        finished: bool,
+        _context: FuseQueryContextRef,
+        _session: SessionRef,
+    }
+
+    impl ResultStream {
+        fn into_stream(self) -> impl futures::Stream<Item = io::Result<Bytes>> + Send {
+            futures::stream::unfold(self, |mut results| async move {
+                if results.finished {
+                    return None;
+                }
+
+                let block = match results.first.take() {
+                    Some(block) => Some(Ok(block)),
+                    None => results.stream.next().await,
+                };
+                let chunk = match block {
+                    Some(Ok(block)) => results.encoder.encode(&block),
+                    Some(Err(error)) => Err(error),
+                    None => {
+                        results.finished = true;
+                        results.encoder.finish()
+                    }
+                };
+
+                match chunk {
+                    Ok(chunk) => Some((Ok(Bytes::from(chunk)), results)),
+                    Err(error) => {
+                        results.finished = true;
+                        let error = io::Error::new(io::ErrorKind::Other, error.to_string());
+                        Some((Err(error), results))
+                    }
+                }
+            })
+        }
+    }
+
+    enum ResultEncoder {
+        Arrow {
+            buf: SharedBuffer,
+            writer: Option<StreamWriter<SharedBuffer>>,
+        },
+        JsonLines {
+            names: Vec<String>,
+        },
+    }
+
+    impl ResultEncoder {
+        fn try_create(format: ResultFormat, schema: &DataSchemaRef) -> Result<ResultEncoder> {
+            match format {
+                ResultFormat::Arrow => {
+                    let buf = SharedBuffer::default();
+                    let writer = StreamWriter::try_new(buf.clone(), &schema.to_arrow())?;
+                    Ok(ResultEncoder::Arrow {
+                        buf,
+                        writer: Some(writer),
+                    })
+                }
+                ResultFormat::JsonLines => {
+                    let names = schema.fields().iter().map(|f| f.name().clone()).collect();
+                    Ok(ResultEncoder::JsonLines { names })
+                }
+            }
+        }
+
+        /// Returns the bytes of the block, which are empty if they are still buffered.
+        fn encode(&mut self, block: &DataBlock) -> Result<Vec<u8>> {
+            match self {
+                ResultEncoder::Arrow { buf, writer } => {
+                    let batch: RecordBatch = block.clone().try_into()?;
+                    if let Some(writer) = writer {
+                        writer.write(&batch)?;
+                    }
+                    Ok(buf.take())
+                }
+                ResultEncoder::JsonLines { names } => {
+                    // The object is written by the fields, which keeps the order of the columns.
+                    let mut lines = vec![];
+                    for row in 0..block.num_rows() {
+                        lines.push(b'{');
+                        for (index, name) in names.iter().enumerate() {
+                            if index > 0 {
+                                lines.push(b',');
+                            }
+                            serde_json::to_writer(&mut lines, name)?;
+                            lines.push(b':');
+                            let value = block.column(index).try_get(row)?;
+                            serde_json::to_writer(&mut lines, &json_value(value))?;
+                        }
+                        lines.extend_from_slice(b"}\n");
+                    }
+                    Ok(lines)
+                }
+            }
+        }
+
+        /// Returns the rest of the bytes of the results.
+        fn finish(&mut self) -> Result<Vec<u8>> {
+            match self {
+                ResultEncoder::Arrow { buf, writer } => {
+                    // The writer flushes all the bytes to the buffer when it is dropped.
+                    if let Some(mut writer) = writer.take() {
+                        writer.finish()?;
+                    }
+                    Ok(buf.take())
+                }
+                ResultEncoder::JsonLines { .. } => Ok(vec![]),
+            }
+        }
+    }
+
+    /// The buffer written by the Arrow writer, whose bytes are taken as the chunks of the body.
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl SharedBuffer {
+        fn take(&self) -> Vec<u8> {
+            std::mem::take(&mut *self.0.lock())
+        }
+    }
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            self.0.lock().extend_from_slice(data);
+            Ok(data.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// The JSON value of the value, the values without a JSON type are their texts.
+    fn json_value(value: DataValue) -> JsonValue {
+        match value {
+            DataValue::Boolean(Some(v)) => JsonValue::Bool(v),
+            DataValue::Int8(Some(v)) => JsonValue::from(v),
+            DataValue::Int16(Some(v)) => JsonValue::from(v),
+            DataValue::Int32(Some(v)) => JsonValue::from(v),
+            DataValue::Int64(Some(v)) => JsonValue::from(v),
+            DataValue::UInt8(Some(v)) => JsonValue::from(v),
+            DataValue::UInt16(Some(v)) => JsonValue::from(v),
+            DataValue::UInt32(Some(v)) => JsonValue::from(v),
+            DataValue::UInt64(Some(v)) => JsonValue::from(v),
+            // The f32 is parsed from its text, which is shorter than the text of its f64.
+            DataValue::Float32(Some(v)) => json_float(v.to_string().parse().unwrap_or(v as f64)),
+            DataValue::Float64(Some(v)) => json_float(v),
+            DataValue::Utf8(Some(v)) => JsonValue::String(v),
+            DataValue::List(Some(values), _) | DataValue::Struct(values) => {
+                JsonValue::Array(values.into_iter().map(json_value).collect())
+            }
+            value if value.is_null() => JsonValue::Null,
+            value => JsonValue::String(value.to_string()),
+        }
+    }
+
+    // The NaN and the infinities are not JSON numbers.
+    fn json_float(v: f64) -> JsonValue {
+        match serde_json::Number::from_f64(v) {
+            Some(number) => JsonValue::Number(number),
+            None => JsonValue::String(v.to_string()),
+        }
+    }
+}
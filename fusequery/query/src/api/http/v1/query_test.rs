@@ -0,0 +1,216 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryInto;
+use std::io::Cursor;
+
+use common_arrow::arrow::ipc::reader::StreamReader;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::api::http::v1::query::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_arrow() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+    let filter = query_handler(sessions);
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query?format=arrow")
+        .body("SELECT number, number * 2 AS doubled FROM numbers_mt(5) ORDER BY number")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert_eq!(ARROW_STREAM_CONTENT_TYPE, res.headers()["content-type"]);
+
+    let reader = StreamReader::try_new(Cursor::new(res.body().to_vec()))?;
+    let blocks = reader
+        .map(|batch| -> Result<DataBlock> { batch?.try_into() })
+        .collect::<Result<Vec<_>>>()?;
+    let expected = vec![
+        "+--------+---------+",
+        "| number | doubled |",
+        "+--------+---------+",
+        "| 0      | 0       |",
+        "| 1      | 2       |",
+        "| 2      | 4       |",
+        "| 3      | 6       |",
+        "| 4      | 8       |",
+        "+--------+---------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, &blocks);
+
+    // The format is arrow by default.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query")
+        .body("SELECT 1")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert_eq!(ARROW_STREAM_CONTENT_TYPE, res.headers()["content-type"]);
+
+    // The statements without results have the schema of the interpreter and no batches.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query")
+        .body("SELECT number FROM numbers(10) WHERE number > 100")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    let reader = StreamReader::try_new(Cursor::new(res.body().to_vec()))?;
+    assert_eq!(1, reader.schema().fields().len());
+    assert_eq!(0, reader.count());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_json() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+    let filter = query_handler(sessions);
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query?format=json")
+        .body("SELECT number, number * 2 AS doubled, 'a' AS s, NULL AS n FROM numbers_mt(3) ORDER BY number")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert_eq!(JSON_LINES_CONTENT_TYPE, res.headers()["content-type"]);
+    assert_eq!(
+        concat!(
+            "{\"number\":0,\"doubled\":0,\"s\":\"a\",\"n\":null}\n",
+            "{\"number\":1,\"doubled\":2,\"s\":\"a\",\"n\":null}\n",
+            "{\"number\":2,\"doubled\":4,\"s\":\"a\",\"n\":null}\n",
+        ),
+        String::from_utf8_lossy(res.body())
+    );
+
+    // No rows, no lines.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query?format=json")
+        .body("SELECT number FROM numbers(10) WHERE number > 100")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert_eq!("", String::from_utf8_lossy(res.body()));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_headers() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+    let filter = query_handler(sessions);
+
+    // The settings and the database of the query are set by the headers.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query?format=json")
+        .header("X-Fuse-Setting-Max-Threads", "3")
+        .header("X-Fuse-Database", "system")
+        .body("SELECT value, database() AS db FROM settings WHERE name = 'max_threads'")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert_eq!(
+        "{\"value\":\"3\",\"db\":\"system\"}\n",
+        String::from_utf8_lossy(res.body())
+    );
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query")
+        .header("X-Fuse-Setting-Not-Exists", "3")
+        .body("SELECT 1")
+        .reply(&filter)
+        .await;
+    assert_eq!(400, res.status());
+    assert!(String::from_utf8_lossy(res.body()).contains("not_exists"));
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query")
+        .header("X-Fuse-Database", "not_exists")
+        .body("SELECT 1")
+        .reply(&filter)
+        .await;
+    assert_eq!(400, res.status());
+    assert!(String::from_utf8_lossy(res.body()).contains("not_exists"));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_id() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+    let filter = query_handler(sessions);
+
+    // The id is given by the client, or generated.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query?query_id=my_query")
+        .body("SELECT 1")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert_eq!("my_query", res.headers()[QUERY_ID_HEADER]);
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query")
+        .body("SELECT 1")
+        .reply(&filter)
+        .await;
+    assert_eq!(200, res.status());
+    assert!(!res.headers()[QUERY_ID_HEADER].is_empty());
+
+    // The query is finished, there is nothing to kill.
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query/my_query/kill")
+        .reply(&filter)
+        .await;
+    assert_eq!(404, res.status());
+    assert_eq!(
+        "Unknown query: my_query",
+        String::from_utf8_lossy(res.body())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_errors() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+    let filter = query_handler(sessions);
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query?format=csv")
+        .body("SELECT 1")
+        .reply(&filter)
+        .await;
+    assert_eq!(400, res.status());
+    assert_eq!(
+        "Code: 6, displayText = Unknown format csv, expects arrow or json.",
+        String::from_utf8_lossy(res.body())
+    );
+
+    let res = warp::test::request()
+        .method("POST")
+        .path("/v1/query")
+        .body("SELECT * FROM system.not_exists")
+        .reply(&filter)
+        .await;
+    assert_eq!(400, res.status());
+    assert!(String::from_utf8_lossy(res.body()).contains("not_exists"));
+
+    Ok(())
+}
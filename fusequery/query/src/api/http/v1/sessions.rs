@@ -0,0 +1,111 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use warp::Filter;
+
+use crate::sessions::ProcessInfo;
+use crate::sessions::SessionManagerRef;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionInfo {
+    pub id: String,
+    pub user: String,
+    pub host: Option<String>,
+    pub state: String,
+    pub database: String,
+    pub extra_info: Option<String>,
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SessionKillRequest {
+    pub id: String,
+}
+
+impl From<&ProcessInfo> for SessionInfo {
+    fn from(process_info: &ProcessInfo) -> Self {
+        SessionInfo {
+            id: process_info.id.clone(),
+            user: process_info.user.clone(),
+            host: process_info
+                .client_address
+                .map(|address| address.to_string()),
+            state: process_info.state.clone(),
+            database: process_info.database.clone(),
+            extra_info: process_info.session_extra_info.clone(),
+        }
+    }
+}
+
+pub fn sessions_handler(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    sessions_list(sessions.clone()).or(sessions_kill(sessions))
+}
+
+/// GET /v1/sessions/list
+fn sessions_list(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "sessions" / "list")
+        .and(warp::get())
+        .and(with_sessions(sessions))
+        .and_then(handlers::list_sessions)
+}
+
+/// POST /v1/sessions/kill
+fn sessions_kill(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("v1" / "sessions" / "kill")
+        .and(warp::post())
+        .and(json_body())
+        .and(with_sessions(sessions))
+        .and_then(handlers::kill_session)
+}
+
+fn with_sessions(
+    sessions: SessionManagerRef,
+) -> impl Filter<Extract = (SessionManagerRef,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || sessions.clone())
+}
+
+fn json_body() -> impl Filter<Extract = (SessionKillRequest,), Error = warp::Rejection> + Clone {
+    warp::body::content_length_limit(1024 * 16).and(warp::body::json())
+}
+
+mod handlers {
+    use log::info;
+
+    use crate::api::http::v1::sessions::SessionInfo;
+    use crate::api::http::v1::sessions::SessionKillRequest;
+    use crate::sessions::SessionManagerRef;
+
+    pub async fn list_sessions(
+        sessions: SessionManagerRef,
+    ) -> Result<impl warp::Reply, std::convert::Infallible> {
+        let sessions = sessions
+            .processes_info()
+            .iter()
+            .map(SessionInfo::from)
+            .collect::<Vec<_>>();
+        Ok(warp::reply::json(&sessions))
+    }
+
+    pub async fn kill_session(
+        req: SessionKillRequest,
+        sessions: SessionManagerRef,
+    ) -> Result<impl warp::Reply, std::convert::Infallible> {
+        info!("Kill session: {:?}", req);
+        match sessions.kill_session(&req.id) {
+            Ok(_) => Ok(warp::reply::with_status(
+                "".to_string(),
+                warp::http::StatusCode::OK,
+            )),
+            Err(error) => Ok(warp::reply::with_status(
+                error.message(),
+                warp::http::StatusCode::NOT_FOUND,
+            )),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_runtime::tokio;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_sessions() -> Result<()> {
+    use pretty_assertions::assert_eq;
+
+    use crate::api::http::v1::sessions::*;
+
+    let sessions = crate::tests::try_create_sessions()?;
+    let filter = sessions_handler(sessions.clone());
+
+    let session = sessions.create_user_session("TestSession", "user1")?;
+
+    // List.
+    {
+        let res = warp::test::request()
+            .path("/v1/sessions/list")
+            .reply(&filter)
+            .await;
+        assert_eq!(200, res.status());
+
+        let infos: Vec<SessionInfo> = serde_json::from_slice(res.body())?;
+        assert_eq!(1, infos.len());
+        assert_eq!(session.get_id(), infos[0].id);
+        assert_eq!("user1", infos[0].user);
+        assert_eq!("Idle", infos[0].state);
+    }
+
+    // Kill.
+    {
+        let res = warp::test::request()
+            .method("POST")
+            .path("/v1/sessions/kill")
+            .json(&SessionKillRequest {
+                id: session.get_id(),
+            })
+            .reply(&filter)
+            .await;
+        assert_eq!(200, res.status());
+        assert!(session.is_aborting());
+
+        let res = warp::test::request()
+            .method("POST")
+            .path("/v1/sessions/kill")
+            .json(&SessionKillRequest {
+                id: "unknown".to_string(),
+            })
+            .reply(&filter)
+            .await;
+        assert_eq!(404, res.status());
+    }
+
+    Ok(())
+}
@@ -6,10 +6,12 @@
 
 pub use http_service::HttpService;
 pub use rpc::BroadcastAction;
+pub use rpc::CatalogChangeAction;
 pub use rpc::FlightAction;
 pub use rpc::FlightClient;
 pub use rpc::FlightTicket;
 pub use rpc::ShuffleAction;
+pub use rpc::SubstraitPlanAction;
 pub use rpc_service::RpcService;
 
 mod http;
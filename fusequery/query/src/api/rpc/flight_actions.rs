@@ -11,6 +11,8 @@ use common_planners::Expression;
 use common_planners::PlanNode;
 use tonic::Status;
 
+use crate::datasources::CatalogChange;
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub struct ShuffleAction {
     pub query_id: String,
@@ -76,10 +78,51 @@ impl TryInto<Vec<u8>> for BroadcastAction {
     }
 }
 
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct CatalogChangeAction {
+    // The tenant whose catalog is changed, None for the catalog of the sessions not bound to a tenant.
+    pub tenant: Option<String>,
+    pub change: CatalogChange,
+}
+
+impl TryInto<CatalogChangeAction> for Vec<u8> {
+    type Error = Status;
+
+    fn try_into(self) -> Result<CatalogChangeAction, Self::Error> {
+        match std::str::from_utf8(&self) {
+            Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+            Ok(utf8_body) => match serde_json::from_str::<CatalogChangeAction>(utf8_body) {
+                Err(cause) => Err(Status::invalid_argument(cause.to_string())),
+                Ok(action) => Ok(action),
+            },
+        }
+    }
+}
+
+impl TryInto<Vec<u8>> for CatalogChangeAction {
+    type Error = ErrorCode;
+
+    fn try_into(self) -> Result<Vec<u8>, Self::Error> {
+        serde_json::to_vec(&self).map_err_to_code(ErrorCode::LogicalError, || {
+            "Logical error: cannot serialize CatalogChangeAction."
+        })
+    }
+}
+
+/// A Substrait plan encoded by protobuf, the body of the action is the plan itself.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SubstraitPlanAction {
+    pub plan: Vec<u8>,
+}
+
 #[derive(Clone, Debug)]
 pub enum FlightAction {
     PrepareShuffleAction(ShuffleAction),
     BroadcastAction(BroadcastAction),
+    // Not a query stage, applies a DDL of another query node to the catalog of this one.
+    CatalogChangeAction(CatalogChangeAction),
+    // Not a query stage, executes a plan of an external front-end as a query of its own.
+    SubstraitPlanAction(SubstraitPlanAction),
 }
 
 impl FlightAction {
@@ -87,6 +130,12 @@ impl FlightAction {
         match self {
             FlightAction::BroadcastAction(action) => action.query_id.clone(),
             FlightAction::PrepareShuffleAction(action) => action.query_id.clone(),
+            FlightAction::CatalogChangeAction(_) => {
+                unreachable!("CatalogChangeAction has no query_id")
+            }
+            FlightAction::SubstraitPlanAction(_) => {
+                unreachable!("SubstraitPlanAction has no query_id")
+            }
         }
     }
 
@@ -94,6 +143,12 @@ impl FlightAction {
         match self {
             FlightAction::BroadcastAction(action) => action.stage_id.clone(),
             FlightAction::PrepareShuffleAction(action) => action.stage_id.clone(),
+            FlightAction::CatalogChangeAction(_) => {
+                unreachable!("CatalogChangeAction has no stage_id")
+            }
+            FlightAction::SubstraitPlanAction(_) => {
+                unreachable!("SubstraitPlanAction has no stage_id")
+            }
         }
     }
 
@@ -101,6 +156,12 @@ impl FlightAction {
         match self {
             FlightAction::BroadcastAction(action) => action.sinks.clone(),
             FlightAction::PrepareShuffleAction(action) => action.sinks.clone(),
+            FlightAction::CatalogChangeAction(_) => {
+                unreachable!("CatalogChangeAction has no sinks")
+            }
+            FlightAction::SubstraitPlanAction(_) => {
+                unreachable!("SubstraitPlanAction has no sinks")
+            }
         }
     }
 
@@ -108,6 +169,8 @@ impl FlightAction {
         match self {
             FlightAction::BroadcastAction(action) => action.plan.clone(),
             FlightAction::PrepareShuffleAction(action) => action.plan.clone(),
+            FlightAction::CatalogChangeAction(_) => unreachable!("CatalogChangeAction has no plan"),
+            FlightAction::SubstraitPlanAction(_) => unreachable!("SubstraitPlanAction has no plan"),
         }
     }
 
@@ -115,6 +178,8 @@ impl FlightAction {
         match self {
             FlightAction::BroadcastAction(_) => None,
             FlightAction::PrepareShuffleAction(action) => Some(action.scatters_expression.clone()),
+            FlightAction::CatalogChangeAction(_) => None,
+            FlightAction::SubstraitPlanAction(_) => None,
         }
     }
 }
@@ -126,6 +191,10 @@ impl TryInto<FlightAction> for Action {
         match self.r#type.as_str() {
             "PrepareShuffleAction" => Ok(FlightAction::PrepareShuffleAction(self.body.try_into()?)),
             "BroadcastAction" => Ok(FlightAction::BroadcastAction(self.body.try_into()?)),
+            "CatalogChangeAction" => Ok(FlightAction::CatalogChangeAction(self.body.try_into()?)),
+            "SubstraitPlanAction" => Ok(FlightAction::SubstraitPlanAction(SubstraitPlanAction {
+                plan: self.body,
+            })),
             un_implemented => Err(Status::unimplemented(format!(
                 "UnImplement action {}",
                 un_implemented
@@ -147,6 +216,14 @@ impl TryInto<Action> for FlightAction {
                 r#type: String::from("BroadcastAction"),
                 body: broadcast_action.try_into()?,
             }),
+            FlightAction::CatalogChangeAction(catalog_change_action) => Ok(Action {
+                r#type: String::from("CatalogChangeAction"),
+                body: catalog_change_action.try_into()?,
+            }),
+            FlightAction::SubstraitPlanAction(substrait_plan_action) => Ok(Action {
+                r#type: String::from("SubstraitPlanAction"),
+                body: substrait_plan_action.plan,
+            }),
         }
     }
 }
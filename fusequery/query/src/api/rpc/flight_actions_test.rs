@@ -11,7 +11,9 @@ use common_planners::Expression;
 use common_runtime::tokio;
 
 use crate::api::rpc::flight_actions::FlightAction;
+use crate::api::CatalogChangeAction;
 use crate::api::ShuffleAction;
+use crate::datasources::CatalogChange;
 use crate::tests::parse_query;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -29,6 +31,8 @@ async fn test_shuffle_action_try_into() -> Result<()> {
     let from_action: FlightAction = to_action.try_into()?;
     match from_action {
         FlightAction::BroadcastAction(_) => assert!(false),
+        FlightAction::CatalogChangeAction(_) => assert!(false),
+        FlightAction::SubstraitPlanAction(_) => assert!(false),
         FlightAction::PrepareShuffleAction(action) => {
             assert_eq!(action.query_id, "query_id");
             assert_eq!(action.stage_id, "stage_id");
@@ -43,3 +47,28 @@ async fn test_shuffle_action_try_into() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_catalog_change_action_try_into() -> Result<()> {
+    let change = CatalogChange::DropTable {
+        db: String::from("db"),
+        table: String::from("table"),
+    };
+
+    let catalog_change_action = CatalogChangeAction {
+        tenant: Some(String::from("tenant")),
+        change,
+    };
+
+    let from_action = FlightAction::CatalogChangeAction(catalog_change_action.clone());
+    let to_action: Action = from_action.try_into()?;
+    assert_eq!(to_action.r#type, "CatalogChangeAction");
+
+    let from_action: FlightAction = to_action.try_into()?;
+    match from_action {
+        FlightAction::CatalogChangeAction(action) => assert_eq!(action, catalog_change_action),
+        _ => assert!(false),
+    }
+
+    Ok(())
+}
@@ -2,12 +2,18 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::convert::TryFrom;
 use std::convert::TryInto;
+use std::sync::Arc;
 
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow_flight::flight_descriptor::DescriptorType;
 use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
 use common_arrow::arrow_flight::Action;
 use common_arrow::arrow_flight::FlightData;
+use common_arrow::arrow_flight::FlightDescriptor;
 use common_arrow::arrow_flight::Ticket;
+use common_datavalues::DataSchema;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -20,6 +26,7 @@ use tonic::Streaming;
 use crate::api::rpc::flight_actions::FlightAction;
 use crate::api::rpc::flight_client_stream::FlightDataStream;
 use crate::api::rpc::flight_tickets::FlightTicket;
+use crate::sessions::FuseQueryContextRef;
 
 pub struct FlightClient {
     inner: FlightServiceClient<Channel>,
@@ -31,15 +38,44 @@ impl FlightClient {
         FlightClient { inner }
     }
 
+    /// Fetch the stream of the ticket, the warnings and the progress of the remote are recorded
+    /// to the context.
     pub async fn fetch_stream(
         &mut self,
+        ctx: FuseQueryContextRef,
         ticket: FlightTicket,
         schema: DataSchemaRef,
         timeout: u64,
     ) -> Result<SendableDataBlockStream> {
         let ticket = ticket.try_into()?;
         let inner = self.do_get(ticket, timeout).await?;
-        Ok(Box::pin(FlightDataStream::from_remote(schema, inner)))
+        let progress_ctx = ctx.clone();
+        Ok(Box::pin(FlightDataStream::from_remote(
+            schema,
+            inner,
+            move |warning| ctx.push_warning(warning),
+            move |progress| progress_ctx.add_remote_progress(&progress),
+        )))
+    }
+
+    /// Fetch the schema of the table `db.table` of the remote cluster.
+    pub async fn get_schema(
+        &mut self,
+        db: &str,
+        table: &str,
+        timeout: u64,
+    ) -> Result<DataSchemaRef> {
+        let descriptor = FlightDescriptor {
+            r#type: DescriptorType::Path as i32,
+            cmd: vec![],
+            path: vec![db.to_string(), table.to_string()],
+        };
+        let mut request = Request::new(descriptor);
+        request.set_timeout(Duration::from_secs(timeout));
+
+        let response = self.inner.get_schema(request).await?;
+        let arrow_schema = ArrowSchema::try_from(&response.into_inner())?;
+        Ok(Arc::new(DataSchema::from(arrow_schema)))
     }
 
     pub async fn execute_action(&mut self, action: FlightAction, timeout: u64) -> Result<()> {
@@ -10,25 +10,50 @@ use common_arrow::arrow_flight::FlightData;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::ErrorCode;
+use common_progress::ProgressValues;
 use common_runtime::tokio::sync::mpsc::Receiver;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::Stream;
 use tokio_stream::StreamExt;
 use tonic::Streaming;
 
+use crate::api::rpc::flight_metadata::FlightMetadata;
+
 #[derive(Debug)]
 pub struct FlightDataStream();
 
 impl FlightDataStream {
+    /// The warnings and the progress sent by the remote are passed to `on_warning` and
+    /// `on_progress`, see FlightMetadata.
     #[inline]
-    pub fn from_remote(
+    pub fn from_remote<F, P>(
         schema: DataSchemaRef,
         inner: Streaming<FlightData>,
-    ) -> impl Stream<Item = Result<DataBlock, ErrorCode>> {
-        inner.map(move |flight_data| -> Result<DataBlock, ErrorCode> {
-            match flight_data {
-                Err(status) => Err(ErrorCode::UnknownException(status.message())),
-                Ok(flight_data) => {
+        on_warning: F,
+        on_progress: P,
+    ) -> impl Stream<Item = Result<DataBlock, ErrorCode>>
+    where
+        F: Fn(String) + Send + Sync + 'static,
+        P: Fn(ProgressValues) + Send + Sync + 'static,
+    {
+        inner.filter_map(move |flight_data| -> Option<Result<DataBlock, ErrorCode>> {
+            let flight_data = match flight_data {
+                Err(status) => return Some(Err(ErrorCode::UnknownException(status.message()))),
+                Ok(flight_data) => flight_data,
+            };
+
+            if !flight_data.app_metadata.is_empty() {
+                match FlightMetadata::decode(&flight_data.app_metadata) {
+                    Err(error) => return Some(Err(error)),
+                    Ok(FlightMetadata::Warning(warning)) => on_warning(warning),
+                    Ok(FlightMetadata::Progress(values)) => on_progress(values),
+                }
+            }
+
+            match flight_data.data_header.is_empty() {
+                // The metadata only.
+                true => None,
+                false => {
                     fn create_data_block(record_batch: RecordBatch) -> DataBlock {
                         let columns = record_batch
                             .columns()
@@ -43,8 +68,11 @@ impl FlightDataStream {
                     }
 
                     let arrow_schema = Arc::new(schema.to_arrow());
-                    Ok(flight_data_to_arrow_batch(&flight_data, arrow_schema, &[])
-                        .map(create_data_block)?)
+                    Some(
+                        flight_data_to_arrow_batch(&flight_data, arrow_schema, &[])
+                            .map(create_data_block)
+                            .map_err(ErrorCode::from),
+                    )
                 }
             }
         })
@@ -38,6 +38,8 @@ struct StreamInfo {
 pub struct FuseQueryFlightDispatcher {
     streams: Arc<RwLock<HashMap<String, StreamInfo>>>,
     stages_notify: Arc<RwLock<HashMap<String, Arc<Notify>>>>,
+    streams_warnings: Arc<RwLock<HashMap<String, Vec<String>>>>,
+    streams_context: Arc<RwLock<HashMap<String, FuseQueryContextRef>>>,
     abort: Arc<AtomicBool>,
 }
 
@@ -46,6 +48,8 @@ impl FuseQueryFlightDispatcher {
         FuseQueryFlightDispatcher {
             streams: Arc::new(RwLock::new(HashMap::new())),
             stages_notify: Arc::new(RwLock::new(HashMap::new())),
+            streams_warnings: Arc::new(RwLock::new(HashMap::new())),
+            streams_context: Arc::new(RwLock::new(HashMap::new())),
             abort: Arc::new(AtomicBool::new(false)),
         }
     }
@@ -77,6 +81,33 @@ impl FuseQueryFlightDispatcher {
         }
     }
 
+    /// The warnings of the stage which produced the stream, they are available when the stream
+    /// is finished. The warnings of a stage are reported by the first stream of the stage only.
+    pub fn take_stream_warnings(
+        &self,
+        query_id: &str,
+        stage_id: &str,
+        stream: &str,
+    ) -> Vec<String> {
+        let stream_name = format!("{}/{}/{}", query_id, stage_id, stream);
+        self.streams_warnings
+            .write()
+            .remove(&stream_name)
+            .unwrap_or_default()
+    }
+
+    /// The context of the stage which produces the stream, the progress of the stage is sent
+    /// with the stream. The progress of a stage is shared by all the streams of the stage.
+    pub fn take_stream_context(
+        &self,
+        query_id: &str,
+        stage_id: &str,
+        stream: &str,
+    ) -> Option<FuseQueryContextRef> {
+        let stream_name = format!("{}/{}/{}", query_id, stage_id, stream);
+        self.streams_context.write().remove(&stream_name)
+    }
+
     pub fn broadcast_action(&self, session: SessionRef, action: FlightAction) -> Result<()> {
         let query_id = action.get_query_id();
         let stage_id = action.get_stage_id();
@@ -122,6 +153,10 @@ impl FuseQueryFlightDispatcher {
         let stream_name = format!("{}/{}", stage_name, action_sinks[0]);
         let tx_ref = self.streams.read().get(&stream_name).map(|x| x.tx.clone());
         let tx = tx_ref.ok_or_else(|| ErrorCode::NotFoundStream("Not found stream"))?;
+        let streams_warnings = self.streams_warnings.clone();
+        self.streams_context
+            .write()
+            .insert(stream_name.clone(), action_context.clone());
 
         query_context.execute_task(async move {
             let _session = session;
@@ -145,6 +180,9 @@ impl FuseQueryFlightDispatcher {
                     }
                 }
             };
+
+            // Before the stream is closed by dropping the sender.
+            Self::save_warnings(&streams_warnings, stream_name, &action_context);
         })?;
         Ok(())
     }
@@ -168,7 +206,12 @@ impl FuseQueryFlightDispatcher {
             for sink in &action_sinks {
                 let stream_name = format!("{}/{}/{}", action_query_id, action_stage_id, sink);
                 match self.streams.read().get(&stream_name) {
-                    Some(stream) => sinks_tx.push(stream.tx.clone()),
+                    Some(stream) => {
+                        sinks_tx.push(stream.tx.clone());
+                        self.streams_context
+                            .write()
+                            .insert(stream_name, action_context.clone());
+                    }
                     None => {
                         return Err(ErrorCode::NotFoundStream(format!(
                             "Not found stream {}",
@@ -183,6 +226,8 @@ impl FuseQueryFlightDispatcher {
 
         let stage_name = format!("{}/{}", action_query_id, action_stage_id);
         let stages_notify = self.stages_notify.clone();
+        let streams_warnings = self.streams_warnings.clone();
+        let warnings_stream_name = format!("{}/{}", stage_name, action.get_sinks()[0]);
 
         let flight_scatter = T::try_create(
             action.get_plan().schema(),
@@ -223,11 +268,25 @@ impl FuseQueryFlightDispatcher {
                     let _ = send_error_message.await;
                 }
             }
+
+            // Before the streams are closed by dropping the senders.
+            Self::save_warnings(&streams_warnings, warnings_stream_name, &action_context);
         })?;
 
         Ok(())
     }
 
+    fn save_warnings(
+        streams_warnings: &RwLock<HashMap<String, Vec<String>>>,
+        stream_name: String,
+        ctx: &FuseQueryContextRef,
+    ) {
+        let warnings = ctx.get_warnings();
+        if !warnings.is_empty() {
+            streams_warnings.write().insert(stream_name, warnings);
+        }
+    }
+
     async fn execute(mut pipeline: Pipeline, ctx: &FuseQueryContextRef) -> Result<AbortStream> {
         let data_stream = pipeline.execute().await?;
         ctx.try_create_abortable(data_stream)
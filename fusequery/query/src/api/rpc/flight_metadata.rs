@@ -0,0 +1,68 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryInto;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_progress::ProgressValues;
+
+/// The metadata of a stage stream, which is piggybacked in the app_metadata of the flight data.
+/// The first byte is the kind of the metadata.
+#[derive(Debug, PartialEq)]
+pub enum FlightMetadata {
+    /// A warning of the stage, the message is UTF-8.
+    Warning(String),
+    /// The progress of the stage since the last progress, the values are three u64 in little
+    /// endian: read rows, read bytes and total rows to read.
+    Progress(ProgressValues),
+}
+
+impl FlightMetadata {
+    const WARNING: u8 = 1;
+    const PROGRESS: u8 = 2;
+
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            FlightMetadata::Warning(warning) => {
+                let mut bytes = Vec::with_capacity(warning.len() + 1);
+                bytes.push(Self::WARNING);
+                bytes.extend_from_slice(warning.as_bytes());
+                bytes
+            }
+            FlightMetadata::Progress(values) => {
+                let mut bytes = Vec::with_capacity(25);
+                bytes.push(Self::PROGRESS);
+                bytes.extend_from_slice(&(values.read_rows as u64).to_le_bytes());
+                bytes.extend_from_slice(&(values.read_bytes as u64).to_le_bytes());
+                bytes.extend_from_slice(&(values.total_rows_to_read as u64).to_le_bytes());
+                bytes
+            }
+        }
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<FlightMetadata> {
+        match bytes.split_first() {
+            Some((&Self::WARNING, warning)) => Ok(FlightMetadata::Warning(
+                String::from_utf8_lossy(warning).to_string(),
+            )),
+            Some((&Self::PROGRESS, values)) if values.len() == 24 => {
+                let read_u64 = |index: usize| {
+                    let bytes = values[index * 8..(index + 1) * 8].try_into().unwrap();
+                    u64::from_le_bytes(bytes) as usize
+                };
+
+                Ok(FlightMetadata::Progress(ProgressValues {
+                    read_rows: read_u64(0),
+                    read_bytes: read_u64(1),
+                    total_rows_to_read: read_u64(2),
+                }))
+            }
+            _ => Err(ErrorCode::BadBytes(format!(
+                "Cannot decode the metadata of the flight data: {:?}",
+                bytes
+            ))),
+        }
+    }
+}
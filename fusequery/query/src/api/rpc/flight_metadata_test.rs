@@ -0,0 +1,34 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_progress::ProgressValues;
+use pretty_assertions::assert_eq;
+
+use crate::api::rpc::flight_metadata::FlightMetadata;
+
+#[test]
+fn test_flight_metadata() -> Result<()> {
+    let warning = FlightMetadata::Warning(String::from("warning"));
+    assert_eq!(warning.encode(), b"\x01warning".to_vec());
+    assert_eq!(FlightMetadata::decode(&warning.encode())?, warning);
+
+    let progress = FlightMetadata::Progress(ProgressValues {
+        read_rows: 1,
+        read_bytes: 8,
+        total_rows_to_read: 1024,
+    });
+    assert_eq!(progress.encode().len(), 25);
+    assert_eq!(FlightMetadata::decode(&progress.encode())?, progress);
+
+    // The progress must be three u64.
+    let error = FlightMetadata::decode(&[2, 1, 0]).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Code: 46, displayText = Cannot decode the metadata of the flight data: [2, 1, 0]."
+    );
+    assert!(FlightMetadata::decode(&[]).is_err());
+
+    Ok(())
+}
@@ -6,6 +6,7 @@ use std::convert::TryInto;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use common_arrow::arrow::ipc::writer::IpcWriteOptions;
 use common_arrow::arrow_flight::flight_service_server::FlightService;
 use common_arrow::arrow_flight::Action;
 use common_arrow::arrow_flight::ActionType;
@@ -18,19 +19,29 @@ use common_arrow::arrow_flight::HandshakeRequest;
 use common_arrow::arrow_flight::HandshakeResponse;
 use common_arrow::arrow_flight::PutResult;
 use common_arrow::arrow_flight::Result as FlightResult;
+use common_arrow::arrow_flight::SchemaAsIpc;
 use common_arrow::arrow_flight::SchemaResult;
 use common_arrow::arrow_flight::Ticket;
+use common_planners::PlanNode;
+use common_runtime::tokio::sync::mpsc;
 use tokio_stream::Stream;
+use tokio_stream::StreamExt;
 use tonic::Request;
 use tonic::Response as RawResponse;
 use tonic::Status;
 use tonic::Streaming;
 
+use crate::api::rpc::flight_actions::BroadcastAction;
 use crate::api::rpc::flight_actions::FlightAction;
 use crate::api::rpc::flight_dispatcher::FuseQueryFlightDispatcher;
 use crate::api::rpc::flight_service_stream::FlightDataStream;
 use crate::api::rpc::flight_tickets::FlightTicket;
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::FuseQueryContextRef;
 use crate::sessions::SessionManagerRef;
+use crate::sessions::SessionRef;
+use crate::sql::PlanParser;
+use crate::sql::SubstraitPlanParser;
 
 pub type FlightStream<T> =
     Pin<Box<dyn Stream<Item = Result<T, tonic::Status>> + Send + Sync + 'static>>;
@@ -47,6 +58,22 @@ impl FuseQueryFlightService {
             dispatcher,
         }
     }
+
+    /// Scans of tables are served by a session of their own, like the queries of a client.
+    fn create_scan_session(&self) -> common_exception::Result<SessionRef> {
+        let session_id = uuid::Uuid::new_v4().to_string();
+        let is_aborted = self.dispatcher.is_aborted();
+        self.sessions.create_rpc_session(session_id, is_aborted)
+    }
+
+    fn scan_plan(
+        ctx: &FuseQueryContextRef,
+        db: &str,
+        table: &str,
+    ) -> common_exception::Result<PlanNode> {
+        let query = format!("SELECT * FROM {}.{}", db, table);
+        PlanParser::create(ctx.clone()).build_from_sql(&query)
+    }
 }
 
 type Response<T> = Result<RawResponse<T>, Status>;
@@ -79,10 +106,24 @@ impl FlightService for FuseQueryFlightService {
         ))
     }
 
-    async fn get_schema(&self, _: Request<FlightDescriptor>) -> Response<SchemaResult> {
-        Err(Status::unimplemented(
-            "FuseQuery does not implement get_schema.",
-        ))
+    async fn get_schema(&self, request: Request<FlightDescriptor>) -> Response<SchemaResult> {
+        let descriptor = request.into_inner();
+
+        match descriptor.path.as_slice() {
+            [db, table] => {
+                let session = self.create_scan_session()?;
+                let plan = Self::scan_plan(&session.create_context(), db, table)?;
+
+                let arrow_schema = plan.schema().to_arrow();
+                let options = IpcWriteOptions::default();
+                Ok(RawResponse::new(
+                    SchemaAsIpc::new(&arrow_schema, &options).into(),
+                ))
+            }
+            _ => Err(Status::invalid_argument(
+                "The path of flight descriptor must be [database, table].",
+            )),
+        }
     }
 
     type DoGetStream = FlightStream<FlightData>;
@@ -98,8 +139,59 @@ impl FlightService for FuseQueryFlightService {
                     &steam_ticket.stream,
                 )?;
 
+                let stage_ctx = self.dispatcher.take_stream_context(
+                    &steam_ticket.query_id,
+                    &steam_ticket.stage_id,
+                    &steam_ticket.stream,
+                );
+
+                let dispatcher = self.dispatcher.clone();
+                let mut stream = FlightDataStream::create(receiver).with_warnings(move || {
+                    dispatcher.take_stream_warnings(
+                        &steam_ticket.query_id,
+                        &steam_ticket.stage_id,
+                        &steam_ticket.stream,
+                    )
+                });
+
+                if let Some(stage_ctx) = stage_ctx {
+                    stream = stream.with_progress(move || stage_ctx.get_and_reset_progress_value());
+                }
+
+                Ok(RawResponse::new(
+                    Box::pin(stream) as FlightStream<FlightData>
+                ))
+            }
+            FlightTicket::ScanTicket(scan_ticket) => {
+                let session = self.create_scan_session()?;
+                let ctx = session.create_context();
+                let plan = Self::scan_plan(&ctx, &scan_ticket.db, &scan_ticket.table)?;
+                let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+
+                let (tx, receiver) = mpsc::channel(5);
+                ctx.execute_task(async move {
+                    let _session = session;
+                    match interpreter.execute().await {
+                        Err(error) => {
+                            tx.send(Err(error)).await.ok();
+                        }
+                        Ok(mut stream) => {
+                            while let Some(item) = stream.next().await {
+                                if tx.send(item).await.is_err() {
+                                    log::warn!("The scan stream is closed by the client.");
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                })?;
+
+                // The rows of a scan are counted by the source of the client, so the progress
+                // is not sent.
+                let stream =
+                    FlightDataStream::create(receiver).with_warnings(move || ctx.get_warnings());
                 Ok(RawResponse::new(
-                    Box::pin(FlightDataStream::create(receiver)) as FlightStream<FlightData>,
+                    Box::pin(stream) as FlightStream<FlightData>
                 ))
             }
         }
@@ -127,7 +219,7 @@ impl FlightService for FuseQueryFlightService {
         let action = request.into_inner();
         let flight_action: FlightAction = action.try_into()?;
 
-        let do_flight_action = || -> common_exception::Result<FlightResult> {
+        let do_flight_action = || -> common_exception::Result<Vec<FlightResult>> {
             match &flight_action {
                 FlightAction::BroadcastAction(action) => {
                     let session_id = action.query_id.clone();
@@ -135,7 +227,7 @@ impl FlightService for FuseQueryFlightService {
                     let session = self.sessions.create_rpc_session(session_id, is_aborted)?;
 
                     self.dispatcher.broadcast_action(session, flight_action)?;
-                    Ok(FlightResult { body: vec![] })
+                    Ok(vec![FlightResult { body: vec![] }])
                 }
                 FlightAction::PrepareShuffleAction(action) => {
                     let session_id = action.query_id.clone();
@@ -143,14 +235,60 @@ impl FlightService for FuseQueryFlightService {
                     let session = self.sessions.create_rpc_session(session_id, is_aborted)?;
 
                     self.dispatcher.shuffle_action(session, flight_action)?;
-                    Ok(FlightResult { body: vec![] })
+                    Ok(vec![FlightResult { body: vec![] }])
+                }
+                FlightAction::CatalogChangeAction(action) => {
+                    let datasource = match &action.tenant {
+                        None => self.sessions.get_datasource(),
+                        Some(tenant) => self.sessions.get_tenant_datasource(tenant)?,
+                    };
+                    datasource.apply_catalog_change(&action.change);
+                    Ok(vec![FlightResult { body: vec![] }])
+                }
+                FlightAction::SubstraitPlanAction(action) => {
+                    // Executed as a query stage of one stream, which is fetched by do_get with the
+                    // returned ticket. The results are the ticket and the schema of the stream.
+                    let query_id = uuid::Uuid::new_v4().to_string();
+                    let is_aborted = self.dispatcher.is_aborted();
+                    let session = self
+                        .sessions
+                        .create_rpc_session(query_id.clone(), is_aborted)?;
+                    let plan = SubstraitPlanParser::create(session.create_context())
+                        .build_from_bytes(&action.plan)?;
+
+                    let stage_id = String::from("substrait");
+                    let stream = String::from("substrait");
+                    let schema = plan.schema().to_arrow();
+                    self.dispatcher.broadcast_action(
+                        session,
+                        FlightAction::BroadcastAction(BroadcastAction {
+                            query_id: query_id.clone(),
+                            stage_id: stage_id.clone(),
+                            plan,
+                            sinks: vec![stream.clone()],
+                        }),
+                    )?;
+
+                    let ticket: Ticket =
+                        FlightTicket::stream(&query_id, &stage_id, &stream).try_into()?;
+                    let options = IpcWriteOptions::default();
+                    let schema: SchemaResult = SchemaAsIpc::new(&schema, &options).into();
+                    Ok(vec![
+                        FlightResult {
+                            body: ticket.ticket,
+                        },
+                        FlightResult {
+                            body: schema.schema,
+                        },
+                    ])
                 }
             }
         };
 
-        let action_result = do_flight_action()?;
+        let action_results = do_flight_action()?;
         Ok(RawResponse::new(
-            Box::pin(tokio_stream::once(Ok(action_result))) as FlightStream<FlightResult>,
+            Box::pin(tokio_stream::iter(action_results.into_iter().map(Ok)))
+                as FlightStream<FlightResult>,
         ))
     }
 
@@ -162,6 +300,14 @@ impl FlightService for FuseQueryFlightService {
                 Ok(ActionType {
                     r#type: "PrepareShuffleAction".to_string(),
                     description: "Prepare a query stage that can be sent to the remote after receiving data from remote".to_string(),
+                }),
+                Ok(ActionType {
+                    r#type: "CatalogChangeAction".to_string(),
+                    description: "Drop the cached metadata changed by a DDL of another query node".to_string(),
+                }),
+                Ok(ActionType {
+                    r#type: "SubstraitPlanAction".to_string(),
+                    description: "Execute a Substrait plan, returns the ticket and the schema of the results".to_string(),
                 })
             ])) as FlightStream<ActionType>
         ))
@@ -2,12 +2,14 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::VecDeque;
 use std::convert::TryInto;
 
 use common_arrow::arrow::ipc::writer::IpcWriteOptions;
 use common_arrow::arrow_flight::utils::flight_data_from_arrow_batch;
 use common_arrow::arrow_flight::FlightData;
 use common_datablocks::DataBlock;
+use common_progress::ProgressValues;
 use common_runtime::tokio::macros::support::Pin;
 use common_runtime::tokio::macros::support::Poll;
 use common_runtime::tokio::sync::mpsc::Receiver;
@@ -15,9 +17,18 @@ use futures::task::Context;
 use tokio_stream::Stream;
 use tonic::Status;
 
+use crate::api::rpc::flight_metadata::FlightMetadata;
+
+type WarningsFn = Box<dyn FnOnce() -> Vec<String> + Send + Sync>;
+type ProgressFn = Box<dyn Fn() -> ProgressValues + Send + Sync>;
+
 pub struct FlightDataStream {
     input: Receiver<common_exception::Result<DataBlock>>,
     options: IpcWriteOptions,
+    warnings_fn: Option<WarningsFn>,
+    warnings: VecDeque<String>,
+    progress_fn: Option<ProgressFn>,
+    finished: bool,
 }
 
 impl FlightDataStream {
@@ -25,8 +36,62 @@ impl FlightDataStream {
         FlightDataStream {
             input,
             options: IpcWriteOptions::default(),
+            warnings_fn: None,
+            warnings: VecDeque::new(),
+            progress_fn: None,
+            finished: false,
         }
     }
+
+    /// The warnings are collected when the input is finished, and sent after the data blocks.
+    pub fn with_warnings<F>(mut self, warnings_fn: F) -> FlightDataStream
+    where F: FnOnce() -> Vec<String> + Send + Sync + 'static {
+        self.warnings_fn = Some(Box::new(warnings_fn));
+        self
+    }
+
+    /// The progress of the stage since the last call is piggybacked on every data block, the
+    /// progress after the last data block is sent before the warnings.
+    pub fn with_progress<F>(mut self, progress_fn: F) -> FlightDataStream
+    where F: Fn() -> ProgressValues + Send + Sync + 'static {
+        self.progress_fn = Some(Box::new(progress_fn));
+        self
+    }
+
+    /// A metadata is sent as a flight data without data, see FlightMetadata.
+    pub fn metadata_data(metadata: FlightMetadata) -> FlightData {
+        FlightData {
+            app_metadata: metadata.encode(),
+            ..Default::default()
+        }
+    }
+
+    fn take_progress(&self) -> Option<FlightMetadata> {
+        match &self.progress_fn {
+            None => None,
+            Some(progress_fn) => match progress_fn() {
+                values if values.is_empty() => None,
+                values => Some(FlightMetadata::Progress(values)),
+            },
+        }
+    }
+
+    fn next_metadata(&mut self) -> Option<Result<FlightData, Status>> {
+        if !self.finished {
+            self.finished = true;
+            if let Some(progress) = self.take_progress() {
+                return Some(Ok(Self::metadata_data(progress)));
+            }
+        }
+
+        if let Some(warnings_fn) = self.warnings_fn.take() {
+            self.warnings.extend(warnings_fn());
+        }
+
+        self.warnings
+            .pop_front()
+            .map(|warning| Ok(Self::metadata_data(FlightMetadata::Warning(warning))))
+    }
 }
 
 impl Stream for FlightDataStream {
@@ -34,7 +99,7 @@ impl Stream for FlightDataStream {
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         self.input.poll_recv(cx).map(|x| match x {
-            None => None,
+            None => self.next_metadata(),
             Some(Err(error)) => Some(Err(Status::from(error))),
             Some(Ok(block)) => match block.try_into() {
                 Err(error) => Some(Err(Status::from(error))),
@@ -43,7 +108,13 @@ impl Stream for FlightDataStream {
                         flight_data_from_arrow_batch(&record_batch, &self.options);
 
                     match dicts.is_empty() {
-                        true => Some(Ok(values)),
+                        true => Some(Ok(FlightData {
+                            app_metadata: self
+                                .take_progress()
+                                .map(|progress| progress.encode())
+                                .unwrap_or_default(),
+                            ..values
+                        })),
                         false => Some(Err(Status::unimplemented(
                             "FuseQuery does not implement dicts.",
                         ))),
@@ -2,26 +2,40 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::sync::Arc;
 
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow_flight::flight_descriptor::DescriptorType;
 use common_arrow::arrow_flight::flight_service_server::FlightService;
 use common_arrow::arrow_flight::Action;
+use common_arrow::arrow_flight::FlightDescriptor;
+use common_arrow::arrow_flight::SchemaResult;
 use common_arrow::arrow_flight::Ticket;
-use common_datavalues::DataValue;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
 use common_exception::exception::ABORT_SESSION;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_flights::substrait;
 use common_planners::Expression;
+use common_progress::ProgressValues;
 use common_runtime::tokio;
+use common_runtime::tokio::sync::mpsc;
+use prost::Message;
+use tokio_stream::StreamExt;
 use tonic::Request;
 
 use crate::api::rpc::flight_actions::FlightAction;
+use crate::api::rpc::flight_metadata::FlightMetadata;
+use crate::api::rpc::flight_service_stream::FlightDataStream;
 use crate::api::rpc::flight_tickets::StreamTicket;
 use crate::api::rpc::FuseQueryFlightDispatcher;
 use crate::api::rpc::FuseQueryFlightService;
 use crate::api::FlightTicket;
 use crate::api::ShuffleAction;
+use crate::api::SubstraitPlanAction;
 use crate::tests::parse_query;
 use crate::tests::try_create_sessions;
 
@@ -143,6 +157,174 @@ async fn test_do_flight_action_with_abort_and_new_session() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_do_get_scan_ticket() -> Result<()> {
+    let sessions = try_create_sessions()?;
+    let dispatcher = Arc::new(FuseQueryFlightDispatcher::create());
+    let service = FuseQueryFlightService::create(dispatcher, sessions);
+
+    // The schema of the table.
+    {
+        let descriptor = FlightDescriptor {
+            r#type: DescriptorType::Path as i32,
+            cmd: vec![],
+            path: vec![String::from("system"), String::from("one")],
+        };
+        let response = service.get_schema(Request::new(descriptor)).await?;
+        let schema = ArrowSchema::try_from(&response.into_inner())?;
+        assert_eq!(schema.fields().len(), 1);
+        assert_eq!(schema.field(0).name(), "dummy");
+    }
+
+    // The rows of the table.
+    {
+        let ticket = FlightTicket::scan("system", "one");
+        let response = service.do_get(Request::new(ticket.try_into()?)).await?;
+        let flight_data = response.into_inner().collect::<Vec<_>>().await;
+        assert_eq!(flight_data.len(), 1);
+        assert!(flight_data[0].is_ok());
+    }
+
+    // The table must exist.
+    {
+        let ticket = FlightTicket::scan("system", "not_exists");
+        assert!(service
+            .do_get(Request::new(ticket.try_into()?))
+            .await
+            .is_err());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_do_substrait_plan_action() -> Result<()> {
+    let sessions = try_create_sessions()?;
+    let dispatcher = Arc::new(FuseQueryFlightDispatcher::create());
+    let service = FuseQueryFlightService::create(dispatcher, sessions);
+
+    // SELECT * FROM system.one
+    let plan = substrait::Plan {
+        extensions: vec![],
+        relations: vec![substrait::PlanRel {
+            rel_type: Some(substrait::plan_rel::RelType::Rel(substrait::Rel {
+                rel_type: Some(substrait::rel::RelType::Read(
+                    substrait::ReadRel {
+                        filter: None,
+                        read_type: Some(substrait::read_rel::ReadType::NamedTable(
+                            substrait::read_rel::NamedTable {
+                                names: vec![String::from("system"), String::from("one")],
+                            },
+                        )),
+                    }
+                    .into(),
+                )),
+            })),
+        }],
+    };
+    let mut body = vec![];
+    plan.encode(&mut body).unwrap();
+    let action: Action =
+        FlightAction::SubstraitPlanAction(SubstraitPlanAction { plan: body }).try_into()?;
+
+    // The results of the action are the ticket and the schema of the stream.
+    let response = service.do_action(Request::new(action)).await?;
+    let results = response.into_inner().collect::<Vec<_>>().await;
+    assert_eq!(results.len(), 2);
+
+    let ticket = Ticket {
+        ticket: results[0].as_ref().unwrap().body.clone(),
+    };
+    let schema = ArrowSchema::try_from(&SchemaResult {
+        schema: results[1].as_ref().unwrap().body.clone(),
+    })?;
+    assert_eq!(schema.field(0).name(), "dummy");
+
+    let response = service.do_get(Request::new(ticket)).await?;
+    let flight_data = response.into_inner().collect::<Vec<_>>().await;
+    assert_eq!(flight_data.len(), 1);
+    assert!(flight_data[0].is_ok());
+
+    // The plan must be decoded.
+    let action: Action = FlightAction::SubstraitPlanAction(SubstraitPlanAction {
+        plan: b"not a plan".to_vec(),
+    })
+    .try_into()?;
+    assert!(service.do_action(Request::new(action)).await.is_err());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_data_stream_with_warnings() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let block = DataBlock::create_by_array(schema, vec![Series::new(vec![1u64, 2])]);
+
+    let (tx, rx) = mpsc::channel(2);
+    tx.send(Ok(block)).await.ok();
+    drop(tx);
+
+    let stream = FlightDataStream::create(rx).with_warnings(|| vec![String::from("warning")]);
+    let flight_data = stream.collect::<Vec<_>>().await;
+    assert_eq!(flight_data.len(), 2);
+
+    // The warnings are sent after the data blocks.
+    let warning = flight_data[1].as_ref().unwrap();
+    assert!(warning.data_header.is_empty());
+    assert_eq!(
+        FlightMetadata::decode(&warning.app_metadata)?,
+        FlightMetadata::Warning(String::from("warning"))
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_data_stream_with_progress() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let block = DataBlock::create_by_array(schema, vec![Series::new(vec![1u64, 2])]);
+
+    let (tx, rx) = mpsc::channel(2);
+    tx.send(Ok(block)).await.ok();
+    drop(tx);
+
+    // The progress is read rows 2 when the data block is sent, and read rows 1 after.
+    let progress = Arc::new(std::sync::atomic::AtomicUsize::new(2));
+    let stream = FlightDataStream::create(rx).with_progress(move || ProgressValues {
+        read_rows: progress.fetch_sub(1, std::sync::atomic::Ordering::Relaxed),
+        read_bytes: 0,
+        total_rows_to_read: 0,
+    });
+    let flight_data = stream.collect::<Vec<_>>().await;
+    assert_eq!(flight_data.len(), 2);
+
+    // The progress is piggybacked on the data block.
+    let data = flight_data[0].as_ref().unwrap();
+    assert!(!data.data_header.is_empty());
+    assert_eq!(
+        FlightMetadata::decode(&data.app_metadata)?,
+        FlightMetadata::Progress(ProgressValues {
+            read_rows: 2,
+            read_bytes: 0,
+            total_rows_to_read: 0,
+        })
+    );
+
+    // The progress after the last data block is sent without data.
+    let last = flight_data[1].as_ref().unwrap();
+    assert!(last.data_header.is_empty());
+    assert_eq!(
+        FlightMetadata::decode(&last.app_metadata)?,
+        FlightMetadata::Progress(ProgressValues {
+            read_rows: 1,
+            read_bytes: 0,
+            total_rows_to_read: 0,
+        })
+    );
+
+    Ok(())
+}
+
 fn do_get_request(query_id: &str, stage_id: &str) -> Result<Request<Ticket>> {
     let stream_ticket = FlightTicket::StreamTicket(StreamTicket {
         query_id: String::from(query_id),
@@ -16,9 +16,17 @@ pub struct StreamTicket {
     pub stream: String,
 }
 
+/// Scan all the rows of a table of the cluster serving the ticket.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+pub struct ScanTicket {
+    pub db: String,
+    pub table: String,
+}
+
 #[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
 pub enum FlightTicket {
     StreamTicket(StreamTicket),
+    ScanTicket(ScanTicket),
 }
 
 impl FlightTicket {
@@ -29,6 +37,13 @@ impl FlightTicket {
             stream: stream.to_string(),
         })
     }
+
+    pub fn scan(db: &str, table: &str) -> FlightTicket {
+        FlightTicket::ScanTicket(ScanTicket {
+            db: db.to_string(),
+            table: table.to_string(),
+        })
+    }
 }
 
 impl TryInto<FlightTicket> for Ticket {
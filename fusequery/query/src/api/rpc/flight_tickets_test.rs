@@ -27,6 +27,24 @@ async fn test_stream_ticket_try_into() -> Result<()> {
             assert_eq!(ticket.stage_id, "stage_id");
             assert_eq!(ticket.stream, "stream");
         }
+        other => panic!("Unexpected ticket: {:?}", other),
+    };
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scan_ticket_try_into() -> Result<()> {
+    let from_ticket = FlightTicket::scan("db", "table");
+
+    let to_ticket: Ticket = from_ticket.try_into()?;
+    let from_ticket: FlightTicket = to_ticket.try_into()?;
+    match from_ticket {
+        FlightTicket::ScanTicket(ticket) => {
+            assert_eq!(ticket.db, "db");
+            assert_eq!(ticket.table, "table");
+        }
+        other => panic!("Unexpected ticket: {:?}", other),
     };
 
     Ok(())
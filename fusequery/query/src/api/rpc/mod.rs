@@ -14,9 +14,14 @@ mod flight_actions_test;
 #[cfg(test)]
 mod flight_tickets_test;
 
+#[cfg(test)]
+mod flight_metadata_test;
+
 pub use flight_actions::BroadcastAction;
+pub use flight_actions::CatalogChangeAction;
 pub use flight_actions::FlightAction;
 pub use flight_actions::ShuffleAction;
+pub use flight_actions::SubstraitPlanAction;
 pub use flight_client::FlightClient;
 pub use flight_dispatcher::FuseQueryFlightDispatcher;
 pub use flight_service::FuseQueryFlightService;
@@ -26,6 +31,7 @@ mod flight_actions;
 mod flight_client;
 mod flight_client_stream;
 mod flight_dispatcher;
+mod flight_metadata;
 mod flight_scatter;
 mod flight_scatter_broadcast;
 mod flight_scatter_hash;
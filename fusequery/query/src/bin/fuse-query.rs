@@ -22,20 +22,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Use customize malloc.
     let malloc = common_allocators::init();
 
-    // First load configs from args.
-    let mut conf = Config::load_from_args();
-
-    // If config file is not empty: -c xx.toml
-    // Reload configs from the file.
-    if !conf.config_file.is_empty() {
-        info!("Config reload from {:?}", conf.config_file);
-        conf = Config::load_from_toml(conf.config_file.as_str())?;
+    // Load configs by layers: the defaults, the config file of -c xx.toml, the env variables
+    // (preferred in cloud native deployment) and the args.
+    let conf = Config::load()?;
+    if conf.print_config {
+        println!("{}", conf.to_toml()?);
+        return Ok(());
     }
 
-    // Prefer to use env variable in cloud native deployment
-    // Override configs based on env variables
-    conf = Config::load_from_env(&conf)?;
-
     env_logger::Builder::from_env(
         env_logger::Env::default().default_filter_or(conf.log_level.to_lowercase().as_str()),
     )
@@ -105,9 +99,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // HTTP API service.
     {
         let listening = conf.http_api_address.parse::<std::net::SocketAddr>()?;
-        let mut srv = HttpService::create(conf.clone(), cluster.clone());
+        let mut srv = HttpService::create(conf.clone(), cluster.clone(), session_manager.clone());
         let listening = srv.start(listening).await?;
-        shutdown_handle.add_service(srv);
+        shutdown_handle.add_api_service(srv);
         info!("HTTP API server listening on {}", listening);
     }
 
@@ -120,6 +114,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         info!("RPC API server listening on {}", listening);
     }
 
+    session_manager.get_health().set_started();
     log::info!("Ready for connections.");
     shutdown_handle.wait_for_termination_request().await;
     log::info!("Shutdown server.");
@@ -4,6 +4,7 @@
 
 use std::collections::hash_map::Entry::Occupied;
 use std::collections::hash_map::Entry::Vacant;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::Arc;
@@ -44,6 +45,19 @@ impl Cluster {
     }
 
     pub async fn add_node(&self, name: &str, priority: u8, address: &str) -> Result<()> {
+        self.add_node_with_labels(name, priority, address, BTreeMap::new())
+            .await
+    }
+
+    /// Adds a node with its labels, e.g. `host` and `zone`, the parts of the reads of a table
+    /// are assigned to the nodes with the labels of the store nodes which hold them.
+    pub async fn add_node_with_labels(
+        &self,
+        name: &str,
+        priority: u8,
+        address: &str,
+        labels: BTreeMap<String, String>,
+    ) -> Result<()> {
         let address = Address::create(address)?;
         let address_is_local = is_local(&address, self.local_port).await?;
         let mut nodes = self.nodes.lock();
@@ -61,6 +75,7 @@ impl Cluster {
                     address.clone(),
                     address_is_local,
                     new_node_sequence,
+                    labels,
                 )?));
 
                 Ok(())
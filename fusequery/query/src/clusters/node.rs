@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
+
 use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
 use common_exception::Result;
 use common_flights::ConnectionFactory;
@@ -21,6 +23,9 @@ pub struct Node {
     pub address: Address,
     pub local: bool,
     pub sequence: usize,
+    // The labels of the node, e.g. `host` and `zone`, by which the parts of the reads are
+    // assigned to the node nearest to their data
+    pub labels: BTreeMap<String, String>,
 }
 
 impl PartialEq for Node {
@@ -29,6 +34,7 @@ impl PartialEq for Node {
             && self.priority == other.priority
             && self.address == other.address
             && self.local == other.local
+            && self.labels == other.labels
     }
 }
 
@@ -39,6 +45,7 @@ impl Node {
         address: Address,
         local: bool,
         sequence: usize,
+        labels: BTreeMap<String, String>,
     ) -> Result<Node> {
         Ok(Node {
             name,
@@ -46,6 +53,7 @@ impl Node {
             address,
             local,
             sequence,
+            labels,
         })
     }
 
@@ -69,6 +77,8 @@ impl serde::Serialize for Node {
             address: Address,
             local: bool,
             sequence: usize,
+            #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+            labels: BTreeMap<String, String>,
         }
 
         NodeSerializeView::serialize(
@@ -78,6 +88,7 @@ impl serde::Serialize for Node {
                 address: self.address.clone(),
                 local: self.local,
                 sequence: self.sequence,
+                labels: self.labels.clone(),
             },
             serializer,
         )
@@ -94,6 +105,8 @@ impl<'de> serde::Deserialize<'de> for Node {
             pub address: Address,
             pub local: bool,
             pub sequence: usize,
+            #[serde(default)]
+            pub labels: BTreeMap<String, String>,
         }
 
         let node_deserialize_view = NodeDeserializeView::deserialize(deserializer)?;
@@ -103,6 +116,7 @@ impl<'de> serde::Deserialize<'de> for Node {
             node_deserialize_view.address.clone(),
             node_deserialize_view.local,
             node_deserialize_view.sequence,
+            node_deserialize_view.labels,
         );
 
         match deserialize_result {
@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
+
 use common_exception::Result;
 use common_runtime::tokio;
 
@@ -16,6 +18,7 @@ async fn test_serialize_node() -> Result<()> {
         Address::create(&String::from("localhost:9090"))?,
         true,
         2,
+        BTreeMap::new(),
     )?;
 
     let node_json = "{\"name\":\"name\",\"priority\":1,\"address\":\"localhost:9090\",\"local\":true,\"sequence\":2}";
@@ -23,5 +26,22 @@ async fn test_serialize_node() -> Result<()> {
     assert_eq!(serde_json::to_string(&node)?, node_json.clone());
     assert_eq!(serde_json::from_str::<Node>(node_json.clone())?, node);
 
+    // The labels are serialized only if there are some.
+    let mut labels = BTreeMap::new();
+    labels.insert(String::from("zone"), String::from("a"));
+    let node = Node::create(
+        String::from("name"),
+        1,
+        Address::create(&String::from("localhost:9090"))?,
+        true,
+        2,
+        labels,
+    )?;
+
+    let node_json = "{\"name\":\"name\",\"priority\":1,\"address\":\"localhost:9090\",\"local\":true,\"sequence\":2,\"labels\":{\"zone\":\"a\"}}";
+
+    assert_eq!(serde_json::to_string(&node)?, node_json.clone());
+    assert_eq!(serde_json::from_str::<Node>(node_json.clone())?, node);
+
     Ok(())
 }
@@ -2,15 +2,19 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::ffi::OsString;
 use std::fmt;
 use std::str::FromStr;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
 use lazy_static::lazy_static;
+use structopt::clap::ArgMatches;
 use structopt::StructOpt;
 use structopt_toml::StructOptToml;
 
+use crate::datasources::LocalIo;
+
 lazy_static! {
     pub static ref FUSE_COMMIT_VERSION: String = {
         let build_semver = option_env!("VERGEN_BUILD_SEMVER");
@@ -33,13 +37,29 @@ lazy_static! {
 
 macro_rules! env_helper {
     ($config:expr, $field:tt, $field_type: ty, $env:expr) => {
-        let env_var = std::env::var_os($env)
-            .unwrap_or($config.$field.to_string().into())
-            .into_string()
-            .expect(format!("cannot convert {} to string", $env).as_str());
-        $config.$field = env_var
-            .parse::<$field_type>()
-            .expect(format!("cannot convert {} to {}", $env, stringify!($field_type)).as_str());
+        if let Some(env_var) = std::env::var_os($env) {
+            let env_var = env_var.into_string().map_err(|_| {
+                ErrorCode::BadArguments(format!("Invalid env {}, expects UTF-8", $env))
+            })?;
+            $config.$field = env_var.parse::<$field_type>().map_err(|e| {
+                ErrorCode::BadArguments(format!(
+                    "Invalid env {}, cannot convert {} to {}: {}",
+                    $env,
+                    env_var,
+                    stringify!($field_type),
+                    e
+                ))
+            })?;
+        }
+    };
+}
+
+// The args on the command line, whose names are the fields in kebab-case, override the config.
+macro_rules! arg_helper {
+    ($config:expr, $args:expr, $matches:expr, $field:tt) => {
+        if $matches.occurrences_of(stringify!($field).replace('_', "-").as_str()) > 0 {
+            $config.$field = $args.$field.clone();
+        }
     };
 }
 
@@ -50,6 +70,12 @@ const NUM_CPUS: &str = "FUSE_QUERY_NUM_CPUS";
 const MYSQL_HANDLER_HOST: &str = "FUSE_QUERY_MYSQL_HANDLER_HOST";
 const MYSQL_HANDLER_PORT: &str = "FUSE_QUERY_MYSQL_HANDLER_PORT";
 const MAX_ACTIVE_SESSIONS: &str = "FUSE_QUERY_MAX_ACTIVE_SESSIONS";
+const MAX_SESSIONS_PER_USER: &str = "FUSE_QUERY_MAX_SESSIONS_PER_USER";
+const MAX_RUNNING_QUERIES: &str = "FUSE_QUERY_MAX_RUNNING_QUERIES";
+const QUERY_QUEUE_TIMEOUT: &str = "FUSE_QUERY_QUERY_QUEUE_TIMEOUT";
+const QUERY_LOG_CAPACITY: &str = "FUSE_QUERY_QUERY_LOG_CAPACITY";
+const QUERY_LOG_FILE: &str = "FUSE_QUERY_QUERY_LOG_FILE";
+const SPILL_DIR: &str = "FUSE_QUERY_SPILL_DIR";
 
 const CLICKHOUSE_HANDLER_HOST: &str = "FUSE_QUERY_CLICKHOUSE_HANDLER_HOST";
 const CLICKHOUSE_HANDLER_PORT: &str = "FUSE_QUERY_CLICKHOUSE_HANDLER_PORT";
@@ -61,10 +87,18 @@ const METRICS_API_ADDRESS: &str = "FUSE_QUERY_METRIC_API_ADDRESS";
 const STORE_API_ADDRESS: &str = "STORE_API_ADDRESS";
 const STORE_API_USERNAME: &str = "STORE_API_USERNAME";
 const STORE_API_PASSWORD: &str = "STORE_API_PASSWORD";
+const READINESS_CHECK_STORE: &str = "FUSE_QUERY_READINESS_CHECK_STORE";
+const STORE_META_CHECK_INTERVAL_MS: &str = "FUSE_QUERY_STORE_META_CHECK_INTERVAL_MS";
+
+const TENANT: &str = "FUSE_QUERY_TENANT";
+
+const LOCAL_IO: &str = "FUSE_QUERY_LOCAL_IO";
 
 const CONFIG_FILE: &str = "CONFIG_FILE";
 
-#[derive(Clone, Debug, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
+#[derive(
+    Clone, Debug, serde::Deserialize, serde::Serialize, PartialEq, StructOpt, StructOptToml,
+)]
 #[serde(default)]
 pub struct Config {
     #[structopt(long, env = LOG_LEVEL, default_value = "INFO")]
@@ -93,6 +127,35 @@ pub struct Config {
     )]
     pub max_active_sessions: u64,
 
+    /// The max sessions of a single user, 0 means the sessions of a user are only limited by
+    /// max_active_sessions.
+    #[structopt(long, env = MAX_SESSIONS_PER_USER, default_value = "0")]
+    pub max_sessions_per_user: u64,
+
+    /// The max queries running at the same time, the other queries wait in the query queue
+    /// until a running query finishes. 0 means the queries are not queued.
+    #[structopt(long, env = MAX_RUNNING_QUERIES, default_value = "0")]
+    pub max_running_queries: u64,
+
+    /// The max seconds a query waits in the query queue before it fails.
+    #[structopt(long, env = QUERY_QUEUE_TIMEOUT, default_value = "60")]
+    pub query_queue_timeout: u64,
+
+    /// The max finished queries kept in system.query_log, the oldest ones are dropped. 0 means
+    /// the queries are not logged.
+    #[structopt(long, env = QUERY_LOG_CAPACITY, default_value = "1000")]
+    pub query_log_capacity: u64,
+
+    /// The file the finished queries are appended to, the queries in it are loaded into
+    /// system.query_log at startup. Empty means the query log is only in memory.
+    #[structopt(long, env = QUERY_LOG_FILE, default_value = "")]
+    pub query_log_file: String,
+
+    /// The directory of the temporary files which the large group bys spill to. Empty means the
+    /// temporary directory of the system.
+    #[structopt(long, env = SPILL_DIR, default_value = "")]
+    pub spill_dir: String,
+
     #[structopt(
     long,
     env = CLICKHOUSE_HANDLER_HOST,
@@ -137,8 +200,38 @@ pub struct Config {
     #[structopt(long, env = STORE_API_PASSWORD, default_value = "root")]
     pub store_api_password: Password,
 
+    /// Whether the node is ready only if the store of store_api_address is reachable, for the
+    /// nodes whose databases are in the store.
+    #[structopt(
+    long,
+    env = READINESS_CHECK_STORE,
+    default_value = "false",
+    parse(try_from_str)
+    )]
+    pub readiness_check_store: bool,
+
+    /// The min milliseconds between the checks of the meta version of the store, the cached
+    /// metas of the remote tables are used until a check finds a newer version.
+    #[structopt(long, env = STORE_META_CHECK_INTERVAL_MS, default_value = "1000")]
+    pub store_meta_check_interval_ms: u64,
+
+    /// The tenant which the sessions of this query node are bound to, the databases of a tenant
+    /// are not visible to the others. Empty if the sessions are not bound to any tenant.
+    #[structopt(long, env = TENANT, default_value = "")]
+    pub tenant: String,
+
+    /// The IO of reading the files of the local tables, `std` or `io_uring`. The io_uring reads
+    /// are only on Linux, they fall back to std elsewhere.
+    #[structopt(long, env = LOCAL_IO, default_value = "std")]
+    pub local_io: String,
+
     #[structopt(long, short = "c", env = CONFIG_FILE, default_value = "")]
     pub config_file: String,
+
+    /// Prints the loaded configs in TOML and exits, the secrets are masked.
+    #[structopt(long)]
+    #[serde(skip)]
+    pub print_config: bool,
 }
 
 #[derive(Clone, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
@@ -174,6 +267,15 @@ impl fmt::Debug for Password {
     }
 }
 
+impl serde::Serialize for Password {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str("******")
+    }
+}
+
 #[derive(Clone, serde::Deserialize, PartialEq, StructOpt, StructOptToml)]
 #[serde(default)]
 pub struct User {
@@ -192,6 +294,15 @@ impl fmt::Debug for User {
     }
 }
 
+impl serde::Serialize for User {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str("******")
+    }
+}
+
 impl AsRef<String> for User {
     fn as_ref(&self) -> &String {
         &self.store_api_username
@@ -217,6 +328,12 @@ impl Config {
             mysql_handler_host: "127.0.0.1".to_string(),
             mysql_handler_port: 3307,
             max_active_sessions: 256,
+            max_sessions_per_user: 0,
+            max_running_queries: 0,
+            query_queue_timeout: 60,
+            query_log_capacity: 1000,
+            query_log_file: "".to_string(),
+            spill_dir: "".to_string(),
             clickhouse_handler_host: "127.0.0.1".to_string(),
             clickhouse_handler_port: 9000,
             flight_api_address: "127.0.0.1:9090".to_string(),
@@ -229,8 +346,124 @@ impl Config {
             store_api_password: Password {
                 store_api_password: "root".to_string(),
             },
+            readiness_check_store: false,
+            store_meta_check_interval_ms: 1000,
+            tenant: "".to_string(),
+            local_io: "std".to_string(),
             config_file: "".to_string(),
+            print_config: false,
+        }
+    }
+
+    /// Load configs by layers, each layer overrides the ones before it: the defaults, the config
+    /// file of `-c` or CONFIG_FILE, the env variables and the args on the command line.
+    pub fn load() -> Result<Self> {
+        Config::load_from_matches(&Config::clap().get_matches())
+    }
+
+    /// Load configs by layers as `load`, with the args of the iterator.
+    pub fn load_from_iter<I>(iter: I) -> Result<Self>
+    where
+        I: IntoIterator,
+        I::Item: Into<OsString> + Clone,
+    {
+        let matches = Config::clap()
+            .get_matches_from_safe(iter)
+            .map_err(|e| ErrorCode::BadArguments(e.message))?;
+        Config::load_from_matches(&matches)
+    }
+
+    fn load_from_matches(matches: &ArgMatches) -> Result<Self> {
+        // The args also take the env variables and the defaults of the args not on the command
+        // line, they are the configs if there is no config file.
+        let args = Config::from_clap(matches);
+        let mut cfg = args.clone();
+        if !args.config_file.is_empty() {
+            cfg = Config::load_from_toml(args.config_file.as_str())?;
+            cfg = Config::load_from_env(&cfg)?;
+            cfg.override_by_args(&args, matches);
+        }
+
+        cfg.config_file = args.config_file.clone();
+        cfg.print_config = args.print_config;
+        if cfg.num_cpus == 0 {
+            cfg.num_cpus = num_cpus::get() as u64;
+        }
+        cfg.validate()?;
+        Ok(cfg)
+    }
+
+    fn override_by_args(&mut self, args: &Config, matches: &ArgMatches) {
+        arg_helper!(self, args, matches, log_level);
+        arg_helper!(self, args, matches, log_dir);
+        arg_helper!(self, args, matches, num_cpus);
+        arg_helper!(self, args, matches, mysql_handler_host);
+        arg_helper!(self, args, matches, mysql_handler_port);
+        arg_helper!(self, args, matches, max_active_sessions);
+        arg_helper!(self, args, matches, max_sessions_per_user);
+        arg_helper!(self, args, matches, max_running_queries);
+        arg_helper!(self, args, matches, query_queue_timeout);
+        arg_helper!(self, args, matches, query_log_capacity);
+        arg_helper!(self, args, matches, query_log_file);
+        arg_helper!(self, args, matches, spill_dir);
+        arg_helper!(self, args, matches, clickhouse_handler_host);
+        arg_helper!(self, args, matches, clickhouse_handler_port);
+        arg_helper!(self, args, matches, flight_api_address);
+        arg_helper!(self, args, matches, http_api_address);
+        arg_helper!(self, args, matches, metric_api_address);
+        arg_helper!(self, args, matches, store_api_address);
+        arg_helper!(self, args, matches, store_api_username);
+        arg_helper!(self, args, matches, store_api_password);
+        arg_helper!(self, args, matches, readiness_check_store);
+        arg_helper!(self, args, matches, store_meta_check_interval_ms);
+        arg_helper!(self, args, matches, tenant);
+        arg_helper!(self, args, matches, local_io);
+    }
+
+    /// Checks the configs, the error names the invalid config.
+    pub fn validate(&self) -> Result<()> {
+        match self.log_level.to_uppercase().as_str() {
+            "TRACE" | "DEBUG" | "INFO" | "WARN" | "ERROR" => {}
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Invalid log_level {}, expects one of TRACE, DEBUG, INFO, WARN, ERROR",
+                    self.log_level
+                )))
+            }
+        }
+
+        let addresses = [
+            ("flight_api_address", &self.flight_api_address),
+            ("http_api_address", &self.http_api_address),
+            ("metric_api_address", &self.metric_api_address),
+            ("store_api_address", &self.store_api_address),
+        ];
+        for (name, address) in addresses.iter() {
+            // The hosts may be the names resolved in the containers, only the ports are parsed.
+            match address.rsplit_once(':') {
+                Some((host, port)) if !host.is_empty() && port.parse::<u16>().is_ok() => {}
+                _ => {
+                    return Err(ErrorCode::BadArguments(format!(
+                        "Invalid {} {}, expects host:port",
+                        name, address
+                    )))
+                }
+            }
+        }
+
+        if self.max_active_sessions == 0 {
+            return Err(ErrorCode::BadArguments(
+                "Invalid max_active_sessions 0, expects a positive number",
+            ));
         }
+
+        LocalIo::try_create(&self.local_io)?;
+        Ok(())
+    }
+
+    /// The configs in TOML, the secrets are masked.
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|e| ErrorCode::BadArguments(e.to_string()))
     }
 
     /// Load configs from args.
@@ -242,12 +475,12 @@ impl Config {
         cfg
     }
 
-    /// Load configs from toml file.
+    /// Load configs from toml file, the configs not in the file are the defaults.
     pub fn load_from_toml(file: &str) -> Result<Self> {
         let context = std::fs::read_to_string(file)
             .map_err(|e| ErrorCode::CannotReadFile(format!("File: {}, err: {:?}", file, e)))?;
-        let mut cfg = Config::from_args_with_toml(context.as_str())
-            .map_err(|e| ErrorCode::BadArguments(format!("{:?}", e)))?;
+        let mut cfg = toml::from_str::<Config>(context.as_str())
+            .map_err(|e| ErrorCode::BadArguments(format!("File: {}, err: {}", file, e)))?;
         if cfg.num_cpus == 0 {
             cfg.num_cpus = num_cpus::get() as u64;
         }
@@ -257,17 +490,23 @@ impl Config {
     /// Change config based on configured env variable
     pub fn load_from_env(cfg: &Config) -> Result<Self> {
         let mut mut_config = cfg.clone();
-        if std::env::var_os(CONFIG_FILE).is_some() {
-            return Config::load_from_toml(
-                std::env::var_os(CONFIG_FILE).unwrap().to_str().unwrap(),
-            );
-        }
         env_helper!(mut_config, log_level, String, LOG_LEVEL);
         env_helper!(mut_config, log_dir, String, LOG_DIR);
         env_helper!(mut_config, num_cpus, u64, NUM_CPUS);
         env_helper!(mut_config, mysql_handler_host, String, MYSQL_HANDLER_HOST);
         env_helper!(mut_config, mysql_handler_port, u16, MYSQL_HANDLER_PORT);
         env_helper!(mut_config, max_active_sessions, u64, MAX_ACTIVE_SESSIONS);
+        env_helper!(
+            mut_config,
+            max_sessions_per_user,
+            u64,
+            MAX_SESSIONS_PER_USER
+        );
+        env_helper!(mut_config, max_running_queries, u64, MAX_RUNNING_QUERIES);
+        env_helper!(mut_config, query_queue_timeout, u64, QUERY_QUEUE_TIMEOUT);
+        env_helper!(mut_config, query_log_capacity, u64, QUERY_LOG_CAPACITY);
+        env_helper!(mut_config, query_log_file, String, QUERY_LOG_FILE);
+        env_helper!(mut_config, spill_dir, String, SPILL_DIR);
         env_helper!(
             mut_config,
             clickhouse_handler_host,
@@ -286,6 +525,20 @@ impl Config {
         env_helper!(mut_config, store_api_address, String, STORE_API_ADDRESS);
         env_helper!(mut_config, store_api_username, User, STORE_API_USERNAME);
         env_helper!(mut_config, store_api_password, Password, STORE_API_PASSWORD);
+        env_helper!(
+            mut_config,
+            readiness_check_store,
+            bool,
+            READINESS_CHECK_STORE
+        );
+        env_helper!(
+            mut_config,
+            store_meta_check_interval_ms,
+            u64,
+            STORE_META_CHECK_INTERVAL_MS
+        );
+        env_helper!(mut_config, tenant, String, TENANT);
+        env_helper!(mut_config, local_io, String, LOCAL_IO);
 
         Ok(mut_config)
     }
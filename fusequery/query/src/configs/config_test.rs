@@ -2,13 +2,21 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::sync::Mutex;
+
 use common_exception::Result;
+use lazy_static::lazy_static;
 use pretty_assertions::assert_eq;
 
 use crate::configs::config::Password;
 use crate::configs::config::User;
 use crate::configs::Config;
 
+lazy_static! {
+    // The tests of the env variables are run one by one, as the env variables are of the process.
+    static ref ENV_LOCK: Mutex<()> = Mutex::new(());
+}
+
 // Default.
 #[test]
 fn test_default_config() -> Result<()> {
@@ -19,6 +27,12 @@ fn test_default_config() -> Result<()> {
         mysql_handler_host: "127.0.0.1".to_string(),
         mysql_handler_port: 3307,
         max_active_sessions: 256,
+        max_sessions_per_user: 0,
+        max_running_queries: 0,
+        query_queue_timeout: 60,
+        query_log_capacity: 1000,
+        query_log_file: "".to_string(),
+        spill_dir: "".to_string(),
         clickhouse_handler_host: "127.0.0.1".to_string(),
         clickhouse_handler_port: 9000,
         flight_api_address: "127.0.0.1:9090".to_string(),
@@ -31,7 +45,12 @@ fn test_default_config() -> Result<()> {
         store_api_password: Password {
             store_api_password: "root".to_string(),
         },
+        readiness_check_store: false,
+        store_meta_check_interval_ms: 1000,
+        tenant: "".to_string(),
+        local_io: "std".to_string(),
         config_file: "".to_string(),
+        print_config: false,
     };
     let actual = Config::default();
     assert_eq!(actual, expect);
@@ -41,10 +60,17 @@ fn test_default_config() -> Result<()> {
 // From env, defaulting.
 #[test]
 fn test_env_config() -> Result<()> {
+    let _guard = ENV_LOCK.lock().unwrap();
     std::env::set_var("FUSE_QUERY_LOG_LEVEL", "DEBUG");
     std::env::set_var("FUSE_QUERY_MYSQL_HANDLER_HOST", "0.0.0.0");
     std::env::set_var("FUSE_QUERY_MYSQL_HANDLER_PORT", "3306");
     std::env::set_var("FUSE_QUERY_MAX_ACTIVE_SESSIONS", "255");
+    std::env::set_var("FUSE_QUERY_MAX_SESSIONS_PER_USER", "16");
+    std::env::set_var("FUSE_QUERY_MAX_RUNNING_QUERIES", "8");
+    std::env::set_var("FUSE_QUERY_QUERY_QUEUE_TIMEOUT", "30");
+    std::env::set_var("FUSE_QUERY_QUERY_LOG_CAPACITY", "100");
+    std::env::set_var("FUSE_QUERY_QUERY_LOG_FILE", "/tmp/query_log.json");
+    std::env::set_var("FUSE_QUERY_SPILL_DIR", "/tmp/spill");
     std::env::set_var("FUSE_QUERY_CLICKHOUSE_HANDLER_HOST", "1.2.3.4");
     std::env::set_var("FUSE_QUERY_CLICKHOUSE_HANDLER_PORT", "9000");
     std::env::set_var("FUSE_QUERY_FLIGHT_API_ADDRESS", "1.2.3.4:9091");
@@ -53,6 +79,10 @@ fn test_env_config() -> Result<()> {
     std::env::set_var("STORE_API_ADDRESS", "1.2.3.4:1234");
     std::env::set_var("STORE_API_USERNAME", "admin");
     std::env::set_var("STORE_API_PASSWORD", "password!");
+    std::env::set_var("FUSE_QUERY_READINESS_CHECK_STORE", "true");
+    std::env::set_var("FUSE_QUERY_STORE_META_CHECK_INTERVAL_MS", "200");
+    std::env::set_var("FUSE_QUERY_TENANT", "tenant1");
+    std::env::set_var("FUSE_QUERY_LOCAL_IO", "io_uring");
     std::env::remove_var("CONFIG_FILE");
     let default = Config::default();
     let configured = Config::load_from_env(&default)?;
@@ -60,6 +90,12 @@ fn test_env_config() -> Result<()> {
     assert_eq!("0.0.0.0", configured.mysql_handler_host);
     assert_eq!(3306, configured.mysql_handler_port);
     assert_eq!(255, configured.max_active_sessions);
+    assert_eq!(16, configured.max_sessions_per_user);
+    assert_eq!(8, configured.max_running_queries);
+    assert_eq!(30, configured.query_queue_timeout);
+    assert_eq!(100, configured.query_log_capacity);
+    assert_eq!("/tmp/query_log.json", configured.query_log_file);
+    assert_eq!("/tmp/spill", configured.spill_dir);
     assert_eq!("1.2.3.4", configured.clickhouse_handler_host);
     assert_eq!(9000, configured.clickhouse_handler_port);
 
@@ -70,12 +106,23 @@ fn test_env_config() -> Result<()> {
     assert_eq!("1.2.3.4:1234", configured.store_api_address);
     assert_eq!("admin", configured.store_api_username.to_string());
     assert_eq!("password!", configured.store_api_password.to_string());
+    assert!(configured.readiness_check_store);
+    assert_eq!(200, configured.store_meta_check_interval_ms);
+    assert_eq!("tenant1", configured.tenant);
+    assert_eq!("io_uring", configured.local_io);
 
     // clean up
     std::env::remove_var("FUSE_QUERY_LOG_LEVEL");
     std::env::remove_var("FUSE_QUERY_MYSQL_HANDLER_HOST");
     std::env::remove_var("FUSE_QUERY_MYSQL_HANDLER_PORT");
     std::env::remove_var("FUSE_QUERY_MYSQL_HANDLER_THREAD_NUM");
+    std::env::remove_var("FUSE_QUERY_MAX_ACTIVE_SESSIONS");
+    std::env::remove_var("FUSE_QUERY_MAX_SESSIONS_PER_USER");
+    std::env::remove_var("FUSE_QUERY_MAX_RUNNING_QUERIES");
+    std::env::remove_var("FUSE_QUERY_QUERY_QUEUE_TIMEOUT");
+    std::env::remove_var("FUSE_QUERY_QUERY_LOG_CAPACITY");
+    std::env::remove_var("FUSE_QUERY_QUERY_LOG_FILE");
+    std::env::remove_var("FUSE_QUERY_SPILL_DIR");
     std::env::remove_var("FUSE_QUERY_CLICKHOUSE_HANDLER_HOST");
     std::env::remove_var("FUSE_QUERY_CLICKHOUSE_HANDLER_PORT");
     std::env::remove_var("FUSE_QUERY_CLICKHOUSE_HANDLER_THREAD_NUM");
@@ -85,6 +132,10 @@ fn test_env_config() -> Result<()> {
     std::env::remove_var("STORE_API_ADDRESS");
     std::env::remove_var("STORE_API_USERNAME");
     std::env::remove_var("STORE_API_PASSWORD");
+    std::env::remove_var("FUSE_QUERY_READINESS_CHECK_STORE");
+    std::env::remove_var("FUSE_QUERY_STORE_META_CHECK_INTERVAL_MS");
+    std::env::remove_var("FUSE_QUERY_TENANT");
+    std::env::remove_var("FUSE_QUERY_LOCAL_IO");
     Ok(())
 }
 
@@ -99,7 +150,6 @@ fn test_args_config() -> Result<()> {
 
 // From file NotFound.
 #[test]
-#[ignore]
 fn test_config_file_not_found() -> Result<()> {
     if let Err(e) = Config::load_from_toml("xx.toml") {
         let expect = "Code: 23, displayText = File: xx.toml, err: Os { code: 2, kind: NotFound, message: \"No such file or directory\" }.";
@@ -110,8 +160,8 @@ fn test_config_file_not_found() -> Result<()> {
 
 // From file.
 #[test]
-#[ignore]
 fn test_file_config() -> Result<()> {
+    let _guard = ENV_LOCK.lock().unwrap();
     std::env::set_var("FUSE_QUERY_LOG_LEVEL", "DEBUG");
     let path = std::env::current_dir()
         .unwrap()
@@ -121,27 +171,135 @@ fn test_file_config() -> Result<()> {
 
     let actual = Config::load_from_toml(path.as_str())?;
     assert_eq!("INFO", actual.log_level);
+    assert_eq!("127.0.0.1:9090", actual.flight_api_address);
+    // The env variables override the file.
     let env = Config::load_from_env(&actual)?;
-    assert_eq!("INFO", env.log_level);
+    assert_eq!("DEBUG", env.log_level);
     std::env::remove_var("FUSE_QUERY_LOG_LEVEL");
     Ok(())
 }
 
-// From env, load config file and ignore the rest settings.
+// From file, env and args, each layer overrides the ones before it.
 #[test]
-#[ignore]
-fn test_env_file_config() -> Result<()> {
-    std::env::set_var("FUSE_QUERY_LOG_LEVEL", "DEBUG");
-    let config_path = std::env::current_dir()
-        .unwrap()
-        .join("conf/fusequery_config_spec.toml")
-        .display()
-        .to_string();
-    std::env::set_var("CONFIG_FILE", config_path);
-    let config = Config::load_from_env(&Config::default())?;
-    assert_eq!(config.log_level, "INFO");
-    std::env::remove_var("FUSE_QUERY_LOG_LEVEL");
+fn test_layered_config() -> Result<()> {
+    let _guard = ENV_LOCK.lock().unwrap();
+    let path = std::env::temp_dir().join(format!("{}.toml", uuid::Uuid::new_v4()));
+    std::fs::write(
+        &path,
+        "log_dir = \"/var/log/fuse-query\"\n\
+         num_cpus = 2\n\
+         mysql_handler_port = 3308\n\
+         tenant = \"file_tenant\"\n",
+    )?;
+    let path = path.display().to_string();
+
+    std::env::set_var("FUSE_QUERY_NUM_CPUS", "4");
+    std::env::set_var("FUSE_QUERY_TENANT", "env_tenant");
+    std::env::set_var("STORE_API_PASSWORD", "secret");
+    let args = vec!["fuse-query", "-c", path.as_str(), "--tenant", "arg_tenant"];
+    let actual = Config::load_from_iter(args)?;
+    assert_eq!("/var/log/fuse-query", actual.log_dir);
+    assert_eq!(3308, actual.mysql_handler_port);
+    assert_eq!(4, actual.num_cpus);
+    assert_eq!("arg_tenant", actual.tenant);
+    assert_eq!("secret", actual.store_api_password.to_string());
+    assert_eq!(path, actual.config_file);
+    // The configs not in the file, env and args are the defaults.
+    assert_eq!(256, actual.max_active_sessions);
+    assert!(!actual.print_config);
+
+    // The config file of the env variable.
+    std::env::set_var("CONFIG_FILE", path.as_str());
+    let actual = Config::load_from_iter(vec!["fuse-query", "--print-config"])?;
+    assert_eq!(3308, actual.mysql_handler_port);
+    assert_eq!("env_tenant", actual.tenant);
+    assert!(actual.print_config);
+
+    // The secrets are masked in the printed configs.
+    let printed = actual.to_toml()?;
+    assert!(printed.contains("mysql_handler_port = 3308\n"));
+    assert!(printed.contains("store_api_password = \"******\"\n"));
+    assert!(!printed.contains("secret"));
+    assert!(!printed.contains("print_config"));
+
+    // The invalid env variables.
+    std::env::set_var("FUSE_QUERY_NUM_CPUS", "four");
+    let expect = "Code: 6, displayText = Invalid env FUSE_QUERY_NUM_CPUS, cannot convert four to u64: invalid digit found in string.";
+    let actual = Config::load_from_env(&Config::default());
+    assert_eq!(expect, actual.unwrap_err().to_string());
+
     std::env::remove_var("CONFIG_FILE");
+    std::env::remove_var("FUSE_QUERY_NUM_CPUS");
+    std::env::remove_var("FUSE_QUERY_TENANT");
+    std::env::remove_var("STORE_API_PASSWORD");
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_validate_config() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        config: Config,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "log_level",
+            config: Config {
+                log_level: "verbose".to_string(),
+                ..Config::default()
+            },
+            expect: "Code: 6, displayText = Invalid log_level verbose, expects one of TRACE, DEBUG, INFO, WARN, ERROR.",
+        },
+        Test {
+            name: "address",
+            config: Config {
+                flight_api_address: "query-0".to_string(),
+                ..Config::default()
+            },
+            expect: "Code: 6, displayText = Invalid flight_api_address query-0, expects host:port.",
+        },
+        Test {
+            name: "port",
+            config: Config {
+                store_api_address: "store-0:65536".to_string(),
+                ..Config::default()
+            },
+            expect: "Code: 6, displayText = Invalid store_api_address store-0:65536, expects host:port.",
+        },
+        Test {
+            name: "max_active_sessions",
+            config: Config {
+                max_active_sessions: 0,
+                ..Config::default()
+            },
+            expect: "Code: 6, displayText = Invalid max_active_sessions 0, expects a positive number.",
+        },
+        Test {
+            name: "local_io",
+            config: Config {
+                local_io: "aio".to_string(),
+                ..Config::default()
+            },
+            expect: "Code: 6, displayText = Unknown local_io aio, expects std or io_uring.",
+        },
+    ];
+
+    Config::default().validate()?;
+    Config {
+        flight_api_address: "query-0.query:9090".to_string(),
+        ..Config::default()
+    }
+    .validate()?;
+    for t in tests {
+        let actual = t.config.validate().unwrap_err().to_string();
+        assert_eq!(t.expect, actual, "{}", t.name);
+    }
+
+    let actual = Config::load_from_iter(vec!["fuse-query", "--mysql-handler-port", "x"]);
+    assert!(actual.is_err());
     Ok(())
 }
 
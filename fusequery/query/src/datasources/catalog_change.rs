@@ -0,0 +1,14 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+/// A DDL of a remote database executed by a query node, broadcast to the other nodes of the cluster,
+/// so that they drop their cached metadata at once instead of serving stale tables until the cache
+/// is re-validated against the store.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub enum CatalogChange {
+    CreateDatabase { db: String },
+    DropDatabase { db: String },
+    CreateTable { db: String, table: String },
+    DropTable { db: String, table: String },
+}
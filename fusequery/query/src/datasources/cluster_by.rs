@@ -0,0 +1,104 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fmt;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_flights::storage_api_impl::get_part_bucket;
+use common_planners::Expression;
+use common_planners::Part;
+use common_planners::TableOptions;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+
+/// The CLUSTER BY of a bucketed table, `CLUSTER BY column INTO buckets BUCKETS`.
+///
+/// A row is in the bucket `sipHash(column) % buckets`, the same hash as the shuffle of the rows by
+/// the column, so the rows with the same value of the column are always in the same bucket.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClusterBy {
+    pub column: String,
+    pub buckets: u64,
+}
+
+impl ClusterBy {
+    /// Creates from the table options, None if the table is not bucketed.
+    pub fn try_create(options: &TableOptions) -> Result<Option<Self>> {
+        let column = match options.get("cluster_by") {
+            None => return Ok(None),
+            Some(column) => column.clone(),
+        };
+        let buckets = options
+            .get("buckets")
+            .ok_or_else(|| ErrorCode::BadOption("CLUSTER BY must have the number of buckets"))?
+            .parse::<u64>()?;
+        if buckets == 0 {
+            return Err(ErrorCode::BadOption(
+                "The number of buckets must be greater than 0",
+            ));
+        }
+        Ok(Some(ClusterBy { column, buckets }))
+    }
+
+    /// Splits a block by bucket, returns the buckets and the rows of each bucket.
+    pub fn split_block(&self, block: &DataBlock) -> Result<Vec<(u64, DataBlock)>> {
+        let expr = Expression::ScalarFunction {
+            op: String::from("modulo"),
+            args: vec![
+                Expression::Cast {
+                    expr: Box::new(Expression::ScalarFunction {
+                        op: String::from("sipHash"),
+                        args: vec![Expression::Column(self.column.clone())],
+                    }),
+                    data_type: DataType::UInt64,
+                },
+                Expression::create_literal(DataValue::UInt64(Some(self.buckets))),
+            ],
+        };
+        let executor = ExpressionExecutor::try_create(
+            "cluster by executor",
+            block.schema().clone(),
+            DataSchemaRefExt::create(vec![DataField::new(
+                &expr.column_name(),
+                DataType::UInt64,
+                false,
+            )]),
+            vec![expr.clone()],
+            false,
+        )?;
+
+        let indices = executor.execute(block)?.column(0).clone();
+        let blocks = DataBlock::scatter_block(block, &indices, self.buckets as usize)?;
+        Ok(blocks
+            .into_iter()
+            .enumerate()
+            .filter(|(_, block)| block.num_rows() > 0)
+            .map(|(bucket, block)| (bucket as u64, block))
+            .collect())
+    }
+
+    /// Returns the bucket of a part, None if the part is not written by bucket.
+    pub fn part_bucket(part: &Part) -> Option<u64> {
+        get_part_bucket(&part.name)
+    }
+
+    /// Whether all the parts of a read are written by bucket. The rows of a part without a bucket
+    /// may have any value of the bucket column, so a read with such a part is not co-located.
+    pub fn all_parts_bucketed(parts: &[Part]) -> bool {
+        parts.iter().all(|part| Self::part_bucket(part).is_some())
+    }
+}
+
+impl fmt::Display for ClusterBy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "CLUSTER BY {} INTO {} BUCKETS",
+            self.column, self.buckets
+        )
+    }
+}
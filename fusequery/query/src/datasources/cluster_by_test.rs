@@ -0,0 +1,106 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::TableOptions;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::*;
+
+#[test]
+fn test_cluster_by_options() -> Result<()> {
+    let mut options: TableOptions = HashMap::new();
+    assert_eq!(ClusterBy::try_create(&options)?, None);
+
+    options.insert("cluster_by".to_string(), "a".to_string());
+    let result = ClusterBy::try_create(&options);
+    assert_eq!(
+        result.err().unwrap().message(),
+        "CLUSTER BY must have the number of buckets"
+    );
+
+    options.insert("buckets".to_string(), "0".to_string());
+    let result = ClusterBy::try_create(&options);
+    assert_eq!(
+        result.err().unwrap().message(),
+        "The number of buckets must be greater than 0"
+    );
+
+    options.insert("buckets".to_string(), "4".to_string());
+    let cluster_by = ClusterBy::try_create(&options)?.unwrap();
+    assert_eq!(cluster_by, ClusterBy {
+        column: "a".to_string(),
+        buckets: 4,
+    });
+    assert_eq!(cluster_by.to_string(), "CLUSTER BY a INTO 4 BUCKETS");
+
+    Ok(())
+}
+
+#[test]
+fn test_cluster_by_split_block() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::Utf8, false),
+    ]);
+    let cluster_by = ClusterBy {
+        column: "a".to_string(),
+        buckets: 4,
+    };
+
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1u64, 2, 1, 3, 2, 1]),
+        Series::new(vec!["x", "y", "z", "w", "v", "u"]),
+    ]);
+    let buckets = cluster_by.split_block(&block)?;
+    assert_eq!(buckets.iter().map(|(_, b)| b.num_rows()).sum::<usize>(), 6);
+
+    // The rows with the same value are in the same bucket.
+    for (bucket, block) in buckets.iter() {
+        assert!(*bucket < 4);
+        assert!(block.num_rows() > 0);
+    }
+    let bucket_of = |value: u64| -> Result<u64> {
+        let mut found = vec![];
+        for (bucket, block) in buckets.iter() {
+            for row in 0..block.num_rows() {
+                if block.column(0).try_get(row)? == DataValue::UInt64(Some(value)) {
+                    found.push(*bucket);
+                }
+            }
+        }
+        found.dedup();
+        assert_eq!(found.len(), 1);
+        Ok(found[0])
+    };
+    bucket_of(1)?;
+    bucket_of(2)?;
+    bucket_of(3)?;
+
+    Ok(())
+}
+
+#[test]
+fn test_cluster_by_part_bucket() -> Result<()> {
+    let part = Part {
+        name: "db/t/bucket-3-0f8fad5bd9cb469fa16570867728950e.parquet".to_string(),
+        version: 0,
+        locations: vec![],
+    };
+    assert_eq!(ClusterBy::part_bucket(&part), Some(3));
+
+    let part = Part {
+        name: "db/t/0f8fad5bd9cb469fa16570867728950e.parquet".to_string(),
+        version: 0,
+        locations: vec![],
+    };
+    assert_eq!(ClusterBy::part_bucket(&part), None);
+
+    Ok(())
+}
@@ -21,6 +21,7 @@ impl Common {
             partitions.push(Part {
                 name: format!("{}-{}-{}", total, start, total,),
                 version: 0,
+                locations: vec![],
             })
         } else {
             for part in 0..workers {
@@ -35,6 +36,7 @@ impl Common {
                 partitions.push(Part {
                     name: format!("{}-{}-{}", total, part_begin, part_end,),
                     version: 0,
+                    locations: vec![],
                 })
             }
         }
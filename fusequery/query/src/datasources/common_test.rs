@@ -19,21 +19,24 @@ fn test_util_generate_parts() -> Result<()> {
         assert_eq!(
             Part {
                 name: "11-0-3".into(),
-                version: 0
+                version: 0,
+                locations: vec![]
             },
             ps[0]
         );
         assert_eq!(
             Part {
                 name: "11-3-6".into(),
-                version: 0
+                version: 0,
+                locations: vec![]
             },
             ps[1]
         );
         assert_eq!(
             Part {
                 name: "11-6-11".into(),
-                version: 0
+                version: 0,
+                locations: vec![]
             },
             ps[2]
         );
@@ -47,7 +50,8 @@ fn test_util_generate_parts() -> Result<()> {
         assert_eq!(
             Part {
                 name: "0-0-0".into(),
-                version: 0
+                version: 0,
+                locations: vec![]
             },
             ps[0]
         );
@@ -60,7 +64,8 @@ fn test_util_generate_parts() -> Result<()> {
         assert_eq!(
             Part {
                 name: "2-0-2".into(),
-                version: 0
+                version: 0,
+                locations: vec![]
             },
             ps[0]
         );
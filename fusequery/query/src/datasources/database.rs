@@ -4,6 +4,7 @@
 
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
@@ -30,4 +31,15 @@ pub trait Database: Sync + Send {
     /// DDL
     async fn create_table(&self, plan: CreateTablePlan) -> Result<()>;
     async fn drop_table(&self, plan: DropTablePlan) -> Result<()>;
+
+    /// Register a table which is created by the caller, such as the tables of an embedding application.
+    fn register_table(&self, _table: Arc<dyn Table>) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "Cannot register table to database {}",
+            self.name()
+        )))
+    }
+
+    /// Drop the cached metadata of a table which is changed by another query node.
+    fn invalidate_table(&self, _table_name: &str) {}
 }
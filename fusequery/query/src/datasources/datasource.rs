@@ -11,18 +11,17 @@ use common_infallible::RwLock;
 use common_planners::CreateDatabasePlan;
 use common_planners::DatabaseEngineType;
 use common_planners::DropDatabasePlan;
-use common_planners::TableOptions;
 use common_store_api::MetaApi;
 
 use crate::configs::Config;
 use crate::datasources::local::LocalDatabase;
 use crate::datasources::local::LocalFactory;
-use crate::datasources::remote::RemoteDatabase;
 use crate::datasources::remote::RemoteFactory;
-use crate::datasources::remote::RemoteTable;
 use crate::datasources::system::SystemFactory;
+use crate::datasources::CatalogChange;
 use crate::datasources::Database;
 use crate::datasources::Table;
+use crate::datasources::TableEngineRegistry;
 use crate::datasources::TableFunction;
 
 // Maintain all the databases of user.
@@ -39,10 +38,22 @@ impl DataSource {
     }
 
     pub fn try_create_with_config(conf: &Config) -> Result<Self> {
+        DataSource::try_create_with_remote_factory(RemoteFactory::new(conf, None))
+    }
+
+    /// The catalog of a tenant, which has its own databases, its remote databases are namespaced
+    /// by the tenant in the meta store.
+    pub fn try_create_with_tenant(tenant: &str) -> Result<Self> {
+        let conf = Config::default();
+        let remote_factory = RemoteFactory::new(&conf, Some(tenant.to_string()));
+        DataSource::try_create_with_remote_factory(remote_factory)
+    }
+
+    fn try_create_with_remote_factory(remote_factory: RemoteFactory) -> Result<Self> {
         let mut datasource = DataSource {
             databases: Default::default(),
             table_functions: Default::default(),
-            remote_factory: RemoteFactory::new(conf),
+            remote_factory,
         };
 
         datasource.register_system_database()?;
@@ -122,6 +133,10 @@ impl DataSource {
         Ok(table.clone())
     }
 
+    pub fn register_table(&self, db_name: &str, table: Arc<dyn Table>) -> Result<()> {
+        self.get_database(db_name)?.register_table(table)
+    }
+
     pub async fn get_remote_table(
         &self,
         db_name: &str,
@@ -134,24 +149,48 @@ impl DataSource {
             ))),
             tbl @ Ok(_) => tbl,
             _ => {
+                let meta_cache = self.remote_factory.meta_cache();
+                if !meta_cache.need_check() {
+                    if let Some(tbl) = meta_cache.get_table(db_name, table_name) {
+                        return Ok(tbl);
+                    }
+                }
+
                 let cli_provider = self.remote_factory.store_client_provider();
                 let mut store_cli = cli_provider.try_get_client().await?;
+
+                // Cached tables are dropped if the catalog has been changed since last check.
+                if meta_cache.need_check() {
+                    let res = store_cli.get_meta_version().await?;
+                    meta_cache.validate(res.meta_ver);
+                    if let Some(tbl) = meta_cache.get_table(db_name, table_name) {
+                        return Ok(tbl);
+                    }
+                }
+
                 let res = store_cli
                     .get_table(db_name.to_string(), table_name.to_string())
                     .await?;
-                let remote_table = RemoteTable::try_create(
-                    db_name.to_string(),
-                    table_name.to_string(),
-                    res.schema,
-                    self.remote_factory.store_client_provider().clone(),
-                    TableOptions::new(),
-                )?;
-
-                // Remote_table we've got here is NOT cached.
-                //
-                // Since we should solve the metadata synchronization problem in a more reasonable way,
-                // let's postpone it until we have taken all the things into account.
-                Ok(Arc::from(remote_table))
+                // The table is built by the engine and the options it is created with, the tables
+                // created before the engine was kept in the meta are of the default engine.
+                let engine = match res.engine.is_empty() {
+                    true => TableEngineRegistry::DEFAULT_STORE_ENGINE.to_string(),
+                    false => res.engine,
+                };
+                let mut options = res.options;
+                options.insert("comment".to_string(), res.comment);
+                let remote_table: Arc<dyn Table> =
+                    Arc::from(TableEngineRegistry::create_store_table(
+                        &engine,
+                        db_name.to_string(),
+                        table_name.to_string(),
+                        res.schema,
+                        self.remote_factory.store_client_provider(),
+                        options,
+                    )?);
+
+                meta_cache.insert_table(db_name, table_name, remote_table.clone());
+                Ok(remote_table)
             }
         }
     }
@@ -200,11 +239,10 @@ impl DataSource {
                     .store_client_provider()
                     .try_get_client()
                     .await?;
-                client.create_database(plan.clone()).await.map(|_| {
-                    let database = RemoteDatabase::create(
-                        self.remote_factory.store_client_provider(),
-                        plan.db.clone(),
-                    );
+                let mut store_plan = plan.clone();
+                store_plan.db = self.remote_factory.store_db_name(&plan.db);
+                client.create_database(store_plan).await.map(|_| {
+                    let database = self.remote_factory.create_database(plan.db.clone());
                     self.databases
                         .write()
                         .insert(plan.db.clone(), Arc::new(database));
@@ -236,11 +274,45 @@ impl DataSource {
                 .store_client_provider()
                 .try_get_client()
                 .await?;
-            client.drop_database(plan.clone()).await.map(|_| {
+            let store_db = self.remote_factory.store_db_name(&plan.db);
+            let mut store_plan = plan.clone();
+            store_plan.db = store_db.clone();
+            client.drop_database(store_plan).await.map(|_| {
                 self.databases.write().remove(plan.db.as_str());
+                self.remote_factory
+                    .meta_cache()
+                    .invalidate_database(&store_db);
             })?;
         };
 
         Ok(())
     }
+
+    /// Apply a DDL of a remote database executed by another query node of the cluster.
+    pub fn apply_catalog_change(&self, change: &CatalogChange) {
+        let meta_cache = self.remote_factory.meta_cache();
+        match change {
+            CatalogChange::CreateDatabase { db } => {
+                let mut databases = self.databases.write();
+                if !databases.contains_key(db) {
+                    let database = self.remote_factory.create_database(db.clone());
+                    databases.insert(db.clone(), Arc::new(database));
+                }
+                meta_cache.invalidate_database(&self.remote_factory.store_db_name(db));
+            }
+            CatalogChange::DropDatabase { db } => {
+                let mut databases = self.databases.write();
+                if matches!(databases.get(db), Some(database) if !database.is_local()) {
+                    databases.remove(db);
+                }
+                meta_cache.invalidate_database(&self.remote_factory.store_db_name(db));
+            }
+            CatalogChange::CreateTable { db, table } | CatalogChange::DropTable { db, table } => {
+                if let Some(database) = self.databases.read().get(db) {
+                    database.invalidate_table(table);
+                }
+                meta_cache.invalidate_table(&self.remote_factory.store_db_name(db), table);
+            }
+        }
+    }
 }
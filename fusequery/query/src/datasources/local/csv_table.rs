@@ -24,6 +24,7 @@ pub struct CsvTable {
     db: String,
     name: String,
     schema: DataSchemaRef,
+    comment: String,
     file: String,
     has_header: bool,
 }
@@ -49,6 +50,7 @@ impl CsvTable {
             db,
             name,
             schema,
+            comment: options.get("comment").cloned().unwrap_or_default(),
             file,
             has_header,
         }))
@@ -65,6 +67,10 @@ impl Table for CsvTable {
         "CSV"
     }
 
+    fn comment(&self) -> &str {
+        &self.comment
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -86,6 +92,11 @@ impl Table for CsvTable {
         let start_line: usize = if self.has_header { 1 } else { 0 };
         let file = &self.file;
         let lines_count = Common::count_lines(File::open(file.clone())?)?;
+        // The rows are estimated by the lines, the bytes by the size of the file.
+        let statistics = Statistics::new_estimated(
+            lines_count.saturating_sub(start_line),
+            std::fs::metadata(file)?.len() as usize,
+        );
 
         Ok(ReadDataSourcePlan {
             db: self.db.clone(),
@@ -96,7 +107,7 @@ impl Table for CsvTable {
                 ctx.get_settings().get_max_threads()?,
                 lines_count as u64,
             ),
-            statistics: Statistics::default(),
+            statistics,
             description: format!("(Read from CSV Engine table  {}.{})", self.db, self.name),
             scan_plan: Arc::new(scan.clone()),
             remote: false,
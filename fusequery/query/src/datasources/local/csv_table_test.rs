@@ -36,7 +36,7 @@ async fn test_csv_table() -> Result<()> {
     let scan_plan = &ScanPlan {
         schema_name: "".to_string(),
         table_schema: DataSchemaRefExt::create(vec![]),
-        table_args: None,
+        table_args: vec![],
         projected_schema: DataSchemaRefExt::create(vec![DataField::new(
             "column1",
             DataType::UInt64,
@@ -47,6 +47,8 @@ async fn test_csv_table() -> Result<()> {
     let partitions = ctx.get_settings().get_max_threads()? as usize;
     let source_plan = table.read_plan(ctx.clone(), &scan_plan, partitions)?;
     ctx.try_set_partitions(source_plan.parts.clone())?;
+    assert_eq!(source_plan.statistics.read_rows, 6);
+    assert!(!source_plan.statistics.is_exact);
 
     let stream = table.read(ctx, &source_plan).await?;
     let result = stream.try_collect::<Vec<_>>().await?;
@@ -108,7 +110,7 @@ async fn test_csv_table_parse_error() -> Result<()> {
     let scan_plan = &ScanPlan {
         schema_name: "".to_string(),
         table_schema: DataSchemaRefExt::create(vec![]),
-        table_args: None,
+        table_args: vec![],
         projected_schema: DataSchemaRefExt::create(vec![DataField::new(
             "column2",
             DataType::UInt64,
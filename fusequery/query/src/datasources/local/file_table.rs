@@ -0,0 +1,223 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::convert::TryInto;
+use std::fs;
+use std::fs::File;
+use std::sync::Arc;
+
+use common_arrow::arrow::csv;
+use common_arrow::arrow::datatypes::SchemaRef as ArrowSchemaRef;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::parquet::arrow::ArrowWriter;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::BlockStream;
+use common_planners::Expression;
+use common_planners::InsertIntoPlan;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_streams::SendableDataBlockStream;
+use futures::stream::StreamExt;
+
+use crate::datasources::Table;
+use crate::datasources::TableFunction;
+use crate::sessions::FuseQueryContextRef;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileFormat {
+    Parquet,
+    Csv,
+}
+
+impl FileFormat {
+    pub fn try_create(format: &str) -> Result<Self> {
+        match format.to_lowercase().as_str() {
+            "parquet" => Ok(FileFormat::Parquet),
+            "csv" => Ok(FileFormat::Csv),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unsupported file format: {}, expected Parquet or CSV",
+                format
+            ))),
+        }
+    }
+
+    /// The format of a file by its extension.
+    pub fn from_path(path: &str) -> Result<Self> {
+        let extension = path.rsplit('.').next().unwrap_or_default();
+        Self::try_create(extension).map_err(|_| {
+            ErrorCode::BadArguments(format!(
+                "Cannot infer the format of file {}, the format must be provided",
+                path
+            ))
+        })
+    }
+}
+
+/// The writable `file(path[, format])` table function, exports the rows of a query to a local file:
+///
+/// INSERT INTO FUNCTION file('/path/to/result.parquet', 'Parquet') SELECT ...
+///
+/// The rows are written to a temporary file which is renamed to `path` after the whole result is written,
+/// a failed query leaves no partial file behind.
+/// A CSV file is written without header, so that it can be read by a table of the CSV engine.
+pub struct FileTable {
+    schema: DataSchemaRef,
+}
+
+impl FileTable {
+    pub fn create() -> Self {
+        FileTable {
+            schema: Arc::new(DataSchema::empty()),
+        }
+    }
+
+    fn parse_args(args: &[Expression]) -> Result<(String, FileFormat)> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                Expression::Literal {
+                    value: DataValue::Utf8(Some(v)),
+                    ..
+                } => Ok(v.clone()),
+                other => Err(ErrorCode::BadArguments(format!(
+                    "Arguments of table function file must be string literals, but got {:?}",
+                    other
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match args.as_slice() {
+            [path] => Ok((path.clone(), FileFormat::from_path(path)?)),
+            [path, format] => Ok((path.clone(), FileFormat::try_create(format)?)),
+            _ => Err(ErrorCode::BadArguments(
+                "Table function file expects arguments (path[, format])",
+            )),
+        }
+    }
+
+    async fn write_file(
+        file: File,
+        format: FileFormat,
+        schema: ArrowSchemaRef,
+        mut stream: BlockStream,
+    ) -> Result<()> {
+        match format {
+            FileFormat::Parquet => {
+                let mut writer = ArrowWriter::try_new(file, schema, None)
+                    .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+                while let Some(block) = stream.next().await {
+                    let batch: RecordBatch = block?.try_into()?;
+                    writer
+                        .write(&batch)
+                        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+                }
+                writer
+                    .close()
+                    .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+            }
+            FileFormat::Csv => {
+                let mut writer = csv::WriterBuilder::new().has_headers(false).build(file);
+                while let Some(block) = stream.next().await {
+                    let batch: RecordBatch = block?.try_into()?;
+                    writer.write(&batch)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for FileTable {
+    fn name(&self) -> &str {
+        "file"
+    }
+
+    fn engine(&self) -> &str {
+        "File"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Err(ErrorCode::UnImplement(
+            "Table function file can only be written by INSERT INTO FUNCTION",
+        ))
+    }
+
+    async fn read(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::UnImplement(
+            "Table function file can only be written by INSERT INTO FUNCTION",
+        ))
+    }
+
+    async fn append_data(
+        &self,
+        _ctx: FuseQueryContextRef,
+        insert_plan: InsertIntoPlan,
+    ) -> Result<()> {
+        let stream = {
+            let mut inner = insert_plan.input_stream.lock();
+            (*inner).take()
+        }
+        .ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
+
+        let (path, format) = Self::parse_args(&insert_plan.table_args)?;
+        let tmp_path = format!("{}.tmp", path);
+        let file = File::create(&tmp_path).map_err(|e| {
+            ErrorCode::CannotReadFile(format!("Cannot create file {}: {}", tmp_path, e))
+        })?;
+
+        let schema = Arc::new(insert_plan.schema().to_arrow());
+        match Self::write_file(file, format, schema, stream).await {
+            Ok(()) => {
+                fs::rename(&tmp_path, &path)?;
+                Ok(())
+            }
+            Err(e) => {
+                let _ = fs::remove_file(&tmp_path);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl TableFunction for FileTable {
+    fn function_name(&self) -> &str {
+        "file"
+    }
+
+    fn db(&self) -> &str {
+        "local"
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::fs;
+
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::local::*;
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_file_table_function() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = env::temp_dir().join(format!("file_table_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir)?;
+
+    // CSV, the format is inferred from the extension.
+    {
+        let path = dir.join("result.csv").display().to_string();
+        let sql = format!(
+            "insert into function file('{}') select number, number * 2 from numbers(3)",
+            path
+        );
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        executor.execute().await?;
+
+        assert_eq!("0,0\n1,2\n2,4\n", fs::read_to_string(&path)?);
+        assert!(!dir.join("result.csv.tmp").exists());
+    }
+
+    // Parquet, read back by a table of the Parquet engine.
+    {
+        let path = dir.join("result").display().to_string();
+        let sql = format!(
+            "insert into function file('{}', 'Parquet') select number as a from numbers(3)",
+            path
+        );
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        executor.execute().await?;
+
+        let options: TableOptions = [("location".to_string(), path)].iter().cloned().collect();
+        let table = ParquetTable::try_create(
+            "default".into(),
+            "result".into(),
+            DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]),
+            options,
+        )?;
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            &ScanPlan::empty(),
+            ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(ctx.clone(), &source_plan).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_blocks_sorted_eq(
+            vec![
+                "+---+", "| a |", "+---+", "| 0 |", "| 1 |", "| 2 |", "+---+",
+            ],
+            &result,
+        );
+    }
+
+    // The format must be known.
+    {
+        let path = dir.join("result.json").display().to_string();
+        let sql = format!(
+            "insert into function file('{}') select number from numbers(3)",
+            path
+        );
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let result = executor.execute().await;
+        assert!(result.is_err());
+        assert!(!dir.join("result.json").exists());
+    }
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -10,14 +10,11 @@ use common_exception::Result;
 use common_infallible::RwLock;
 use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
-use common_planners::TableEngineType;
 
-use crate::datasources::local::CsvTable;
-use crate::datasources::local::MemoryTable;
-use crate::datasources::local::NullTable;
-use crate::datasources::local::ParquetTable;
+use crate::datasources::local::FileTable;
 use crate::datasources::Database;
 use crate::datasources::Table;
+use crate::datasources::TableEngineRegistry;
 use crate::datasources::TableFunction;
 
 pub struct LocalDatabase {
@@ -59,7 +56,7 @@ impl Database for LocalDatabase {
     }
 
     fn get_table_functions(&self) -> Result<Vec<Arc<dyn TableFunction>>> {
-        Ok(vec![])
+        Ok(vec![Arc::new(FileTable::create())])
     }
 
     async fn create_table(&self, plan: CreateTablePlan) -> Result<()> {
@@ -77,26 +74,29 @@ impl Database for LocalDatabase {
             };
         }
 
-        let table = match &plan.engine {
-            TableEngineType::Parquet => {
-                ParquetTable::try_create(plan.db, plan.table, plan.schema, plan.options)?
-            }
-            TableEngineType::Csv => {
-                CsvTable::try_create(plan.db, plan.table, plan.schema, plan.options)?
-            }
-            TableEngineType::Null => {
-                NullTable::try_create(plan.db, plan.table, plan.schema, plan.options)?
-            }
-            TableEngineType::Memory => {
-                MemoryTable::try_create(plan.db, plan.table, plan.schema, plan.options)?
-            }
-            _ => {
-                return Result::Err(ErrorCode::UnImplement(format!(
-                    "Local database does not support '{:?}' table engine",
-                    plan.engine
-                )));
-            }
+        let engine = match plan.engine.is_empty() {
+            true => TableEngineRegistry::DEFAULT_LOCAL_ENGINE,
+            false => plan.engine.as_str(),
         };
+        let table = TableEngineRegistry::create_table(
+            engine,
+            plan.db,
+            plan.table,
+            plan.schema,
+            plan.options,
+        )?;
+        if clone.options.contains_key("partition_by") && table.partition_by().is_none() {
+            return Err(ErrorCode::UnImplement(format!(
+                "Table engine '{}' does not support PARTITION BY",
+                engine
+            )));
+        }
+        if clone.options.contains_key("cluster_by") && table.cluster_by().is_none() {
+            return Err(ErrorCode::UnImplement(format!(
+                "Table engine '{}' does not support CLUSTER BY",
+                engine
+            )));
+        }
 
         self.tables
             .write()
@@ -104,6 +104,19 @@ impl Database for LocalDatabase {
         Ok(())
     }
 
+    fn register_table(&self, table: Arc<dyn Table>) -> Result<()> {
+        let mut tables = self.tables.write();
+        if tables.contains_key(table.name()) {
+            return Err(ErrorCode::TableAlreadyExists(format!(
+                "Table: '{}' already exists.",
+                table.name()
+            )));
+        }
+
+        tables.insert(table.name().to_string(), table);
+        Ok(())
+    }
+
     async fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
         let table_name = plan.table.as_str();
         if self.tables.read().get(table_name).is_none() {
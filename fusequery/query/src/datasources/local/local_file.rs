@@ -0,0 +1,221 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::fs::File;
+use std::io;
+use std::io::Cursor;
+use std::path::Path;
+
+use common_arrow::parquet::errors::ParquetError;
+use common_arrow::parquet::file::reader::ChunkReader;
+use common_arrow::parquet::file::reader::Length;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// How the local tables read their files, set by the `local_io` config.
+///
+/// `io_uring` submits the reads of the chunks of a file to an io_uring of the reading thread at
+/// once, which keeps the disk busy on cold scans. It is only on Linux, the reads fall back to the
+/// std IO elsewhere, or if the kernel does not support io_uring.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LocalIo {
+    Std,
+    IoUring,
+}
+
+impl LocalIo {
+    pub fn try_create(name: &str) -> Result<LocalIo> {
+        match name.to_lowercase().as_str() {
+            "std" => Ok(LocalIo::Std),
+            "io_uring" => Ok(LocalIo::IoUring),
+            _ => Err(ErrorCode::BadArguments(format!(
+                "Unknown local_io {}, expects std or io_uring",
+                name
+            ))),
+        }
+    }
+}
+
+/// A local file of a table, it is read by ranges as the Parquet files, or as a whole as the
+/// segments of the Log tables.
+pub struct LocalFile {
+    file: File,
+    len: u64,
+    io: LocalIo,
+}
+
+impl LocalFile {
+    pub fn open(path: impl AsRef<Path>, io: LocalIo) -> Result<LocalFile> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            ErrorCode::CannotReadFile(format!("File: {}, err: {}", path.display(), e))
+        })?;
+        let len = file.metadata()?.len();
+        Ok(LocalFile { file, len, io })
+    }
+
+    /// Reads the bytes of the range, the range is cut at the end of the file.
+    pub fn read_at(&self, offset: u64, length: usize) -> Result<Vec<u8>> {
+        let length = length.min(self.len.saturating_sub(offset) as usize);
+        let mut buf = vec![0; length];
+        self.read_exact_at(offset, &mut buf)
+            .map_err(|e| ErrorCode::CannotReadFile(e.to_string()))?;
+        Ok(buf)
+    }
+
+    pub fn read_all(&self) -> Result<Vec<u8>> {
+        self.read_at(0, self.len as usize)
+    }
+
+    fn read_exact_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        #[cfg(target_os = "linux")]
+        if self.io == LocalIo::IoUring && uring::read_exact_at(&self.file, offset, buf)? {
+            return Ok(());
+        }
+
+        std_read_exact_at(&self.file, offset, buf)
+    }
+}
+
+impl Length for LocalFile {
+    fn len(&self) -> u64 {
+        self.len
+    }
+}
+
+impl ChunkReader for LocalFile {
+    type T = Cursor<Vec<u8>>;
+
+    fn get_read(
+        &self,
+        start: u64,
+        length: usize,
+    ) -> common_arrow::parquet::errors::Result<Self::T> {
+        self.read_at(start, length)
+            .map(Cursor::new)
+            .map_err(|e| ParquetError::General(e.message()))
+    }
+}
+
+#[cfg(unix)]
+fn std_read_exact_at(file: &File, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.read_exact_at(buf, offset)
+}
+
+#[cfg(not(unix))]
+fn std_read_exact_at(file: &File, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+    use std::io::Read;
+    use std::io::Seek;
+    use std::io::SeekFrom;
+
+    let mut file = file;
+    file.seek(SeekFrom::Start(offset))?;
+    file.read_exact(buf)
+}
+
+#[cfg(target_os = "linux")]
+mod uring {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::collections::VecDeque;
+    use std::fs::File;
+    use std::io;
+    use std::os::unix::io::AsRawFd;
+
+    use io_uring::opcode;
+    use io_uring::types;
+    use io_uring::IoUring;
+
+    const QUEUE_DEPTH: u32 = 32;
+    const CHUNK_SIZE: usize = 1 << 20;
+
+    thread_local! {
+        // The ring of the reading thread, None if the kernel does not support io_uring.
+        static RING: Option<RefCell<IoUring>> = IoUring::new(QUEUE_DEPTH).ok().map(RefCell::new);
+    }
+
+    /// Fills the buffer with the bytes of the file from the offset, the chunks of the buffer are
+    /// read at once. Returns false if io_uring is not supported.
+    pub fn read_exact_at(file: &File, offset: u64, buf: &mut [u8]) -> io::Result<bool> {
+        RING.with(|ring| match ring {
+            None => Ok(false),
+            Some(ring) => {
+                read_chunks(&mut ring.borrow_mut(), file, offset, buf)?;
+                Ok(true)
+            }
+        })
+    }
+
+    fn read_chunks(ring: &mut IoUring, file: &File, offset: u64, buf: &mut [u8]) -> io::Result<()> {
+        let fd = types::Fd(file.as_raw_fd());
+        // The (start, end) in the buffer of the chunks to read, a short read leaves the rest of
+        // its chunk to read again.
+        let mut pending = (0..buf.len())
+            .step_by(CHUNK_SIZE)
+            .map(|start| (start, buf.len().min(start + CHUNK_SIZE)))
+            .collect::<VecDeque<_>>();
+        let mut in_flight = HashMap::new();
+        let mut error = None;
+
+        // The buffer is written by the kernel until the reads in flight complete, so they are
+        // waited for even after an error.
+        while !in_flight.is_empty() || (error.is_none() && !pending.is_empty()) {
+            while error.is_none() && in_flight.len() < QUEUE_DEPTH as usize {
+                let (start, end) = match pending.pop_front() {
+                    None => break,
+                    Some(chunk) => chunk,
+                };
+                let entry = opcode::Read::new(
+                    fd,
+                    unsafe { buf.as_mut_ptr().add(start) },
+                    (end - start) as u32,
+                )
+                .offset((offset + start as u64) as i64)
+                .build()
+                .user_data(start as u64);
+
+                // The queue has room for the reads in flight and this one.
+                unsafe {
+                    ring.submission()
+                        .push(&entry)
+                        .map_err(|_| io::Error::new(io::ErrorKind::Other, "io_uring is full"))?;
+                }
+                in_flight.insert(start, end);
+            }
+
+            match ring.submit_and_wait(1) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => result?,
+            };
+            let completed = ring
+                .completion()
+                .map(|cqe| (cqe.user_data() as usize, cqe.result()))
+                .collect::<Vec<_>>();
+            for (start, result) in completed {
+                let end = in_flight.remove(&start).unwrap_or(start);
+                match result {
+                    result if result < 0 => {
+                        error.get_or_insert(io::Error::from_raw_os_error(-result));
+                    }
+                    0 => {
+                        error.get_or_insert(io::Error::new(
+                            io::ErrorKind::UnexpectedEof,
+                            "failed to fill whole buffer",
+                        ));
+                    }
+                    read if start + (read as usize) < end => {
+                        pending.push_back((start + read as usize, end))
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        match error {
+            None => Ok(()),
+            Some(error) => Err(error),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+
+use common_arrow::parquet::file::reader::ChunkReader;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::local::*;
+
+#[test]
+fn test_local_file() -> Result<()> {
+    // Larger than a chunk of the io_uring reads.
+    let data = (0..3_000_000_u32)
+        .map(|i| (i % 251) as u8)
+        .collect::<Vec<_>>();
+    let path = env::temp_dir().join(format!("local_file_{}", uuid::Uuid::new_v4()));
+    std::fs::write(&path, &data)?;
+
+    for io in &[LocalIo::Std, LocalIo::IoUring] {
+        let file = LocalFile::open(&path, *io)?;
+        assert_eq!(data, file.read_all()?, "{:?}", io);
+        assert_eq!(
+            &data[10..2_000_010],
+            &file.read_at(10, 2_000_000)?[..],
+            "{:?}",
+            io
+        );
+
+        // The ranges are cut at the end of the file.
+        assert_eq!(
+            &data[2_999_990..],
+            &file.read_at(2_999_990, 100)?[..],
+            "{:?}",
+            io
+        );
+        assert!(file.read_at(3_000_100, 100)?.is_empty(), "{:?}", io);
+
+        let mut reader = file.get_read(5, 10).unwrap();
+        let mut buf = vec![];
+        std::io::Read::read_to_end(&mut reader, &mut buf)?;
+        assert_eq!(&data[5..15], &buf[..], "{:?}", io);
+    }
+
+    assert_eq!(LocalIo::IoUring, LocalIo::try_create("IO_URING")?);
+    assert_eq!(
+        "Code: 6, displayText = Unknown local_io aio, expects std or io_uring.",
+        LocalIo::try_create("aio").unwrap_err().to_string()
+    );
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
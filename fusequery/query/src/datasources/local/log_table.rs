@@ -0,0 +1,395 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::convert::TryInto;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use common_arrow::arrow::ipc::writer::FileWriter;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_planners::DropPartitionPlan;
+use common_planners::InsertIntoPlan;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_planners::TableOptions;
+use common_planners::TruncateTablePlan;
+use common_streams::SendableDataBlockStream;
+use futures::stream::StreamExt;
+use indexmap::IndexMap;
+
+use super::LogTableStream;
+use crate::datasources::PartitionBy;
+use crate::datasources::Table;
+use crate::sessions::FuseQueryContextRef;
+
+const SEGMENT_SUFFIX: &str = ".log";
+const PARTITION_PREFIX: &str = "partition=";
+
+/// An append-only table for fast ingestion.
+///
+/// Every append is written as a new segment file in the table directory, an Arrow IPC file named
+/// `{sequence}-{rows}.log`. There is no sorting, index or other metadata, the rows of a segment are
+/// kept in the file name so that the statistics of the table are exact without reading the data.
+///
+/// The segments of a table with PARTITION BY are in the directory of each partition,
+/// `partition={id}` in the table directory, so that a partition is pruned or dropped as a whole.
+pub struct LogTable {
+    db: String,
+    name: String,
+    schema: DataSchemaRef,
+    comment: String,
+    partition_by: Option<String>,
+    dir: PathBuf,
+    // The sequence of the next segment.
+    next_sequence: Mutex<u64>,
+}
+
+/// A segment file of a Log table.
+struct Segment {
+    sequence: u64,
+    rows: usize,
+    bytes: usize,
+    path: PathBuf,
+}
+
+impl LogTable {
+    pub fn try_create(
+        db: String,
+        name: String,
+        schema: DataSchemaRef,
+        options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        let dir = match options.get("location") {
+            None => {
+                return Result::Err(ErrorCode::BadOption(
+                    "Log Engine must contains directory location options",
+                ));
+            }
+            Some(v) => PathBuf::from(v.trim_matches(|s| s == '\'' || s == '"')),
+        };
+        fs::create_dir_all(&dir)?;
+
+        // The directory may contain the segments of a table created before, keep appending after them.
+        let mut next_sequence = 0;
+        let mut dirs = vec![dir.clone()];
+        dirs.extend(
+            Self::list_partitions(&dir)?
+                .into_iter()
+                .map(|(_, path)| path),
+        );
+        for dir in dirs {
+            if let Some(segment) = Self::list_segments(&dir)?.last() {
+                next_sequence = next_sequence.max(segment.sequence + 1);
+            }
+        }
+
+        Ok(Box::new(Self {
+            db,
+            name,
+            schema,
+            comment: options.get("comment").cloned().unwrap_or_default(),
+            partition_by: options.get("partition_by").cloned(),
+            dir,
+            next_sequence: Mutex::new(next_sequence),
+        }))
+    }
+
+    fn try_get_partition_by(&self, ctx: FuseQueryContextRef) -> Result<Option<PartitionBy>> {
+        match &self.partition_by {
+            None => Ok(None),
+            Some(partition_by) => Ok(Some(PartitionBy::try_create(
+                ctx,
+                partition_by,
+                &self.schema,
+            )?)),
+        }
+    }
+
+    fn partition_dir(&self, id: &str) -> PathBuf {
+        self.dir
+            .join(format!("{}{}", PARTITION_PREFIX, escape_partition_id(id)))
+    }
+
+    /// Lists the partition ids and directories.
+    fn list_partitions(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+        let mut partitions = vec![];
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if let Some(id) = file_name.strip_prefix(PARTITION_PREFIX) {
+                partitions.push((unescape_partition_id(id)?, entry.path()));
+            }
+        }
+        Ok(partitions)
+    }
+
+    /// Lists the segments in the order of appending.
+    fn list_segments(dir: &Path) -> Result<Vec<Segment>> {
+        let mut segments = vec![];
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            // Skip the files not written by the table, e.g. the temporary file of an unfinished append.
+            let stem = match file_name.strip_suffix(SEGMENT_SUFFIX) {
+                Some(stem) => stem,
+                None => continue,
+            };
+            let (sequence, rows) = match parse_segment_name(stem) {
+                Some(segment) => segment,
+                None => {
+                    log::warn!(
+                        "Skip the file {} of the Log table, it is not named as a segment",
+                        entry.path().display()
+                    );
+                    continue;
+                }
+            };
+            segments.push(Segment {
+                sequence,
+                rows,
+                bytes: entry.metadata()?.len() as usize,
+                path: entry.path(),
+            });
+        }
+        segments.sort_by_key(|segment| segment.sequence);
+        Ok(segments)
+    }
+
+    fn write_segment(&self, dir: &Path, batches: &[RecordBatch]) -> Result<()> {
+        let rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+        if rows == 0 {
+            return Ok(());
+        }
+
+        let mut next_sequence = self.next_sequence.lock();
+        let sequence = *next_sequence;
+
+        // Write to a temporary file first, a segment is visible only after it is completely written.
+        let tmp_path = dir.join(format!("{:020}.tmp", sequence));
+        {
+            let file = File::create(&tmp_path)?;
+            let mut writer = FileWriter::try_new(file, &self.schema.to_arrow())?;
+            for batch in batches {
+                writer.write(batch)?;
+            }
+            writer.finish()?;
+        }
+        let path = dir.join(format!("{:020}-{}{}", sequence, rows, SEGMENT_SUFFIX));
+        fs::rename(&tmp_path, &path)?;
+
+        *next_sequence += 1;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for LogTable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn engine(&self) -> &str {
+        "Log"
+    }
+
+    fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    fn partition_by(&self) -> Option<&str> {
+        self.partition_by.as_deref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        let segments = match self.try_get_partition_by(ctx)? {
+            None => Self::list_segments(&self.dir)?,
+            Some(partition_by) => {
+                // Skip the partitions that can not match the filters.
+                let pruner = partition_by.pruner(&scan.push_downs.filters)?;
+                let mut segments = vec![];
+                for (id, dir) in Self::list_partitions(&self.dir)? {
+                    if !pruner.can_skip(&partition_by.partition_value(&id)?)? {
+                        segments.extend(Self::list_segments(&dir)?);
+                    }
+                }
+                segments
+            }
+        };
+        let rows = segments.iter().map(|segment| segment.rows).sum();
+        let bytes = segments.iter().map(|segment| segment.bytes).sum();
+
+        // One part for each segment.
+        let parts = segments
+            .iter()
+            .map(|segment| Part {
+                name: segment.path.display().to_string(),
+                version: 0,
+                locations: vec![],
+            })
+            .collect();
+
+        Ok(ReadDataSourcePlan {
+            db: self.db.clone(),
+            table: self.name().to_string(),
+            schema: self.schema.clone(),
+            parts,
+            statistics: Statistics::new_exact(rows, bytes),
+            description: format!("(Read from Log Engine table  {}.{})", self.db, self.name),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        Ok(LogTableStream::try_create(ctx, self.schema.clone())?.into_stream())
+    }
+
+    async fn append_data(
+        &self,
+        ctx: FuseQueryContextRef,
+        insert_plan: InsertIntoPlan,
+    ) -> Result<()> {
+        let mut s = {
+            let mut inner = insert_plan.input_stream.lock();
+            (*inner).take()
+        }
+        .ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
+
+        if insert_plan.schema().as_ref() != self.schema.as_ref() {
+            return Err(ErrorCode::BadArguments("DataBlock schema mismatch"));
+        }
+
+        // All the blocks of one append go to one segment, or one segment for each partition.
+        let partition_by = self.try_get_partition_by(ctx)?;
+        let mut partitions: IndexMap<PathBuf, Vec<RecordBatch>> = IndexMap::new();
+        while let Some(block) = s.next().await {
+            let block = block?;
+            match &partition_by {
+                None => partitions
+                    .entry(self.dir.clone())
+                    .or_default()
+                    .push(block.try_into()?),
+                Some(partition_by) => {
+                    for (id, block) in partition_by.split_block(&block)? {
+                        partitions
+                            .entry(self.partition_dir(&id))
+                            .or_default()
+                            .push(block.try_into()?);
+                    }
+                }
+            }
+        }
+
+        for (dir, batches) in partitions {
+            fs::create_dir_all(&dir)?;
+            self.write_segment(&dir, &batches)?;
+        }
+        Ok(())
+    }
+
+    async fn truncate(&self, _ctx: FuseQueryContextRef, _plan: TruncateTablePlan) -> Result<()> {
+        // Hold the sequence lock so that no segment is written during the truncation.
+        let _next_sequence = self.next_sequence.lock();
+        for segment in Self::list_segments(&self.dir)? {
+            fs::remove_file(&segment.path)?;
+        }
+        for (_, dir) in Self::list_partitions(&self.dir)? {
+            fs::remove_dir_all(&dir)?;
+        }
+        Ok(())
+    }
+
+    async fn drop_partition(
+        &self,
+        ctx: FuseQueryContextRef,
+        plan: DropPartitionPlan,
+    ) -> Result<()> {
+        let partition_by = self.try_get_partition_by(ctx)?.ok_or_else(|| {
+            ErrorCode::BadArguments(format!(
+                "Table {}.{} is not partitioned",
+                self.db, self.name
+            ))
+        })?;
+
+        let _next_sequence = self.next_sequence.lock();
+        let dir = self.partition_dir(&partition_by.partition_id(&plan.partition)?);
+        if dir.exists() {
+            fs::remove_dir_all(&dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses the sequence and the rows of a segment from its file name without the suffix.
+fn parse_segment_name(stem: &str) -> Option<(u64, usize)> {
+    let (sequence, rows) = stem.split_once('-')?;
+    Some((sequence.parse().ok()?, rows.parse().ok()?))
+}
+
+/// Escapes a partition id to a directory name, the bytes other than alphanumerics, `-` and `_`
+/// are written as `%XX`.
+fn escape_partition_id(id: &str) -> String {
+    let mut escaped = String::with_capacity(id.len());
+    for byte in id.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => escaped.push(byte as char),
+            _ => escaped.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    escaped
+}
+
+fn unescape_partition_id(escaped: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(escaped.len());
+    let mut iter = escaped.bytes();
+    while let Some(byte) = iter.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let hex = [iter.next(), iter.next()];
+        let hex = match hex {
+            [Some(high), Some(low)] => String::from_utf8(vec![high, low])?,
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "Invalid partition directory name: {}",
+                    escaped
+                )))
+            }
+        };
+        bytes.push(u8::from_str_radix(&hex, 16)?);
+    }
+    Ok(String::from_utf8(bytes)?)
+}
@@ -0,0 +1,88 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::VecDeque;
+use std::convert::TryInto;
+use std::io::Cursor;
+
+use common_arrow::arrow::ipc::reader::FileReader;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_runtime::tokio::task;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::local::LocalFile;
+use crate::datasources::local::LocalIo;
+use crate::sessions::FuseQueryContextRef;
+
+/// Reads the segments of a Log table, a part is the path of a segment file.
+///
+/// The segments are read and decoded by the blocking threads of the runtime, so that the reads
+/// do not hold the async workers.
+pub struct LogTableStream {
+    ctx: FuseQueryContextRef,
+    schema: DataSchemaRef,
+    io: LocalIo,
+    // The blocks of the current segment not returned yet.
+    blocks: VecDeque<DataBlock>,
+    finished: bool,
+}
+
+impl LogTableStream {
+    pub fn try_create(ctx: FuseQueryContextRef, schema: DataSchemaRef) -> Result<Self> {
+        let io = LocalIo::try_create(&ctx.get_config().local_io)?;
+        Ok(LogTableStream {
+            ctx,
+            schema,
+            io,
+            blocks: VecDeque::new(),
+            finished: false,
+        })
+    }
+
+    pub async fn try_get_one_block(&mut self) -> Result<Option<DataBlock>> {
+        while self.blocks.is_empty() {
+            let partitions = self.ctx.try_get_partitions(1)?;
+            if partitions.is_empty() {
+                return Ok(None);
+            }
+
+            let path = partitions[0].name.clone();
+            let (schema, io) = (self.schema.clone(), self.io);
+            self.blocks = task::spawn_blocking(move || read_segment(&path, io, schema))
+                .await
+                .map_err(|e| ErrorCode::TokioError(e.to_string()))??;
+        }
+        Ok(self.blocks.pop_front())
+    }
+
+    pub fn into_stream(self) -> SendableDataBlockStream {
+        Box::pin(futures::stream::unfold(self, |mut stream| async move {
+            if stream.finished {
+                return None;
+            }
+            match stream.try_get_one_block().await {
+                Ok(None) => None,
+                Ok(Some(block)) => Some((Ok(block), stream)),
+                Err(error) => {
+                    stream.finished = true;
+                    Some((Err(error), stream))
+                }
+            }
+        }))
+    }
+}
+
+fn read_segment(path: &str, io: LocalIo, schema: DataSchemaRef) -> Result<VecDeque<DataBlock>> {
+    let segment = LocalFile::open(path, io)?.read_all()?;
+    let reader = FileReader::try_new(Cursor::new(segment))?;
+    let mut blocks = VecDeque::new();
+    for batch in reader {
+        let block: DataBlock = batch?.try_into()?;
+        blocks.push_back(DataBlock::create(schema.clone(), block.columns().to_vec()));
+    }
+    Ok(blocks)
+}
@@ -0,0 +1,165 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::fs;
+
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::datasources::local::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_logtable() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::Utf8, false),
+    ]);
+
+    let dir = env::temp_dir().join(format!("log_table_{}", uuid::Uuid::new_v4()));
+    let options: TableOptions = [("location".to_string(), dir.display().to_string())]
+        .iter()
+        .cloned()
+        .collect();
+
+    // Location is required.
+    let result = LogTable::try_create(
+        "default".into(),
+        "a".into(),
+        schema.clone(),
+        TableOptions::default(),
+    );
+    assert!(result.is_err());
+
+    let table = LogTable::try_create(
+        "default".into(),
+        "a".into(),
+        schema.clone(),
+        options.clone(),
+    )?;
+    assert_eq!(table.engine(), "Log");
+
+    // Two appends, each of which is a segment.
+    let appends = vec![
+        vec![
+            DataBlock::create_by_array(schema.clone(), vec![
+                Series::new(vec![1u64, 2]),
+                Series::new(vec!["x", "y"]),
+            ]),
+            DataBlock::create_by_array(schema.clone(), vec![
+                Series::new(vec![3u64]),
+                Series::new(vec!["z"]),
+            ]),
+        ],
+        vec![DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![4u64]),
+            Series::new(vec!["w"]),
+        ])],
+    ];
+    for blocks in appends {
+        let input_stream =
+            futures::stream::iter::<Vec<Result<DataBlock>>>(blocks.into_iter().map(Ok).collect());
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            schema: schema.clone(),
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+            select_plan: None,
+            table_function: None,
+            table_args: vec![],
+        };
+        table.append_data(ctx.clone(), insert_plan).await?;
+    }
+
+    // The files which are not named as segments are skipped.
+    fs::write(dir.join("access-2021.log"), "not a segment")?;
+
+    let expected = vec![
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | x |",
+        "| 2 | y |",
+        "| 3 | z |",
+        "| 4 | w |",
+        "+---+---+",
+    ];
+
+    let source_plan = table.read_plan(
+        ctx.clone(),
+        &ScanPlan::empty(),
+        ctx.get_settings().get_max_threads()? as usize,
+    )?;
+    assert_eq!(source_plan.parts.len(), 2);
+    assert_eq!(source_plan.statistics.read_rows, 4);
+    assert!(source_plan.statistics.is_exact);
+
+    ctx.try_set_partitions(source_plan.parts.clone())?;
+    let stream = table.read(ctx.clone(), &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert_blocks_sorted_eq(expected.clone(), &result);
+
+    // A table created on the same location reads the segments written before, and appends after them.
+    {
+        let table = LogTable::try_create(
+            "default".into(),
+            "b".into(),
+            schema.clone(),
+            options.clone(),
+        )?;
+        let input_stream =
+            futures::stream::iter::<Vec<Result<DataBlock>>>(vec![Ok(DataBlock::create_by_array(
+                schema.clone(),
+                vec![Series::new(vec![5u64]), Series::new(vec!["v"])],
+            ))]);
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "b".to_string(),
+            schema: schema.clone(),
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+            select_plan: None,
+            table_function: None,
+            table_args: vec![],
+        };
+        table.append_data(ctx.clone(), insert_plan).await?;
+
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            &ScanPlan::empty(),
+            ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        assert_eq!(source_plan.parts.len(), 3);
+        assert_eq!(source_plan.statistics.read_rows, 5);
+    }
+
+    // Truncate.
+    {
+        let truncate_plan = TruncateTablePlan {
+            db: "default".to_string(),
+            table: "a".to_string(),
+        };
+        table.truncate(ctx.clone(), truncate_plan).await?;
+
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            &ScanPlan::empty(),
+            ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        assert_eq!(source_plan.parts.len(), 0);
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(ctx, &source_plan).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_blocks_sorted_eq(vec!["++", "++"], &result);
+    }
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
@@ -14,6 +14,7 @@ use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
 use common_planners::TableOptions;
+use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
 use futures::stream::StreamExt;
 
@@ -26,6 +27,7 @@ pub struct MemoryTable {
     db: String,
     name: String,
     schema: DataSchemaRef,
+    comment: String,
     blocks: Arc<RwLock<Vec<DataBlock>>>,
 }
 
@@ -34,16 +36,33 @@ impl MemoryTable {
         db: String,
         name: String,
         schema: DataSchemaRef,
-        _options: TableOptions,
+        options: TableOptions,
     ) -> Result<Box<dyn Table>> {
         let table = Self {
             db,
             name,
             schema,
+            comment: options.get("comment").cloned().unwrap_or_default(),
             blocks: Arc::new(RwLock::new(vec![])),
         };
         Ok(Box::new(table))
     }
+
+    /// A memory table of the blocks, the blocks must be of the schema.
+    pub fn create_with_blocks(
+        db: String,
+        name: String,
+        schema: DataSchemaRef,
+        blocks: Vec<DataBlock>,
+    ) -> Self {
+        MemoryTable {
+            db,
+            name,
+            schema,
+            comment: String::new(),
+            blocks: Arc::new(RwLock::new(blocks)),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -56,6 +75,10 @@ impl Table for MemoryTable {
         "Memory"
     }
 
+    fn comment(&self) -> &str {
+        &self.comment
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -121,10 +144,19 @@ impl Table for MemoryTable {
             return Err(ErrorCode::BadArguments("DataBlock schema mismatch"));
         }
 
+        // The blocks are added at once, nothing is added if the input fails.
+        let mut appended = vec![];
         while let Some(block) = s.next().await {
-            let mut blocks = self.blocks.write();
-            blocks.push(block);
+            appended.push(block?);
         }
+        let mut blocks = self.blocks.write();
+        blocks.append(&mut appended);
+        Ok(())
+    }
+
+    async fn truncate(&self, _ctx: FuseQueryContextRef, _plan: TruncateTablePlan) -> Result<()> {
+        let mut blocks = self.blocks.write();
+        blocks.clear();
         Ok(())
     }
 }
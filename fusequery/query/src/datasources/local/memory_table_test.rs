@@ -5,6 +5,7 @@
 use common_datablocks::assert_blocks_sorted_eq;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use common_planners::*;
@@ -38,12 +39,17 @@ async fn test_memorytable() -> Result<()> {
     ]);
     let blocks = vec![block, block2];
 
-    let input_stream = futures::stream::iter::<Vec<DataBlock>>(blocks.clone());
+    let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(
+        blocks.clone().into_iter().map(Ok).collect(),
+    );
     let insert_plan = InsertIntoPlan {
         db_name: "default".to_string(),
         tbl_name: "a".to_string(),
         schema: schema.clone(),
         input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+        select_plan: None,
+        table_function: None,
+        table_args: vec![],
     };
     table.append_data(ctx.clone(), insert_plan).await.unwrap();
 
@@ -55,7 +61,7 @@ async fn test_memorytable() -> Result<()> {
     ctx.try_set_partitions(source_plan.parts.clone())?;
     assert_eq!(table.engine(), "Memory");
 
-    let stream = table.read(ctx, &source_plan).await?;
+    let stream = table.read(ctx.clone(), &source_plan).await?;
     let result = stream.try_collect::<Vec<_>>().await?;
     assert_blocks_sorted_eq(
         vec![
@@ -70,5 +76,52 @@ async fn test_memorytable() -> Result<()> {
         ],
         &result,
     );
+
+    // Truncate.
+    {
+        let truncate_plan = TruncateTablePlan {
+            db: "default".to_string(),
+            table: "a".to_string(),
+        };
+        table.truncate(ctx.clone(), truncate_plan).await?;
+
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            &ScanPlan::empty(),
+            ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(ctx.clone(), &source_plan).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_blocks_sorted_eq(vec!["++", "++"], &result);
+    }
+
+    // A failed input appends nothing.
+    {
+        let input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![
+            Ok(blocks[0].clone()),
+            Err(ErrorCode::BadArguments("input failed")),
+        ]);
+        let insert_plan = InsertIntoPlan {
+            db_name: "default".to_string(),
+            tbl_name: "a".to_string(),
+            schema: schema.clone(),
+            input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+            select_plan: None,
+            table_function: None,
+            table_args: vec![],
+        };
+        assert!(table.append_data(ctx.clone(), insert_plan).await.is_err());
+
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            &ScanPlan::empty(),
+            ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(ctx, &source_plan).await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert_blocks_sorted_eq(vec!["++", "++"], &result);
+    }
     Ok(())
 }
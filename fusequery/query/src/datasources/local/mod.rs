@@ -5,6 +5,12 @@
 #[cfg(test)]
 mod csv_table_test;
 #[cfg(test)]
+mod file_table_test;
+#[cfg(test)]
+mod local_file_test;
+#[cfg(test)]
+mod log_table_test;
+#[cfg(test)]
 mod memory_table_test;
 #[cfg(test)]
 mod null_table_test;
@@ -13,8 +19,12 @@ mod parquet_table_test;
 
 mod csv_table;
 mod csv_table_stream;
+mod file_table;
 mod local_database;
 mod local_factory;
+mod local_file;
+mod log_table;
+mod log_table_stream;
 mod memory_table;
 mod memory_table_stream;
 mod null_table;
@@ -22,8 +32,14 @@ mod parquet_table;
 
 pub use csv_table::CsvTable;
 pub use csv_table_stream::CsvTableStream;
+pub use file_table::FileFormat;
+pub use file_table::FileTable;
 pub use local_database::LocalDatabase;
 pub use local_factory::LocalFactory;
+pub use local_file::LocalFile;
+pub use local_file::LocalIo;
+pub use log_table::LogTable;
+pub use log_table_stream::LogTableStream;
 pub use memory_table::MemoryTable;
 pub use memory_table_stream::MemoryTableStream;
 pub use null_table::NullTable;
@@ -26,6 +26,7 @@ pub struct NullTable {
     db: String,
     name: String,
     schema: DataSchemaRef,
+    comment: String,
 }
 
 impl NullTable {
@@ -33,9 +34,14 @@ impl NullTable {
         db: String,
         name: String,
         schema: DataSchemaRef,
-        _options: TableOptions,
+        options: TableOptions,
     ) -> Result<Box<dyn Table>> {
-        let table = Self { db, name, schema };
+        let table = Self {
+            db,
+            name,
+            schema,
+            comment: options.get("comment").cloned().unwrap_or_default(),
+        };
         Ok(Box::new(table))
     }
 }
@@ -50,6 +56,10 @@ impl Table for NullTable {
         "Null"
     }
 
+    fn comment(&self) -> &str {
+        &self.comment
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -75,6 +85,7 @@ impl Table for NullTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::new_exact(0, 0),
             description: format!("(Read from Null Engine table  {}.{})", self.db, self.name),
@@ -109,6 +120,7 @@ impl Table for NullTable {
         .ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
 
         while let Some(block) = s.next().await {
+            let block = block?;
             info!("Ignore one block rows: {}", block.num_rows())
         }
         Ok(())
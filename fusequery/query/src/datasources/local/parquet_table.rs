@@ -3,29 +3,40 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::fs::File;
 use std::sync::Arc;
 
 use common_arrow::parquet::arrow::ArrowReader;
 use common_arrow::parquet::arrow::ParquetFileArrowReader;
+use common_arrow::parquet::file::metadata::RowGroupMetaData;
+use common_arrow::parquet::file::reader::FileReader;
 use common_arrow::parquet::file::reader::SerializedFileReader;
+use common_arrow::parquet::file::statistics::Statistics as ParquetStatistics;
 use common_datablocks::DataBlock;
 use common_datavalues::DataSchemaRef;
+use common_datavalues::DataType;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Expression;
 use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
 use common_planners::TableOptions;
 use common_runtime::tokio::task;
+use common_store_api::ColumnStatistics;
 use common_streams::ParquetStream;
 use common_streams::SendableDataBlockStream;
 use crossbeam::channel::bounded;
 use crossbeam::channel::Receiver;
 use crossbeam::channel::Sender;
 
+use crate::datasources::local::LocalFile;
+use crate::datasources::local::LocalIo;
+use crate::datasources::RangePruner;
 use crate::datasources::Table;
 use crate::sessions::FuseQueryContextRef;
 
@@ -33,6 +44,7 @@ pub struct ParquetTable {
     db: String,
     name: String,
     schema: DataSchemaRef,
+    comment: String,
     file: String,
 }
 
@@ -50,6 +62,7 @@ impl ParquetTable {
                     db,
                     name,
                     schema,
+                    comment: options.get("comment").cloned().unwrap_or_default(),
                     file: file.trim_matches(|s| s == '\'' || s == '"').to_string(),
                 };
                 Ok(Box::new(table))
@@ -59,19 +72,124 @@ impl ParquetTable {
             )),
         };
     }
+
+    /// One part for each row group of the file which may match the filters, the name of the part
+    /// is the index of the row group. The rows are read from the metadata of the row groups, the
+    /// bytes are their uncompressed sizes in the file, so the statistics are estimated.
+    fn read_row_groups(&self, filters: &[Expression]) -> Result<(Vec<Part>, Statistics)> {
+        let file = File::open(&self.file).map_err(|e| ErrorCode::CannotReadFile(e.to_string()))?;
+        let file_reader =
+            SerializedFileReader::new(file).map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+
+        let pruner = RangePruner::create(filters);
+        let mut parts = vec![];
+        let (mut rows, mut bytes) = (0, 0);
+        for (index, row_group) in file_reader.metadata().row_groups().iter().enumerate() {
+            if !pruner.is_empty() && pruner.can_skip(&self.column_statistics(row_group)) {
+                continue;
+            }
+
+            parts.push(Part {
+                name: index.to_string(),
+                version: 0,
+                locations: vec![],
+            });
+            rows += row_group.num_rows() as usize;
+            bytes += row_group.total_byte_size() as usize;
+        }
+        Ok((parts, Statistics::new_estimated(rows, bytes)))
+    }
+
+    /// The minimum and the maximum of the columns in the row group, only for the columns whose
+    /// values are ordered in the file as in the table.
+    fn column_statistics(&self, row_group: &RowGroupMetaData) -> HashMap<String, ColumnStatistics> {
+        let mut col_stats = HashMap::new();
+        for column in row_group.columns() {
+            let field = match self.schema.field_with_name(column.column_descr().name()) {
+                Ok(field) => field,
+                Err(_) => continue,
+            };
+
+            let stats = match column.statistics() {
+                Some(stats) if stats.has_min_max_set() => stats,
+                _ => continue,
+            };
+            let (min, max) = match (field.data_type(), stats) {
+                (
+                    DataType::Int8 | DataType::Int16 | DataType::Int32,
+                    ParquetStatistics::Int32(s),
+                ) => (
+                    DataValue::Int32(Some(*s.min())),
+                    DataValue::Int32(Some(*s.max())),
+                ),
+                (DataType::Int64, ParquetStatistics::Int64(s)) => (
+                    DataValue::Int64(Some(*s.min())),
+                    DataValue::Int64(Some(*s.max())),
+                ),
+                (DataType::Float32, ParquetStatistics::Float(s)) => (
+                    DataValue::Float32(Some(*s.min())),
+                    DataValue::Float32(Some(*s.max())),
+                ),
+                (DataType::Float64, ParquetStatistics::Double(s)) => (
+                    DataValue::Float64(Some(*s.min())),
+                    DataValue::Float64(Some(*s.max())),
+                ),
+                (DataType::Utf8, ParquetStatistics::ByteArray(s)) => {
+                    match (s.min().as_utf8(), s.max().as_utf8()) {
+                        (Ok(min), Ok(max)) => (
+                            DataValue::Utf8(Some(min.to_string())),
+                            DataValue::Utf8(Some(max.to_string())),
+                        ),
+                        _ => continue,
+                    }
+                }
+                _ => continue,
+            };
+            col_stats.insert(field.name().clone(), ColumnStatistics { min, max });
+        }
+        col_stats
+    }
+}
+
+/// Reads the row groups of the parts taken from the context until there is none left, the
+/// sources of the table share the row groups of the plan.
+fn read_row_groups(
+    ctx: FuseQueryContextRef,
+    file: &str,
+    tx: Sender<Option<Result<DataBlock>>>,
+    projection: &[usize],
+) -> Result<()> {
+    let io = LocalIo::try_create(&ctx.get_config().local_io)?;
+    loop {
+        let partitions = ctx.try_get_partitions(1)?;
+        if partitions.is_empty() {
+            return Ok(());
+        }
+
+        let index = partitions[0].name.parse::<usize>().map_err(|e| {
+            ErrorCode::BadBytes(format!(
+                "Invalid row group {} of Parquet file {}: {}",
+                partitions[0].name, file, e
+            ))
+        })?;
+        read_row_group(file, io, index, tx.clone(), projection)?;
+    }
 }
 
-fn read_file(
+/// Reads the row group of the index, the column chunks of the row group are read by the local IO.
+fn read_row_group(
     file: &str,
+    io: LocalIo,
+    index: usize,
     tx: Sender<Option<Result<DataBlock>>>,
     projection: &[usize],
 ) -> Result<()> {
-    let file_reader = File::open(file).map_err(|e| ErrorCode::CannotReadFile(e.to_string()))?;
-    let file_reader = SerializedFileReader::new(file_reader)
+    let mut file_reader = SerializedFileReader::new(LocalFile::open(file, io)?)
         .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    file_reader.filter_row_groups(&|_, i| i == index);
     let mut arrow_reader = ParquetFileArrowReader::new(Arc::new(file_reader));
 
-    // TODO projection, row filters, batch size configurable, schema judgement
+    // TODO row filters, batch size configurable, schema judgement
     let batch_size = 2048;
     let mut batch_reader = arrow_reader
         .get_record_reader_by_columns(projection.to_owned(), batch_size)
@@ -111,6 +229,14 @@ impl Table for ParquetTable {
         "Parquet"
     }
 
+    fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    fn prunes_by_filters(&self) -> bool {
+        true
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -129,15 +255,14 @@ impl Table for ParquetTable {
         scan: &ScanPlan,
         _partitions: usize,
     ) -> Result<ReadDataSourcePlan> {
+        // Skip the row groups that can not match the filters.
+        let (parts, statistics) = self.read_row_groups(&scan.push_downs.filters)?;
         Ok(ReadDataSourcePlan {
             db: self.db.clone(),
             table: self.name().to_string(),
             schema: self.schema.clone(),
-            parts: vec![Part {
-                name: "".to_string(),
-                version: 0,
-            }],
-            statistics: Statistics::default(),
+            parts,
+            statistics,
             description: format!(
                 "(Read from Parquet Engine table  {}.{})",
                 self.db, self.name
@@ -149,18 +274,25 @@ impl Table for ParquetTable {
 
     async fn read(
         &self,
-        _ctx: FuseQueryContextRef,
-        _source_plan: &ReadDataSourcePlan,
+        ctx: FuseQueryContextRef,
+        source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
         type BlockSender = Sender<Option<Result<DataBlock>>>;
         type BlockReceiver = Receiver<Option<Result<DataBlock>>>;
 
         let (response_tx, response_rx): (BlockSender, BlockReceiver) = bounded(2);
 
+        // Only the columns of the plan are read, the columns of the file are in the order of the
+        // columns of the table.
         let file = self.file.clone();
-        let projection: Vec<usize> = (0..self.schema.fields().len()).collect();
+        let projection = source_plan
+            .schema
+            .fields()
+            .iter()
+            .map(|field| self.schema.index_of(field.name()))
+            .collect::<Result<Vec<_>>>()?;
         task::spawn_blocking(move || {
-            if let Err(e) = read_file(&file, response_tx, &projection) {
+            if let Err(e) = read_row_groups(ctx, &file, response_tx, &projection) {
                 println!("Parquet reader thread terminated due to error: {:?}", e);
             }
         });
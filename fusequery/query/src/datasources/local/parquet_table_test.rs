@@ -2,9 +2,16 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::convert::TryInto;
 use std::env;
+use std::fs::File;
+use std::sync::Arc;
 
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::parquet::arrow::ArrowWriter;
+use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::*;
 use common_runtime::tokio;
@@ -38,7 +45,10 @@ async fn test_parquet_table() -> Result<()> {
         &ScanPlan::empty(),
         ctx.get_settings().get_max_threads()? as usize,
     )?;
+    assert_eq!(source_plan.statistics.read_rows, 8);
+    assert!(!source_plan.statistics.is_exact);
 
+    ctx.try_set_partitions(source_plan.parts.clone())?;
     let stream = table.read(ctx, &source_plan).await?;
     let blocks = stream.try_collect::<Vec<_>>().await?;
     let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
@@ -46,3 +56,96 @@ async fn test_parquet_table() -> Result<()> {
     assert_eq!(rows, 8);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_parquet_table_row_groups() -> Result<()> {
+    // Each batch is written to a row group, of the ids [0, 10), [10, 20) and [20, 30).
+    let schema = DataSchemaRefExt::create(vec![DataField::new("id", DataType::Int64, false)]);
+    let path = env::temp_dir().join(format!("parquet_table_{}.parquet", uuid::Uuid::new_v4()));
+    let mut writer = ArrowWriter::try_new(File::create(&path)?, Arc::new(schema.to_arrow()), None)
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    for start in &[0_i64, 10, 20] {
+        let ids = (*start..*start + 10).collect::<Vec<_>>();
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(ids)]);
+        let batch: RecordBatch = block.try_into()?;
+        writer
+            .write(&batch)
+            .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+    }
+    writer
+        .close()
+        .map_err(|e| ErrorCode::ParquetError(e.to_string()))?;
+
+    let options: TableOptions = [("location".to_string(), path.display().to_string())]
+        .iter()
+        .cloned()
+        .collect();
+    let ctx = crate::tests::try_create_context()?;
+    let table = ParquetTable::try_create(
+        "default".into(),
+        "test_parquet".into(),
+        schema.clone(),
+        options,
+    )?;
+
+    struct Test {
+        name: &'static str,
+        filters: Vec<Expression>,
+        parts: Vec<&'static str>,
+        rows: usize,
+    }
+
+    let tests = vec![
+        Test {
+            name: "No filter",
+            filters: vec![],
+            parts: vec!["0", "1", "2"],
+            rows: 30,
+        },
+        Test {
+            name: "Row groups of id > 15",
+            filters: vec![col("id").gt(lit(15))],
+            parts: vec!["1", "2"],
+            rows: 20,
+        },
+        Test {
+            name: "Row groups of id >= 20 and id < 25",
+            filters: vec![col("id").gt_eq(lit(20)).and(col("id").lt(lit(25)))],
+            parts: vec!["2"],
+            rows: 10,
+        },
+        Test {
+            name: "Row groups of id = 100",
+            filters: vec![col("id").eq(lit(100))],
+            parts: vec![],
+            rows: 0,
+        },
+    ];
+
+    for t in tests {
+        let mut scan = ScanPlan::empty();
+        scan.push_downs.filters = t.filters;
+        let source_plan = table.read_plan(
+            ctx.clone(),
+            &scan,
+            ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        let parts = source_plan
+            .parts
+            .iter()
+            .map(|part| part.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(t.parts, parts, "{}", t.name);
+        assert_eq!(t.rows, source_plan.statistics.read_rows, "{}", t.name);
+
+        // Only the rows of the row groups are read, the filter is applied above the scan.
+        ctx.try_set_partitions(source_plan.parts.clone())?;
+        let stream = table.read(ctx.clone(), &source_plan).await?;
+        let blocks = stream.try_collect::<Vec<_>>().await?;
+        let rows: usize = blocks.iter().map(|block| block.num_rows()).sum();
+        assert_eq!(t.rows, rows, "{}", t.name);
+    }
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
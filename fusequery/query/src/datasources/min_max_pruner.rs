@@ -0,0 +1,99 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataValue;
+use common_datavalues::DataValueAggregateOperator;
+use common_planners::Expression;
+use common_store_api::DataPartInfo;
+
+/// Prunes parts with the MIN and MAX aggregates pushed down to a scan without filters.
+///
+/// The result of `max(a)` is at least the largest minimum of `a` in the parts, so a part whose
+/// maximum of `a` is less than that can not hold the result, likewise for `min(a)` with the
+/// smallest maximum. A part is read if it may hold the result of any of the aggregates, and a
+/// part without the statistics of the column is always read.
+pub struct MinMaxPruner {
+    aggregates: Vec<(DataValueAggregateOperator, String)>,
+}
+
+impl MinMaxPruner {
+    /// Returns None unless all the aggregates are MIN or MAX of a column.
+    pub fn try_create(aggregates: &[Expression]) -> Option<MinMaxPruner> {
+        if aggregates.is_empty() {
+            return None;
+        }
+
+        let mut columns = Vec::with_capacity(aggregates.len());
+        for aggregate in aggregates {
+            match aggregate {
+                Expression::AggregateFunction { op, args, .. } => {
+                    let op = match op.to_lowercase().as_str() {
+                        "min" => DataValueAggregateOperator::Min,
+                        "max" => DataValueAggregateOperator::Max,
+                        _ => return None,
+                    };
+                    match &args[..] {
+                        [Expression::Column(column)] => columns.push((op, column.clone())),
+                        _ => return None,
+                    }
+                }
+                _ => return None,
+            }
+        }
+        Some(MinMaxPruner {
+            aggregates: columns,
+        })
+    }
+
+    pub fn prune(&self, parts: Vec<DataPartInfo>) -> Vec<DataPartInfo> {
+        let mut can_hold = vec![false; parts.len()];
+        for (op, column) in &self.aggregates {
+            let bound = match Self::bound(op, column, &parts) {
+                Some(bound) => bound,
+                None => return parts,
+            };
+
+            for (part, can_hold) in parts.iter().zip(can_hold.iter_mut()) {
+                *can_hold |= match part.col_stats.get(column) {
+                    None => true,
+                    Some(stats) => {
+                        let value = match op {
+                            DataValueAggregateOperator::Max => &stats.max,
+                            _ => &stats.min,
+                        };
+                        // The value reaches the bound if the aggregate of both is the value.
+                        !matches!(
+                            DataValue::agg(op.clone(), value.clone(), bound.clone()),
+                            Ok(ref result) if result != value
+                        )
+                    }
+                };
+            }
+        }
+
+        parts
+            .into_iter()
+            .zip(can_hold)
+            .filter_map(|(part, can_hold)| if can_hold { Some(part) } else { None })
+            .collect()
+    }
+
+    /// The value which the result of the aggregate reaches: the largest minimum of the parts for
+    /// MAX, the smallest maximum of the parts for MIN. None if the values are not comparable.
+    fn bound(
+        op: &DataValueAggregateOperator,
+        column: &str,
+        parts: &[DataPartInfo],
+    ) -> Option<DataValue> {
+        let mut bound = DataValue::Null;
+        for stats in parts.iter().filter_map(|part| part.col_stats.get(column)) {
+            let value = match op {
+                DataValueAggregateOperator::Max => stats.min.clone(),
+                _ => stats.max.clone(),
+            };
+            bound = DataValue::agg(op.clone(), bound, value).ok()?;
+        }
+        Some(bound)
+    }
+}
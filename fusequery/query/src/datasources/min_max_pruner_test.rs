@@ -0,0 +1,91 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::*;
+use common_store_api::ColumnStatistics;
+use common_store_api::DataPartInfo;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::*;
+
+fn aggregate(op: &str, column: &str) -> Expression {
+    Expression::AggregateFunction {
+        op: op.to_string(),
+        distinct: false,
+        args: vec![col(column)],
+    }
+}
+
+fn part(name: &str, min_max: Option<(i64, i64)>) -> DataPartInfo {
+    let mut col_stats = HashMap::new();
+    if let Some((min, max)) = min_max {
+        col_stats.insert("ts".to_string(), ColumnStatistics {
+            min: DataValue::Int64(Some(min)),
+            max: DataValue::Int64(Some(max)),
+        });
+    }
+    DataPartInfo {
+        part: Part {
+            name: name.to_string(),
+            version: 1,
+            locations: vec![],
+        },
+        stats: Statistics::new_exact(10, 80),
+        col_stats,
+    }
+}
+
+fn pruned_names(pruner: &MinMaxPruner, parts: &[DataPartInfo]) -> Vec<String> {
+    pruner
+        .prune(parts.to_vec())
+        .into_iter()
+        .map(|part| part.part.name)
+        .collect()
+}
+
+#[test]
+fn test_min_max_pruner() -> Result<()> {
+    let parts = vec![
+        part("p1", Some((0, 10))),
+        part("p2", Some((20, 30))),
+        part("p3", Some((5, 25))),
+        part("p4", Some((12, 18))),
+        part("p5", None),
+    ];
+
+    // The result of max(ts) is at least 20, which p1 and p4 can not reach.
+    let pruner = MinMaxPruner::try_create(&[aggregate("max", "ts")]).unwrap();
+    assert_eq!(pruned_names(&pruner, &parts), vec!["p2", "p3", "p5"]);
+
+    // The result of min(ts) is at most 10, which p2 and p4 can not reach.
+    let pruner = MinMaxPruner::try_create(&[aggregate("MIN", "ts")]).unwrap();
+    assert_eq!(pruned_names(&pruner, &parts), vec!["p1", "p3", "p5"]);
+
+    // A part is read if it may hold the result of any aggregate.
+    let pruner =
+        MinMaxPruner::try_create(&[aggregate("min", "ts"), aggregate("max", "ts")]).unwrap();
+    assert_eq!(pruned_names(&pruner, &parts), vec!["p1", "p2", "p3", "p5"]);
+
+    // The parts without the statistics of the column are all read.
+    let pruner = MinMaxPruner::try_create(&[aggregate("max", "value")]).unwrap();
+    assert_eq!(pruned_names(&pruner, &parts).len(), 5);
+
+    // Only MIN and MAX of columns prune the parts.
+    assert!(MinMaxPruner::try_create(&[]).is_none());
+    assert!(MinMaxPruner::try_create(&[aggregate("sum", "ts")]).is_none());
+    assert!(
+        MinMaxPruner::try_create(&[aggregate("max", "ts"), aggregate("count", "ts")]).is_none()
+    );
+    assert!(MinMaxPruner::try_create(&[Expression::AggregateFunction {
+        op: "max".to_string(),
+        distinct: false,
+        args: vec![add(col("ts"), lit(1i64))],
+    }])
+    .is_none());
+    Ok(())
+}
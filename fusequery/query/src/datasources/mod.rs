@@ -2,23 +2,50 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod cluster_by_test;
 #[cfg(test)]
 mod common_test;
 #[cfg(test)]
+mod min_max_pruner_test;
+#[cfg(test)]
+mod partition_by_test;
+#[cfg(test)]
+mod range_pruner_test;
+#[cfg(test)]
+mod table_engine_registry_test;
+#[cfg(test)]
 mod tests;
 
+mod catalog_change;
+mod cluster_by;
 mod common;
 mod database;
 mod datasource;
 mod local;
+mod min_max_pruner;
+mod partition_by;
+mod range_pruner;
 mod remote;
 mod system;
 mod table;
+mod table_engine_registry;
 mod table_function;
 
+pub use catalog_change::CatalogChange;
+pub use cluster_by::ClusterBy;
 pub use common::Common;
 pub use database::Database;
 pub use datasource::DataSource;
+pub use local::LocalIo;
+pub use local::MemoryTable;
+pub use min_max_pruner::MinMaxPruner;
+pub use partition_by::PartitionBy;
+pub use partition_by::PartitionPruner;
+pub use range_pruner::RangePruner;
 pub use table::Table;
 pub use table::TablePtr;
+pub use table_engine_registry::StoreTableCreator;
+pub use table_engine_registry::TableCreator;
+pub use table_engine_registry::TableEngineRegistry;
 pub use table_function::TableFunction;
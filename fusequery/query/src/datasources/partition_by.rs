@@ -0,0 +1,233 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::expr_as_column_expr;
+use common_planners::find_column_exprs;
+use common_planners::rebase_expr;
+use common_planners::Expression;
+use indexmap::IndexMap;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::FuseQueryContextRef;
+use crate::sql::DfParser;
+use crate::sql::PlanParser;
+
+/// The PARTITION BY expression of a table.
+///
+/// A partition is identified by the value of the expression in text, which is also the name of the
+/// partition in the storage, so the expression must be of an integer or string type.
+pub struct PartitionBy {
+    expr: Expression,
+    field: DataField,
+    executor: ExpressionExecutor,
+}
+
+impl PartitionBy {
+    pub fn try_create(
+        ctx: FuseQueryContextRef,
+        partition_by: &str,
+        schema: &DataSchemaRef,
+    ) -> Result<Self> {
+        let expr = DfParser::parse_expr(partition_by)?;
+        let expr = PlanParser::create(ctx).sql_to_rex(&expr, schema.as_ref(), None)?;
+        Self::check_expr(&expr)?;
+
+        let field = expr.to_data_field(schema)?;
+        let data_type = field.data_type();
+        if !is_integer(data_type) && data_type != &DataType::Utf8 {
+            return Err(ErrorCode::BadArguments(format!(
+                "PARTITION BY expression must be of an integer or string type, but got {}",
+                data_type
+            )));
+        }
+
+        let executor = ExpressionExecutor::try_create(
+            "partition by executor",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![field.clone()]),
+            vec![expr.clone()],
+            false,
+        )?;
+        Ok(PartitionBy {
+            expr,
+            field,
+            executor,
+        })
+    }
+
+    /// Splits a block by partition, returns the partition ids and the rows of each partition.
+    pub fn split_block(&self, block: &DataBlock) -> Result<Vec<(String, DataBlock)>> {
+        let column = self.executor.execute(block)?.column(0).clone();
+        let ids = column.cast_with_type(&DataType::Utf8)?.to_array()?;
+        let ids = ids.utf8()?;
+        if ids.null_count() > 0 {
+            return Err(ErrorCode::BadDataValueType(
+                "PARTITION BY expression can not be NULL",
+            ));
+        }
+
+        let mut partitions: IndexMap<&str, u64> = IndexMap::new();
+        let mut indices = Vec::with_capacity(block.num_rows());
+        for id in ids.into_no_null_iter() {
+            let next = partitions.len() as u64;
+            indices.push(*partitions.entry(id).or_insert(next));
+        }
+
+        let blocks = if partitions.len() == 1 {
+            vec![block.clone()]
+        } else {
+            let indices = DataColumn::Array(Series::new(indices));
+            DataBlock::scatter_block(block, &indices, partitions.len())?
+        };
+        Ok(partitions
+            .keys()
+            .map(|id| id.to_string())
+            .zip(blocks.into_iter())
+            .collect())
+    }
+
+    /// Returns the value of the PARTITION BY expression of a partition.
+    pub fn partition_value(&self, id: &str) -> Result<DataValue> {
+        DataColumn::Constant(DataValue::Utf8(Some(id.to_string())), 1)
+            .cast_with_type(self.field.data_type())?
+            .try_get(0)
+    }
+
+    /// Returns the id of the partition with the given value of the PARTITION BY expression.
+    pub fn partition_id(&self, value: &DataValue) -> Result<String> {
+        let id = DataColumn::Constant(value.clone(), 1)
+            .cast_with_type(self.field.data_type())?
+            .cast_with_type(&DataType::Utf8)?
+            .try_get(0)?;
+        match id {
+            DataValue::Utf8(Some(id)) => Ok(id),
+            _ => Err(ErrorCode::BadDataValueType(format!(
+                "Partition value {} can not be cast to {}",
+                value,
+                self.field.data_type()
+            ))),
+        }
+    }
+
+    /// Creates a pruner of partitions with the filters pushed down to a scan.
+    pub fn pruner(&self, filters: &[Expression]) -> Result<PartitionPruner> {
+        PartitionPruner::try_create(&self.expr, &self.field, filters)
+    }
+
+    // The expression is evaluated for every row on append, it can only be made of scalar functions.
+    fn check_expr(expr: &Expression) -> Result<()> {
+        match expr {
+            Expression::Column(_) | Expression::Literal { .. } => Ok(()),
+            Expression::Alias(_, expr)
+            | Expression::UnaryExpression { expr, .. }
+            | Expression::Cast { expr, .. } => Self::check_expr(expr),
+            Expression::BinaryExpression { left, right, .. } => {
+                Self::check_expr(left)?;
+                Self::check_expr(right)
+            }
+            Expression::ScalarFunction { args, .. } => args.iter().try_for_each(Self::check_expr),
+            other => Err(ErrorCode::SyntaxException(format!(
+                "Unsupported PARTITION BY expression: {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Prunes partitions with the filters pushed down to a scan.
+///
+/// A conjunct of the filters is used if it references the columns only through the PARTITION BY
+/// expression, e.g. `a % 10 = 1` for `PARTITION BY a % 10`. A partition is skipped if any such
+/// conjunct is false or NULL for the partition value.
+pub struct PartitionPruner {
+    input_schema: DataSchemaRef,
+    executor: Option<ExpressionExecutor>,
+}
+
+impl PartitionPruner {
+    fn try_create(
+        partition_by: &Expression,
+        partition_field: &DataField,
+        filters: &[Expression],
+    ) -> Result<Self> {
+        let partition_column = expr_as_column_expr(partition_by)?;
+        // The predicates compare the partition values by the collation of the partition column.
+        let input_schema = DataSchemaRefExt::create(vec![DataField::new(
+            &partition_column.column_name(),
+            partition_field.data_type().clone(),
+            partition_field.is_nullable(),
+        )
+        .with_collation(partition_field.collation().cloned())]);
+
+        let mut conjuncts = vec![];
+        for filter in filters {
+            Self::split_conjuncts(filter, &mut conjuncts);
+        }
+
+        let mut predicates = vec![];
+        for conjunct in conjuncts {
+            if PartitionBy::check_expr(conjunct).is_err() {
+                continue;
+            }
+            let predicate = rebase_expr(conjunct, &[partition_by.clone()])?;
+            let columns = find_column_exprs(&[predicate.clone()]);
+            if columns.iter().all(|column| column == &partition_column)
+                && predicate.to_data_type(&input_schema).is_ok()
+            {
+                predicates.push(predicate);
+            }
+        }
+
+        let executor =
+            match predicates
+                .into_iter()
+                .reduce(|left, right| Expression::BinaryExpression {
+                    left: Box::new(left),
+                    op: "and".to_string(),
+                    right: Box::new(right),
+                }) {
+                None => None,
+                Some(predicate) => Some(ExpressionExecutor::try_create(
+                    "partition pruner executor",
+                    input_schema.clone(),
+                    DataSchemaRefExt::create(vec![predicate.to_data_field(&input_schema)?]),
+                    vec![predicate],
+                    false,
+                )?),
+            };
+        Ok(PartitionPruner {
+            input_schema,
+            executor,
+        })
+    }
+
+    /// Returns true if no row of the partition can match the filters.
+    pub fn can_skip(&self, partition: &DataValue) -> Result<bool> {
+        let executor = match &self.executor {
+            None => return Ok(false),
+            Some(executor) => executor,
+        };
+
+        let block = DataBlock::create(self.input_schema.clone(), vec![DataColumn::Constant(
+            partition.clone(),
+            1,
+        )]);
+        let matched = executor.execute(&block)?.column(0).try_get(0)?;
+        Ok(matched.is_null() || matched == DataValue::Boolean(Some(false)))
+    }
+
+    fn split_conjuncts<'a>(expr: &'a Expression, conjuncts: &mut Vec<&'a Expression>) {
+        match expr {
+            Expression::BinaryExpression { left, op, right } if op.to_lowercase() == "and" => {
+                Self::split_conjuncts(left, conjuncts);
+                Self::split_conjuncts(right, conjuncts);
+            }
+            other => conjuncts.push(other),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::*;
+use crate::sql::DfParser;
+use crate::sql::PlanParser;
+
+#[test]
+fn test_partition_by_split_block() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::Utf8, false),
+    ]);
+
+    // Only an integer or string expression is allowed.
+    let result = PartitionBy::try_create(ctx.clone(), "a / 2", &schema);
+    assert_eq!(
+        result.err().unwrap().message(),
+        "PARTITION BY expression must be of an integer or string type, but got Float64"
+    );
+    let result = PartitionBy::try_create(ctx.clone(), "sum(a)", &schema);
+    assert!(result.is_err());
+
+    let partition_by = PartitionBy::try_create(ctx, "a % 3", &schema)?;
+    let block = DataBlock::create_by_array(schema, vec![
+        Series::new(vec![1u64, 2, 3, 4, 5]),
+        Series::new(vec!["x", "y", "z", "w", "v"]),
+    ]);
+    let partitions = partition_by.split_block(&block)?;
+    let ids = partitions
+        .iter()
+        .map(|(id, _)| id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["1", "2", "0"]);
+    let rows = partitions
+        .iter()
+        .map(|(_, block)| block.num_rows())
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec![2, 2, 1]);
+    assert_eq!(
+        partitions[0].1.column(1).try_get(1)?,
+        DataValue::Utf8(Some("w".to_string()))
+    );
+
+    assert_eq!(
+        partition_by.partition_value("2")?,
+        DataValue::UInt8(Some(2))
+    );
+    assert_eq!(partition_by.partition_id(&DataValue::Int64(Some(2)))?, "2");
+
+    Ok(())
+}
+
+#[test]
+fn test_partition_pruner() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::Utf8, false),
+    ]);
+    let partition_by = PartitionBy::try_create(ctx.clone(), "a % 3", &schema)?;
+
+    struct Test {
+        name: &'static str,
+        filter: &'static str,
+        skipped: Vec<bool>,
+    }
+
+    let tests = vec![
+        Test {
+            name: "partition-expression",
+            filter: "a % 3 = 1",
+            skipped: vec![true, false, true],
+        },
+        Test {
+            name: "partition-expression-with-other-conjuncts",
+            filter: "b = 'x' and a % 3 > 0 and a % 3 < 2",
+            skipped: vec![true, false, true],
+        },
+        Test {
+            name: "column-outside-partition-expression",
+            filter: "a = 1",
+            skipped: vec![false, false, false],
+        },
+        Test {
+            name: "disjunction",
+            filter: "a % 3 = 1 or b = 'x'",
+            skipped: vec![false, false, false],
+        },
+    ];
+
+    let parser = PlanParser::create(ctx);
+    for test in tests {
+        let filter = parser.sql_to_rex(&DfParser::parse_expr(test.filter)?, &schema, None)?;
+        let pruner = partition_by.pruner(&[filter])?;
+        let skipped = ["0", "1", "2"]
+            .iter()
+            .map(|id| pruner.can_skip(&partition_by.partition_value(id)?))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(test.skipped, skipped, "{}", test.name);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use common_datavalues::prelude::*;
+use common_datavalues::DataValueAggregateOperator;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::RewriteHelper;
+use common_store_api::ColumnStatistics;
+
+/// Prunes parts with the filters pushed down to a scan and the minimum and the maximum of the
+/// columns in the parts.
+///
+/// A conjunct of the filters is used if it compares a column with a literal by `=`, `<`, `<=`,
+/// `>` or `>=`, e.g. `a > 10` or `10 < a`. A part is skipped if any such conjunct is false for
+/// all the values between the minimum and the maximum of the column. A conjunct is ignored for
+/// the parts without the statistics of its column, or whose statistics are not comparable with
+/// the literal.
+pub struct RangePruner {
+    // The column on the left of the comparison, the operator and the literal on the right.
+    conjuncts: Vec<(String, String, DataValue)>,
+}
+
+impl RangePruner {
+    pub fn create(filters: &[Expression]) -> RangePruner {
+        let conjuncts = filters
+            .iter()
+            .flat_map(RewriteHelper::split_conjunctions)
+            .filter_map(|conjunct| Self::comparison(&conjunct))
+            .collect();
+        RangePruner { conjuncts }
+    }
+
+    /// Returns true if no conjunct can prune the parts.
+    pub fn is_empty(&self) -> bool {
+        self.conjuncts.is_empty()
+    }
+
+    /// Returns true if no row of the part with the statistics can match the filters.
+    pub fn can_skip(&self, col_stats: &HashMap<String, ColumnStatistics>) -> bool {
+        self.conjuncts.iter().any(|(column, op, value)| {
+            let stats = match col_stats.get(column) {
+                None => return false,
+                Some(stats) => stats,
+            };
+
+            let min = Self::compare(&stats.min, value);
+            let max = Self::compare(&stats.max, value);
+            match (op.as_str(), min, max) {
+                ("=", Some(min), Some(max)) => min == Ordering::Greater || max == Ordering::Less,
+                ("<", Some(min), _) => min != Ordering::Less,
+                ("<=", Some(min), _) => min == Ordering::Greater,
+                (">", _, Some(max)) => max != Ordering::Greater,
+                (">=", _, Some(max)) => max == Ordering::Less,
+                _ => false,
+            }
+        })
+    }
+
+    fn comparison(conjunct: &Expression) -> Option<(String, String, DataValue)> {
+        let (left, op, right) = match conjunct {
+            Expression::BinaryExpression { left, op, right } => (left, op, right),
+            _ => return None,
+        };
+
+        match (left.as_ref(), right.as_ref()) {
+            (Expression::Column(column), Expression::Literal { value, .. }) => match op.as_str() {
+                "=" | "<" | "<=" | ">" | ">=" if !value.is_null() => {
+                    Some((column.clone(), op.clone(), value.clone()))
+                }
+                _ => None,
+            },
+            // The literal is moved to the right, `10 < a` is `a > 10`.
+            (Expression::Literal { value, .. }, Expression::Column(column)) => {
+                let op = match op.as_str() {
+                    "=" => "=",
+                    "<" => ">",
+                    "<=" => ">=",
+                    ">" => "<",
+                    ">=" => "<=",
+                    _ => return None,
+                };
+                match value.is_null() {
+                    true => None,
+                    false => Some((column.clone(), op.to_string(), value.clone())),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Compares the values in the common type of both, None if they are not comparable.
+    fn compare(lhs: &DataValue, rhs: &DataValue) -> Option<Ordering> {
+        let data_type = equal_coercion(&lhs.data_type(), &rhs.data_type()).ok()?;
+        let lhs = Self::cast(lhs, &data_type).ok()?;
+        let rhs = Self::cast(rhs, &data_type).ok()?;
+        if lhs.is_null() || rhs.is_null() {
+            return None;
+        }
+
+        if lhs == rhs {
+            return Some(Ordering::Equal);
+        }
+        match DataValue::agg(DataValueAggregateOperator::Min, lhs.clone(), rhs).ok()? {
+            min if min == lhs => Some(Ordering::Less),
+            _ => Some(Ordering::Greater),
+        }
+    }
+
+    fn cast(value: &DataValue, data_type: &DataType) -> Result<DataValue> {
+        DataColumn::Constant(value.clone(), 1)
+            .cast_with_type(data_type)?
+            .try_get(0)
+    }
+}
@@ -0,0 +1,98 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::*;
+use common_store_api::ColumnStatistics;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::*;
+
+fn stats(min: i64, max: i64) -> HashMap<String, ColumnStatistics> {
+    let mut col_stats = HashMap::new();
+    col_stats.insert("a".to_string(), ColumnStatistics {
+        min: DataValue::Int64(Some(min)),
+        max: DataValue::Int64(Some(max)),
+    });
+    col_stats
+}
+
+#[test]
+fn test_range_pruner() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        filters: Vec<Expression>,
+        // The parts of [0, 10] and [20, 30] which are skipped.
+        expect: (bool, bool),
+    }
+
+    let tests = vec![
+        Test {
+            name: "a = 5",
+            filters: vec![col("a").eq(lit(5))],
+            expect: (false, true),
+        },
+        Test {
+            name: "a > 10",
+            filters: vec![col("a").gt(lit(10))],
+            expect: (true, false),
+        },
+        Test {
+            name: "a >= 10",
+            filters: vec![col("a").gt_eq(lit(10))],
+            expect: (false, false),
+        },
+        Test {
+            name: "a < 20.5",
+            filters: vec![col("a").lt(lit(20.5))],
+            expect: (false, false),
+        },
+        Test {
+            name: "a <= 19",
+            filters: vec![col("a").lt_eq(lit(19))],
+            expect: (false, true),
+        },
+        Test {
+            name: "15 < a",
+            filters: vec![lit(15).lt(col("a"))],
+            expect: (true, false),
+        },
+        Test {
+            name: "a > 5 and a < 20",
+            filters: vec![col("a").gt(lit(5)).and(col("a").lt(lit(20)))],
+            expect: (false, true),
+        },
+        Test {
+            name: "a > 5 or a > 25",
+            filters: vec![col("a").gt(lit(5)).or(col("a").gt(lit(25)))],
+            expect: (false, false),
+        },
+        Test {
+            name: "b = 5",
+            filters: vec![col("b").eq(lit(5))],
+            expect: (false, false),
+        },
+        Test {
+            name: "a = 'x'",
+            filters: vec![col("a").eq(lit("x"))],
+            expect: (false, false),
+        },
+    ];
+
+    for t in tests {
+        let pruner = RangePruner::create(&t.filters);
+        let actual = (
+            pruner.can_skip(&stats(0, 10)),
+            pruner.can_skip(&stats(20, 30)),
+        );
+        assert_eq!(t.expect, actual, "{}", t.name);
+    }
+
+    assert!(RangePruner::create(&[]).is_empty());
+    assert!(RangePruner::create(&[col("a").not_eq(lit(5))]).is_empty());
+    Ok(())
+}
@@ -1,13 +1,19 @@
 // Copyright 2020-2021 The Datafuse Authors.
 //
 // SPDX-License-Identifier: Apache-2.0.
+
+#[cfg(test)]
+mod remote_meta_cache_test;
+
 mod remote_database;
 mod remote_factory;
+mod remote_meta_cache;
 mod remote_table;
 mod remote_table_do_read;
 mod store_client_provider;
 
 pub use remote_database::RemoteDatabase;
 pub use remote_factory::RemoteFactory;
+pub use remote_meta_cache::RemoteMetaCache;
 pub use remote_table::RemoteTable;
 pub use store_client_provider::StoreClientProvider;
@@ -12,23 +12,34 @@ use common_planners::CreateTablePlan;
 use common_planners::DropTablePlan;
 use common_store_api::MetaApi;
 
-use crate::datasources::remote::remote_table::RemoteTable;
 use crate::datasources::remote::store_client_provider::StoreClientProvider;
+use crate::datasources::remote::RemoteMetaCache;
 use crate::datasources::Database;
 use crate::datasources::Table;
+use crate::datasources::TableEngineRegistry;
 use crate::datasources::TableFunction;
 
 pub struct RemoteDatabase {
     name: String,
+    // The name of the database in the meta store.
+    store_name: String,
     store_client_provider: StoreClientProvider,
+    meta_cache: Arc<RemoteMetaCache>,
     tables: RwLock<HashMap<String, Arc<dyn Table>>>,
 }
 
 impl RemoteDatabase {
-    pub fn create(store_client_provider: StoreClientProvider, name: String) -> Self {
+    pub fn create(
+        store_client_provider: StoreClientProvider,
+        meta_cache: Arc<RemoteMetaCache>,
+        name: String,
+        store_name: String,
+    ) -> Self {
         RemoteDatabase {
             name,
+            store_name,
             store_client_provider,
+            meta_cache,
             tables: RwLock::new(HashMap::default()),
         }
     }
@@ -81,11 +92,23 @@ impl Database for RemoteDatabase {
             };
         }
 
-        // Call remote create.
-        let clone = plan.clone();
+        if plan.options.contains_key("partition_by") {
+            return Err(ErrorCode::UnImplement(
+                "PARTITION BY is not supported by remote tables",
+            ));
+        }
+
+        // The engine is checked before the table is created in the store, the store keeps the
+        // engine name which the table is built with when it is read again.
+        let mut clone = plan.clone();
+        clone.db = self.store_name.clone();
+        if clone.engine.is_empty() {
+            clone.engine = TableEngineRegistry::DEFAULT_STORE_ENGINE.to_string();
+        }
         let provider = self.store_client_provider.clone();
-        let table = RemoteTable::try_create(
-            plan.db,
+        let table = TableEngineRegistry::create_store_table(
+            &clone.engine,
+            self.store_name.clone(),
             plan.table,
             plan.schema,
             provider.clone(),
@@ -99,6 +122,12 @@ impl Database for RemoteDatabase {
         Ok(())
     }
 
+    fn invalidate_table(&self, table_name: &str) {
+        self.tables.write().remove(table_name);
+        self.meta_cache
+            .invalidate_table(&self.store_name, table_name);
+    }
+
     async fn drop_table(&self, plan: DropTablePlan) -> Result<()> {
         let table_name = plan.table.as_str();
         if self.tables.read().get(table_name).is_none() {
@@ -112,11 +141,15 @@ impl Database for RemoteDatabase {
             };
         }
 
-        // Call remote create.
+        // Call remote drop.
+        let mut store_plan = plan.clone();
+        store_plan.db = self.store_name.clone();
         let mut client = self.store_client_provider.try_get_client().await?;
-        client.drop_table(plan.clone()).await.map(|_| {
+        client.drop_table(store_plan).await.map(|_| {
             let mut tables = self.tables.write();
             tables.remove(table_name);
+            self.meta_cache
+                .invalidate_table(&self.store_name, table_name);
         })?;
         Ok(())
     }
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -12,31 +13,58 @@ use crate::configs::Config;
 use crate::datasources::remote::store_client_provider::StoreClientProvider;
 use crate::datasources::remote::store_client_provider::TryGetStoreClient;
 use crate::datasources::remote::RemoteDatabase;
+use crate::datasources::remote::RemoteMetaCache;
 use crate::datasources::Database;
 
 pub struct RemoteFactory {
     store_client_provider: StoreClientProvider,
+    meta_cache: Arc<RemoteMetaCache>,
+    tenant: Option<String>,
 }
 
 impl RemoteFactory {
-    pub fn new(conf: &Config) -> Self {
+    pub fn new(conf: &Config, tenant: Option<String>) -> Self {
         RemoteFactory {
             store_client_provider: Arc::new(ClientProvider::new(conf)),
+            meta_cache: Arc::new(RemoteMetaCache::create(Duration::from_millis(
+                conf.store_meta_check_interval_ms,
+            ))),
+            tenant,
         }
     }
 
     pub fn load_databases(&self) -> Result<Vec<Arc<dyn Database>>> {
         // Load databases from remote.
-        let databases: Vec<Arc<dyn Database>> = vec![Arc::new(RemoteDatabase::create(
-            self.store_client_provider.clone(),
-            "for_test".to_string(),
-        ))];
+        let databases: Vec<Arc<dyn Database>> =
+            vec![Arc::new(self.create_database("for_test".to_string()))];
         Ok(databases)
     }
 
+    /// The name of a database in the meta store, the databases of a tenant are namespaced by the tenant.
+    pub fn store_db_name(&self, db: &str) -> String {
+        match &self.tenant {
+            None => db.to_string(),
+            Some(tenant) => format!("{}/{}", tenant, db),
+        }
+    }
+
+    pub fn create_database(&self, db: String) -> RemoteDatabase {
+        let store_name = self.store_db_name(&db);
+        RemoteDatabase::create(
+            self.store_client_provider.clone(),
+            self.meta_cache.clone(),
+            db,
+            store_name,
+        )
+    }
+
     pub fn store_client_provider(&self) -> StoreClientProvider {
         self.store_client_provider.clone()
     }
+
+    pub fn meta_cache(&self) -> Arc<RemoteMetaCache> {
+        self.meta_cache.clone()
+    }
 }
 struct ClientProvider {
     conf: Config,
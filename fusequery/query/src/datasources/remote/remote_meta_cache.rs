@@ -0,0 +1,90 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_infallible::RwLock;
+
+use crate::datasources::Table;
+
+/// Cache of remote tables fetched from the meta service.
+///
+/// Every entry belongs to the catalog version `meta_ver` the cache has seen.
+/// Once the version reported by the store changes, all entries are dropped.
+/// The version is re-checked at most once every `check_interval`,
+/// thus a query does not need a remote meta call for every table it references.
+///
+/// The store has no API to watch the meta changes, so the version is polled instead of
+/// subscribed to: a table changed by another query node may be served from the cache for up
+/// to `check_interval`, the `store_meta_check_interval_ms` of the config.
+pub struct RemoteMetaCache {
+    check_interval: Duration,
+    inner: RwLock<CacheInner>,
+}
+
+#[derive(Default)]
+struct CacheInner {
+    meta_ver: Option<u64>,
+    checked_at: Option<Instant>,
+    tables: HashMap<(String, String), Arc<dyn Table>>,
+}
+
+impl RemoteMetaCache {
+    pub fn create(check_interval: Duration) -> Self {
+        RemoteMetaCache {
+            check_interval,
+            inner: Default::default(),
+        }
+    }
+
+    /// Returns true if the cached version has not been validated against the store in `check_interval`.
+    pub fn need_check(&self) -> bool {
+        match self.inner.read().checked_at {
+            None => true,
+            Some(t) => t.elapsed() >= self.check_interval,
+        }
+    }
+
+    /// Record the latest catalog version from the store.
+    /// All cached tables are dropped if it differs from the one seen last time.
+    pub fn validate(&self, meta_ver: u64) {
+        let mut inner = self.inner.write();
+        if inner.meta_ver != Some(meta_ver) {
+            inner.tables.clear();
+            inner.meta_ver = Some(meta_ver);
+        }
+        inner.checked_at = Some(Instant::now());
+    }
+
+    pub fn meta_version(&self) -> Option<u64> {
+        self.inner.read().meta_ver
+    }
+
+    pub fn get_table(&self, db_name: &str, table_name: &str) -> Option<Arc<dyn Table>> {
+        let key = (db_name.to_string(), table_name.to_string());
+        self.inner.read().tables.get(&key).cloned()
+    }
+
+    pub fn insert_table(&self, db_name: &str, table_name: &str, table: Arc<dyn Table>) {
+        let key = (db_name.to_string(), table_name.to_string());
+        self.inner.write().tables.insert(key, table);
+    }
+
+    /// Drop a cached table, e.g., when it is dropped by this query node.
+    pub fn invalidate_table(&self, db_name: &str, table_name: &str) {
+        let key = (db_name.to_string(), table_name.to_string());
+        self.inner.write().tables.remove(&key);
+    }
+
+    /// Drop all cached tables of a database.
+    pub fn invalidate_database(&self, db_name: &str) {
+        self.inner
+            .write()
+            .tables
+            .retain(|(db, _), _| db.as_str() != db_name);
+    }
+}
@@ -0,0 +1,64 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::TableOptions;
+
+use crate::datasources::local::NullTable;
+use crate::datasources::remote::RemoteMetaCache;
+use crate::datasources::Table;
+
+fn new_table(db: &str, name: &str) -> Result<Arc<dyn Table>> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+    let table = NullTable::try_create(db.into(), name.into(), schema, TableOptions::default())?;
+    Ok(Arc::from(table))
+}
+
+#[test]
+fn test_remote_meta_cache() -> Result<()> {
+    let cache = RemoteMetaCache::create(Duration::from_secs(3600));
+    assert!(cache.need_check());
+    assert_eq!(None, cache.meta_version());
+
+    cache.validate(1);
+    assert!(!cache.need_check());
+    assert_eq!(Some(1), cache.meta_version());
+
+    cache.insert_table("db1", "t1", new_table("db1", "t1")?);
+    cache.insert_table("db1", "t2", new_table("db1", "t2")?);
+    cache.insert_table("db2", "t1", new_table("db2", "t1")?);
+    assert!(cache.get_table("db1", "t1").is_some());
+
+    // Same version keeps the cached tables.
+    cache.validate(1);
+    assert!(cache.get_table("db1", "t1").is_some());
+
+    cache.invalidate_table("db1", "t1");
+    assert!(cache.get_table("db1", "t1").is_none());
+    assert!(cache.get_table("db1", "t2").is_some());
+
+    cache.invalidate_database("db1");
+    assert!(cache.get_table("db1", "t2").is_none());
+    assert!(cache.get_table("db2", "t1").is_some());
+
+    // A new version drops everything.
+    cache.validate(2);
+    assert!(cache.get_table("db2", "t1").is_none());
+    assert_eq!(Some(2), cache.meta_version());
+
+    Ok(())
+}
+
+#[test]
+fn test_remote_meta_cache_check_interval() -> Result<()> {
+    let cache = RemoteMetaCache::create(Duration::from_secs(0));
+    cache.validate(1);
+    assert!(cache.need_check());
+
+    Ok(())
+}
@@ -9,8 +9,10 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_flights::StoreClient;
+use common_infallible::Mutex;
+use common_planners::BlockStream;
 use common_planners::InsertIntoPlan;
-use common_planners::Part;
 use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
 use common_planners::Statistics;
@@ -18,8 +20,11 @@ use common_planners::TableOptions;
 use common_store_api::ReadPlanResult;
 use common_store_api::StorageApi;
 use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
 
 use crate::datasources::remote::StoreClientProvider;
+use crate::datasources::ClusterBy;
+use crate::datasources::MinMaxPruner;
 use crate::datasources::Table;
 use crate::sessions::FuseQueryContextRef;
 
@@ -27,23 +32,34 @@ use crate::sessions::FuseQueryContextRef;
 pub struct RemoteTable {
     pub(crate) db: String,
     pub(crate) name: String,
+    /// The engine name which the table is created with, persisted in the meta store.
+    pub(crate) engine: String,
     pub(crate) schema: DataSchemaRef,
+    pub(crate) comment: String,
     pub(crate) store_client_provider: StoreClientProvider,
+    /// Engine options of the table, persisted in the meta store.
+    pub(crate) options: TableOptions,
+    pub(crate) cluster_by: Option<ClusterBy>,
 }
 
 impl RemoteTable {
     pub fn try_create(
+        engine: String,
         db: String,
         name: String,
         schema: DataSchemaRef,
         store_client_provider: StoreClientProvider,
-        _options: TableOptions,
+        options: TableOptions,
     ) -> Result<Box<dyn Table>> {
         let table = Self {
             db,
             name,
+            engine,
             schema,
+            comment: options.get("comment").cloned().unwrap_or_default(),
             store_client_provider,
+            cluster_by: ClusterBy::try_create(&options)?,
+            options,
         };
         Ok(Box::new(table))
     }
@@ -56,7 +72,15 @@ impl Table for RemoteTable {
     }
 
     fn engine(&self) -> &str {
-        "remote"
+        &self.engine
+    }
+
+    fn comment(&self) -> &str {
+        &self.comment
+    }
+
+    fn cluster_by(&self) -> Option<&ClusterBy> {
+        self.cluster_by.as_ref()
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -71,6 +95,10 @@ impl Table for RemoteTable {
         false
     }
 
+    fn executes_store_plans(&self) -> bool {
+        true
+    }
+
     fn read_plan(
         &self,
         ctx: FuseQueryContextRef,
@@ -113,59 +141,157 @@ impl Table for RemoteTable {
         self.do_read(ctx, source_plan).await
     }
 
-    async fn append_data(&self, _ctx: FuseQueryContextRef, plan: InsertIntoPlan) -> Result<()> {
+    async fn append_data(&self, ctx: FuseQueryContextRef, plan: InsertIntoPlan) -> Result<()> {
         let opt_stream = {
             let mut inner = plan.input_stream.lock();
             (*inner).take()
         };
+        let block_stream =
+            opt_stream.ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
+
+        let mut client = self.store_client_provider.try_get_client().await?;
+        let deduplicate = ctx.get_settings().get_insert_deduplicate()? != 0;
 
+        // All the parts of an insert are staged in one transaction and become visible at once
+        // after the whole input is appended, readers never see the parts of a failed insert.
+        let tx_id = uuid::Uuid::new_v4().to_string();
+        match self
+            .append_in_tx(&mut client, &plan, &tx_id, deduplicate, block_stream)
+            .await
         {
-            let block_stream =
-                opt_stream.ok_or_else(|| ErrorCode::EmptyData("input stream consumed"))?;
-
-            let mut client = self.store_client_provider.try_get_client().await?;
-
-            client
-                .append_data(
-                    plan.db_name.clone(),
-                    plan.tbl_name.clone(),
-                    (&plan).schema().clone(),
-                    block_stream,
-                )
-                .await?;
-
-            //            let mut um = UserMgr::new(client);
-            //            let a = "test";
-            //            um.get_users(&vec![a]).await;
-            //            um.add_user("user", "pass", "salt").await;
-            //            um.drop_user("user", None).await;
-            //            um.update_user("user", None, None, None).await;
-            //            um.get_users(&vec!["user"]).await;
-            //            um.get_all_users().await;
+            Ok(()) => {
+                // The blocks are deduplicated against the blocks committed before this commit.
+                let res = client.commit_append(tx_id).await?;
+                if res.deduplicated_blocks > 0 {
+                    ctx.push_warning(format!(
+                        "Skipped {} inserted blocks of {}.{} which are the same as blocks inserted before",
+                        res.deduplicated_blocks, self.db, plan.tbl_name
+                    ));
+                }
+                Ok(())
+            }
+            Err(e) => {
+                if let Err(abort_err) = client.abort_append(tx_id.clone()).await {
+                    log::warn!("failed to abort append tx {}: {}", tx_id, abort_err);
+                }
+                Err(e)
+            }
         }
 
-        Ok(())
+        //            let mut um = UserMgr::new(client);
+        //            let a = "test";
+        //            um.get_users(&vec![a]).await;
+        //            um.add_user("user", "pass", "salt").await;
+        //            um.drop_user("user", None).await;
+        //            um.update_user("user", None, None, None).await;
+        //            um.get_users(&vec!["user"]).await;
+        //            um.get_all_users().await;
     }
 }
 
 impl RemoteTable {
+    async fn append_in_tx(
+        &self,
+        client: &mut StoreClient,
+        plan: &InsertIntoPlan,
+        tx_id: &str,
+        deduplicate: bool,
+        block_stream: BlockStream,
+    ) -> Result<()> {
+        match &self.cluster_by {
+            None => {
+                // The store only sees the end of the stream if the input fails,
+                // the error is kept aside to abort the transaction.
+                let input_error = Arc::new(Mutex::new(None));
+                let error_slot = input_error.clone();
+                let blocks = block_stream
+                    .take_while(move |item| {
+                        let ok = match item {
+                            Ok(_) => true,
+                            Err(e) => {
+                                *error_slot.lock() = Some(e.clone());
+                                false
+                            }
+                        };
+                        futures::future::ready(ok)
+                    })
+                    .filter_map(|item| futures::future::ready(item.ok()));
+
+                client
+                    .append_data(
+                        self.db.clone(),
+                        plan.tbl_name.clone(),
+                        plan.schema(),
+                        None,
+                        Some(tx_id.to_string()),
+                        deduplicate,
+                        Box::pin(blocks),
+                    )
+                    .await?;
+
+                let input_error = input_error.lock().take();
+                match input_error {
+                    None => Ok(()),
+                    Some(e) => Err(e),
+                }
+            }
+            Some(cluster_by) => {
+                // The rows of a bucket are appended together, so that every part is in one bucket.
+                let blocks = block_stream
+                    .collect::<Vec<_>>()
+                    .await
+                    .into_iter()
+                    .collect::<Result<Vec<_>>>()?;
+                let mut buckets = vec![vec![]; cluster_by.buckets as usize];
+                for block in blocks {
+                    for (bucket, block) in cluster_by.split_block(&block)? {
+                        buckets[bucket as usize].push(block);
+                    }
+                }
+
+                for (bucket, blocks) in buckets.into_iter().enumerate() {
+                    if blocks.is_empty() {
+                        continue;
+                    }
+                    client
+                        .append_data(
+                            self.db.clone(),
+                            plan.tbl_name.clone(),
+                            plan.schema(),
+                            Some(bucket as u64),
+                            Some(tx_id.to_string()),
+                            deduplicate,
+                            Box::pin(futures::stream::iter(blocks)),
+                        )
+                        .await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
     fn partitions_to_plan(&self, res: ReadPlanResult, scan_plan: ScanPlan) -> ReadDataSourcePlan {
         let mut partitions = vec![];
-        let mut statistics = Statistics {
-            read_rows: 0,
-            read_bytes: 0,
-            is_exact: false,
-        };
+        let mut statistics = Statistics::new_exact(0, 0);
+
+        // The parts are a snapshot of the table at one data version, the sum of their rows is
+        // exact as a scan of the snapshot. The parts of version 0 are appended before the store
+        // stamps the versions, their statistics are not trusted.
+        if let Some(mut parts) = res {
+            // Skip the parts which can not hold the results of the MIN/MAX aggregates, the
+            // statistics of the rest are no longer the statistics of the table.
+            let pruner = MinMaxPruner::try_create(&scan_plan.push_downs.aggregates);
+            if let Some(pruner) = pruner.filter(|_| scan_plan.push_downs.filters.is_empty()) {
+                let total = parts.len();
+                parts = pruner.prune(parts);
+                statistics.is_exact &= parts.len() == total;
+            }
 
-        if let Some(parts) = res {
             for part in parts {
-                partitions.push(Part {
-                    name: part.part.name,
-                    version: 0,
-                });
                 statistics.read_rows += part.stats.read_rows;
                 statistics.read_bytes += part.stats.read_bytes;
-                statistics.is_exact &= part.stats.is_exact;
+                statistics.is_exact &= part.stats.is_exact && part.part.version > 0;
+                partitions.push(part.part);
             }
         }
 
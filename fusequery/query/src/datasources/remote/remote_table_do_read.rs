@@ -2,10 +2,16 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::Extras;
 use common_planners::PlanNode;
 use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
 use common_store_api::ReadAction;
 use common_store_api::StorageApi;
 use common_streams::ProgressStream;
@@ -14,36 +20,47 @@ use futures::StreamExt;
 
 use crate::datasources::remote::remote_table::RemoteTable;
 use crate::sessions::FuseQueryContextRef;
+use crate::sql::DefaultValues;
 
 impl RemoteTable {
     #[inline]
     pub(super) async fn do_read(
         &self,
         ctx: FuseQueryContextRef,
-        _source_plan: &ReadDataSourcePlan,
+        source_plan: &ReadDataSourcePlan,
     ) -> Result<SendableDataBlockStream> {
         let client = self.store_client_provider.try_get_client().await?;
-        let schema = self.schema.clone();
-        let db = self.db.to_string();
-        let tbl = self.name.to_string();
+        // The store only sends the columns of the plan, which are pruned by the optimizers.
+        // With a store plan, the store executes the plan on the rows of every part and sends its
+        // results instead, see `Extras::store_plan`.
+        let push_downs = source_plan.get_push_downs();
+        let push_down = match &push_downs.store_plan {
+            None => PlanNode::ReadSource(self.store_read(
+                source_plan.schema.clone(),
+                &push_downs,
+                vec![],
+            )),
+            Some(store_plan) => self.store_plan(ctx.clone(), store_plan)?,
+        };
+        // The store sends the blocks of the size the query prefers, and at most `window` blocks
+        // ahead of the query, so a slow query does not make the store buffer the whole part.
+        let batch_size = ctx.get_settings().get_max_block_size()? as usize;
+        let window = ctx.get_settings().get_flight_read_window()? as usize;
         let progress_callback = ctx.progress_callback();
+        let default_values = DefaultValues::create(ctx.clone());
 
         let iter = std::iter::from_fn(move || match ctx.try_get_partitions(1) {
             Err(_) => None,
             Ok(parts) if parts.is_empty() => None,
             Ok(parts) => Some(ReadAction {
                 part: parts[0].clone(),
-                push_down: PlanNode::ReadSource(ReadDataSourcePlan {
-                    db: db.clone(),
-                    table: tbl.clone(),
-                    schema: schema.clone(),
-                    remote: true,
-                    ..ReadDataSourcePlan::empty()
-                }),
+                push_down: push_down.clone(),
+                batch_size: Some(batch_size),
+                window: Some(window),
             }),
         });
 
-        let schema = self.schema.clone();
+        let schema = source_plan.schema.clone();
         let parts = futures::stream::iter(iter);
         let streams = parts.then(move |parts| {
             let mut client = client.clone();
@@ -61,7 +78,78 @@ impl RemoteTable {
             }
         });
 
-        let stream = ProgressStream::try_create(Box::pin(streams.flatten()), progress_callback?)?;
+        // A partition written before some columns were added does not have them, the store fills
+        // them for the store plans.
+        let schema = source_plan.schema.clone();
+        let fills_default_values = push_downs.store_plan.is_none();
+        let blocks = streams
+            .flatten()
+            .map(move |block| match fills_default_values {
+                true => block.and_then(|block| default_values.fill(&block, &schema)),
+                false => block,
+            });
+
+        let stream = ProgressStream::try_create(Box::pin(blocks), progress_callback?)?;
         Ok(Box::pin(stream))
     }
+
+    // The read sent to the store, the store only reads the columns of the projection, by their
+    // indices in the table. It skips the rows which can not pass the filters, they are still
+    // applied by the filter above the scan.
+    fn store_read(
+        &self,
+        schema: DataSchemaRef,
+        push_downs: &Extras,
+        default_values: Vec<(String, DataValue)>,
+    ) -> ReadDataSourcePlan {
+        ReadDataSourcePlan {
+            db: self.db.clone(),
+            table: self.name.clone(),
+            schema,
+            scan_plan: Arc::new(ScanPlan {
+                push_downs: Extras {
+                    projection: push_downs.projection.clone(),
+                    filters: push_downs.filters.clone(),
+                    default_values,
+                    ..Extras::default()
+                },
+                ..ScanPlan::empty()
+            }),
+            remote: true,
+            ..ReadDataSourcePlan::empty()
+        }
+    }
+
+    // The store plan with the read at its bottom replaced by the read sent to the store. The
+    // default values of the columns are evaluated by the query, the store fills the columns
+    // which a part does not have with them.
+    fn store_plan(&self, ctx: FuseQueryContextRef, plan: &PlanNode) -> Result<PlanNode> {
+        match plan {
+            PlanNode::ReadSource(read) => {
+                let default_values = DefaultValues::create(ctx);
+                let values = read
+                    .schema
+                    .fields()
+                    .iter()
+                    .filter_map(|field| {
+                        let value = default_values
+                            .default_column(field, 1)
+                            .and_then(|column| column.try_get(0));
+                        value.ok().map(|value| (field.name().clone(), value))
+                    })
+                    .collect();
+                Ok(PlanNode::ReadSource(self.store_read(
+                    read.schema.clone(),
+                    &read.get_push_downs(),
+                    values,
+                )))
+            }
+            _ => {
+                let input = self.store_plan(ctx, plan.input(0).as_ref())?;
+                let mut plan = plan.clone();
+                plan.set_inputs(vec![&input])?;
+                Ok(plan)
+            }
+        }
+    }
 }
@@ -70,6 +70,7 @@ impl Table for ClustersTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.clusters table)".to_string(),
@@ -65,6 +65,7 @@ impl Table for ContributorsTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.contributors table)".to_string(),
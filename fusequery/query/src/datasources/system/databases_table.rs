@@ -65,6 +65,7 @@ impl Table for DatabasesTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.databases table)".to_string(),
@@ -0,0 +1,210 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
+use common_arrow::arrow_flight::utils::flight_data_to_arrow_batch;
+use common_arrow::arrow_flight::FlightData;
+use common_arrow::arrow_flight::Ticket;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_flights::ConnectionFactory;
+use common_planners::Expression;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_runtime::tokio::time::Duration;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+use tonic::Request;
+use tonic::Streaming;
+
+use crate::datasources::Table;
+use crate::datasources::TableFunction;
+use crate::sessions::FuseQueryContextRef;
+
+/// The `flight('host:port', ticket)` table function, reads the stream of the ticket from any
+/// Arrow Flight endpoint:
+///
+/// SELECT * FROM flight('127.0.0.1:8815', 'ticket')
+///
+/// The schema is the schema message which starts the stream of DoGet, so the stream of the ticket
+/// is requested once more when the query is planned. Dictionary encoded columns are not supported.
+pub struct FlightTable {
+    schema: DataSchemaRef,
+}
+
+impl FlightTable {
+    pub fn create() -> Self {
+        FlightTable {
+            schema: Arc::new(DataSchema::empty()),
+        }
+    }
+
+    /// Returns the (address, ticket) of the arguments.
+    fn parse_args(args: &[Expression]) -> Result<(String, String)> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                Expression::Literal {
+                    value: DataValue::Utf8(Some(v)),
+                    ..
+                } => Ok(v.clone()),
+                other => Err(ErrorCode::BadArguments(format!(
+                    "Arguments of table function flight must be string literals, but got {:?}",
+                    other
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match args.as_slice() {
+            [address, ticket] => Ok((address.clone(), ticket.clone())),
+            _ => Err(ErrorCode::BadArguments(
+                "Table function flight expects arguments ('host:port', ticket)",
+            )),
+        }
+    }
+
+    async fn do_get(address: &str, ticket: &str, timeout: u64) -> Result<Streaming<FlightData>> {
+        let channel = ConnectionFactory::create_flight_channel(address, None).await?;
+        let mut client = FlightServiceClient::new(channel);
+
+        let mut request = Request::new(Ticket {
+            ticket: ticket.as_bytes().to_vec(),
+        });
+        request.set_timeout(Duration::from_secs(timeout));
+        Ok(client.do_get(request).await?.into_inner())
+    }
+
+    async fn fetch_schema(address: &str, ticket: &str, timeout: u64) -> Result<DataSchemaRef> {
+        let mut stream = Self::do_get(address, ticket, timeout).await?;
+        match stream.message().await? {
+            Some(flight_data) => {
+                let arrow_schema = ArrowSchema::try_from(&flight_data).map_err(|e| {
+                    ErrorCode::BadBytes(format!(
+                        "The stream of flight endpoint {} does not start with the schema: {}",
+                        address, e
+                    ))
+                })?;
+                Ok(Arc::new(DataSchema::from(arrow_schema)))
+            }
+            None => Err(ErrorCode::EmptyDataFromServer(format!(
+                "The stream of flight endpoint {} is empty",
+                address
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for FlightTable {
+    fn name(&self) -> &str {
+        "flight"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemFlight"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        let (address, ticket) = Self::parse_args(&scan.table_args)?;
+
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            schema: scan.table_schema.clone(),
+            parts: vec![Part {
+                name: format!("{}/{}", address, ticket),
+                version: 0,
+                locations: vec![],
+            }],
+            statistics: Statistics::default(),
+            description: format!("(Read from flight endpoint {})", address),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: FuseQueryContextRef,
+        source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let (address, ticket) = Self::parse_args(&source_plan.scan_plan.table_args)?;
+        let timeout = ctx.get_settings().get_flight_client_timeout()?;
+        let stream = Self::do_get(&address, &ticket, timeout).await?;
+
+        let arrow_schema = Arc::new(source_plan.schema.to_arrow());
+        Ok(Box::pin(stream.filter_map(move |flight_data| {
+            let block = match flight_data {
+                Err(status) => Some(Err(ErrorCode::from(status))),
+                // The schema message, which has no body.
+                Ok(flight_data) if ArrowSchema::try_from(&flight_data).is_ok() => None,
+                Ok(flight_data) => Some(
+                    flight_data_to_arrow_batch(&flight_data, arrow_schema.clone(), &[])
+                        .map_err(ErrorCode::from)
+                        .and_then(DataBlock::try_from),
+                ),
+            };
+            futures::future::ready(block)
+        })))
+    }
+}
+
+impl TableFunction for FlightTable {
+    fn function_name(&self) -> &str {
+        "flight"
+    }
+
+    fn db(&self) -> &str {
+        "system"
+    }
+
+    fn schema_with_args(
+        &self,
+        ctx: FuseQueryContextRef,
+        args: &[Expression],
+    ) -> Result<DataSchemaRef> {
+        let (address, ticket) = Self::parse_args(args)?;
+        let timeout = ctx.get_settings().get_flight_client_timeout()?;
+
+        // The plan is built synchronously, the schema is fetched by the runtime of the query.
+        let fetch_schema =
+            ctx.execute_task(async move { Self::fetch_schema(&address, &ticket, timeout).await })?;
+
+        futures::executor::block_on(fetch_schema)
+            .map_err(|e| ErrorCode::TokioError(format!("Cannot fetch the flight schema: {}", e)))?
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
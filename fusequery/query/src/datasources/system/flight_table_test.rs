@@ -0,0 +1,58 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use common_planners::Expression;
+use common_runtime::tokio;
+
+use crate::datasources::system::*;
+use crate::datasources::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_flight_table_args() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let table = FlightTable::create();
+
+    let literal = |v: &str| Expression::create_literal(DataValue::Utf8(Some(v.to_string())));
+
+    struct Test {
+        name: &'static str,
+        args: Vec<Expression>,
+        error: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "no-args",
+            args: vec![],
+            error: "Code: 6, displayText = Table function flight expects arguments ('host:port', ticket).",
+        },
+        Test {
+            name: "no-ticket",
+            args: vec![literal("127.0.0.1:8815")],
+            error: "Code: 6, displayText = Table function flight expects arguments ('host:port', ticket).",
+        },
+        Test {
+            name: "not-string",
+            args: vec![
+                literal("127.0.0.1:8815"),
+                Expression::create_literal(DataValue::UInt64(Some(1))),
+            ],
+            error: "Code: 6, displayText = Arguments of table function flight must be string literals, but got 1.",
+        },
+    ];
+
+    for test in tests {
+        match table.schema_with_args(ctx.clone(), &test.args) {
+            Ok(_) => assert!(false, "{} must be failed", test.name),
+            Err(e) => assert_eq!(test.error, e.to_string(), "{}", test.name),
+        }
+    }
+
+    // Nothing listens on the endpoint.
+    let args = vec![literal("127.0.0.1:1"), literal("ticket")];
+    assert!(table.schema_with_args(ctx.clone(), &args).is_err());
+    Ok(())
+}
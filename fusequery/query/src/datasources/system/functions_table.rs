@@ -70,6 +70,7 @@ impl Table for FunctionsTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.functions table)".to_string(),
@@ -9,42 +9,66 @@ mod contributors_table_test;
 #[cfg(test)]
 mod databases_table_test;
 #[cfg(test)]
+mod flight_table_test;
+#[cfg(test)]
 mod functions_table_test;
 #[cfg(test)]
 mod numbers_table_test;
 #[cfg(test)]
+mod processes_table_test;
+#[cfg(test)]
+mod query_log_table_test;
+#[cfg(test)]
+mod query_queue_table_test;
+#[cfg(test)]
 mod settings_table_test;
 #[cfg(test)]
 mod tables_table_test;
 #[cfg(test)]
 mod tracing_table_test;
+#[cfg(test)]
+mod variables_table_test;
+#[cfg(test)]
+mod warnings_table_test;
 
 mod clusters_table;
 mod contributors_table;
 mod databases_table;
+mod flight_table;
 mod functions_table;
 mod numbers_stream;
 mod numbers_table;
 mod one_table;
 mod processes_table;
+mod query_log_table;
+mod query_queue_table;
+mod remote_cluster_table;
 mod settings_table;
 mod system_database;
 mod system_factory;
 mod tables_table;
 mod tracing_table;
 mod tracing_table_stream;
+mod variables_table;
+mod warnings_table;
 
 pub use clusters_table::ClustersTable;
 pub use contributors_table::ContributorsTable;
 pub use databases_table::DatabasesTable;
+pub use flight_table::FlightTable;
 pub use functions_table::FunctionsTable;
 pub use numbers_stream::NumbersStream;
 pub use numbers_table::NumbersTable;
 pub use one_table::OneTable;
 pub use processes_table::ProcessesTable;
+pub use query_log_table::QueryLogTable;
+pub use query_queue_table::QueryQueueTable;
+pub use remote_cluster_table::RemoteClusterTable;
 pub use settings_table::SettingsTable;
 pub use system_database::SystemDatabase;
 pub use system_factory::SystemFactory;
 pub use tables_table::TablesTable;
 pub use tracing_table::TracingTable;
 pub use tracing_table_stream::TracingTableStream;
+pub use variables_table::VariablesTable;
+pub use warnings_table::WarningsTable;
@@ -77,8 +77,7 @@ impl Table for NumbersTable {
         _partitions: usize,
     ) -> Result<ReadDataSourcePlan> {
         let mut total = None;
-        let ScanPlan { table_args, .. } = scan.clone();
-        if let Some(Expression::Literal { value, .. }) = table_args {
+        if let [Expression::Literal { value, .. }] = scan.table_args.as_slice() {
             total = Some(value.as_u64()?);
         }
 
@@ -92,7 +91,6 @@ impl Table for NumbersTable {
         let statistics =
             Statistics::new_exact(total as usize, ((total) * size_of::<u64>() as u64) as usize);
         ctx.try_set_statistics(&statistics)?;
-        ctx.add_total_rows_approx(statistics.read_rows);
 
         Ok(ReadDataSourcePlan {
             db: "system".to_string(),
@@ -33,10 +33,18 @@ impl ProcessesTable {
         ProcessesTable {
             schema: DataSchemaRefExt::create(vec![
                 DataField::new("id", DataType::Utf8, false),
+                DataField::new("query_id", DataType::Utf8, true),
+                DataField::new("user", DataType::Utf8, false),
                 DataField::new("host", DataType::Utf8, true),
                 DataField::new("state", DataType::Utf8, false),
                 DataField::new("database", DataType::Utf8, false),
                 DataField::new("extra_info", DataType::Utf8, true),
+                DataField::new("read_rows", DataType::UInt64, true),
+                DataField::new("read_bytes", DataType::UInt64, true),
+                DataField::new("total_rows_approx", DataType::UInt64, true),
+                DataField::new("elapsed", DataType::Float64, true),
+                DataField::new("percent", DataType::Float64, true),
+                DataField::new("estimated_remaining", DataType::Float64, true),
             ]),
         }
     }
@@ -50,6 +58,19 @@ impl ProcessesTable {
     fn process_extra_info(process_info: &ProcessInfo) -> Option<String> {
         process_info.session_extra_info.clone()
     }
+
+    /// Returns the (percent, estimated remaining seconds) of the running query.
+    fn process_estimation(process_info: &ProcessInfo) -> (Option<f64>, Option<f64>) {
+        match (&process_info.progress, process_info.elapsed) {
+            (Some(progress), Some(elapsed)) => (
+                progress.percent(),
+                progress
+                    .estimated_remaining(elapsed)
+                    .map(|remaining| remaining.as_secs_f64()),
+            ),
+            _ => (None, None),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -87,6 +108,7 @@ impl Table for ProcessesTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.processes table)".to_string(),
@@ -103,26 +125,55 @@ impl Table for ProcessesTable {
         let processes_info = ctx.processes_info();
 
         let mut processes_id = Vec::with_capacity(processes_info.len());
+        let mut processes_query_id = Vec::with_capacity(processes_info.len());
+        let mut processes_user = Vec::with_capacity(processes_info.len());
         let mut processes_host = Vec::with_capacity(processes_info.len());
         let mut processes_state = Vec::with_capacity(processes_info.len());
         let mut processes_database = Vec::with_capacity(processes_info.len());
         let mut processes_extra_info = Vec::with_capacity(processes_info.len());
+        let mut processes_read_rows = Vec::with_capacity(processes_info.len());
+        let mut processes_read_bytes = Vec::with_capacity(processes_info.len());
+        let mut processes_total_rows_approx = Vec::with_capacity(processes_info.len());
+        let mut processes_elapsed = Vec::with_capacity(processes_info.len());
+        let mut processes_percent = Vec::with_capacity(processes_info.len());
+        let mut processes_estimated_remaining = Vec::with_capacity(processes_info.len());
 
         for process_info in &processes_info {
             processes_id.push(process_info.id.clone());
+            processes_query_id.push(process_info.query_id.clone());
+            processes_user.push(process_info.user.clone());
             processes_state.push(process_info.state.clone());
             processes_database.push(process_info.database.clone());
             processes_host.push(ProcessesTable::process_host(process_info));
             processes_extra_info.push(ProcessesTable::process_extra_info(process_info));
+
+            let progress = process_info.progress.as_ref();
+            processes_read_rows.push(progress.map(|progress| progress.read_rows as u64));
+            processes_read_bytes.push(progress.map(|progress| progress.read_bytes as u64));
+            processes_total_rows_approx
+                .push(progress.map(|progress| progress.total_rows_to_read as u64));
+            processes_elapsed.push(process_info.elapsed.map(|elapsed| elapsed.as_secs_f64()));
+
+            let (percent, estimated_remaining) = ProcessesTable::process_estimation(process_info);
+            processes_percent.push(percent);
+            processes_estimated_remaining.push(estimated_remaining);
         }
 
         let schema = self.schema.clone();
         let block = DataBlock::create_by_array(schema.clone(), vec![
             Series::new(processes_id),
+            Series::new(processes_query_id),
+            Series::new(processes_user),
             Series::new(processes_host),
             Series::new(processes_state),
             Series::new(processes_database),
             Series::new(processes_extra_info),
+            Series::new(processes_read_rows),
+            Series::new(processes_read_bytes),
+            Series::new(processes_total_rows_approx),
+            Series::new(processes_elapsed),
+            Series::new(processes_percent),
+            Series::new(processes_estimated_remaining),
         ]);
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -0,0 +1,41 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::assert_blocks_eq;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_processes_table() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+
+    let running_session = sessions.create_user_session("TestSession", "user1")?;
+    let running = running_session.create_context();
+    running.set_id("query_a".to_string())?;
+    running.attach_query_info("SELECT 1");
+
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context();
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "select user, query_id, state, extra_info, read_rows, elapsed >= 0 from system.processes \
+         where query_id = 'query_a'",
+    )?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let stream = executor.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+-------+----------+-------+------------+-----------+----------------+",
+        "| user  | query_id | state | extra_info | read_rows | (elapsed >= 0) |",
+        "+-------+----------+-------+------------+-----------+----------------+",
+        "| user1 | query_a  | Query | SELECT 1   | 0         | true           |",
+        "+-------+----------+-------+------------+-----------+----------------+",
+    ];
+    assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -0,0 +1,144 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::Table;
+use crate::sessions::FuseQueryContextRef;
+
+pub struct QueryLogTable {
+    schema: DataSchemaRef,
+}
+
+impl QueryLogTable {
+    pub fn create() -> Self {
+        QueryLogTable {
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("query_id", DataType::Utf8, false),
+                DataField::new("session_id", DataType::Utf8, false),
+                DataField::new("user", DataType::Utf8, false),
+                DataField::new("query", DataType::Utf8, false),
+                DataField::new("event_time", DataType::UInt64, false),
+                DataField::new("duration", DataType::Float64, false),
+                DataField::new("read_rows", DataType::UInt64, false),
+                DataField::new("read_bytes", DataType::UInt64, false),
+                DataField::new("result_rows", DataType::UInt64, false),
+                DataField::new("error_code", DataType::UInt16, false),
+                DataField::new("error_message", DataType::Utf8, true),
+            ]),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryLogTable {
+    fn name(&self) -> &str {
+        "query_log"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemQueryLog"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            schema: self.schema.clone(),
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+                locations: vec![],
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.query_log table)".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let logs = ctx.query_logs();
+
+        let mut query_ids = Vec::with_capacity(logs.len());
+        let mut session_ids = Vec::with_capacity(logs.len());
+        let mut users = Vec::with_capacity(logs.len());
+        let mut queries = Vec::with_capacity(logs.len());
+        let mut event_times = Vec::with_capacity(logs.len());
+        let mut durations = Vec::with_capacity(logs.len());
+        let mut read_rows = Vec::with_capacity(logs.len());
+        let mut read_bytes = Vec::with_capacity(logs.len());
+        let mut result_rows = Vec::with_capacity(logs.len());
+        let mut error_codes = Vec::with_capacity(logs.len());
+        let mut error_messages = Vec::with_capacity(logs.len());
+
+        for log in logs {
+            query_ids.push(log.query_id);
+            session_ids.push(log.session_id);
+            users.push(log.user);
+            queries.push(log.query);
+            event_times.push(log.event_time);
+            durations.push(log.duration);
+            read_rows.push(log.read_rows);
+            read_bytes.push(log.read_bytes);
+            result_rows.push(log.result_rows);
+            error_codes.push(log.error_code);
+            error_messages.push(log.error_message);
+        }
+
+        let schema = self.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(query_ids),
+            Series::new(session_ids),
+            Series::new(users),
+            Series::new(queries),
+            Series::new(event_times),
+            Series::new(durations),
+            Series::new(read_rows),
+            Series::new(read_bytes),
+            Series::new(result_rows),
+            Series::new(error_codes),
+            Series::new(error_messages),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::time::Duration;
+
+use common_datablocks::assert_blocks_eq;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_log_table() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+
+    // A query which succeeds and a query which fails, each in its own session.
+    {
+        let session = sessions.create_user_session("TestSession", "user1")?;
+        let ctx = session.create_context();
+        ctx.set_id("query_a".to_string())?;
+        ctx.attach_query_info("SELECT number FROM numbers(3)");
+        let plan =
+            PlanParser::create(ctx.clone()).build_from_sql("SELECT number FROM numbers(3)")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = ctx.try_create_abortable(executor.execute().await?)?;
+        stream.try_collect::<Vec<_>>().await?;
+    }
+    {
+        let session = sessions.create_user_session("TestSession", "user1")?;
+        let ctx = session.create_context();
+        ctx.set_id("query_b".to_string())?;
+        ctx.attach_query_info("SELECT * FROM t_not_exists");
+        let res = PlanParser::create(ctx.clone()).build_from_sql("SELECT * FROM t_not_exists");
+        ctx.set_query_error(&res.unwrap_err());
+    }
+
+    // The queries are logged by the writer thread when their contexts are destroyed.
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context();
+    for _ in 0..100 {
+        if ctx.query_logs().len() == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "select query_id, user, query, read_rows, result_rows, error_code, duration >= 0 \
+         from system.query_log order by query_id",
+    )?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let stream = executor.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+----------+-------+-------------------------------+-----------+-------------+------------+-----------------+",
+        "| query_id | user  | query                         | read_rows | result_rows | error_code | (duration >= 0) |",
+        "+----------+-------+-------------------------------+-----------+-------------+------------+-----------------+",
+        "| query_a  | user1 | SELECT number FROM numbers(3) | 3         | 3           | 0          | true            |",
+        "| query_b  | user1 | SELECT * FROM t_not_exists    | 0         | 0           | 25         | true            |",
+        "+----------+-------+-------------------------------+-----------+-------------+------------+-----------------+",
+    ];
+    assert_blocks_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::series::Series;
+use common_datavalues::series::SeriesFrom;
+use common_datavalues::DataField;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataSchemaRefExt;
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::Table;
+use crate::sessions::FuseQueryContextRef;
+
+/// The queries waiting in the query queue when max_running_queries queries are running, in the
+/// order they run. The wait_time is in seconds.
+pub struct QueryQueueTable {
+    schema: DataSchemaRef,
+}
+
+impl QueryQueueTable {
+    pub fn create() -> Self {
+        QueryQueueTable {
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("query_id", DataType::Utf8, false),
+                DataField::new("session_id", DataType::Utf8, false),
+                DataField::new("user", DataType::Utf8, false),
+                DataField::new("query", DataType::Utf8, false),
+                DataField::new("position", DataType::UInt64, false),
+                DataField::new("wait_time", DataType::Float64, false),
+            ]),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for QueryQueueTable {
+    fn name(&self) -> &str {
+        "query_queue"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemQueryQueue"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            schema: self.schema.clone(),
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+                locations: vec![],
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.query_queue table)".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let queued_queries = ctx.queued_queries_info();
+
+        let mut query_ids = Vec::with_capacity(queued_queries.len());
+        let mut session_ids = Vec::with_capacity(queued_queries.len());
+        let mut users = Vec::with_capacity(queued_queries.len());
+        let mut queries = Vec::with_capacity(queued_queries.len());
+        let mut positions = Vec::with_capacity(queued_queries.len());
+        let mut wait_times = Vec::with_capacity(queued_queries.len());
+        for queued in queued_queries {
+            query_ids.push(queued.query_id);
+            session_ids.push(queued.session_id);
+            users.push(queued.user);
+            queries.push(queued.query);
+            positions.push(queued.position as u64);
+            wait_times.push(queued.wait_time.as_secs_f64());
+        }
+
+        let schema = self.schema.clone();
+        let block = DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(query_ids),
+            Series::new(session_ids),
+            Series::new(users),
+            Series::new(queries),
+            Series::new(positions),
+            Series::new(wait_times),
+        ]);
+
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
+    }
+}
@@ -0,0 +1,76 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::time::Duration;
+
+use common_datablocks::assert_blocks_eq;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::clusters::Cluster;
+use crate::configs::Config;
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::SessionManager;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_query_queue_table() -> Result<()> {
+    let mut config = Config::default();
+    config.log_dir = env::current_dir()?
+        .join("../../tests/data/logs")
+        .display()
+        .to_string();
+    config.max_running_queries = 1;
+    let sessions = SessionManager::from_conf(config, Cluster::empty())?;
+
+    let running_session = sessions.create_user_session("TestSession", "user1")?;
+    let running = running_session.create_context();
+    running.attach_query_info("SELECT 1");
+    running.wait_in_query_queue().await?;
+
+    // The second query waits for the first one.
+    let queued_session = sessions.create_user_session("TestSession", "user2")?;
+    let queued = queued_session.create_context();
+    queued.attach_query_info("SELECT 2");
+    let waiting = tokio::spawn(async move {
+        queued.wait_in_query_queue().await?;
+        Ok::<_, common_exception::ErrorCode>(queued.get_warnings())
+    });
+
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context();
+    for _ in 0..100 {
+        if !ctx.queued_queries_info().is_empty() {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select user, query, position, wait_time > 0 from system.query_queue")?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let stream = executor.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+-------+----------+----------+-----------------+",
+        "| user  | query    | position | (wait_time > 0) |",
+        "+-------+----------+----------+-----------------+",
+        "| user2 | SELECT 2 | 1        | true            |",
+        "+-------+----------+----------+-----------------+",
+    ];
+    assert_blocks_eq(expected, result.as_slice());
+
+    // The queued query runs when the running one finishes.
+    drop(running);
+    let warnings = waiting.await.unwrap()?;
+    assert_eq!(1, warnings.len());
+    assert!(warnings[0].starts_with("The query waited"));
+    assert!(warnings[0].ends_with("in the query queue at position 1"));
+    assert!(ctx.queued_queries_info().is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,169 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_arrow::arrow_flight::flight_service_client::FlightServiceClient;
+use common_datavalues::DataSchema;
+use common_datavalues::DataSchemaRef;
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_flights::ConnectionFactory;
+use common_planners::Expression;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::SendableDataBlockStream;
+
+use crate::api::FlightClient;
+use crate::api::FlightTicket;
+use crate::datasources::Table;
+use crate::datasources::TableFunction;
+use crate::sessions::FuseQueryContextRef;
+
+/// The `remote('host:port', db, table)` table function, scans a table of another fuse-query cluster
+/// through the flight api of one of its nodes:
+///
+/// SELECT * FROM remote('127.0.0.1:9090', 'default', 't1')
+///
+/// The schema of the table is fetched from the remote cluster when the query is planned,
+/// the table is read by a single stream which is planned and executed by the remote cluster.
+pub struct RemoteClusterTable {
+    schema: DataSchemaRef,
+}
+
+impl RemoteClusterTable {
+    pub fn create() -> Self {
+        RemoteClusterTable {
+            schema: Arc::new(DataSchema::empty()),
+        }
+    }
+
+    /// Returns the (address, database, table) of the arguments.
+    fn parse_args(args: &[Expression]) -> Result<(String, String, String)> {
+        let args = args
+            .iter()
+            .map(|arg| match arg {
+                Expression::Literal {
+                    value: DataValue::Utf8(Some(v)),
+                    ..
+                } => Ok(v.clone()),
+                other => Err(ErrorCode::BadArguments(format!(
+                    "Arguments of table function remote must be string literals, but got {:?}",
+                    other
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        match args.as_slice() {
+            [address, db, table] => Ok((address.clone(), db.clone(), table.clone())),
+            _ => Err(ErrorCode::BadArguments(
+                "Table function remote expects arguments ('host:port', database, table)",
+            )),
+        }
+    }
+
+    async fn create_flight_client(address: &str) -> Result<FlightClient> {
+        let channel = ConnectionFactory::create_flight_channel(address, None).await?;
+        Ok(FlightClient::new(FlightServiceClient::new(channel)))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for RemoteClusterTable {
+    fn name(&self) -> &str {
+        "remote"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemRemote"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        let (address, db, table) = Self::parse_args(&scan.table_args)?;
+
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            schema: scan.table_schema.clone(),
+            parts: vec![Part {
+                name: format!("{}/{}.{}", address, db, table),
+                version: 0,
+                locations: vec![],
+            }],
+            statistics: Statistics::default(),
+            description: format!("(Read from remote table {}.{} of {})", db, table, address),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: FuseQueryContextRef,
+        source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let (address, db, table) = Self::parse_args(&source_plan.scan_plan.table_args)?;
+        let timeout = ctx.get_settings().get_flight_client_timeout()?;
+
+        let mut client = Self::create_flight_client(&address).await?;
+        let ticket = FlightTicket::scan(&db, &table);
+        client
+            .fetch_stream(ctx, ticket, source_plan.schema.clone(), timeout)
+            .await
+    }
+}
+
+impl TableFunction for RemoteClusterTable {
+    fn function_name(&self) -> &str {
+        "remote"
+    }
+
+    fn db(&self) -> &str {
+        "system"
+    }
+
+    fn schema_with_args(
+        &self,
+        ctx: FuseQueryContextRef,
+        args: &[Expression],
+    ) -> Result<DataSchemaRef> {
+        let (address, db, table) = Self::parse_args(args)?;
+        let timeout = ctx.get_settings().get_flight_client_timeout()?;
+
+        // The plan is built synchronously, the schema is fetched by the runtime of the query.
+        let fetch_schema = ctx.execute_task(async move {
+            let mut client = Self::create_flight_client(&address).await?;
+            client.get_schema(&db, &table, timeout).await
+        })?;
+
+        futures::executor::block_on(fetch_schema)
+            .map_err(|e| ErrorCode::TokioError(format!("Cannot fetch the remote schema: {}", e)))?
+    }
+
+    fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
+    where Self: 'a {
+        self
+    }
+}
@@ -30,6 +30,10 @@ impl SettingsTable {
                 DataField::new("value", DataType::Utf8, false),
                 DataField::new("default_value", DataType::Utf8, false),
                 DataField::new("description", DataType::Utf8, false),
+                DataField::new("min", DataType::Utf8, false),
+                DataField::new("max", DataType::Utf8, false),
+                DataField::new("tier", DataType::Utf8, false),
+                DataField::new("requires_restart", DataType::Boolean, false),
             ]),
         }
     }
@@ -70,6 +74,7 @@ impl Table for SettingsTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.settings table)".to_string(),
@@ -89,12 +94,20 @@ impl Table for SettingsTable {
         let mut values: Vec<String> = vec![];
         let mut default_values: Vec<String> = vec![];
         let mut descs: Vec<String> = vec![];
+        let mut mins: Vec<String> = vec![];
+        let mut maxs: Vec<String> = vec![];
+        let mut tiers: Vec<String> = vec![];
+        let mut requires_restarts: Vec<bool> = vec![];
         for setting in settings.iter() {
             if let DataValue::Struct(vals) = setting {
                 names.push(format!("{:?}", vals[0]));
                 values.push(format!("{:?}", vals[1]));
                 default_values.push(format!("{:?}", vals[2]));
                 descs.push(format!("{:?}", vals[3]));
+                mins.push(format!("{:?}", vals[4]));
+                maxs.push(format!("{:?}", vals[5]));
+                tiers.push(format!("{:?}", vals[6]));
+                requires_restarts.push(vals[7] == DataValue::Boolean(Some(true)));
             }
         }
 
@@ -102,11 +115,18 @@ impl Table for SettingsTable {
         let values: Vec<&str> = values.iter().map(|x| x.as_str()).collect();
         let default_values: Vec<&str> = default_values.iter().map(|x| x.as_str()).collect();
         let descs: Vec<&str> = descs.iter().map(|x| x.as_str()).collect();
+        let mins: Vec<&str> = mins.iter().map(|x| x.as_str()).collect();
+        let maxs: Vec<&str> = maxs.iter().map(|x| x.as_str()).collect();
+        let tiers: Vec<&str> = tiers.iter().map(|x| x.as_str()).collect();
         let block = DataBlock::create_by_array(self.schema.clone(), vec![
             Series::new(names),
             Series::new(values),
             Series::new(default_values),
             Series::new(descs),
+            Series::new(mins),
+            Series::new(maxs),
+            Series::new(tiers),
+            Series::new(requires_restarts),
         ]);
         Ok(Box::pin(DataBlockStream::create(
             self.schema.clone(),
@@ -36,6 +36,12 @@ impl SystemDatabase {
             Arc::new(system::DatabasesTable::create()),
             Arc::new(system::TracingTable::create()),
             Arc::new(system::ProcessesTable::create()),
+            Arc::new(system::QueryLogTable::create()),
+            Arc::new(system::QueryQueueTable::create()),
+            Arc::new(system::RemoteClusterTable::create()),
+            Arc::new(system::FlightTable::create()),
+            Arc::new(system::WarningsTable::create()),
+            Arc::new(system::VariablesTable::create()),
         ];
         let mut tables: HashMap<String, Arc<dyn Table>> = HashMap::default();
         for tbl in table_list.iter() {
@@ -47,6 +53,8 @@ impl SystemDatabase {
             Arc::new(system::NumbersTable::create("numbers")),
             Arc::new(system::NumbersTable::create("numbers_mt")),
             Arc::new(system::NumbersTable::create("numbers_local")),
+            Arc::new(system::RemoteClusterTable::create()),
+            Arc::new(system::FlightTable::create()),
         ];
         let mut table_functions: HashMap<String, Arc<dyn TableFunction>> = HashMap::default();
         for tbl_func in table_function_list.iter() {
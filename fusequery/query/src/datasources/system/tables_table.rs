@@ -29,6 +29,7 @@ impl TablesTable {
                 DataField::new("database", DataType::Utf8, false),
                 DataField::new("name", DataType::Utf8, false),
                 DataField::new("engine", DataType::Utf8, false),
+                DataField::new("comment", DataType::Utf8, false),
             ]),
         }
     }
@@ -69,6 +70,7 @@ impl Table for TablesTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.functions table)".to_string(),
@@ -87,11 +89,13 @@ impl Table for TablesTable {
         let databases: Vec<&str> = database_tables.iter().map(|(d, _)| d.as_str()).collect();
         let names: Vec<&str> = database_tables.iter().map(|(_, v)| v.name()).collect();
         let engines: Vec<&str> = database_tables.iter().map(|(_, v)| v.engine()).collect();
+        let comments: Vec<&str> = database_tables.iter().map(|(_, v)| v.comment()).collect();
 
         let block = DataBlock::create_by_array(self.schema.clone(), vec![
             Series::new(databases),
             Series::new(names),
             Series::new(engines),
+            Series::new(comments),
         ]);
 
         Ok(Box::pin(DataBlockStream::create(
@@ -23,25 +23,31 @@ async fn test_tables_table() -> Result<()> {
     let stream = table.read(ctx, &source_plan).await?;
     let result = stream.try_collect::<Vec<_>>().await?;
     let block = &result[0];
-    assert_eq!(block.num_columns(), 3);
+    assert_eq!(block.num_columns(), 4);
 
     let expected = vec![
-        "+----------+---------------+--------------------+",
-        "| database | name          | engine             |",
-        "+----------+---------------+--------------------+",
-        "| system   | clusters      | SystemClusters     |",
-        "| system   | contributors  | SystemContributors |",
-        "| system   | databases     | SystemDatabases    |",
-        "| system   | functions     | SystemFunctions    |",
-        "| system   | numbers       | SystemNumbers      |",
-        "| system   | numbers_local | SystemNumbersLocal |",
-        "| system   | numbers_mt    | SystemNumbersMt    |",
-        "| system   | one           | SystemOne          |",
-        "| system   | processes     | SystemProcesses    |",
-        "| system   | settings      | SystemSettings     |",
-        "| system   | tables        | SystemTables       |",
-        "| system   | tracing       | SystemTracing      |",
-        "+----------+---------------+--------------------+",
+        "+----------+---------------+--------------------+---------+",
+        "| database | name          | engine             | comment |",
+        "+----------+---------------+--------------------+---------+",
+        "| system   | clusters      | SystemClusters     |         |",
+        "| system   | contributors  | SystemContributors |         |",
+        "| system   | databases     | SystemDatabases    |         |",
+        "| system   | flight        | SystemFlight       |         |",
+        "| system   | functions     | SystemFunctions    |         |",
+        "| system   | numbers       | SystemNumbers      |         |",
+        "| system   | numbers_local | SystemNumbersLocal |         |",
+        "| system   | numbers_mt    | SystemNumbersMt    |         |",
+        "| system   | one           | SystemOne          |         |",
+        "| system   | processes     | SystemProcesses    |         |",
+        "| system   | query_log     | SystemQueryLog     |         |",
+        "| system   | query_queue   | SystemQueryQueue   |         |",
+        "| system   | remote        | SystemRemote       |         |",
+        "| system   | settings      | SystemSettings     |         |",
+        "| system   | tables        | SystemTables       |         |",
+        "| system   | tracing       | SystemTracing      |         |",
+        "| system   | variables     | SystemVariables    |         |",
+        "| system   | warnings      | SystemWarnings     |         |",
+        "+----------+---------------+--------------------+---------+",
     ];
     common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
 
@@ -79,6 +79,7 @@ impl Table for TracingTable {
             parts: vec![Part {
                 name: "".to_string(),
                 version: 0,
+                locations: vec![],
             }],
             statistics: Statistics::default(),
             description: "(Read from system.tracing table)".to_string(),
@@ -0,0 +1,104 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::datasources::Table;
+use crate::functions::ContextVariable;
+use crate::sessions::FuseQueryContextRef;
+
+pub struct VariablesTable {
+    schema: DataSchemaRef,
+}
+
+impl VariablesTable {
+    pub fn create() -> Self {
+        VariablesTable {
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("Variable_name", DataType::Utf8, false),
+                DataField::new("Value", DataType::Utf8, false),
+            ]),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for VariablesTable {
+    fn name(&self) -> &str {
+        "variables"
+    }
+
+    fn engine(&self) -> &str {
+        "SystemVariables"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "system".to_string(),
+            table: self.name().to_string(),
+            schema: self.schema.clone(),
+            parts: vec![Part {
+                name: "".to_string(),
+                version: 0,
+                locations: vec![],
+            }],
+            statistics: Statistics::default(),
+            description: "(Read from system.variables table)".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: false,
+        })
+    }
+
+    async fn read(
+        &self,
+        ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        let variables = ContextVariable::list(ctx)?;
+        let names: Vec<&str> = variables.iter().map(|(name, _)| name.as_str()).collect();
+        let values: Vec<String> = variables
+            .iter()
+            .map(|(_, value)| format!("{:?}", value))
+            .collect();
+        let values: Vec<&str> = values.iter().map(|x| x.as_str()).collect();
+
+        let block = DataBlock::create_by_array(self.schema.clone(), vec![
+            Series::new(names),
+            Series::new(values),
+        ]);
+        Ok(Box::pin(DataBlockStream::create(
+            self.schema.clone(),
+            None,
+            vec![block],
+        )))
+    }
+}
@@ -0,0 +1,33 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::system::*;
+use crate::datasources::*;
+use crate::functions::ContextVariable;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_variables_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let table = VariablesTable::create();
+    let source_plan = table.read_plan(
+        ctx.clone(),
+        &ScanPlan::empty(),
+        ctx.get_settings().get_max_threads()? as usize,
+    )?;
+
+    let stream = table.read(ctx.clone(), &source_plan).await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let block = &result[0];
+    assert_eq!(block.num_columns(), 2);
+    assert_eq!(block.num_rows(), ContextVariable::list(ctx)?.len());
+
+    Ok(())
+}
@@ -0,0 +1,44 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::assert_blocks_eq;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sql::PlanParser;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_warnings_table() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+    let session = sessions.create_session("TestSession")?;
+
+    // The warnings of a query are kept by the session when the query is finished.
+    {
+        let ctx = session.create_context();
+        ctx.push_warning("Partial results of stage 1 of node node1");
+        ctx.push_warning("Partial results of stage 1 of node node2");
+    }
+
+    {
+        let ctx = session.create_context();
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("show warnings")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+
+        let expected = vec![
+            "+---------+------------------------------------------+",
+            "| level   | message                                  |",
+            "+---------+------------------------------------------+",
+            "| Warning | Partial results of stage 1 of node node1 |",
+            "| Warning | Partial results of stage 1 of node node2 |",
+            "+---------+------------------------------------------+",
+        ];
+        assert_blocks_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
@@ -8,21 +8,46 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::DropPartitionPlan;
 use common_planners::InsertIntoPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::ScanPlan;
+use common_planners::TruncateTablePlan;
 use common_streams::SendableDataBlockStream;
 
+use crate::datasources::ClusterBy;
 use crate::sessions::FuseQueryContextRef;
 
 #[async_trait::async_trait]
 pub trait Table: Sync + Send {
     fn name(&self) -> &str;
     fn engine(&self) -> &str;
+    // The COMMENT of the table.
+    fn comment(&self) -> &str {
+        ""
+    }
+    // The PARTITION BY expression of the table, if the table is partitioned.
+    fn partition_by(&self) -> Option<&str> {
+        None
+    }
+    // Whether read_plan prunes the parts with the filters pushed down to the scan, the
+    // partitioned tables prune their partitions.
+    fn prunes_by_filters(&self) -> bool {
+        self.partition_by().is_some()
+    }
+    // The CLUSTER BY of the table, if the table is bucketed.
+    fn cluster_by(&self) -> Option<&ClusterBy> {
+        None
+    }
     fn as_any(&self) -> &dyn Any;
     fn schema(&self) -> Result<DataSchemaRef>;
     // Is Local or Remote.
     fn is_local(&self) -> bool;
+    // Whether the nodes storing the parts execute the store plan pushed down with the read, see
+    // `Extras::store_plan`.
+    fn executes_store_plans(&self) -> bool {
+        false
+    }
     // Get the read source plan.
     fn read_plan(
         &self,
@@ -48,6 +73,26 @@ pub trait Table: Sync + Send {
             self.name()
         )))
     }
+
+    // Remove all the data of the table.
+    async fn truncate(&self, _ctx: FuseQueryContextRef, _plan: TruncateTablePlan) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "truncate for local table {} is not implemented",
+            self.name()
+        )))
+    }
+
+    // Remove the data of a partition.
+    async fn drop_partition(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _plan: DropPartitionPlan,
+    ) -> Result<()> {
+        Err(ErrorCode::UnImplement(format!(
+            "drop partition for table {} is not implemented",
+            self.name()
+        )))
+    }
 }
 
 pub type TablePtr = Arc<dyn Table>;
@@ -0,0 +1,165 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::RwLock;
+use common_planners::TableOptions;
+use indexmap::IndexMap;
+use lazy_static::lazy_static;
+
+use crate::datasources::local::CsvTable;
+use crate::datasources::local::LogTable;
+use crate::datasources::local::MemoryTable;
+use crate::datasources::local::NullTable;
+use crate::datasources::local::ParquetTable;
+use crate::datasources::remote::RemoteTable;
+use crate::datasources::remote::StoreClientProvider;
+use crate::datasources::Table;
+
+pub type TableCreator = fn(
+    db: String,
+    name: String,
+    schema: DataSchemaRef,
+    options: TableOptions,
+) -> Result<Box<dyn Table>>;
+
+/// Creates a table of an engine which keeps the data in the store, the engine is the name which
+/// the table is created with.
+pub type StoreTableCreator = fn(
+    engine: String,
+    db: String,
+    name: String,
+    schema: DataSchemaRef,
+    store_client_provider: StoreClientProvider,
+    options: TableOptions,
+) -> Result<Box<dyn Table>>;
+
+#[derive(Clone, Copy)]
+enum TableEngine {
+    // The tables of a local database, the data is in the query node.
+    Local(TableCreator),
+    // The tables of a remote database, the data is in the store.
+    Store(StoreTableCreator),
+}
+
+// Engine names are case insensitive, the keys are in lower case.
+type TableEngineMapRef = Arc<RwLock<IndexMap<String, TableEngine>>>;
+
+lazy_static! {
+    static ref ENGINES: TableEngineMapRef = {
+        let map: TableEngineMapRef = Arc::new(RwLock::new(IndexMap::new()));
+        {
+            let mut map = map.write();
+            map.insert(
+                "parquet".into(),
+                TableEngine::Local(ParquetTable::try_create),
+            );
+            map.insert("csv".into(), TableEngine::Local(CsvTable::try_create));
+            map.insert("null".into(), TableEngine::Local(NullTable::try_create));
+            map.insert("memory".into(), TableEngine::Local(MemoryTable::try_create));
+            map.insert("log".into(), TableEngine::Local(LogTable::try_create));
+            map.insert("fuse".into(), TableEngine::Store(RemoteTable::try_create));
+        }
+        map
+    };
+}
+
+/// Table engines by name, `CREATE TABLE ... ENGINE = X` creates a table with the engine registered
+/// as X. The local engines create the tables of the local databases, the store engines create the
+/// tables of the remote databases.
+pub struct TableEngineRegistry;
+
+impl TableEngineRegistry {
+    /// The engine of the tables created without ENGINE in a local database.
+    pub const DEFAULT_LOCAL_ENGINE: &'static str = "Null";
+    /// The engine of the tables created without ENGINE in a remote database.
+    pub const DEFAULT_STORE_ENGINE: &'static str = "Fuse";
+
+    /// Register a local table engine, an engine can not be registered twice.
+    pub fn register(engine: &str, creator: TableCreator) -> Result<()> {
+        Self::register_engine(engine, TableEngine::Local(creator))
+    }
+
+    /// Register a store table engine, an engine can not be registered twice.
+    pub fn register_store(engine: &str, creator: StoreTableCreator) -> Result<()> {
+        Self::register_engine(engine, TableEngine::Store(creator))
+    }
+
+    fn register_engine(engine: &str, table_engine: TableEngine) -> Result<()> {
+        let mut map = ENGINES.write();
+        let key = engine.to_lowercase();
+        if map.contains_key(&key) {
+            return Err(ErrorCode::DuplicateTableEngine(format!(
+                "Table engine '{}' is already registered",
+                engine
+            )));
+        }
+        map.insert(key, table_engine);
+        Ok(())
+    }
+
+    pub fn check(engine: &str) -> bool {
+        ENGINES.read().contains_key(&engine.to_lowercase())
+    }
+
+    fn get_engine(engine: &str) -> Result<TableEngine> {
+        ENGINES
+            .read()
+            .get(&engine.to_lowercase())
+            .copied()
+            .ok_or_else(|| {
+                ErrorCode::UnknownTableEngine(format!("Unknown table engine: '{}'", engine))
+            })
+    }
+
+    /// Creates a table of a local database.
+    pub fn create_table(
+        engine: &str,
+        db: String,
+        name: String,
+        schema: DataSchemaRef,
+        options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        match Self::get_engine(engine)? {
+            TableEngine::Local(creator) => (creator)(db, name, schema, options),
+            TableEngine::Store(_) => Err(ErrorCode::UnknownTableEngine(format!(
+                "Table engine '{}' keeps the data in the store, it is only for the remote databases",
+                engine
+            ))),
+        }
+    }
+
+    /// Creates a table of a remote database.
+    pub fn create_store_table(
+        engine: &str,
+        db: String,
+        name: String,
+        schema: DataSchemaRef,
+        store_client_provider: StoreClientProvider,
+        options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        match Self::get_engine(engine)? {
+            TableEngine::Store(creator) => (creator)(
+                engine.to_string(),
+                db,
+                name,
+                schema,
+                store_client_provider,
+                options,
+            ),
+            TableEngine::Local(_) => Err(ErrorCode::UnknownTableEngine(format!(
+                "Table engine '{}' keeps the data in the query node, it is only for the local databases",
+                engine
+            ))),
+        }
+    }
+
+    pub fn registered_names() -> Vec<String> {
+        ENGINES.read().keys().cloned().collect()
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::*;
+use pretty_assertions::assert_eq;
+
+use crate::datasources::local::NullTable;
+use crate::datasources::*;
+
+#[test]
+fn test_table_engine_registry() -> Result<()> {
+    let schema = DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]);
+
+    // Engine names are case insensitive.
+    for engine in &["Null", "null", "NULL"] {
+        let table = TableEngineRegistry::create_table(
+            engine,
+            "default".into(),
+            "a".into(),
+            schema.clone(),
+            TableOptions::default(),
+        )?;
+        assert_eq!(table.engine(), "Null");
+    }
+
+    // Unknown engine.
+    let result = TableEngineRegistry::create_table(
+        "Unknown",
+        "default".into(),
+        "a".into(),
+        schema.clone(),
+        TableOptions::default(),
+    );
+    assert_eq!(
+        result.err().unwrap().message(),
+        "Unknown table engine: 'Unknown'"
+    );
+
+    // The store engines create the tables of the remote databases only.
+    let result = TableEngineRegistry::create_table(
+        "fuse",
+        "default".into(),
+        "a".into(),
+        schema.clone(),
+        TableOptions::default(),
+    );
+    assert_eq!(
+        result.err().unwrap().message(),
+        "Table engine 'fuse' keeps the data in the store, it is only for the remote databases"
+    );
+
+    // Register a new engine.
+    assert!(!TableEngineRegistry::check("Blackhole"));
+    TableEngineRegistry::register("Blackhole", NullTable::try_create)?;
+    assert!(TableEngineRegistry::check("blackhole"));
+    TableEngineRegistry::create_table(
+        "blackhole",
+        "default".into(),
+        "a".into(),
+        schema,
+        TableOptions::default(),
+    )?;
+
+    // An engine can not be registered twice.
+    let result = TableEngineRegistry::register("MEMORY", NullTable::try_create);
+    assert_eq!(
+        result.err().unwrap().message(),
+        "Table engine 'MEMORY' is already registered"
+    );
+
+    Ok(())
+}
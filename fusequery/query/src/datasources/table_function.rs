@@ -4,12 +4,27 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_planners::Expression;
+
 use crate::datasources::Table;
+use crate::sessions::FuseQueryContextRef;
 
 pub trait TableFunction: Sync + Send + Table {
     fn function_name(&self) -> &str;
     fn db(&self) -> &str;
 
+    /// The schema of the table returned by the function called with `args`,
+    /// by default it doesn't depend on the arguments.
+    fn schema_with_args(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _args: &[Expression],
+    ) -> Result<DataSchemaRef> {
+        self.schema()
+    }
+
     fn as_table<'a>(self: Arc<Self>) -> Arc<dyn Table + 'a>
     where Self: 'a;
 }
@@ -7,6 +7,7 @@ use common_planners::*;
 use common_runtime::tokio;
 use pretty_assertions::assert_eq;
 
+use crate::datasources::CatalogChange;
 use crate::datasources::DataSource;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
@@ -52,3 +53,36 @@ async fn test_datasource() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_datasource_apply_catalog_change() -> Result<()> {
+    let datasource = DataSource::try_create()?;
+
+    // A remote database created by another query node.
+    datasource.apply_catalog_change(&CatalogChange::CreateDatabase {
+        db: "peer_db".to_string(),
+    });
+    let database = datasource.get_database("peer_db")?;
+    assert_eq!("remote", database.engine());
+
+    // The cached tables of a changed table are dropped.
+    datasource.apply_catalog_change(&CatalogChange::DropTable {
+        db: "peer_db".to_string(),
+        table: "t1".to_string(),
+    });
+    assert!(datasource.get_table("peer_db", "t1").is_err());
+
+    // A remote database dropped by another query node.
+    datasource.apply_catalog_change(&CatalogChange::DropDatabase {
+        db: "peer_db".to_string(),
+    });
+    assert!(datasource.get_database("peer_db").is_err());
+
+    // Local databases belong to the query node only.
+    datasource.apply_catalog_change(&CatalogChange::DropDatabase {
+        db: "default".to_string(),
+    });
+    assert!(datasource.get_database("default").is_ok());
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+use crate::sessions::FuseQueryContextRef;
+
+/// The server variables of `SELECT @@name` and `SHOW VARIABLES`.
+/// The settings of the session are variables too, the other variables have the fixed values
+/// which the MySQL JDBC/ODBC drivers expect when they connect.
+pub struct ContextVariable;
+
+impl ContextVariable {
+    /// Returns the variable, the name is case insensitive.
+    pub fn get(name: &str, ctx: FuseQueryContextRef) -> Result<DataValue> {
+        let name = name.to_lowercase();
+        Self::list(ctx)?
+            .into_iter()
+            .find(|(variable, _)| *variable == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| ErrorCode::UnknownVariable(format!("Unknown variable: {:?}", name)))
+    }
+
+    /// Returns all the variables, sorted by name.
+    pub fn list(ctx: FuseQueryContextRef) -> Result<Vec<(String, DataValue)>> {
+        let mut variables = vec![];
+        for setting in ctx.get_settings().iter() {
+            if let DataValue::Struct(vals) = setting {
+                variables.push((format!("{:?}", vals[0]), vals[1].clone()));
+            }
+        }
+
+        let version = ctx.get_fuse_version();
+        for (name, value) in Self::compatible_variables(&version) {
+            if !variables.iter().any(|(variable, _)| variable == name) {
+                variables.push((name.to_string(), value));
+            }
+        }
+
+        variables.sort_by(|a, b| a.0.cmp(&b.0));
+        Ok(variables)
+    }
+
+    /// Returns true if the variable only exists for the compatibility with the drivers,
+    /// `SET` of such a variable has no effect.
+    pub fn is_compatible(name: &str) -> bool {
+        let name = name.to_lowercase();
+        Self::compatible_variables("")
+            .iter()
+            .any(|(variable, _)| *variable == name)
+    }
+
+    fn compatible_variables(version: &str) -> Vec<(&'static str, DataValue)> {
+        let string = |v: &str| DataValue::Utf8(Some(v.to_string()));
+        let number = |v: u64| DataValue::UInt64(Some(v));
+
+        vec![
+            ("auto_increment_increment", number(1)),
+            ("autocommit", number(1)),
+            ("character_set_client", string("utf8mb4")),
+            ("character_set_connection", string("utf8mb4")),
+            ("character_set_database", string("utf8mb4")),
+            ("character_set_results", string("utf8mb4")),
+            ("character_set_server", string("utf8mb4")),
+            ("collation_connection", string("utf8mb4_general_ci")),
+            ("collation_database", string("utf8mb4_general_ci")),
+            ("collation_server", string("utf8mb4_general_ci")),
+            ("init_connect", string("")),
+            ("interactive_timeout", number(28800)),
+            ("license", string("Apache-2.0")),
+            ("lower_case_table_names", number(0)),
+            ("max_allowed_packet", number(64 * 1024 * 1024)),
+            ("net_buffer_length", number(16384)),
+            ("net_write_timeout", number(60)),
+            ("performance_schema", number(0)),
+            ("query_cache_size", number(0)),
+            ("query_cache_type", string("OFF")),
+            ("sql_mode", string("")),
+            ("sql_select_limit", number(u64::MAX)),
+            ("system_time_zone", string("UTC")),
+            ("time_zone", string("SYSTEM")),
+            ("transaction_isolation", string("REPEATABLE-READ")),
+            ("transaction_read_only", number(0)),
+            ("tx_isolation", string("REPEATABLE-READ")),
+            ("tx_read_only", number(0)),
+            ("version", string(&format!("8.0.26-{}", version))),
+            ("version_comment", string(version)),
+            ("wait_timeout", number(28800)),
+        ]
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+
+use crate::functions::ContextVariable;
+
+#[test]
+fn test_context_variable() -> Result<()> {
+    use pretty_assertions::assert_eq;
+    let ctx = crate::tests::try_create_context()?;
+
+    // The variables of the drivers.
+    {
+        let value = ContextVariable::get("VERSION_COMMENT", ctx.clone())?;
+        assert_eq!(ctx.get_fuse_version(), format!("{:?}", value));
+
+        let value = ContextVariable::get("auto_increment_increment", ctx.clone())?;
+        assert_eq!("1", format!("{:?}", value));
+    }
+
+    // The settings.
+    {
+        ctx.get_settings().set_max_block_size(1000)?;
+        let value = ContextVariable::get("max_block_size", ctx.clone())?;
+        assert_eq!("1000", format!("{:?}", value));
+    }
+
+    // Sorted by name, without duplicates.
+    {
+        let variables = ContextVariable::list(ctx.clone())?;
+        let mut names = variables
+            .iter()
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        names.dedup();
+        assert_eq!(variables.len(), names.len());
+        assert!(names.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    assert!(ContextVariable::is_compatible("SQL_MODE"));
+    assert!(!ContextVariable::is_compatible("max_threads"));
+
+    let result = ContextVariable::get("not_exists", ctx);
+    assert_eq!(
+        "Code: 20, displayText = Unknown variable: \"not_exists\".",
+        result.unwrap_err().to_string()
+    );
+    Ok(())
+}
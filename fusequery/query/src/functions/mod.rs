@@ -4,7 +4,11 @@
 
 #[cfg(test)]
 mod context_function_test;
+#[cfg(test)]
+mod context_variable_test;
 
 mod context_function;
+mod context_variable;
 
 pub use context_function::ContextFunction;
+pub use context_variable::ContextVariable;
@@ -0,0 +1,54 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use crate::api::CatalogChangeAction;
+use crate::api::FlightAction;
+use crate::datasources::CatalogChange;
+use crate::sessions::FuseQueryContextRef;
+
+/// Notify the other query nodes of the cluster of a DDL of a remote database executed by this node.
+///
+/// The DDL is already committed to the store when it is broadcast, an unreachable node is only logged,
+/// it drops its stale metadata when its cache is re-validated against the store.
+pub struct CatalogBroadcast;
+
+impl CatalogBroadcast {
+    pub async fn broadcast(ctx: &FuseQueryContextRef, change: CatalogChange) {
+        let nodes = match ctx
+            .try_get_cluster()
+            .and_then(|cluster| cluster.get_nodes())
+        {
+            Ok(nodes) => nodes,
+            Err(error) => {
+                log::warn!(
+                    "Cannot broadcast {:?}, cannot get the cluster: {}",
+                    change,
+                    error
+                );
+                return;
+            }
+        };
+
+        let timeout = ctx.get_settings().get_flight_client_timeout().unwrap_or(60);
+        for node in nodes.iter().filter(|node| !node.is_local()) {
+            let action = FlightAction::CatalogChangeAction(CatalogChangeAction {
+                tenant: ctx.get_tenant(),
+                change: change.clone(),
+            });
+            let notify_node = async {
+                let mut flight_client = node.get_flight_client().await?;
+                flight_client.execute_action(action, timeout).await
+            };
+
+            if let Err(error) = notify_node.await {
+                log::warn!(
+                    "Cannot broadcast {:?} to node {}: {}",
+                    change,
+                    node.name,
+                    error
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryInto;
+use std::fs::File;
+use std::sync::Arc;
+
+use common_arrow::arrow::csv;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::CopyPlan;
+use common_planners::InsertIntoPlan;
+use common_runtime::tokio::sync::mpsc::channel;
+use common_streams::DataBlockStream;
+use common_streams::ProgressStream;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::FuseQueryContextRef;
+use crate::sql::DefaultValues;
+
+/// Loads a file into a table. The file is read by blocks of max_block_size rows, which are
+/// streamed into the table as the rows of an INSERT, so the file is never held in memory as a
+/// whole. The rows read from the file are counted in the progress of the query.
+pub struct CopyInterpreter {
+    ctx: FuseQueryContextRef,
+    plan: CopyPlan,
+}
+
+impl CopyInterpreter {
+    pub fn try_create(ctx: FuseQueryContextRef, plan: CopyPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(CopyInterpreter { ctx, plan }))
+    }
+
+    fn read_file(&self) -> Result<SendableDataBlockStream> {
+        let location = &self.plan.location;
+        let file = File::open(location)
+            .map_err(|e| ErrorCode::CannotReadFile(format!("File: {}, err: {}", location, e)))?;
+
+        let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+        let arrow_schema = Arc::new(self.plan.schema.to_arrow());
+        let reader = csv::Reader::new(file, arrow_schema, false, None, block_size, None, None);
+        let blocks = reader.map(|batch| {
+            batch
+                .map_err(ErrorCode::from)
+                .and_then(|batch| batch.try_into())
+        });
+
+        Ok(Box::pin(ProgressStream::try_create(
+            Box::pin(futures::stream::iter(blocks)),
+            self.ctx.progress_callback()?,
+        )?))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for CopyInterpreter {
+    fn name(&self) -> &str {
+        "CopyInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let table = self
+            .ctx
+            .get_table(&self.plan.db_name, &self.plan.tbl_name)?;
+        let mut file_stream = self.read_file()?;
+
+        // The columns absent from the file take their default values.
+        // The table keeps nothing if the file fails to be read in the middle.
+        let default_values = DefaultValues::create(self.ctx.clone());
+        let schema = table.schema()?;
+        let (tx, rx) = channel(2);
+        self.ctx.execute_task(async move {
+            while let Some(block) = file_stream.next().await {
+                let block = block.and_then(|block| default_values.fill(&block, &schema));
+                let failed = block.is_err();
+                if tx.send(block).await.is_err() || failed {
+                    break;
+                }
+            }
+        })?;
+
+        let insert_plan = InsertIntoPlan {
+            db_name: self.plan.db_name.clone(),
+            tbl_name: self.plan.tbl_name.clone(),
+            schema: table.schema()?,
+            input_stream: InsertIntoPlan::empty_stream(),
+            select_plan: None,
+            table_function: None,
+            table_args: vec![],
+        };
+        insert_plan.set_input_stream(Box::pin(ReceiverStream::new(rx)));
+        table.append_data(self.ctx.clone(), insert_plan).await?;
+
+        Ok(Box::pin(DataBlockStream::create(
+            self.plan.schema(),
+            None,
+            vec![],
+        )))
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::fs;
+
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_copy_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = env::temp_dir().join(format!("copy_{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir)?;
+    let path = dir.join("a.csv").display().to_string();
+    fs::write(&path, "1,x\n2,y\n3,z\n")?;
+
+    // Create table.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.a(a bigint, b varchar, c int default 7) Engine = Memory",
+        )?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        executor.execute().await?;
+    }
+
+    // Copy into, the file is read by blocks of 2 rows and the absent column takes its default.
+    {
+        ctx.get_settings().set_max_block_size(2)?;
+        let sql = format!("copy into default.a (a, b) from '{}' format csv", path);
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        assert_eq!(executor.name(), "CopyInterpreter");
+        executor.execute().await?;
+        assert_eq!(3, ctx.get_total_progress_value().read_rows);
+    }
+
+    // Select.
+    {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql("select * from default.a")?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let expected = vec![
+            "+---+---+---+",
+            "| a | b | c |",
+            "+---+---+---+",
+            "| 1 | x | 7 |",
+            "| 2 | y | 7 |",
+            "| 3 | z | 7 |",
+            "+---+---+---+",
+        ];
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    // Errors.
+    {
+        let sql = format!("copy into default.a from '{}' format parquet", path);
+        let result = PlanParser::create(ctx.clone()).build_from_sql(&sql);
+        assert_eq!(
+            "Code: 2, displayText = COPY does not support Parquet files yet, expected CSV.",
+            result.err().unwrap().to_string()
+        );
+
+        let missing = dir.join("missing.csv").display().to_string();
+        let sql = format!("copy into default.a from '{}'", missing);
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(&sql)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let result = executor.execute().await;
+        assert_eq!(
+            format!(
+                "Code: 23, displayText = File: {}, err: No such file or directory (os error 2).",
+                missing
+            ),
+            result.err().unwrap().to_string()
+        );
+    }
+
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
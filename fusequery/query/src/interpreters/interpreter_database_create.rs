@@ -6,10 +6,13 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_planners::CreateDatabasePlan;
+use common_planners::DatabaseEngineType;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
+use crate::datasources::CatalogChange;
+use crate::interpreters::CatalogBroadcast;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::FuseQueryContextRef;
@@ -40,6 +43,13 @@ impl Interpreter for CreateDatabaseInterpreter {
         let datasource = self.ctx.get_datasource();
         datasource.create_database(self.plan.clone()).await?;
 
+        if self.plan.engine == DatabaseEngineType::Remote {
+            let change = CatalogChange::CreateDatabase {
+                db: self.plan.db.clone(),
+            };
+            CatalogBroadcast::broadcast(&self.ctx, change).await;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
@@ -9,6 +9,8 @@ use common_planners::DropDatabasePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::datasources::CatalogChange;
+use crate::interpreters::CatalogBroadcast;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::FuseQueryContextRef;
@@ -32,8 +34,19 @@ impl Interpreter for DropDatabaseInterpreter {
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let datasource = self.ctx.get_datasource();
+        let is_remote = datasource
+            .get_database(self.plan.db.as_str())
+            .map(|database| !database.is_local())
+            .unwrap_or(false);
         datasource.drop_database(self.plan.clone()).await?;
 
+        if is_remote {
+            let change = CatalogChange::DropDatabase {
+                db: self.plan.db.clone(),
+            };
+            CatalogBroadcast::broadcast(&self.ctx, change).await;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
@@ -42,6 +42,7 @@ impl Interpreter for DescribeTableInterpreter {
         let mut names: Vec<String> = vec![];
         let mut types: Vec<String> = vec![];
         let mut nulls: Vec<String> = vec![];
+        let mut comments: Vec<String> = vec![];
         for field in schema.fields().iter() {
             names.push(field.name().to_string());
             types.push(format!("{:?}", field.data_type()));
@@ -50,10 +51,12 @@ impl Interpreter for DescribeTableInterpreter {
             } else {
                 "NO".to_string()
             });
+            comments.push(field.comment().cloned().unwrap_or_default());
         }
         let names: Vec<&str> = names.iter().map(|x| x.as_str()).collect();
         let types: Vec<&str> = types.iter().map(|x| x.as_str()).collect();
         let nulls: Vec<&str> = nulls.iter().map(|x| x.as_str()).collect();
+        let comments: Vec<&str> = comments.iter().map(|x| x.as_str()).collect();
 
         let desc_schema = self.plan.schema();
 
@@ -61,6 +64,7 @@ impl Interpreter for DescribeTableInterpreter {
             Series::new(names),
             Series::new(types),
             Series::new(nulls),
+            Series::new(comments),
         ]);
 
         Ok(Box::pin(DataBlockStream::create(desc_schema, None, vec![
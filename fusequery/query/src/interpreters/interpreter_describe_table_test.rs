@@ -18,7 +18,7 @@ async fn interpreter_describe_table_test() -> Result<()> {
     // Create table.
     {
         if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
-            .build_from_sql("create table default.a(a bigint, b int, c varchar(255), d smallint, e Date ) Engine = Null")?
+            .build_from_sql("create table default.a(a bigint comment 'the id', b int, c varchar(255), d smallint, e Date ) Engine = Null")?
         {
             let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
             let _ = executor.execute().await?;
@@ -36,15 +36,15 @@ async fn interpreter_describe_table_test() -> Result<()> {
             let stream = executor.execute().await?;
             let result = stream.try_collect::<Vec<_>>().await?;
             let expected = vec![
-                "+-------+--------+------+",
-                "| Field | Type   | Null |",
-                "+-------+--------+------+",
-                "| a     | Int64  | NO   |",
-                "| b     | Int32  | NO   |",
-                "| c     | Utf8   | NO   |",
-                "| d     | Int16  | NO   |",
-                "| e     | Date32 | NO   |",
-                "+-------+--------+------+",
+                "+-------+--------+------+---------+",
+                "| Field | Type   | Null | Comment |",
+                "+-------+--------+------+---------+",
+                "| a     | Int64  | NO   | the id  |",
+                "| b     | Int32  | NO   |         |",
+                "| c     | Utf8   | NO   |         |",
+                "| d     | Int16  | NO   |         |",
+                "| e     | Date32 | NO   |         |",
+                "+-------+--------+------+---------+",
             ];
             common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
         } else {
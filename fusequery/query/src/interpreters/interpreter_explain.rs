@@ -9,8 +9,10 @@ use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::ExplainPlan;
 use common_planners::ExplainType;
+use common_planners::PlanNode;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
+use futures::TryStreamExt;
 
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
@@ -36,6 +38,9 @@ impl Interpreter for ExplainInterpreter {
             ExplainType::Graph => self.explain_graph(),
             ExplainType::Syntax => self.explain_syntax(),
             ExplainType::Pipeline => self.explain_pipeline(),
+            ExplainType::PipelineGraph => self.explain_pipeline_graph(),
+            ExplainType::Statistics => self.explain_statistics(),
+            ExplainType::Analyze => self.explain_analyze().await,
         }?;
 
         Ok(Box::pin(DataBlockStream::create(schema, None, vec![block])))
@@ -51,10 +56,19 @@ impl ExplainInterpreter {
         Ok(Arc::new(ExplainInterpreter { ctx, explain }))
     }
 
+    /// The plan with the scatters, the stages on the cluster are labelled with its nodes.
     fn explain_graph(&self) -> Result<DataBlock> {
         let schema = self.schema();
         let plan = Optimizers::create(self.ctx.clone()).optimize(&self.explain.input)?;
-        let formatted_plan = Series::new(vec![format!("{}", plan.display_graphviz()).as_str()]);
+        let cluster_nodes = self
+            .ctx
+            .try_get_cluster()?
+            .get_nodes()?
+            .iter()
+            .map(|node| node.name.clone())
+            .collect::<Vec<_>>();
+        let formatted_plan = format!("{}", plan.display_graphviz_in_cluster(&cluster_nodes));
+        let formatted_plan = Series::new(vec![formatted_plan.as_str()]);
         Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
     }
 
@@ -65,6 +79,46 @@ impl ExplainInterpreter {
         Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
     }
 
+    /// One row for every optimizer, which is the plan after the optimizer with the estimated
+    /// rows and bytes of every node.
+    fn explain_statistics(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let passes = Optimizers::create(self.ctx.clone()).optimize_passes(&self.explain.input)?;
+        let formatted_passes = passes
+            .iter()
+            .map(|(name, plan)| format!("{}:\n{}", name, plan.display_indent_with_statistics()))
+            .collect::<Vec<_>>();
+        let formatted_passes = Series::new(
+            formatted_passes
+                .iter()
+                .map(|pass| pass.as_str())
+                .collect::<Vec<_>>(),
+        );
+        Ok(DataBlock::create_by_array(schema, vec![formatted_passes]))
+    }
+
+    /// Runs the query without the scatters and discards the result, the plan is annotated with
+    /// the rows, the bytes and the elapsed time of the blocks produced by every node.
+    async fn explain_analyze(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let plan = Optimizers::without_scatters(self.ctx.clone()).optimize(&self.explain.input)?;
+
+        self.ctx.enable_plan_profiles();
+        let mut pipeline = PipelineBuilder::create(self.ctx.clone()).build(&plan)?;
+        let stream = pipeline.execute().await?;
+        stream.try_for_each(|_| async { Ok(()) }).await?;
+        let profiles = self.ctx.take_plan_profiles();
+
+        let annotate = |node: &PlanNode| {
+            profiles
+                .get(&(node as *const PlanNode as usize))
+                .map(|profile| profile.to_string())
+        };
+        let formatted_plan = format!("{}", plan.display_indent_with(&annotate));
+        let formatted_plan = Series::new(vec![formatted_plan.as_str()]);
+        Ok(DataBlock::create_by_array(schema, vec![formatted_plan]))
+    }
+
     fn explain_pipeline(&self) -> Result<DataBlock> {
         let schema = self.schema();
         let plan = Optimizers::without_scatters(self.ctx.clone()).optimize(&self.explain.input)?;
@@ -73,4 +127,14 @@ impl ExplainInterpreter {
         let formatted_pipeline = Series::new(vec![format!("{:?}", pipeline).as_str()]);
         Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
     }
+
+    fn explain_pipeline_graph(&self) -> Result<DataBlock> {
+        let schema = self.schema();
+        let plan = Optimizers::without_scatters(self.ctx.clone()).optimize(&self.explain.input)?;
+        let pipeline_builder = PipelineBuilder::create(self.ctx.clone());
+        let pipeline = pipeline_builder.build(&plan)?;
+        let formatted_pipeline = format!("{}", pipeline.display_graphviz());
+        let formatted_pipeline = Series::new(vec![formatted_pipeline.as_str()]);
+        Ok(DataBlock::create_by_array(schema, vec![formatted_pipeline]))
+    }
 }
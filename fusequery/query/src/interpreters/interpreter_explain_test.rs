@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planners::*;
 use common_runtime::tokio;
@@ -43,3 +44,109 @@ async fn test_explain_interpreter() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_statistics_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+        "explain statistics select number from numbers_mt(10) where (number+1)=4 limit 1",
+    )? {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let block = &result[0];
+        assert_eq!(block.num_columns(), 1);
+
+        let plan = "\
+        \nLimit: 1 [estimated rows: 1, bytes: 8]\
+        \n  Projection: number:UInt64 [estimated rows: 5, bytes: 40]\
+        \n    Filter: ((number + 1) = 4) [estimated rows: 5, bytes: 40]\
+        \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80] [exact rows: 10, bytes: 80]";
+        let expected = [
+            "ConstantFolding:",
+            "PredicatePushdown:",
+            "ProjectionPushDown:",
+            "StatisticsExact:",
+            "DistinctAggregate:",
+            "TopN:",
+            "Scatters:",
+        ]
+        .iter()
+        .map(|name| DataValue::Utf8(Some(format!("{}{}", name, plan))))
+        .collect::<Vec<_>>();
+
+        assert_eq!(block.num_rows(), expected.len());
+        for (row, expected) in expected.into_iter().enumerate() {
+            assert_eq!(block.column(0).try_get(row)?, expected);
+        }
+    } else {
+        assert!(false)
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_analyze_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("explain analyze select number from numbers_mt(10) where (number+1)=4")?
+    {
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let block = &result[0];
+        assert_eq!(block.num_columns(), 1);
+        assert_eq!(block.num_rows(), 1);
+
+        // The bytes and the elapsed time vary, the nodes and their rows are checked.
+        let explain = block.column(0).try_get(0)?.to_string();
+        let lines = explain
+            .lines()
+            .map(|line| {
+                let (node, profile) = line.split_at(line.rfind(" [rows: ").unwrap());
+                let rows = profile.trim_start_matches(" [rows: ").split(',').next();
+                format!("{} [rows: {}]", node, rows.unwrap())
+            })
+            .collect::<Vec<_>>();
+        let expected = vec![
+            "Projection: number:UInt64 [rows: 1]",
+            "  Filter: ((number + 1) = 4) [rows: 1]",
+            "    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80] [rows: 10]",
+        ];
+        assert_eq!(lines, expected);
+        assert!(explain.contains(", elapsed: "));
+    } else {
+        assert!(false)
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_explain_pipeline_graph_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::Explain(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("explain pipeline graph select number from numbers_mt(10) limit 1")?
+    {
+        assert_eq!(plan.typ, ExplainType::PipelineGraph);
+        let executor = ExplainInterpreter::try_create(ctx, plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        let block = &result[0];
+        assert_eq!(block.num_rows(), 1);
+
+        let explain = block.column(0).try_get(0)?.to_string();
+        assert!(explain.starts_with("// Begin DataFuse GraphViz Pipeline"));
+        assert!(explain.contains("[shape=box, label=\"SourceTransform\"];"));
+        assert!(explain.contains("[shape=box, label=\"LimitTransform\"];"));
+        assert!(explain.contains("processor_0 -> "));
+    } else {
+        assert!(false)
+    }
+
+    Ok(())
+}
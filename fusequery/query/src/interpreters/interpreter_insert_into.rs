@@ -6,12 +6,17 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_planners::InsertIntoPlan;
+use common_runtime::tokio::sync::mpsc::channel;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+use tokio_stream::wrappers::ReceiverStream;
 
 use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterFactory;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::FuseQueryContextRef;
+use crate::sql::DefaultValues;
 
 pub struct InsertIntoInterpreter {
     ctx: FuseQueryContextRef,
@@ -32,8 +37,37 @@ impl Interpreter for InsertIntoInterpreter {
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let datasource = self.ctx.get_datasource();
-        let database = datasource.get_database(self.plan.db_name.as_str())?;
-        let table = database.get_table(self.plan.tbl_name.as_str())?;
+        let table = match &self.plan.table_function {
+            Some(name) => datasource.get_table_function(name)?.as_table(),
+            None => {
+                let database = datasource.get_database(self.plan.db_name.as_str())?;
+                database.get_table(self.plan.tbl_name.as_str())?
+            }
+        };
+
+        if let Some(select_plan) = &self.plan.select_plan {
+            // The rows of the SELECT are streamed into the table, the omitted columns take their default values.
+            // The table keeps nothing if the SELECT fails.
+            let interpreter =
+                InterpreterFactory::get(self.ctx.clone(), select_plan.as_ref().clone())?;
+            let mut select_stream = interpreter.execute().await?;
+
+            let default_values = DefaultValues::create(self.ctx.clone());
+            let schema = self.plan.schema();
+            let (tx, rx) = channel(2);
+            self.ctx.execute_task(async move {
+                while let Some(block) = select_stream.next().await {
+                    let block = block.and_then(|block| default_values.fill(&block, &schema));
+                    let failed = block.is_err();
+                    if tx.send(block).await.is_err() || failed {
+                        break;
+                    }
+                }
+            })?;
+            self.plan
+                .set_input_stream(Box::pin(ReceiverStream::new(rx)));
+        }
+
         table
             .append_data(self.ctx.clone(), self.plan.clone())
             .await?;
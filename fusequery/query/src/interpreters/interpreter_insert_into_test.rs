@@ -0,0 +1,75 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_insert_into_select_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    // Create table.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "create table default.a(a bigint, b bigint, c bigint default 7) Engine = Memory",
+        )? {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Insert into select, the SELECT columns are cast to the inserted columns by position.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone()).build_from_sql(
+            "insert into default.a(a, b) select number, number * 2 from numbers(3)",
+        )? {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "InsertIntoInterpreter");
+            let _ = executor.execute().await?;
+        } else {
+            assert!(false)
+        }
+    }
+
+    // Select.
+    {
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select * from default.a")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+---+---+",
+                "| a | b | c |",
+                "+---+---+---+",
+                "| 0 | 0 | 7 |",
+                "| 1 | 2 | 7 |",
+                "| 2 | 4 | 7 |",
+                "+---+---+---+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // The SELECT must have as many columns as inserted.
+    {
+        let result = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a(a, b) select number from numbers(3)");
+        assert_eq!(
+            "Code: 6, displayText = INSERT has 2 columns, but SELECT has 1 columns.",
+            format!("{}", result.err().unwrap())
+        );
+    }
+
+    Ok(())
+}
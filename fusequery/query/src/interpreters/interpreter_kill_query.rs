@@ -0,0 +1,39 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datavalues::DataSchema;
+use common_exception::Result;
+use common_planners::KillQueryPlan;
+use common_streams::DataBlockStream;
+use common_streams::SendableDataBlockStream;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::sessions::FuseQueryContextRef;
+
+pub struct KillQueryInterpreter {
+    ctx: FuseQueryContextRef,
+    plan: KillQueryPlan,
+}
+
+impl KillQueryInterpreter {
+    pub fn try_create(ctx: FuseQueryContextRef, plan: KillQueryPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(KillQueryInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for KillQueryInterpreter {
+    fn name(&self) -> &str {
+        "KillQueryInterpreter"
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.ctx.kill_query(&self.plan.query_id)?;
+        let schema = Arc::new(DataSchema::empty());
+        Ok(Box::pin(DataBlockStream::create(schema, None, vec![])))
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::stream::StreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_kill_query_interpreter() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+
+    let session = sessions.create_session("TestSession")?;
+    let query_ctx = session.create_context();
+    query_ctx.set_id("query_a".to_string())?;
+
+    // The queries of the other tenants are unknown.
+    let session_b = sessions.create_session("TestSession")?;
+    session_b.bind_tenant("tenant_b")?;
+    let plan =
+        PlanParser::create(session_b.create_context()).build_from_sql("kill query 'query_a'")?;
+    let result = InterpreterFactory::get(session_b.create_context(), plan)?
+        .execute()
+        .await;
+    assert_eq!(
+        "Code: 56, displayText = Unknown query: query_a.",
+        result.unwrap_err().to_string()
+    );
+
+    // Kill the query from another session.
+    let session2 = sessions.create_session("TestSession")?;
+    let ctx = session2.create_context();
+    if let PlanNode::KillQuery(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("kill query 'query_a'")?
+    {
+        let executor = KillQueryInterpreter::try_create(ctx.clone(), plan)?;
+        assert_eq!(executor.name(), "KillQueryInterpreter");
+        let mut stream = executor.execute().await?;
+        while let Some(_block) = stream.next().await {}
+    } else {
+        assert!(false)
+    }
+
+    // The killed query fails with the AbortedQuery error.
+    let plan = PlanParser::create(query_ctx.clone()).build_from_sql("SELECT * FROM numbers(10)")?;
+    let stream = InterpreterFactory::get(query_ctx.clone(), plan)?
+        .execute()
+        .await?;
+    let mut stream = query_ctx.try_create_abortable(stream)?;
+    let result = stream.next().await.unwrap();
+    assert_eq!(43, result.unwrap_err().code());
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("kill query 'unknown'")?;
+    let result = InterpreterFactory::get(ctx, plan)?.execute().await;
+    assert_eq!(
+        "Code: 56, displayText = Unknown query: unknown.",
+        result.unwrap_err().to_string()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::fs;
+
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::sql::*;
+
+#[tokio::test]
+async fn test_drop_partition_interpreter() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = env::temp_dir().join(format!("log_table_{}", uuid::Uuid::new_v4()));
+
+    // Create table.
+    {
+        let sql = format!(
+            "create table default.a(a bigint, b bigint) Engine = Log partition by a % 3 location = '{}'",
+            dir.display()
+        );
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(&sql)? {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // Insert into.
+    {
+        if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("insert into default.a values(1, 1), (2, 2), (3, 3), (4, 4)")?
+        {
+            let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let _ = executor.execute().await?;
+        }
+    }
+
+    // The partitions not matching the filter are pruned.
+    {
+        if let PlanNode::Select(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("select * from default.a where a % 3 = 1")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| 1 | 1 |",
+                "| 4 | 4 |",
+                "+---+---+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+
+        let plan = PlanParser::create(ctx.clone())
+            .build_from_sql("select * from default.a where a % 3 = 1 and b > 1")?;
+        let plan = format!("{:?}", plan);
+        assert!(plan.contains("scan partitions: [1]"), "{}", plan);
+        assert!(plan.contains("read_rows: 2"), "{}", plan);
+    }
+
+    // Drop partition.
+    {
+        if let PlanNode::DropPartition(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("alter table default.a drop partition 1")?
+        {
+            let executor = DropPartitionInterpreter::try_create(ctx.clone(), plan.clone())?;
+            assert_eq!(executor.name(), "DropPartitionInterpreter");
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec!["++", "++"];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // Select.
+    {
+        if let PlanNode::Select(plan) =
+            PlanParser::create(ctx.clone()).build_from_sql("select * from default.a")?
+        {
+            let executor = SelectInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let stream = executor.execute().await?;
+            let result = stream.try_collect::<Vec<_>>().await?;
+            let expected = vec![
+                "+---+---+",
+                "| a | b |",
+                "+---+---+",
+                "| 2 | 2 |",
+                "| 3 | 3 |",
+                "+---+---+",
+            ];
+            common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+        } else {
+            assert!(false)
+        }
+    }
+
+    // PARTITION BY is not supported by the Memory engine.
+    {
+        if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
+            .build_from_sql("create table default.b(a bigint) Engine = Memory partition by a % 3")?
+        {
+            let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
+            let result = executor.execute().await;
+            assert_eq!(
+                result.err().unwrap().message(),
+                "Table engine 'Memory' does not support PARTITION BY"
+            );
+        }
+    }
+
+    fs::remove_dir_all(dir)?;
+    Ok(())
+}
@@ -9,6 +9,8 @@ use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::SelectPlan;
+use common_streams::LimitResultStream;
+use common_streams::OverflowMode;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
 
@@ -28,6 +30,23 @@ impl SelectInterpreter {
     pub fn try_create(ctx: FuseQueryContextRef, select: SelectPlan) -> Result<InterpreterPtr> {
         Ok(Arc::new(SelectInterpreter { ctx, select }))
     }
+
+    /// Applies max_result_rows and max_result_bytes to the result which goes to the client.
+    fn limit_result(&self, stream: SendableDataBlockStream) -> Result<SendableDataBlockStream> {
+        let settings = self.ctx.get_settings();
+        let max_rows = settings.get_max_result_rows()? as usize;
+        let max_bytes = settings.get_max_result_bytes()? as usize;
+        if max_rows == 0 && max_bytes == 0 {
+            return Ok(stream);
+        }
+
+        let mode = match settings.get_result_overflow_mode()?.to_lowercase().as_str() {
+            "break" => OverflowMode::Break,
+            _ => OverflowMode::Throw,
+        };
+        let stream = LimitResultStream::try_create(stream, max_rows, max_bytes, mode)?;
+        Ok(Box::pin(stream))
+    }
 }
 
 #[async_trait::async_trait]
@@ -68,7 +87,8 @@ impl Interpreter for SelectInterpreter {
 
         let pipeline_builder = PipelineBuilder::create(self.ctx.clone());
         let mut in_local_pipeline = pipeline_builder.build(&scheduled_tasks.get_local_task())?;
-        in_local_pipeline.execute().await
+        let stream = in_local_pipeline.execute().await?;
+        self.limit_result(stream)
     }
 
     fn schema(&self) -> DataSchemaRef {
@@ -12,6 +12,7 @@ use common_planners::SettingPlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::functions::ContextVariable;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::FuseQueryContextRef;
@@ -38,11 +39,7 @@ impl Interpreter for SettingInterpreter {
         for var in plan.vars {
             match var.variable.to_lowercase().as_str() {
                 // To be compatible with some drivers
-                "sql_mode" | "autocommit" => {}
-                "max_threads" => {
-                    let threads: u64 = var.value.parse()?;
-                    self.ctx.get_settings().set_max_threads(threads)?;
-                }
+                name if ContextVariable::is_compatible(name) => {}
                 _ => {
                     self.ctx
                         .get_settings()
@@ -48,3 +48,25 @@ async fn test_setting_interpreter_error() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_setting_interpreter_bad_value() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    if let PlanNode::SetVariable(plan) =
+        PlanParser::create(ctx.clone()).build_from_sql("set max_threads=0")?
+    {
+        let executor = SettingInterpreter::try_create(ctx.clone(), plan)?;
+        if let Err(e) = executor.execute().await {
+            let expect =
+                "Code: 52, displayText = Value 0 of setting \"max_threads\" is less than the minimum 1.";
+            assert_eq!(expect, format!("{}", e));
+        } else {
+            assert!(false);
+        }
+    }
+
+    // The value is unchanged.
+    assert_eq!(ctx.get_settings().get_max_threads()?, 8);
+    Ok(())
+}
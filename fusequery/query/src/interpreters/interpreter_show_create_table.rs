@@ -51,11 +51,33 @@ impl Interpreter for ShowCreateTableInterpreter {
 
         let mut table_info = format!("CREATE TABLE `{}` (\n", name);
         for field in schema.fields().iter() {
-            let column = format!("  `{}` {},\n", field.name(), field.data_type());
+            let mut column = format!("  `{}` {}", field.name(), field.data_type());
+            if let Some(collation) = field.collation() {
+                column.push_str(format!(" COLLATE {}", collation).as_str());
+            }
+            if let Some(expr) = field.default_expr() {
+                column.push_str(format!(" DEFAULT {}", expr).as_str());
+            }
+            if let Some(comment) = field.comment() {
+                column.push_str(format!(" COMMENT '{}'", comment).as_str());
+            }
+            column.push_str(",\n");
             table_info.push_str(column.as_str());
         }
         let table_engine = format!(") ENGINE={}", engine);
         table_info.push_str(table_engine.as_str());
+        if let Some(partition_by) = table.partition_by() {
+            let table_partition_by = format!(" PARTITION BY {}", partition_by);
+            table_info.push_str(table_partition_by.as_str());
+        }
+        if let Some(cluster_by) = table.cluster_by() {
+            let table_cluster_by = format!(" {}", cluster_by);
+            table_info.push_str(table_cluster_by.as_str());
+        }
+        if !table.comment().is_empty() {
+            let table_comment = format!(" COMMENT='{}'", table.comment());
+            table_info.push_str(table_comment.as_str());
+        }
 
         let show_fields = vec![
             DataField::new("Table", DataType::Utf8, false),
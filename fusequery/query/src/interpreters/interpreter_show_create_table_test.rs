@@ -18,7 +18,7 @@ async fn interpreter_show_create_table_test() -> Result<()> {
     // Create table.
     {
         if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone())
-            .build_from_sql("create table default.a(a bigint, b int, c varchar(255), d smallint, e Date ) Engine = Null")?
+            .build_from_sql("create table default.a(a bigint comment 'id', b int, c varchar(255), d smallint, e Date ) Engine = Null comment = 'table a'")?
         {
             let executor = CreateTableInterpreter::try_create(ctx.clone(), plan.clone())?;
             let _ = executor.execute().await?;
@@ -35,17 +35,17 @@ async fn interpreter_show_create_table_test() -> Result<()> {
             let stream = executor.execute().await?;
             let result = stream.try_collect::<Vec<_>>().await?;
             let expected = vec![
-                "+-------+--------------------+",
-                "| Table | Create Table       |",
-                "+-------+--------------------+",
-                "| a     | CREATE TABLE `a` ( |",
-                "|       |   `a` Int64,       |",
-                "|       |   `b` Int32,       |",
-                "|       |   `c` Utf8,        |",
-                "|       |   `d` Int16,       |",
-                "|       |   `e` Date32,      |",
-                "|       | ) ENGINE=Null      |",
-                "+-------+--------------------+",
+                "+-------+---------------------------------+",
+                "| Table | Create Table                    |",
+                "+-------+---------------------------------+",
+                "| a     | CREATE TABLE `a` (              |",
+                "|       |   `a` Int64 COMMENT 'id',       |",
+                "|       |   `b` Int32,                    |",
+                "|       |   `c` Utf8,                     |",
+                "|       |   `d` Int16,                    |",
+                "|       |   `e` Date32,                   |",
+                "|       | ) ENGINE=Null COMMENT='table a' |",
+                "+-------+---------------------------------+",
             ];
             common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
         } else {
@@ -9,6 +9,8 @@ use common_planners::DropTablePlan;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 
+use crate::datasources::CatalogChange;
+use crate::interpreters::CatalogBroadcast;
 use crate::interpreters::Interpreter;
 use crate::interpreters::InterpreterPtr;
 use crate::sessions::FuseQueryContextRef;
@@ -35,6 +37,14 @@ impl Interpreter for DropTableInterpreter {
         let database = datasource.get_database(self.plan.db.as_str())?;
         database.drop_table(self.plan.clone()).await?;
 
+        if !database.is_local() {
+            let change = CatalogChange::DropTable {
+                db: self.plan.db.clone(),
+                table: self.plan.table.clone(),
+            };
+            CatalogBroadcast::broadcast(&self.ctx, change).await;
+        }
+
         Ok(Box::pin(DataBlockStream::create(
             self.plan.schema(),
             None,
@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod interpreter_copy_test;
 #[cfg(test)]
 mod interpreter_database_create_test;
 #[cfg(test)]
@@ -11,6 +13,12 @@ mod interpreter_describe_table_test;
 #[cfg(test)]
 mod interpreter_explain_test;
 #[cfg(test)]
+mod interpreter_insert_into_test;
+#[cfg(test)]
+mod interpreter_kill_query_test;
+#[cfg(test)]
+mod interpreter_partition_drop_test;
+#[cfg(test)]
 mod interpreter_select_test;
 #[cfg(test)]
 mod interpreter_setting_test;
@@ -21,37 +29,49 @@ mod interpreter_table_create_test;
 #[cfg(test)]
 mod interpreter_table_drop_test;
 #[cfg(test)]
+mod interpreter_table_truncate_test;
+#[cfg(test)]
 mod interpreter_use_database_test;
 #[cfg(test)]
 mod plan_scheduler_test;
 
+mod catalog_broadcast;
 mod interpreter;
+mod interpreter_copy;
 mod interpreter_database_create;
 mod interpreter_database_drop;
 mod interpreter_describe_table;
 mod interpreter_explain;
 mod interpreter_factory;
 mod interpreter_insert_into;
+mod interpreter_kill_query;
+mod interpreter_partition_drop;
 mod interpreter_select;
 mod interpreter_setting;
 mod interpreter_show_create_table;
 mod interpreter_table_create;
 mod interpreter_table_drop;
+mod interpreter_table_truncate;
 mod interpreter_use_database;
 #[allow(clippy::needless_range_loop)]
 mod plan_scheduler;
 
+pub use catalog_broadcast::CatalogBroadcast;
 pub use interpreter::Interpreter;
 pub use interpreter::InterpreterPtr;
+pub use interpreter_copy::CopyInterpreter;
 pub use interpreter_database_create::CreateDatabaseInterpreter;
 pub use interpreter_database_drop::DropDatabaseInterpreter;
 pub use interpreter_describe_table::DescribeTableInterpreter;
 pub use interpreter_explain::ExplainInterpreter;
 pub use interpreter_factory::InterpreterFactory;
 pub use interpreter_insert_into::InsertIntoInterpreter;
+pub use interpreter_kill_query::KillQueryInterpreter;
+pub use interpreter_partition_drop::DropPartitionInterpreter;
 pub use interpreter_select::SelectInterpreter;
 pub use interpreter_setting::SettingInterpreter;
 pub use interpreter_show_create_table::ShowCreateTableInterpreter;
 pub use interpreter_table_create::CreateTableInterpreter;
 pub use interpreter_table_drop::DropTableInterpreter;
+pub use interpreter_table_truncate::TruncateTableInterpreter;
 pub use interpreter_use_database::UseDatabaseInterpreter;
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::collections::hash_map::Entry;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::sync::Arc;
@@ -18,8 +19,10 @@ use common_planners::ExpressionPlan;
 use common_planners::Expressions;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
+use common_planners::Part;
 use common_planners::Partitions;
 use common_planners::PlanNode;
 use common_planners::ProjectionPlan;
@@ -30,13 +33,16 @@ use common_planners::SelectPlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
+use common_planners::Statistics;
 use common_planners::SubQueriesSetPlan;
+use common_planners::UnionPlan;
 use common_tracing::tracing;
 
 use crate::api::BroadcastAction;
 use crate::api::FlightAction;
 use crate::api::ShuffleAction;
 use crate::clusters::Node;
+use crate::datasources::ClusterBy;
 use crate::datasources::TablePtr;
 use crate::sessions::FuseQueryContext;
 use crate::sessions::FuseQueryContextRef;
@@ -55,6 +61,10 @@ pub struct Tasks {
 pub struct PlanScheduler {
     stage_id: String,
     cluster_nodes: Vec<String>,
+    cluster_nodes_labels: Vec<BTreeMap<String, String>>,
+    // The labels by which the parts are assigned to the nodes near their data, the first is
+    // preferred.
+    locality_labels: Vec<String>,
 
     local_pos: usize,
     nodes_plan: Vec<PlanNode>,
@@ -71,6 +81,7 @@ impl PlanScheduler {
         let mut local_pos = 0;
         let mut nodes_plan = Vec::new();
         let mut cluster_nodes_name = Vec::with_capacity(cluster_nodes.len());
+        let mut cluster_nodes_labels = Vec::with_capacity(cluster_nodes.len());
         for index in 0..cluster_nodes.len() {
             if cluster_nodes[index].is_local() {
                 local_pos = index;
@@ -78,8 +89,17 @@ impl PlanScheduler {
 
             nodes_plan.push(PlanNode::Empty(EmptyPlan::create()));
             cluster_nodes_name.push(cluster_nodes[index].name.clone());
+            cluster_nodes_labels.push(cluster_nodes[index].labels.clone());
         }
 
+        let locality_labels = context
+            .get_settings()
+            .get_read_locality_labels()?
+            .split(',')
+            .map(|label| label.trim().to_string())
+            .filter(|label| !label.is_empty())
+            .collect();
+
         Ok(PlanScheduler {
             local_pos,
             nodes_plan,
@@ -87,6 +107,8 @@ impl PlanScheduler {
             query_context: context,
             subqueries_expressions: vec![],
             cluster_nodes: cluster_nodes_name,
+            cluster_nodes_labels,
+            locality_labels,
             running_mode: RunningMode::Standalone,
         })
     }
@@ -300,6 +322,8 @@ impl PlanScheduler {
             PlanNode::Having(plan) => self.visit_having(plan, tasks),
             PlanNode::Expression(plan) => self.visit_expression(plan, tasks),
             PlanNode::SubQueryExpression(plan) => self.visit_subqueries_set(plan, tasks),
+            PlanNode::Union(plan) => self.visit_union(plan, tasks),
+            PlanNode::Join(plan) => self.visit_join(plan, tasks),
             _ => Err(ErrorCode::UnImplement("")),
         }
     }
@@ -703,6 +727,7 @@ impl PlanScheduler {
             schema: plan.schema.clone(),
             order_by: plan.order_by.clone(),
             input: Arc::new(self.nodes_plan[self.local_pos].clone()),
+            limit: plan.limit,
         });
     }
 
@@ -712,6 +737,7 @@ impl PlanScheduler {
                 schema: plan.schema.clone(),
                 order_by: plan.order_by.clone(),
                 input: Arc::new(self.nodes_plan[index].clone()),
+                limit: plan.limit,
             });
         }
     }
@@ -776,8 +802,12 @@ impl PlanScheduler {
         match table.is_local() {
             true => self.visit_local_data_source(plan),
             false => {
+                // The optimizer drops the shuffle of a bucketed table only if all the parts it
+                // read are written by bucket, the same check decides the placement of the parts.
+                let bucketed =
+                    table.cluster_by().is_some() && ClusterBy::all_parts_bucketed(&plan.parts);
                 let cluster_source = self.cluster_source(&plan.scan_plan, table)?;
-                self.visit_cluster_data_source(&cluster_source)
+                self.visit_cluster_data_source(&cluster_source, bucketed)
             }
         }
     }
@@ -788,19 +818,148 @@ impl PlanScheduler {
         Ok(())
     }
 
-    fn visit_cluster_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<()> {
+    fn visit_cluster_data_source(
+        &mut self,
+        plan: &ReadDataSourcePlan,
+        bucketed: bool,
+    ) -> Result<()> {
         self.running_mode = RunningMode::Cluster;
-        let nodes_parts = self.repartition(plan);
+        let nodes_parts = match bucketed {
+            false => self.repartition(plan),
+            true => self.repartition_by_bucket(plan)?,
+        };
 
         for index in 0..self.nodes_plan.len() {
             let mut read_plan = plan.clone();
             read_plan.parts = nodes_parts[index].clone();
+            read_plan.statistics = Self::node_statistics(plan, read_plan.parts.len());
             self.nodes_plan[index] = PlanNode::ReadSource(read_plan);
         }
 
         Ok(())
     }
 
+    /// The statistics of the parts which a node reads are proportional to the parts, so the
+    /// approx total rows of the progress of the nodes add up to the rows of the table.
+    fn node_statistics(plan: &ReadDataSourcePlan, node_parts: usize) -> Statistics {
+        let total_parts = plan.parts.len();
+        match node_parts == total_parts || total_parts == 0 {
+            true => plan.statistics.clone(),
+            false => Statistics::new_estimated(
+                plan.statistics.read_rows * node_parts / total_parts,
+                plan.statistics.read_bytes * node_parts / total_parts,
+            ),
+        }
+    }
+
+    fn visit_union(&mut self, plan: &UnionPlan, tasks: &mut Tasks) -> Result<()> {
+        // The inputs are all in cluster mode, or all converged to the local node.
+        let mut inputs_nodes_plan = Vec::with_capacity(plan.inputs.len());
+        let mut cluster_inputs = 0;
+        for input in &plan.inputs {
+            self.visit_plan_node(input.as_ref(), tasks)?;
+            if let RunningMode::Cluster = self.running_mode {
+                cluster_inputs += 1;
+            }
+            inputs_nodes_plan.push(self.nodes_plan.clone());
+        }
+
+        match cluster_inputs {
+            0 => self.visit_local_union(plan, &inputs_nodes_plan),
+            n if n == plan.inputs.len() => self.visit_cluster_union(plan, &inputs_nodes_plan),
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "The inputs of UNION must be converged in cluster mode",
+                ))
+            }
+        };
+        Ok(())
+    }
+
+    fn union_node_plan(
+        plan: &UnionPlan,
+        inputs_nodes_plan: &[Vec<PlanNode>],
+        index: usize,
+    ) -> PlanNode {
+        PlanNode::Union(UnionPlan {
+            inputs: inputs_nodes_plan
+                .iter()
+                .map(|nodes_plan| Arc::new(nodes_plan[index].clone()))
+                .collect(),
+            schema: plan.schema(),
+        })
+    }
+
+    fn visit_local_union(&mut self, plan: &UnionPlan, inputs_nodes_plan: &[Vec<PlanNode>]) {
+        self.running_mode = RunningMode::Standalone;
+        self.nodes_plan[self.local_pos] =
+            Self::union_node_plan(plan, inputs_nodes_plan, self.local_pos);
+    }
+
+    fn visit_cluster_union(&mut self, plan: &UnionPlan, inputs_nodes_plan: &[Vec<PlanNode>]) {
+        self.running_mode = RunningMode::Cluster;
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] = Self::union_node_plan(plan, inputs_nodes_plan, index);
+        }
+    }
+
+    fn visit_join(&mut self, plan: &JoinPlan, tasks: &mut Tasks) -> Result<()> {
+        // The inputs are both in cluster mode if the buckets of the join are co-located, or both
+        // converged to the local node.
+        self.visit_plan_node(plan.left.as_ref(), tasks)?;
+        let left_cluster = matches!(self.running_mode, RunningMode::Cluster);
+        let left_nodes_plan = self.nodes_plan.clone();
+        self.visit_plan_node(plan.right.as_ref(), tasks)?;
+        let right_cluster = matches!(self.running_mode, RunningMode::Cluster);
+        let right_nodes_plan = self.nodes_plan.clone();
+
+        match (left_cluster, right_cluster) {
+            (false, false) => self.visit_local_join(plan, &left_nodes_plan, &right_nodes_plan),
+            (true, true) => self.visit_cluster_join(plan, &left_nodes_plan, &right_nodes_plan),
+            _ => {
+                return Err(ErrorCode::LogicalError(
+                    "The inputs of JOIN must be converged in cluster mode",
+                ))
+            }
+        };
+        Ok(())
+    }
+
+    fn join_node_plan(
+        plan: &JoinPlan,
+        left_nodes_plan: &[PlanNode],
+        right_nodes_plan: &[PlanNode],
+        index: usize,
+    ) -> PlanNode {
+        let mut join = plan.clone();
+        join.set_inputs(&left_nodes_plan[index], &right_nodes_plan[index]);
+        PlanNode::Join(join)
+    }
+
+    fn visit_local_join(
+        &mut self,
+        plan: &JoinPlan,
+        left_nodes_plan: &[PlanNode],
+        right_nodes_plan: &[PlanNode],
+    ) {
+        self.running_mode = RunningMode::Standalone;
+        self.nodes_plan[self.local_pos] =
+            Self::join_node_plan(plan, left_nodes_plan, right_nodes_plan, self.local_pos);
+    }
+
+    fn visit_cluster_join(
+        &mut self,
+        plan: &JoinPlan,
+        left_nodes_plan: &[PlanNode],
+        right_nodes_plan: &[PlanNode],
+    ) {
+        self.running_mode = RunningMode::Cluster;
+        for index in 0..self.nodes_plan.len() {
+            self.nodes_plan[index] =
+                Self::join_node_plan(plan, left_nodes_plan, right_nodes_plan, index);
+        }
+    }
+
     fn visit_select(&mut self, plan: &SelectPlan, tasks: &mut Tasks) -> Result<()> {
         self.visit_plan_node(plan.input.as_ref(), tasks)?;
         match self.running_mode {
@@ -835,6 +994,10 @@ impl PlanScheduler {
     }
 
     fn repartition(&mut self, cluster_source: &ReadDataSourcePlan) -> Vec<Partitions> {
+        if self.has_locality(&cluster_source.parts) {
+            return self.repartition_by_locality(cluster_source);
+        }
+
         // We always put adjacent partitions in the same node
         let nodes = self.cluster_nodes.clone();
         let cluster_parts = &cluster_source.parts;
@@ -858,4 +1021,87 @@ impl PlanScheduler {
 
         nodes_parts
     }
+
+    // Whether some parts and some nodes have the locality labels, else the parts are assigned
+    // in order.
+    fn has_locality(&self, parts: &[Part]) -> bool {
+        let labeled = |labels: &BTreeMap<String, String>| {
+            self.locality_labels
+                .iter()
+                .any(|label| labels.contains_key(label))
+        };
+
+        self.cluster_nodes_labels.iter().any(labeled)
+            && parts.iter().any(|part| part.locations.iter().any(labeled))
+    }
+
+    fn repartition_by_locality(&mut self, cluster_source: &ReadDataSourcePlan) -> Vec<Partitions> {
+        // Every node takes at most its share of the parts, the parts are assigned to the nodes
+        // with the preferred label of one of their locations first.
+        let nodes_len = self.cluster_nodes.len();
+        let cluster_parts = &cluster_source.parts;
+        let capacity = (cluster_parts.len() + nodes_len - 1) / nodes_len;
+
+        let mut nodes_parts: Vec<Partitions> = vec![vec![]; nodes_len];
+        let mut remain_cluster_parts = vec![];
+        for part in cluster_parts {
+            let mut nearest = None;
+            for label in &self.locality_labels {
+                nearest = (0..nodes_len)
+                    .filter(|index| nodes_parts[*index].len() < capacity)
+                    .filter(|index| match self.cluster_nodes_labels[*index].get(label) {
+                        None => false,
+                        Some(value) => part
+                            .locations
+                            .iter()
+                            .any(|location| location.get(label) == Some(value)),
+                    })
+                    .min_by_key(|index| nodes_parts[*index].len());
+
+                if nearest.is_some() {
+                    break;
+                }
+            }
+
+            match nearest {
+                Some(index) => nodes_parts[index].push(part.clone()),
+                None => remain_cluster_parts.push(part.clone()),
+            }
+        }
+
+        // The parts without a near node are assigned to the nodes with the fewest parts
+        for part in remain_cluster_parts {
+            let index = (0..nodes_len)
+                .min_by_key(|index| nodes_parts[*index].len())
+                .unwrap_or(0);
+            nodes_parts[index].push(part);
+        }
+
+        nodes_parts
+    }
+
+    fn repartition_by_bucket(
+        &mut self,
+        cluster_source: &ReadDataSourcePlan,
+    ) -> Result<Vec<Partitions>> {
+        // The partitions of the same bucket are always in the same node, so that the rows with the
+        // same bucket column value are processed in one node without shuffle. The plan has no
+        // shuffle to fix a part without a bucket, e.g. written after the plan was optimized.
+        let nodes = self.cluster_nodes.clone();
+        let mut nodes_parts = vec![vec![]; nodes.len()];
+        for part in &cluster_source.parts {
+            let node_index = match ClusterBy::part_bucket(part) {
+                Some(bucket) => bucket as usize % nodes.len(),
+                None => {
+                    return Err(ErrorCode::LogicalError(format!(
+                        "The part {} of the bucketed read of {}.{} is not written by bucket",
+                        part.name, cluster_source.db, cluster_source.table
+                    )))
+                }
+            };
+            nodes_parts[node_index].push(part.clone());
+        }
+
+        Ok(nodes_parts)
+    }
 }
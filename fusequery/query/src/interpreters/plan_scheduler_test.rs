@@ -2,16 +2,27 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::any::Any;
 use std::sync::Arc;
 
-use common_datavalues::DataValue;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_flights::storage_api_impl::part_file_name;
 use common_planners::*;
 use common_runtime::tokio;
+use common_streams::SendableDataBlockStream;
 
 use crate::api::FlightAction;
+use crate::api::ShuffleAction;
+use crate::datasources::ClusterBy;
+use crate::datasources::Table;
+use crate::datasources::TableEngineRegistry;
 use crate::interpreters::plan_scheduler::PlanScheduler;
+use crate::optimizers::Optimizer;
+use crate::optimizers::ScattersOptimizer;
 use crate::sessions::FuseQueryContextRef;
+use crate::sql::PlanParser;
 use crate::tests::try_create_cluster_context;
 use crate::tests::ClusterNode;
 
@@ -316,6 +327,463 @@ async fn test_scheduler_plan_with_convergent_and_normal_stage() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scheduler_plan_with_cluster_join() -> Result<()> {
+    // The co-located join runs in every node, on the inputs which the node reads.
+    let context = create_env().await?;
+    let cluster_empty = PlanNode::Empty(EmptyPlan::cluster());
+    let join = PlanBuilder::from(&cluster_empty)
+        .nested_loop_join(JoinType::Inner, &cluster_empty, None)?
+        .build()?;
+    let scheduler = PlanScheduler::try_create(context.clone())?;
+    let scheduled_tasks = scheduler.reschedule(&PlanNode::Stage(StagePlan {
+        kind: StageKind::Convergent,
+        scatters_expr: Expression::create_literal(DataValue::UInt64(Some(0))),
+        input: Arc::new(join.clone()),
+    }))?;
+
+    let mut remote_plans = vec![];
+    for (_, remote_action) in scheduled_tasks.get_tasks()? {
+        match remote_action {
+            FlightAction::BroadcastAction(_) => assert!(false),
+            FlightAction::PrepareShuffleAction(action) => remote_plans.push(action.plan),
+        }
+    }
+    assert_eq!(remote_plans, vec![join.clone(), join]);
+
+    // The inputs of a join are in the same mode, the optimizer converges the others.
+    let mixed = PlanBuilder::from(&cluster_empty)
+        .nested_loop_join(JoinType::Inner, &PlanNode::Empty(EmptyPlan::create()), None)?
+        .build()?;
+    let scheduler = PlanScheduler::try_create(context)?;
+    match scheduler.reschedule(&mixed) {
+        Ok(_) => assert!(
+            false,
+            "The inputs of JOIN in different modes must be rejected"
+        ),
+        Err(e) => assert_eq!(
+            e.message(),
+            "The inputs of JOIN must be converged in cluster mode"
+        ),
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scheduler_plan_with_read_locality() -> Result<()> {
+    let _ = TableEngineRegistry::register("LocatedTest", LocatedTable::try_create);
+
+    // The parts are read by the nodes in the zones of their copies, the nodes of both zones
+    // hold part-1 and part-3, which are taken by the node with the fewest parts. part-5 has no
+    // copy in the zones of the nodes.
+    let context = try_create_cluster_context(&vec![
+        ClusterNode::create("dummy_local", 1, "localhost:9090").with_labels(&[("zone", "a")]),
+        ClusterNode::create("dummy", 1, "github.com:9090").with_labels(&[("zone", "b")]),
+    ])?;
+    create_located_table(&context).await?;
+    assert_eq!(
+        vec![
+            ("dummy_local".to_string(), vec![
+                "part-1", "part-2", "part-4"
+            ]),
+            ("dummy".to_string(), vec!["part-3", "part-5"]),
+        ],
+        scheduled_parts(&context).await?
+    );
+
+    // The adjacent parts are assigned in order without the locality labels.
+    context
+        .get_settings()
+        .update_settings("read_locality_labels", "".to_string())?;
+    assert_eq!(
+        vec![
+            ("dummy_local".to_string(), vec![
+                "part-1", "part-2", "part-5"
+            ]),
+            ("dummy".to_string(), vec!["part-3", "part-4"]),
+        ],
+        scheduled_parts(&context).await?
+    );
+
+    // The host is preferred to the zone, but a node takes at most its share of the parts, the
+    // rest of host h1 are read in its zone.
+    let context = try_create_cluster_context(&vec![
+        ClusterNode::create("dummy_local", 1, "localhost:9090")
+            .with_labels(&[("host", "h1"), ("zone", "a")]),
+        ClusterNode::create("dummy", 1, "github.com:9090")
+            .with_labels(&[("host", "h4"), ("zone", "a")]),
+    ])?;
+    create_located_table(&context).await?;
+    assert_eq!(
+        vec![
+            ("dummy_local".to_string(), vec![
+                "part-1", "part-2", "part-3"
+            ]),
+            ("dummy".to_string(), vec!["part-4", "part-5"]),
+        ],
+        scheduled_parts(&context).await?
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scheduler_plan_with_bucketed_parts() -> Result<()> {
+    let _ = TableEngineRegistry::register("BucketedPartsTest", BucketedPartsTable::try_create);
+    let context = create_env().await?;
+    create_bucketed_parts_table(&context, "bucketed", false).await?;
+    create_bucketed_parts_table(&context, "mixed", true).await?;
+
+    // The parts of a bucket are read by the node of the bucket.
+    let table = context.get_table("default", "bucketed")?;
+    let read_plan = table.read_plan(context.clone(), &ScanPlan::empty(), 1)?;
+    assert_eq!(
+        vec![
+            ("dummy_local".to_string(), vec![
+                "bucket-0-1.parquet".to_string(),
+                "bucket-2-3.parquet".to_string(),
+            ]),
+            ("dummy".to_string(), vec![
+                "bucket-1-2.parquet".to_string(),
+                "bucket-3-4.parquet".to_string(),
+            ]),
+        ],
+        scheduled_read_parts(&context, read_plan)?
+    );
+
+    // A read with a part without a bucket keeps its shuffle, and its parts are assigned in order.
+    let plan = PlanParser::create(context.clone())
+        .build_from_sql("SELECT SUM(b) FROM default.mixed GROUP BY a")?;
+    let optimized = ScattersOptimizer::create(context.clone()).optimize(&plan)?;
+    let actual = format!("{:?}", optimized);
+    assert!(
+        actual.contains("RedistributeStage[expr: sipHash(_group_by_key)]"),
+        "{}",
+        actual
+    );
+
+    let table = context.get_table("default", "mixed")?;
+    let read_plan = table.read_plan(context.clone(), &ScanPlan::empty(), 1)?;
+    assert_eq!(
+        vec![
+            ("dummy_local".to_string(), vec![
+                "bucket-0-1.parquet".to_string(),
+                "bucket-1-2.parquet".to_string(),
+                "part-5.parquet".to_string(),
+            ]),
+            ("dummy".to_string(), vec![
+                "bucket-2-3.parquet".to_string(),
+                "bucket-3-4.parquet".to_string(),
+            ]),
+        ],
+        scheduled_read_parts(&context, read_plan)?
+    );
+
+    // The parts of a read planned without the shuffle must all be in the buckets when they are
+    // scheduled, a part written in between is never read by an arbitrary node.
+    let mut read_plan = table.read_plan(context.clone(), &ScanPlan::empty(), 1)?;
+    read_plan
+        .parts
+        .retain(|part| ClusterBy::part_bucket(part).is_some());
+    match scheduled_read_parts(&context, read_plan) {
+        Ok(_) => assert!(false, "The part without a bucket must be rejected"),
+        Err(e) => assert_eq!(
+            e.message(),
+            "The part part-5.parquet of the bucketed read of default.mixed is not written by bucket"
+        ),
+    }
+
+    Ok(())
+}
+
+// The parts which the remote actions of a convergent stage over a read of the located table read.
+async fn scheduled_parts(
+    context: &FuseQueryContextRef,
+) -> Result<Vec<(String, Vec<&'static str>)>> {
+    let table = context.get_table("default", "located")?;
+    let read_plan = table.read_plan(context.clone(), &ScanPlan::empty(), 1)?;
+    let nodes_parts = scheduled_read_parts(context, read_plan)?
+        .into_iter()
+        .map(|(node, parts)| {
+            let parts = parts
+                .iter()
+                .map(|part| {
+                    PART_NAMES
+                        .iter()
+                        .find(|name| *name == part)
+                        .cloned()
+                        .unwrap()
+                })
+                .collect();
+            (node, parts)
+        })
+        .collect();
+    Ok(nodes_parts)
+}
+
+// The parts which the remote actions of a convergent stage over the read read.
+fn scheduled_read_parts(
+    context: &FuseQueryContextRef,
+    read_plan: ReadDataSourcePlan,
+) -> Result<Vec<(String, Vec<String>)>> {
+    let scheduler = PlanScheduler::try_create(context.clone())?;
+    let scheduled_tasks = scheduler.reschedule(&PlanNode::Stage(StagePlan {
+        kind: StageKind::Convergent,
+        scatters_expr: Expression::create_literal(DataValue::UInt64(Some(0))),
+        input: Arc::new(PlanNode::ReadSource(read_plan)),
+    }))?;
+
+    let mut nodes_parts = vec![];
+    for (node, remote_action) in scheduled_tasks.get_tasks()? {
+        match remote_action {
+            FlightAction::PrepareShuffleAction(ShuffleAction {
+                plan: PlanNode::ReadSource(plan),
+                ..
+            }) => {
+                let parts = plan.parts.iter().map(|part| part.name.clone()).collect();
+                nodes_parts.push((node.name.clone(), parts));
+            }
+            _ => assert!(false, "The remote actions must read the table"),
+        }
+    }
+    Ok(nodes_parts)
+}
+
+const PART_NAMES: [&str; 5] = ["part-1", "part-2", "part-3", "part-4", "part-5"];
+
+struct LocatedTable {
+    name: String,
+    schema: DataSchemaRef,
+}
+
+impl LocatedTable {
+    fn try_create(
+        _db: String,
+        name: String,
+        schema: DataSchemaRef,
+        _options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        Ok(Box::new(LocatedTable { name, schema }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for LocatedTable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn engine(&self) -> &str {
+        "LocatedTest"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    // Host h1 of zone a holds a copy of every part but part-5, host h2 of zone b holds the other
+    // copies of part-1 and part-3.
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        let location = |labels: &[(&str, &str)]| {
+            labels
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+        let locations = vec![
+            vec![
+                location(&[("host", "h2"), ("zone", "b")]),
+                location(&[("host", "h1"), ("zone", "a")]),
+            ],
+            vec![location(&[("host", "h1"), ("zone", "a")])],
+            vec![
+                location(&[("host", "h2"), ("zone", "b")]),
+                location(&[("host", "h1"), ("zone", "a")]),
+            ],
+            vec![location(&[("host", "h1"), ("zone", "a")])],
+            vec![location(&[("host", "h3"), ("zone", "c")])],
+        ];
+        let parts = PART_NAMES
+            .iter()
+            .zip(locations)
+            .map(|(name, locations)| Part {
+                name: name.to_string(),
+                version: 0,
+                locations,
+            })
+            .collect();
+        Ok(ReadDataSourcePlan {
+            db: "default".to_string(),
+            table: self.name.clone(),
+            schema: self.schema.clone(),
+            parts,
+            statistics: Statistics::new_estimated(5, 40),
+            description: "".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: true,
+        })
+    }
+
+    async fn read(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::UnImplement("LocatedTest table has no data"))
+    }
+}
+
+async fn create_located_table(ctx: &FuseQueryContextRef) -> Result<()> {
+    let database = ctx.get_datasource().get_database("default")?;
+    database
+        .create_table(CreateTablePlan {
+            if_not_exists: false,
+            db: "default".to_string(),
+            table: "located".to_string(),
+            schema: DataSchemaRefExt::create(vec![DataField::new("a", DataType::UInt64, false)]),
+            engine: "LocatedTest".to_string(),
+            options: TableOptions::new(),
+        })
+        .await
+}
+
+// A cluster table bucketed into 4 buckets by column a, with a part of each bucket and with a part
+// written without a bucket if the option unbucketed is set.
+struct BucketedPartsTable {
+    name: String,
+    schema: DataSchemaRef,
+    cluster_by: Option<ClusterBy>,
+    unbucketed: bool,
+}
+
+impl BucketedPartsTable {
+    fn try_create(
+        _db: String,
+        name: String,
+        schema: DataSchemaRef,
+        options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        Ok(Box::new(BucketedPartsTable {
+            name,
+            schema,
+            cluster_by: ClusterBy::try_create(&options)?,
+            unbucketed: options.contains_key("unbucketed"),
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for BucketedPartsTable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn engine(&self) -> &str {
+        "BucketedPartsTest"
+    }
+
+    fn cluster_by(&self) -> Option<&ClusterBy> {
+        self.cluster_by.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        let mut names = (0..4)
+            .map(|bucket| part_file_name(Some(bucket), &(bucket + 1).to_string()))
+            .collect::<Vec<_>>();
+        if self.unbucketed {
+            names.push(part_file_name(None, "part-5"));
+        }
+        let parts = names
+            .into_iter()
+            .map(|name| Part {
+                name,
+                version: 0,
+                locations: vec![],
+            })
+            .collect::<Vec<_>>();
+        Ok(ReadDataSourcePlan {
+            db: "default".to_string(),
+            table: self.name.clone(),
+            schema: self.schema.clone(),
+            statistics: Statistics::new_estimated(parts.len(), parts.len() * 16),
+            parts,
+            description: "".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: true,
+        })
+    }
+
+    async fn read(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::UnImplement(
+            "BucketedPartsTest table has no data",
+        ))
+    }
+}
+
+async fn create_bucketed_parts_table(
+    ctx: &FuseQueryContextRef,
+    table: &str,
+    unbucketed: bool,
+) -> Result<()> {
+    let mut options = [("cluster_by", "a"), ("buckets", "4")]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect::<TableOptions>();
+    if unbucketed {
+        options.insert("unbucketed".to_string(), "true".to_string());
+    }
+
+    let database = ctx.get_datasource().get_database("default")?;
+    database
+        .create_table(CreateTablePlan {
+            if_not_exists: false,
+            db: "default".to_string(),
+            table: table.to_string(),
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::UInt64, false),
+            ]),
+            engine: "BucketedPartsTest".to_string(),
+            options,
+        })
+        .await
+}
+
 async fn create_env() -> Result<FuseQueryContextRef> {
     try_create_cluster_context(&vec![
         ClusterNode::create("dummy_local", 1, "localhost:9090"),
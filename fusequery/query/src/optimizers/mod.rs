@@ -5,6 +5,12 @@
 #[cfg(test)]
 mod optimizer_constant_folding_test;
 #[cfg(test)]
+mod optimizer_distinct_aggregate_test;
+#[cfg(test)]
+mod optimizer_golden_test;
+#[cfg(test)]
+mod optimizer_predicate_pushdown_test;
+#[cfg(test)]
 mod optimizer_projection_push_down_test;
 #[cfg(test)]
 mod optimizer_scatters_test;
@@ -12,16 +18,24 @@ mod optimizer_scatters_test;
 mod optimizer_statistics_exact_test;
 #[cfg(test)]
 mod optimizer_test;
+#[cfg(test)]
+mod optimizer_top_n_test;
 
 mod optimizer;
 mod optimizer_constant_folding;
+mod optimizer_distinct_aggregate;
+mod optimizer_predicate_pushdown;
 mod optimizer_projection_push_down;
 mod optimizer_scatters;
 mod optimizer_statistics_exact;
+mod optimizer_top_n;
 
 pub use optimizer::Optimizer;
 pub use optimizer::Optimizers;
 pub use optimizer_constant_folding::ConstantFoldingOptimizer;
+pub use optimizer_distinct_aggregate::DistinctAggregateOptimizer;
+pub use optimizer_predicate_pushdown::PredicatePushdownOptimizer;
 pub use optimizer_projection_push_down::ProjectionPushDownOptimizer;
 pub use optimizer_scatters::ScattersOptimizer;
 pub use optimizer_statistics_exact::StatisticsExactOptimizer;
+pub use optimizer_top_n::TopNOptimizer;
@@ -8,8 +8,11 @@ use common_tracing::tracing;
 
 use crate::optimizers::optimizer_scatters::ScattersOptimizer;
 use crate::optimizers::ConstantFoldingOptimizer;
+use crate::optimizers::DistinctAggregateOptimizer;
+use crate::optimizers::PredicatePushdownOptimizer;
 use crate::optimizers::ProjectionPushDownOptimizer;
 use crate::optimizers::StatisticsExactOptimizer;
+use crate::optimizers::TopNOptimizer;
 use crate::sessions::FuseQueryContextRef;
 
 pub trait Optimizer {
@@ -34,8 +37,11 @@ impl Optimizers {
         Optimizers {
             inner: vec![
                 Box::new(ConstantFoldingOptimizer::create(ctx.clone())),
+                Box::new(PredicatePushdownOptimizer::create(ctx.clone())),
                 Box::new(ProjectionPushDownOptimizer::create(ctx.clone())),
-                Box::new(StatisticsExactOptimizer::create(ctx)),
+                Box::new(StatisticsExactOptimizer::create(ctx.clone())),
+                Box::new(DistinctAggregateOptimizer::create(ctx.clone())),
+                Box::new(TopNOptimizer::create(ctx)),
             ],
         }
     }
@@ -49,4 +55,15 @@ impl Optimizers {
         }
         Ok(plan)
     }
+
+    /// Returns the plan after every optimizer, named by the optimizer.
+    pub fn optimize_passes(&mut self, plan: &PlanNode) -> Result<Vec<(String, PlanNode)>> {
+        let mut plan = plan.clone();
+        let mut passes = Vec::with_capacity(self.inner.len());
+        for optimizer in self.inner.iter_mut() {
+            plan = optimizer.optimize(&plan)?;
+            passes.push((optimizer.name().to_string(), plan.clone()));
+        }
+        Ok(passes)
+    }
 }
@@ -42,6 +42,22 @@ mod tests {
                 \n  Expression: (6 + dummy):UInt64 (Before Projection)\
                 \n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
             },
+            Test {
+                name: "Projection right const sub expression",
+                query: "SELECT dummy + (1 + 2)",
+                expect: "\
+                Projection: (dummy + (1 + 2)):UInt32\
+                \n  Expression: (dummy + 3):UInt32 (Before Projection)\
+                \n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
+            },
+            Test {
+                name: "Filter const sub expression",
+                query: "SELECT dummy FROM system.one WHERE dummy > 1 + 1",
+                expect: "\
+                Projection: dummy:UInt8\
+                \n  Filter: (dummy > 2)\
+                \n    ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
+            },
             Test {
                 name: "Projection arithmetic const recursion",
                 query: "SELECT 1 + 2 + 3 / 3",
@@ -0,0 +1,193 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashSet;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::FuseQueryContextRef;
+
+/// Rewrites the distinct aggregates of a cluster query into two aggregates, the first one groups
+/// by the group by keys and the distinct arguments, the second one aggregates the distinct
+/// arguments of every group, e.g.
+///   SELECT COUNT(DISTINCT number) FROM t GROUP BY a
+/// is rewritten into
+///   SELECT COUNT(number) FROM (SELECT a, number FROM t GROUP BY a, number) GROUP BY a
+/// The first aggregate is shuffled by its keys, so every node only keeps a part of the distinct
+/// values instead of merging the whole hash-set states in one node.
+pub struct DistinctAggregateOptimizer {
+    ctx: FuseQueryContextRef,
+}
+
+struct DistinctAggregateImpl {
+    before_group_by_schema: Option<DataSchemaRef>,
+}
+
+impl DistinctAggregateImpl {
+    pub fn new() -> DistinctAggregateImpl {
+        DistinctAggregateImpl {
+            before_group_by_schema: None,
+        }
+    }
+
+    // The arguments shared by all the aggregates if they are all distinct, the aggregates of the
+    // distinct values skip the nulls and are not aware of the collations, so the nullable and
+    // the collated arguments are not rewritten.
+    fn distinct_arguments(plan: &AggregatorFinalPlan) -> Option<Vec<Expression>> {
+        let mut distinct_args: Option<&Vec<Expression>> = None;
+        for expr in &plan.aggr_expr {
+            match expr {
+                Expression::AggregateFunction {
+                    distinct: true,
+                    args,
+                    ..
+                } if !args.is_empty() => match distinct_args {
+                    Some(distinct_args) if distinct_args != args => return None,
+                    _ => distinct_args = Some(args),
+                },
+                _ => return None,
+            }
+        }
+
+        let distinct_args = distinct_args?;
+        for arg in distinct_args {
+            match plan
+                .schema_before_group_by
+                .field_with_name(&arg.column_name())
+            {
+                Ok(field) if !field.is_nullable() && field.collation().is_none() => {}
+                _ => return None,
+            }
+        }
+        Some(distinct_args.clone())
+    }
+
+    fn rewrite_distinct_aggregate(
+        input: &PlanNode,
+        plan: &AggregatorFinalPlan,
+        distinct_args: &[Expression],
+    ) -> Result<PlanNode> {
+        // Group by the group by keys and the distinct arguments.
+        let mut names = HashSet::new();
+        let mut keys = vec![];
+        for expr in plan.group_expr.iter().chain(distinct_args.iter()) {
+            if names.insert(expr.column_name()) {
+                keys.push(expr.clone());
+            }
+        }
+        let distinct_plan = PlanBuilder::from(input)
+            .aggregate_partial(&[], &keys)?
+            .aggregate_final(input.schema(), &[], &keys)?
+            .build()?;
+
+        // Aggregate the distinct values of every group.
+        let group_expr = plan
+            .group_expr
+            .iter()
+            .map(|expr| Expression::Column(expr.column_name()))
+            .collect::<Vec<_>>();
+        let aggr_expr = plan
+            .aggr_expr
+            .iter()
+            .map(|expr| match expr {
+                Expression::AggregateFunction { op, args, .. } => {
+                    Ok(Expression::AggregateFunction {
+                        op: op.clone(),
+                        distinct: false,
+                        args: args
+                            .iter()
+                            .map(|arg| Expression::Column(arg.column_name()))
+                            .collect(),
+                    })
+                }
+                _ => Err(ErrorCode::LogicalError(format!(
+                    "Logical error: {:?} is not a distinct aggregate",
+                    expr
+                ))),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Keep the names of the distinct aggregates for the plans above.
+        let mut projection = aggr_expr
+            .iter()
+            .zip(plan.aggr_expr.iter())
+            .map(|(expr, distinct_expr)| {
+                Expression::Column(expr.column_name()).alias(&distinct_expr.column_name())
+            })
+            .collect::<Vec<_>>();
+        projection.extend(group_expr.iter().cloned());
+
+        PlanBuilder::from(&distinct_plan)
+            .aggregate_partial(&aggr_expr, &group_expr)?
+            .aggregate_final(distinct_plan.schema(), &aggr_expr, &group_expr)?
+            .project(&projection)?
+            .build()
+    }
+}
+
+impl PlanRewriter for DistinctAggregateImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        match self.before_group_by_schema {
+            Some(_) => Err(ErrorCode::LogicalError(
+                "Logical error: before group by schema must be None",
+            )),
+            None => {
+                self.before_group_by_schema = Some(new_input.schema());
+                PlanBuilder::from(&new_input)
+                    .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+                    .build()
+            }
+        }
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+
+        match self.before_group_by_schema.take() {
+            None => Err(ErrorCode::LogicalError(
+                "Logical error: before group by schema must be Some",
+            )),
+            Some(schema_before_group_by) => match (&new_input, Self::distinct_arguments(plan)) {
+                (PlanNode::AggregatorPartial(partial), Some(distinct_args)) => {
+                    Self::rewrite_distinct_aggregate(&partial.input, plan, &distinct_args)
+                }
+                _ => PlanBuilder::from(&new_input)
+                    .aggregate_final(schema_before_group_by, &plan.aggr_expr, &plan.group_expr)?
+                    .build(),
+            },
+        }
+    }
+}
+
+impl Optimizer for DistinctAggregateOptimizer {
+    fn name(&self) -> &str {
+        "DistinctAggregate"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        if self.ctx.try_get_cluster()?.is_empty()? {
+            // Standalone mode, the hash-set states are merged in memory.
+            return Ok(plan.clone());
+        }
+
+        let mut visitor = DistinctAggregateImpl::new();
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl DistinctAggregateOptimizer {
+    pub fn create(ctx: FuseQueryContextRef) -> Self {
+        DistinctAggregateOptimizer { ctx }
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::DistinctAggregateOptimizer;
+use crate::optimizers::Optimizer;
+use crate::optimizers::Optimizers;
+use crate::sql::PlanParser;
+use crate::tests::try_create_cluster_context;
+use crate::tests::ClusterNode;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_distinct_aggregate_optimizer() -> Result<()> {
+    #[allow(dead_code)]
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Distinct aggregate without group by",
+            query: "SELECT count(distinct number) FROM numbers(100000000)",
+            expect: "\
+            Projection: count(distinct number):UInt64\
+            \n  Projection: count(number) as count(distinct number):UInt64\
+            \n    AggregatorFinal: groupBy=[[]], aggr=[[count(number)]]\
+            \n      AggregatorPartial: groupBy=[[]], aggr=[[count(number)]]\
+            \n        AggregatorFinal: groupBy=[[number]], aggr=[[]]\
+            \n          AggregatorPartial: groupBy=[[number]], aggr=[[]]\
+            \n            ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
+        },
+        Test {
+            name: "Distinct aggregates with group by",
+            query: "SELECT count(distinct number), sum(distinct number) FROM numbers(100000000) GROUP BY number % 3",
+            expect: "\
+            Projection: count(distinct number):UInt64, sum(distinct number):UInt64\
+            \n  Projection: count(number) as count(distinct number):UInt64, sum(number) as sum(distinct number):UInt64, (number % 3):UInt8\
+            \n    AggregatorFinal: groupBy=[[(number % 3)]], aggr=[[count(number), sum(number)]]\
+            \n      AggregatorPartial: groupBy=[[(number % 3)]], aggr=[[count(number), sum(number)]]\
+            \n        AggregatorFinal: groupBy=[[(number % 3), number]], aggr=[[]]\
+            \n          AggregatorPartial: groupBy=[[(number % 3), number]], aggr=[[]]\
+            \n            Expression: (number % 3):UInt8, number:UInt64 (Before GroupBy)\
+            \n              ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
+        },
+        Test {
+            name: "Distinct and non-distinct aggregates are not rewritten",
+            query: "SELECT count(distinct number), sum(number) FROM numbers(100000000)",
+            expect: "\
+            Projection: count(distinct number):UInt64, sum(number):UInt64\
+            \n  AggregatorFinal: groupBy=[[]], aggr=[[count(distinct number), sum(number)]]\
+            \n    AggregatorPartial: groupBy=[[]], aggr=[[count(distinct number), sum(number)]]\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
+        },
+    ];
+
+    let ctx = try_create_cluster_context(&vec![ClusterNode::create(
+        "Github",
+        1,
+        "www.github.com:9090",
+    )])?;
+    for test in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(test.query)?;
+        let mut optimizer = DistinctAggregateOptimizer::create(ctx.clone());
+        let optimized = optimizer.optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(test.expect, actual, "{:#?}", test.name);
+    }
+
+    // The distinct values are shuffled by the keys of the first aggregate.
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("SELECT count(distinct number) FROM numbers(100000000)")?;
+    let optimized = Optimizers::create(ctx).optimize(&plan)?;
+    let expect = "\
+    Projection: count(distinct number):UInt64\
+    \n  Projection: count(number) as count(distinct number):UInt64\
+    \n    AggregatorFinal: groupBy=[[]], aggr=[[count(number)]]\
+    \n      RedistributeStage[expr: 0]\
+    \n        AggregatorPartial: groupBy=[[]], aggr=[[count(number)]]\
+    \n          AggregatorFinal: groupBy=[[number]], aggr=[[]]\
+    \n            RedistributeStage[expr: sipHash(_group_by_key)]\
+    \n              AggregatorPartial: groupBy=[[number]], aggr=[[]]\
+    \n                ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]";
+    assert_eq!(expect, format!("{:?}", optimized));
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_distinct_aggregate_optimizer_standalone() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("SELECT count(distinct number) FROM numbers(100)")?;
+    let optimized = DistinctAggregateOptimizer::create(ctx).optimize(&plan)?;
+    assert_eq!(format!("{:?}", plan), format!("{:?}", optimized));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use common_exception::Result;
+use common_planners::PlanNode;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::InterpreterFactory;
+use crate::optimizers::Optimizers;
+use crate::sessions::FuseQueryContextRef;
+use crate::sql::PlanParser;
+use crate::tests::try_create_cluster_context;
+use crate::tests::try_create_context;
+use crate::tests::ClusterNode;
+
+/// The golden tests of the optimized plans, see tests/plans/README.md.
+///
+/// Every tests/plans/*.sql file has a *.plan golden file, which is the optimized plan of every
+/// SELECT of the file. The other statements (CREATE TABLE, INSERT...) are executed to set up
+/// the tables of the queries, they are not in the golden file. The files named *_cluster.sql
+/// run in a cluster of one node, as the stateless tests.
+///
+/// Run with BLESS=1 to rewrite the golden files with the actual plans.
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_optimizer_golden_plans() -> Result<()> {
+    let bless = env::var("BLESS").map(|v| v == "1").unwrap_or(false);
+
+    let dir = env::current_dir()?.join("../../tests/plans");
+    let mut files = fs::read_dir(&dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    files.retain(|path| path.extension().map_or(false, |ext| ext == "sql"));
+    files.sort();
+    assert!(!files.is_empty(), "No golden tests in {:?}", dir);
+
+    for file in files {
+        let actual = explain_file(&file).await?;
+        let golden = file.with_extension("plan");
+        if bless {
+            fs::write(&golden, &actual)?;
+            continue;
+        }
+
+        let expect = fs::read_to_string(&golden).unwrap_or_default();
+        assert_eq!(
+            expect,
+            actual,
+            "The plans of {:?} changed, run the test with BLESS=1 to update {:?} if the change is expected",
+            file,
+            golden
+        );
+    }
+
+    Ok(())
+}
+
+async fn explain_file(file: &Path) -> Result<String> {
+    let ctx = match is_cluster_file(file) {
+        true => {
+            try_create_cluster_context(&[ClusterNode::create("Github", 1, "www.github.com:9090")])?
+        }
+        false => try_create_context()?,
+    };
+
+    let mut output = String::new();
+    for query in split_queries(&fs::read_to_string(file)?) {
+        match explain_query(ctx.clone(), &query).await? {
+            None => {}
+            Some(plan) => output.push_str(&format!("{};\n----\n{}\n\n", query, plan)),
+        }
+    }
+    Ok(output)
+}
+
+/// Returns the optimized plan of the SELECT, or the error of the planning.
+/// The other statements are executed and have no plan.
+async fn explain_query(ctx: FuseQueryContextRef, query: &str) -> Result<Option<String>> {
+    let plan = match PlanParser::create(ctx.clone()).build_from_sql(query) {
+        Ok(plan) => plan,
+        Err(e) => return Ok(Some(format!("ERROR: {}", e))),
+    };
+
+    match plan {
+        PlanNode::Select(_) => match Optimizers::create(ctx).optimize(&plan) {
+            Ok(optimized) => Ok(Some(format!("{:?}", optimized))),
+            Err(e) => Ok(Some(format!("ERROR: {}", e))),
+        },
+        _ => {
+            let interpreter = InterpreterFactory::get(ctx, plan)?;
+            interpreter.execute().await?.try_collect::<Vec<_>>().await?;
+            Ok(None)
+        }
+    }
+}
+
+/// Splits the file into the statements by `;`, the lines starting with `--` are comments.
+/// The whitespaces of a statement are collapsed, so it is one line of the golden file.
+fn split_queries(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .split(';')
+        .map(|query| query.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|query| !query.is_empty())
+        .collect()
+}
+
+fn is_cluster_file(file: &Path) -> bool {
+    file.file_stem()
+        .and_then(|stem| stem.to_str())
+        .map_or(false, |stem| stem.ends_with("_cluster"))
+}
+
+#[test]
+fn test_split_queries() {
+    let content =
+        "-- A comment; with a semicolon\nSELECT 1;\n\nSELECT number\n  FROM numbers(10);\n";
+    assert_eq!(split_queries(content), vec![
+        "SELECT 1".to_string(),
+        "SELECT number FROM numbers(10)".to_string(),
+    ]);
+    assert!(is_cluster_file(Path::new("a_cluster.sql")));
+    assert!(!is_cluster_file(Path::new("cluster_a.sql")));
+}
@@ -0,0 +1,259 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::DataSchemaRef;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_planners::find_aggregate_exprs;
+use common_planners::find_column_exprs;
+use common_planners::resolve_aliases_to_exprs;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::Expression;
+use common_planners::FilterPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::ReadDataSourcePlan;
+use common_planners::RewriteHelper;
+use common_planners::SelectPlan;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::FuseQueryContextRef;
+
+/// Pushes the filters below the projections and the expressions, as close to the scans as
+/// possible, and into the push downs of the scans so that the partitioned tables only read the
+/// partitions which may match the filters, and the Parquet tables only the row groups. The
+/// filters above the scans are still applied, the tables only prune with them.
+///
+/// A filter on a non-deterministic expression, e.g. `now()`, is kept above it, as the expression
+/// computed again below the projection may have another value than the one filtered.
+pub struct PredicatePushdownOptimizer {
+    ctx: FuseQueryContextRef,
+}
+
+struct PredicatePushdownImpl<'a> {
+    ctx: &'a FuseQueryContextRef,
+}
+
+impl PlanRewriter for PredicatePushdownImpl<'_> {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AggregatorPartial(AggregatorPartialPlan {
+            input: Arc::new(self.rewrite_plan_node(plan.input.as_ref())?),
+            ..plan.clone()
+        }))
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AggregatorFinal(AggregatorFinalPlan {
+            input: Arc::new(self.rewrite_plan_node(plan.input.as_ref())?),
+            ..plan.clone()
+        }))
+    }
+
+    fn rewrite_filter(&mut self, plan: &FilterPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        self.push_down(
+            RewriteHelper::split_conjunctions(&plan.predicate),
+            &new_input,
+        )
+    }
+}
+
+impl PredicatePushdownImpl<'_> {
+    /// Applies the conjunctions to the input, each of them is pushed down as far as it can be.
+    fn push_down(&self, conjunctions: Vec<Expression>, input: &PlanNode) -> Result<PlanNode> {
+        match input {
+            // The filter with subqueries is kept above its subqueries.
+            PlanNode::Filter(plan)
+                if !matches!(plan.input.as_ref(), PlanNode::SubQueryExpression(_)) =>
+            {
+                let mut merged = RewriteHelper::split_conjunctions(&plan.predicate);
+                merged.extend(conjunctions);
+                self.push_down(merged, plan.input.as_ref())
+            }
+            PlanNode::Select(plan) => Ok(PlanNode::Select(SelectPlan {
+                input: Arc::new(self.push_down(conjunctions, plan.input.as_ref())?),
+            })),
+            PlanNode::Projection(plan) => {
+                self.push_down_through(conjunctions, &plan.expr, plan.input.as_ref(), |input| {
+                    PlanBuilder::from(input).project(&plan.expr)?.build()
+                })
+            }
+            PlanNode::Expression(plan) => {
+                self.push_down_through(conjunctions, &plan.exprs, plan.input.as_ref(), |input| {
+                    PlanBuilder::from(input)
+                        .expression(&plan.exprs, &plan.desc)?
+                        .build()
+                })
+            }
+            PlanNode::ReadSource(plan) => {
+                let read_source = self.push_down_to_scan(&conjunctions, plan)?;
+                Self::filter(conjunctions, &read_source)
+            }
+            _ => Self::filter(conjunctions, input),
+        }
+    }
+
+    /// The output columns of the exprs are replaced by the expressions computing them, the
+    /// conjunctions which then only reference the columns of the input are pushed below.
+    fn push_down_through<F>(
+        &self,
+        conjunctions: Vec<Expression>,
+        exprs: &[Expression],
+        input: &PlanNode,
+        rebuild: F,
+    ) -> Result<PlanNode>
+    where
+        F: Fn(&PlanNode) -> Result<PlanNode>,
+    {
+        let outputs = exprs
+            .iter()
+            .filter_map(|expr| match expr {
+                Expression::Alias(name, expr) => Some((name.clone(), expr.as_ref().clone())),
+                Expression::Column(_) | Expression::Wildcard => None,
+                expr => Some((expr.column_name(), expr.clone())),
+            })
+            .collect::<HashMap<_, _>>();
+
+        let input_schema = input.schema();
+        let mut pushed = vec![];
+        let mut kept = vec![];
+        for conjunction in conjunctions {
+            let rewritten = resolve_aliases_to_exprs(&conjunction, &outputs)?;
+            match Self::can_push_down(&rewritten, &input_schema)? {
+                true => pushed.push(rewritten),
+                false => kept.push(conjunction),
+            }
+        }
+
+        let new_input = match pushed.is_empty() {
+            true => input.clone(),
+            false => self.push_down(pushed, input)?,
+        };
+        Self::filter(kept, &rebuild(&new_input)?)
+    }
+
+    /// Adds the conjunctions to the filters of the scan, the tables which prune with the filters,
+    /// e.g. the partitioned tables, are planned again to prune their parts with them.
+    fn push_down_to_scan(
+        &self,
+        conjunctions: &[Expression],
+        plan: &ReadDataSourcePlan,
+    ) -> Result<PlanNode> {
+        let mut scan_plan = plan.scan_plan.as_ref().clone();
+        let mut filters = scan_plan
+            .push_downs
+            .filters
+            .iter()
+            .flat_map(RewriteHelper::split_conjunctions)
+            .collect::<Vec<_>>();
+
+        let mut changed = false;
+        for conjunction in conjunctions {
+            if !filters.contains(conjunction)
+                && Self::can_push_down(conjunction, &scan_plan.table_schema)?
+            {
+                filters.push(conjunction.clone());
+                changed = true;
+            }
+        }
+        if !changed {
+            return Ok(PlanNode::ReadSource(plan.clone()));
+        }
+        scan_plan.push_downs.filters = filters;
+
+        // The table functions are not in the catalog, and they do not prune with the filters.
+        let table = match self.ctx.get_table(&plan.db, &plan.table) {
+            Ok(table) if table.prunes_by_filters() => table,
+            _ => {
+                return Ok(PlanNode::ReadSource(ReadDataSourcePlan {
+                    scan_plan: Arc::new(scan_plan),
+                    ..plan.clone()
+                }))
+            }
+        };
+
+        let pruned_plan = table.read_plan(
+            self.ctx.clone(),
+            &scan_plan,
+            self.ctx.get_settings().get_max_threads()? as usize,
+        )?;
+        Ok(PlanNode::ReadSource(ReadDataSourcePlan {
+            parts: pruned_plan.parts,
+            statistics: pruned_plan.statistics,
+            scan_plan: pruned_plan.scan_plan,
+            ..plan.clone()
+        }))
+    }
+
+    /// The subqueries are planned with the filter, and the aggregates are not computed below it,
+    /// neither are the non-deterministic functions.
+    fn can_push_down(expr: &Expression, schema: &DataSchemaRef) -> Result<bool> {
+        let exprs = [expr.clone()];
+        if !RewriteHelper::collect_exprs_sub_queries(&exprs)?.is_empty()
+            || !find_aggregate_exprs(&exprs).is_empty()
+            || !Self::is_deterministic(expr)?
+        {
+            return Ok(false);
+        }
+
+        Ok(find_column_exprs(&exprs).iter().all(|column| match column {
+            Expression::Column(name) => schema.index_of(name).is_ok(),
+            _ => false,
+        }))
+    }
+
+    fn is_deterministic(expr: &Expression) -> Result<bool> {
+        let op = match expr {
+            Expression::UnaryExpression { op, .. }
+            | Expression::BinaryExpression { op, .. }
+            | Expression::ScalarFunction { op, .. } => Some(op),
+            _ => None,
+        };
+        if let Some(op) = op {
+            if !FunctionFactory::get(op)?.is_deterministic() {
+                return Ok(false);
+            }
+        }
+
+        for child in RewriteHelper::expression_plan_children(expr)? {
+            if !Self::is_deterministic(&child)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn filter(conjunctions: Vec<Expression>, input: &PlanNode) -> Result<PlanNode> {
+        let mut conjunctions = conjunctions.into_iter();
+        match conjunctions.next() {
+            None => Ok(input.clone()),
+            Some(first) => {
+                let predicate = conjunctions.fold(first, |acc, expr| acc.and(expr));
+                PlanBuilder::from(input).filter(predicate)?.build()
+            }
+        }
+    }
+}
+
+impl Optimizer for PredicatePushdownOptimizer {
+    fn name(&self) -> &str {
+        "PredicatePushdown"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = PredicatePushdownImpl { ctx: &self.ctx };
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl PredicatePushdownOptimizer {
+    pub fn create(ctx: FuseQueryContextRef) -> Self {
+        PredicatePushdownOptimizer { ctx }
+    }
+}
@@ -0,0 +1,114 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::interpreters::*;
+use crate::optimizers::*;
+use crate::sql::*;
+
+#[test]
+fn test_predicate_pushdown_optimizer() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Filter below the projection of the subquery",
+            query: "SELECT * FROM (SELECT number + 1 AS a FROM numbers_mt(10)) WHERE a > 5",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Projection: (number + 1) as a:UInt64\
+            \n    Expression: (number + 1):UInt64 (Before Projection)\
+            \n      Filter: ((number + 1) > 5)\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+        },
+        Test {
+            name: "Filters merged below the subquery",
+            query: "SELECT * FROM (SELECT number AS a FROM numbers_mt(10) WHERE number > 1) WHERE a < 5",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Projection: number as a:UInt64\
+            \n    Filter: ((number > 1) and (number < 5))\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+        },
+        Test {
+            name: "Filter kept above the limit of the subquery",
+            query: "SELECT * FROM (SELECT number AS a FROM numbers_mt(10) LIMIT 3) WHERE a > 1",
+            expect: "\
+            Projection: a:UInt64\
+            \n  Filter: (a > 1)\
+            \n    Limit: 3\
+            \n      Projection: number as a:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+        },
+    ];
+
+    for t in tests {
+        let ctx = crate::tests::try_create_context()?;
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(t.query)?;
+        let optimized = PredicatePushdownOptimizer::create(ctx).optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(t.expect, actual, "{}", t.name);
+    }
+
+    // The filter on now() is kept above the projection computing it, the other one is pushed.
+    let ctx = crate::tests::try_create_context()?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "SELECT * FROM (SELECT number, now() AS t FROM numbers_mt(10)) WHERE t = t AND number > 1",
+    )?;
+    let optimized = PredicatePushdownOptimizer::create(ctx).optimize(&plan)?;
+    let actual = format!("{:?}", optimized);
+    let kept = actual.find("Filter: (t = t)");
+    let projection = actual.find("now() as t");
+    let pushed = actual.find("Filter: (number > 1)");
+    assert!(kept.is_some() && kept < projection, "{}", actual);
+    assert!(pushed.is_some() && projection < pushed, "{}", actual);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_predicate_pushdown_optimizer_partitions() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let dir = env::temp_dir().join(format!("log_table_{}", uuid::Uuid::new_v4()));
+
+    let sql = format!(
+        "create table default.a(a bigint, b bigint) Engine = Log partition by a % 3 location = '{}'",
+        dir.display()
+    );
+    if let PlanNode::CreateTable(plan) = PlanParser::create(ctx.clone()).build_from_sql(&sql)? {
+        let executor = CreateTableInterpreter::try_create(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    if let PlanNode::InsertInto(plan) = PlanParser::create(ctx.clone())
+        .build_from_sql("insert into default.a values(1, 1), (2, 2), (3, 3), (4, 4)")?
+    {
+        let executor = InsertIntoInterpreter::try_create(ctx.clone(), plan)?;
+        let _ = executor.execute().await?;
+    }
+
+    // The filter of the outer query prunes the partitions of the subquery.
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select * from (select a, b from default.a) where a % 3 = 1 and b > 1")?;
+    let actual = format!("{:?}", plan);
+    assert!(actual.contains("scan partitions: [3]"), "{}", actual);
+
+    let optimized = PredicatePushdownOptimizer::create(ctx).optimize(&plan)?;
+    let actual = format!("{:?}", optimized);
+    assert!(actual.contains("scan partitions: [1]"), "{}", actual);
+    assert!(actual.contains("read_rows: 2"), "{}", actual);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
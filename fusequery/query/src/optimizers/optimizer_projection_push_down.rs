@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use common_datavalues::DataField;
 use common_datavalues::DataSchema;
@@ -15,13 +16,16 @@ use common_planners::AggregatorPartialPlan;
 use common_planners::EmptyPlan;
 use common_planners::Expression;
 use common_planners::FilterPlan;
+use common_planners::JoinPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
 use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::RewriteHelper;
+use common_planners::ScanPlan;
 use common_planners::SortPlan;
+use common_planners::UnionPlan;
 
 use crate::optimizers::Optimizer;
 use crate::sessions::FuseQueryContextRef;
@@ -89,29 +93,61 @@ impl PlanRewriter for ProjectionPushDownImpl {
             .build()
     }
 
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        self.collect_column_names_from_expr_vec(plan.left_keys.as_slice())?;
+        self.collect_column_names_from_expr_vec(plan.right_keys.as_slice())?;
+        if let Some(condition) = &plan.condition {
+            self.collect_column_names_from_expr(condition)?;
+        }
+        let new_left = self.rewrite_plan_node(&plan.left)?;
+        let new_right = self.rewrite_plan_node(&plan.right)?;
+        if plan.is_nested_loop() {
+            return PlanBuilder::from(&new_left)
+                .nested_loop_join(plan.join_type, &new_right, plan.condition.clone())?
+                .build();
+        }
+
+        PlanBuilder::from(&new_left)
+            .join(
+                plan.join_type,
+                &new_right,
+                &self.rewrite_exprs(&new_left.schema(), &plan.left_keys)?,
+                &self.rewrite_exprs(&new_right.schema(), &plan.right_keys)?,
+            )?
+            .build()
+    }
+
+    fn rewrite_union(&mut self, plan: &UnionPlan) -> Result<PlanNode> {
+        // The columns of the inputs are matched by their positions, so every input keeps all its
+        // columns, and only the columns below the projection of the input are pruned.
+        let new_inputs = plan
+            .inputs
+            .iter()
+            .map(|input| ProjectionPushDownImpl::new().rewrite_plan_node(input))
+            .collect::<Result<Vec<_>>>()?;
+        PlanBuilder::from(&new_inputs[0])
+            .union_all(&new_inputs[1..])?
+            .build()
+    }
+
     fn rewrite_sort(&mut self, plan: &SortPlan) -> Result<PlanNode> {
         self.collect_column_names_from_expr_vec(plan.order_by.as_slice())?;
         let new_input = self.rewrite_plan_node(&plan.input)?;
         PlanBuilder::from(&new_input)
-            .sort(&self.rewrite_exprs(&new_input.schema(), &plan.order_by)?)?
+            .sort_with_limit(
+                &self.rewrite_exprs(&new_input.schema(), &plan.order_by)?,
+                plan.limit,
+            )?
             .build()
     }
 
     fn rewrite_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<PlanNode> {
-        // TODO: rewrite scan
-        self.get_projected_schema(plan.schema.as_ref())
-            .map(|projected_schema| {
-                PlanNode::ReadSource(ReadDataSourcePlan {
-                    db: plan.db.to_string(),
-                    table: plan.table.to_string(),
-                    schema: projected_schema,
-                    parts: plan.parts.clone(),
-                    statistics: plan.statistics.clone(),
-                    description: plan.description.to_string(),
-                    scan_plan: plan.scan_plan.clone(),
-                    remote: plan.remote,
-                })
-            })
+        let projected_schema = self.get_projected_schema(plan.schema.as_ref())?;
+        Ok(PlanNode::ReadSource(ReadDataSourcePlan {
+            schema: projected_schema.clone(),
+            scan_plan: Arc::new(Self::project_scan(&plan.scan_plan, projected_schema)),
+            ..plan.clone()
+        }))
     }
 }
 
@@ -180,6 +216,27 @@ impl ProjectionPushDownImpl {
         }
         Ok(DataSchemaRefExt::create(projected_fields))
     }
+
+    // The scan only asks the table for the projected columns, so the remote tables do not
+    // transfer the other columns from the store.
+    fn project_scan(scan_plan: &ScanPlan, projected_schema: DataSchemaRef) -> ScanPlan {
+        let projection = projected_schema
+            .fields()
+            .iter()
+            .map(|field| scan_plan.table_schema.index_of(field.name()))
+            .collect::<Result<Vec<_>>>();
+
+        match projection {
+            Ok(projection) => {
+                let mut scan_plan = scan_plan.clone();
+                scan_plan.projected_schema = projected_schema;
+                scan_plan.push_downs.projection = Some(projection);
+                scan_plan
+            }
+            // The scan does not know all the columns, the table reads them as before.
+            Err(_) => scan_plan.clone(),
+        }
+    }
 }
 
 impl Optimizer for ProjectionPushDownOptimizer {
@@ -72,6 +72,53 @@ fn test_projection_push_down_optimizer_group_by() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_projection_push_down_optimizer_join() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "select a.value, b.value from system.settings as a join system.settings as b on a.name = b.name",
+    )?;
+
+    let mut project_push_down = ProjectionPushDownOptimizer::create(ctx);
+    let optimized = project_push_down.optimize(&plan)?;
+
+    // The keys of the join are read with the projected columns.
+    let expect = "\
+        Projection: value:Utf8, b.value:Utf8\
+        \n  Join: INNER, on: [name = b.name]\
+        \n    ReadDataSource: scan partitions: [1], scan schema: [name:Utf8, value:Utf8], statistics: [read_rows: 0, read_bytes: 0]\
+        \n    Projection: name as b.name:Utf8, value as b.value:Utf8, default_value as b.default_value:Utf8, description as b.description:Utf8, min as b.min:Utf8, max as b.max:Utf8, tier as b.tier:Utf8, requires_restart as b.requires_restart:Boolean\
+        \n      ReadDataSource: scan partitions: [1], scan schema: [name:Utf8, value:Utf8, default_value:Utf8, description:Utf8, min:Utf8, max:Utf8, tier:Utf8, requires_restart:Boolean], statistics: [read_rows: 0, read_bytes: 0]";
+
+    let actual = format!("{:?}", optimized);
+    assert_eq!(expect, actual);
+    Ok(())
+}
+
+#[test]
+fn test_projection_push_down_optimizer_scan() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("select value from system.settings where name = 'max_threads'")?;
+
+    let mut project_push_down = ProjectionPushDownOptimizer::create(ctx);
+    let mut optimized = project_push_down.optimize(&plan)?;
+    while !matches!(optimized, PlanNode::ReadSource(_)) {
+        optimized = optimized.input(0).as_ref().clone();
+    }
+
+    // The scan only asks the table for the columns of the query.
+    if let PlanNode::ReadSource(read_source) = optimized {
+        let scan_plan = read_source.scan_plan;
+        assert_eq!(Some(vec![0, 1]), scan_plan.push_downs.projection);
+        assert_eq!(read_source.schema, scan_plan.projected_schema);
+        assert_eq!(8, scan_plan.table_schema.fields().len());
+    }
+    Ok(())
+}
+
 #[test]
 fn test_projection_push_down_optimizer_2() -> Result<()> {
     let ctx = crate::tests::try_create_context()?;
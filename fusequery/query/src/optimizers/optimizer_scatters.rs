@@ -12,16 +12,22 @@ use common_planners::AggregatorFinalPlan;
 use common_planners::AggregatorPartialPlan;
 use common_planners::BroadcastPlan;
 use common_planners::Expression;
+use common_planners::HavingPlan;
+use common_planners::JoinPlan;
+use common_planners::JoinType;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
+use common_planners::ProjectionPlan;
 use common_planners::ReadDataSourcePlan;
 use common_planners::SortPlan;
 use common_planners::StageKind;
 use common_planners::StagePlan;
+use common_planners::UnionPlan;
 
+use crate::datasources::ClusterBy;
 use crate::optimizers::Optimizer;
 use crate::sessions::FuseQueryContext;
 use crate::sessions::FuseQueryContextRef;
@@ -40,6 +46,9 @@ struct ScattersOptimizerImpl {
     ctx: FuseQueryContextRef,
     running_mode: RunningMode,
     before_group_by_schema: Option<DataSchemaRef>,
+    // The bucketing if the rows are read from a bucketed table, the rows with the same value of the
+    // bucket column are in the same node, the column is renamed by the aliases above the read.
+    bucketing: Option<ClusterBy>,
 
     // temporary node
     input: Option<Arc<PlanNode>>,
@@ -51,6 +60,7 @@ impl ScattersOptimizerImpl {
             ctx,
             running_mode: RunningMode::Standalone,
             before_group_by_schema: None,
+            bucketing: None,
             input: None,
         }
     }
@@ -73,6 +83,10 @@ impl ScattersOptimizerImpl {
         // Keep running in cluster mode
         self.running_mode = RunningMode::Cluster;
 
+        if self.is_bucketed_by(&plan.group_expr) {
+            return self.colocated_aggregate(plan);
+        }
+
         match self.input.take() {
             None => Err(ErrorCode::LogicalError("Cluster aggr input is None")),
             Some(input) => Self::normal_shuffle_stage(
@@ -84,6 +98,59 @@ impl ScattersOptimizerImpl {
         }
     }
 
+    // The groups are complete in every node if the bucket column is one of the group by keys.
+    fn is_bucketed_by(&self, group_expr: &[Expression]) -> bool {
+        match &self.bucketing {
+            None => false,
+            Some(bucketing) => group_expr
+                .iter()
+                .any(|expr| expr == &Expression::Column(bucketing.column.clone())),
+        }
+    }
+
+    // The join is co-located if both inputs are bucketed into the same number of buckets by the
+    // columns of a pair of its keys. The matched rows have the same bucket columns, so they are in
+    // the buckets of the same number, whose parts are read by the same node.
+    fn is_colocated_join(
+        plan: &JoinPlan,
+        left: &ScattersOptimizerImpl,
+        right: &ScattersOptimizerImpl,
+    ) -> bool {
+        match (
+            &left.running_mode,
+            &right.running_mode,
+            &left.bucketing,
+            &right.bucketing,
+        ) {
+            (
+                RunningMode::Cluster,
+                RunningMode::Cluster,
+                Some(left_bucketing),
+                Some(right_bucketing),
+            ) if left_bucketing.buckets == right_bucketing.buckets => {
+                let left_column = Expression::Column(left_bucketing.column.clone());
+                let right_column = Expression::Column(right_bucketing.column.clone());
+                plan.left_keys
+                    .iter()
+                    .zip(plan.right_keys.iter())
+                    .any(|(left_key, right_key)| {
+                        left_key == &left_column && right_key == &right_column
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn colocated_aggregate(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        // No shuffle for the co-located groups, keep aggregating in every node
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Cluster aggr input is None")),
+            Some(input) => PlanBuilder::from(input.as_ref())
+                .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+                .build(),
+        }
+    }
+
     fn cluster_aggregate(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
         match plan.group_expr.len() {
             0 => self.cluster_aggregate_without_key(plan),
@@ -100,12 +167,43 @@ impl ScattersOptimizerImpl {
         }
     }
 
+    fn cluster_having(&mut self, plan: &HavingPlan) -> Result<PlanNode> {
+        // Having we convergent it in local node, after the groups are aggregated
+        self.running_mode = RunningMode::Standalone;
+
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Cluster having input is None")),
+            Some(input) => Self::convergent_shuffle_stage_builder(input)
+                .having(plan.predicate.clone())?
+                .build(),
+        }
+    }
+
+    fn standalone_having(&mut self, plan: &HavingPlan) -> Result<PlanNode> {
+        match self.input.take() {
+            None => Err(ErrorCode::LogicalError("Standalone having input is None")),
+            Some(input) => PlanBuilder::from(input.as_ref())
+                .having(plan.predicate.clone())?
+                .build(),
+        }
+    }
+
     fn cluster_sort(&mut self, plan: &SortPlan) -> Result<PlanNode> {
         // Order by we convergent it in local node
         self.running_mode = RunningMode::Standalone;
 
         match self.input.take() {
             None => Err(ErrorCode::LogicalError("Cluster sort input is None")),
+            // The top N of every node is sorted before the convergent, so that every node only
+            // sends its first N rows.
+            Some(input) if plan.limit.is_some() => {
+                let partial_top_n = PlanBuilder::from(input.as_ref())
+                    .sort_with_limit(&plan.order_by, plan.limit)?
+                    .build()?;
+                Self::convergent_shuffle_stage_builder(Arc::new(partial_top_n))
+                    .sort_with_limit(&plan.order_by, plan.limit)?
+                    .build()
+            }
             Some(input) => Self::convergent_shuffle_stage_builder(input)
                 .sort(&plan.order_by)?
                 .build(),
@@ -116,7 +214,7 @@ impl ScattersOptimizerImpl {
         match self.input.take() {
             None => Err(ErrorCode::LogicalError("Standalone sort input is None")),
             Some(input) => PlanBuilder::from(input.as_ref())
-                .sort(&plan.order_by)?
+                .sort_with_limit(&plan.order_by, plan.limit)?
                 .build(),
         }
     }
@@ -165,6 +263,50 @@ impl ScattersOptimizerImpl {
         }
     }
 
+    // The partial aggregate without group by is executed by the store nodes holding the parts of
+    // a remote table, with the filters and the expressions below it, when the parts have more rows
+    // on average than the one row of states sent instead. The read then returns the states.
+    fn store_aggregate(
+        ctx: &FuseQueryContextRef,
+        plan: &AggregatorPartialPlan,
+        input: &PlanNode,
+    ) -> Result<Option<PlanNode>> {
+        if !plan.group_expr.is_empty() || ctx.get_settings().get_enable_store_plan()? == 0 {
+            return Ok(None);
+        }
+
+        // The filters and the expressions with subqueries read the sets of the subqueries below
+        // them, they are left to the query.
+        let mut node = input;
+        let read = loop {
+            match node {
+                PlanNode::Filter(filter) => node = filter.input.as_ref(),
+                PlanNode::Expression(expression) => node = expression.input.as_ref(),
+                PlanNode::ReadSource(read) => break read,
+                _ => return Ok(None),
+            }
+        };
+
+        let table = ctx.get_table(&read.db, &read.table)?;
+        if !table.executes_store_plans()
+            || read.scan_plan.push_downs.store_plan.is_some()
+            || read.statistics.read_rows <= read.parts.len()
+        {
+            return Ok(None);
+        }
+
+        let store_plan = PlanBuilder::from(input)
+            .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+            .build()?;
+        let mut scan_plan = read.scan_plan.as_ref().clone();
+        scan_plan.push_downs.store_plan = Some(Box::new(store_plan.clone()));
+        Ok(Some(PlanNode::ReadSource(ReadDataSourcePlan {
+            schema: store_plan.schema(),
+            scan_plan: Arc::new(scan_plan),
+            ..read.clone()
+        })))
+    }
+
     fn convergent_shuffle_stage_builder(input: Arc<PlanNode>) -> PlanBuilder {
         PlanBuilder::from(&PlanNode::Stage(StagePlan {
             kind: StageKind::Convergent,
@@ -219,16 +361,99 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_union(&mut self, plan: &UnionPlan) -> Result<PlanNode> {
+        let mut new_inputs = Vec::with_capacity(plan.inputs.len());
+        let mut inputs_running_mode = Vec::with_capacity(plan.inputs.len());
+        for input in &plan.inputs {
+            let mut input_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+            new_inputs.push(input_optimizer.rewrite_plan_node(input.as_ref())?);
+            inputs_running_mode.push(input_optimizer.running_mode);
+        }
+
+        // The union runs in every node if all the inputs run in cluster mode, otherwise the inputs
+        // in cluster mode are converged to the local node.
+        let all_cluster = inputs_running_mode
+            .iter()
+            .all(|mode| matches!(mode, RunningMode::Cluster));
+        self.bucketing = None;
+        self.running_mode = match all_cluster {
+            true => RunningMode::Cluster,
+            false => {
+                for (new_input, mode) in new_inputs.iter_mut().zip(inputs_running_mode) {
+                    if let RunningMode::Cluster = mode {
+                        *new_input = Self::convergent_shuffle_stage(new_input.clone())?;
+                    }
+                }
+                RunningMode::Standalone
+            }
+        };
+
+        PlanBuilder::from(&new_inputs[0])
+            .union_all(&new_inputs[1..])?
+            .build()
+    }
+
+    fn rewrite_join(&mut self, plan: &JoinPlan) -> Result<PlanNode> {
+        let mut left_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+        let new_left = left_optimizer.rewrite_plan_node(plan.left.as_ref())?;
+        let mut right_optimizer = ScattersOptimizerImpl::create(self.ctx.clone());
+        let new_right = right_optimizer.rewrite_plan_node(plan.right.as_ref())?;
+
+        let mut new_join = plan.clone();
+        if Self::is_colocated_join(plan, &left_optimizer, &right_optimizer) {
+            // No shuffle for the co-located buckets, every node joins the buckets it reads. The
+            // output is bucketed by the bucket column of the input whose rows are all kept.
+            self.running_mode = RunningMode::Cluster;
+            self.bucketing = match plan.join_type {
+                JoinType::Inner | JoinType::Left | JoinType::Semi | JoinType::Anti => {
+                    left_optimizer.bucketing
+                }
+                JoinType::Right => right_optimizer.bucketing,
+                JoinType::Full => None,
+            };
+            new_join.set_inputs(&new_left, &new_right);
+            return Ok(PlanNode::Join(new_join));
+        }
+
+        // Otherwise the inputs in cluster mode are converged, the join runs in the local node.
+        let converge = |input: PlanNode, mode: &RunningMode| match mode {
+            RunningMode::Cluster => Self::convergent_shuffle_stage(input),
+            RunningMode::Standalone => Ok(input),
+        };
+        self.running_mode = RunningMode::Standalone;
+        self.bucketing = None;
+        new_join.set_inputs(
+            &converge(new_left, &left_optimizer.running_mode)?,
+            &converge(new_right, &right_optimizer.running_mode)?,
+        );
+        Ok(PlanNode::Join(new_join))
+    }
+
     fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
         let new_input = Arc::new(self.rewrite_plan_node(&plan.input)?);
 
         self.input = Some(new_input.clone());
         self.before_group_by_schema = Some(new_input.schema());
 
-        match self.running_mode {
+        if let Some(read) = Self::store_aggregate(&self.ctx, plan, &new_input)? {
+            self.input = None;
+            self.bucketing = None;
+            // The states of all the parts are merged in the local node.
+            return match self.running_mode {
+                RunningMode::Cluster => {
+                    self.running_mode = RunningMode::Standalone;
+                    Self::convergent_shuffle_stage(read)
+                }
+                RunningMode::Standalone => Ok(read),
+            };
+        }
+
+        let aggregate = match self.running_mode {
             RunningMode::Cluster => self.cluster_aggregate(plan),
             RunningMode::Standalone => self.standalone_aggregate(plan),
-        }
+        };
+        self.bucketing = None;
+        aggregate
     }
 
     fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
@@ -242,6 +467,37 @@ impl PlanRewriter for ScattersOptimizerImpl {
         }
     }
 
+    fn rewrite_projection(&mut self, plan: &ProjectionPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+
+        // The bucket column is known by its alias if it is projected as it is, or as an alias of it.
+        if let Some(bucketing) = self.bucketing.take() {
+            let bucket_column = Expression::Column(bucketing.column.clone());
+            self.bucketing = plan.expr.iter().find_map(|expr| match expr {
+                Expression::Alias(alias, expr) if expr.as_ref() == &bucket_column => {
+                    Some(ClusterBy {
+                        column: alias.clone(),
+                        buckets: bucketing.buckets,
+                    })
+                }
+                expr if expr == &bucket_column => Some(bucketing.clone()),
+                _ => None,
+            });
+        }
+
+        let new_exprs = self.rewrite_exprs(&new_input.schema(), &plan.expr)?;
+        PlanBuilder::from(&new_input).project(&new_exprs)?.build()
+    }
+
+    fn rewrite_having(&mut self, plan: &HavingPlan) -> Result<PlanNode> {
+        self.input = Some(Arc::new(self.rewrite_plan_node(plan.input.as_ref())?));
+
+        match self.running_mode {
+            RunningMode::Cluster => self.cluster_having(plan),
+            RunningMode::Standalone => self.standalone_having(plan),
+        }
+    }
+
     fn rewrite_sort(&mut self, plan: &SortPlan) -> Result<PlanNode> {
         self.input = Some(Arc::new(self.rewrite_plan_node(plan.input.as_ref())?));
 
@@ -277,11 +533,54 @@ impl PlanRewriter for ScattersOptimizerImpl {
             false => self.running_mode = RunningMode::Cluster,
             true => self.running_mode = RunningMode::Standalone,
         }
+        // The read keeps the shuffle if any of its parts is not written by bucket, e.g. written
+        // before the table was bucketed.
+        self.bucketing = match self.running_mode {
+            RunningMode::Cluster if ClusterBy::all_parts_bucketed(&plan.parts) => {
+                select_table.cluster_by().cloned()
+            }
+            _ => None,
+        };
 
         Ok(PlanNode::ReadSource(plan.clone()))
     }
 }
 
+// Without a cluster, the plan only changes where the store nodes execute the partial aggregates.
+struct StandaloneStorePlans {
+    ctx: FuseQueryContextRef,
+    before_group_by_schema: Option<DataSchemaRef>,
+    rewritten: bool,
+}
+
+impl PlanRewriter for StandaloneStorePlans {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+        self.before_group_by_schema = Some(new_input.schema());
+
+        match ScattersOptimizerImpl::store_aggregate(&self.ctx, plan, &new_input)? {
+            Some(read) => {
+                self.rewritten = true;
+                Ok(read)
+            }
+            None => PlanBuilder::from(&new_input)
+                .aggregate_partial(&plan.aggr_expr, &plan.group_expr)?
+                .build(),
+        }
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(&plan.input)?;
+
+        match self.before_group_by_schema.take() {
+            None => Ok(PlanNode::AggregatorFinal(plan.clone())),
+            Some(schema_before_group_by) => PlanBuilder::from(&new_input)
+                .aggregate_final(schema_before_group_by, &plan.aggr_expr, &plan.group_expr)?
+                .build(),
+        }
+    }
+}
+
 impl ScattersOptimizer {
     pub fn create(ctx: FuseQueryContextRef) -> ScattersOptimizer {
         ScattersOptimizer { ctx }
@@ -296,7 +595,16 @@ impl Optimizer for ScattersOptimizer {
     fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
         if self.ctx.try_get_cluster()?.is_empty()? {
             // Standalone mode.
-            return Ok(plan.clone());
+            let mut store_plans = StandaloneStorePlans {
+                ctx: self.ctx.clone(),
+                before_group_by_schema: None,
+                rewritten: false,
+            };
+            let rewrite_plan = store_plans.rewrite_plan_node(plan)?;
+            return match store_plans.rewritten {
+                true => Ok(rewrite_plan),
+                false => Ok(plan.clone()),
+            };
         }
 
         let mut optimizer_impl = ScattersOptimizerImpl::create(self.ctx.clone());
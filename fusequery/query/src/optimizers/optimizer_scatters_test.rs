@@ -2,11 +2,27 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_planners::CreateTablePlan;
+use common_planners::Part;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+use common_planners::Statistics;
+use common_planners::TableOptions;
 use common_runtime::tokio;
+use common_streams::SendableDataBlockStream;
 
+use crate::datasources::ClusterBy;
+use crate::datasources::Table;
+use crate::datasources::TableEngineRegistry;
 use crate::optimizers::optimizer_scatters::ScattersOptimizer;
 use crate::optimizers::Optimizer;
+use crate::sessions::FuseQueryContextRef;
 use crate::sql::PlanParser;
 use crate::tests::try_create_cluster_context;
 use crate::tests::ClusterNode;
@@ -143,6 +159,19 @@ async fn test_scatter_optimizer() -> Result<()> {
             \n      AggregatorPartial: groupBy=[[]], aggr=[[SUM(number)]]\
             \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
         },
+        Test {
+            name: "Large cluster table aggregate query with having",
+            query: "SELECT SUM(number) AS s FROM numbers(100000000) GROUP BY number % 3 HAVING s > 10",
+            expect: "\
+            Projection: SUM(number) as s:UInt64\
+            \n  Having: (SUM(number) > 10)\
+            \n    RedistributeStage[expr: 0]\
+            \n      AggregatorFinal: groupBy=[[(number % 3)]], aggr=[[SUM(number)]]\
+            \n        RedistributeStage[expr: sipHash(_group_by_key)]\
+            \n          AggregatorPartial: groupBy=[[(number % 3)]], aggr=[[SUM(number)]]\
+            \n            Expression: (number % 3):UInt8, number:UInt64 (Before GroupBy)\
+            \n              ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 100000000, read_bytes: 800000000]",
+        },
         Test {
             name: "Standalone query with standalone subquery",
             query: "SELECT * FROM numbers_local(1) WHERE EXISTS(SELECT * FROM numbers_local(1))",
@@ -191,6 +220,28 @@ async fn test_scatter_optimizer() -> Result<()> {
             \n            ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 1, read_bytes: 8]\
             \n        ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 1, read_bytes: 8]",
         },
+        Test {
+            name: "Cluster union with cluster inputs",
+            query: "SELECT number FROM numbers(1) UNION ALL SELECT number FROM numbers(2)",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Union All\
+            \n    Projection: number:UInt64\
+            \n      ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 1, read_bytes: 8]\
+            \n    Projection: number:UInt64\
+            \n      ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 2, read_bytes: 16]",
+        },
+        Test {
+            name: "Standalone union with cluster input",
+            query: "SELECT number FROM numbers_local(1) UNION ALL SELECT number FROM numbers(2)",
+            expect: "\
+            Union All\
+            \n  Projection: number:UInt64\
+            \n    ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 1, read_bytes: 8]\
+            \n  RedistributeStage[expr: 0]\
+            \n    Projection: number:UInt64\
+            \n      ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 2, read_bytes: 16]",
+        },
     ];
 
     for test in tests {
@@ -209,3 +260,375 @@ async fn test_scatter_optimizer() -> Result<()> {
 
     Ok(())
 }
+
+// A cluster table bucketed by the CLUSTER BY options, without data.
+struct BucketedTable {
+    name: String,
+    schema: DataSchemaRef,
+    cluster_by: Option<ClusterBy>,
+}
+
+impl BucketedTable {
+    fn try_create(
+        _db: String,
+        name: String,
+        schema: DataSchemaRef,
+        options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        Ok(Box::new(BucketedTable {
+            name,
+            schema,
+            cluster_by: ClusterBy::try_create(&options)?,
+        }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for BucketedTable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn engine(&self) -> &str {
+        "BucketedTest"
+    }
+
+    fn cluster_by(&self) -> Option<&ClusterBy> {
+        self.cluster_by.as_ref()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        Ok(ReadDataSourcePlan {
+            db: "default".to_string(),
+            table: self.name.clone(),
+            schema: self.schema.clone(),
+            parts: vec![],
+            statistics: Statistics::default(),
+            description: "".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: true,
+        })
+    }
+
+    async fn read(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::UnImplement("BucketedTest table has no data"))
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scatter_optimizer_with_bucketed_table() -> Result<()> {
+    #[allow(dead_code)]
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Bucketed table aggregate query with group by bucket column",
+            query: "SELECT SUM(b) FROM default.bucketed GROUP BY a",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Projection: SUM(b):UInt64\
+            \n    AggregatorFinal: groupBy=[[a]], aggr=[[SUM(b)]]\
+            \n      AggregatorPartial: groupBy=[[a]], aggr=[[SUM(b)]]\
+            \n        ReadDataSource: scan partitions: [0], scan schema: [a:UInt64, b:UInt64], statistics: [read_rows: 0, read_bytes: 0]",
+        },
+        Test {
+            name: "Bucketed table aggregate query with group by other column",
+            query: "SELECT SUM(a) FROM default.bucketed GROUP BY b",
+            expect: "\
+            RedistributeStage[expr: 0]\
+            \n  Projection: SUM(a):UInt64\
+            \n    AggregatorFinal: groupBy=[[b]], aggr=[[SUM(a)]]\
+            \n      RedistributeStage[expr: sipHash(_group_by_key)]\
+            \n        AggregatorPartial: groupBy=[[b]], aggr=[[SUM(a)]]\
+            \n          ReadDataSource: scan partitions: [0], scan schema: [a:UInt64, b:UInt64], statistics: [read_rows: 0, read_bytes: 0]",
+        },
+    ];
+
+    let _ = TableEngineRegistry::register("BucketedTest", BucketedTable::try_create);
+    let ctx = try_create_cluster_context(&vec![ClusterNode::create(
+        "Github",
+        1,
+        "www.github.com:9090",
+    )])?;
+    let database = ctx.get_datasource().get_database("default")?;
+    database
+        .create_table(CreateTablePlan {
+            if_not_exists: false,
+            db: "default".to_string(),
+            table: "bucketed".to_string(),
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::UInt64, false),
+            ]),
+            engine: "BucketedTest".to_string(),
+            options: [("cluster_by", "a"), ("buckets", "4")]
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        })
+        .await?;
+
+    for test in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(test.query)?;
+        let mut optimizer = ScattersOptimizer::create(ctx.clone());
+        let optimized = optimizer.optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(test.expect, actual, "{:#?}", test.name);
+    }
+
+    // The group by column is not the bucket column after the subquery renames the columns.
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "SELECT SUM(x) FROM (SELECT b AS a, a AS x FROM default.bucketed) GROUP BY a",
+    )?;
+    let optimized = ScattersOptimizer::create(ctx).optimize(&plan)?;
+    let actual = format!("{:?}", optimized);
+    assert!(
+        actual.contains("RedistributeStage[expr: sipHash(_group_by_key)]"),
+        "{}",
+        actual
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scatter_optimizer_with_bucketed_join() -> Result<()> {
+    let _ = TableEngineRegistry::register("BucketedTest", BucketedTable::try_create);
+    let ctx = try_create_cluster_context(&vec![ClusterNode::create(
+        "Github",
+        1,
+        "www.github.com:9090",
+    )])?;
+    let database = ctx.get_datasource().get_database("default")?;
+    for (table, buckets) in [("bucketed4", "4"), ("bucketed8", "8")].iter() {
+        database
+            .create_table(CreateTablePlan {
+                if_not_exists: false,
+                db: "default".to_string(),
+                table: table.to_string(),
+                schema: DataSchemaRefExt::create(vec![
+                    DataField::new("a", DataType::UInt64, false),
+                    DataField::new("b", DataType::UInt64, false),
+                ]),
+                engine: "BucketedTest".to_string(),
+                options: [("cluster_by", "a"), ("buckets", buckets)]
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            })
+            .await?;
+    }
+
+    // The co-located joins run in every node and only converge at the end, the others converge
+    // their inputs.
+    let tests = vec![
+        (
+            "Join on the bucket columns of the same buckets",
+            "SELECT * FROM default.bucketed4 AS l JOIN default.bucketed4 AS r ON l.a = r.a",
+            1,
+        ),
+        (
+            "Group by the bucket column of a co-located join",
+            "SELECT SUM(r.b) FROM default.bucketed4 AS l JOIN default.bucketed4 AS r ON l.a = r.a GROUP BY l.a",
+            1,
+        ),
+        (
+            "Join on the other columns",
+            "SELECT * FROM default.bucketed4 AS l JOIN default.bucketed4 AS r ON l.b = r.b",
+            2,
+        ),
+        (
+            "Join on the bucket columns of different buckets",
+            "SELECT * FROM default.bucketed4 AS l JOIN default.bucketed8 AS r ON l.a = r.a",
+            2,
+        ),
+    ];
+
+    for (name, query, stages) in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let optimized = ScattersOptimizer::create(ctx.clone()).optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(
+            stages,
+            actual.matches("RedistributeStage[expr: 0]").count(),
+            "{}: {}",
+            name,
+            actual
+        );
+        assert!(!actual.contains("sipHash"), "{}: {}", name, actual);
+        match stages {
+            1 => assert!(actual.starts_with("RedistributeStage"), "{}", actual),
+            _ => assert!(actual.find("Join") < actual.find("RedistributeStage")),
+        }
+    }
+
+    Ok(())
+}
+
+// A cluster table of two parts, whose store nodes execute the store plans, without data.
+struct StoredTable {
+    name: String,
+    schema: DataSchemaRef,
+}
+
+impl StoredTable {
+    fn try_create(
+        _db: String,
+        name: String,
+        schema: DataSchemaRef,
+        _options: TableOptions,
+    ) -> Result<Box<dyn Table>> {
+        Ok(Box::new(StoredTable { name, schema }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for StoredTable {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn engine(&self) -> &str {
+        "StoredTest"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn executes_store_plans(&self) -> bool {
+        true
+    }
+
+    fn read_plan(
+        &self,
+        _ctx: FuseQueryContextRef,
+        scan: &ScanPlan,
+        _partitions: usize,
+    ) -> Result<ReadDataSourcePlan> {
+        let parts = ["part-1", "part-2"]
+            .iter()
+            .map(|name| Part {
+                name: name.to_string(),
+                version: 0,
+                locations: vec![],
+            })
+            .collect();
+        Ok(ReadDataSourcePlan {
+            db: "default".to_string(),
+            table: self.name.clone(),
+            schema: self.schema.clone(),
+            parts,
+            statistics: Statistics::new_estimated(2000, 32000),
+            description: "".to_string(),
+            scan_plan: Arc::new(scan.clone()),
+            remote: true,
+        })
+    }
+
+    async fn read(
+        &self,
+        _ctx: FuseQueryContextRef,
+        _source_plan: &ReadDataSourcePlan,
+    ) -> Result<SendableDataBlockStream> {
+        Err(ErrorCode::UnImplement("StoredTest table has no data"))
+    }
+}
+
+async fn create_stored_table(ctx: &FuseQueryContextRef) -> Result<()> {
+    let database = ctx.get_datasource().get_database("default")?;
+    database
+        .create_table(CreateTablePlan {
+            if_not_exists: false,
+            db: "default".to_string(),
+            table: "stored".to_string(),
+            schema: DataSchemaRefExt::create(vec![
+                DataField::new("a", DataType::UInt64, false),
+                DataField::new("b", DataType::UInt64, false),
+            ]),
+            engine: "StoredTest".to_string(),
+            options: TableOptions::new(),
+        })
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_scatter_optimizer_with_store_plans() -> Result<()> {
+    let _ = TableEngineRegistry::register("StoredTest", StoredTable::try_create);
+    let query = "SELECT SUM(b) FROM default.stored WHERE a > 1";
+
+    // The partial aggregate and the filter are executed by the store nodes, the states of the
+    // parts are converged to the local node.
+    let ctx = try_create_cluster_context(&vec![ClusterNode::create(
+        "Github",
+        1,
+        "www.github.com:9090",
+    )])?;
+    create_stored_table(&ctx).await?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let optimized = ScattersOptimizer::create(ctx.clone()).optimize(&plan)?;
+    let expect = "\
+        Projection: SUM(b):UInt64\
+        \n  AggregatorFinal: groupBy=[[]], aggr=[[SUM(b)]]\
+        \n    RedistributeStage[expr: 0]\
+        \n      ReadDataSource: scan partitions: [2], scan schema: [SUM(b):Binary], statistics: [read_rows: 2000, read_bytes: 32000], store plan: [AggregatorPartial: groupBy=[[]], aggr=[[SUM(b)]], Filter: (a > 1)]";
+    assert_eq!(expect, format!("{:?}", optimized));
+
+    // The groups are aggregated by the query.
+    let plan = PlanParser::create(ctx.clone())
+        .build_from_sql("SELECT SUM(b) FROM default.stored GROUP BY a")?;
+    let optimized = ScattersOptimizer::create(ctx.clone()).optimize(&plan)?;
+    assert!(!format!("{:?}", optimized).contains("store plan"));
+
+    // The store plans are disabled.
+    ctx.get_settings().set_enable_store_plan(0)?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let optimized = ScattersOptimizer::create(ctx).optimize(&plan)?;
+    assert!(!format!("{:?}", optimized).contains("store plan"));
+
+    // Standalone mode.
+    let ctx = crate::tests::try_create_context()?;
+    create_stored_table(&ctx).await?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    let optimized = ScattersOptimizer::create(ctx).optimize(&plan)?;
+    let expect = "\
+        Projection: SUM(b):UInt64\
+        \n  AggregatorFinal: groupBy=[[]], aggr=[[SUM(b)]]\
+        \n    ReadDataSource: scan partitions: [2], scan schema: [SUM(b):Binary], statistics: [read_rows: 2000, read_bytes: 32000], store plan: [AggregatorPartial: groupBy=[[]], aggr=[[SUM(b)]], Filter: (a > 1)]";
+    assert_eq!(expect, format!("{:?}", optimized));
+
+    Ok(())
+}
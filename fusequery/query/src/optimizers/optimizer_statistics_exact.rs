@@ -4,6 +4,7 @@
 
 use std::sync::Arc;
 
+use bumpalo::Bump;
 use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planners::AggregatorFinalPlan;
@@ -13,7 +14,9 @@ use common_planners::ExpressionPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
 use common_planners::PlanRewriter;
+use common_planners::ReadDataSourcePlan;
 
+use crate::datasources::MinMaxPruner;
 use crate::optimizers::Optimizer;
 use crate::sessions::FuseQueryContextRef;
 
@@ -52,7 +55,14 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
                             table
                                 .schema()
                                 .and_then(|ref schema| {
-                                    PlanBuilder::scan(db_name, table_name, schema, None, None, None)
+                                    PlanBuilder::scan(
+                                        db_name,
+                                        table_name,
+                                        schema,
+                                        None,
+                                        vec![],
+                                        None,
+                                    )
                                 })
                                 .and_then(|builder| builder.build())
                                 .and_then(|dummy_scan_plan| match dummy_scan_plan {
@@ -68,21 +78,40 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
                                     }
                                 })
                         })?;
-                    let rows = read_source_plan.statistics.read_rows as u64;
-                    let states = DataValue::Struct(vec![DataValue::UInt64(Some(rows))]);
-                    let ser = serde_json::to_string(&states)?;
+                    // The partial state of count, which is merged by the final aggregator as
+                    // the states of the scan.
+                    let rows = read_source_plan.statistics.read_rows;
+                    let func = plan.aggr_expr[0].to_aggregate_function(&plan.input.schema())?;
+                    let arena = Bump::new();
+                    let place = func.allocate_state(&arena);
+                    func.accumulate(place, &[], rows)?;
+                    let mut state = vec![];
+                    func.serialize_state(place, &mut state)?;
+
+                    let column_name = plan.aggr_expr[0].column_name();
                     PlanBuilder::from(&dummy_read_plan)
                         .expression(
-                            &[Expression::create_literal(DataValue::Utf8(Some(
-                                ser.clone(),
-                            )))],
+                            &[Expression::Literal {
+                                value: DataValue::Binary(Some(state)),
+                                column_name: Some(column_name.clone()),
+                            }],
                             "Exact Statistics",
                         )?
-                        .project(&[Expression::Column(ser).alias("count(0)")])?
+                        .project(&[Expression::Column(column_name)])?
                         .build()?
                 }
                 _ => PlanNode::AggregatorPartial(plan.clone()),
             },
+            ([], aggr_expr, PlanNode::Expression(ExpressionPlan { input, .. }))
+                if MinMaxPruner::try_create(aggr_expr).is_some() =>
+            {
+                match input.as_ref() {
+                    PlanNode::ReadSource(read_source_plan) if read_source_plan.remote => {
+                        self.prune_min_max_parts(plan, read_source_plan)?
+                    }
+                    _ => PlanNode::AggregatorPartial(plan.clone()),
+                }
+            }
             (_, _, _) => PlanNode::AggregatorPartial(plan.clone()),
         };
         Ok(new_plan)
@@ -99,6 +128,39 @@ impl PlanRewriter for StatisticsExactImpl<'_> {
     }
 }
 
+impl StatisticsExactImpl<'_> {
+    /// MIN/MAX without filters and GROUP BY only reads the parts which may hold the results, by
+    /// the statistics of the columns of the parts.
+    fn prune_min_max_parts(
+        &self,
+        plan: &AggregatorPartialPlan,
+        read_source_plan: &ReadDataSourcePlan,
+    ) -> Result<PlanNode> {
+        let mut scan_plan = read_source_plan.scan_plan.as_ref().clone();
+        scan_plan.push_downs.aggregates = plan.aggr_expr.clone();
+        let table = self
+            .ctx
+            .get_table(&read_source_plan.db, &read_source_plan.table)?;
+        let pruned_plan = table.read_plan(
+            self.ctx.clone(),
+            &scan_plan,
+            self.ctx.get_settings().get_max_threads()? as usize,
+        )?;
+
+        let mut input = plan.input.as_ref().clone();
+        input.set_inputs(vec![&PlanNode::ReadSource(ReadDataSourcePlan {
+            parts: pruned_plan.parts,
+            statistics: pruned_plan.statistics,
+            scan_plan: pruned_plan.scan_plan,
+            ..read_source_plan.clone()
+        })])?;
+        Ok(PlanNode::AggregatorPartial(AggregatorPartialPlan {
+            input: Arc::new(input),
+            ..plan.clone()
+        }))
+    }
+}
+
 impl Optimizer for StatisticsExactOptimizer {
     fn name(&self) -> &str {
         "StatisticsExact"
@@ -0,0 +1,92 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_planners::AggregatorFinalPlan;
+use common_planners::AggregatorPartialPlan;
+use common_planners::ExpressionPlan;
+use common_planners::LimitPlan;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::PlanRewriter;
+use common_planners::ProjectionPlan;
+use common_planners::SortPlan;
+
+use crate::optimizers::Optimizer;
+use crate::sessions::FuseQueryContextRef;
+
+/// Turns the sort under a limit into a top N, which only keeps the first rows of the limit and
+/// its offset. The projections and the expressions between them keep the rows as they are, the
+/// other nodes (filters, aggregates...) stop the limit.
+pub struct TopNOptimizer {}
+
+struct TopNImpl {}
+
+impl PlanRewriter for TopNImpl {
+    fn rewrite_aggregate_partial(&mut self, plan: &AggregatorPartialPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AggregatorPartial(AggregatorPartialPlan {
+            input: Arc::new(self.rewrite_plan_node(plan.input.as_ref())?),
+            ..plan.clone()
+        }))
+    }
+
+    fn rewrite_aggregate_final(&mut self, plan: &AggregatorFinalPlan) -> Result<PlanNode> {
+        Ok(PlanNode::AggregatorFinal(AggregatorFinalPlan {
+            input: Arc::new(self.rewrite_plan_node(plan.input.as_ref())?),
+            ..plan.clone()
+        }))
+    }
+
+    fn rewrite_limit(&mut self, plan: &LimitPlan) -> Result<PlanNode> {
+        let new_input = self.rewrite_plan_node(plan.input.as_ref())?;
+        let new_input = match plan.n {
+            Some(n) => Self::limit_sort(&new_input, n + plan.offset),
+            None => new_input,
+        };
+
+        PlanBuilder::from(&new_input)
+            .limit_offset(plan.n, plan.offset)?
+            .build()
+    }
+}
+
+impl TopNImpl {
+    fn limit_sort(plan: &PlanNode, limit: usize) -> PlanNode {
+        match plan {
+            PlanNode::Projection(plan) => PlanNode::Projection(ProjectionPlan {
+                input: Arc::new(Self::limit_sort(plan.input.as_ref(), limit)),
+                ..plan.clone()
+            }),
+            PlanNode::Expression(plan) => PlanNode::Expression(ExpressionPlan {
+                input: Arc::new(Self::limit_sort(plan.input.as_ref(), limit)),
+                ..plan.clone()
+            }),
+            // The sort may already be limited by an inner limit.
+            PlanNode::Sort(plan) => PlanNode::Sort(SortPlan {
+                limit: Some(plan.limit.map_or(limit, |inner| inner.min(limit))),
+                ..plan.clone()
+            }),
+            other => other.clone(),
+        }
+    }
+}
+
+impl Optimizer for TopNOptimizer {
+    fn name(&self) -> &str {
+        "TopN"
+    }
+
+    fn optimize(&mut self, plan: &PlanNode) -> Result<PlanNode> {
+        let mut visitor = TopNImpl {};
+        visitor.rewrite_plan_node(plan)
+    }
+}
+
+impl TopNOptimizer {
+    pub fn create(_ctx: FuseQueryContextRef) -> Self {
+        TopNOptimizer {}
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::optimizers::*;
+use crate::sql::*;
+
+#[test]
+fn test_top_n_optimizer() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        query: &'static str,
+        expect: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Sort under the limit",
+            query: "SELECT number FROM numbers_mt(10) ORDER BY number LIMIT 3",
+            expect: "\
+            Limit: 3\
+            \n  Projection: number:UInt64\
+            \n    Sort: number:UInt64 (limit: 3)\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+        },
+        Test {
+            name: "Sort keeps the rows of the offset",
+            query: "SELECT number FROM numbers_mt(10) ORDER BY number LIMIT 2 OFFSET 3",
+            expect: "\
+            Limit: 2, 3\
+            \n  Projection: number:UInt64\
+            \n    Sort: number:UInt64 (limit: 5)\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+        },
+        Test {
+            name: "Sort under the filter",
+            query: "SELECT * FROM (SELECT number FROM numbers_mt(10) ORDER BY number) WHERE number > 1 LIMIT 3",
+            expect: "\
+            Limit: 3\
+            \n  Projection: number:UInt64\
+            \n    Filter: (number > 1)\
+            \n      Projection: number:UInt64\
+            \n        Sort: number:UInt64\
+            \n          ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+        },
+    ];
+
+    for t in tests {
+        let ctx = crate::tests::try_create_context()?;
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(t.query)?;
+        let optimized = TopNOptimizer::create(ctx).optimize(&plan)?;
+        let actual = format!("{:?}", optimized);
+        assert_eq!(t.expect, actual, "{}", t.name);
+    }
+
+    Ok(())
+}
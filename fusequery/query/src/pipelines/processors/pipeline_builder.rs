@@ -11,6 +11,7 @@ use common_planners::BroadcastPlan;
 use common_planners::ExpressionPlan;
 use common_planners::FilterPlan;
 use common_planners::HavingPlan;
+use common_planners::JoinPlan;
 use common_planners::LimitByPlan;
 use common_planners::LimitPlan;
 use common_planners::PlanNode;
@@ -21,9 +22,11 @@ use common_planners::SelectPlan;
 use common_planners::SortPlan;
 use common_planners::StagePlan;
 use common_planners::SubQueriesSetPlan;
+use common_planners::UnionPlan;
 use common_tracing::tracing;
 
 use crate::pipelines::processors::Pipeline;
+use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::AggregatorFinalTransform;
 use crate::pipelines::transforms::AggregatorPartialTransform;
 use crate::pipelines::transforms::CreateSetsTransform;
@@ -31,25 +34,31 @@ use crate::pipelines::transforms::ExpressionTransform;
 use crate::pipelines::transforms::FilterTransform;
 use crate::pipelines::transforms::GroupByFinalTransform;
 use crate::pipelines::transforms::GroupByPartialTransform;
+use crate::pipelines::transforms::HashJoinTransform;
+use crate::pipelines::transforms::JoinHashTableBuilder;
 use crate::pipelines::transforms::LimitByTransform;
 use crate::pipelines::transforms::LimitTransform;
+use crate::pipelines::transforms::MergeJoinTransform;
+use crate::pipelines::transforms::NestedLoopJoinRightBuilder;
+use crate::pipelines::transforms::NestedLoopJoinTransform;
+use crate::pipelines::transforms::ProfileTransform;
 use crate::pipelines::transforms::ProjectionTransform;
 use crate::pipelines::transforms::RemoteTransform;
 use crate::pipelines::transforms::SortMergeTransform;
 use crate::pipelines::transforms::SortPartialTransform;
 use crate::pipelines::transforms::SourceTransform;
 use crate::pipelines::transforms::SubQueriesPuller;
+use crate::pipelines::transforms::UnionTransform;
+use crate::sessions::FuseQueryContext;
 use crate::sessions::FuseQueryContextRef;
 
 pub struct PipelineBuilder {
     ctx: FuseQueryContextRef,
-
-    limit: Option<usize>,
 }
 
 impl PipelineBuilder {
     pub fn create(ctx: FuseQueryContextRef) -> PipelineBuilder {
-        PipelineBuilder { ctx, limit: None }
+        PipelineBuilder { ctx }
     }
 
     #[tracing::instrument(level = "info", skip(self))]
@@ -61,6 +70,20 @@ impl PipelineBuilder {
     }
 
     fn visit(&mut self, node: &PlanNode) -> Result<Pipeline> {
+        let mut pipeline = self.visit_node(node)?;
+
+        // The blocks of the node are profiled for EXPLAIN ANALYZE, the select is its input.
+        if !matches!(node, PlanNode::Select(_)) && pipeline.nums() > 0 {
+            if let Some(profile) = self.ctx.get_plan_profile(node) {
+                pipeline.add_simple_transform(|| {
+                    Ok(Box::new(ProfileTransform::create(profile.clone())))
+                })?;
+            }
+        }
+        Ok(pipeline)
+    }
+
+    fn visit_node(&mut self, node: &PlanNode) -> Result<Pipeline> {
         match node {
             PlanNode::Select(node) => self.visit_select(node),
             PlanNode::Stage(node) => self.visit_stage(node),
@@ -72,6 +95,8 @@ impl PipelineBuilder {
             PlanNode::AggregatorFinal(node) => self.visit_aggregator_final(node),
             PlanNode::Filter(node) => self.visit_filter(node),
             PlanNode::Having(node) => self.visit_having(node),
+            PlanNode::Join(node) => self.visit_join(node),
+            PlanNode::Union(node) => self.visit_union(node),
             PlanNode::Sort(node) => self.visit_sort(node),
             PlanNode::Limit(node) => self.visit_limit(node),
             PlanNode::LimitBy(node) => self.visit_limit_by(node),
@@ -155,6 +180,7 @@ impl PipelineBuilder {
         } else {
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByPartialTransform::create(
+                    self.ctx.clone(),
                     node.schema(),
                     node.input.schema(),
                     node.aggr_expr.clone(),
@@ -181,6 +207,7 @@ impl PipelineBuilder {
             let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(GroupByFinalTransform::create(
+                    self.ctx.clone(),
                     node.schema(),
                     max_block_size,
                     node.schema_before_group_by.clone(),
@@ -217,6 +244,97 @@ impl PipelineBuilder {
         Ok(pipeline)
     }
 
+    fn visit_join(&mut self, plan: &JoinPlan) -> Result<Pipeline> {
+        if plan.is_nested_loop() {
+            return self.visit_nested_loop_join(plan);
+        }
+        if plan.sorted {
+            return self.visit_merge_join(plan);
+        }
+
+        // The left side is probed by the transforms, which share the hash table of the right side.
+        let mut pipeline = self.visit(&*plan.left)?;
+        let context = self.ctx.clone();
+        let left_schema = plan.left.schema();
+        let hash_table_builder = JoinHashTableBuilder::create(
+            context.clone(),
+            plan.join_type,
+            plan.right.clone(),
+            plan.right_keys.clone(),
+        );
+        pipeline.add_simple_transform(move || {
+            Ok(Box::new(HashJoinTransform::try_create(
+                context.clone(),
+                plan.join_type,
+                plan.schema(),
+                left_schema.clone(),
+                plan.left_keys.clone(),
+                hash_table_builder.clone(),
+            )?))
+        })?;
+        Ok(pipeline)
+    }
+
+    fn visit_nested_loop_join(&mut self, plan: &JoinPlan) -> Result<Pipeline> {
+        // The left side is joined by the transforms, which share the rows of the right side.
+        let mut pipeline = self.visit(&*plan.left)?;
+        let context = self.ctx.clone();
+        let left_schema = plan.left.schema();
+        let right_schema = plan.right.schema();
+        let right_builder =
+            NestedLoopJoinRightBuilder::create(context.clone(), plan.join_type, plan.right.clone());
+        pipeline.add_simple_transform(move || {
+            Ok(Box::new(NestedLoopJoinTransform::try_create(
+                context.clone(),
+                plan.join_type,
+                plan.schema(),
+                left_schema.clone(),
+                right_schema.clone(),
+                plan.condition.clone(),
+                right_builder.clone(),
+            )?))
+        })?;
+        Ok(pipeline)
+    }
+
+    fn visit_merge_join(&mut self, plan: &JoinPlan) -> Result<Pipeline> {
+        // The left side is one sorted stream, which is merged with the right side by one transform.
+        let mut pipeline = self.visit(&*plan.left)?;
+        if pipeline.last_pipe()?.nums() > 1 {
+            return Err(ErrorCode::LogicalError(
+                "The left side of a merge join must be one sorted stream",
+            ));
+        }
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(MergeJoinTransform::try_create(
+                self.ctx.clone(),
+                plan.join_type,
+                plan.schema(),
+                plan.left.schema(),
+                plan.left_keys.clone(),
+                plan.right.clone(),
+                plan.right_keys.clone(),
+            )?))
+        })?;
+        Ok(pipeline)
+    }
+
+    fn visit_union(&mut self, plan: &UnionPlan) -> Result<Pipeline> {
+        // The processors of all the inputs are the sources of the union, they run in parallel.
+        // Every input reads its own partitions, so it runs in a context of its own.
+        let mut pipeline = Pipeline::create(self.ctx.clone());
+        for input in &plan.inputs {
+            let input_ctx = FuseQueryContext::new(self.ctx.clone());
+            let input_pipeline = PipelineBuilder::create(input_ctx).build(input)?;
+            for processor in input_pipeline.last_pipe()?.processors() {
+                let mut transform = UnionTransform::try_create(plan.schema())?;
+                transform.connect_to(processor)?;
+                pipeline.add_source(Arc::new(transform))?;
+            }
+        }
+        Ok(pipeline)
+    }
+
     fn visit_sort(&mut self, plan: &SortPlan) -> Result<Pipeline> {
         let mut pipeline = self.visit(&*plan.input)?;
 
@@ -227,7 +345,7 @@ impl PipelineBuilder {
             Ok(Box::new(SortPartialTransform::try_create(
                 plan.schema(),
                 plan.order_by.clone(),
-                self.limit,
+                plan.limit,
             )?))
         })?;
 
@@ -236,9 +354,10 @@ impl PipelineBuilder {
         // processor 3: [sorted blocks ...] ---> merge to one sorted block
         pipeline.add_simple_transform(|| {
             Ok(Box::new(SortMergeTransform::try_create(
+                self.ctx.clone(),
                 plan.schema(),
                 plan.order_by.clone(),
-                self.limit,
+                plan.limit,
             )?))
         })?;
 
@@ -251,9 +370,10 @@ impl PipelineBuilder {
             pipeline.merge_processor()?;
             pipeline.add_simple_transform(|| {
                 Ok(Box::new(SortMergeTransform::try_create(
+                    self.ctx.clone(),
                     plan.schema(),
                     plan.order_by.clone(),
-                    self.limit,
+                    plan.limit,
                 )?))
             })?;
         }
@@ -261,8 +381,6 @@ impl PipelineBuilder {
     }
 
     fn visit_limit(&mut self, node: &LimitPlan) -> Result<Pipeline> {
-        self.limit = node.n;
-
         let mut pipeline = self.visit(&*node.input)?;
         pipeline.merge_processor()?;
         pipeline.add_simple_transform(|| {
@@ -284,6 +402,12 @@ impl PipelineBuilder {
     }
 
     fn visit_read_data_source(&mut self, plan: &ReadDataSourcePlan) -> Result<Pipeline> {
+        // Fail before reading if the statistics of the source exceed the read limits.
+        self.ctx
+            .check_read_limits(plan.statistics.read_rows, plan.statistics.read_bytes)?;
+        // The progress of the query is estimated by the statistics of the sources.
+        self.ctx.add_total_rows_approx(plan.statistics.read_rows);
+
         // Bind plan partitions to context.
         self.ctx.try_set_partitions(plan.parts.clone())?;
 
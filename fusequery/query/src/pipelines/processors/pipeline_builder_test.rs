@@ -150,3 +150,77 @@ async fn test_local_pipeline_builds() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipeline_builds_with_read_limits() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let plan =
+        PlanParser::create(ctx.clone()).build_from_sql("select number from numbers_mt(100)")?;
+
+    struct Test {
+        max_rows_to_read: u64,
+        max_bytes_to_read: u64,
+        error: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            max_rows_to_read: 10,
+            max_bytes_to_read: 0,
+            error: "Code: 54, displayText = Limit for rows to read exceeded, max rows: 10, current rows: 100.",
+        },
+        Test {
+            max_rows_to_read: 0,
+            max_bytes_to_read: 100,
+            error: "Code: 54, displayText = Limit for bytes to read exceeded, max bytes: 100, current bytes: 800.",
+        },
+        Test {
+            max_rows_to_read: 100,
+            max_bytes_to_read: 800,
+            error: "",
+        },
+    ];
+
+    for test in tests {
+        ctx.get_settings()
+            .set_max_rows_to_read(test.max_rows_to_read)?;
+        ctx.get_settings()
+            .set_max_bytes_to_read(test.max_bytes_to_read)?;
+
+        match PipelineBuilder::create(ctx.clone()).build(&plan) {
+            Ok(_) => assert_eq!(test.error, ""),
+            Err(e) => assert_eq!(test.error, e.to_string()),
+        }
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipeline_executes_with_memory_limit() -> Result<()> {
+    let queries = vec![
+        "select number from numbers_mt(10000) order by number",
+        "select number % 1000 as k, count(*) from numbers_mt(10000) group by k",
+    ];
+
+    for query in queries {
+        for max_memory_usage in &[1000, 0] {
+            let ctx = crate::tests::try_create_context()?;
+            ctx.get_settings().set_max_memory_usage(*max_memory_usage)?;
+            let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+            let mut pipeline = PipelineBuilder::create(ctx.clone()).build(&plan)?;
+            let result = match pipeline.execute().await {
+                Ok(stream) => stream.try_collect::<Vec<_>>().await,
+                Err(e) => Err(e),
+            };
+
+            match *max_memory_usage {
+                0 => {
+                    assert!(result.is_ok(), "{}", query);
+                    assert!(ctx.get_memory_tracker()?.peak() > 1000, "{}", query);
+                }
+                _ => assert_eq!(57, result.unwrap_err().code(), "{}", query),
+            }
+        }
+    }
+    Ok(())
+}
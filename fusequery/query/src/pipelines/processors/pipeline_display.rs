@@ -2,10 +2,13 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Display;
+use std::sync::Arc;
 
 use crate::pipelines::processors::Pipeline;
+use crate::pipelines::processors::Processor;
 
 impl Pipeline {
     pub fn display_indent(&self) -> impl fmt::Display + '_ {
@@ -121,6 +124,8 @@ impl Pipeline {
         Wrapper(self)
     }
 
+    /// The processors in the DOT language of graphviz, the processors of every pipe are grouped
+    /// in a cluster, the data flows along the edges from the inputs to the processors.
     pub fn display_graphviz(&self) -> impl fmt::Display + '_ {
         struct Wrapper<'a>(&'a Pipeline);
         impl<'a> fmt::Display for Wrapper<'a> {
@@ -130,7 +135,51 @@ impl Pipeline {
                     "// Begin DataFuse GraphViz Pipeline (see https://graphviz.org)"
                 )?;
                 writeln!(f, "digraph {{")?;
-                // TODO()
+
+                // The processors are identified by their addresses, as the inputs of them.
+                let id_of =
+                    |processor: &Arc<dyn Processor>| Arc::as_ptr(processor) as *const () as usize;
+                let pipes = self.0.pipes();
+                let mut ids = HashMap::new();
+                for (index, pipe) in pipes.iter().enumerate() {
+                    let ways = pipe.nums();
+                    writeln!(f, "  subgraph cluster_{} {{", index)?;
+                    writeln!(
+                        f,
+                        "    label = \"{} × {} {}\";",
+                        pipe.name(),
+                        ways,
+                        if ways == 1 { "processor" } else { "processors" },
+                    )?;
+                    for processor in pipe.processors() {
+                        let id = ids.len();
+                        ids.insert(id_of(&processor), id);
+                        writeln!(
+                            f,
+                            "    processor_{} [shape=box, label=\"{}\"];",
+                            id,
+                            processor.name()
+                        )?;
+                    }
+                    writeln!(f, "  }}")?;
+                }
+
+                // The inputs out of the pipeline, e.g. the EmptyProcessor of the sources, are
+                // skipped.
+                for pipe in pipes.iter() {
+                    for processor in pipe.processors() {
+                        for input in processor.inputs() {
+                            if let Some(from) = ids.get(&id_of(&input)) {
+                                writeln!(
+                                    f,
+                                    "  processor_{} -> processor_{};",
+                                    from,
+                                    ids[&id_of(&processor)]
+                                )?;
+                            }
+                        }
+                    }
+                }
                 writeln!(f, "}}")?;
                 writeln!(f, "// End DataFuse GraphViz Pipeline")?;
                 Ok(())
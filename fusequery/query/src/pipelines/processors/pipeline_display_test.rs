@@ -31,3 +31,75 @@ async fn test_pipeline_display() -> Result<()> {
     assert_eq!(expect, actual);
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_pipeline_display_graphviz() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_threads(2)?;
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "select sum(number+1)+2 as sumx from numbers_mt(80000) where (number+1)=4 limit 1",
+    )?;
+    let pipeline_builder = PipelineBuilder::create(ctx.clone());
+    let pipeline = pipeline_builder.build(&plan)?;
+    let expect = "// Begin DataFuse GraphViz Pipeline (see https://graphviz.org)\
+    \ndigraph {\
+    \n  subgraph cluster_0 {\
+    \n    label = \"SourceTransform × 2 processors\";\
+    \n    processor_0 [shape=box, label=\"SourceTransform\"];\
+    \n    processor_1 [shape=box, label=\"SourceTransform\"];\
+    \n  }\
+    \n  subgraph cluster_1 {\
+    \n    label = \"FilterTransform × 2 processors\";\
+    \n    processor_2 [shape=box, label=\"FilterTransform\"];\
+    \n    processor_3 [shape=box, label=\"FilterTransform\"];\
+    \n  }\
+    \n  subgraph cluster_2 {\
+    \n    label = \"ExpressionTransform × 2 processors\";\
+    \n    processor_4 [shape=box, label=\"ExpressionTransform\"];\
+    \n    processor_5 [shape=box, label=\"ExpressionTransform\"];\
+    \n  }\
+    \n  subgraph cluster_3 {\
+    \n    label = \"AggregatorPartialTransform × 2 processors\";\
+    \n    processor_6 [shape=box, label=\"AggregatorPartialTransform\"];\
+    \n    processor_7 [shape=box, label=\"AggregatorPartialTransform\"];\
+    \n  }\
+    \n  subgraph cluster_4 {\
+    \n    label = \"MergeProcessor × 1 processor\";\
+    \n    processor_8 [shape=box, label=\"MergeProcessor\"];\
+    \n  }\
+    \n  subgraph cluster_5 {\
+    \n    label = \"AggregatorFinalTransform × 1 processor\";\
+    \n    processor_9 [shape=box, label=\"AggregatorFinalTransform\"];\
+    \n  }\
+    \n  subgraph cluster_6 {\
+    \n    label = \"ExpressionTransform × 1 processor\";\
+    \n    processor_10 [shape=box, label=\"ExpressionTransform\"];\
+    \n  }\
+    \n  subgraph cluster_7 {\
+    \n    label = \"ProjectionTransform × 1 processor\";\
+    \n    processor_11 [shape=box, label=\"ProjectionTransform\"];\
+    \n  }\
+    \n  subgraph cluster_8 {\
+    \n    label = \"LimitTransform × 1 processor\";\
+    \n    processor_12 [shape=box, label=\"LimitTransform\"];\
+    \n  }\
+    \n  processor_0 -> processor_2;\
+    \n  processor_1 -> processor_3;\
+    \n  processor_2 -> processor_4;\
+    \n  processor_3 -> processor_5;\
+    \n  processor_4 -> processor_6;\
+    \n  processor_5 -> processor_7;\
+    \n  processor_6 -> processor_8;\
+    \n  processor_7 -> processor_8;\
+    \n  processor_8 -> processor_9;\
+    \n  processor_9 -> processor_10;\
+    \n  processor_10 -> processor_11;\
+    \n  processor_11 -> processor_12;\
+    \n}\
+    \n// End DataFuse GraphViz Pipeline\
+    \n";
+    let actual = format!("{}", pipeline.display_graphviz());
+    assert_eq!(expect, actual);
+    Ok(())
+}
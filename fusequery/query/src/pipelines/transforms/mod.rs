@@ -11,13 +11,21 @@ pub use transform_expression_executor::ExpressionExecutor;
 pub use transform_filter::FilterTransform;
 pub use transform_group_by_final::GroupByFinalTransform;
 pub use transform_group_by_partial::GroupByPartialTransform;
+pub use transform_hash_join::HashJoinTransform;
+pub use transform_hash_join::JoinHashTableBuilder;
 pub use transform_limit::LimitTransform;
 pub use transform_limit_by::LimitByTransform;
+pub use transform_merge_join::MergeJoinTransform;
+pub use transform_nested_loop_join::NestedLoopJoinRightBuilder;
+pub use transform_nested_loop_join::NestedLoopJoinTransform;
+pub use transform_profile::PlanProfile;
+pub use transform_profile::ProfileTransform;
 pub use transform_projection::ProjectionTransform;
 pub use transform_remote::RemoteTransform;
 pub use transform_sort_merge::SortMergeTransform;
 pub use transform_sort_partial::SortPartialTransform;
 pub use transform_source::SourceTransform;
+pub use transform_union::UnionTransform;
 
 #[cfg(test)]
 mod transform_aggregator_final_test;
@@ -32,15 +40,27 @@ mod transform_group_by_final_test;
 #[cfg(test)]
 mod transform_group_by_partial_test;
 #[cfg(test)]
+mod transform_hash_join_test;
+#[cfg(test)]
 mod transform_limit_by_test;
 #[cfg(test)]
 mod transform_limit_test;
 #[cfg(test)]
+mod transform_merge_join_test;
+#[cfg(test)]
+mod transform_nested_loop_join_test;
+#[cfg(test)]
+mod transform_profile_test;
+#[cfg(test)]
 mod transform_projection_test;
 #[cfg(test)]
+mod transform_remote_test;
+#[cfg(test)]
 mod transform_sort_test;
 #[cfg(test)]
 mod transform_source_test;
+#[cfg(test)]
+mod transform_union_test;
 
 mod transform_aggregator_final;
 mod transform_aggregator_partial;
@@ -50,10 +70,15 @@ mod transform_expression_executor;
 mod transform_filter;
 mod transform_group_by_final;
 mod transform_group_by_partial;
+mod transform_hash_join;
 mod transform_limit;
 mod transform_limit_by;
+mod transform_merge_join;
+mod transform_nested_loop_join;
+mod transform_profile;
 mod transform_projection;
 mod transform_remote;
 mod transform_sort_merge;
 mod transform_sort_partial;
 mod transform_source;
+mod transform_union;
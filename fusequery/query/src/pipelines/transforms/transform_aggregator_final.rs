@@ -86,7 +86,7 @@ impl Processor for AggregatorFinalTransform {
 
                 let place = func.allocate_state(&arena);
                 let data = array.value(0);
-                func.deserialize(place, data)?;
+                func.deserialize_state(place, data)?;
                 func.merge(places[i], place)?;
             }
         }
@@ -105,7 +105,7 @@ impl Processor for AggregatorPartialTransform {
         let mut columns: Vec<Series> = vec![];
         for (idx, func) in funcs.iter().enumerate() {
             let mut writer = vec![];
-            func.serialize(places[idx], &mut writer)?;
+            func.serialize_state(places[idx], &mut writer)?;
             let mut array_builder = BinaryArrayBuilder::new(4);
             array_builder.append_value(writer);
 
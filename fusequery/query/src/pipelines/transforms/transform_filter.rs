@@ -3,15 +3,16 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
-use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Instant;
 
-use common_arrow::arrow;
 use common_datablocks::DataBlock;
 use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::Expression;
+use common_planners::ExpressionAction;
+use common_planners::ExpressionChain;
+use common_planners::RewriteHelper;
 use common_streams::CorrectWithSchemaStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
@@ -21,36 +22,181 @@ use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::ExpressionExecutor;
 
+/// Filters the blocks by the conjunctions of the predicate one after another, the cheapest and
+/// the most selective conjunction first, so that the later conjunctions are only evaluated on
+/// the rows which pass the former ones.
+///
+/// The rows which pass are kept as a selection vector of their indices, a conjunction only takes
+/// the selected rows of the columns it references, and the block is materialized by the
+/// selection once all the conjunctions are evaluated, or not at all if all the rows pass.
 pub struct FilterTransform {
     schema: DataSchemaRef,
     input: Arc<dyn Processor>,
-    executor: Arc<ExpressionExecutor>,
-    predicate: Expression,
+    conjunctions: Arc<Vec<FilterConjunction>>,
     having: bool,
 }
 
-impl FilterTransform {
-    pub fn try_create(schema: DataSchemaRef, predicate: Expression, having: bool) -> Result<Self> {
-        let mut fields = schema.fields().clone();
-        fields.push(predicate.to_data_field(&schema)?);
+struct FilterConjunction {
+    column_name: String,
+    /// The columns of the input which the conjunction references.
+    input_columns: Vec<String>,
+    executor: ExpressionExecutor,
+}
+
+impl FilterConjunction {
+    fn try_create(schema: &DataSchemaRef, conjunction: Expression) -> Result<Self> {
+        let chain = ExpressionChain::try_create(schema.clone(), &[conjunction.clone()])?;
+        let mut input_columns = vec![];
+        for action in &chain.actions {
+            if let ExpressionAction::Input(input) = action {
+                if !input_columns.contains(&input.name) {
+                    input_columns.push(input.name.clone());
+                }
+            }
+        }
 
         let executor = ExpressionExecutor::try_create(
             "filter executor",
             schema.clone(),
-            DataSchemaRefExt::create(fields),
-            vec![predicate.clone()],
+            DataSchemaRefExt::create(vec![conjunction.to_data_field(schema)?]),
+            vec![conjunction.clone()],
             false,
         )?;
         executor.validate()?;
 
+        Ok(FilterConjunction {
+            column_name: conjunction.column_name(),
+            input_columns,
+            executor,
+        })
+    }
+
+    /// Evaluates the conjunction on the selected rows of the block, returns the selected rows
+    /// which pass.
+    fn select(&self, block: &DataBlock, selection: Option<Vec<u32>>) -> Result<Vec<u32>> {
+        // A conjunction without input columns, e.g. a constant, is evaluated on all the rows.
+        let selected_input = match &selection {
+            Some(selection) if !self.input_columns.is_empty() => {
+                let mut fields = Vec::with_capacity(self.input_columns.len());
+                let mut columns = Vec::with_capacity(self.input_columns.len());
+                for name in &self.input_columns {
+                    fields.push(block.schema().field_with_name(name)?.clone());
+                    columns.push(block.try_column_by_name(name)?.clone());
+                }
+                let input = DataBlock::create(DataSchemaRefExt::create(fields), columns);
+                Some(DataBlock::block_take_by_indices(&input, &[], selection)?)
+            }
+            _ => None,
+        };
+
+        let filter = match &selected_input {
+            Some(input) => self.executor.execute(input)?,
+            None => self.executor.execute(block)?,
+        };
+        let filter = filter.try_column_by_name(&self.column_name)?.to_array()?;
+        let passed = filter
+            .bool()?
+            .downcast_iter()
+            .map(|v| v == Some(true))
+            .collect::<Vec<_>>();
+
+        Ok(match selection {
+            None => (0..passed.len() as u32)
+                .filter(|row| passed[*row as usize])
+                .collect(),
+            Some(selection) if selected_input.is_some() => selection
+                .into_iter()
+                .zip(passed)
+                .filter_map(|(row, passed)| if passed { Some(row) } else { None })
+                .collect(),
+            Some(selection) => selection
+                .into_iter()
+                .filter(|row| passed[*row as usize])
+                .collect(),
+        })
+    }
+}
+
+impl FilterTransform {
+    /// The selectivity of the predicates whose selectivity is unknown.
+    const DEFAULT_SELECTIVITY: f64 = 0.5;
+
+    pub fn try_create(schema: DataSchemaRef, predicate: Expression, having: bool) -> Result<Self> {
+        let mut conjunctions = RewriteHelper::split_conjunctions(&predicate);
+        // Sorts by the rank of the predicate ordering: the cost to evaluate a conjunction for
+        // the rows which it removes. The sort is stable, the equal ones keep their order.
+        conjunctions.sort_by(|a, b| {
+            let (a, b) = (Self::rank(a), Self::rank(b));
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let conjunctions = conjunctions
+            .into_iter()
+            .map(|conjunction| FilterConjunction::try_create(&schema, conjunction))
+            .collect::<Result<Vec<_>>>()?;
+
         Ok(FilterTransform {
             schema,
             input: Arc::new(EmptyProcessor::create()),
-            executor: Arc::new(executor),
-            predicate,
+            conjunctions: Arc::new(conjunctions),
             having,
         })
     }
+
+    fn rank(expr: &Expression) -> f64 {
+        Self::estimate_cost(expr) / (1.0 - Self::estimate_selectivity(expr)).max(0.01)
+    }
+
+    /// The estimated fraction of the rows which pass the predicate, as the data is not analyzed.
+    fn estimate_selectivity(expr: &Expression) -> f64 {
+        match expr {
+            Expression::Alias(_, expr) => Self::estimate_selectivity(expr),
+            Expression::UnaryExpression { op, expr } if op.to_lowercase() == "not" => {
+                1.0 - Self::estimate_selectivity(expr)
+            }
+            Expression::BinaryExpression { op, left, right } => match op.to_lowercase().as_str() {
+                "and" => Self::estimate_selectivity(left) * Self::estimate_selectivity(right),
+                "or" => {
+                    let (left, right) = (
+                        Self::estimate_selectivity(left),
+                        Self::estimate_selectivity(right),
+                    );
+                    left + right - left * right
+                }
+                "=" | "<=>" | "is not distinct from" => 0.1,
+                "!=" | "<>" | "is distinct from" => 0.9,
+                "<" | "<=" | ">" | ">=" => 0.3,
+                "like" => 0.25,
+                "not like" => 0.75,
+                _ => Self::DEFAULT_SELECTIVITY,
+            },
+            _ => Self::DEFAULT_SELECTIVITY,
+        }
+    }
+
+    /// The estimated cost to evaluate the predicate for a row, by the functions to call.
+    fn estimate_cost(expr: &Expression) -> f64 {
+        match expr {
+            Expression::Column(_) | Expression::Literal { .. } | Expression::Wildcard => 0.0,
+            Expression::Alias(_, expr) | Expression::Sort { expr, .. } => Self::estimate_cost(expr),
+            Expression::UnaryExpression { expr, .. } | Expression::Cast { expr, .. } => {
+                1.0 + Self::estimate_cost(expr)
+            }
+            Expression::BinaryExpression { op, left, right } => {
+                // Matching the patterns costs more than comparing the values.
+                let cost = match op.to_lowercase().as_str() {
+                    "like" | "not like" => 10.0,
+                    _ => 1.0,
+                };
+                cost + Self::estimate_cost(left) + Self::estimate_cost(right)
+            }
+            Expression::ScalarFunction { args, .. }
+            | Expression::AggregateFunction { args, .. } => {
+                2.0 + args.iter().map(Self::estimate_cost).sum::<f64>()
+            }
+            Expression::Subquery { .. } | Expression::ScalarSubquery { .. } => 100.0,
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -77,37 +223,42 @@ impl Processor for FilterTransform {
 
     async fn execute(&self) -> Result<SendableDataBlockStream> {
         let input_stream = self.input.execute().await?;
-        let executor = self.executor.clone();
-        let column_name = self.predicate.column_name();
-
-        let execute_fn = |executor: Arc<ExpressionExecutor>,
-                          column_name: &str,
-                          block: Result<DataBlock>|
-         -> Result<DataBlock> {
-            tracing::debug!("execute...");
-            let start = Instant::now();
-
-            let block = block?;
-            let filter_block = executor.execute(&block)?;
-            let filter_array = filter_block.try_column_by_name(column_name)?.to_array()?;
-            // Downcast to boolean array
-            let filter_array = filter_array.bool()?.downcast_ref();
-            // Convert to arrow record_batch
-            let batch = block.try_into()?;
-            let batch = arrow::compute::filter_record_batch(&batch, filter_array)?;
-
-            let delta = start.elapsed();
-            tracing::debug!("Filter cost: {:?}", delta);
-            batch.try_into()
-        };
+        let conjunctions = self.conjunctions.clone();
+
+        let execute_fn =
+            |conjunctions: &[FilterConjunction], block: Result<DataBlock>| -> Result<DataBlock> {
+                tracing::debug!("execute...");
+                let start = Instant::now();
+
+                let block = block?;
+                // The rows which pass the conjunctions evaluated so far, None for all the rows.
+                let mut selection = None;
+                for conjunction in conjunctions {
+                    let selected = conjunction.select(&block, selection)?;
+                    let no_rows = selected.is_empty();
+                    selection = Some(selected);
+                    if no_rows {
+                        break;
+                    }
+                }
+
+                let block = match selection {
+                    Some(selection) if selection.len() < block.num_rows() => {
+                        DataBlock::block_take_by_indices(&block, &[], &selection)?
+                    }
+                    _ => block,
+                };
+
+                let delta = start.elapsed();
+                tracing::debug!("Filter cost: {:?}", delta);
+                Ok(block)
+            };
         let stream =
-            input_stream.filter_map(
-                move |v| match execute_fn(executor.clone(), &column_name, v) {
-                    Err(error) => Some(Err(error)),
-                    Ok(data_block) if data_block.is_empty() => None,
-                    Ok(data_block) => Some(Ok(data_block)),
-                },
-            );
+            input_stream.filter_map(move |v| match execute_fn(conjunctions.as_slice(), v) {
+                Err(error) => Some(Err(error)),
+                Ok(data_block) if data_block.is_empty() => None,
+                Ok(data_block) => Some(Ok(data_block)),
+            });
 
         Ok(Box::pin(CorrectWithSchemaStream::new(
             Box::pin(stream),
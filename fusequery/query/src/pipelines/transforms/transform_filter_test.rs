@@ -76,3 +76,103 @@ async fn test_transform_filter_error() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_filter_conjunctions() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+
+    let source = test_source.number_source_transform_for_test(10000)?;
+    pipeline.add_source(Arc::new(source))?;
+
+    // The conjunctions are evaluated one after another on the rows which pass the former ones.
+    let predicate = modular(col("number"), lit(2))
+        .eq(lit(0))
+        .and(col("number").gt(lit(9990)))
+        .and(not(col("number").eq(lit(9996))))
+        .and(
+            col("number")
+                .eq(lit(9992))
+                .or(col("number").gt_eq(lit(9994))),
+        );
+    if let PlanNode::Filter(plan) = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .filter(predicate)?
+        .build()?
+    {
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(FilterTransform::try_create(
+                plan.input.schema(),
+                plan.predicate.clone(),
+                false,
+            )?))
+        })?;
+    }
+    pipeline.merge_processor()?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 9992   |",
+        "| 9994   |",
+        "| 9998   |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // No row passes the first conjunction.
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(10000)?;
+    pipeline.add_source(Arc::new(source))?;
+    let schema = test_source.number_schema_for_test()?;
+    let predicate = col("number").eq(lit(10000)).and(col("number").gt(lit(1)));
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(FilterTransform::try_create(
+            schema.clone(),
+            predicate.clone(),
+            false,
+        )?))
+    })?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    assert!(result.is_empty());
+
+    // A conjunction without columns is evaluated on all the rows, then taken by the selection.
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let source = test_source.number_source_transform_for_test(10000)?;
+    pipeline.add_source(Arc::new(source))?;
+    let predicate = col("number")
+        .lt(lit(3))
+        .and(lit(1i64).eq(lit(1i64)))
+        .and(col("number").gt_eq(lit(1)));
+    pipeline.add_simple_transform(|| {
+        Ok(Box::new(FilterTransform::try_create(
+            schema.clone(),
+            predicate.clone(),
+            false,
+        )?))
+    })?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 1      |",
+        "| 2      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    Ok(())
+}
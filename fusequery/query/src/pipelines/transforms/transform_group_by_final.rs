@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -17,7 +19,7 @@ use common_datavalues::DFUInt32Array;
 use common_datavalues::DFUInt64Array;
 use common_datavalues::DFUInt8Array;
 use common_exception::Result;
-use common_infallible::RwLock;
+use common_hashtable::HashTable;
 use common_planners::Expression;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -26,9 +28,16 @@ use futures::stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::sessions::FuseQueryContextRef;
+use crate::sessions::SpillFile;
+
+// The buckets of the groups which the final spills, the memory of merging a bucket is about
+// its share of the spilled groups.
+const SPILL_BUCKETS: usize = 16;
 
 pub struct GroupByFinalTransform {
     max_block_size: usize,
+    ctx: FuseQueryContextRef,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
     schema: DataSchemaRef,
@@ -38,6 +47,7 @@ pub struct GroupByFinalTransform {
 
 impl GroupByFinalTransform {
     pub fn create(
+        ctx: FuseQueryContextRef,
         schema: DataSchemaRef,
         max_block_size: usize,
         schema_before_group_by: DataSchemaRef,
@@ -45,6 +55,7 @@ impl GroupByFinalTransform {
         group_exprs: Vec<Expression>,
     ) -> Self {
         Self {
+            ctx,
             max_block_size,
             aggr_exprs,
             group_exprs,
@@ -92,7 +103,16 @@ impl Processor for GroupByFinalTransform {
             .collect::<Vec<_>>();
 
         let start = Instant::now();
-        let arena = Bump::new();
+        let mut arena = Bump::new();
+        // The groups and their merged states are held until the input is finished.
+        let mut memory = self.ctx.get_memory_tracker()?.reservation();
+        // Once the groups hold more bytes, the rows of the new groups are spilled to the buckets
+        // by the hash of their keys, and the buckets are merged one by one at the end.
+        let max_bytes = self
+            .ctx
+            .get_settings()
+            .get_max_bytes_before_external_group_by()? as usize;
+        let mut buckets: Vec<SpillFile> = vec![];
 
         let mut stream = self.input.execute().await?;
         let sample_block = DataBlock::empty_with_schema(self.schema.clone());
@@ -101,11 +121,18 @@ impl Processor for GroupByFinalTransform {
         macro_rules! apply {
             ($hash_method: ident, $key_array_type: ty, $downcast_fn: ident, $group_func_table: ty) => {{
                 type GroupFuncTable = $group_func_table;
-                let groups_locker = GroupFuncTable::default();
 
-                while let Some(block) = stream.next().await {
-                    let mut groups = groups_locker.write();
-                    let block = block?;
+                // Merges the rows of a block of partial states into the groups. If `spilling`,
+                // the rows of the new groups are not merged but returned by their buckets.
+                let merge = |groups: &mut GroupFuncTable,
+                             arena: &Bump,
+                             block: &DataBlock,
+                             spilling: bool|
+                 -> Result<Vec<Vec<u32>>> {
+                    let mut spilled_rows = match spilling {
+                        true => vec![vec![]; SPILL_BUCKETS],
+                        false => vec![],
+                    };
 
                     let key_array = block.column(aggr_funcs_len + group_expr_len).to_array()?;
                     let key_array: $key_array_type = key_array.$downcast_fn()?;
@@ -124,12 +151,18 @@ impl Processor for GroupByFinalTransform {
                     for row in 0..block.num_rows() {
                         let group_key = $hash_method.get_key(&key_array, row);
                         match groups.get_mut(&group_key) {
+                            None if spilling => {
+                                let mut hasher = DefaultHasher::new();
+                                group_key.hash(&mut hasher);
+                                let bucket = hasher.finish() as usize % SPILL_BUCKETS;
+                                spilled_rows[bucket].push(row as u32);
+                            }
                             None => {
                                 let mut places = Vec::with_capacity(aggr_funcs_len);
                                 for (i, func) in aggr_funcs.iter().enumerate() {
                                     let data = states_binary_arrays[i].value(row);
-                                    let place = func.allocate_state(&arena);
-                                    func.deserialize(place, data)?;
+                                    let place = func.allocate_state(arena);
+                                    func.deserialize_state(place, data)?;
                                     places.push(place);
                                 }
                                 let mut values = Vec::with_capacity(group_expr_len);
@@ -142,67 +175,110 @@ impl Processor for GroupByFinalTransform {
                             Some((places, _)) => {
                                 for (i, func) in aggr_funcs.iter().enumerate() {
                                     let data = states_binary_arrays[i].value(row);
-                                    let place = func.allocate_state(&arena);
-                                    func.deserialize(place, data)?;
+                                    let place = func.allocate_state(arena);
+                                    func.deserialize_state(place, data)?;
                                     func.merge(places[i], place)?;
                                 }
                             }
                         };
                     }
-                }
-                let delta = start.elapsed();
-                tracing::debug!("Group by final cost: {:?}", delta);
-
-                // Collect the merge states.
-                let groups = groups_locker.read();
-
-                let mut group_values: Vec<Vec<DataValue>> = {
-                    let mut values = vec![];
-                    for _i in 0..group_expr_len {
-                        values.push(vec![])
-                    }
-                    values
+                    Ok(spilled_rows)
                 };
 
-                let mut aggr_values: Vec<Vec<DataValue>> = {
-                    let mut values = vec![];
-                    for _i in 0..aggr_funcs_len {
-                        values.push(vec![])
+                // The final blocks of the merged states of the groups.
+                let finish = |groups: &GroupFuncTable| -> Result<Vec<DataBlock>> {
+                    let mut group_values: Vec<Vec<DataValue>> = {
+                        let mut values = vec![];
+                        for _i in 0..group_expr_len {
+                            values.push(vec![])
+                        }
+                        values
+                    };
+
+                    let mut aggr_values: Vec<Vec<DataValue>> = {
+                        let mut values = vec![];
+                        for _i in 0..aggr_funcs_len {
+                            values.push(vec![])
+                        }
+                        values
+                    };
+                    for (_key, (places, values)) in groups.iter() {
+                        for (i, value) in values.iter().enumerate() {
+                            group_values[i].push(value.clone());
+                        }
+
+                        for (i, func) in aggr_funcs.iter().enumerate() {
+                            let merge = func.merge_result(places[i])?;
+                            aggr_values[i].push(merge);
+                        }
                     }
-                    values
-                };
-                for (_key, (places, values)) in groups.iter() {
-                    for (i, value) in values.iter().enumerate() {
-                        group_values[i].push(value.clone());
+
+                    // Build final state block.
+                    let mut columns: Vec<Series> =
+                        Vec::with_capacity(aggr_funcs_len + group_expr_len);
+
+                    for (i, value) in aggr_values.iter().enumerate() {
+                        columns.push(DataValue::try_into_data_array(
+                            value.as_slice(),
+                            &self.aggr_exprs[i].to_data_type(&self.schema_before_group_by)?,
+                        )?);
                     }
 
-                    for (i, func) in aggr_funcs.iter().enumerate() {
-                        let merge = func.merge_result(places[i])?;
-                        aggr_values[i].push(merge);
+                    for (i, value) in group_values.iter().enumerate() {
+                        columns.push(DataValue::try_into_data_array(
+                            value.as_slice(),
+                            &self.group_exprs[i].to_data_type(&self.schema_before_group_by)?,
+                        )?);
                     }
-                }
 
-                // Build final state block.
-                let mut columns: Vec<Series> = Vec::with_capacity(aggr_funcs_len + group_expr_len);
+                    let mut blocks = vec![];
+                    if !columns.is_empty() {
+                        let block = DataBlock::create_by_array(self.schema.clone(), columns);
+                        blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+                    }
+                    Ok(blocks)
+                };
 
-                for (i, value) in aggr_values.iter().enumerate() {
-                    columns.push(DataValue::try_into_data_array(
-                        value.as_slice(),
-                        &self.aggr_exprs[i].to_data_type(&self.schema_before_group_by)?,
-                    )?);
-                }
+                let mut groups = GroupFuncTable::create();
+                while let Some(block) = stream.next().await {
+                    let block = block?;
+                    let spilled_rows = merge(&mut groups, &arena, &block, !buckets.is_empty())?;
+                    for (bucket, rows) in spilled_rows.iter().enumerate() {
+                        if !rows.is_empty() {
+                            buckets[bucket].write(DataBlock::block_take_by_indices(
+                                &block,
+                                &[],
+                                rows,
+                            )?)?;
+                        }
+                    }
 
-                for (i, value) in group_values.iter().enumerate() {
-                    columns.push(DataValue::try_into_data_array(
-                        value.as_slice(),
-                        &self.group_exprs[i].to_data_type(&self.schema_before_group_by)?,
-                    )?);
+                    memory.resize(arena.allocated_bytes() + groups.memory_size())?;
+                    if buckets.is_empty() && max_bytes > 0 && memory.size() > max_bytes {
+                        for _ in 0..SPILL_BUCKETS {
+                            buckets.push(self.ctx.create_spill_file(block.schema())?);
+                        }
+                    }
                 }
+                let delta = start.elapsed();
+                tracing::debug!("Group by final cost: {:?}", delta);
 
-                let mut blocks = vec![];
-                if !columns.is_empty() {
-                    let block = DataBlock::create_by_array(self.schema.clone(), columns);
-                    blocks = DataBlock::split_block_by_size(&block, self.max_block_size)?;
+                // Collect the merge states.
+                let mut blocks = finish(&groups)?;
+
+                // Merge the buckets of the spilled groups, every bucket holds its part of them.
+                for bucket in buckets {
+                    tracing::debug!("Group by final merges {} spilled rows", bucket.rows());
+                    groups = GroupFuncTable::create();
+                    arena.reset();
+                    let mut bucket_stream = bucket.into_stream()?;
+                    while let Some(block) = bucket_stream.next().await {
+                        merge(&mut groups, &arena, &block?, false)?;
+                        memory.resize(arena.allocated_bytes() + groups.memory_size())?;
+                    }
+                    if !groups.is_empty() {
+                        blocks.extend(finish(&groups)?);
+                    }
                 }
 
                 Ok(Box::pin(DataBlockStream::create(
@@ -217,19 +293,19 @@ impl Processor for GroupByFinalTransform {
             ($method: ident, $apply: ident) => {{
                 match $method {
                     HashMethodKind::Serializer(hash_method) => {
-                        apply! { hash_method,  &DFBinaryArray, binary,   RwLock<HashMap<Vec<u8>, (Vec<usize>, Vec<DataValue>), ahash::RandomState>>}
+                        apply! { hash_method,  &DFBinaryArray, binary,   HashTable<Vec<u8>, (Vec<usize>, Vec<DataValue>)>}
                     }
                     HashMethodKind::KeysU8(hash_method) => {
-                        apply! { hash_method , &DFUInt8Array, u8,  RwLock<HashMap<u8, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt8Array, u8,  HashTable<u8, (Vec<usize>, Vec<DataValue>)> }
                     }
                     HashMethodKind::KeysU16(hash_method) => {
-                        apply! { hash_method , &DFUInt16Array, u16,  RwLock<HashMap<u16, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt16Array, u16,  HashTable<u16, (Vec<usize>, Vec<DataValue>)> }
                     }
                     HashMethodKind::KeysU32(hash_method) => {
-                        apply! { hash_method , &DFUInt32Array, u32,  RwLock<HashMap<u32, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt32Array, u32,  HashTable<u32, (Vec<usize>, Vec<DataValue>)> }
                     }
                     HashMethodKind::KeysU64(hash_method) => {
-                        apply! { hash_method , &DFUInt64Array, u64,  RwLock<HashMap<u64, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , &DFUInt64Array, u64,  HashTable<u64, (Vec<usize>, Vec<DataValue>)> }
                     }
                 }
             }};
@@ -4,6 +4,7 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planners::*;
 use common_planners::{self};
@@ -11,8 +12,10 @@ use common_runtime::tokio;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::interpreters::InterpreterFactory;
 use crate::pipelines::processors::*;
 use crate::pipelines::transforms::*;
+use crate::sql::PlanParser;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_transform_final_group_by() -> Result<()> {
@@ -41,6 +44,7 @@ async fn test_transform_final_group_by() -> Result<()> {
     pipeline.add_source(Arc::new(source))?;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByPartialTransform::create(
+            ctx.clone(),
             aggr_partial.schema(),
             source_schema.clone(),
             aggr_exprs.to_vec(),
@@ -52,6 +56,7 @@ async fn test_transform_final_group_by() -> Result<()> {
     let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
     pipeline.add_simple_transform(|| {
         Ok(Box::new(GroupByFinalTransform::create(
+            ctx.clone(),
             aggr_final.schema(),
             max_block_size,
             source_schema.clone(),
@@ -82,3 +87,43 @@ async fn test_transform_final_group_by() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_final_group_by_with_spill() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    // Every block of 100 numbers has 100 groups, the partials spill them by the block. The
+    // final holds the groups of the first block and spills the rows of the others to the
+    // buckets, every group of the buckets is merged from the rows of two blocks.
+    ctx.get_settings().set_max_block_size(100)?;
+    ctx.get_settings()
+        .set_max_bytes_before_external_group_by(1)?;
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "SELECT number % 300 AS k, count(*) AS c, sum(number) AS s FROM numbers_mt(600) GROUP BY k",
+    )?;
+    let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+    let stream = executor.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+
+    let mut groups = 0;
+    for block in result.iter() {
+        for row in 0..block.num_rows() {
+            let k = match block.try_column_by_name("k")?.try_get(row)? {
+                DataValue::UInt16(Some(k)) => k as u64,
+                v => panic!("Unexpected key {:?}", v),
+            };
+            assert_eq!(
+                DataValue::UInt64(Some(2)),
+                block.try_column_by_name("c")?.try_get(row)?
+            );
+            assert_eq!(
+                DataValue::UInt64(Some(k * 2 + 300)),
+                block.try_column_by_name("s")?.try_get(row)?
+            );
+        }
+        groups += block.num_rows();
+    }
+    assert_eq!(300, groups);
+
+    Ok(())
+}
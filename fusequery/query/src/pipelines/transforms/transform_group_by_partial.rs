@@ -3,7 +3,6 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::any::Any;
-use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -14,7 +13,8 @@ use common_datablocks::HashMethodKind;
 use common_datavalues::arrays::BinaryArrayBuilder;
 use common_datavalues::prelude::*;
 use common_exception::Result;
-use common_infallible::RwLock;
+use common_functions::aggregates::StateAddr;
+use common_hashtable::HashTable;
 use common_planners::Expression;
 use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
@@ -23,8 +23,10 @@ use futures::stream::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
+use crate::sessions::FuseQueryContextRef;
 
 pub struct GroupByPartialTransform {
+    ctx: FuseQueryContextRef,
     aggr_exprs: Vec<Expression>,
     group_exprs: Vec<Expression>,
 
@@ -35,12 +37,14 @@ pub struct GroupByPartialTransform {
 
 impl GroupByPartialTransform {
     pub fn create(
+        ctx: FuseQueryContextRef,
         schema: DataSchemaRef,
         schema_before_group_by: DataSchemaRef,
         aggr_exprs: Vec<Expression>,
         group_exprs: Vec<Expression>,
     ) -> Self {
         Self {
+            ctx,
             aggr_exprs,
             group_exprs,
             schema,
@@ -69,7 +73,8 @@ impl Processor for GroupByPartialTransform {
         self
     }
 
-    /// Create hash group based on row index and apply the function with vector.
+    /// Find the group of every row by the hash of the group keys, then apply the functions to
+    /// the states of the groups in one pass over the columns.
     /// For example:
     /// row_idx, A
     /// 0, 1
@@ -87,13 +92,16 @@ impl Processor for GroupByPartialTransform {
     /// 3, 1, 4
     /// 4, 2, 5
     ///
-    /// 1.2) make indices group(for vector compute)
-    /// group_key, indices
-    /// 0, [2]
-    /// 1, [0, 3]
-    /// 2, [1, 4]
+    /// 1.2) make the group offset of every row, the groups are numbered in the order of
+    /// their first rows
+    /// row_idx, offset
+    /// 0, 0
+    /// 1, 1
+    /// 2, 2
+    /// 3, 0
+    /// 4, 1
     ///
-    /// 1.3) apply aggregate function(SUM(A)) to the take block
+    /// 1.3) apply aggregate function(SUM(A)) to the states of the offsets
     /// group_key, SUM(A)
     /// <0, 3>
     /// <1, 1+4>
@@ -106,140 +114,186 @@ impl Processor for GroupByPartialTransform {
         let schema_before_group_by = self.schema_before_group_by.clone();
         let mut funcs = Vec::with_capacity(self.aggr_exprs.len());
         let mut arg_names = Vec::with_capacity(self.aggr_exprs.len());
-        let mut aggr_cols = Vec::with_capacity(self.aggr_exprs.len());
 
         for expr in self.aggr_exprs.iter() {
             funcs.push(expr.to_aggregate_function(&schema_before_group_by)?);
             arg_names.push(expr.to_aggregate_function_names()?);
-            aggr_cols.push(expr.column_name());
         }
         let group_cols = self
             .group_exprs
             .iter()
             .map(|x| x.column_name())
             .collect::<Vec<_>>();
+        // The strings of a collated column are grouped by their collation keys.
+        let group_collations = self
+            .group_exprs
+            .iter()
+            .map(|x| DataCollation::from_field(&x.to_data_field(&schema_before_group_by)?))
+            .collect::<Result<Vec<_>>>()?;
 
         let mut stream = self.input.execute().await?;
-        let arena = Bump::new();
+        let mut arena = Bump::new();
+        // The groups and their states are held until the input is finished.
+        let mut memory = self.ctx.get_memory_tracker()?.reservation();
+        // The groups are spilled once they hold more bytes, the final merges them.
+        let max_bytes = self
+            .ctx
+            .get_settings()
+            .get_max_bytes_before_external_group_by()? as usize;
+        let mut spill_file = None;
         let sample_block = DataBlock::empty_with_schema(self.schema.clone());
         let method = DataBlock::choose_hash_method(&sample_block, &group_cols)?;
 
         macro_rules! apply {
             ($hash_method: ident, $key_array_builder: ty, $group_func_table: ty) => {{
-                // Table for <group_key, (place, keys) >
+                // Table for <group_key, keys>, the offset of a group is the index of its entry.
                 type GroupFuncTable = $group_func_table;
-                let groups_locker = GroupFuncTable::default();
+                type KeyBuilder = $key_array_builder;
+
+                // The block of the states, the group columns and the keys of the groups.
+                let to_block = |groups: &GroupFuncTable,
+                                places: &[Vec<StateAddr>]|
+                 -> Result<DataBlock> {
+                    let mut group_arrays = Vec::with_capacity(group_cols.len());
+                    for _i in 0..group_cols.len() {
+                        group_arrays.push(Vec::with_capacity(groups.len()));
+                    }
+
+                    // Builders.
+                    let mut state_builders: Vec<BinaryArrayBuilder> = (0..aggr_len)
+                        .map(|_| BinaryArrayBuilder::new(groups.len() * 4))
+                        .collect();
+
+                    let mut group_key_builder = KeyBuilder::new(groups.len());
+                    for (offset, (key, values)) in groups.iter().enumerate() {
+                        for (idx, func) in funcs.iter().enumerate() {
+                            let mut writer = vec![];
+                            func.serialize_state(places[idx][offset], &mut writer)?;
+
+                            state_builders[idx].append_value(&writer);
+                        }
+
+                        for (i, value) in values.iter().enumerate() {
+                            group_arrays[i].push(value.clone());
+                        }
+                        // Keys
+                        group_key_builder.append_value((*key).clone());
+                    }
+
+                    let mut columns: Vec<Series> = Vec::with_capacity(self.schema.fields().len());
+                    for mut builder in state_builders {
+                        columns.push(builder.finish().into_series());
+                    }
+                    for (i, values) in group_arrays.iter().enumerate() {
+                        columns.push(DataValue::try_into_data_array(
+                            values,
+                            &self.group_exprs[i].to_data_type(&self.schema_before_group_by)?,
+                        )?)
+                    }
+                    let array = group_key_builder.finish();
+                    columns.push(array.into_series());
+
+                    Ok(DataBlock::create_by_array(self.schema.clone(), columns))
+                };
+
+                let mut groups = GroupFuncTable::create();
+                // The states of the groups by offset, for every function.
+                let mut places: Vec<Vec<StateAddr>> = vec![vec![]; aggr_len];
                 while let Some(block) = stream.next().await {
                     let block = block?;
+                    let rows = block.num_rows();
+                    let group_columns = group_cols
+                        .iter()
+                        .map(|col| block.try_column_by_name(col))
+                        .collect::<Result<Vec<_>>>()?;
+
                     // 1.1 and 1.2.
-                    let group_blocks = $hash_method.group_by(&block, &group_cols)?;
-                    // 1.3 Apply take blocks to aggregate function by group_key.
-                    {
-                        for (group_key, group_keys, take_block) in group_blocks {
-                            let rows = take_block.num_rows();
-
-                            let mut groups = groups_locker.write();
-                            match groups.get_mut(&group_key) {
-                                // New group.
-                                None => {
-                                    let mut places = Vec::with_capacity(aggr_cols.len());
-                                    for (idx, _aggr_col) in aggr_cols.iter().enumerate() {
-                                        let func = funcs[idx].clone();
-                                        let place = funcs[idx].allocate_state(&arena);
-
-                                        let arg_columns = arg_names[idx]
-                                            .iter()
-                                            .map(|arg| {
-                                                take_block
-                                                    .try_column_by_name(arg)
-                                                    .map(|c| c.clone())
-                                            })
-                                            .collect::<Result<Vec<DataColumn>>>()?;
-                                        func.accumulate(place, &arg_columns, rows)?;
-
-                                        places.push(place);
-                                    }
-
-                                    groups.insert(group_key.clone(), (places, group_keys));
-                                }
-                                // Accumulate result against the take block by indices.
-                                Some((places, _)) => {
-                                    for (idx, _aggr_col) in aggr_cols.iter().enumerate() {
-                                        let arg_columns = arg_names[idx]
-                                            .iter()
-                                            .map(|arg| {
-                                                take_block
-                                                    .try_column_by_name(arg)
-                                                    .map(|c| c.clone())
-                                            })
-                                            .collect::<Result<Vec<DataColumn>>>()?;
-
-                                        funcs[idx].accumulate(places[idx], &arg_columns, rows)?
-                                    }
+                    let key_columns = group_columns
+                        .iter()
+                        .zip(group_collations.iter())
+                        .map(|(col, collation)| collation.keys(col))
+                        .collect::<Result<Vec<_>>>()?;
+                    let key_columns = key_columns.iter().collect::<Vec<_>>();
+                    let group_keys = $hash_method.build_keys(&key_columns, rows)?;
+                    let mut offsets = Vec::with_capacity(rows);
+                    for (row, group_key) in group_keys.into_iter().enumerate() {
+                        let offset = match groups.find(&group_key) {
+                            Some(offset) => offset,
+                            // New group.
+                            None => {
+                                for (idx, func) in funcs.iter().enumerate() {
+                                    places[idx].push(func.allocate_state(&arena));
                                 }
+
+                                let values = group_columns
+                                    .iter()
+                                    .map(|col| col.try_get(row))
+                                    .collect::<Result<Vec<_>>>()?;
+                                groups.insert(group_key, values).0
                             }
-                        }
+                        };
+                        offsets.push(offset);
                     }
-                }
 
-                let delta = start.elapsed();
-                tracing::debug!("Group by partial cost: {:?}", delta);
+                    // 1.3 Apply the block to the states of the groups by offsets.
+                    for (idx, func) in funcs.iter().enumerate() {
+                        let arg_columns = arg_names[idx]
+                            .iter()
+                            .map(|arg| block.try_column_by_name(arg).map(|c| c.clone()))
+                            .collect::<Result<Vec<DataColumn>>>()?;
+                        func.accumulate_keys(&places[idx], &offsets, &arg_columns, rows)?;
+                    }
 
-                let groups = groups_locker.read();
-                if groups.is_empty() {
-                    return Ok(Box::pin(DataBlockStream::create(
-                        DataSchemaRefExt::create(vec![]),
-                        None,
-                        vec![],
-                    )));
-                }
+                    let places_size = places
+                        .iter()
+                        .map(|places| places.capacity() * std::mem::size_of::<StateAddr>())
+                        .sum::<usize>();
+                    let bytes = arena.allocated_bytes() + groups.memory_size() + places_size;
+                    if max_bytes == 0 || bytes <= max_bytes {
+                        memory.resize(bytes)?;
+                        continue;
+                    }
 
-                let mut group_arrays = Vec::with_capacity(group_cols.len());
-                for _i in 0..group_cols.len() {
-                    group_arrays.push(Vec::with_capacity(groups.len()));
+                    // Spill the groups, the groups of the next blocks start from empty.
+                    if spill_file.is_none() {
+                        spill_file = Some(self.ctx.create_spill_file(&self.schema)?);
+                    }
+                    if let Some(file) = spill_file.as_mut() {
+                        file.write(to_block(&groups, &places)?)?;
+                    }
+                    groups = GroupFuncTable::create();
+                    places = vec![vec![]; aggr_len];
+                    arena.reset();
+                    memory.resize(0)?;
                 }
 
-                // Builders.
-                let mut state_builders: Vec<BinaryArrayBuilder> = (0..aggr_len)
-                    .map(|_| BinaryArrayBuilder::new(groups.len() * 4))
-                    .collect();
-
-                type KeyBuilder = $key_array_builder;
-                let mut group_key_builder = KeyBuilder::new(groups.len());
-                for (key, (places, values)) in groups.iter() {
-                    for (idx, func) in funcs.iter().enumerate() {
-                        let mut writer = vec![];
-                        func.serialize(places[idx], &mut writer)?;
+                let delta = start.elapsed();
+                tracing::debug!("Group by partial cost: {:?}", delta);
 
-                        state_builders[idx].append_value(&writer);
+                let spill_stream = match spill_file {
+                    None if groups.is_empty() => {
+                        return Ok(Box::pin(DataBlockStream::create(
+                            DataSchemaRefExt::create(vec![]),
+                            None,
+                            vec![],
+                        )));
                     }
-
-                    for (i, value) in values.iter().enumerate() {
-                        group_arrays[i].push(value.clone());
+                    None => None,
+                    Some(file) => {
+                        tracing::debug!("Group by partial spilled {} groups", file.rows());
+                        Some(file.into_stream()?)
                     }
-                    // Keys
-                    group_key_builder.append_value((*key).clone());
-                }
+                };
 
-                let mut columns: Vec<Series> = Vec::with_capacity(self.schema.fields().len());
-                for mut builder in state_builders {
-                    columns.push(builder.finish().into_series());
+                let mut blocks = vec![];
+                if !groups.is_empty() {
+                    blocks.push(to_block(&groups, &places)?);
                 }
-                for (i, values) in group_arrays.iter().enumerate() {
-                    columns.push(DataValue::try_into_data_array(
-                        values,
-                        &self.group_exprs[i].to_data_type(&self.schema_before_group_by)?,
-                    )?)
+                let stream = DataBlockStream::create(self.schema.clone(), None, blocks);
+                match spill_stream {
+                    None => Ok(Box::pin(stream)),
+                    Some(spill_stream) => Ok(Box::pin(stream.chain(spill_stream))),
                 }
-                let array = group_key_builder.finish();
-                columns.push(array.into_series());
-
-                let block = DataBlock::create_by_array(self.schema.clone(), columns);
-                Ok(Box::pin(DataBlockStream::create(
-                    self.schema.clone(),
-                    None,
-                    vec![block],
-                )))
             }};
         }
 
@@ -247,19 +301,19 @@ impl Processor for GroupByPartialTransform {
             ($method: ident, $apply: ident) => {{
                 match $method {
                     HashMethodKind::Serializer(hash_method) => {
-                        apply! { hash_method, BinaryArrayBuilder , RwLock<HashMap<Vec<u8>, (Vec<usize>, Vec<DataValue>), ahash::RandomState>>}
+                        apply! { hash_method, BinaryArrayBuilder , HashTable<Vec<u8>, Vec<DataValue>>}
                     }
                     HashMethodKind::KeysU8(hash_method) => {
-                        apply! { hash_method , DFUInt8ArrayBuilder, RwLock<HashMap<u8, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , DFUInt8ArrayBuilder, HashTable<u8, Vec<DataValue>> }
                     }
                     HashMethodKind::KeysU16(hash_method) => {
-                        apply! { hash_method , DFUInt16ArrayBuilder, RwLock<HashMap<u16, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , DFUInt16ArrayBuilder, HashTable<u16, Vec<DataValue>> }
                     }
                     HashMethodKind::KeysU32(hash_method) => {
-                        apply! { hash_method , DFUInt32ArrayBuilder, RwLock<HashMap<u32, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , DFUInt32ArrayBuilder, HashTable<u32, Vec<DataValue>> }
                     }
                     HashMethodKind::KeysU64(hash_method) => {
-                        apply! { hash_method , DFUInt64ArrayBuilder, RwLock<HashMap<u64, (Vec<usize>, Vec<DataValue>), ahash::RandomState>> }
+                        apply! { hash_method , DFUInt64ArrayBuilder, HashTable<u64, Vec<DataValue>> }
                     }
                 }
             }};
@@ -0,0 +1,657 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hasher;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_datablocks::HashMethod;
+use common_datablocks::HashMethodSerializer;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_hashtable::HashTable;
+use common_infallible::Mutex;
+use common_planners::Expression;
+use common_planners::JoinType;
+use common_planners::PlanNode;
+use common_planners::RewriteHelper;
+use common_streams::CorrectWithSchemaStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use futures::FutureExt;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::FuseQueryContext;
+use crate::sessions::FuseQueryContextRef;
+use crate::sessions::MemoryReservation;
+use crate::sessions::SpillFile;
+
+// The partitions of a join which spills, the memory of joining a partition is about its share of
+// the right side.
+const SPILL_PARTITIONS: usize = 16;
+
+/// Joins the blocks of the input (the left side) with the rows of the right side by the hash
+/// table of the right side, which is built once and shared by all the transforms of the join.
+///
+/// The rows of the left side which match no row are output with nulls by each transform, the
+/// rows of the right side which match no row are output with nulls by the transform which ends
+/// the last, once all the rows of the left side are probed.
+///
+/// If the right side is spilled to partitions (a grace join), the left side is spilled to the
+/// partitions of the same keys, then the partitions are joined one by one.
+pub struct HashJoinTransform {
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    schema: DataSchemaRef,
+    executor: Arc<ExpressionExecutor>,
+    hash_table_builder: Arc<Mutex<JoinHashTableBuilder>>,
+    input: Arc<dyn Processor>,
+}
+
+impl HashJoinTransform {
+    pub fn try_create(
+        ctx: FuseQueryContextRef,
+        join_type: JoinType,
+        schema: DataSchemaRef,
+        left_schema: DataSchemaRef,
+        left_keys: Vec<Expression>,
+        hash_table_builder: Arc<Mutex<JoinHashTableBuilder>>,
+    ) -> Result<Self> {
+        let executor = JoinHashTable::keys_executor("join probe keys", left_schema, left_keys)?;
+        hash_table_builder.lock().add_probe();
+
+        Ok(HashJoinTransform {
+            ctx,
+            join_type,
+            schema,
+            executor: Arc::new(executor),
+            hash_table_builder,
+            input: Arc::new(EmptyProcessor::create()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for HashJoinTransform {
+    fn name(&self) -> &str {
+        "HashJoinTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let hash_table = self.hash_table_builder.lock().take_hash_table()?;
+        let hash_table = match self.ctx.execute_task(hash_table)?.await {
+            Ok(hash_table) => hash_table?,
+            Err(error) => {
+                return Err(ErrorCode::TokioError(format!(
+                    "Cannot build the hash table of JOIN. cause: {}",
+                    error
+                )))
+            }
+        };
+
+        let input_stream = self.input.execute().await?;
+        let stream = match hash_table.partitions.is_empty() {
+            true => Self::join(
+                hash_table,
+                input_stream,
+                self.join_type,
+                self.executor.clone(),
+                self.schema.clone(),
+            ),
+            false => self.join_partitions(hash_table, input_stream).await?,
+        };
+
+        let stream = stream.filter(|block| {
+            futures::future::ready(!matches!(block, Ok(block) if block.is_empty()))
+        });
+
+        Ok(Box::pin(CorrectWithSchemaStream::new(
+            Box::pin(stream),
+            self.schema.clone(),
+        )))
+    }
+}
+
+impl HashJoinTransform {
+    fn join(
+        hash_table: JoinHashTableRef,
+        input_stream: SendableDataBlockStream,
+        join_type: JoinType,
+        executor: Arc<ExpressionExecutor>,
+        schema: DataSchemaRef,
+    ) -> SendableDataBlockStream {
+        let probe_table = hash_table.clone();
+        let probe_schema = schema.clone();
+        let probed = input_stream.map(move |block| {
+            block.and_then(|block| probe_table.probe(join_type, &executor, &probe_schema, &block))
+        });
+
+        let unmatched =
+            futures::stream::once(async move { hash_table.finish_probe(join_type, &schema) });
+        Box::pin(probed.chain(unmatched))
+    }
+
+    // Spills the left side to the partitions, then joins every partition with the hash table of
+    // the right side of the partition, which is built by the first probe to reach it.
+    async fn join_partitions(
+        &self,
+        hash_table: JoinHashTableRef,
+        mut input_stream: SendableDataBlockStream,
+    ) -> Result<SendableDataBlockStream> {
+        let mut files: Vec<SpillFile> = vec![];
+        while let Some(block) = input_stream.next().await {
+            let block = block?;
+            if block.is_empty() {
+                continue;
+            }
+            if files.is_empty() {
+                for _ in 0..SPILL_PARTITIONS {
+                    files.push(self.ctx.create_spill_file(block.schema())?);
+                }
+            }
+            JoinHashTable::spill(&self.executor, &block, &mut files)?;
+        }
+
+        let mut files = files.into_iter().map(Some).collect::<Vec<_>>();
+        files.resize_with(SPILL_PARTITIONS, || None);
+        let join_type = self.join_type;
+        let executor = self.executor.clone();
+        let schema = self.schema.clone();
+        let stream = futures::stream::iter(files.into_iter().enumerate())
+            .then(move |(partition, file)| {
+                let hash_table = hash_table.clone();
+                let executor = executor.clone();
+                let schema = schema.clone();
+                Self::join_partition(hash_table, partition, file, join_type, executor, schema)
+            })
+            .flat_map(|stream| -> SendableDataBlockStream {
+                match stream {
+                    Ok(stream) => stream,
+                    Err(error) => Box::pin(futures::stream::once(async { Err(error) })),
+                }
+            });
+        Ok(Box::pin(stream))
+    }
+
+    async fn join_partition(
+        hash_table: JoinHashTableRef,
+        partition: usize,
+        file: Option<SpillFile>,
+        join_type: JoinType,
+        executor: Arc<ExpressionExecutor>,
+        schema: DataSchemaRef,
+    ) -> Result<SendableDataBlockStream> {
+        let partition_table = hash_table.take_partition(partition)?.await?;
+        let input_stream: SendableDataBlockStream = match file {
+            Some(file) => file.into_stream()?,
+            None => Box::pin(futures::stream::empty()),
+        };
+        Ok(Self::join(
+            partition_table,
+            input_stream,
+            join_type,
+            executor,
+            schema,
+        ))
+    }
+}
+
+type JoinHashTableRef = Arc<JoinHashTable>;
+type SharedFuture = Shared<BoxFuture<'static, Result<JoinHashTableRef>>>;
+
+/// Builds the hash table of the right side of the join on the first take, the later takes
+/// share the same build.
+pub struct JoinHashTableBuilder {
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    plan: Arc<PlanNode>,
+    keys: Vec<Expression>,
+    probes: usize,
+    hash_table: Option<SharedFuture>,
+}
+
+impl JoinHashTableBuilder {
+    pub fn create(
+        ctx: FuseQueryContextRef,
+        join_type: JoinType,
+        plan: Arc<PlanNode>,
+        keys: Vec<Expression>,
+    ) -> Arc<Mutex<JoinHashTableBuilder>> {
+        Arc::new(Mutex::new(JoinHashTableBuilder {
+            ctx,
+            join_type,
+            plan,
+            keys,
+            probes: 0,
+            hash_table: None,
+        }))
+    }
+
+    /// Registers a transform which probes the hash table.
+    pub fn add_probe(&mut self) {
+        self.probes += 1;
+    }
+
+    pub fn take_hash_table(&mut self) -> Result<SharedFuture> {
+        if self.hash_table.is_none() {
+            // The right side reads its own partitions, so it runs in a context of its own.
+            let build_ctx = FuseQueryContext::new(self.ctx.clone());
+            let mut pipeline = PipelineBuilder::create(build_ctx).build(&self.plan)?;
+            let executor = JoinHashTable::keys_executor(
+                "join build keys",
+                self.plan.schema(),
+                self.keys.clone(),
+            )?;
+            let max_bytes = self
+                .ctx
+                .get_settings()
+                .get_max_bytes_before_external_join()? as usize;
+            let build = JoinHashTableBuild {
+                ctx: self.ctx.clone(),
+                executor: Arc::new(executor),
+                schema: self.plan.schema(),
+                keeps_unmatched: self.join_type.keeps_right(),
+                probes: self.probes,
+            };
+            let hash_table = async move {
+                let stream = pipeline.execute().await?;
+                JoinHashTable::build(Arc::new(build), stream, max_bytes).await
+            };
+            self.hash_table = Some(hash_table.boxed().shared());
+        }
+
+        match &self.hash_table {
+            Some(hash_table) => Ok(hash_table.clone()),
+            None => Err(ErrorCode::LogicalError(
+                "The hash table of JOIN must be built",
+            )),
+        }
+    }
+}
+
+// What the hash tables of the right side, and of its partitions if it is spilled, are built by.
+struct JoinHashTableBuild {
+    ctx: FuseQueryContextRef,
+    executor: Arc<ExpressionExecutor>,
+    schema: DataSchemaRef,
+    keeps_unmatched: bool,
+    probes: usize,
+}
+
+// A partition of the spilled right side, its hash table is built once and released after the
+// last probe takes it.
+struct JoinPartition {
+    file: Option<SpillFile>,
+    hash_table: Option<SharedFuture>,
+    takes: usize,
+}
+
+/// The rows of the right side by their serialized keys, the rows with NULL in any key are never
+/// equal to any row, so they are left out.
+///
+/// Once the right side holds more bytes than `max_bytes_before_external_join`, it is spilled to
+/// the partitions by the hash of the keys, and the table holds no rows but the partitions.
+pub struct JoinHashTable {
+    // The memory of the block and the rows, which is held until the probes finish.
+    _memory: MemoryReservation,
+    block: DataBlock,
+    rows: HashTable<Vec<u8>, Vec<u32>>,
+    /// Whether the rows of the block match any row, only if the unmatched rows are output.
+    matched: Vec<AtomicBool>,
+    unfinished_probes: AtomicUsize,
+    build: Arc<JoinHashTableBuild>,
+    partitions: Vec<Mutex<JoinPartition>>,
+}
+
+impl JoinHashTable {
+    /// The executor of the keys of a side of the join, the keys are named by their expressions.
+    pub fn keys_executor(
+        description: &str,
+        schema: DataSchemaRef,
+        keys: Vec<Expression>,
+    ) -> Result<ExpressionExecutor> {
+        let fields = RewriteHelper::exprs_to_fields(&keys, &schema)?;
+        let executor = ExpressionExecutor::try_create(
+            description,
+            schema,
+            DataSchemaRefExt::create(fields),
+            keys,
+            false,
+        )?;
+        executor.validate()?;
+        Ok(executor)
+    }
+
+    // Builds the hash table of the blocks of the stream, or spills them to the partitions once
+    // they hold more bytes than `max_bytes` if it is not 0.
+    async fn build(
+        build: Arc<JoinHashTableBuild>,
+        mut stream: SendableDataBlockStream,
+        max_bytes: usize,
+    ) -> Result<JoinHashTableRef> {
+        let start = Instant::now();
+        let mut memory = build.ctx.get_memory_tracker()?.reservation();
+        let mut blocks = vec![];
+        let mut files: Vec<SpillFile> = vec![];
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            if block.is_empty() {
+                continue;
+            }
+            if !files.is_empty() {
+                Self::spill(&build.executor, &block, &mut files)?;
+                continue;
+            }
+
+            memory.resize(memory.size() + block.memory_size())?;
+            blocks.push(block);
+            if max_bytes > 0 && memory.size() > max_bytes {
+                for _ in 0..SPILL_PARTITIONS {
+                    files.push(build.ctx.create_spill_file(blocks[0].schema())?);
+                }
+                for block in blocks.drain(..) {
+                    Self::spill(&build.executor, &block, &mut files)?;
+                }
+                memory.resize(0)?;
+            }
+        }
+
+        if !files.is_empty() {
+            tracing::debug!(
+                "Spill the right side of JOIN with {} rows to {} partitions cost: {:?}",
+                files.iter().map(|file| file.rows()).sum::<usize>(),
+                files.len(),
+                start.elapsed()
+            );
+            let partitions = files
+                .into_iter()
+                .map(|file| {
+                    Mutex::new(JoinPartition {
+                        file: Some(file),
+                        hash_table: None,
+                        takes: 0,
+                    })
+                })
+                .collect();
+            return Ok(Arc::new(JoinHashTable {
+                _memory: memory,
+                block: DataBlock::empty_with_schema(build.schema.clone()),
+                rows: HashTable::with_capacity(0),
+                matched: vec![],
+                unfinished_probes: AtomicUsize::new(build.probes),
+                build,
+                partitions,
+            }));
+        }
+
+        let block = match blocks.is_empty() {
+            true => DataBlock::empty_with_schema(build.schema.clone()),
+            false => DataBlock::concat_blocks(&blocks)?,
+        };
+
+        // The blocks are concatenated into one, then the keys are added.
+        memory.resize(memory.size() + block.memory_size())?;
+        drop(blocks);
+        let mut rows = HashTable::with_capacity(block.num_rows());
+        // The bytes of the keys and the row numbers, which the entries own on the heap.
+        let mut entries_size = 0;
+        if !block.is_empty() {
+            let keys_block = build.executor.execute(&block)?;
+            let nulls = Self::null_rows(&keys_block)?;
+            let keys = Self::build_keys(&keys_block)?;
+            for (row, key) in keys.into_iter().enumerate() {
+                if !nulls[row] {
+                    let key_size = key.len();
+                    let (index, inserted) = rows.insert_with(key, Vec::new);
+                    if inserted {
+                        entries_size += key_size;
+                    }
+                    rows.value_mut(index).push(row as u32);
+                    entries_size += std::mem::size_of::<u32>();
+                }
+            }
+        }
+
+        tracing::debug!(
+            "Build the hash table of JOIN with {} rows cost: {:?}",
+            block.num_rows(),
+            start.elapsed()
+        );
+        let matched = match build.keeps_unmatched {
+            true => (0..block.num_rows())
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+            false => vec![],
+        };
+        memory.resize(block.memory_size() + rows.memory_size() + entries_size)?;
+        Ok(Arc::new(JoinHashTable {
+            _memory: memory,
+            block,
+            rows,
+            matched,
+            unfinished_probes: AtomicUsize::new(build.probes),
+            build,
+            partitions: vec![],
+        }))
+    }
+
+    /// Writes the rows of the block to the partitions by the hash of their keys, the rows with
+    /// NULL in any key are written to the first partition.
+    fn spill(
+        executor: &ExpressionExecutor,
+        block: &DataBlock,
+        files: &mut [SpillFile],
+    ) -> Result<()> {
+        let keys_block = executor.execute(block)?;
+        let nulls = Self::null_rows(&keys_block)?;
+        let keys = Self::build_keys(&keys_block)?;
+        let mut partition_rows = vec![vec![]; files.len()];
+        for (row, key) in keys.iter().enumerate() {
+            let partition = match nulls[row] {
+                true => 0,
+                false => {
+                    let mut hasher = DefaultHasher::new();
+                    hasher.write(key);
+                    hasher.finish() as usize % files.len()
+                }
+            };
+            partition_rows[partition].push(row as u32);
+        }
+
+        for (file, rows) in files.iter_mut().zip(partition_rows.iter()) {
+            if !rows.is_empty() {
+                file.write(DataBlock::block_take_by_indices(block, &[], rows)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    // Takes the hash table of the partition of the spilled right side, the first take builds it.
+    fn take_partition(&self, partition: usize) -> Result<SharedFuture> {
+        let mut partition = self
+            .partitions
+            .get(partition)
+            .ok_or_else(|| ErrorCode::LogicalError("The partition of JOIN does not exist"))?
+            .lock();
+        if let Some(file) = partition.file.take() {
+            let build = self.build.clone();
+            let stream = file.into_stream()?;
+            let hash_table = async move { Self::build(build, stream, 0).await };
+            partition.hash_table = Some(hash_table.boxed().shared());
+        }
+
+        // The last probe releases the hash table once it finishes the probe.
+        partition.takes += 1;
+        let hash_table = match partition.takes >= self.build.probes {
+            true => partition.hash_table.take(),
+            false => partition.hash_table.clone(),
+        };
+        hash_table.ok_or_else(|| ErrorCode::LogicalError("The partition of JOIN is already joined"))
+    }
+
+    /// Joins the block with the rows of the same keys, the output has the columns of the block
+    /// followed by the columns of the right side, or the columns of the block only for the semi
+    /// and the anti joins.
+    fn probe(
+        &self,
+        join_type: JoinType,
+        executor: &ExpressionExecutor,
+        schema: &DataSchemaRef,
+        block: &DataBlock,
+    ) -> Result<DataBlock> {
+        let keeps_left = join_type.keeps_left() || join_type == JoinType::Anti;
+        if block.is_empty() || (self.rows.is_empty() && !keeps_left) {
+            return Ok(DataBlock::empty_with_schema(schema.clone()));
+        }
+
+        let keys_block = executor.execute(block)?;
+        let nulls = Self::null_rows(&keys_block)?;
+        let keys = Self::build_keys(&keys_block)?;
+        if !join_type.outputs_right() {
+            return Self::probe_exists(join_type, schema, block, &keys, &nulls, &self.rows);
+        }
+
+        let mut left_indices = vec![];
+        let mut right_indices = vec![];
+        for (row, key) in keys.iter().enumerate() {
+            let right_rows = match nulls[row] {
+                true => None,
+                false => self.rows.get(key),
+            };
+            match right_rows {
+                Some(right_rows) => {
+                    for right_row in right_rows {
+                        left_indices.push(row as u32);
+                        right_indices.push(Some(*right_row));
+                        if let Some(matched) = self.matched.get(*right_row as usize) {
+                            matched.store(true, Ordering::Relaxed);
+                        }
+                    }
+                }
+                None if join_type.keeps_left() => {
+                    left_indices.push(row as u32);
+                    right_indices.push(None);
+                }
+                None => {}
+            }
+        }
+
+        if left_indices.is_empty() {
+            return Ok(DataBlock::empty_with_schema(schema.clone()));
+        }
+
+        let left = DataBlock::block_take_by_indices(block, &[], &left_indices)?;
+        let right = DataBlock::block_take_by_opt_indices(&self.block, &right_indices)?;
+        let mut columns = Vec::with_capacity(schema.fields().len());
+        columns.extend_from_slice(left.columns());
+        columns.extend_from_slice(right.columns());
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    // The semi join outputs the rows of the block which match any row, the anti join outputs
+    // the others, the probe of a row stops at its first match.
+    fn probe_exists(
+        join_type: JoinType,
+        schema: &DataSchemaRef,
+        block: &DataBlock,
+        keys: &[Vec<u8>],
+        nulls: &[bool],
+        rows: &HashTable<Vec<u8>, Vec<u32>>,
+    ) -> Result<DataBlock> {
+        let semi = join_type == JoinType::Semi;
+        let indices = keys
+            .iter()
+            .enumerate()
+            .filter(|(row, key)| (!nulls[*row] && rows.get(*key).is_some()) == semi)
+            .map(|(row, _)| row as u32)
+            .collect::<Vec<_>>();
+
+        let columns = match indices.len() == block.num_rows() {
+            true => block.columns().to_vec(),
+            false => DataBlock::block_take_by_indices(block, &[], &indices)?
+                .columns()
+                .to_vec(),
+        };
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    /// Ends a probe, the last probe outputs the rows of the right side which match no row with
+    /// nulls for the columns of the left side, if the join keeps them.
+    fn finish_probe(&self, join_type: JoinType, schema: &DataSchemaRef) -> Result<DataBlock> {
+        let last_probe = self.unfinished_probes.fetch_sub(1, Ordering::AcqRel) == 1;
+        if !last_probe || !join_type.keeps_right() {
+            return Ok(DataBlock::empty_with_schema(schema.clone()));
+        }
+
+        let right_indices = self
+            .matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| !matched.load(Ordering::Relaxed))
+            .map(|(row, _)| row as u32)
+            .collect::<Vec<_>>();
+        if right_indices.is_empty() {
+            return Ok(DataBlock::empty_with_schema(schema.clone()));
+        }
+
+        let right = DataBlock::block_take_by_indices(&self.block, &[], &right_indices)?;
+        let left_fields = schema.fields().len() - right.num_columns();
+        let mut columns = Vec::with_capacity(schema.fields().len());
+        for field in &schema.fields()[..left_fields] {
+            let null = DataValue::from(field.data_type());
+            columns.push(DataColumn::Constant(null, right_indices.len()));
+        }
+        columns.extend_from_slice(right.columns());
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    fn build_keys(keys_block: &DataBlock) -> Result<Vec<Vec<u8>>> {
+        let key_columns = keys_block.columns().iter().collect::<Vec<_>>();
+        HashMethodSerializer::default().build_keys(&key_columns, keys_block.num_rows())
+    }
+
+    fn null_rows(keys_block: &DataBlock) -> Result<Vec<bool>> {
+        let rows = keys_block.num_rows();
+        let mut nulls = vec![false; rows];
+        for column in keys_block.columns() {
+            match column {
+                DataColumn::Constant(value, _) if value.is_null() => return Ok(vec![true; rows]),
+                DataColumn::Constant(_, _) => {}
+                DataColumn::Array(array) if array.null_count() > 0 => {
+                    for (row, null) in nulls.iter_mut().enumerate() {
+                        *null |= array.is_null(row);
+                    }
+                }
+                DataColumn::Array(_) => {}
+            }
+        }
+        Ok(nulls)
+    }
+}
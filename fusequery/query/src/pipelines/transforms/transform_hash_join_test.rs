@@ -0,0 +1,265 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::pipelines::transforms::*;
+use crate::sessions::FuseQueryContextRef;
+
+/// Joins numbers(5) read in two streams with the doubled numbers of the right side, which are
+/// numbers(right_rows) selected by the filter.
+async fn execute_join(
+    join_type: JoinType,
+    right_rows: i64,
+    right_filter: Option<Expression>,
+) -> Result<Vec<DataBlock>> {
+    let ctx = crate::tests::try_create_context()?;
+    execute_join_with_context(ctx, join_type, right_rows, right_filter).await
+}
+
+async fn execute_join_with_context(
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    right_rows: i64,
+    right_filter: Option<Expression>,
+) -> Result<Vec<DataBlock>> {
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    for _ in 0..2 {
+        let source = test_source.number_source_transform_for_test(5)?;
+        pipeline.add_source(Arc::new(source))?;
+    }
+
+    let mut right = PlanBuilder::from(&PlanNode::ReadSource(
+        test_source.number_read_source_plan_for_test(right_rows)?,
+    ));
+    if let Some(right_filter) = right_filter {
+        right = right.filter(right_filter)?;
+    }
+    let right = right
+        .project(&[
+            col("number").alias("b.number"),
+            add(col("number"), col("number")).alias("b.double"),
+        ])?
+        .build()?;
+
+    let plan = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .join(join_type, &right, &[col("number")], &[col("b.number")])?
+        .build()?;
+    if let PlanNode::Join(plan) = plan {
+        let hash_table_builder = JoinHashTableBuilder::create(
+            ctx.clone(),
+            plan.join_type,
+            plan.right.clone(),
+            plan.right_keys.clone(),
+        );
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(HashJoinTransform::try_create(
+                ctx.clone(),
+                plan.join_type,
+                plan.schema(),
+                plan.left.schema(),
+                plan.left_keys.clone(),
+                hash_table_builder.clone(),
+            )?))
+        })?;
+    }
+    pipeline.merge_processor()?;
+
+    let stream = pipeline.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join() -> Result<()> {
+    let result = execute_join(JoinType::Inner, 3, None).await?;
+    assert_eq!(result[0].num_columns(), 3);
+
+    // Every number of the right side matches the same number of the two streams.
+    let expected = vec![
+        "+--------+----------+----------+",
+        "| number | b.number | b.double |",
+        "+--------+----------+----------+",
+        "| 0      | 0        | 0        |",
+        "| 0      | 0        | 0        |",
+        "| 1      | 1        | 2        |",
+        "| 1      | 1        | 2        |",
+        "| 2      | 2        | 4        |",
+        "| 2      | 2        | 4        |",
+        "+--------+----------+----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_outer_hash_join() -> Result<()> {
+    // The numbers of the left side which match no number have nulls.
+    let result = execute_join(JoinType::Left, 3, None).await?;
+    let expected = vec![
+        "+--------+----------+----------+",
+        "| number | b.number | b.double |",
+        "+--------+----------+----------+",
+        "| 0      | 0        | 0        |",
+        "| 0      | 0        | 0        |",
+        "| 1      | 1        | 2        |",
+        "| 1      | 1        | 2        |",
+        "| 2      | 2        | 4        |",
+        "| 2      | 2        | 4        |",
+        "| 3      |          |          |",
+        "| 3      |          |          |",
+        "| 4      |          |          |",
+        "| 4      |          |          |",
+        "+--------+----------+----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The numbers of the right side which match no number are output once.
+    let result = execute_join(JoinType::Right, 8, Some(col("number").gt(lit(2u64)))).await?;
+    let expected = vec![
+        "+--------+----------+----------+",
+        "| number | b.number | b.double |",
+        "+--------+----------+----------+",
+        "| 3      | 3        | 6        |",
+        "| 3      | 3        | 6        |",
+        "| 4      | 4        | 8        |",
+        "| 4      | 4        | 8        |",
+        "|        | 5        | 10       |",
+        "|        | 6        | 12       |",
+        "|        | 7        | 14       |",
+        "+--------+----------+----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let result = execute_join(JoinType::Full, 8, Some(col("number").gt(lit(2u64)))).await?;
+    let expected = vec![
+        "+--------+----------+----------+",
+        "| number | b.number | b.double |",
+        "+--------+----------+----------+",
+        "| 0      |          |          |",
+        "| 0      |          |          |",
+        "| 1      |          |          |",
+        "| 1      |          |          |",
+        "| 2      |          |          |",
+        "| 2      |          |          |",
+        "| 3      | 3        | 6        |",
+        "| 3      | 3        | 6        |",
+        "| 4      | 4        | 8        |",
+        "| 4      | 4        | 8        |",
+        "|        | 5        | 10       |",
+        "|        | 6        | 12       |",
+        "|        | 7        | 14       |",
+        "+--------+----------+----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // No number of the right side matches.
+    let result = execute_join(JoinType::Left, 8, Some(col("number").gt(lit(7u64)))).await?;
+    assert_eq!(
+        result.iter().map(|block| block.num_rows()).sum::<usize>(),
+        10
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_semi_hash_join() -> Result<()> {
+    // Every number of the two streams is output once, however many numbers it matches.
+    let filter = Some(col("number").lt(lit(3u64)).or(col("number").gt(lit(5u64))));
+    let result = execute_join(JoinType::Semi, 8, filter.clone()).await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 0      |",
+        "| 0      |",
+        "| 1      |",
+        "| 1      |",
+        "| 2      |",
+        "| 2      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let result = execute_join(JoinType::Anti, 8, filter).await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 3      |",
+        "| 3      |",
+        "| 4      |",
+        "| 4      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // No number of the right side, every number of the left side is output by the anti join.
+    let result = execute_join(JoinType::Anti, 8, Some(col("number").gt(lit(7u64)))).await?;
+    assert_eq!(
+        result.iter().map(|block| block.num_rows()).sum::<usize>(),
+        10
+    );
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_hash_join_with_spill() -> Result<()> {
+    // The right side spills to the partitions once it holds any byte, then both streams of the
+    // left side spill to the partitions and join them one by one.
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_bytes_before_external_join(1)?;
+    let result = execute_join_with_context(ctx, JoinType::Inner, 3, None).await?;
+    let expected = vec![
+        "+--------+----------+----------+",
+        "| number | b.number | b.double |",
+        "+--------+----------+----------+",
+        "| 0      | 0        | 0        |",
+        "| 0      | 0        | 0        |",
+        "| 1      | 1        | 2        |",
+        "| 1      | 1        | 2        |",
+        "| 2      | 2        | 4        |",
+        "| 2      | 2        | 4        |",
+        "+--------+----------+----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The unmatched rows of the right side are output once by the partitions.
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_bytes_before_external_join(1)?;
+    let filter = Some(col("number").gt(lit(2u64)));
+    let result = execute_join_with_context(ctx, JoinType::Full, 8, filter).await?;
+    let expected = vec![
+        "+--------+----------+----------+",
+        "| number | b.number | b.double |",
+        "+--------+----------+----------+",
+        "| 0      |          |          |",
+        "| 0      |          |          |",
+        "| 1      |          |          |",
+        "| 1      |          |          |",
+        "| 2      |          |          |",
+        "| 2      |          |          |",
+        "| 3      | 3        | 6        |",
+        "| 3      | 3        | 6        |",
+        "| 4      | 4        | 8        |",
+        "| 4      | 4        | 8        |",
+        "|        | 5        | 10       |",
+        "|        | 6        | 12       |",
+        "|        | 7        | 14       |",
+        "+--------+----------+----------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
@@ -0,0 +1,493 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_arrow::arrow::array::build_compare;
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::ArrayRef;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::Expression;
+use common_planners::JoinType;
+use common_planners::PlanNode;
+use common_streams::CorrectWithSchemaStream;
+use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::transform_hash_join::JoinHashTable;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::FuseQueryContext;
+use crate::sessions::FuseQueryContextRef;
+
+// The blocks of the right side which the rows of a block of the left side pass before the joined
+// rows are output, so that the passed blocks are released.
+const MAX_PASSED_BLOCKS: usize = 2;
+
+/// Joins the blocks of the input (the left side) with the rows of the right side by merging both
+/// sides, which are sorted ascending on the keys. A row of the left side is joined with the rows
+/// of the right side of the same keys, which start at the first row not smaller than the keys of
+/// the previous row, so the right side is read once along the left side, and only its blocks
+/// from the rows of the current keys are held.
+///
+/// The left side is one sorted stream, so the join is one transform, and the output keeps the
+/// order of the left side. The rows of the right side which match no row are output as they are
+/// passed, if the join keeps them.
+pub struct MergeJoinTransform {
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    schema: DataSchemaRef,
+    left_executor: Arc<ExpressionExecutor>,
+    right: Arc<PlanNode>,
+    right_executor: Arc<ExpressionExecutor>,
+    input: Arc<dyn Processor>,
+}
+
+impl MergeJoinTransform {
+    pub fn try_create(
+        ctx: FuseQueryContextRef,
+        join_type: JoinType,
+        schema: DataSchemaRef,
+        left_schema: DataSchemaRef,
+        left_keys: Vec<Expression>,
+        right: Arc<PlanNode>,
+        right_keys: Vec<Expression>,
+    ) -> Result<Self> {
+        let left_executor =
+            JoinHashTable::keys_executor("merge join left keys", left_schema, left_keys)?;
+        let right_executor =
+            JoinHashTable::keys_executor("merge join right keys", right.schema(), right_keys)?;
+
+        Ok(MergeJoinTransform {
+            ctx,
+            join_type,
+            schema,
+            left_executor: Arc::new(left_executor),
+            right,
+            right_executor: Arc::new(right_executor),
+            input: Arc::new(EmptyProcessor::create()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for MergeJoinTransform {
+    fn name(&self) -> &str {
+        "MergeJoinTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        // The right side reads its own partitions, so it runs in a context of its own.
+        let right_ctx = FuseQueryContext::new(self.ctx.clone());
+        let mut right_pipeline = PipelineBuilder::create(right_ctx).build(&self.right)?;
+        let right_stream = right_pipeline.execute().await?;
+        let left_stream = self.input.execute().await?;
+
+        let joiner = MergeJoiner {
+            join_type: self.join_type,
+            schema: self.schema.clone(),
+            left_stream,
+            left_executor: self.left_executor.clone(),
+            left: None,
+            left_row: 0,
+            left_finished: false,
+            scan: None,
+            right_stream,
+            right_executor: self.right_executor.clone(),
+            right_fields: self.right.schema().fields().len(),
+            right: VecDeque::new(),
+            right_matched: VecDeque::new(),
+            right_finished: false,
+            cursor: (0, 0),
+            finished: false,
+        };
+        let stream = futures::stream::unfold(joiner, |mut joiner| async move {
+            match joiner.next().await {
+                Ok(None) => None,
+                Ok(Some(block)) => Some((Ok(block), joiner)),
+                Err(error) => {
+                    joiner.finished = true;
+                    Some((Err(error), joiner))
+                }
+            }
+        });
+        Ok(Box::pin(CorrectWithSchemaStream::new(
+            Box::pin(stream),
+            self.schema.clone(),
+        )))
+    }
+}
+
+// A block of a side of the join and the arrays of its keys.
+struct SortedBlock {
+    block: DataBlock,
+    keys: Vec<ArrayRef>,
+    // Whether the rows have NULL in any key, such rows match no row.
+    nulls: Vec<bool>,
+}
+
+impl SortedBlock {
+    fn try_create(executor: &ExpressionExecutor, block: DataBlock) -> Result<SortedBlock> {
+        let keys_block = executor.execute(&block)?;
+        let keys = keys_block
+            .columns()
+            .iter()
+            .map(|column| Ok(column.to_array()?.get_array_ref()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut nulls = vec![false; block.num_rows()];
+        for key in keys.iter().filter(|key| key.null_count() > 0) {
+            for (row, null) in nulls.iter_mut().enumerate() {
+                *null |= key.is_null(row);
+            }
+        }
+        Ok(SortedBlock { block, keys, nulls })
+    }
+}
+
+// A pair of the rows of the output: the row of the current block of the left side, and the row
+// of the right side as its block in the buffer and its row in the block, None for nulls.
+type Pair = (Option<u32>, Option<(usize, u32)>);
+
+struct MergeJoiner {
+    join_type: JoinType,
+    schema: DataSchemaRef,
+    left_stream: SendableDataBlockStream,
+    left_executor: Arc<ExpressionExecutor>,
+    // The block of the left side which is joined, and its next row to join.
+    left: Option<SortedBlock>,
+    left_row: usize,
+    left_finished: bool,
+    // The next row of the right side of the keys of the left row and whether any row matches,
+    // if the rows of the keys are found before the next block of the right side is read.
+    scan: Option<((usize, usize), bool)>,
+    right_stream: SendableDataBlockStream,
+    right_executor: Arc<ExpressionExecutor>,
+    right_fields: usize,
+    // The blocks of the right side from the block of the cursor.
+    right: VecDeque<SortedBlock>,
+    // Whether the rows of the blocks match any row, only if the unmatched rows are output.
+    right_matched: VecDeque<Vec<bool>>,
+    right_finished: bool,
+    // The first row of the right side which is not smaller than the keys of the left row, as its
+    // block in `right` and its row in the block.
+    cursor: (usize, usize),
+    finished: bool,
+}
+
+impl MergeJoiner {
+    async fn next(&mut self) -> Result<Option<DataBlock>> {
+        while !self.finished {
+            if self.left_finished {
+                match self.unmatched_right().await? {
+                    Some(block) if block.is_empty() => continue,
+                    Some(block) => return Ok(Some(block)),
+                    None => self.finished = true,
+                }
+                continue;
+            }
+
+            let left = match self.left.take() {
+                Some(left) => left,
+                None => match self.left_stream.next().await {
+                    Some(block) => {
+                        let block = block?;
+                        if block.is_empty() {
+                            continue;
+                        }
+                        self.left_row = 0;
+                        SortedBlock::try_create(&self.left_executor, block)?
+                    }
+                    None => {
+                        self.left_finished = true;
+                        continue;
+                    }
+                },
+            };
+
+            let mut pairs = vec![];
+            while self.join_rows(&left, &mut pairs)? {
+                self.read_right().await?;
+            }
+            let block = self.output(Some(&left), &pairs)?;
+            self.release_passed_blocks();
+            if self.left_row < left.block.num_rows() {
+                self.left = Some(left);
+            }
+            if !block.is_empty() {
+                return Ok(Some(block));
+            }
+        }
+        Ok(None)
+    }
+
+    // Joins the rows of the left block from `left_row` until the block ends, or until the rows
+    // of the right side pass `MAX_PASSED_BLOCKS` blocks. Returns true if it needs the next block
+    // of the right side, then it is called again to go on.
+    fn join_rows(&mut self, left: &SortedBlock, pairs: &mut Vec<Pair>) -> Result<bool> {
+        let comparators = self
+            .right
+            .iter()
+            .map(|right| {
+                left.keys
+                    .iter()
+                    .zip(right.keys.iter())
+                    .map(|(l, r)| build_compare(l.as_ref(), r.as_ref()))
+                    .collect::<common_arrow::arrow::error::Result<Vec<_>>>()
+            })
+            .collect::<common_arrow::arrow::error::Result<Vec<_>>>()?;
+        // The order of the keys of the left row and the keys of the right row.
+        let compare = |left_row: usize, (block, right_row): (usize, usize)| {
+            for comparator in &comparators[block] {
+                match comparator(left_row, right_row) {
+                    Ordering::Equal => continue,
+                    other => return other,
+                }
+            }
+            Ordering::Equal
+        };
+
+        let join_type = self.join_type;
+        while self.left_row < left.block.num_rows() && self.cursor.0 < MAX_PASSED_BLOCKS {
+            let row = self.left_row;
+            let (mut position, mut matched) = match self.scan.take() {
+                Some(scan) => scan,
+                None if left.nulls[row] => {
+                    if join_type.keeps_left() || join_type == JoinType::Anti {
+                        pairs.push((Some(row as u32), None));
+                    }
+                    self.left_row += 1;
+                    continue;
+                }
+                None => {
+                    // Passes the rows of the right side smaller than the keys of the row, which
+                    // no later row matches either.
+                    loop {
+                        let (block, right_row) = match Self::position(&self.right, self.cursor) {
+                            Some(position) => position,
+                            None if self.right_finished => break,
+                            None => return Ok(true),
+                        };
+                        let null = self.right[block].nulls[right_row];
+                        if !null && compare(row, (block, right_row)) != Ordering::Greater {
+                            break;
+                        }
+                        if join_type.keeps_right() && !self.right_matched[block][right_row] {
+                            pairs.push((None, Some((block, right_row as u32))));
+                        }
+                        self.cursor = (block, right_row + 1);
+                    }
+                    (self.cursor, false)
+                }
+            };
+
+            // The rows of the right side of the same keys, from the cursor.
+            loop {
+                let (block, right_row) = match Self::position(&self.right, position) {
+                    Some(position) => position,
+                    None if self.right_finished => break,
+                    None => {
+                        self.scan = Some((position, matched));
+                        return Ok(true);
+                    }
+                };
+                let null = self.right[block].nulls[right_row];
+                if null || compare(row, (block, right_row)) != Ordering::Equal {
+                    break;
+                }
+                matched = true;
+                if !join_type.outputs_right() {
+                    break;
+                }
+                pairs.push((Some(row as u32), Some((block, right_row as u32))));
+                if join_type.keeps_right() {
+                    self.right_matched[block][right_row] = true;
+                }
+                position = (block, right_row + 1);
+            }
+
+            let outputs_unmatched = match join_type {
+                JoinType::Semi => matched,
+                JoinType::Anti | JoinType::Left | JoinType::Full => !matched,
+                JoinType::Inner | JoinType::Right => false,
+            };
+            if outputs_unmatched {
+                pairs.push((Some(row as u32), None));
+            }
+            self.left_row += 1;
+        }
+        Ok(false)
+    }
+
+    // Outputs the rows of the right side which match no row once the left side is finished, the
+    // rows of the blocks read are output, then the blocks left one by one. Returns None at the
+    // end of the right side.
+    async fn unmatched_right(&mut self) -> Result<Option<DataBlock>> {
+        if !self.join_type.keeps_right() {
+            return Ok(None);
+        }
+        if self.right.is_empty() {
+            if self.right_finished {
+                return Ok(None);
+            }
+            self.read_right().await?;
+            return Ok(Some(DataBlock::empty_with_schema(self.schema.clone())));
+        }
+
+        let mut pairs = vec![];
+        let mut position = self.cursor;
+        while let Some((block, right_row)) = Self::position(&self.right, position) {
+            if !self.right_matched[block][right_row] {
+                pairs.push((None, Some((block, right_row as u32))));
+            }
+            position = (block, right_row + 1);
+        }
+        let block = self.output(None, &pairs)?;
+        self.cursor = (self.right.len(), 0);
+        self.release_passed_blocks();
+        Ok(Some(block))
+    }
+
+    // Reads the next block of the right side into the buffer.
+    async fn read_right(&mut self) -> Result<()> {
+        while let Some(block) = self.right_stream.next().await {
+            let block = block?;
+            if block.is_empty() {
+                continue;
+            }
+            let matched = match self.join_type.keeps_right() {
+                true => vec![false; block.num_rows()],
+                false => vec![],
+            };
+            self.right
+                .push_back(SortedBlock::try_create(&self.right_executor, block)?);
+            self.right_matched.push_back(matched);
+            return Ok(());
+        }
+        self.right_finished = true;
+        Ok(())
+    }
+
+    // The row of the right side at the position, or at the start of the next block if the
+    // position is at the end of its block, None if the row is not read.
+    fn position(
+        right: &VecDeque<SortedBlock>,
+        (mut block, mut row): (usize, usize),
+    ) -> Option<(usize, usize)> {
+        while block < right.len() {
+            if row < right[block].block.num_rows() {
+                return Some((block, row));
+            }
+            block += 1;
+            row = 0;
+        }
+        None
+    }
+
+    // Releases the blocks of the right side before the cursor, which no later row matches.
+    fn release_passed_blocks(&mut self) {
+        self.cursor = Self::position(&self.right, self.cursor).unwrap_or((self.right.len(), 0));
+        for _ in 0..self.cursor.0 {
+            self.right.pop_front();
+            self.right_matched.pop_front();
+        }
+        self.cursor.0 = 0;
+    }
+
+    // The block of the pairs, the columns of the left block are followed by the columns of the
+    // right side, or the columns of the left block only for the semi and the anti joins.
+    fn output(&self, left: Option<&SortedBlock>, pairs: &[Pair]) -> Result<DataBlock> {
+        if pairs.is_empty() {
+            return Ok(DataBlock::empty_with_schema(self.schema.clone()));
+        }
+
+        if !self.join_type.outputs_right() {
+            let columns = match left {
+                Some(left) => {
+                    let indices = pairs.iter().filter_map(|pair| pair.0).collect::<Vec<_>>();
+                    DataBlock::block_take_by_indices(&left.block, &[], &indices)?
+                        .columns()
+                        .to_vec()
+                }
+                None => vec![],
+            };
+            return Ok(DataBlock::create(self.schema.clone(), columns));
+        }
+
+        let left_fields = self.schema.fields().len() - self.right_fields;
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        match left {
+            Some(left) => {
+                let indices = pairs.iter().map(|pair| pair.0).collect::<Vec<_>>();
+                let block = DataBlock::block_take_by_opt_indices(&left.block, &indices)?;
+                columns.extend_from_slice(block.columns());
+            }
+            None => {
+                for field in &self.schema.fields()[..left_fields] {
+                    let null = DataValue::from(field.data_type());
+                    columns.push(DataColumn::Constant(null, pairs.len()));
+                }
+            }
+        }
+
+        // The rows are taken from the blocks of the right side which the pairs have.
+        let blocks = pairs
+            .iter()
+            .filter_map(|pair| pair.1.map(|(block, _)| block));
+        match (blocks.clone().min(), blocks.max()) {
+            (Some(first), Some(last)) => {
+                let mut offsets = vec![0];
+                for block in self.right.range(first..last) {
+                    offsets.push(offsets[offsets.len() - 1] + block.block.num_rows());
+                }
+                let block = match first == last {
+                    true => self.right[first].block.clone(),
+                    false => DataBlock::concat_blocks(
+                        &self
+                            .right
+                            .range(first..=last)
+                            .map(|block| block.block.clone())
+                            .collect::<Vec<_>>(),
+                    )?,
+                };
+                let indices = pairs
+                    .iter()
+                    .map(|pair| {
+                        pair.1
+                            .map(|(block, row)| (offsets[block - first] as u32) + row)
+                    })
+                    .collect::<Vec<_>>();
+                let block = DataBlock::block_take_by_opt_indices(&block, &indices)?;
+                columns.extend_from_slice(block.columns());
+            }
+            _ => {
+                for field in &self.schema.fields()[left_fields..] {
+                    let null = DataValue::from(field.data_type());
+                    columns.push(DataColumn::Constant(null, pairs.len()));
+                }
+            }
+        }
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::pipelines::processors::*;
+
+/// Joins numbers(6) sorted on number % 3 with the numbers 5, 6 and 7 of the right side sorted on
+/// number % 4, both sides are sorted on the keys, so they are merged.
+async fn execute_merge_join(join_type: JoinType) -> Result<Vec<DataBlock>> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let left = PlanBuilder::from(&PlanNode::ReadSource(
+        test_source.number_read_source_plan_for_test(6)?,
+    ))
+    .project(&[col("number"), modular(col("number"), lit(3u8)).alias("k")])?
+    .sort(&[sort("k", true, false)])?
+    .build()?;
+    let right = PlanBuilder::from(&PlanNode::ReadSource(
+        test_source.number_read_source_plan_for_test(8)?,
+    ))
+    .filter(col("number").gt(lit(4u64)))?
+    .project(&[
+        col("number").alias("b.number"),
+        modular(col("number"), lit(4u8)).alias("b.k"),
+    ])?
+    .sort(&[sort("b.k", true, false)])?
+    .build()?;
+    let plan = PlanBuilder::from(&left)
+        .join(join_type, &right, &[col("k")], &[col("b.k")])?
+        .build()?;
+    assert!(matches!(&plan, PlanNode::Join(join) if join.sorted));
+
+    let mut pipeline = PipelineBuilder::create(ctx).build(&plan)?;
+    let stream = pipeline.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_merge_join() -> Result<()> {
+    let result = execute_merge_join(JoinType::Inner).await?;
+    let expected = vec![
+        "+--------+---+----------+-----+",
+        "| number | k | b.number | b.k |",
+        "+--------+---+----------+-----+",
+        "| 1      | 1 | 5        | 1   |",
+        "| 2      | 2 | 6        | 2   |",
+        "| 4      | 1 | 5        | 1   |",
+        "| 5      | 2 | 6        | 2   |",
+        "+--------+---+----------+-----+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_outer_merge_join() -> Result<()> {
+    // The rows of the left side which match no row have nulls, and the rows of the right side.
+    let tests = vec![
+        (JoinType::Left, vec![
+            "+--------+---+----------+-----+",
+            "| number | k | b.number | b.k |",
+            "+--------+---+----------+-----+",
+            "| 0      | 0 |          |     |",
+            "| 1      | 1 | 5        | 1   |",
+            "| 2      | 2 | 6        | 2   |",
+            "| 3      | 0 |          |     |",
+            "| 4      | 1 | 5        | 1   |",
+            "| 5      | 2 | 6        | 2   |",
+            "+--------+---+----------+-----+",
+        ]),
+        (JoinType::Right, vec![
+            "+--------+---+----------+-----+",
+            "| number | k | b.number | b.k |",
+            "+--------+---+----------+-----+",
+            "|        |   | 7        | 3   |",
+            "| 1      | 1 | 5        | 1   |",
+            "| 2      | 2 | 6        | 2   |",
+            "| 4      | 1 | 5        | 1   |",
+            "| 5      | 2 | 6        | 2   |",
+            "+--------+---+----------+-----+",
+        ]),
+        (JoinType::Full, vec![
+            "+--------+---+----------+-----+",
+            "| number | k | b.number | b.k |",
+            "+--------+---+----------+-----+",
+            "|        |   | 7        | 3   |",
+            "| 0      | 0 |          |     |",
+            "| 1      | 1 | 5        | 1   |",
+            "| 2      | 2 | 6        | 2   |",
+            "| 3      | 0 |          |     |",
+            "| 4      | 1 | 5        | 1   |",
+            "| 5      | 2 | 6        | 2   |",
+            "+--------+---+----------+-----+",
+        ]),
+    ];
+    for (join_type, expected) in tests {
+        let result = execute_merge_join(join_type).await?;
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_semi_merge_join() -> Result<()> {
+    // The rows of the left side which match any row, or no row.
+    let tests = vec![
+        (JoinType::Semi, vec![
+            "+--------+---+",
+            "| number | k |",
+            "+--------+---+",
+            "| 1      | 1 |",
+            "| 2      | 2 |",
+            "| 4      | 1 |",
+            "| 5      | 2 |",
+            "+--------+---+",
+        ]),
+        (JoinType::Anti, vec![
+            "+--------+---+",
+            "| number | k |",
+            "+--------+---+",
+            "| 0      | 0 |",
+            "| 3      | 0 |",
+            "+--------+---+",
+        ]),
+    ];
+    for (join_type, expected) in tests {
+        let result = execute_merge_join(join_type).await?;
+        common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    }
+
+    Ok(())
+}
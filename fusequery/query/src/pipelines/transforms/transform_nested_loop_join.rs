@@ -0,0 +1,469 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_planners::Expression;
+use common_planners::JoinType;
+use common_planners::PlanNode;
+use common_streams::CorrectWithSchemaStream;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use futures::future::BoxFuture;
+use futures::future::Shared;
+use futures::FutureExt;
+use futures::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::PipelineBuilder;
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::FuseQueryContext;
+use crate::sessions::FuseQueryContextRef;
+use crate::sessions::MemoryReservation;
+
+/// Joins the blocks of the input (the left side) with every row of the right side, a pair of the
+/// rows is joined if the condition of the join is true for it, or always without condition. The
+/// right side is read into memory once and shared by all the transforms of the join.
+///
+/// The pairs of a block are evaluated by chunks of the rows of the block, about a block of pairs
+/// at a time. As the pairs grow with the product of the rows of both sides, the join fails once
+/// the pairs of all the transforms exceed `max_nested_loop_join_pairs`.
+pub struct NestedLoopJoinTransform {
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    schema: DataSchemaRef,
+    pairs_schema: DataSchemaRef,
+    condition: Option<Arc<NestedLoopJoinCondition>>,
+    right_builder: Arc<Mutex<NestedLoopJoinRightBuilder>>,
+    input: Arc<dyn Processor>,
+}
+
+impl NestedLoopJoinTransform {
+    pub fn try_create(
+        ctx: FuseQueryContextRef,
+        join_type: JoinType,
+        schema: DataSchemaRef,
+        left_schema: DataSchemaRef,
+        right_schema: DataSchemaRef,
+        condition: Option<Expression>,
+        right_builder: Arc<Mutex<NestedLoopJoinRightBuilder>>,
+    ) -> Result<Self> {
+        // The pairs have the columns of both sides, whatever the output of the join has.
+        let mut fields = left_schema.fields().clone();
+        fields.extend_from_slice(right_schema.fields());
+        let pairs_schema = DataSchemaRefExt::create(fields);
+        let condition = match condition {
+            Some(condition) => Some(Arc::new(NestedLoopJoinCondition::try_create(
+                &pairs_schema,
+                condition,
+            )?)),
+            None => None,
+        };
+        right_builder.lock().add_probe();
+
+        Ok(NestedLoopJoinTransform {
+            ctx,
+            join_type,
+            schema,
+            pairs_schema,
+            condition,
+            right_builder,
+            input: Arc::new(EmptyProcessor::create()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for NestedLoopJoinTransform {
+    fn name(&self) -> &str {
+        "NestedLoopJoinTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        let right = self.right_builder.lock().take_right()?;
+        let right = match self.ctx.execute_task(right)?.await {
+            Ok(right) => right?,
+            Err(error) => {
+                return Err(ErrorCode::TokioError(format!(
+                    "Cannot read the right side of JOIN. cause: {}",
+                    error
+                )))
+            }
+        };
+
+        let probe = Arc::new(NestedLoopJoinProbe {
+            join_type: self.join_type,
+            schema: self.schema.clone(),
+            pairs_schema: self.pairs_schema.clone(),
+            condition: self.condition.clone(),
+            max_block_size: self.ctx.get_settings().get_max_block_size()? as usize,
+            right,
+        });
+
+        let input_stream = self.input.execute().await?;
+        let block_probe = probe.clone();
+        let joined = input_stream
+            .map(move |block| block.and_then(|block| block_probe.join(&block)))
+            .flat_map(|blocks| {
+                let blocks = match blocks {
+                    Ok(blocks) => blocks.into_iter().map(Ok).collect(),
+                    Err(error) => vec![Err(error)],
+                };
+                futures::stream::iter(blocks)
+            });
+        let unmatched = futures::stream::once(async move { probe.finish() });
+        let stream = joined.chain(unmatched).filter(|block| {
+            futures::future::ready(!matches!(block, Ok(block) if block.is_empty()))
+        });
+
+        Ok(Box::pin(CorrectWithSchemaStream::new(
+            Box::pin(stream),
+            self.schema.clone(),
+        )))
+    }
+}
+
+// The condition of the join on the pairs of the rows.
+struct NestedLoopJoinCondition {
+    column_name: String,
+    executor: ExpressionExecutor,
+}
+
+impl NestedLoopJoinCondition {
+    fn try_create(schema: &DataSchemaRef, condition: Expression) -> Result<Self> {
+        let executor = ExpressionExecutor::try_create(
+            "join condition executor",
+            schema.clone(),
+            DataSchemaRefExt::create(vec![condition.to_data_field(schema)?]),
+            vec![condition.clone()],
+            false,
+        )?;
+        executor.validate()?;
+
+        Ok(NestedLoopJoinCondition {
+            column_name: condition.column_name(),
+            executor,
+        })
+    }
+
+    /// The pairs for which the condition is true.
+    fn select(&self, pairs: &DataBlock) -> Result<Vec<u32>> {
+        let passed = self.executor.execute(pairs)?;
+        let passed = passed.try_column_by_name(&self.column_name)?.to_array()?;
+        Ok(passed
+            .bool()?
+            .downcast_iter()
+            .enumerate()
+            .filter_map(|(pair, v)| match v {
+                Some(true) => Some(pair as u32),
+                _ => None,
+            })
+            .collect())
+    }
+}
+
+type NestedLoopJoinRightRef = Arc<NestedLoopJoinRight>;
+type SharedFuture = Shared<BoxFuture<'static, Result<NestedLoopJoinRightRef>>>;
+
+/// Reads the right side of the join on the first take, the later takes share the same read.
+pub struct NestedLoopJoinRightBuilder {
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    plan: Arc<PlanNode>,
+    probes: usize,
+    right: Option<SharedFuture>,
+}
+
+impl NestedLoopJoinRightBuilder {
+    pub fn create(
+        ctx: FuseQueryContextRef,
+        join_type: JoinType,
+        plan: Arc<PlanNode>,
+    ) -> Arc<Mutex<NestedLoopJoinRightBuilder>> {
+        Arc::new(Mutex::new(NestedLoopJoinRightBuilder {
+            ctx,
+            join_type,
+            plan,
+            probes: 0,
+            right: None,
+        }))
+    }
+
+    /// Registers a transform which joins with the right side.
+    pub fn add_probe(&mut self) {
+        self.probes += 1;
+    }
+
+    pub fn take_right(&mut self) -> Result<SharedFuture> {
+        if self.right.is_none() {
+            // The right side reads its own partitions, so it runs in a context of its own.
+            let read_ctx = FuseQueryContext::new(self.ctx.clone());
+            let mut pipeline = PipelineBuilder::create(read_ctx).build(&self.plan)?;
+            let max_pairs = self.ctx.get_settings().get_max_nested_loop_join_pairs()? as usize;
+            let ctx = self.ctx.clone();
+            let schema = self.plan.schema();
+            let keeps_unmatched = self.join_type.keeps_right();
+            let probes = self.probes;
+            let right = async move {
+                let stream = pipeline.execute().await?;
+                NestedLoopJoinRight::read(ctx, schema, stream, keeps_unmatched, probes, max_pairs)
+                    .await
+            };
+            self.right = Some(right.boxed().shared());
+        }
+
+        match &self.right {
+            Some(right) => Ok(right.clone()),
+            None => Err(ErrorCode::LogicalError(
+                "The right side of JOIN must be read",
+            )),
+        }
+    }
+}
+
+/// The rows of the right side in one block.
+pub struct NestedLoopJoinRight {
+    // The memory of the block, which is held until the probes finish.
+    _memory: MemoryReservation,
+    block: DataBlock,
+    /// Whether the rows of the block match any row, only if the unmatched rows are output.
+    matched: Vec<AtomicBool>,
+    unfinished_probes: AtomicUsize,
+    /// The pairs evaluated by all the probes.
+    pairs: AtomicUsize,
+    max_pairs: usize,
+}
+
+impl NestedLoopJoinRight {
+    async fn read(
+        ctx: FuseQueryContextRef,
+        schema: DataSchemaRef,
+        mut stream: SendableDataBlockStream,
+        keeps_unmatched: bool,
+        probes: usize,
+        max_pairs: usize,
+    ) -> Result<NestedLoopJoinRightRef> {
+        let start = Instant::now();
+        let mut memory = ctx.get_memory_tracker()?.reservation();
+        let mut blocks = vec![];
+        while let Some(block) = stream.next().await {
+            let block = block?;
+            if !block.is_empty() {
+                memory.resize(memory.size() + block.memory_size())?;
+                blocks.push(block);
+            }
+        }
+
+        let block = match blocks.is_empty() {
+            true => DataBlock::empty_with_schema(schema),
+            false => DataBlock::concat_blocks(&blocks)?,
+        };
+        drop(blocks);
+        memory.resize(block.memory_size())?;
+
+        tracing::debug!(
+            "Read the right side of JOIN with {} rows cost: {:?}",
+            block.num_rows(),
+            start.elapsed()
+        );
+        let matched = match keeps_unmatched {
+            true => (0..block.num_rows())
+                .map(|_| AtomicBool::new(false))
+                .collect(),
+            false => vec![],
+        };
+        Ok(Arc::new(NestedLoopJoinRight {
+            _memory: memory,
+            block,
+            matched,
+            unfinished_probes: AtomicUsize::new(probes),
+            pairs: AtomicUsize::new(0),
+            max_pairs,
+        }))
+    }
+
+    fn add_pairs(&self, pairs: usize) -> Result<()> {
+        let pairs = self.pairs.fetch_add(pairs, Ordering::Relaxed) + pairs;
+        if self.max_pairs != 0 && pairs > self.max_pairs {
+            return Err(ErrorCode::JoinLimitExceeded(format!(
+                "Limit for pairs of nested loop join exceeded, max pairs: {}, current pairs: {}",
+                self.max_pairs, pairs
+            )));
+        }
+        Ok(())
+    }
+}
+
+// What a transform joins its blocks by.
+struct NestedLoopJoinProbe {
+    join_type: JoinType,
+    schema: DataSchemaRef,
+    pairs_schema: DataSchemaRef,
+    condition: Option<Arc<NestedLoopJoinCondition>>,
+    max_block_size: usize,
+    right: NestedLoopJoinRightRef,
+}
+
+impl NestedLoopJoinProbe {
+    /// Joins the block with the rows of the right side, the output has the columns of the block
+    /// followed by the columns of the right side, or the columns of the block only for the semi
+    /// and the anti joins.
+    fn join(&self, block: &DataBlock) -> Result<Vec<DataBlock>> {
+        let left_rows = block.num_rows();
+        let right_rows = self.right.block.num_rows();
+        let mut left_matched = vec![false; left_rows];
+        let mut blocks = vec![];
+
+        // The semi and the anti joins without condition only tell whether the right side has rows.
+        let all_match = self.condition.is_none() && !self.join_type.outputs_right();
+        if all_match {
+            left_matched
+                .iter_mut()
+                .for_each(|matched| *matched = right_rows > 0);
+        } else if left_rows > 0 && right_rows > 0 {
+            self.right.add_pairs(left_rows * right_rows)?;
+            let chunk_rows = (self.max_block_size / right_rows).max(1);
+            for start in (0..left_rows).step_by(chunk_rows) {
+                let end = (start + chunk_rows).min(left_rows);
+                let joined = self.join_chunk(block, start, end, &mut left_matched)?;
+                blocks.extend(joined);
+            }
+        }
+
+        // The rows of the block which match no row, and the rows of the semi and the anti joins.
+        let keeps_unmatched = self.join_type.keeps_left() || self.join_type == JoinType::Anti;
+        let indices = left_matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| match self.join_type {
+                JoinType::Semi => **matched,
+                _ => keeps_unmatched && !**matched,
+            })
+            .map(|(row, _)| row as u32)
+            .collect::<Vec<_>>();
+        if !indices.is_empty() {
+            let left = DataBlock::block_take_by_indices(block, &[], &indices)?;
+            let mut columns = left.columns().to_vec();
+            for field in &self.schema.fields()[columns.len()..] {
+                let null = DataValue::from(field.data_type());
+                columns.push(DataColumn::Constant(null, indices.len()));
+            }
+            blocks.push(DataBlock::create(self.schema.clone(), columns));
+        }
+        Ok(blocks)
+    }
+
+    // Joins the rows [start, end) of the block with all the rows of the right side.
+    fn join_chunk(
+        &self,
+        block: &DataBlock,
+        start: usize,
+        end: usize,
+        left_matched: &mut [bool],
+    ) -> Result<Option<DataBlock>> {
+        let right_rows = self.right.block.num_rows();
+        let pairs = (end - start) * right_rows;
+        let mut left_indices = Vec::with_capacity(pairs);
+        let mut right_indices = Vec::with_capacity(pairs);
+        for left_row in start..end {
+            for right_row in 0..right_rows {
+                left_indices.push(left_row as u32);
+                right_indices.push(right_row as u32);
+            }
+        }
+
+        let left = DataBlock::block_take_by_indices(block, &[], &left_indices)?;
+        let right = DataBlock::block_take_by_indices(&self.right.block, &[], &right_indices)?;
+        let mut columns = left.columns().to_vec();
+        columns.extend_from_slice(right.columns());
+        let mut pairs = DataBlock::create(self.pairs_schema.clone(), columns);
+        if let Some(condition) = &self.condition {
+            let passed = condition.select(&pairs)?;
+            for pair in passed.iter() {
+                left_matched[left_indices[*pair as usize] as usize] = true;
+                let right_row = right_indices[*pair as usize] as usize;
+                if let Some(matched) = self.right.matched.get(right_row) {
+                    matched.store(true, Ordering::Relaxed);
+                }
+            }
+            if passed.is_empty() {
+                return Ok(None);
+            }
+            if passed.len() != pairs.num_rows() {
+                pairs = DataBlock::block_take_by_indices(&pairs, &[], &passed)?;
+            }
+        } else {
+            left_matched[start..end]
+                .iter_mut()
+                .for_each(|matched| *matched = true);
+            self.right
+                .matched
+                .iter()
+                .for_each(|matched| matched.store(true, Ordering::Relaxed));
+        }
+
+        match self.join_type.outputs_right() {
+            true => Ok(Some(DataBlock::create(
+                self.schema.clone(),
+                pairs.columns().to_vec(),
+            ))),
+            false => Ok(None),
+        }
+    }
+
+    /// Ends a probe, the last probe outputs the rows of the right side which match no row with
+    /// nulls for the columns of the left side, if the join keeps them.
+    fn finish(&self) -> Result<DataBlock> {
+        let last_probe = self.right.unfinished_probes.fetch_sub(1, Ordering::AcqRel) == 1;
+        if !last_probe || !self.join_type.keeps_right() {
+            return Ok(DataBlock::empty_with_schema(self.schema.clone()));
+        }
+
+        let right_indices = self
+            .right
+            .matched
+            .iter()
+            .enumerate()
+            .filter(|(_, matched)| !matched.load(Ordering::Relaxed))
+            .map(|(row, _)| row as u32)
+            .collect::<Vec<_>>();
+        if right_indices.is_empty() {
+            return Ok(DataBlock::empty_with_schema(self.schema.clone()));
+        }
+
+        let right = DataBlock::block_take_by_indices(&self.right.block, &[], &right_indices)?;
+        let left_fields = self.schema.fields().len() - right.num_columns();
+        let mut columns = Vec::with_capacity(self.schema.fields().len());
+        for field in &self.schema.fields()[..left_fields] {
+            let null = DataValue::from(field.data_type());
+            columns.push(DataColumn::Constant(null, right_indices.len()));
+        }
+        columns.extend_from_slice(right.columns());
+        Ok(DataBlock::create(self.schema.clone(), columns))
+    }
+}
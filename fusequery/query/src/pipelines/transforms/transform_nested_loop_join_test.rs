@@ -0,0 +1,204 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datablocks::assert_blocks_sorted_eq;
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+use crate::pipelines::transforms::*;
+use crate::sessions::FuseQueryContextRef;
+
+/// Joins numbers(3) read in two streams with numbers(right_rows) of the right side by the
+/// condition.
+async fn execute_join(
+    ctx: FuseQueryContextRef,
+    join_type: JoinType,
+    right_rows: i64,
+    condition: Option<Expression>,
+) -> Result<Vec<DataBlock>> {
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    for _ in 0..2 {
+        let source = test_source.number_source_transform_for_test(3)?;
+        pipeline.add_source(Arc::new(source))?;
+    }
+
+    let right = PlanBuilder::from(&PlanNode::ReadSource(
+        test_source.number_read_source_plan_for_test(right_rows)?,
+    ))
+    .project(&[col("number").alias("b.number")])?
+    .build()?;
+
+    let plan = PlanBuilder::create(test_source.number_schema_for_test()?)
+        .nested_loop_join(join_type, &right, condition)?
+        .build()?;
+    if let PlanNode::Join(plan) = plan {
+        let right_builder =
+            NestedLoopJoinRightBuilder::create(ctx.clone(), plan.join_type, plan.right.clone());
+        pipeline.add_simple_transform(|| {
+            Ok(Box::new(NestedLoopJoinTransform::try_create(
+                ctx.clone(),
+                plan.join_type,
+                plan.schema(),
+                plan.left.schema(),
+                plan.right.schema(),
+                plan.condition.clone(),
+                right_builder.clone(),
+            )?))
+        })?;
+    }
+    pipeline.merge_processor()?;
+
+    let stream = pipeline.execute().await?;
+    stream.try_collect::<Vec<_>>().await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_cross_join() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let result = execute_join(ctx, JoinType::Inner, 2, None).await?;
+    let expected = vec![
+        "+--------+----------+",
+        "| number | b.number |",
+        "+--------+----------+",
+        "| 0      | 0        |",
+        "| 0      | 0        |",
+        "| 0      | 1        |",
+        "| 0      | 1        |",
+        "| 1      | 0        |",
+        "| 1      | 0        |",
+        "| 1      | 1        |",
+        "| 1      | 1        |",
+        "| 2      | 0        |",
+        "| 2      | 0        |",
+        "| 2      | 1        |",
+        "| 2      | 1        |",
+        "+--------+----------+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_nested_loop_join() -> Result<()> {
+    // The pairs of a block are evaluated by the chunks of one row of the left side.
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_block_size(2)?;
+    let condition = col("number").lt(col("b.number"));
+    let result = execute_join(ctx, JoinType::Inner, 3, Some(condition.clone())).await?;
+    let expected = vec![
+        "+--------+----------+",
+        "| number | b.number |",
+        "+--------+----------+",
+        "| 0      | 1        |",
+        "| 0      | 1        |",
+        "| 0      | 2        |",
+        "| 0      | 2        |",
+        "| 1      | 2        |",
+        "| 1      | 2        |",
+        "+--------+----------+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The numbers of the left side which match no number have nulls.
+    let ctx = crate::tests::try_create_context()?;
+    let result = execute_join(ctx, JoinType::Left, 3, Some(condition)).await?;
+    let expected = vec![
+        "+--------+----------+",
+        "| number | b.number |",
+        "+--------+----------+",
+        "| 0      | 1        |",
+        "| 0      | 1        |",
+        "| 0      | 2        |",
+        "| 0      | 2        |",
+        "| 1      | 2        |",
+        "| 1      | 2        |",
+        "| 2      |          |",
+        "| 2      |          |",
+        "+--------+----------+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+
+    // The numbers of the right side which match no number are output once.
+    let ctx = crate::tests::try_create_context()?;
+    let condition = col("number").gt(col("b.number"));
+    let result = execute_join(ctx, JoinType::Right, 4, Some(condition)).await?;
+    let expected = vec![
+        "+--------+----------+",
+        "| number | b.number |",
+        "+--------+----------+",
+        "| 1      | 0        |",
+        "| 1      | 0        |",
+        "| 2      | 0        |",
+        "| 2      | 0        |",
+        "| 2      | 1        |",
+        "| 2      | 1        |",
+        "|        | 2        |",
+        "|        | 3        |",
+        "+--------+----------+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_semi_nested_loop_join() -> Result<()> {
+    let condition = col("number").gt(col("b.number"));
+    let ctx = crate::tests::try_create_context()?;
+    let result = execute_join(ctx, JoinType::Semi, 4, Some(condition.clone())).await?;
+    assert_eq!(result[0].num_columns(), 1);
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 1      |",
+        "| 1      |",
+        "| 2      |",
+        "| 2      |",
+        "+--------+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+
+    let ctx = crate::tests::try_create_context()?;
+    let result = execute_join(ctx, JoinType::Anti, 4, Some(condition)).await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 0      |",
+        "| 0      |",
+        "+--------+",
+    ];
+    assert_blocks_sorted_eq(expected, result.as_slice());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_nested_loop_join_limit() -> Result<()> {
+    // Every stream joins 3 x 3 pairs.
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_nested_loop_join_pairs(5)?;
+    let result = execute_join(ctx, JoinType::Inner, 3, None).await;
+    let actual = result.err().unwrap().to_string();
+    assert!(
+        actual.starts_with(
+            "Code: 58, displayText = Limit for pairs of nested loop join exceeded, max pairs: 5"
+        ),
+        "{}",
+        actual
+    );
+
+    Ok(())
+}
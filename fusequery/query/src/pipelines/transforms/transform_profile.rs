@@ -0,0 +1,156 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_datablocks::DataBlock;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use futures::Stream;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+
+/// The runtime statistics of the blocks produced by a plan node, collected by the
+/// `ProfileTransform`s after all the processors of the node for EXPLAIN ANALYZE.
+///
+/// The bytes are the memory size of the blocks. The elapsed time is the wall time from the first
+/// processor of the node starts to the last one ends, including the time of the inputs of it.
+pub struct PlanProfile {
+    base: Instant,
+    rows: AtomicUsize,
+    bytes: AtomicUsize,
+    // The nanoseconds from the base to the first start and to the last end of the processors.
+    start_nanos: AtomicU64,
+    end_nanos: AtomicU64,
+}
+
+impl PlanProfile {
+    pub fn create() -> PlanProfile {
+        PlanProfile {
+            base: Instant::now(),
+            rows: AtomicUsize::new(0),
+            bytes: AtomicUsize::new(0),
+            start_nanos: AtomicU64::new(u64::MAX),
+            end_nanos: AtomicU64::new(0),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        let start = self.start_nanos.load(Ordering::Relaxed);
+        let end = self.end_nanos.load(Ordering::Relaxed);
+        Duration::from_nanos(end.saturating_sub(start))
+    }
+
+    fn add_block(&self, block: &DataBlock) {
+        self.rows.fetch_add(block.num_rows(), Ordering::Relaxed);
+        self.bytes.fetch_add(block.memory_size(), Ordering::Relaxed);
+    }
+
+    fn start(&self) {
+        self.start_nanos.fetch_min(self.nanos(), Ordering::Relaxed);
+    }
+
+    fn end(&self) {
+        self.end_nanos.fetch_max(self.nanos(), Ordering::Relaxed);
+    }
+
+    fn nanos(&self) -> u64 {
+        self.base.elapsed().as_nanos() as u64
+    }
+}
+
+impl fmt::Display for PlanProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "rows: {}, bytes: {}, elapsed: {:?}",
+            self.rows(),
+            self.bytes(),
+            self.elapsed()
+        )
+    }
+}
+
+/// Passes the blocks of the input through, adding them to the profile of a plan node.
+pub struct ProfileTransform {
+    profile: Arc<PlanProfile>,
+    input: Arc<dyn Processor>,
+}
+
+impl ProfileTransform {
+    pub fn create(profile: Arc<PlanProfile>) -> Self {
+        ProfileTransform {
+            profile,
+            input: Arc::new(EmptyProcessor::create()),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for ProfileTransform {
+    fn name(&self) -> &str {
+        "ProfileTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        self.profile.start();
+        let input = self.input.execute().await?;
+        Ok(Box::pin(ProfileStream {
+            input,
+            profile: self.profile.clone(),
+        }))
+    }
+}
+
+struct ProfileStream {
+    input: SendableDataBlockStream,
+    profile: Arc<PlanProfile>,
+}
+
+impl Stream for ProfileStream {
+    type Item = Result<DataBlock>;
+
+    fn poll_next(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let next = this.input.as_mut().poll_next(ctx);
+        match &next {
+            Poll::Ready(Some(Ok(block))) => this.profile.add_block(block),
+            Poll::Ready(None) => this.profile.end(),
+            _ => {}
+        }
+        next
+    }
+}
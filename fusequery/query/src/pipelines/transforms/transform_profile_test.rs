@@ -0,0 +1,46 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_profile() -> Result<()> {
+    use crate::pipelines::processors::*;
+    use crate::pipelines::transforms::*;
+
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    let mut pipeline = Pipeline::create(ctx.clone());
+    let a = test_source.number_source_transform_for_test(8)?;
+    pipeline.add_source(Arc::new(a))?;
+
+    let profile = Arc::new(PlanProfile::create());
+    pipeline.add_simple_transform(|| Ok(Box::new(ProfileTransform::create(profile.clone()))))?;
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let rows: usize = result.iter().map(|block| block.num_rows()).sum();
+    let bytes: usize = result.iter().map(|block| block.memory_size()).sum();
+    assert_eq!(8, rows);
+
+    // The blocks pass through, they are all counted once the stream ends.
+    assert_eq!(8, profile.rows());
+    assert_eq!(bytes, profile.bytes());
+    assert_eq!(
+        format!(
+            "rows: 8, bytes: {}, elapsed: {:?}",
+            bytes,
+            profile.elapsed()
+        ),
+        profile.to_string()
+    );
+
+    Ok(())
+}
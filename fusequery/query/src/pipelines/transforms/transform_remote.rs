@@ -8,8 +8,10 @@ use std::sync::Arc;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_streams::DataBlockStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use futures::StreamExt;
 
 use crate::api::FlightTicket;
 use crate::pipelines::processors::EmptyProcessor;
@@ -74,6 +76,16 @@ impl Processor for RemoteTransform {
             self.fetch_node_name
         );
 
+        let fetch_stream = self.fetch_stream().await;
+        match self.ctx.get_settings().get_allow_partial_results()? {
+            0 => fetch_stream,
+            _ => self.partial_results_stream(fetch_stream),
+        }
+    }
+}
+
+impl RemoteTransform {
+    async fn fetch_stream(&self) -> Result<SendableDataBlockStream> {
         let context = self.ctx.clone();
         let cluster = context.try_get_cluster()?;
         let fetch_node = cluster.get_node_by_name(self.fetch_node_name.clone())?;
@@ -84,7 +96,43 @@ impl Processor for RemoteTransform {
 
         let ticket = FlightTicket::stream(&self.query_id, &self.stage_id, &self.stream_id);
         flight_client
-            .fetch_stream(ticket, data_schema, timeout)
+            .fetch_stream(context, ticket, data_schema, timeout)
             .await
     }
+
+    /// With allow_partial_results, a failure of the remote stage ends its stream with a warning,
+    /// the blocks received before the failure are kept.
+    fn partial_results_stream(
+        &self,
+        fetch_stream: Result<SendableDataBlockStream>,
+    ) -> Result<SendableDataBlockStream> {
+        let ctx = self.ctx.clone();
+        let stage_description = format!("stage {} of node {}", self.stage_id, self.fetch_node_name);
+
+        match fetch_stream {
+            Err(error) => {
+                ctx.push_warning(format!(
+                    "Skipped the results of {}, cause: {}",
+                    stage_description, error
+                ));
+                Ok(Box::pin(DataBlockStream::create(
+                    self.schema.clone(),
+                    None,
+                    vec![],
+                )))
+            }
+            Ok(stream) => Ok(Box::pin(stream.scan((), move |_, item| {
+                futures::future::ready(match item {
+                    Ok(block) => Some(Ok(block)),
+                    Err(error) => {
+                        ctx.push_warning(format!(
+                            "Partial results of {}, cause: {}",
+                            stage_description, error
+                        ));
+                        None
+                    }
+                })
+            }))),
+        }
+    }
 }
@@ -0,0 +1,54 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::Processor;
+use crate::pipelines::transforms::RemoteTransform;
+use crate::tests::try_create_cluster_context;
+use crate::tests::ClusterNode;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_remote_partial_results() -> Result<()> {
+    // Nothing is listening on the address of the node, the remote stage always fails.
+    let ctx = try_create_cluster_context(&[ClusterNode::create("unreachable", 1, "127.0.0.1:1")])?;
+    let schema = DataSchemaRefExt::create(vec![DataField::new("number", DataType::UInt64, false)]);
+
+    let remote = RemoteTransform::try_create(
+        "query_id".to_string(),
+        "stage_id".to_string(),
+        "stream_id".to_string(),
+        "unreachable".to_string(),
+        schema.clone(),
+        ctx.clone(),
+    )?;
+
+    // Fail the query by default.
+    {
+        let result = match remote.execute().await {
+            Ok(stream) => stream.try_collect::<Vec<_>>().await,
+            Err(error) => Err(error),
+        };
+        assert!(result.is_err());
+        assert!(ctx.get_warnings().is_empty());
+    }
+
+    // Return the partial results with warnings.
+    {
+        ctx.get_settings().set_allow_partial_results(1)?;
+        let stream = remote.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+        assert!(result.is_empty());
+
+        let warnings = ctx.get_warnings();
+        assert_eq!(1, warnings.len());
+        assert!(warnings[0].contains("stage stage_id of node unreachable"));
+    }
+
+    Ok(())
+}
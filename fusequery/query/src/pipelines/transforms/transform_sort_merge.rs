@@ -6,8 +6,10 @@ use std::any::Any;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_arrow::arrow::compute::SortOptions;
 use common_datablocks::DataBlock;
-use common_datavalues::DataSchemaRef;
+use common_datablocks::SortColumnDescription;
+use common_datavalues::prelude::*;
 use common_exception::Result;
 use common_planners::Expression;
 use common_streams::CorrectWithSchemaStream;
@@ -19,8 +21,17 @@ use futures::StreamExt;
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
 use crate::pipelines::transforms::transform_sort_partial::get_sort_descriptions;
+use crate::sessions::FuseQueryContextRef;
+use crate::sessions::MemoryReservation;
+use crate::sessions::SpillFile;
 
+/// Merges the sorted blocks of the input into one sorted block.
+///
+/// Once the blocks hold more bytes than `max_bytes_before_external_sort`, they are merged into a
+/// sorted run which is spilled to a temporary file, and the runs are merged by their blocks at
+/// the end, so the sort holds about a block of every run at a time.
 pub struct SortMergeTransform {
+    ctx: FuseQueryContextRef,
     schema: DataSchemaRef,
     exprs: Vec<Expression>,
     limit: Option<usize>,
@@ -29,11 +40,13 @@ pub struct SortMergeTransform {
 
 impl SortMergeTransform {
     pub fn try_create(
+        ctx: FuseQueryContextRef,
         schema: DataSchemaRef,
         exprs: Vec<Expression>,
         limit: Option<usize>,
     ) -> Result<Self> {
         Ok(SortMergeTransform {
+            ctx,
             schema,
             exprs,
             limit,
@@ -67,9 +80,86 @@ impl Processor for SortMergeTransform {
         let sort_columns_descriptions = get_sort_descriptions(&self.schema, &self.exprs)?;
         let mut blocks = vec![];
         let mut stream = self.input.execute().await?;
+        // The blocks to merge are held until the input is finished.
+        let mut memory = self.ctx.get_memory_tracker()?.reservation();
+        let max_bytes = self
+            .ctx
+            .get_settings()
+            .get_max_bytes_before_external_sort()? as usize;
+        let max_block_size = self.ctx.get_settings().get_max_block_size()? as usize;
+        let mut runs: Vec<SpillFile> = vec![];
 
+        let mut rows = 0;
         while let Some(block) = stream.next().await {
-            blocks.push(block?);
+            let block = block?;
+            rows += block.num_rows();
+            memory.resize(memory.size() + block.memory_size())?;
+            blocks.push(block);
+
+            // The top N keeps the first rows merged as the blocks come, so it holds at most
+            // twice the rows of the limit rather than all the blocks.
+            if let Some(limit) = self.limit {
+                if blocks.len() > 1 && rows > limit * 2 {
+                    let merged = DataBlock::merge_sort_blocks(
+                        &blocks,
+                        &sort_columns_descriptions,
+                        Some(limit),
+                    )?;
+                    rows = merged.num_rows();
+                    memory.resize(merged.memory_size())?;
+                    blocks = vec![merged];
+                }
+            }
+
+            if max_bytes > 0 && memory.size() > max_bytes {
+                let run =
+                    DataBlock::merge_sort_blocks(&blocks, &sort_columns_descriptions, self.limit)?;
+                let mut file = self.ctx.create_spill_file(run.schema())?;
+                for block in DataBlock::split_block_by_size(&run, max_block_size)? {
+                    file.write(block)?;
+                }
+                runs.push(file);
+                rows = 0;
+                memory.resize(0)?;
+                blocks.clear();
+            }
+        }
+
+        if !runs.is_empty() {
+            tracing::debug!(
+                "Merge {} sorted runs of {} spilled rows",
+                runs.len(),
+                runs.iter().map(|run| run.rows()).sum::<usize>()
+            );
+            let mut streams: Vec<SendableDataBlockStream> = vec![];
+            for run in runs {
+                streams.push(run.into_stream()?);
+            }
+            // The blocks in memory are the last run.
+            if !blocks.is_empty() {
+                let run =
+                    DataBlock::merge_sort_blocks(&blocks, &sort_columns_descriptions, self.limit)?;
+                let run_blocks = DataBlock::split_block_by_size(&run, max_block_size)?;
+                let stream = DataBlockStream::create(self.schema.clone(), None, run_blocks);
+                streams.push(Box::pin(stream));
+            }
+
+            let merger =
+                SortedRunsMerger::create(streams, sort_columns_descriptions, self.limit, memory);
+            let stream = futures::stream::unfold(merger, |mut merger| async move {
+                match merger.next().await {
+                    Ok(None) => None,
+                    Ok(Some(block)) => Some((Ok(block), merger)),
+                    Err(error) => {
+                        merger.finished = true;
+                        Some((Err(error), merger))
+                    }
+                }
+            });
+            return Ok(Box::pin(CorrectWithSchemaStream::new(
+                Box::pin(stream),
+                self.schema.clone(),
+            )));
         }
 
         let results = match blocks.len() {
@@ -87,3 +177,122 @@ impl Processor for SortMergeTransform {
         )))
     }
 }
+
+// Merges the sorted runs by their blocks. The run whose block in memory ends with the smallest
+// row bounds every step: the rows of the blocks of the runs up to that row are merged and output,
+// as no row read later can come before them.
+struct SortedRunsMerger {
+    runs: Vec<SendableDataBlockStream>,
+    // The rows of the runs which are read but not output yet, None once the run is finished.
+    heads: Vec<Option<DataBlock>>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    sort_options: Vec<SortOptions>,
+    // The rows left to output, if the sort has a limit.
+    limit: Option<usize>,
+    finished: bool,
+    // The memory of the run which is not spilled, which is held until the merge is finished.
+    _memory: MemoryReservation,
+}
+
+impl SortedRunsMerger {
+    fn create(
+        runs: Vec<SendableDataBlockStream>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        limit: Option<usize>,
+        memory: MemoryReservation,
+    ) -> SortedRunsMerger {
+        let sort_options = sort_columns_descriptions
+            .iter()
+            .map(|f| SortOptions {
+                descending: !f.asc,
+                nulls_first: f.nulls_first,
+            })
+            .collect();
+        SortedRunsMerger {
+            heads: vec![Some(DataBlock::empty()); runs.len()],
+            runs,
+            sort_columns_descriptions,
+            sort_options,
+            limit,
+            finished: false,
+            _memory: memory,
+        }
+    }
+
+    async fn next(&mut self) -> Result<Option<DataBlock>> {
+        if self.finished || self.limit == Some(0) {
+            return Ok(None);
+        }
+
+        // Reads the next blocks of the runs which have no rows left.
+        for (run, head) in self.runs.iter_mut().zip(self.heads.iter_mut()) {
+            while matches!(head, Some(block) if block.is_empty()) {
+                *head = match run.next().await {
+                    Some(block) => Some(block?),
+                    None => None,
+                };
+            }
+        }
+
+        let mut bound: Option<(usize, DataBlock)> = None;
+        for (run, head) in self.heads.iter().enumerate() {
+            if let Some(block) = head {
+                let last = DataBlock::slice_block(block, block.num_rows() - 1, 1);
+                let smaller = match &bound {
+                    None => true,
+                    Some((_, bound)) => self.rows_before(&last, bound)? == 1,
+                };
+                if smaller {
+                    bound = Some((run, last));
+                }
+            }
+        }
+        let (bound_run, bound) = match bound {
+            None => {
+                self.finished = true;
+                return Ok(None);
+            }
+            Some(bound) => bound,
+        };
+
+        let mut blocks = vec![];
+        for run in 0..self.heads.len() {
+            if let Some(block) = &self.heads[run] {
+                let rows = match run == bound_run {
+                    true => block.num_rows(),
+                    false => self.rows_before(block, &bound)?,
+                };
+                if rows > 0 {
+                    blocks.push(DataBlock::slice_block(block, 0, rows));
+                    let rest = DataBlock::slice_block(block, rows, block.num_rows() - rows);
+                    self.heads[run] = Some(rest);
+                }
+            }
+        }
+
+        let block =
+            DataBlock::merge_sort_blocks(&blocks, &self.sort_columns_descriptions, self.limit)?;
+        if let Some(limit) = self.limit.as_mut() {
+            *limit -= block.num_rows().min(*limit);
+        }
+        Ok(Some(block))
+    }
+
+    // The rows of the sorted block which come before the row of the bound.
+    fn rows_before(&self, block: &DataBlock, bound: &DataBlock) -> Result<usize> {
+        let indices = DataColumnCommon::merge_indices(
+            &self.sort_columns(block)?,
+            &self.sort_columns(bound)?,
+            &self.sort_options,
+            None,
+        )?;
+        Ok(indices.iter().take_while(|lhs| **lhs).count())
+    }
+
+    fn sort_columns(&self, block: &DataBlock) -> Result<Vec<DataColumn>> {
+        self.sort_columns_descriptions
+            .iter()
+            .map(|f| f.collation.keys(block.try_column_by_name(&f.column_name)?))
+            .collect()
+    }
+}
@@ -7,6 +7,7 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use common_datablocks::SortColumnDescription;
+use common_datavalues::DataCollation;
 use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
@@ -82,11 +83,12 @@ pub fn get_sort_descriptions(
                 asc,
                 nulls_first,
             } => {
-                let column_name = expr.to_data_field(schema)?.name().clone();
+                let field = expr.to_data_field(schema)?;
                 sort_columns_descriptions.push(SortColumnDescription {
-                    column_name,
+                    column_name: field.name().clone(),
                     asc,
                     nulls_first,
+                    collation: DataCollation::from_field(&field)?,
                 });
             }
             _ => {
@@ -4,6 +4,7 @@
 
 use std::sync::Arc;
 
+use common_datavalues::DataValue;
 use common_exception::Result;
 use common_planners::*;
 use common_planners::{self};
@@ -11,8 +12,10 @@ use common_runtime::tokio;
 use futures::TryStreamExt;
 use pretty_assertions::assert_eq;
 
+use crate::interpreters::InterpreterFactory;
 use crate::pipelines::processors::*;
 use crate::pipelines::transforms::*;
+use crate::sql::PlanParser;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_transform_sort() -> Result<()> {
@@ -39,6 +42,7 @@ async fn test_transform_sort() -> Result<()> {
 
     pipeline.add_simple_transform(|| {
         Ok(Box::new(SortMergeTransform::try_create(
+            ctx.clone(),
             plan.schema().clone(),
             sort_expression.to_vec(),
             None,
@@ -49,6 +53,7 @@ async fn test_transform_sort() -> Result<()> {
         pipeline.merge_processor()?;
         pipeline.add_simple_transform(|| {
             Ok(Box::new(SortMergeTransform::try_create(
+                ctx.clone(),
                 plan.schema().clone(),
                 sort_expression.to_vec(),
                 None,
@@ -80,3 +85,42 @@ async fn test_transform_sort() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_sort_with_spill() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    // Every sorted block of 100 numbers is spilled as a run, the runs are merged at the end.
+    ctx.get_settings().set_max_block_size(100)?;
+    ctx.get_settings().set_max_bytes_before_external_sort(1)?;
+
+    let tests = vec![
+        (
+            "SELECT number FROM numbers_mt(1000) ORDER BY number DESC",
+            1000,
+        ),
+        (
+            "SELECT number FROM numbers_mt(1000) ORDER BY number DESC LIMIT 10",
+            10,
+        ),
+    ];
+    for (query, rows) in tests {
+        let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        let executor = InterpreterFactory::get(ctx.clone(), plan)?;
+        let stream = executor.execute().await?;
+        let result = stream.try_collect::<Vec<_>>().await?;
+
+        let mut numbers = vec![];
+        for block in result.iter() {
+            for row in 0..block.num_rows() {
+                numbers.push(block.column(0).try_get(row)?);
+            }
+        }
+        let expected = (1000 - rows..1000)
+            .rev()
+            .map(|number| DataValue::UInt64(Some(number)))
+            .collect::<Vec<_>>();
+        assert_eq!(expected, numbers, "{}", query);
+    }
+
+    Ok(())
+}
@@ -11,6 +11,7 @@ use common_planners::ReadDataSourcePlan;
 use common_streams::CorrectWithSchemaStream;
 use common_streams::SendableDataBlockStream;
 use common_tracing::tracing;
+use futures::StreamExt;
 
 use crate::pipelines::processors::EmptyProcessor;
 use crate::pipelines::processors::Processor;
@@ -70,9 +71,18 @@ impl Processor for SourceTransform {
 
         // We need to keep the block struct with the schema
         // Because the table may not support require columns
-        Ok(Box::pin(CorrectWithSchemaStream::new(
-            table_stream.await?,
-            self.source_plan.schema.clone(),
-        )))
+        let stream =
+            CorrectWithSchemaStream::new(table_stream.await?, self.source_plan.schema.clone());
+        if !self.ctx.has_read_limits()? {
+            return Ok(Box::pin(stream));
+        }
+
+        // The progress is shared by all the sources of the query.
+        let ctx = self.ctx.clone();
+        Ok(Box::pin(stream.map(move |block| {
+            let progress = ctx.get_total_progress_value();
+            ctx.check_read_limits(progress.read_rows, progress.read_bytes)?;
+            block
+        })))
     }
 }
@@ -43,3 +43,30 @@ async fn transform_source_test() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn transform_source_read_limits_test() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    ctx.get_settings().set_max_block_size(10)?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // The source is created without the check of the statistics by the pipeline builder,
+    // the limit is exceeded by the progress of the reading.
+    ctx.get_settings().set_max_rows_to_read(15)?;
+    let mut pipeline = Pipeline::create(ctx.clone());
+    pipeline.add_source(Arc::new(test_source.number_source_transform_for_test(100)?))?;
+
+    let stream = pipeline.execute().await?;
+    match stream.try_collect::<Vec<_>>().await {
+        Ok(_) => assert!(false, "The read limits must be exceeded"),
+        Err(e) => assert!(
+            e.to_string().starts_with(
+                "Code: 54, displayText = Limit for rows to read exceeded, max rows: 15, current rows:"
+            ),
+            "{}",
+            e
+        ),
+    }
+
+    Ok(())
+}
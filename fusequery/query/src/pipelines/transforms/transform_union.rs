@@ -0,0 +1,82 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+use common_tracing::tracing;
+use tokio_stream::StreamExt;
+
+use crate::pipelines::processors::EmptyProcessor;
+use crate::pipelines::processors::Processor;
+
+/// Outputs the blocks of an input of UNION with the schema of the union, the columns of the
+/// blocks are renamed by their positions, as the inputs are cast to the types of the union.
+pub struct UnionTransform {
+    schema: DataSchemaRef,
+    input: Arc<dyn Processor>,
+}
+
+impl UnionTransform {
+    pub fn try_create(schema: DataSchemaRef) -> Result<Self> {
+        Ok(UnionTransform {
+            schema,
+            input: Arc::new(EmptyProcessor::create()),
+        })
+    }
+
+    fn union_block(schema: &DataSchemaRef, block: Result<DataBlock>) -> Result<DataBlock> {
+        let block = block?;
+        let fields = schema.fields();
+        let columns = block.columns();
+        let matched = fields.len() == columns.len()
+            && fields
+                .iter()
+                .zip(columns)
+                .all(|(field, column)| field.data_type() == &column.data_type());
+
+        match matched {
+            true => Ok(DataBlock::create(schema.clone(), columns.to_vec())),
+            false => Err(ErrorCode::IllegalSchema(format!(
+                "Illegal schema of the input of UNION, expect: {:?}, found: {:?}",
+                schema,
+                block.schema()
+            ))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for UnionTransform {
+    fn name(&self) -> &str {
+        "UnionTransform"
+    }
+
+    fn connect_to(&mut self, input: Arc<dyn Processor>) -> Result<()> {
+        self.input = input;
+        Ok(())
+    }
+
+    fn inputs(&self) -> Vec<Arc<dyn Processor>> {
+        vec![self.input.clone()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    async fn execute(&self) -> Result<SendableDataBlockStream> {
+        tracing::debug!("execute...");
+
+        let schema = self.schema.clone();
+        let input_stream = self.input.execute().await?;
+        let stream = input_stream.map(move |block| Self::union_block(&schema, block));
+        Ok(Box::pin(stream))
+    }
+}
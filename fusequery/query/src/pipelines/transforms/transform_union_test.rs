@@ -0,0 +1,52 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataType;
+use common_exception::Result;
+use common_planners::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use pretty_assertions::assert_eq;
+
+use crate::pipelines::processors::*;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_transform_union() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let test_source = crate::tests::NumberTestData::create(ctx.clone());
+
+    // The right input is cast to the type of the left input.
+    let left = PlanNode::ReadSource(test_source.number_read_source_plan_for_test(3)?);
+    let right = PlanBuilder::from(&PlanNode::ReadSource(
+        test_source.number_read_source_plan_for_test(2)?,
+    ))
+    .project(&[Expression::Cast {
+        expr: Box::new(add(col("number"), lit(10u32))),
+        data_type: DataType::UInt32,
+    }
+    .alias("b")])?
+    .build()?;
+    let plan = PlanBuilder::from(&left).union_all(&[right])?.build()?;
+
+    let mut pipeline = PipelineBuilder::create(ctx.clone()).build(&plan)?;
+    assert_eq!(pipeline.last_pipe()?.name(), "UnionTransform");
+    assert_eq!(pipeline.nums(), 2);
+
+    let stream = pipeline.execute().await?;
+    let result = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+--------+",
+        "| number |",
+        "+--------+",
+        "| 0      |",
+        "| 1      |",
+        "| 10     |",
+        "| 11     |",
+        "| 2      |",
+        "+--------+",
+    ];
+    common_datablocks::assert_blocks_sorted_eq(expected, result.as_slice());
+    assert_eq!(result[0].schema().field(0).data_type(), &DataType::UInt64);
+    Ok(())
+}
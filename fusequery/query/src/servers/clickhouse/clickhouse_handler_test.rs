@@ -33,6 +33,37 @@ async fn test_clickhouse_handler_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_clickhouse_handler_stream_blocks() -> Result<()> {
+    let sessions = SessionManager::try_create(1)?;
+    let mut handler = ClickHouseHandler::create(sessions);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let listening = handler.start(listening).await?;
+    let mut handler = create_conn(listening.port()).await?;
+
+    // The result of many blocks.
+    let query_str = "SELECT number AS c FROM numbers(100000)";
+    let block = query(&mut handler, query_str).await?;
+    assert_eq!(block.row_count(), 100000);
+
+    // The result of no blocks still has the header.
+    let query_str = "SELECT number AS c FROM numbers(10) WHERE number > 100";
+    let block = query(&mut handler, query_str).await?;
+    assert_eq!(block.row_count(), 0);
+    assert_eq!(block.column_count(), 1);
+
+    // The connection is usable after the error of a query.
+    let query_str = "SELECT * FROM system.not_exists";
+    assert!(query(&mut handler, query_str).await.is_err());
+
+    let query_str = "SELECT COUNT() AS c FROM numbers(1000)";
+    let block = query(&mut handler, query_str).await?;
+    assert_eq!(get_u64_data(block)?, 1000);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_clickhouse_insert_data() -> Result<()> {
     let sessions = SessionManager::try_create(1)?;
@@ -37,8 +37,11 @@ impl ClickHouseSession for InteractiveWorker {
         context.attach_query_info(&ctx.state.query);
         let mut query_writer = QueryWriter::create(ctx.client_revision, conn, context.clone());
 
-        let get_query_result = InteractiveWorkerBase::do_query(ctx, context);
-        query_writer.write(get_query_result.await).await?;
+        let query_result = InteractiveWorkerBase::do_query(ctx, context.clone()).await;
+        if let Err(error) = &query_result {
+            context.set_query_error(error);
+        }
+        query_writer.write(query_result).await?;
 
         histogram!(
             super::clickhouse_metrics::METRIC_CLICKHOUSE_PROCESSOR_REQUEST_DURATION,
@@ -84,7 +87,12 @@ impl ClickHouseSession for InteractiveWorker {
     }
 
     // TODO: remove it
+    // The progress of the queries is sent by the QueryWriter along with the blocks.
     fn get_progress(&self) -> clickhouse_srv::types::Progress {
-        unimplemented!()
+        clickhouse_srv::types::Progress {
+            rows: 0,
+            bytes: 0,
+            total_rows: 0,
+        }
     }
 }
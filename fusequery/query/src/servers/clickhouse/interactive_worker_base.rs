@@ -33,8 +33,9 @@ pub struct InteractiveWorkerBase;
 
 pub enum BlockItem {
     Block(Result<DataBlock>),
-    // for insert prepare, we do not need to send another block again
-    InsertSample(DataBlock),
+    // The header of the columns without the data, for the insert prepare and the results of no
+    // blocks, the clients expect the header before the end of the stream.
+    Header(DataBlock),
     ProgressTicker,
 }
 
@@ -47,11 +48,13 @@ impl InteractiveWorkerBase {
         log::debug!("{}", query);
 
         let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+        ctx.wait_in_query_queue().await?;
 
         match plan {
             PlanNode::InsertInto(insert) => Self::process_insert_query(insert, ch_ctx, ctx).await,
             _ => {
                 let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+                let schema = interpreter.schema();
 
                 let async_data_stream = interpreter.execute();
                 let data_stream = async_data_stream.await?;
@@ -67,15 +70,26 @@ impl InteractiveWorkerBase {
                 tokio::spawn(async move {
                     while !cancel.load(Ordering::Relaxed) {
                         let _ = interval_stream.next().await;
-                        tx.send(BlockItem::ProgressTicker).await.ok();
+                        if tx.send(BlockItem::ProgressTicker).await.is_err() {
+                            break;
+                        }
                     }
                 });
 
                 ctx.execute_task(async move {
+                    let mut sent_blocks = false;
                     while let Some(block) = abort_stream.next().await {
-                        tx2.send(BlockItem::Block(block)).await.ok();
+                        // The receiver is dropped if the client is gone, the query stops.
+                        if tx2.send(BlockItem::Block(block)).await.is_err() {
+                            break;
+                        }
+                        sent_blocks = true;
                     }
 
+                    if !sent_blocks && !schema.fields().is_empty() {
+                        let header = DataBlock::empty_with_schema(schema);
+                        tx2.send(BlockItem::Header(header)).await.ok();
+                    }
                     cancel_clone.store(true, Ordering::Relaxed);
                 })?;
 
@@ -103,12 +117,16 @@ impl InteractiveWorkerBase {
         let interpreter = InterpreterFactory::get(ctx.clone(), PlanNode::InsertInto(insert))?;
 
         let (mut tx, rx) = mpsc::channel(20);
-        tx.send(BlockItem::InsertSample(sample_block)).await.ok();
+        tx.send(BlockItem::Header(sample_block)).await.ok();
 
         // the data is comming in async mode
         let sent_all_data = ch_ctx.state.sent_all_data.clone();
         ctx.execute_task(async move {
-            interpreter.execute().await.unwrap();
+            // The result of the query is written before the data comes, the client is notified
+            // of the end of the data even if the insert fails, instead of waiting forever.
+            if let Err(error) = interpreter.execute().await {
+                log::error!("ClickHouse insert failed: {}", error);
+            }
             sent_all_data.notify_one();
         })?;
         Ok(rx)
@@ -121,7 +139,7 @@ pub struct FromClickHouseBlockStream {
 }
 
 impl futures::stream::Stream for FromClickHouseBlockStream {
-    type Item = DataBlock;
+    type Item = Result<DataBlock>;
 
     fn poll_next(
         mut self: std::pin::Pin<&mut Self>,
@@ -130,16 +148,13 @@ impl futures::stream::Stream for FromClickHouseBlockStream {
         self.input.poll_next_unpin(cx).map(|x| match x {
             Some(v) => {
                 let block = from_clickhouse_block(self.schema.clone(), v);
-                match block {
-                    Ok(block) => Some(block),
-                    Err(e) => {
-                        log::error!(
-                            "failed to convert ClickHouseBlock to block , breaking out, {:?}",
-                            e
-                        );
-                        None
-                    }
+                if let Err(e) = &block {
+                    log::error!(
+                        "failed to convert ClickHouseBlock to block , breaking out, {:?}",
+                        e
+                    );
                 }
+                Some(block)
             }
             _ => None,
         })
@@ -51,7 +51,7 @@ impl<'a> QueryWriter<'a> {
         let progress = clickhouse_srv::types::Progress {
             rows: values.read_rows as u64,
             bytes: values.read_bytes as u64,
-            total_rows: 0,
+            total_rows: values.total_rows_to_read as u64,
         };
 
         let version = self.client_version;
@@ -64,6 +64,14 @@ impl<'a> QueryWriter<'a> {
         }
     }
 
+    // The progress since the last ticker, which is not sent if no rows are read after it.
+    async fn write_last_progress(&mut self) -> Result<()> {
+        match self.ctx.get_progress_value().read_rows {
+            0 => Ok(()),
+            _ => self.write_progress().await,
+        }
+    }
+
     async fn write_error(&mut self, error: ErrorCode) -> Result<()> {
         let clickhouse_err = to_clickhouse_err(error);
         match self.conn.write_error(&clickhouse_err).await {
@@ -88,7 +96,7 @@ impl<'a> QueryWriter<'a> {
         loop {
             match receiver.next().await {
                 None => {
-                    return Ok(());
+                    return self.write_last_progress().await;
                 }
                 Some(BlockItem::ProgressTicker) => self.write_progress().await?,
                 Some(BlockItem::Block(Err(error))) => {
@@ -104,12 +112,7 @@ impl<'a> QueryWriter<'a> {
                     self.write_block(block).await?;
                     return self.write_tail_data(receiver).await;
                 }
-                Some(BlockItem::InsertSample(block)) => {
-                    let schema = block.schema();
-                    let header = DataBlock::empty_with_schema(schema.clone());
-
-                    self.write_block(header).await?;
-                }
+                Some(BlockItem::Header(header)) => self.write_block(header).await?,
             }
         }
     }
@@ -119,12 +122,15 @@ impl<'a> QueryWriter<'a> {
             match item {
                 BlockItem::ProgressTicker => self.write_progress().await?,
                 BlockItem::Block(Ok(block)) => self.write_block(block).await?,
-                BlockItem::Block(Err(error)) => self.write_error(error).await?,
-                BlockItem::InsertSample(block) => self.write_block(block).await?,
+                BlockItem::Header(block) => self.write_block(block).await?,
+                BlockItem::Block(Err(error)) => {
+                    // Nothing is sent after the exception.
+                    return self.write_error(error).await;
+                }
             };
         }
 
-        Ok(())
+        self.write_last_progress().await
     }
 }
 
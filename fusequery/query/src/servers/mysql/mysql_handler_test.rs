@@ -37,6 +37,42 @@ async fn test_use_database_with_on_query() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_driver_metadata_queries() -> Result<()> {
+    let mut handler = MySQLHandler::create(SessionManager::try_create(1)?);
+
+    let listening = "0.0.0.0:0".parse::<SocketAddr>()?;
+    let runnable_server = handler.start(listening).await?;
+    let mut connection = create_connection(runnable_server.port())?;
+
+    // The queries of the JDBC/ODBC drivers when they connect.
+    let received_data: Vec<String> = query(&mut connection, "SELECT @@version_comment LIMIT 1")?;
+    assert!(received_data[0].starts_with("FuseQuery"));
+
+    let received_data: Vec<(u64, String, u64)> = query(
+        &mut connection,
+        "SELECT @@session.auto_increment_increment AS auto_increment_increment, \
+         @@character_set_client AS character_set_client, \
+         @@max_allowed_packet AS max_allowed_packet",
+    )?;
+    assert_eq!(received_data, vec![(1, "utf8mb4".to_string(), 67108864)]);
+
+    let received_data: Vec<(String, String)> =
+        query(&mut connection, "SHOW VARIABLES LIKE 'tx_isolation'")?;
+    assert_eq!(received_data, vec![(
+        "tx_isolation".to_string(),
+        "REPEATABLE-READ".to_string()
+    )]);
+
+    query::<EmptyRow>(&mut connection, "SET autocommit=1")?;
+    query::<EmptyRow>(&mut connection, "SET character_set_results = NULL")?;
+
+    let received_data: Vec<EmptyRow> = query(&mut connection, "SHOW KEYS FROM tables FROM system")?;
+    assert!(received_data.is_empty());
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_rejected_session_with_sequence() -> Result<()> {
     let mut handler = MySQLHandler::create(SessionManager::try_create(1)?);
@@ -93,7 +93,11 @@ impl<W: std::io::Write> MysqlShim<W> for InteractiveWorker<W> {
         let context = self.session.create_context();
 
         context.attach_query_info(query);
-        DFQueryResultWriter::create(writer).write(self.base.do_query(query, context))?;
+        let blocks = self.base.do_query(query, context.clone());
+        if let Err(error) = &blocks {
+            context.set_query_error(error);
+        }
+        DFQueryResultWriter::create(writer).write(blocks)?;
 
         histogram!(
             super::mysql_metrics::METRIC_MYSQL_PROCESSOR_REQUEST_DURATION,
@@ -157,7 +161,9 @@ impl<W: std::io::Write> InteractiveWorkerBase<W> {
         let (plan, hints) = PlanParser::create(context.clone()).build_with_hint_from_sql(query);
 
         let fetch_query_blocks = || -> Result<Vec<DataBlock>> {
-            let interpreter = InterpreterFactory::get(context.clone(), plan?)?;
+            let plan = plan?;
+            runtime.block_on(context.wait_in_query_queue())?;
+            let interpreter = InterpreterFactory::get(context.clone(), plan)?;
             let data_stream = runtime.block_on(interpreter.execute())?;
             let abort_stream = context.try_create_abortable(data_stream)?;
 
@@ -2,8 +2,12 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod text_column_test;
+
 mod init_result_writer;
 mod query_result_writer;
+mod text_column;
 
 pub use self::init_result_writer::DFInitResultWriter;
 pub use self::query_result_writer::DFQueryResultWriter;
@@ -12,6 +12,8 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use msql_srv::*;
 
+use crate::servers::mysql::writers::text_column::TextColumn;
+
 pub struct DFQueryResultWriter<'a, W: std::io::Write> {
     inner: Option<QueryResultWriter<'a, W>>,
 }
@@ -52,8 +54,11 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
                 DataType::Float64 => Ok(ColumnType::MYSQL_TYPE_FLOAT),
                 DataType::Utf8 => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 DataType::Boolean => Ok(ColumnType::MYSQL_TYPE_SHORT),
-                DataType::Date32 => Ok(ColumnType::MYSQL_TYPE_TIMESTAMP),
-                DataType::Date64 => Ok(ColumnType::MYSQL_TYPE_TIMESTAMP),
+                DataType::Date32 => Ok(ColumnType::MYSQL_TYPE_DATE),
+                DataType::Date64 => Ok(ColumnType::MYSQL_TYPE_DATETIME),
+                DataType::Binary => Ok(ColumnType::MYSQL_TYPE_BLOB),
+                DataType::Decimal(_, _) => Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL),
+                DataType::List(_) => Ok(ColumnType::MYSQL_TYPE_VARCHAR),
                 _ => Err(ErrorCode::UnImplement(format!(
                     "Unsupported column type:{:?}",
                     field.data_type()
@@ -78,18 +83,21 @@ impl<'a, W: std::io::Write> DFQueryResultWriter<'a, W> {
         match convert_schema(block.schema()) {
             Err(error) => Self::err(&error, dataset_writer),
             Ok(columns) => {
-                let columns_size = block.num_columns();
                 let mut row_writer = dataset_writer.start(&columns)?;
 
+                // The texts of the columns of a block are encoded at once, then written into
+                // the packets row by row.
                 for block in &blocks {
-                    let rows_size = block.column(0).len();
-                    for row_index in 0..rows_size {
-                        let mut row = Vec::with_capacity(columns_size);
-                        for column_index in 0..columns_size {
-                            let column = block.column(column_index).to_array()?;
-                            row.push(format!("{}", column.try_get(row_index)?));
+                    let texts = block
+                        .columns()
+                        .iter()
+                        .map(TextColumn::try_create)
+                        .collect::<Result<Vec<_>>>()?;
+                    for row_index in 0..block.num_rows() {
+                        for text in &texts {
+                            row_writer.write_col(text.value(row_index))?;
                         }
-                        row_writer.write_row(row)?;
+                        row_writer.end_row()?;
                     }
                 }
 
@@ -0,0 +1,109 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::io::Write;
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+
+/// The text of the values of a column in the MySQL text protocol, encoded column by column into
+/// one buffer, the text of the row is `buf[offsets[row]..offsets[row + 1]]`.
+///
+/// The numbers, the strings and the booleans are encoded from the arrays without the DataValue
+/// of each row, the integers with itoa. The floats keep their Display, as ryu rounds the ties of
+/// the shortest digits to even where Display rounds them up. The texts are those of the Display
+/// of DataValue, NULL is the text "NULL".
+pub struct TextColumn {
+    buf: Vec<u8>,
+    offsets: Vec<usize>,
+    // The text of a constant column is encoded once for all the rows.
+    constant: bool,
+}
+
+macro_rules! encode_integers {
+    ($TEXT: expr, $ARRAY: expr) => {{
+        let mut itoa = itoa::Buffer::new();
+        for value in $ARRAY.downcast_iter() {
+            match value {
+                Some(value) => $TEXT.buf.extend_from_slice(itoa.format(value).as_bytes()),
+                None => $TEXT.buf.extend_from_slice(b"NULL"),
+            }
+            $TEXT.offsets.push($TEXT.buf.len());
+        }
+    }};
+}
+
+macro_rules! encode_floats {
+    ($TEXT: expr, $ARRAY: expr) => {{
+        for value in $ARRAY.downcast_iter() {
+            match value {
+                Some(value) => write!($TEXT.buf, "{}", value)?,
+                None => $TEXT.buf.extend_from_slice(b"NULL"),
+            }
+            $TEXT.offsets.push($TEXT.buf.len());
+        }
+    }};
+}
+
+impl TextColumn {
+    pub fn try_create(column: &DataColumn) -> Result<TextColumn> {
+        let mut text = TextColumn {
+            buf: vec![],
+            offsets: vec![0],
+            constant: false,
+        };
+
+        let series = match column {
+            DataColumn::Constant(value, _) => {
+                write!(text.buf, "{}", value)?;
+                text.offsets.push(text.buf.len());
+                text.constant = true;
+                return Ok(text);
+            }
+            DataColumn::Array(series) => series,
+        };
+
+        match series.data_type() {
+            DataType::Int8 => encode_integers!(text, series.i8()?),
+            DataType::Int16 => encode_integers!(text, series.i16()?),
+            DataType::Int32 => encode_integers!(text, series.i32()?),
+            DataType::Int64 => encode_integers!(text, series.i64()?),
+            DataType::UInt8 => encode_integers!(text, series.u8()?),
+            DataType::UInt16 => encode_integers!(text, series.u16()?),
+            DataType::UInt32 => encode_integers!(text, series.u32()?),
+            DataType::UInt64 => encode_integers!(text, series.u64()?),
+            DataType::Float32 => encode_floats!(text, series.f32()?),
+            DataType::Float64 => encode_floats!(text, series.f64()?),
+            DataType::Utf8 => {
+                for value in series.utf8()?.downcast_iter() {
+                    text.buf
+                        .extend_from_slice(value.map_or(&b"NULL"[..], str::as_bytes));
+                    text.offsets.push(text.buf.len());
+                }
+            }
+            DataType::Boolean => {
+                for value in series.bool()?.downcast_iter() {
+                    text.buf.extend_from_slice(match value {
+                        Some(true) => &b"true"[..],
+                        Some(false) => &b"false"[..],
+                        None => &b"NULL"[..],
+                    });
+                    text.offsets.push(text.buf.len());
+                }
+            }
+            _ => {
+                for row in 0..series.len() {
+                    write!(text.buf, "{}", series.try_get(row)?)?;
+                    text.offsets.push(text.buf.len());
+                }
+            }
+        }
+        Ok(text)
+    }
+
+    pub fn value(&self, row: usize) -> &[u8] {
+        let row = if self.constant { 0 } else { row };
+        &self.buf[self.offsets[row]..self.offsets[row + 1]]
+    }
+}
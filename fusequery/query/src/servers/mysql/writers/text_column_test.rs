@@ -0,0 +1,79 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::servers::mysql::writers::text_column::TextColumn;
+
+fn texts(column: DataColumn) -> Result<Vec<String>> {
+    let text = TextColumn::try_create(&column)?;
+    Ok((0..column.len())
+        .map(|row| String::from_utf8_lossy(text.value(row)).to_string())
+        .collect())
+}
+
+#[test]
+fn test_text_column() -> Result<()> {
+    struct Test {
+        name: &'static str,
+        column: DataColumn,
+    }
+
+    let tests = vec![
+        Test {
+            name: "Int8",
+            column: Series::new(vec![Some(-128_i8), None, Some(127)]).into(),
+        },
+        Test {
+            name: "UInt64",
+            column: Series::new(vec![0_u64, u64::MAX]).into(),
+        },
+        Test {
+            name: "Float32",
+            column: Series::new(vec![Some(1.0_f32), Some(1539213.25), None]).into(),
+        },
+        Test {
+            name: "Float64",
+            column: Series::new(vec![0.1_f64, -0.0, 1e20, 1e-7, f64::NAN, f64::INFINITY]).into(),
+        },
+        Test {
+            name: "Utf8",
+            column: Series::new(vec![Some("a"), None, Some("")]).into(),
+        },
+        Test {
+            name: "Boolean",
+            column: Series::new(vec![Some(true), Some(false), None]).into(),
+        },
+        Test {
+            name: "Date32",
+            column: DataColumn::Array(
+                Series::new(vec![0_i32, 18628]).cast_with_type(&DataType::Date32)?,
+            ),
+        },
+        Test {
+            name: "Constant",
+            column: DataColumn::Constant(DataValue::Float64(Some(2.5)), 3),
+        },
+        Test {
+            name: "Constant NULL",
+            column: DataColumn::Constant(DataValue::Null, 2),
+        },
+    ];
+
+    // The texts are those of the values.
+    for t in tests {
+        let expect = (0..t.column.len())
+            .map(|row| Ok(format!("{}", t.column.try_get(row)?)))
+            .collect::<Result<Vec<_>>>()?;
+        assert_eq!(expect, texts(t.column)?, "{}", t.name);
+    }
+
+    assert_eq!(
+        vec!["1", "1539213.3", "NULL"],
+        texts(Series::new(vec![Some(1.0_f32), Some(1539213.25), None]).into())?
+    );
+    Ok(())
+}
@@ -29,12 +29,16 @@ pub struct ShutdownHandle {
     shutdown: Arc<AtomicBool>,
     sessions: SessionManagerRef,
     services: Vec<Box<dyn Server>>,
+    // The services of the APIs are shut down after the sessions are closed, so the probes see
+    // the node draining meanwhile.
+    api_services: Vec<Box<dyn Server>>,
 }
 
 impl ShutdownHandle {
     pub fn create(sessions: SessionManagerRef) -> ShutdownHandle {
         ShutdownHandle {
             services: vec![],
+            api_services: vec![],
             sessions,
             shutdown: Arc::new(AtomicBool::new(false)),
         }
@@ -46,13 +50,21 @@ impl ShutdownHandle {
             shutdown_jobs.push(service.shutdown());
         }
 
+        let mut api_shutdown_jobs = vec![];
+        for service in &mut self.api_services {
+            api_shutdown_jobs.push(service.shutdown());
+        }
+
         let shutdown = self.shutdown.clone();
         let sessions = self.sessions.clone();
         let join_all = futures::future::join_all(shutdown_jobs);
+        let api_join_all = futures::future::join_all(api_shutdown_jobs);
         async move {
             if !shutdown.load(Ordering::Relaxed) {
+                sessions.get_health().set_draining();
                 join_all.await;
                 sessions.shutdown(signal).await;
+                api_join_all.await;
                 shutdown.store(true, Ordering::Relaxed);
             }
         }
@@ -86,6 +98,11 @@ impl ShutdownHandle {
     pub fn add_service(&mut self, service: Box<dyn Server>) {
         self.services.push(service);
     }
+
+    /// Adds the service of the APIs, which is shut down after the sessions are closed.
+    pub fn add_api_service(&mut self, service: Box<dyn Server>) {
+        self.api_services.push(service);
+    }
 }
 
 impl Drop for ShutdownHandle {
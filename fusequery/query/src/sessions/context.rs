@@ -2,12 +2,14 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::future::Future;
 use std::sync::atomic::Ordering;
 use std::sync::atomic::Ordering::Acquire;
 use std::sync::Arc;
 
+use common_datavalues::DataSchemaRef;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
@@ -20,15 +22,21 @@ use common_progress::ProgressValues;
 use common_runtime::tokio::task::JoinHandle;
 use common_streams::AbortStream;
 use common_streams::SendableDataBlockStream;
+use futures::StreamExt;
 
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
 use crate::datasources::DataSource;
 use crate::datasources::Table;
 use crate::datasources::TableFunction;
+use crate::pipelines::transforms::PlanProfile;
 use crate::sessions::context_shared::FuseQueryContextShared;
+use crate::sessions::MemoryTracker;
 use crate::sessions::ProcessInfo;
+use crate::sessions::QueryLogInfo;
+use crate::sessions::QueuedQueryInfo;
 use crate::sessions::Settings;
+use crate::sessions::SpillFile;
 
 pub struct FuseQueryContext {
     statistics: Arc<RwLock<Statistics>>,
@@ -75,11 +83,19 @@ impl FuseQueryContext {
     /// Note that the callback can be called from different threads.
     pub fn progress_callback(&self) -> Result<ProgressCallback> {
         let current_progress = self.shared.progress.clone();
+        let total_progress = self.shared.total_progress.clone();
         Ok(Box::new(move |value: &ProgressValues| {
             current_progress.incr(value);
+            total_progress.incr(value);
         }))
     }
 
+    /// Adds the progress of a remote stage of the query, which is sent with the data of the stage.
+    pub fn add_remote_progress(&self, value: &ProgressValues) {
+        self.shared.progress.incr(value);
+        self.shared.total_progress.incr(value);
+    }
+
     pub fn get_progress_value(&self) -> ProgressValues {
         self.shared.progress.as_ref().get_values()
     }
@@ -88,12 +104,77 @@ impl FuseQueryContext {
         self.shared.progress.as_ref().get_and_reset()
     }
 
-    // Some table can estimate the approx total rows, such as NumbersTable
+    /// The progress of the whole query, which is not reset by get_and_reset_progress_value.
+    pub fn get_total_progress_value(&self) -> ProgressValues {
+        self.shared.total_progress.as_ref().get_values()
+    }
+
+    pub fn has_read_limits(&self) -> Result<bool> {
+        let settings = self.get_settings();
+        Ok(settings.get_max_rows_to_read()? != 0 || settings.get_max_bytes_to_read()? != 0)
+    }
+
+    /// Checks the rows and bytes which the query reads against the settings max_rows_to_read
+    /// and max_bytes_to_read, 0 is unlimited.
+    pub fn check_read_limits(&self, read_rows: usize, read_bytes: usize) -> Result<()> {
+        let settings = self.get_settings();
+        let max_rows = settings.get_max_rows_to_read()? as usize;
+        if max_rows != 0 && read_rows > max_rows {
+            return Err(ErrorCode::ReadLimitExceeded(format!(
+                "Limit for rows to read exceeded, max rows: {}, current rows: {}",
+                max_rows, read_rows
+            )));
+        }
+
+        let max_bytes = settings.get_max_bytes_to_read()? as usize;
+        if max_bytes != 0 && read_bytes > max_bytes {
+            return Err(ErrorCode::ReadLimitExceeded(format!(
+                "Limit for bytes to read exceeded, max bytes: {}, current bytes: {}",
+                max_bytes, read_bytes
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// The memory tracker of the query, whose limit is the setting max_memory_usage.
+    pub fn get_memory_tracker(&self) -> Result<Arc<MemoryTracker>> {
+        let max_memory_usage = self.get_settings().get_max_memory_usage()?;
+        let tracker = self.shared.memory_tracker.clone();
+        tracker.set_limit(max_memory_usage as usize);
+        Ok(tracker)
+    }
+
+    /// Creates a temporary file of the blocks of the schema in the spill directory of the config.
+    pub fn create_spill_file(&self, schema: &DataSchemaRef) -> Result<SpillFile> {
+        SpillFile::create(&self.shared.conf.spill_dir, schema)
+    }
+
+    /// Adds the approx total rows of a source, which are estimated by the statistics of the
+    /// table, to the progress.
     pub fn add_total_rows_approx(&self, total_rows: usize) {
         self.shared
             .progress
             .as_ref()
             .add_total_rows_approx(total_rows);
+        self.shared
+            .total_progress
+            .as_ref()
+            .add_total_rows_approx(total_rows);
+    }
+
+    /// Record a non-fatal warning of the query, the query goes on.
+    pub fn push_warning(&self, warning: impl Into<String>) {
+        self.shared.warnings.write().push(warning.into());
+    }
+
+    pub fn get_warnings(&self) -> Vec<String> {
+        self.shared.warnings.read().clone()
+    }
+
+    /// The warnings of the previous query of the session, which are shown by SHOW WARNINGS.
+    pub fn get_last_query_warnings(&self) -> Vec<String> {
+        self.shared.session.get_last_query_warnings()
     }
 
     // Steal n partitions from the partition pool by the pipeline worker.
@@ -160,12 +241,62 @@ impl FuseQueryContext {
         self.shared.init_query_id.as_ref().read().clone()
     }
 
+    /// Sets the id of the query given by the client, which must not be the id of another
+    /// running query.
+    pub fn set_id(&self, query_id: String) -> Result<()> {
+        if self.shared.session.sessions.find_query(&query_id).is_some() {
+            return Err(ErrorCode::BadArguments(format!(
+                "The query {} is already running",
+                query_id
+            )));
+        }
+
+        *self.shared.init_query_id.write() = query_id;
+        Ok(())
+    }
+
     pub fn try_create_abortable(&self, input: SendableDataBlockStream) -> Result<AbortStream> {
+        // The rows and the error of the result are logged in system.query_log.
+        let shared = self.shared.clone();
+        let input = Box::pin(input.map(move |block| {
+            match &block {
+                Ok(block) => {
+                    shared
+                        .result_rows
+                        .fetch_add(block.num_rows(), Ordering::Relaxed);
+                }
+                Err(error) => shared.set_error(error),
+            }
+            block
+        }));
         let (abort_handle, abort_stream) = AbortStream::try_create(input)?;
         self.shared.add_source_abort_handle(abort_handle);
         Ok(abort_stream)
     }
 
+    /// Records the error the query fails with for system.query_log, only the first error of the
+    /// query is kept.
+    pub fn set_query_error(&self, error: &ErrorCode) {
+        self.shared.set_error(error);
+    }
+
+    /// The queries of the same tenant in system.query_log, the oldest first.
+    pub fn query_logs(&self) -> Vec<QueryLogInfo> {
+        let session = &self.shared.session;
+        let tenant = session.get_tenant();
+        session
+            .sessions
+            .get_query_log()
+            .logs()
+            .into_iter()
+            .filter(|log| log.tenant == tenant)
+            .collect()
+    }
+
+    pub fn get_tenant(&self) -> Option<String> {
+        self.shared.session.get_tenant()
+    }
+
     pub fn get_current_database(&self) -> String {
         self.shared.get_current_database()
     }
@@ -208,9 +339,87 @@ impl FuseQueryContext {
         self.shared.attach_query_info(query);
     }
 
+    /// Profiles the plan nodes of the pipelines built from now on, see `get_plan_profile`.
+    pub fn enable_plan_profiles(&self) {
+        let mut plan_profiles = self.shared.plan_profiles.write();
+        if plan_profiles.is_none() {
+            *plan_profiles = Some(HashMap::new());
+        }
+    }
+
+    /// The profile of the blocks produced by a plan node, None if the plan nodes are not
+    /// profiled. The nodes are told apart by their addresses, so the profiles are of the very
+    /// plan the pipelines are built from.
+    pub fn get_plan_profile(&self, node: &PlanNode) -> Option<Arc<PlanProfile>> {
+        self.shared.plan_profiles.write().as_mut().map(|profiles| {
+            profiles
+                .entry(node as *const PlanNode as usize)
+                .or_insert_with(|| Arc::new(PlanProfile::create()))
+                .clone()
+        })
+    }
+
+    /// Takes the profiles of the plan nodes by their addresses, and stops profiling.
+    pub fn take_plan_profiles(&self) -> HashMap<usize, Arc<PlanProfile>> {
+        self.shared.plan_profiles.write().take().unwrap_or_default()
+    }
+
+    /// Waits in the query queue until the query can run, the query counts as running until the
+    /// context of the query is destroyed. The time the query waited is a warning of the query.
+    pub async fn wait_in_query_queue(&self) -> Result<()> {
+        if self.shared.admission.read().is_some() {
+            return Ok(());
+        }
+
+        let session = &self.shared.session;
+        let info = QueuedQueryInfo {
+            query_id: self.get_id(),
+            session_id: session.get_id(),
+            user: session.get_user(),
+            tenant: session.get_tenant(),
+            query: self.shared.running_query.read().clone().unwrap_or_default(),
+            position: 0,
+            wait_time: Default::default(),
+        };
+
+        let queue = session.sessions.get_query_queue();
+        self.shared.queued.store(true, Ordering::Relaxed);
+        let admission = queue.admit(info).await;
+        self.shared.queued.store(false, Ordering::Relaxed);
+
+        let admission = admission?;
+        if let Some((position, wait_time)) = admission.queued {
+            self.push_warning(format!(
+                "The query waited {:.3}s in the query queue at position {}",
+                wait_time.as_secs_f64(),
+                position
+            ));
+        }
+        *self.shared.admission.write() = Some(admission);
+        Ok(())
+    }
+
+    /// The queries waiting in the query queue of the sessions of the same tenant.
+    pub fn queued_queries_info(&self) -> Vec<QueuedQueryInfo> {
+        let session = &self.shared.session;
+        let tenant = session.get_tenant();
+        session
+            .sessions
+            .get_query_queue()
+            .queued_queries()
+            .into_iter()
+            .filter(|queued| queued.tenant == tenant)
+            .collect()
+    }
+
     pub fn processes_info(self: &Arc<Self>) -> Vec<ProcessInfo> {
         self.shared.session.processes_info()
     }
+
+    /// Kill a running query of the sessions of the same tenant by its id, e.g. by KILL QUERY.
+    pub fn kill_query(&self, query_id: &str) -> Result<()> {
+        self.shared.session.kill_query(query_id)
+    }
 }
 
 impl std::fmt::Debug for FuseQueryContext {
@@ -230,6 +439,7 @@ impl FuseQueryContextShared {
         if self.ref_count.fetch_sub(1, Ordering::Release) == 1 {
             std::sync::atomic::fence(Acquire);
             log::info!("Destroy FuseQueryContext");
+            self.log_query();
             self.session.destroy_context_shared();
         }
     }
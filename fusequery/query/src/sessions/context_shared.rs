@@ -2,9 +2,16 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Instant;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::RwLock;
 use common_progress::Progress;
@@ -15,6 +22,10 @@ use uuid::Uuid;
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
 use crate::datasources::DataSource;
+use crate::pipelines::transforms::PlanProfile;
+use crate::sessions::MemoryTracker;
+use crate::sessions::QueryAdmission;
+use crate::sessions::QueryLogInfo;
 use crate::sessions::Session;
 use crate::sessions::Settings;
 
@@ -30,6 +41,8 @@ use crate::sessions::Settings;
 pub struct FuseQueryContextShared {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) progress: Arc<Progress>,
+    // The progress of the whole query, it is not reset when the progress is reported.
+    pub(in crate::sessions) total_progress: Arc<Progress>,
     pub(in crate::sessions) session: Arc<Session>,
     pub(in crate::sessions) runtime: Arc<RwLock<Option<Arc<Runtime>>>>,
     pub(in crate::sessions) init_query_id: Arc<RwLock<String>>,
@@ -38,6 +51,21 @@ pub struct FuseQueryContextShared {
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
     pub(in crate::sessions) subquery_index: Arc<AtomicUsize>,
     pub(in crate::sessions) running_query: Arc<RwLock<Option<String>>>,
+    pub(in crate::sessions) warnings: Arc<RwLock<Vec<String>>>,
+    pub(in crate::sessions) created_time: Instant,
+    // The query is waiting in the query queue.
+    pub(in crate::sessions) queued: Arc<AtomicBool>,
+    // The query counts as running in the query queue until the admission is dropped.
+    pub(in crate::sessions) admission: Arc<RwLock<Option<QueryAdmission>>>,
+    // The query is killed, the sources created after it are aborted at once.
+    pub(in crate::sessions) killed: Arc<AtomicBool>,
+    // The profiles of the plan nodes by their addresses for EXPLAIN ANALYZE, None if the plan
+    // nodes are not profiled.
+    pub(in crate::sessions) plan_profiles: Arc<RwLock<Option<HashMap<usize, Arc<PlanProfile>>>>>,
+    // The rows of the result and the first error of the query, for system.query_log.
+    pub(in crate::sessions) result_rows: Arc<AtomicUsize>,
+    pub(in crate::sessions) error: Arc<RwLock<Option<(u16, String)>>>,
+    pub(in crate::sessions) memory_tracker: Arc<MemoryTracker>,
 }
 
 impl FuseQueryContextShared {
@@ -46,6 +74,7 @@ impl FuseQueryContextShared {
             conf,
             init_query_id: Arc::new(RwLock::new(Uuid::new_v4().to_string())),
             progress: Arc::new(Progress::create()),
+            total_progress: Arc::new(Progress::create()),
             session,
             runtime: Arc::new(RwLock::new(None)),
             cluster_cache: Arc::new(RwLock::new(None)),
@@ -53,12 +82,22 @@ impl FuseQueryContextShared {
             ref_count: Arc::new(AtomicUsize::new(0)),
             subquery_index: Arc::new(AtomicUsize::new(1)),
             running_query: Arc::new(RwLock::new(None)),
+            warnings: Arc::new(RwLock::new(vec![])),
+            created_time: Instant::now(),
+            queued: Arc::new(AtomicBool::new(false)),
+            admission: Arc::new(RwLock::new(None)),
+            killed: Arc::new(AtomicBool::new(false)),
+            plan_profiles: Arc::new(RwLock::new(None)),
+            result_rows: Arc::new(AtomicUsize::new(0)),
+            error: Arc::new(RwLock::new(None)),
+            memory_tracker: MemoryTracker::create(),
         })
     }
 
     pub fn kill(&self) {
         let mut sources_abort_handle = self.sources_abort_handle.write();
 
+        self.killed.store(true, Ordering::Relaxed);
         while let Some(source_abort_handle) = sources_abort_handle.pop() {
             source_abort_handle.abort();
         }
@@ -105,7 +144,11 @@ impl FuseQueryContextShared {
             None => {
                 let settings = self.get_settings();
                 let max_threads = settings.get_max_threads()? as usize;
-                let runtime = Arc::new(Runtime::with_worker_threads(max_threads)?);
+                let runtime = match settings.get_enable_thread_pinning()? {
+                    0 => Runtime::with_worker_threads(max_threads)?,
+                    _ => Runtime::with_pinned_worker_threads(max_threads)?,
+                };
+                let runtime = Arc::new(runtime);
                 *query_runtime = Some(runtime.clone());
                 Ok(runtime)
             }
@@ -117,15 +160,62 @@ impl FuseQueryContextShared {
         *running_query = Some(query.to_string());
     }
 
+    /// Records the first error of the query.
+    pub fn set_error(&self, error: &ErrorCode) {
+        let mut current = self.error.write();
+        if current.is_none() {
+            *current = Some((error.code(), error.message()));
+        }
+    }
+
+    /// Logs the query into system.query_log when it finishes, the queries without SQL, e.g. the
+    /// stages of the queries of the other nodes, are not logged.
+    pub(in crate::sessions) fn log_query(&self) {
+        let query = match self.running_query.read().as_ref() {
+            Some(query) => query.clone(),
+            None => return,
+        };
+
+        let progress = self.total_progress.get_values();
+        let error = self.error.read().clone();
+        let event_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let session = &self.session;
+        session.sessions.get_query_log().log(QueryLogInfo {
+            query_id: self.init_query_id.read().clone(),
+            session_id: session.get_id(),
+            user: session.get_user(),
+            tenant: session.get_tenant(),
+            query,
+            event_time,
+            duration: self.created_time.elapsed().as_secs_f64(),
+            read_rows: progress.read_rows as u64,
+            read_bytes: progress.read_bytes as u64,
+            result_rows: self.result_rows.load(Ordering::Relaxed) as u64,
+            error_code: error.as_ref().map(|(code, _)| *code).unwrap_or(0),
+            error_message: error.map(|(_, message)| message),
+        });
+    }
+
     pub fn add_source_abort_handle(&self, handle: AbortHandle) {
         let mut sources_abort_handle = self.sources_abort_handle.write();
-        sources_abort_handle.push(handle);
+
+        // The query may be killed before its sources are created, e.g. in the query queue.
+        match self.killed.load(Ordering::Relaxed) {
+            true => handle.abort(),
+            false => sources_abort_handle.push(handle),
+        }
     }
 }
 
 impl Session {
     pub(in crate::sessions) fn destroy_context_shared(&self) {
         let mut mutable_state = self.mutable_state.lock();
-        mutable_state.context_shared.take();
+        if let Some(context_shared) = mutable_state.context_shared.take() {
+            // Kept for the SHOW WARNINGS of the next query.
+            mutable_state.last_query_warnings = context_shared.warnings.read().clone();
+        }
     }
 }
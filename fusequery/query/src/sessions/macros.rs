@@ -4,7 +4,7 @@
 
 // for settings getter setter
 macro_rules! apply_getter_setter_settings {
-    ($(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr)),* ) => {
+    ($(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr, $META: expr)),* ) => {
         $(
             paste::paste!{
                 pub fn [< get_ $NAME >](&self) -> Result<$TYPE> {
@@ -20,12 +20,13 @@ macro_rules! apply_getter_setter_settings {
 }
 
 macro_rules! apply_initial_settings {
-    ($(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr)),* ) => {
+    ($(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr, $META: expr)),* ) => {
 
         pub fn initial_settings(&self) -> Result<()> {
             paste::paste! {
                 $(
                     self.inner.[<try_set_ $TYPE:lower>]($NAME, $VALUE, $DESC)?;
+                    self.inner.try_set_meta($NAME, $META)?;
                 )*
             }
             Ok(())
@@ -43,13 +44,32 @@ macro_rules! apply_parse_value {
     };
 }
 
+macro_rules! apply_data_value {
+    ($VALUE: expr, u64) => {
+        DataValue::UInt64(Some($VALUE))
+    };
+
+    ($VALUE: expr, i64) => {
+        DataValue::Int64(Some($VALUE))
+    };
+
+    ($VALUE: expr, f64) => {
+        DataValue::Float64(Some($VALUE))
+    };
+
+    ($VALUE: expr, String) => {
+        DataValue::Utf8(Some($VALUE.clone()))
+    };
+}
+
 macro_rules! apply_update_settings {
-    ($(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr)),* ) => {
+    ($(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr, $META: expr)),* ) => {
         pub fn update_settings(&self, key: &str, value: String) -> Result<()> {
             paste::paste! {
                 $(
                     if (key.to_lowercase().as_str() == $NAME) {
                         let v = apply_parse_value!{value, $TYPE};
+                        self.inner.check_value($NAME, &apply_data_value!{v, $TYPE})?;
                         return self.inner.[<try_update_ $TYPE:lower>]($NAME, v);
                     }
                 )*
@@ -62,9 +82,9 @@ macro_rules! apply_update_settings {
 }
 
 macro_rules! apply_macros {
-    ($MACRO_A: ident, $MACRO_B: ident, $MACRO_C: ident, $(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr)),* ) => {
-        $MACRO_A! { $( ($NAME, $TYPE, $VALUE, $DESC) ), * }
-        $MACRO_B! { $( ($NAME, $TYPE, $VALUE, $DESC) ), * }
-        $MACRO_C! { $( ($NAME, $TYPE, $VALUE, $DESC) ), * }
+    ($MACRO_A: ident, $MACRO_B: ident, $MACRO_C: ident, $(($NAME: expr, $TYPE: tt, $VALUE:expr, $DESC: expr, $META: expr)),* ) => {
+        $MACRO_A! { $( ($NAME, $TYPE, $VALUE, $DESC, $META) ), * }
+        $MACRO_B! { $( ($NAME, $TYPE, $VALUE, $DESC, $META) ), * }
+        $MACRO_C! { $( ($NAME, $TYPE, $VALUE, $DESC, $META) ), * }
     };
 }
@@ -0,0 +1,106 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// The memory which the processors of a query hold, e.g. the blocks to sort and the hash tables
+/// of the group by and the join. The processors reserve the memory before they keep more data,
+/// so the query fails with an error once it exceeds the `max_memory_usage` setting, instead of
+/// the server being killed for out of memory.
+///
+/// The memory is estimated by the sizes of the blocks and the hash tables, not tracked by the
+/// allocator, the small allocations of the other processors are not counted.
+pub struct MemoryTracker {
+    // The max bytes of the query, 0 is unlimited.
+    limit: AtomicUsize,
+    used: AtomicUsize,
+    peak: AtomicUsize,
+}
+
+impl MemoryTracker {
+    pub fn create() -> Arc<MemoryTracker> {
+        Arc::new(MemoryTracker {
+            limit: AtomicUsize::new(0),
+            used: AtomicUsize::new(0),
+            peak: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn set_limit(&self, limit: usize) {
+        self.limit.store(limit, Ordering::Relaxed);
+    }
+
+    /// The bytes held now.
+    pub fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// The most bytes held at the same time.
+    pub fn peak(&self) -> usize {
+        self.peak.load(Ordering::Relaxed)
+    }
+
+    /// Reserves the bytes, fails without reserving them if the query would exceed the limit.
+    pub fn alloc(&self, bytes: usize) -> Result<()> {
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        let limit = self.limit.load(Ordering::Relaxed);
+        if limit != 0 && used > limit {
+            self.used.fetch_sub(bytes, Ordering::Relaxed);
+            return Err(ErrorCode::MemoryLimitExceeded(format!(
+                "Memory limit for the query exceeded, max memory usage: {}, current memory usage: {}, cannot allocate {} bytes",
+                limit,
+                used - bytes,
+                bytes
+            )));
+        }
+        self.peak.fetch_max(used, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn free(&self, bytes: usize) {
+        self.used.fetch_sub(bytes, Ordering::Relaxed);
+    }
+
+    /// An empty reservation, which is freed when it is dropped.
+    pub fn reservation(self: &Arc<Self>) -> MemoryReservation {
+        MemoryReservation {
+            tracker: self.clone(),
+            bytes: 0,
+        }
+    }
+}
+
+/// The memory a processor holds, the processor resizes it as its data grows and shrinks.
+pub struct MemoryReservation {
+    tracker: Arc<MemoryTracker>,
+    bytes: usize,
+}
+
+impl MemoryReservation {
+    pub fn size(&self) -> usize {
+        self.bytes
+    }
+
+    /// Resizes the reservation to the bytes, the reservation is unchanged if it fails.
+    pub fn resize(&mut self, bytes: usize) -> Result<()> {
+        if bytes > self.bytes {
+            self.tracker.alloc(bytes - self.bytes)?;
+        } else {
+            self.tracker.free(self.bytes - bytes);
+        }
+        self.bytes = bytes;
+        Ok(())
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.tracker.free(self.bytes);
+    }
+}
@@ -0,0 +1,43 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+
+use crate::sessions::MemoryTracker;
+
+#[test]
+fn test_memory_tracker() -> Result<()> {
+    let tracker = MemoryTracker::create();
+    tracker.set_limit(100);
+
+    let mut sort = tracker.reservation();
+    let mut group_by = tracker.reservation();
+    sort.resize(60)?;
+    group_by.resize(30)?;
+    assert_eq!(90, tracker.used());
+
+    // The reservation which exceeds the limit is unchanged.
+    let res = group_by.resize(50);
+    assert_eq!(
+        "Code: 57, displayText = Memory limit for the query exceeded, max memory usage: 100, current memory usage: 90, cannot allocate 20 bytes.",
+        res.unwrap_err().to_string()
+    );
+    assert_eq!(30, group_by.size());
+    assert_eq!(90, tracker.used());
+
+    // The memory is freed by shrinking or dropping the reservations.
+    sort.resize(10)?;
+    group_by.resize(50)?;
+    assert_eq!(60, tracker.used());
+    drop(sort);
+    drop(group_by);
+    assert_eq!(0, tracker.used());
+    assert_eq!(90, tracker.peak());
+
+    // 0 is unlimited.
+    tracker.set_limit(0);
+    tracker.alloc(usize::MAX / 2)?;
+
+    Ok(())
+}
@@ -4,3 +4,5 @@
 
 pub static METRIC_SESSION_CONNECT_NUMBERS: &str = "session.connect_numbers";
 pub static METRIC_SESSION_CLOSE_NUMBERS: &str = "session.close_numbers";
+pub static METRIC_QUERY_QUEUE_WAIT_TIME: &str = "query.queue_wait_time";
+pub static METRIC_QUERY_QUEUE_TIMEOUT_NUMBERS: &str = "query.queue_timeout_numbers";
@@ -2,12 +2,29 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+#[cfg(test)]
+mod memory_tracker_test;
+#[cfg(test)]
+mod query_log_test;
+#[cfg(test)]
+mod query_queue_test;
+#[cfg(test)]
+mod session_test;
+#[cfg(test)]
+mod settings_test;
+#[cfg(test)]
+mod spill_file_test;
+
 #[macro_use]
 mod macros;
 
 mod context;
 mod context_shared;
+mod memory_tracker;
 mod metrics;
+mod query_log;
+mod query_queue;
+mod server_health;
 mod session;
 mod session_info;
 mod session_ref;
@@ -15,12 +32,24 @@ mod session_ref;
 mod sessions;
 mod sessions_info;
 mod settings;
+mod spill_file;
 
 pub use context::FuseQueryContext;
 pub use context::FuseQueryContextRef;
+pub use memory_tracker::MemoryReservation;
+pub use memory_tracker::MemoryTracker;
+pub use query_log::QueryLog;
+pub use query_log::QueryLogInfo;
+pub use query_queue::QueryAdmission;
+pub use query_queue::QueryQueue;
+pub use query_queue::QueuedQueryInfo;
+pub use server_health::ServerHealth;
 pub use session::Session;
 pub use session_info::ProcessInfo;
 pub use session_ref::SessionRef;
 pub use sessions::SessionManager;
 pub use sessions::SessionManagerRef;
+pub use settings::SettingMeta;
+pub use settings::SettingTier;
 pub use settings::Settings;
+pub use spill_file::SpillFile;
@@ -0,0 +1,139 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+use common_infallible::Mutex;
+
+/// A finished query in system.query_log.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QueryLogInfo {
+    pub query_id: String,
+    pub session_id: String,
+    pub user: String,
+    pub tenant: Option<String>,
+    pub query: String,
+    /// The unix time in seconds when the query finished.
+    pub event_time: u64,
+    /// The seconds the query took.
+    pub duration: f64,
+    pub read_rows: u64,
+    pub read_bytes: u64,
+    pub result_rows: u64,
+    /// The code of the error the query failed with, 0 if the query succeeded.
+    pub error_code: u16,
+    pub error_message: Option<String>,
+}
+
+/// The last `capacity` finished queries. The queries are logged by a writer thread, so a query
+/// never waits for its log, and are appended to the file of the log if there is one, the
+/// queries in the file are loaded at the start.
+pub struct QueryLog {
+    logs: Arc<Mutex<VecDeque<QueryLogInfo>>>,
+    sender: Mutex<Option<mpsc::Sender<QueryLogInfo>>>,
+}
+
+impl QueryLog {
+    /// The queries are not logged if the capacity is 0.
+    pub fn create(capacity: usize, file: &str) -> QueryLog {
+        let logs = Arc::new(Mutex::new(VecDeque::with_capacity(capacity)));
+        if capacity == 0 {
+            return QueryLog {
+                logs,
+                sender: Mutex::new(None),
+            };
+        }
+
+        let mut writer = None;
+        if !file.is_empty() {
+            Self::load(file, capacity, &mut logs.lock());
+            match OpenOptions::new().create(true).append(true).open(file) {
+                Ok(file) => writer = Some(file),
+                Err(e) => log::warn!("Cannot open the query log file {}: {}", file, e),
+            }
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let writer_logs = logs.clone();
+        let spawned = std::thread::Builder::new()
+            .name("query-log-writer".to_string())
+            .spawn(move || Self::write(receiver, writer_logs, capacity, writer));
+        let sender = match spawned {
+            Ok(_) => Some(sender),
+            Err(e) => {
+                log::warn!("Cannot start the query log writer: {}", e);
+                None
+            }
+        };
+
+        QueryLog {
+            logs,
+            sender: Mutex::new(sender),
+        }
+    }
+
+    /// Logs a finished query, the query does not wait for the log to be written.
+    pub fn log(&self, info: QueryLogInfo) {
+        if let Some(sender) = self.sender.lock().as_ref() {
+            let _ = sender.send(info);
+        }
+    }
+
+    /// The logged queries, the oldest first.
+    pub fn logs(&self) -> Vec<QueryLogInfo> {
+        self.logs.lock().iter().cloned().collect()
+    }
+
+    // The lines which are not a query, e.g. the last line written partly, are skipped.
+    fn load(file: &str, capacity: usize, logs: &mut VecDeque<QueryLogInfo>) {
+        let file = match File::open(file) {
+            Ok(file) => file,
+            Err(_) => return,
+        };
+        for line in BufReader::new(file).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(_) => break,
+            };
+            if let Ok(info) = serde_json::from_str::<QueryLogInfo>(&line) {
+                if logs.len() == capacity {
+                    logs.pop_front();
+                }
+                logs.push_back(info);
+            }
+        }
+    }
+
+    // Runs until the query log is dropped.
+    fn write(
+        receiver: mpsc::Receiver<QueryLogInfo>,
+        logs: Arc<Mutex<VecDeque<QueryLogInfo>>>,
+        capacity: usize,
+        mut writer: Option<File>,
+    ) {
+        for info in receiver {
+            if let Some(file) = writer.as_mut() {
+                let written = serde_json::to_string(&info)
+                    .map_err(|e| e.to_string())
+                    .and_then(|line| writeln!(file, "{}", line).map_err(|e| e.to_string()));
+                if let Err(e) = written {
+                    log::warn!("Cannot write the query log file: {}", e);
+                }
+            }
+
+            let mut logs = logs.lock();
+            if logs.len() == capacity {
+                logs.pop_front();
+            }
+            logs.push_back(info);
+        }
+    }
+}
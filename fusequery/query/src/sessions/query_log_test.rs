@@ -0,0 +1,86 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::env;
+use std::time::Duration;
+
+use common_exception::Result;
+
+use crate::sessions::QueryLog;
+use crate::sessions::QueryLogInfo;
+
+fn query_log_info(query_id: &str) -> QueryLogInfo {
+    QueryLogInfo {
+        query_id: query_id.to_string(),
+        session_id: "session".to_string(),
+        user: "user".to_string(),
+        tenant: None,
+        query: format!("SELECT '{}'", query_id),
+        event_time: 1,
+        duration: 0.5,
+        read_rows: 1,
+        read_bytes: 8,
+        result_rows: 1,
+        error_code: 0,
+        error_message: None,
+    }
+}
+
+// The queries are logged by the writer thread.
+fn wait_logs(query_log: &QueryLog, last: &str) -> Vec<QueryLogInfo> {
+    for _ in 0..100 {
+        let logs = query_log.logs();
+        if logs.last().map(|log| log.query_id.as_str()) == Some(last) {
+            return logs;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+    query_log.logs()
+}
+
+fn query_ids(logs: &[QueryLogInfo]) -> Vec<&str> {
+    logs.iter().map(|log| log.query_id.as_str()).collect()
+}
+
+#[test]
+fn test_query_log() -> Result<()> {
+    // The logging is disabled.
+    {
+        let query_log = QueryLog::create(0, "");
+        query_log.log(query_log_info("a"));
+        assert!(query_log.logs().is_empty());
+    }
+
+    // The oldest queries are evicted.
+    {
+        let query_log = QueryLog::create(2, "");
+        for query_id in &["a", "b", "c"] {
+            query_log.log(query_log_info(query_id));
+        }
+        let logs = wait_logs(&query_log, "c");
+        assert_eq!(vec!["b", "c"], query_ids(&logs));
+        assert_eq!(query_log_info("c"), logs[1]);
+    }
+
+    // The queries in the file are loaded at the start.
+    {
+        let path = env::temp_dir().join(format!("query_log_{}", uuid::Uuid::new_v4()));
+        let path = path.display().to_string();
+        {
+            let query_log = QueryLog::create(2, &path);
+            for query_id in &["a", "b", "c"] {
+                query_log.log(query_log_info(query_id));
+            }
+            wait_logs(&query_log, "c");
+        }
+
+        let query_log = QueryLog::create(2, &path);
+        assert_eq!(vec!["b", "c"], query_ids(&query_log.logs()));
+        query_log.log(query_log_info("d"));
+        assert_eq!(vec!["c", "d"], query_ids(&wait_logs(&query_log, "d")));
+        std::fs::remove_file(&path)?;
+    }
+
+    Ok(())
+}
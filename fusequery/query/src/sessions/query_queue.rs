@@ -0,0 +1,152 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+use common_runtime::tokio;
+use common_runtime::tokio::sync::OwnedSemaphorePermit;
+use common_runtime::tokio::sync::Semaphore;
+use metrics::counter;
+use metrics::histogram;
+
+/// A query waiting in the query queue.
+#[derive(Clone, Debug)]
+pub struct QueuedQueryInfo {
+    pub query_id: String,
+    pub session_id: String,
+    pub user: String,
+    pub tenant: Option<String>,
+    pub query: String,
+    /// The position in the queue, the query at 1 runs next.
+    pub position: usize,
+    pub wait_time: Duration,
+}
+
+struct QueuedQuery {
+    ticket: u64,
+    info: QueuedQueryInfo,
+    enqueued_time: Instant,
+}
+
+/// The admission control of the queries, at most `max_running_queries` queries run at the same
+/// time, the others wait in the queue in the order they arrive. A query fails if it waits longer
+/// than the timeout. The queue is not used if `max_running_queries` is 0.
+pub struct QueryQueue {
+    max_running_queries: usize,
+    timeout: Duration,
+    permits: Arc<Semaphore>,
+    queued: Mutex<Vec<QueuedQuery>>,
+    next_ticket: AtomicU64,
+}
+
+/// The admission of a query, the query counts as running until the admission is dropped.
+pub struct QueryAdmission {
+    _permit: Option<OwnedSemaphorePermit>,
+    /// The position the query entered the queue at and the time it waited, None if the query
+    /// was not queued.
+    pub queued: Option<(usize, Duration)>,
+}
+
+// Removes a query from the queue once it is admitted, timed out or dropped while waiting.
+struct QueuedGuard<'a> {
+    queue: &'a QueryQueue,
+    ticket: u64,
+}
+
+impl<'a> Drop for QueuedGuard<'a> {
+    fn drop(&mut self) {
+        self.queue
+            .queued
+            .lock()
+            .retain(|queued| queued.ticket != self.ticket);
+    }
+}
+
+impl QueryQueue {
+    pub fn create(max_running_queries: usize, timeout: Duration) -> QueryQueue {
+        QueryQueue {
+            max_running_queries,
+            timeout,
+            permits: Arc::new(Semaphore::new(max_running_queries)),
+            queued: Mutex::new(vec![]),
+            next_ticket: AtomicU64::new(0),
+        }
+    }
+
+    pub async fn admit(&self, info: QueuedQueryInfo) -> Result<QueryAdmission> {
+        if self.max_running_queries == 0 {
+            return Ok(QueryAdmission {
+                _permit: None,
+                queued: None,
+            });
+        }
+
+        // The permits released are handed to the queued queries first, so a query only skips
+        // the queue if it is empty.
+        if let Ok(permit) = self.permits.clone().try_acquire_owned() {
+            return Ok(QueryAdmission {
+                _permit: Some(permit),
+                queued: None,
+            });
+        }
+
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        let enqueued_time = Instant::now();
+        let position = {
+            let mut queued = self.queued.lock();
+            queued.push(QueuedQuery {
+                ticket,
+                info,
+                enqueued_time,
+            });
+            queued.len()
+        };
+
+        let _guard = QueuedGuard {
+            queue: self,
+            ticket,
+        };
+        let acquired =
+            tokio::time::timeout(self.timeout, self.permits.clone().acquire_owned()).await;
+
+        let wait_time = enqueued_time.elapsed();
+        histogram!(super::metrics::METRIC_QUERY_QUEUE_WAIT_TIME, wait_time);
+        match acquired {
+            Ok(Ok(permit)) => Ok(QueryAdmission {
+                _permit: Some(permit),
+                queued: Some((position, wait_time)),
+            }),
+            Ok(Err(_)) => Err(ErrorCode::LogicalError("The query queue is closed")),
+            Err(_) => {
+                counter!(super::metrics::METRIC_QUERY_QUEUE_TIMEOUT_NUMBERS, 1);
+                Err(ErrorCode::Timeout(format!(
+                    "The query waited {:.3}s in the query queue at position {}, and exceeded query_queue_timeout config",
+                    wait_time.as_secs_f64(),
+                    position
+                )))
+            }
+        }
+    }
+
+    /// The queries waiting in the queue, in the order they run.
+    pub fn queued_queries(&self) -> Vec<QueuedQueryInfo> {
+        self.queued
+            .lock()
+            .iter()
+            .enumerate()
+            .map(|(index, queued)| QueuedQueryInfo {
+                position: index + 1,
+                wait_time: queued.enqueued_time.elapsed(),
+                ..queued.info.clone()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::time::Duration;
+
+use common_exception::Result;
+use common_runtime::tokio;
+use pretty_assertions::assert_eq;
+
+use crate::sessions::QueryQueue;
+use crate::sessions::QueuedQueryInfo;
+
+fn query(query_id: &str) -> QueuedQueryInfo {
+    QueuedQueryInfo {
+        query_id: query_id.to_string(),
+        session_id: "session".to_string(),
+        user: "user".to_string(),
+        tenant: None,
+        query: format!("SELECT '{}'", query_id),
+        position: 0,
+        wait_time: Duration::default(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_query_queue() -> Result<()> {
+    // Not queued without max_running_queries.
+    {
+        let queue = QueryQueue::create(0, Duration::from_millis(10));
+        let _admission1 = queue.admit(query("q1")).await?;
+        let admission2 = queue.admit(query("q2")).await?;
+        assert!(admission2.queued.is_none());
+    }
+
+    // The query fails after it waits for the timeout.
+    {
+        let queue = QueryQueue::create(1, Duration::from_millis(50));
+        let admission1 = queue.admit(query("q1")).await?;
+        assert!(admission1.queued.is_none());
+
+        let result = queue.admit(query("q2")).await;
+        let error = result.err().unwrap();
+        assert_eq!(40, error.code());
+        assert!(error.message().ends_with(
+            "in the query queue at position 1, and exceeded query_queue_timeout config"
+        ));
+        assert!(queue.queued_queries().is_empty());
+
+        // The next query runs once the running one finishes.
+        drop(admission1);
+        let admission3 = queue.admit(query("q3")).await?;
+        assert!(admission3.queued.is_none());
+    }
+
+    Ok(())
+}
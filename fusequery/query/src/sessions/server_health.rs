@@ -0,0 +1,33 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+/// The health of the query node for the probes of the orchestrators. The node is started once
+/// all its services are bound, and it is draining from the beginning of the shutdown, when the
+/// new sessions are rejected and the active sessions are closing.
+#[derive(Default)]
+pub struct ServerHealth {
+    started: AtomicBool,
+    draining: AtomicBool,
+}
+
+impl ServerHealth {
+    pub fn set_started(&self) {
+        self.started.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_started(&self) -> bool {
+        self.started.load(Ordering::Relaxed)
+    }
+
+    pub fn set_draining(&self) {
+        self.draining.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::Relaxed)
+    }
+}
@@ -6,6 +6,7 @@ use std::net::SocketAddr;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_infallible::Mutex;
 use futures::channel::oneshot::Sender;
@@ -29,11 +30,15 @@ pub(in crate::sessions) struct MutableStatus {
     pub(in crate::sessions) client_host: Option<SocketAddr>,
     pub(in crate::sessions) io_shutdown_tx: Option<Sender<Sender<()>>>,
     pub(in crate::sessions) context_shared: Option<Arc<FuseQueryContextShared>>,
+    pub(in crate::sessions) tenant: Option<String>,
+    pub(in crate::sessions) tenant_datasource: Option<Arc<DataSource>>,
+    pub(in crate::sessions) last_query_warnings: Vec<String>,
 }
 
 #[derive(Clone)]
 pub struct Session {
     pub(in crate::sessions) id: String,
+    pub(in crate::sessions) user: String,
     pub(in crate::sessions) config: Config,
     pub(in crate::sessions) sessions: SessionManagerRef,
     pub(in crate::sessions) ref_count: Arc<AtomicUsize>,
@@ -44,10 +49,13 @@ impl Session {
     pub fn try_create(
         config: Config,
         id: String,
+        user: String,
         sessions: SessionManagerRef,
     ) -> Result<Arc<Session>> {
-        Ok(Arc::new(Session {
+        let tenant = config.tenant.clone();
+        let session = Arc::new(Session {
             id,
+            user,
             config,
             sessions,
             ref_count: Arc::new(AtomicUsize::new(0)),
@@ -58,14 +66,26 @@ impl Session {
                 client_host: None,
                 io_shutdown_tx: None,
                 context_shared: None,
+                tenant: None,
+                tenant_datasource: None,
+                last_query_warnings: vec![],
             })),
-        }))
+        });
+
+        if !tenant.is_empty() {
+            session.bind_tenant(&tenant)?;
+        }
+        Ok(session)
     }
 
     pub fn get_id(self: &Arc<Self>) -> String {
         self.id.clone()
     }
 
+    pub fn get_user(self: &Arc<Self>) -> String {
+        self.user.clone()
+    }
+
     pub fn is_aborting(self: &Arc<Self>) -> bool {
         self.mutable_state.lock().abort
     }
@@ -142,15 +162,68 @@ impl Session {
         self.mutable_state.lock().session_settings.clone()
     }
 
+    /// The warnings of the last finished query of the session.
+    pub fn get_last_query_warnings(self: &Arc<Self>) -> Vec<String> {
+        self.mutable_state.lock().last_query_warnings.clone()
+    }
+
     pub fn try_get_cluster(self: &Arc<Self>) -> Result<ClusterRef> {
         Ok(self.sessions.get_cluster())
     }
 
+    /// The processes of the sessions of the same tenant.
     pub fn processes_info(self: &Arc<Self>) -> Vec<ProcessInfo> {
-        self.sessions.processes_info()
+        let tenant = self.get_tenant();
+        self.sessions
+            .processes_info()
+            .into_iter()
+            .filter(|process| process.tenant == tenant)
+            .collect()
+    }
+
+    /// Kill a running query of the sessions of the same tenant by its id, the query fails with
+    /// the AbortedQuery error. The queries of the other tenants are unknown to the session.
+    pub fn kill_query(self: &Arc<Self>, query_id: &str) -> Result<()> {
+        let tenant = self.get_tenant();
+        match self.sessions.find_query(query_id) {
+            Some(context_shared) if context_shared.session.get_tenant() == tenant => {
+                context_shared.kill();
+                Ok(())
+            }
+            _ => Err(ErrorCode::UnknownQuery(format!(
+                "Unknown query: {}",
+                query_id
+            ))),
+        }
+    }
+
+    /// Bind the session to a tenant, the session can only see the databases of the tenant.
+    /// A session is bound once, it can't switch to another tenant.
+    pub fn bind_tenant(self: &Arc<Self>, tenant: &str) -> Result<()> {
+        let mut mutable_state = self.mutable_state.lock();
+        match &mutable_state.tenant {
+            Some(bound) if bound == tenant => Ok(()),
+            Some(bound) => Err(ErrorCode::TenantAlreadyBound(format!(
+                "The session is already bound to tenant {}",
+                bound
+            ))),
+            None => {
+                let datasource = self.sessions.get_tenant_datasource(tenant)?;
+                mutable_state.tenant = Some(tenant.to_string());
+                mutable_state.tenant_datasource = Some(datasource);
+                Ok(())
+            }
+        }
+    }
+
+    pub fn get_tenant(self: &Arc<Self>) -> Option<String> {
+        self.mutable_state.lock().tenant.clone()
     }
 
     pub fn get_datasource(self: &Arc<Self>) -> Arc<DataSource> {
-        self.sessions.get_datasource()
+        match &self.mutable_state.lock().tenant_datasource {
+            Some(datasource) => datasource.clone(),
+            None => self.sessions.get_datasource(),
+        }
     }
 }
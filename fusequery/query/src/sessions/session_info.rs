@@ -3,7 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use std::net::SocketAddr;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::Duration;
+
+use common_progress::ProgressValues;
 
 use crate::sessions::session::MutableStatus;
 use crate::sessions::Session;
@@ -11,12 +15,20 @@ use crate::sessions::Settings;
 
 pub struct ProcessInfo {
     pub id: String,
+    pub user: String,
     pub state: String,
     pub database: String,
     #[allow(unused)]
     pub settings: Arc<Settings>,
     pub client_address: Option<SocketAddr>,
     pub session_extra_info: Option<String>,
+    pub tenant: Option<String>,
+    /// The id of the running query, None if the session is idle.
+    pub query_id: Option<String>,
+    /// The progress of the running query, None if the session is idle.
+    pub progress: Option<ProgressValues>,
+    /// The elapsed time of the running query, None if the session is idle.
+    pub elapsed: Option<Duration>,
 }
 
 impl Session {
@@ -28,11 +40,25 @@ impl Session {
     fn to_process_info(self: &Arc<Self>, status: &MutableStatus) -> ProcessInfo {
         ProcessInfo {
             id: self.id.clone(),
+            user: self.user.clone(),
             state: self.process_state(status),
             database: status.current_database.clone(),
             settings: status.session_settings.clone(),
             client_address: status.client_host,
             session_extra_info: self.process_extra_info(status),
+            tenant: status.tenant.clone(),
+            query_id: status
+                .context_shared
+                .as_ref()
+                .map(|context_shared| context_shared.init_query_id.read().clone()),
+            progress: status
+                .context_shared
+                .as_ref()
+                .map(|context_shared| context_shared.total_progress.get_values()),
+            elapsed: status
+                .context_shared
+                .as_ref()
+                .map(|context_shared| context_shared.created_time.elapsed()),
         }
     }
 
@@ -40,6 +66,9 @@ impl Session {
         match status.context_shared {
             _ if status.abort => String::from("Aborting"),
             None => String::from("Idle"),
+            Some(ref context_shared) if context_shared.queued.load(Ordering::Relaxed) => {
+                String::from("Queued")
+            }
             Some(_) => String::from("Query"),
         }
     }
@@ -7,6 +7,8 @@ use std::sync::atomic::Ordering;
 use std::sync::atomic::Ordering::Acquire;
 use std::sync::Arc;
 
+use common_exception::Result;
+
 use crate::sessions::FuseQueryContextRef;
 use crate::sessions::ProcessInfo;
 use crate::sessions::Session;
@@ -28,6 +30,10 @@ impl SessionRef {
         self.session.get_id()
     }
 
+    pub fn get_user(&self) -> String {
+        self.session.get_user()
+    }
+
     pub fn get_type(&self) -> String {
         self.typ.clone()
     }
@@ -36,6 +42,10 @@ impl SessionRef {
         self.session.create_context()
     }
 
+    pub fn bind_tenant(&self, tenant: &str) -> Result<()> {
+        self.session.bind_tenant(tenant)
+    }
+
     pub fn is_aborting(&self) -> bool {
         self.session.is_aborting()
     }
@@ -0,0 +1,151 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::StreamExt;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::FuseQueryContextRef;
+use crate::sql::PlanParser;
+
+async fn execute(ctx: FuseQueryContextRef, query: &str) -> Result<()> {
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(query)?;
+    InterpreterFactory::get(ctx, plan)?.execute().await?;
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_tenant_catalogs() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+
+    let session_a = sessions.create_session("TestSession")?;
+    session_a.bind_tenant("tenant_a")?;
+    let session_a2 = sessions.create_session("TestSession")?;
+    session_a2.bind_tenant("tenant_a")?;
+    let session_b = sessions.create_session("TestSession")?;
+    session_b.bind_tenant("tenant_b")?;
+    let session_none = sessions.create_session("TestSession")?;
+
+    execute(
+        session_a.create_context(),
+        "create database db1 Engine = Local",
+    )
+    .await?;
+
+    // Visible to the sessions of the same tenant only.
+    assert!(session_a2
+        .create_context()
+        .get_datasource()
+        .get_database("db1")
+        .is_ok());
+    assert!(session_b
+        .create_context()
+        .get_datasource()
+        .get_database("db1")
+        .is_err());
+    assert!(session_none
+        .create_context()
+        .get_datasource()
+        .get_database("db1")
+        .is_err());
+
+    // Every tenant has its own catalog, the same database can be created by another tenant.
+    execute(
+        session_b.create_context(),
+        "create database db1 Engine = Local",
+    )
+    .await?;
+
+    // A session can't switch to another tenant.
+    assert!(session_a.bind_tenant("tenant_a").is_ok());
+    let result = session_a.bind_tenant("tenant_b");
+    assert!(result.is_err());
+    assert_eq!(
+        "Code: 51, displayText = The session is already bound to tenant tenant_a.",
+        format!("{}", result.unwrap_err())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_user_limits() -> Result<()> {
+    let mut conf = crate::configs::Config::default();
+    conf.max_active_sessions = 3;
+    conf.max_sessions_per_user = 2;
+    let sessions =
+        crate::sessions::SessionManager::from_conf(conf, crate::clusters::Cluster::empty())?;
+
+    let session_a1 = sessions.create_user_session("TestSession", "a")?;
+    let _session_a2 = sessions.create_user_session("TestSession", "a")?;
+    let result = sessions.create_user_session("TestSession", "a");
+    assert!(result.is_err());
+    assert_eq!(
+        "Code: 41, displayText = The sessions of user a has exceeded max_sessions_per_user config.",
+        format!("{}", result.err().unwrap())
+    );
+
+    // Other users are only limited by max_active_sessions.
+    let session_b = sessions.create_user_session("TestSession", "b")?;
+    assert!(sessions.create_user_session("TestSession", "c").is_err());
+
+    let processes = session_b.create_context().processes_info();
+    let mut users = processes.iter().map(|p| p.user.clone()).collect::<Vec<_>>();
+    users.sort();
+    assert_eq!(vec!["a", "a", "b"], users);
+
+    // A killed session is aborting, and removed from the manager when its last reference is dropped.
+    sessions.kill_session(&session_a1.get_id())?;
+    assert!(session_a1.is_aborting());
+    drop(session_a1);
+    assert_eq!(2, sessions.processes_info().len());
+    let _session_a3 = sessions.create_user_session("TestSession", "a")?;
+
+    let result = sessions.kill_session("unknown");
+    assert!(result.is_err());
+    assert_eq!(
+        "Code: 44, displayText = Not found session: unknown.",
+        format!("{}", result.unwrap_err())
+    );
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_session_kill_query() -> Result<()> {
+    let sessions = crate::tests::try_create_sessions()?;
+
+    let session = sessions.create_session("TestSession")?;
+    let ctx = session.create_context();
+    ctx.set_id("query_a".to_string())?;
+    assert_eq!("query_a", ctx.get_id());
+
+    // The id of a running query can't be used by another query.
+    let session2 = sessions.create_session("TestSession")?;
+    let result = session2.create_context().set_id("query_a".to_string());
+    assert_eq!(
+        "Code: 6, displayText = The query query_a is already running.",
+        result.unwrap_err().to_string()
+    );
+
+    // The query killed before its sources are created is aborted at once.
+    sessions.kill_query("query_a")?;
+    let plan = PlanParser::create(ctx.clone()).build_from_sql("SELECT * FROM numbers(10)")?;
+    let stream = InterpreterFactory::get(ctx.clone(), plan)?
+        .execute()
+        .await?;
+    let mut stream = ctx.try_create_abortable(stream)?;
+    let result = stream.next().await.unwrap();
+    assert_eq!(43, result.unwrap_err().code());
+    assert!(!session.is_aborting());
+
+    let result = sessions.kill_query("unknown");
+    assert_eq!(
+        "Code: 56, displayText = Unknown query: unknown.",
+        result.unwrap_err().to_string()
+    );
+
+    Ok(())
+}
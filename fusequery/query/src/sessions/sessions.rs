@@ -21,16 +21,28 @@ use crate::clusters::Cluster;
 use crate::clusters::ClusterRef;
 use crate::configs::Config;
 use crate::datasources::DataSource;
+use crate::sessions::context_shared::FuseQueryContextShared;
+use crate::sessions::query_log::QueryLog;
+use crate::sessions::query_queue::QueryQueue;
+use crate::sessions::server_health::ServerHealth;
 use crate::sessions::session::Session;
 use crate::sessions::session_ref::SessionRef;
 
+/// The user of the sessions which are not created for a user.
+pub const DEFAULT_USER: &str = "default";
+
 pub struct SessionManager {
     pub(in crate::sessions) conf: Config,
     pub(in crate::sessions) cluster: ClusterRef,
     pub(in crate::sessions) datasource: Arc<DataSource>,
+    pub(in crate::sessions) tenant_datasources: RwLock<HashMap<String, Arc<DataSource>>>,
 
     pub(in crate::sessions) max_sessions: usize,
+    pub(in crate::sessions) max_sessions_per_user: usize,
     pub(in crate::sessions) active_sessions: Arc<RwLock<HashMap<String, Arc<Session>>>>,
+    pub(in crate::sessions) query_queue: Arc<QueryQueue>,
+    pub(in crate::sessions) query_log: Arc<QueryLog>,
+    pub(in crate::sessions) health: Arc<ServerHealth>,
 }
 
 pub type SessionManagerRef = Arc<SessionManager>;
@@ -41,23 +53,39 @@ impl SessionManager {
             conf: Config::default(),
             cluster: Cluster::empty(),
             datasource: Arc::new(DataSource::try_create()?),
+            tenant_datasources: Default::default(),
 
             max_sessions: max_mysql_sessions as usize,
+            max_sessions_per_user: 0,
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(
                 max_mysql_sessions as usize,
             ))),
+            query_queue: Arc::new(QueryQueue::create(0, Duration::default())),
+            query_log: Arc::new(QueryLog::create(0, "")),
+            health: Arc::new(ServerHealth::default()),
         }))
     }
 
     pub fn from_conf(conf: Config, cluster: ClusterRef) -> Result<SessionManagerRef> {
         let max_active_sessions = conf.max_active_sessions as usize;
+        let max_sessions_per_user = conf.max_sessions_per_user as usize;
+        let query_queue = QueryQueue::create(
+            conf.max_running_queries as usize,
+            Duration::from_secs(conf.query_queue_timeout),
+        );
+        let query_log = QueryLog::create(conf.query_log_capacity as usize, &conf.query_log_file);
         Ok(Arc::new(SessionManager {
             conf,
             cluster,
             datasource: Arc::new(DataSource::try_create()?),
+            tenant_datasources: Default::default(),
 
             max_sessions: max_active_sessions,
+            max_sessions_per_user,
             active_sessions: Arc::new(RwLock::new(HashMap::with_capacity(max_active_sessions))),
+            query_queue: Arc::new(query_queue),
+            query_log: Arc::new(query_log),
+            health: Arc::new(ServerHealth::default()),
         }))
     }
 
@@ -69,25 +97,82 @@ impl SessionManager {
         self.datasource.clone()
     }
 
+    pub fn get_query_queue(self: &Arc<Self>) -> Arc<QueryQueue> {
+        self.query_queue.clone()
+    }
+
+    pub fn get_query_log(self: &Arc<Self>) -> Arc<QueryLog> {
+        self.query_log.clone()
+    }
+
+    pub fn get_health(self: &Arc<Self>) -> Arc<ServerHealth> {
+        self.health.clone()
+    }
+
+    /// The catalog of a tenant, which is created on the first session of the tenant.
+    pub fn get_tenant_datasource(self: &Arc<Self>, tenant: &str) -> Result<Arc<DataSource>> {
+        if let Some(datasource) = self.tenant_datasources.read().get(tenant) {
+            return Ok(datasource.clone());
+        }
+
+        match self.tenant_datasources.write().entry(tenant.to_string()) {
+            Occupied(entry) => Ok(entry.get().clone()),
+            Vacant(entry) => {
+                let datasource = Arc::new(DataSource::try_create_with_tenant(tenant)?);
+                Ok(entry.insert(datasource).clone())
+            }
+        }
+    }
+
     pub fn create_session(self: &Arc<Self>, typ: impl Into<String>) -> Result<SessionRef> {
+        self.create_user_session(typ, DEFAULT_USER)
+    }
+
+    /// Create a session of the user, the session is tracked by the manager until the last
+    /// SessionRef of it is dropped or it's killed by `kill_session`.
+    pub fn create_user_session(
+        self: &Arc<Self>,
+        typ: impl Into<String>,
+        user: &str,
+    ) -> Result<SessionRef> {
         counter!(super::metrics::METRIC_SESSION_CONNECT_NUMBERS, 1);
 
+        if self.health.is_draining() {
+            return Err(ErrorCode::AbortedSession(
+                "The server is shutting down, no new sessions are accepted",
+            ));
+        }
+
         let mut sessions = self.active_sessions.write();
-        match sessions.len() == self.max_sessions {
-            true => Err(ErrorCode::TooManyUserConnections(
+        if sessions.len() == self.max_sessions {
+            return Err(ErrorCode::TooManyUserConnections(
                 "The current accept connection has exceeded mysql_handler_thread_num config",
-            )),
-            false => {
-                let session = Session::try_create(
-                    self.conf.clone(),
-                    uuid::Uuid::new_v4().to_string(),
-                    self.clone(),
-                )?;
+            ));
+        }
+
+        if self.max_sessions_per_user != 0 {
+            let user_sessions = sessions
+                .values()
+                .filter(|session| session.user == user)
+                .count();
 
-                sessions.insert(session.get_id(), session.clone());
-                Ok(SessionRef::create(typ.into(), session))
+            if user_sessions >= self.max_sessions_per_user {
+                return Err(ErrorCode::TooManyUserConnections(format!(
+                    "The sessions of user {} has exceeded max_sessions_per_user config",
+                    user
+                )));
             }
         }
+
+        let session = Session::try_create(
+            self.conf.clone(),
+            uuid::Uuid::new_v4().to_string(),
+            user.to_string(),
+            self.clone(),
+        )?;
+
+        sessions.insert(session.get_id(), session.clone());
+        Ok(SessionRef::create(typ.into(), session))
     }
 
     pub fn create_rpc_session(self: &Arc<Self>, id: String, aborted: bool) -> Result<SessionRef> {
@@ -99,8 +184,12 @@ impl SessionManager {
             Occupied(entry) => entry.get().clone(),
             Vacant(_) if aborted => return Err(ErrorCode::AbortedSession("Aborting server.")),
             Vacant(entry) => {
-                let session =
-                    Session::try_create(self.conf.clone(), entry.key().clone(), self.clone())?;
+                let session = Session::try_create(
+                    self.conf.clone(),
+                    entry.key().clone(),
+                    DEFAULT_USER.to_string(),
+                    self.clone(),
+                )?;
 
                 entry.insert(session).clone()
             }
@@ -116,6 +205,51 @@ impl SessionManager {
         self.active_sessions.write().remove(session_id);
     }
 
+    /// Close a session administratively, the running query of the session is killed
+    /// and the connection of the session is shut down.
+    pub fn kill_session(self: &Arc<Self>, session_id: &str) -> Result<()> {
+        let session = self.active_sessions.read().get(session_id).cloned();
+        match session {
+            Some(session) => {
+                session.force_kill();
+                Ok(())
+            }
+            None => Err(ErrorCode::NotFoundSession(format!(
+                "Not found session: {}",
+                session_id
+            ))),
+        }
+    }
+
+    /// Kill a running query by its id, the query fails with the AbortedQuery error and
+    /// its session is kept.
+    pub fn kill_query(self: &Arc<Self>, query_id: &str) -> Result<()> {
+        match self.find_query(query_id) {
+            Some(context_shared) => {
+                context_shared.kill();
+                Ok(())
+            }
+            None => Err(ErrorCode::UnknownQuery(format!(
+                "Unknown query: {}",
+                query_id
+            ))),
+        }
+    }
+
+    pub(in crate::sessions) fn find_query(
+        &self,
+        query_id: &str,
+    ) -> Option<Arc<FuseQueryContextShared>> {
+        self.active_sessions.read().values().find_map(|session| {
+            let mutable_state = session.mutable_state.lock();
+            mutable_state
+                .context_shared
+                .as_ref()
+                .filter(|shared| *shared.init_query_id.read() == query_id)
+                .cloned()
+        })
+    }
+
     pub fn shutdown(self: &Arc<Self>, signal: Option<Receiver<()>>) -> impl Future<Output = ()> {
         let active_sessions = self.active_sessions.clone();
         async move {
@@ -17,11 +17,27 @@ pub struct Settings {
 
 impl Settings {
     apply_macros! { apply_getter_setter_settings, apply_initial_settings, apply_update_settings,
-        ("max_block_size", u64, 10000, "Maximum block size for reading".to_string()),
-        ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically.".to_string()),
-        ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds".to_string()),
-        ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query.".to_string()),
-        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.".to_string())
+        ("max_block_size", u64, 10000, "Maximum block size for reading".to_string(), SettingMeta::advanced().min(1)),
+        ("max_threads", u64, 16, "The maximum number of threads to execute the request. By default, it is determined automatically.".to_string(), SettingMeta::common().min(1).max(1024)),
+        ("enable_thread_pinning", u64, 0, "Pin the threads which execute the request to the CPU cores of one NUMA node, so that the memory they allocate stays local to their cores. By default, it is 0 (disabled).".to_string(), SettingMeta::advanced().min(0).max(1)),
+        ("flight_client_timeout", u64, 60, "Max duration the flight client request is allowed to take in seconds. By default, it is 60 seconds".to_string(), SettingMeta::advanced().min(1)),
+        ("flight_read_window", u64, 2, "The maximum number of blocks a store node reads ahead of a remote table read, a slow reader makes the store wait instead of buffering the part. By default, it is 2.".to_string(), SettingMeta::advanced().min(1).max(1024)),
+        ("enable_store_plan", u64, 1, "Execute the filters and the partial aggregates of a remote table read on the store nodes holding the parts, when a part has more rows on average than the aggregate results sent instead. By default, it is 1 (enabled).".to_string(), SettingMeta::common().min(0).max(1)),
+        ("read_locality_labels", String, "host,zone".to_string(), "The node labels by which the parts of a remote table read are assigned to the query nodes with the labels of the store nodes holding them, the first label is preferred. By default, it is 'host,zone', an empty value assigns the parts in order.".to_string(), SettingMeta::advanced()),
+        ("min_distributed_rows", u64, 100000000, "Minimum distributed read rows. In cluster mode, when read rows exceeds this value, the local table converted to distributed query.".to_string(), SettingMeta::advanced()),
+        ("min_distributed_bytes", u64, 500 * 1024 * 1024, "Minimum distributed read bytes. In cluster mode, when read bytes exceeds this value, the local table converted to distributed query.".to_string(), SettingMeta::advanced()),
+        ("allow_partial_results", u64, 0, "Return the results of the remote stages which succeeded with warnings instead of failing the query when some remote stages fail. By default, it is 0 (disabled).".to_string(), SettingMeta::common().min(0).max(1)),
+        ("insert_deduplicate", u64, 0, "Skip the inserted blocks of a remote table which are the same as a block among the latest blocks inserted into the table, so that a retried insert does not duplicate the data. By default, it is 0 (disabled).".to_string(), SettingMeta::common().min(0).max(1)),
+        ("max_result_rows", u64, 0, "Maximum rows of the result which a query returns to the client. By default, it is 0 (unlimited).".to_string(), SettingMeta::common()),
+        ("max_result_bytes", u64, 0, "Maximum bytes of the result which a query returns to the client. By default, it is 0 (unlimited).".to_string(), SettingMeta::common()),
+        ("result_overflow_mode", String, "throw".to_string(), "What to do when the result exceeds max_result_rows or max_result_bytes, 'throw' fails the query and 'break' returns the truncated result. By default, it is 'throw'.".to_string(), SettingMeta::common().values(&["throw", "break"])),
+        ("max_rows_to_read", u64, 0, "Maximum rows a query can read from the tables, the query fails if the statistics of the tables or the rows read exceed it. By default, it is 0 (unlimited).".to_string(), SettingMeta::common()),
+        ("max_bytes_to_read", u64, 0, "Maximum bytes a query can read from the tables, the query fails if the statistics of the tables or the bytes read exceed it. By default, it is 0 (unlimited).".to_string(), SettingMeta::common()),
+        ("max_memory_usage", u64, 0, "Maximum memory a query can hold for the blocks to sort and the hash tables of the group by and the join, the query fails if it exceeds it. By default, it is 0 (unlimited).".to_string(), SettingMeta::common()),
+        ("max_bytes_before_external_group_by", u64, 0, "Spill the states of the group by to temporary files once its hash table holds more bytes, so a group by of many groups runs in bounded memory. By default, it is 0 (never spill).".to_string(), SettingMeta::common()),
+        ("max_bytes_before_external_join", u64, 0, "Spill the right side of the join and then the left side to temporary files by the hash of the keys once the right side holds more bytes, and join them partition by partition. By default, it is 0 (never spill).".to_string(), SettingMeta::common()),
+        ("max_bytes_before_external_sort", u64, 0, "Spill the blocks of the ORDER BY to temporary files as sorted runs once they hold more bytes, and merge the runs at the end. By default, it is 0 (never spill).".to_string(), SettingMeta::common()),
+        ("max_nested_loop_join_pairs", u64, 1000000000, "Maximum pairs of the rows of both sides which a join without equalities of the columns of both sides evaluates, e.g. a CROSS JOIN or a range join, the query fails if it exceeds it. 0 is unlimited. By default, it is 1000000000.".to_string(), SettingMeta::common())
     }
 
     pub fn try_create() -> Result<Arc<Settings>> {
@@ -43,16 +59,136 @@ impl Settings {
     }
 }
 
+/// Who is expected to change a setting.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SettingTier {
+    /// The settings which users tune for their queries.
+    Common,
+    /// The settings of the planner and the cluster, which are changed for known issues only.
+    Advanced,
+    /// The settings for the tests and the debugging of the server.
+    Internal,
+}
+
+/// The metadata of a setting, the constraints are checked when the setting is changed by SET.
+#[derive(Debug, Clone)]
+pub struct SettingMeta {
+    pub tier: SettingTier,
+    /// The inclusive bounds of a numeric setting.
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// The allowed values of a string setting, which are case insensitive.
+    pub values: Option<&'static [&'static str]>,
+    /// The setting is read when the server starts, SET cannot change it.
+    pub requires_restart: bool,
+}
+
+impl SettingMeta {
+    pub fn common() -> Self {
+        Self::create(SettingTier::Common)
+    }
+
+    pub fn advanced() -> Self {
+        Self::create(SettingTier::Advanced)
+    }
+
+    pub fn internal() -> Self {
+        Self::create(SettingTier::Internal)
+    }
+
+    fn create(tier: SettingTier) -> Self {
+        SettingMeta {
+            tier,
+            min: None,
+            max: None,
+            values: None,
+            requires_restart: false,
+        }
+    }
+
+    pub fn min(mut self, min: impl Into<f64>) -> Self {
+        self.min = Some(min.into());
+        self
+    }
+
+    pub fn max(mut self, max: impl Into<f64>) -> Self {
+        self.max = Some(max.into());
+        self
+    }
+
+    pub fn values(mut self, values: &'static [&'static str]) -> Self {
+        self.values = Some(values);
+        self
+    }
+
+    pub fn requires_restart(mut self) -> Self {
+        self.requires_restart = true;
+        self
+    }
+
+    /// Checks the new value of SET.
+    pub fn check(&self, key: &str, value: &DataValue) -> Result<()> {
+        if self.requires_restart {
+            return Err(ErrorCode::ReadOnlySetting(format!(
+                "Setting {:?} requires a restart of the server, it cannot be changed by SET",
+                key
+            )));
+        }
+
+        if let (Some(values), DataValue::Utf8(Some(value))) = (self.values, value) {
+            if !values.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+                return Err(ErrorCode::BadSettingValue(format!(
+                    "Value {:?} of setting {:?} is not one of {:?}",
+                    value, key, values
+                )));
+            }
+        }
+
+        let value = match value {
+            DataValue::UInt64(Some(v)) => Some(*v as f64),
+            DataValue::Int64(Some(v)) => Some(*v as f64),
+            DataValue::Float64(Some(v)) => Some(*v),
+            _ => None,
+        };
+        match (value, self.min, self.max) {
+            (Some(value), Some(min), _) if value < min => Err(ErrorCode::BadSettingValue(format!(
+                "Value {} of setting {:?} is less than the minimum {}",
+                value, key, min
+            ))),
+            (Some(value), _, Some(max)) if value > max => Err(ErrorCode::BadSettingValue(format!(
+                "Value {} of setting {:?} is greater than the maximum {}",
+                value, key, max
+            ))),
+            _ => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SettingsBase {
     // DataValue is of DataValue::Struct([name, value, default_value, description])
     settings: Arc<RwLock<HashMap<&'static str, DataValue>>>,
+    metas: Arc<RwLock<HashMap<&'static str, SettingMeta>>>,
 }
 
 impl SettingsBase {
     pub fn create() -> Self {
         SettingsBase {
             settings: Arc::new(RwLock::new(HashMap::default())),
+            metas: Arc::new(RwLock::new(HashMap::default())),
+        }
+    }
+
+    pub fn try_set_meta(&self, key: &'static str, meta: SettingMeta) -> Result<()> {
+        self.metas.write().insert(key, meta);
+        Ok(())
+    }
+
+    /// Checks the new value of the setting against the constraints of its metadata.
+    pub fn check_value(&self, key: &str, value: &DataValue) -> Result<()> {
+        match self.metas.read().get(key) {
+            Some(meta) => meta.check(key, value),
+            None => Ok(()),
         }
     }
 
@@ -254,17 +390,25 @@ impl SettingsBase {
         )))
     }
 
+    // DataValue is of DataValue::Struct([name, value, default_value, description, min, max,
+    // tier, requires_restart])
     pub fn get_settings(&self) -> Vec<DataValue> {
         let settings = self.settings.read();
+        let metas = self.metas.read();
 
         let mut result = vec![];
         for (k, v) in settings.iter() {
             if let DataValue::Struct(values) = v {
+                let meta = metas.get(k).cloned().unwrap_or_else(SettingMeta::internal);
                 let res = DataValue::Struct(vec![
                     DataValue::Utf8(Some(k.to_string())),
                     values[0].clone(),
                     values[1].clone(),
                     values[2].clone(),
+                    DataValue::Float64(meta.min),
+                    DataValue::Float64(meta.max),
+                    DataValue::Utf8(Some(format!("{:?}", meta.tier))),
+                    DataValue::Boolean(Some(meta.requires_restart)),
                 ]);
                 result.push(res);
             }
@@ -0,0 +1,99 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datavalues::DataValue;
+use common_exception::Result;
+use pretty_assertions::assert_eq;
+
+use crate::sessions::settings::SettingsBase;
+use crate::sessions::SettingMeta;
+use crate::sessions::Settings;
+
+#[test]
+fn test_settings_constraints() -> Result<()> {
+    let settings = Settings::try_create()?;
+
+    settings.update_settings("max_threads", "1024".to_string())?;
+    assert_eq!(settings.get_max_threads()?, 1024);
+    settings.update_settings("result_overflow_mode", "BREAK".to_string())?;
+    assert_eq!(settings.get_result_overflow_mode()?, "BREAK");
+
+    struct Test {
+        key: &'static str,
+        value: &'static str,
+        error: &'static str,
+    }
+
+    let tests = vec![
+        Test {
+            key: "max_threads",
+            value: "0",
+            error: "Code: 52, displayText = Value 0 of setting \"max_threads\" is less than the minimum 1.",
+        },
+        Test {
+            key: "MAX_THREADS",
+            value: "1025",
+            error: "Code: 52, displayText = Value 1025 of setting \"max_threads\" is greater than the maximum 1024.",
+        },
+        Test {
+            key: "result_overflow_mode",
+            value: "truncate",
+            error: "Code: 52, displayText = Value \"truncate\" of setting \"result_overflow_mode\" is not one of [\"throw\", \"break\"].",
+        },
+        Test {
+            key: "allow_partial_results",
+            value: "2",
+            error: "Code: 52, displayText = Value 2 of setting \"allow_partial_results\" is greater than the maximum 1.",
+        },
+        Test {
+            key: "enable_thread_pinning",
+            value: "2",
+            error: "Code: 52, displayText = Value 2 of setting \"enable_thread_pinning\" is greater than the maximum 1.",
+        },
+        Test {
+            key: "insert_deduplicate",
+            value: "2",
+            error: "Code: 52, displayText = Value 2 of setting \"insert_deduplicate\" is greater than the maximum 1.",
+        },
+    ];
+
+    for test in tests {
+        match settings.update_settings(test.key, test.value.to_string()) {
+            Ok(_) => assert!(false, "SET {} = {} must be failed", test.key, test.value),
+            Err(e) => assert_eq!(test.error, e.to_string()),
+        }
+    }
+
+    // The invalid values are not applied.
+    assert_eq!(settings.get_max_threads()?, 1024);
+    assert_eq!(settings.get_allow_partial_results()?, 0);
+    Ok(())
+}
+
+#[test]
+fn test_settings_meta() -> Result<()> {
+    let settings = SettingsBase::create();
+    settings.try_set_u64("listen_port", 3307, "The port".to_string())?;
+    settings.try_set_meta("listen_port", SettingMeta::internal().requires_restart())?;
+
+    assert_eq!(
+        "Code: 53, displayText = Setting \"listen_port\" requires a restart of the server, it cannot be changed by SET.",
+        settings
+            .check_value("listen_port", &DataValue::UInt64(Some(3308)))
+            .unwrap_err()
+            .to_string()
+    );
+
+    assert_eq!(settings.get_settings(), vec![DataValue::Struct(vec![
+        DataValue::Utf8(Some("listen_port".to_string())),
+        DataValue::UInt64(Some(3307)),
+        DataValue::UInt64(Some(3307)),
+        DataValue::Utf8(Some("The port".to_string())),
+        DataValue::Float64(None),
+        DataValue::Float64(None),
+        DataValue::Utf8(Some("Internal".to_string())),
+        DataValue::Boolean(Some(true)),
+    ])]);
+    Ok(())
+}
@@ -0,0 +1,85 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryInto;
+use std::fs::File;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::PathBuf;
+
+use common_arrow::arrow::ipc::reader::StreamReader;
+use common_arrow::arrow::ipc::writer::StreamWriter;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchemaRef;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_streams::SendableDataBlockStream;
+
+/// A temporary file of the blocks which a processor spills when it holds more data than its
+/// budget, e.g. the states of a group by of many groups. The blocks are written as an arrow IPC
+/// stream and read back in the order they are written, the file is removed when it is dropped.
+pub struct SpillFile {
+    path: PathBuf,
+    writer: Option<StreamWriter<BufWriter<File>>>,
+    rows: usize,
+}
+
+impl SpillFile {
+    /// Creates the file in the directory, the temporary directory of the system if it is empty.
+    pub fn create(dir: &str, schema: &DataSchemaRef) -> Result<SpillFile> {
+        let dir = match dir.is_empty() {
+            true => std::env::temp_dir(),
+            false => PathBuf::from(dir),
+        };
+        std::fs::create_dir_all(&dir)?;
+
+        let path = dir.join(format!("fuse-query-spill-{}", uuid::Uuid::new_v4()));
+        let file = File::create(&path)?;
+        let writer = StreamWriter::try_new(BufWriter::new(file), &schema.to_arrow())?;
+        Ok(SpillFile {
+            path,
+            writer: Some(writer),
+            rows: 0,
+        })
+    }
+
+    /// The rows written.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn write(&mut self, block: DataBlock) -> Result<()> {
+        let writer = self
+            .writer
+            .as_mut()
+            .ok_or_else(|| ErrorCode::LogicalError("The spill file is already read"))?;
+        self.rows += block.num_rows();
+        let batch: RecordBatch = block.try_into()?;
+        writer.write(&batch)?;
+        Ok(())
+    }
+
+    /// Finishes the writes and reads the blocks back, the stream holds the file open after the
+    /// file is removed.
+    pub fn into_stream(mut self) -> Result<SendableDataBlockStream> {
+        if let Some(mut writer) = self.writer.take() {
+            writer.finish()?;
+        }
+
+        let reader = StreamReader::try_new(BufReader::new(File::open(&self.path)?))?;
+        let blocks = reader.map(|batch| -> Result<DataBlock> { batch?.try_into() });
+        Ok(Box::pin(futures::stream::iter(blocks)))
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        // Drops the writer first, which closes the file.
+        self.writer.take();
+        if let Err(e) = std::fs::remove_file(&self.path) {
+            log::warn!("Cannot remove the spill file {:?}: {}", self.path, e);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+
+use crate::sessions::SpillFile;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_spill_file() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("fuse-query-spill-test-{}", uuid::Uuid::new_v4()));
+    let dir_files = || -> Result<usize> { Ok(std::fs::read_dir(&dir)?.count()) };
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::UInt64, false),
+        DataField::new("b", DataType::Utf8, true),
+    ]);
+    let mut file = SpillFile::create(&dir.display().to_string(), &schema)?;
+    file.write(DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1u64, 2]),
+        Series::new(vec![Some("x"), None]),
+    ]))?;
+    file.write(DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![3u64]),
+        Series::new(vec![Some("z")]),
+    ]))?;
+    assert_eq!(3, file.rows());
+    assert_eq!(1, dir_files()?);
+
+    // The blocks are read back in order, the file is removed once it is opened for the reads.
+    let stream = file.into_stream()?;
+    assert_eq!(0, dir_files()?);
+    let blocks = stream.try_collect::<Vec<_>>().await?;
+    let expected = vec![
+        "+---+------+",
+        "| a | b    |",
+        "+---+------+",
+        "| 1 | x    |",
+        "| 2 | NULL |",
+        "| 3 | z    |",
+        "+---+------+",
+    ];
+    assert_blocks_eq(expected, &blocks);
+    assert_eq!(schema, blocks[0].schema().clone());
+
+    // The file which is not read is removed when it is dropped.
+    let file = SpillFile::create(&dir.display().to_string(), &schema)?;
+    assert_eq!(1, dir_files()?);
+    drop(file);
+    assert_eq!(0, dir_files()?);
+
+    std::fs::remove_dir(&dir)?;
+    Ok(())
+}
@@ -5,14 +5,22 @@
 #[cfg(test)]
 mod plan_parser_test;
 #[cfg(test)]
+mod sql_default_values_test;
+#[cfg(test)]
 mod sql_parser_test;
+#[cfg(test)]
+mod substrait_plan_parser_test;
 
 mod plan_parser;
 mod sql_common;
+mod sql_default_values;
 mod sql_parser;
 mod sql_statement;
+mod substrait_plan_parser;
 
 pub use plan_parser::PlanParser;
 pub use sql_common::SQLCommon;
+pub use sql_default_values::DefaultValues;
 pub use sql_parser::DfParser;
 pub use sql_statement::*;
+pub use substrait_plan_parser::SubstraitPlanParser;
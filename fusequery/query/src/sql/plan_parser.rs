@@ -11,56 +11,82 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_functions::aggregates::AggregateFunctionFactory;
 use common_infallible::Mutex;
+use common_planners::col;
 use common_planners::expand_aggregate_arg_exprs;
 use common_planners::expand_wildcard;
 use common_planners::expr_as_column_expr;
 use common_planners::extract_aliases;
 use common_planners::find_aggregate_exprs;
+use common_planners::find_column_exprs;
 use common_planners::find_columns_not_satisfy_exprs;
 use common_planners::rebase_expr;
 use common_planners::rebase_expr_from_input;
 use common_planners::resolve_aliases_to_exprs;
+use common_planners::resolve_grouping_exprs;
 use common_planners::sort_to_inner_expr;
 use common_planners::unwrap_alias_exprs;
+use common_planners::CopyPlan;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DescribeTablePlan;
 use common_planners::DropDatabasePlan;
+use common_planners::DropPartitionPlan;
 use common_planners::DropTablePlan;
 use common_planners::ExplainPlan;
 use common_planners::Expression;
 use common_planners::InsertIntoPlan;
+use common_planners::JoinType;
+use common_planners::KillQueryPlan;
 use common_planners::PlanBuilder;
 use common_planners::PlanNode;
+use common_planners::RewriteHelper;
 use common_planners::SelectPlan;
 use common_planners::SettingPlan;
 use common_planners::ShowCreateTablePlan;
+use common_planners::TruncateTablePlan;
 use common_planners::UseDatabasePlan;
 use common_planners::VarValue;
 use common_tracing::tracing;
+use sqlparser::ast::BinaryOperator;
+use sqlparser::ast::ColumnOption;
 use sqlparser::ast::Expr;
 use sqlparser::ast::FunctionArg;
 use sqlparser::ast::Ident;
+use sqlparser::ast::JoinConstraint;
+use sqlparser::ast::JoinOperator;
 use sqlparser::ast::ObjectName;
 use sqlparser::ast::OrderByExpr;
 use sqlparser::ast::Query;
 use sqlparser::ast::Statement;
 use sqlparser::ast::TableFactor;
+use sqlparser::tokenizer::Token;
 
+use crate::datasources::local::FileFormat;
+use crate::datasources::PartitionBy;
 use crate::datasources::Table;
 use crate::functions::ContextFunction;
+use crate::functions::ContextVariable;
 use crate::sessions::FuseQueryContextRef;
 use crate::sql::sql_statement::DfCreateTable;
 use crate::sql::sql_statement::DfDropDatabase;
 use crate::sql::sql_statement::DfUseDatabase;
+use crate::sql::DefaultValues;
+use crate::sql::DfCopy;
 use crate::sql::DfCreateDatabase;
 use crate::sql::DfDescribeTable;
+use crate::sql::DfDropPartition;
 use crate::sql::DfDropTable;
 use crate::sql::DfExplain;
 use crate::sql::DfHint;
+use crate::sql::DfInsertIntoFunction;
+use crate::sql::DfKillQuery;
 use crate::sql::DfParser;
 use crate::sql::DfShowCreateTable;
+use crate::sql::DfShowFilter;
+use crate::sql::DfShowKeys;
+use crate::sql::DfShowVariables;
 use crate::sql::DfStatement;
+use crate::sql::DfTruncateTable;
 use crate::sql::SQLCommon;
 
 pub struct PlanParser {
@@ -111,8 +137,13 @@ impl PlanParser {
             DfStatement::CreateTable(v) => self.sql_create_table_to_plan(v),
             DfStatement::DescribeTable(v) => self.sql_describe_table_to_plan(v),
             DfStatement::DropTable(v) => self.sql_drop_table_to_plan(v),
+            DfStatement::TruncateTable(v) => self.sql_truncate_table_to_plan(v),
+            DfStatement::DropPartition(v) => self.sql_drop_partition_to_plan(v),
+            DfStatement::InsertIntoFunction(v) => self.sql_insert_into_function_to_plan(v),
+            DfStatement::Copy(v) => self.sql_copy_to_plan(v),
             DfStatement::UseDatabase(v) => self.sql_use_database_to_plan(v),
             DfStatement::ShowCreateTable(v) => self.sql_show_create_table_to_plan(v),
+            DfStatement::KillQuery(v) => self.sql_kill_query_to_plan(v),
 
             // TODO: support like and other filters in show queries
             DfStatement::ShowTables(_) => self.build_from_sql(
@@ -126,9 +157,50 @@ impl PlanParser {
             DfStatement::ShowProcessList(_) => {
                 self.build_from_sql("SELECT * FROM system.processes")
             }
+            DfStatement::ShowWarnings(_) => self.build_from_sql("SELECT * FROM system.warnings"),
+            DfStatement::ShowVariables(v) => self.sql_show_variables_to_plan(v),
+            DfStatement::ShowKeys(v) => self.sql_show_keys_to_plan(v),
         }
     }
 
+    /// DfShowVariables to plan.
+    pub fn sql_show_variables_to_plan(&self, show: &DfShowVariables) -> Result<PlanNode> {
+        let selection = match &show.filter {
+            None => String::new(),
+            Some(DfShowFilter::Like(pattern)) => format!(
+                " WHERE Variable_name LIKE '{}'",
+                pattern.replace('\'', "''")
+            ),
+            Some(DfShowFilter::Where(expr)) => format!(" WHERE {}", expr),
+        };
+        self.build_from_sql(&format!(
+            "SELECT Variable_name, Value FROM system.variables{}",
+            selection
+        ))
+    }
+
+    /// DfShowKeys to plan, the tables have no keys, so the result is always empty.
+    pub fn sql_show_keys_to_plan(&self, show: &DfShowKeys) -> Result<PlanNode> {
+        let (db, table) = match show.name.0.as_slice() {
+            [table] => (self.ctx.get_current_database(), table.value.clone()),
+            [db, table] => (db.value.clone(), table.value.clone()),
+            _ => {
+                return Result::Err(ErrorCode::SyntaxException(format!(
+                    "Unsupported table name '{}'",
+                    show.name
+                )))
+            }
+        };
+        self.ctx.get_table(&db, &table)?;
+
+        self.build_from_sql(
+            "SELECT '' AS Table, 0 AS Non_unique, '' AS Key_name, 0 AS Seq_in_index, \
+             '' AS Column_name, '' AS Collation, 0 AS Cardinality, '' AS Sub_part, \
+             '' AS Packed, '' AS Null, '' AS Index_type, '' AS Comment, '' AS Index_comment \
+             FROM system.one LIMIT 0",
+        )
+    }
+
     /// Builds plan from AST statement.
     #[tracing::instrument(level = "info", skip(self, statement))]
     pub fn sql_statement_to_plan(&self, statement: &sqlparser::ast::Statement) -> Result<PlanNode> {
@@ -203,6 +275,12 @@ impl PlanParser {
         Ok(PlanNode::UseDatabase(UseDatabasePlan { db }))
     }
 
+    pub fn sql_kill_query_to_plan(&self, kill: &DfKillQuery) -> Result<PlanNode> {
+        Ok(PlanNode::KillQuery(KillQueryPlan {
+            query_id: kill.query_id.clone(),
+        }))
+    }
+
     #[tracing::instrument(level = "info", skip(self, create), fields(ctx.id = self.ctx.get_id().as_str()))]
     pub fn sql_create_table_to_plan(&self, create: &DfCreateTable) -> Result<PlanNode> {
         let mut db = self.ctx.get_current_database();
@@ -215,12 +293,48 @@ impl PlanParser {
             table = create.name.0[1].value.clone();
         }
 
+        let default_values = DefaultValues::create(self.ctx.clone());
         let fields = create
             .columns
             .iter()
             .map(|column| {
-                SQLCommon::make_data_type(&column.data_type)
-                    .map(|data_type| DataField::new(&column.name.value, data_type, false))
+                let data_type = SQLCommon::make_data_type(&column.data_type)?;
+                let default_expr = column.options.iter().find_map(|v| match &v.option {
+                    ColumnOption::Default(expr) => Some(expr.to_string()),
+                    _ => None,
+                });
+                let comment = column.options.iter().find_map(|v| match &v.option {
+                    ColumnOption::DialectSpecific(tokens) => match tokens.as_slice() {
+                        [Token::Word(w), Token::SingleQuotedString(comment)]
+                            if w.value == "COMMENT" =>
+                        {
+                            Some(comment.clone())
+                        }
+                        _ => None,
+                    },
+                    _ => None,
+                });
+                let collation = match &column.collation {
+                    None => None,
+                    Some(_) if data_type != DataType::Utf8 => {
+                        return Err(ErrorCode::BadArguments(format!(
+                            "COLLATE is only allowed for the string columns, but column {} is {}",
+                            column.name.value, data_type
+                        )));
+                    }
+                    Some(collation) => {
+                        let collation = DataCollation::try_create(&collation.to_string())?;
+                        Some(collation.name().to_string())
+                    }
+                };
+                let field = DataField::new(&column.name.value, data_type, false)
+                    .with_default_expr(default_expr)
+                    .with_comment(comment)
+                    .with_collation(collation);
+
+                // Rejects a default expression that can not be evaluated to the column type.
+                default_values.default_column(&field, 1)?;
+                Ok(field)
             })
             .collect::<Result<Vec<DataField>>>()?;
 
@@ -236,12 +350,29 @@ impl PlanParser {
         }
 
         let schema = DataSchemaRefExt::create(fields);
+        if let Some(partition_by) = &create.partition_by {
+            // Rejects a partition expression that can not be evaluated with the table columns.
+            let partition_by = partition_by.to_string();
+            PartitionBy::try_create(self.ctx.clone(), &partition_by, &schema)?;
+            options.insert("partition_by".to_string(), partition_by);
+        }
+        if let Some((column, buckets)) = &create.cluster_by {
+            let field = schema.field_with_name(&column.value)?;
+            if *buckets == 0 {
+                return Result::Err(ErrorCode::SyntaxException(
+                    "The number of buckets must be greater than 0",
+                ));
+            }
+            options.insert("cluster_by".to_string(), field.name().clone());
+            options.insert("buckets".to_string(), buckets.to_string());
+        }
+
         Ok(PlanNode::CreateTable(CreateTablePlan {
             if_not_exists: create.if_not_exists,
             db,
             table,
             schema,
-            engine: create.engine,
+            engine: create.engine.clone(),
             options,
         }))
     }
@@ -293,6 +424,7 @@ impl PlanParser {
             DataField::new("Field", DataType::Utf8, false),
             DataField::new("Type", DataType::Utf8, false),
             DataField::new("Null", DataType::Utf8, false),
+            DataField::new("Comment", DataType::Utf8, false),
         ]);
 
         Ok(PlanNode::DescribeTable(DescribeTablePlan {
@@ -321,6 +453,50 @@ impl PlanParser {
         }))
     }
 
+    /// DfTruncateTable to plan.
+    #[tracing::instrument(level = "info", skip(self, truncate), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_truncate_table_to_plan(&self, truncate: &DfTruncateTable) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if truncate.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Truncate table name is empty"));
+        }
+        let mut table = truncate.name.0[0].value.clone();
+        if truncate.name.0.len() > 1 {
+            db = table;
+            table = truncate.name.0[1].value.clone();
+        }
+        Ok(PlanNode::TruncateTable(TruncateTablePlan { db, table }))
+    }
+
+    /// DfDropPartition to plan.
+    #[tracing::instrument(level = "info", skip(self, drop), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_drop_partition_to_plan(&self, drop: &DfDropPartition) -> Result<PlanNode> {
+        let mut db = self.ctx.get_current_database();
+        if drop.name.0.is_empty() {
+            return Result::Err(ErrorCode::SyntaxException("Alter table name is empty"));
+        }
+        let mut table = drop.name.0[0].value.clone();
+        if drop.name.0.len() > 1 {
+            db = table;
+            table = drop.name.0[1].value.clone();
+        }
+
+        let partition = match self.sql_to_rex(&drop.partition, &DataSchema::empty(), None)? {
+            Expression::Literal { value, .. } => value,
+            other => {
+                return Result::Err(ErrorCode::SyntaxException(format!(
+                    "Partition must be a literal value, but got {:?}",
+                    other
+                )))
+            }
+        };
+        Ok(PlanNode::DropPartition(DropPartitionPlan {
+            db,
+            table,
+            partition,
+        }))
+    }
+
     #[tracing::instrument(level = "info", skip(self, table_name, columns, source), fields(ctx.id = self.ctx.get_id().as_str()))]
     fn insert_to_plan(
         &self,
@@ -337,7 +513,8 @@ impl PlanParser {
         }
         let table = self.ctx.get_datasource().get_table(&db_name, &tbl_name)?;
 
-        let mut schema = table.schema()?;
+        let table_schema = table.schema()?;
+        let mut schema = table_schema.clone();
 
         if !columns.is_empty() {
             let fields = columns
@@ -348,7 +525,8 @@ impl PlanParser {
             schema = DataSchemaRefExt::create(fields);
         }
 
-        let mut input_stream = futures::stream::iter::<Vec<DataBlock>>(vec![]);
+        let mut input_stream = futures::stream::iter::<Vec<Result<DataBlock>>>(vec![]);
+        let mut select_plan = None;
         if let Some(source) = source {
             if let sqlparser::ast::SetExpr::Values(vs) = &source.body {
                 let values = &vs.0;
@@ -367,6 +545,7 @@ impl PlanParser {
                     ));
                 }
                 // Buffers some chunks if possible
+                let default_values = DefaultValues::create(self.ctx.clone());
                 let chunks = values.chunks(100);
 
                 let blocks: Vec<DataBlock> = chunks
@@ -383,30 +562,147 @@ impl PlanParser {
                             })
                             .collect();
 
+                        // The values are cast to the column types, so that the blocks are in the table schema.
                         let cols = transposed
                             .iter()
-                            .map(|col| {
-                                Series::new(col.iter().map(|s| s as &str).collect::<Vec<&str>>())
+                            .zip(schema.fields().iter())
+                            .map(|(col, field)| {
+                                let series = Series::new(
+                                    col.iter().map(|s| s as &str).collect::<Vec<&str>>(),
+                                );
+                                DataColumn::Array(series).cast_with_type(field.data_type())
                             })
-                            .collect::<Vec<_>>();
+                            .collect::<Result<Vec<_>>>()?;
 
-                        DataBlock::create_by_array(schema.clone(), cols)
+                        // The omitted columns take their default values.
+                        let block = DataBlock::create(schema.clone(), cols);
+                        default_values.fill(&block, &table_schema)
                     })
-                    .collect();
-                input_stream = futures::stream::iter(blocks);
+                    .collect::<Result<_>>()?;
+                input_stream = futures::stream::iter(blocks.into_iter().map(Ok).collect());
+            } else {
+                select_plan = Some(Box::new(self.insert_select_to_plan(source, &schema)?));
             }
         }
 
         let plan_node = InsertIntoPlan {
             db_name,
             tbl_name,
-            schema,
+            schema: table_schema,
             // this is crazy, please do not keep it, I am just test driving apis
             input_stream: Arc::new(Mutex::new(Some(Box::pin(input_stream)))),
+            select_plan,
+            table_function: None,
+            table_args: vec![],
         };
         Ok(PlanNode::InsertInto(plan_node))
     }
 
+    /// Builds the insertion of the rows of a query into a writable table function.
+    pub fn sql_insert_into_function_to_plan(
+        &self,
+        insert: &DfInsertIntoFunction,
+    ) -> Result<PlanNode> {
+        let name = insert.name.value.to_lowercase();
+        // Make sure the table function exists before running the query.
+        self.ctx.get_datasource().get_table_function(&name)?;
+
+        let empty_schema = Arc::new(DataSchema::empty());
+        let table_args = insert
+            .args
+            .iter()
+            .map(|arg| self.sql_to_rex(arg, empty_schema.as_ref(), None))
+            .collect::<Result<Vec<_>>>()?;
+        let select_plan = self.query_to_plan(&insert.query)?;
+
+        Ok(PlanNode::InsertInto(InsertIntoPlan {
+            db_name: "".to_string(),
+            tbl_name: name.clone(),
+            schema: select_plan.schema(),
+            input_stream: InsertIntoPlan::empty_stream(),
+            select_plan: Some(Box::new(select_plan)),
+            table_function: Some(name),
+            table_args,
+        }))
+    }
+
+    /// DfCopy to plan, the columns of the file are those of the table if they are not given.
+    #[tracing::instrument(level = "info", skip(self, copy), fields(ctx.id = self.ctx.get_id().as_str()))]
+    pub fn sql_copy_to_plan(&self, copy: &DfCopy) -> Result<PlanNode> {
+        let mut db_name = self.ctx.get_current_database();
+        let mut tbl_name = copy.name.0[0].value.clone();
+        if copy.name.0.len() > 1 {
+            db_name = tbl_name;
+            tbl_name = copy.name.0[1].value.clone();
+        }
+
+        let table = self.ctx.get_table(&db_name, &tbl_name)?;
+        let mut schema = table.schema()?;
+        if !copy.columns.is_empty() {
+            let fields = copy
+                .columns
+                .iter()
+                .map(|ident| schema.field_with_name(&ident.value).map(|v| v.clone()))
+                .collect::<Result<Vec<_>>>()?;
+            schema = DataSchemaRefExt::create(fields);
+        }
+
+        let format = match &copy.format {
+            Some(format) => FileFormat::try_create(&format.value)?,
+            None => FileFormat::from_path(&copy.location)?,
+        };
+        if format != FileFormat::Csv {
+            return Result::Err(ErrorCode::UnImplement(format!(
+                "COPY does not support {:?} files yet, expected CSV",
+                format
+            )));
+        }
+
+        Ok(PlanNode::Copy(CopyPlan {
+            db_name,
+            tbl_name,
+            schema,
+            location: copy.location.clone(),
+            format: "CSV".to_string(),
+        }))
+    }
+
+    /// Builds the SELECT of an INSERT SELECT, whose output columns are cast to the inserted columns by position.
+    fn insert_select_to_plan(&self, query: &Query, schema: &DataSchemaRef) -> Result<PlanNode> {
+        let input = match self.query_to_plan(query)? {
+            PlanNode::Select(select) => select.input.as_ref().clone(),
+            other => other,
+        };
+
+        let select_schema = input.schema();
+        if select_schema.fields().len() != schema.fields().len() {
+            return Result::Err(ErrorCode::BadArguments(format!(
+                "INSERT has {} columns, but SELECT has {} columns",
+                schema.fields().len(),
+                select_schema.fields().len()
+            )));
+        }
+
+        let exprs = select_schema
+            .fields()
+            .iter()
+            .zip(schema.fields().iter())
+            .map(|(from, to)| {
+                Expression::Alias(
+                    to.name().clone(),
+                    Box::new(Expression::Cast {
+                        expr: Box::new(Expression::Column(from.name().clone())),
+                        data_type: to.data_type().clone(),
+                    }),
+                )
+            })
+            .collect::<Vec<_>>();
+        let plan = PlanBuilder::from(&input).project(&exprs)?.build()?;
+        Ok(PlanNode::Select(SelectPlan {
+            input: Arc::new(plan),
+        }))
+    }
+
     /// Generate a logic plan from an SQL query
     pub fn query_to_plan(&self, query: &sqlparser::ast::Query) -> Result<PlanNode> {
         if query.with.is_some() {
@@ -417,6 +713,7 @@ impl PlanParser {
             sqlparser::ast::SetExpr::Select(s) => {
                 self.select_to_plan(s.as_ref(), &query.limit, &query.offset, &query.order_by)
             }
+            sqlparser::ast::SetExpr::SetOperation { .. } => self.set_operation_to_plan(query),
             _ => Result::Err(ErrorCode::UnImplement(format!(
                 "Query {} is not yet implemented",
                 query.body
@@ -424,6 +721,75 @@ impl PlanParser {
         }
     }
 
+    /// Generate a logic plan from an SQL set operation, the ORDER BY and LIMIT of the query
+    /// apply to the rows of the set operation.
+    /// For example:
+    /// "select number from numbers(3) union all select number + 1 from numbers(3) order by number limit 3"
+    fn set_operation_to_plan(&self, query: &sqlparser::ast::Query) -> Result<PlanNode> {
+        let plan = self.set_expr_to_plan(&query.body)?;
+
+        let order_by_exprs = query
+            .order_by
+            .iter()
+            .map(|e| -> Result<Expression> {
+                Ok(Expression::Sort {
+                    expr: Box::new(self.sql_to_rex(&e.expr, &plan.schema(), None)?),
+                    asc: e.asc.unwrap_or(true),
+                    nulls_first: e.nulls_first.unwrap_or(true),
+                })
+            })
+            .collect::<Result<Vec<Expression>>>()?;
+
+        let plan = self.sort(&plan, &order_by_exprs)?;
+        let plan = self.limit(&plan, &query.limit, &query.offset, None)?;
+
+        Ok(PlanNode::Select(SelectPlan {
+            input: Arc::new(plan),
+        }))
+    }
+
+    fn set_expr_to_plan(&self, set_expr: &sqlparser::ast::SetExpr) -> Result<PlanNode> {
+        let plan = match set_expr {
+            sqlparser::ast::SetExpr::Select(s) => {
+                self.select_to_plan(s.as_ref(), &None, &None, &[])
+            }
+            sqlparser::ast::SetExpr::Query(q) => self.query_to_plan(q.as_ref()),
+            sqlparser::ast::SetExpr::SetOperation {
+                op: sqlparser::ast::SetOperator::Union,
+                all,
+                left,
+                right,
+            } => {
+                let left = self.set_expr_to_plan(left.as_ref())?;
+                let right = self.set_expr_to_plan(right.as_ref())?;
+                let plan = PlanBuilder::from(&left).union_all(&[right])?.build()?;
+                match all {
+                    true => Ok(plan),
+                    // UNION DISTINCT groups the rows of UNION ALL by all the columns.
+                    false => {
+                        let group_by_exprs = plan
+                            .schema()
+                            .fields()
+                            .iter()
+                            .map(|field| col(field.name()))
+                            .collect::<Vec<_>>();
+                        self.aggregate(&plan, &[], &group_by_exprs)
+                    }
+                }
+            }
+            other => Result::Err(ErrorCode::UnImplement(format!(
+                "Set operation {} is not yet implemented",
+                other
+            ))),
+        }?;
+
+        // The inputs of the set operations are not wrapped in the select plans.
+        match plan {
+            PlanNode::Select(plan) => Ok(plan.input.as_ref().clone()),
+            plan => Ok(plan),
+        }
+    }
+
     /// Generate a logic plan from an SQL select
     /// For example:
     /// "select sum(number+1)+2, number%3 as id from numbers(10) where number>1 group by id having id>1 order by id desc limit 3"
@@ -438,7 +804,7 @@ impl PlanParser {
         // Filter expression
         // In example: Filter=(number > 1)
         let plan = self
-            .plan_tables_with_joins(&select.from)
+            .plan_tables_with_joins(&select.from, &select.selection, select)
             .and_then(|input| self.filter(&input, &select.selection, Some(select)))?;
 
         // Projection expression
@@ -467,6 +833,13 @@ impl PlanParser {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        // GROUPING(...) resolved against the group by expressions
+        // In example: Projection=[grouping((number % 3))] to the value of the group
+        let projection_exprs = projection_exprs
+            .iter()
+            .map(|expr| resolve_grouping_exprs(expr, &group_by_exprs, &aliases))
+            .collect::<Result<Vec<_>>>()?;
+
         // Having Expression after against aliases
         // In example: Having=((number % 3) > 1)
         let having_expr_opt = select
@@ -475,6 +848,7 @@ impl PlanParser {
             .map::<Result<Expression>, _>(|having_expr| {
                 let having_expr = self.sql_to_rex(having_expr, &plan.schema(), Some(select))?;
                 let having_expr = resolve_aliases_to_exprs(&having_expr, &aliases)?;
+                let having_expr = resolve_grouping_exprs(&having_expr, &group_by_exprs, &aliases)?;
 
                 Ok(having_expr)
             })
@@ -488,7 +862,10 @@ impl PlanParser {
                 Ok(Expression::Sort {
                     expr: Box::new(
                         self.sql_to_rex(&e.expr, &plan.schema(), Some(select))
-                            .and_then(|expr| resolve_aliases_to_exprs(&expr, &aliases))?,
+                            .and_then(|expr| resolve_aliases_to_exprs(&expr, &aliases))
+                            .and_then(|expr| {
+                                resolve_grouping_exprs(&expr, &group_by_exprs, &aliases)
+                            })?,
                     ),
                     asc: e.asc.unwrap_or(true),
                     nulls_first: e.nulls_first.unwrap_or(true),
@@ -616,14 +993,43 @@ impl PlanParser {
         }
     }
 
-    fn plan_tables_with_joins(&self, from: &[sqlparser::ast::TableWithJoins]) -> Result<PlanNode> {
+    fn plan_tables_with_joins(
+        &self,
+        from: &[sqlparser::ast::TableWithJoins],
+        selection: &Option<sqlparser::ast::Expr>,
+        select: &sqlparser::ast::Select,
+    ) -> Result<PlanNode> {
         match from.len() {
             0 => self.plan_with_dummy_source(),
-            1 => self.plan_table_with_joins(&from[0]),
-            _ => Result::Err(ErrorCode::SyntaxException("Cannot support JOIN clause")),
+            1 => self.plan_table_with_joins(&from[0], selection, select),
+            _ => self.plan_cross_joins(from, select),
         }
     }
 
+    /// `FROM a, b, ...` is the cross join of the relations, the selection of the query filters
+    /// the joined rows, so it is not pushed down to the relations.
+    fn plan_cross_joins(
+        &self,
+        from: &[sqlparser::ast::TableWithJoins],
+        select: &sqlparser::ast::Select,
+    ) -> Result<PlanNode> {
+        let mut plan = self.plan_table_with_joins(&from[0], &None, select)?;
+        for t in &from[1..] {
+            let right = self.plan_table_with_joins(t, &None, select)?;
+            let right = match t.joins.is_empty() {
+                true => Self::qualify_join_columns(&plan.schema(), &t.relation, &right)?,
+                false => {
+                    let relation = TableFactor::NestedJoin(Box::new(t.clone()));
+                    Self::qualify_join_columns(&plan.schema(), &relation, &right)?
+                }
+            };
+            plan = PlanBuilder::from(&plan)
+                .nested_loop_join(JoinType::Inner, &right, None)?
+                .build()?;
+        }
+        Ok(plan)
+    }
+
     fn plan_with_dummy_source(&self) -> Result<PlanNode> {
         let db_name = "system";
         let table_name = "one";
@@ -632,7 +1038,7 @@ impl PlanParser {
             table
                 .schema()
                 .and_then(|ref schema| {
-                    PlanBuilder::scan(db_name, table_name, schema, None, None, None)
+                    PlanBuilder::scan(db_name, table_name, schema, None, vec![], None)
                 })
                 .and_then(|builder| builder.build())
                 .and_then(|dummy_scan_plan| match dummy_scan_plan {
@@ -648,11 +1054,235 @@ impl PlanParser {
         })
     }
 
-    fn plan_table_with_joins(&self, t: &sqlparser::ast::TableWithJoins) -> Result<PlanNode> {
-        self.create_relation(&t.relation)
+    fn plan_table_with_joins(
+        &self,
+        t: &sqlparser::ast::TableWithJoins,
+        selection: &Option<sqlparser::ast::Expr>,
+        select: &sqlparser::ast::Select,
+    ) -> Result<PlanNode> {
+        if t.joins.is_empty() {
+            return self.create_relation(&t.relation, selection, select);
+        }
+
+        // The selection may reference the columns of any relation of the joins, so it is not
+        // pushed down to the relations.
+        let mut plan = self.create_relation(&t.relation, &None, select)?;
+        for join in &t.joins {
+            plan = self.join_to_plan(&plan, join, select)?;
+        }
+        Ok(plan)
+    }
+
+    /// Joins the plan with the relation of the join by the equalities of the ON or USING clause,
+    /// the other predicates of the ON clause filter the joined rows. A join without such
+    /// equalities, e.g. a CROSS JOIN or a range join, and an outer join with other predicates are
+    /// joined by a nested loop on all the predicates.
+    fn join_to_plan(
+        &self,
+        left: &PlanNode,
+        join: &sqlparser::ast::Join,
+        select: &sqlparser::ast::Select,
+    ) -> Result<PlanNode> {
+        let (join_type, constraint) = match &join.join_operator {
+            JoinOperator::Inner(constraint) => (JoinType::Inner, Some(constraint)),
+            JoinOperator::LeftOuter(constraint) => (JoinType::Left, Some(constraint)),
+            JoinOperator::RightOuter(constraint) => (JoinType::Right, Some(constraint)),
+            JoinOperator::FullOuter(constraint) => (JoinType::Full, Some(constraint)),
+            JoinOperator::CrossJoin => (JoinType::Inner, None),
+            other => {
+                return Result::Err(ErrorCode::UnImplement(format!(
+                    "JOIN {:?} is not yet implemented",
+                    other
+                )))
+            }
+        };
+
+        let right = self.create_relation(&join.relation, &None, select)?;
+        let right = Self::qualify_join_columns(&left.schema(), &join.relation, &right)?;
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+
+        let mut conditions = vec![];
+        match constraint {
+            None => {}
+            Some(JoinConstraint::On(expr)) => {
+                let mut fields = left_schema.fields().clone();
+                fields.extend_from_slice(right_schema.fields());
+                let schema = DataSchema::new(fields);
+                let expr = self.sql_to_rex(expr, &schema, Some(select))?;
+                conditions = RewriteHelper::split_conjunctions(&expr);
+            }
+            Some(JoinConstraint::Using(idents)) => {
+                let qualifier = Self::relation_qualifier(&join.relation);
+                for ident in idents {
+                    let name = ident.value.clone();
+                    let right_name = match (right_schema.field_with_name(&name), &qualifier) {
+                        (Err(_), Some(qualifier)) => format!("{}.{}", qualifier, name),
+                        _ => name.clone(),
+                    };
+                    conditions.push(Expression::Column(name).eq(Expression::Column(right_name)));
+                }
+            }
+            Some(other) => {
+                return Result::Err(ErrorCode::UnImplement(format!(
+                    "JOIN constraint {:?} is not yet implemented",
+                    other
+                )))
+            }
+        }
+
+        let mut left_keys = vec![];
+        let mut right_keys = vec![];
+        let mut residual: Option<Expression> = None;
+        for condition in conditions.iter() {
+            match Self::join_keys(condition, &left_schema, &right_schema)? {
+                Some((left_key, right_key)) => {
+                    left_keys.push(left_key);
+                    right_keys.push(right_key);
+                }
+                None => {
+                    residual = Some(match residual {
+                        Some(residual) => residual.and(condition.clone()),
+                        None => condition.clone(),
+                    })
+                }
+            }
+        }
+
+        // The unmatched rows of an outer join are output whatever the other predicates are, so
+        // they can not filter the joined rows, and are evaluated with the equalities instead.
+        if left_keys.is_empty() || (residual.is_some() && join_type != JoinType::Inner) {
+            let condition = conditions.into_iter().reduce(|left, right| left.and(right));
+            return PlanBuilder::from(left)
+                .nested_loop_join(join_type, &right, condition)?
+                .build();
+        }
+
+        let builder = PlanBuilder::from(left).join(join_type, &right, &left_keys, &right_keys)?;
+        match residual {
+            Some(residual) => builder.filter(residual)?.build(),
+            None => builder.build(),
+        }
     }
 
-    fn create_relation(&self, relation: &sqlparser::ast::TableFactor) -> Result<PlanNode> {
+    /// The name which qualifies the columns of the relation, the alias or the name of the table.
+    fn relation_qualifier(relation: &TableFactor) -> Option<String> {
+        match relation {
+            TableFactor::Table {
+                alias: Some(alias), ..
+            } => Some(alias.name.value.clone()),
+            TableFactor::Derived {
+                alias: Some(alias), ..
+            } => Some(alias.name.value.clone()),
+            TableFactor::Table { name, .. } => name.0.last().map(|ident| ident.value.clone()),
+            _ => None,
+        }
+    }
+
+    fn collect_relation_qualifiers(relation: &TableFactor, qualifiers: &mut Vec<String>) {
+        match relation {
+            TableFactor::NestedJoin(table_with_joins) => {
+                Self::collect_relation_qualifiers(&table_with_joins.relation, qualifiers);
+                for join in &table_with_joins.joins {
+                    Self::collect_relation_qualifiers(&join.relation, qualifiers);
+                }
+            }
+            other => qualifiers.extend(Self::relation_qualifier(other)),
+        }
+    }
+
+    /// Renames the columns of the right side of a join whose names are already in the left side
+    /// to `qualifier.column`, e.g. `id` of `a JOIN b ON a.id = b.id` is renamed to `b.id`.
+    fn qualify_join_columns(
+        left: &DataSchema,
+        relation: &TableFactor,
+        right: &PlanNode,
+    ) -> Result<PlanNode> {
+        let right_schema = right.schema();
+        let clashed = |name: &String| left.field_with_name(name).is_ok();
+        if !right_schema.fields().iter().any(|f| clashed(f.name())) {
+            return Ok(right.clone());
+        }
+
+        let qualifier = Self::relation_qualifier(relation).ok_or_else(|| {
+            ErrorCode::SyntaxException(format!(
+                "The relation of JOIN must have an alias: {}",
+                relation
+            ))
+        })?;
+        let exprs = right_schema
+            .fields()
+            .iter()
+            .map(|f| match clashed(f.name()) {
+                true => Expression::Alias(
+                    format!("{}.{}", qualifier, f.name()),
+                    Box::new(Expression::Column(f.name().clone())),
+                ),
+                false => Expression::Column(f.name().clone()),
+            })
+            .collect::<Vec<_>>();
+        PlanBuilder::from(right).project(&exprs)?.build()
+    }
+
+    /// The keys of the equality if one of its sides only references the columns of the left
+    /// input and the other only the columns of the right input, the keys are cast to their common
+    /// type if their types are different.
+    fn join_keys(
+        condition: &Expression,
+        left_schema: &DataSchemaRef,
+        right_schema: &DataSchemaRef,
+    ) -> Result<Option<(Expression, Expression)>> {
+        let references_only = |expr: &Expression, schema: &DataSchemaRef| {
+            let columns = find_column_exprs(&[expr.clone()]);
+            !columns.is_empty()
+                && columns.iter().all(|column| match column {
+                    Expression::Column(name) => schema.field_with_name(name).is_ok(),
+                    _ => false,
+                })
+        };
+
+        let (left_key, right_key) = match condition {
+            Expression::BinaryExpression { op, left, right } if op == "=" => {
+                if references_only(left, left_schema) && references_only(right, right_schema) {
+                    (left.as_ref().clone(), right.as_ref().clone())
+                } else if references_only(right, left_schema) && references_only(left, right_schema)
+                {
+                    (right.as_ref().clone(), left.as_ref().clone())
+                } else {
+                    return Ok(None);
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        let left_type = left_key.to_data_type(left_schema)?;
+        let right_type = right_key.to_data_type(right_schema)?;
+        if left_type == right_type {
+            return Ok(Some((left_key, right_key)));
+        }
+
+        let data_type = equal_coercion(&left_type, &right_type)?;
+        let cast = |expr: Expression, expr_type: &DataType| match expr_type == &data_type {
+            true => expr,
+            false => Expression::Cast {
+                expr: Box::new(expr),
+                data_type: data_type.clone(),
+            },
+        };
+        Ok(Some((
+            cast(left_key, &left_type),
+            cast(right_key, &right_type),
+        )))
+    }
+
+    /// Creates the read plan of a relation, the `selection` of the query is pushed down to the scan
+    /// of a table, e.g. for pruning the partitions of the table.
+    fn create_relation(
+        &self,
+        relation: &sqlparser::ast::TableFactor,
+        selection: &Option<sqlparser::ast::Expr>,
+        select: &sqlparser::ast::Select,
+    ) -> Result<PlanNode> {
         match relation {
             TableFactor::Table { name, args, .. } => {
                 let mut db_name = self.ctx.get_current_database();
@@ -661,8 +1291,9 @@ impl PlanParser {
                     db_name = name.0[0].to_string();
                     table_name = name.0[1].to_string();
                 }
-                let mut table_args = None;
+                let mut table_args = vec![];
                 let table: Arc<dyn Table>;
+                let schema: DataSchemaRef;
 
                 // only table functions has table args
                 if !args.is_empty() {
@@ -673,58 +1304,108 @@ impl PlanParser {
                     }
 
                     let empty_schema = Arc::new(DataSchema::empty());
-                    match &args[0] {
-                        FunctionArg::Named { arg, .. } => {
-                            table_args = Some(self.sql_to_rex(arg, empty_schema.as_ref(), None)?);
-                        }
-                        FunctionArg::Unnamed(arg) => {
-                            table_args = Some(self.sql_to_rex(arg, empty_schema.as_ref(), None)?);
-                        }
+                    for arg in args {
+                        let arg = match arg {
+                            FunctionArg::Named { arg, .. } => arg,
+                            FunctionArg::Unnamed(arg) => arg,
+                        };
+                        table_args.push(self.sql_to_rex(arg, empty_schema.as_ref(), None)?);
                     }
 
                     let table_function = self.ctx.get_table_function(&table_name)?;
                     table_name = table_function.name().to_string();
                     db_name = table_function.db().to_string();
+                    schema = table_function.schema_with_args(self.ctx.clone(), &table_args)?;
                     table = table_function.as_table();
                 } else {
                     table = self.ctx.get_table(&db_name, table_name.as_str())?;
+                    schema = table.schema()?;
                 }
 
-                let scan = {
-                    table.schema().and_then(|schema| {
-                        PlanBuilder::scan(
-                            &db_name,
-                            &table_name,
-                            schema.as_ref(),
-                            None,
-                            table_args,
-                            None,
-                        )
-                        .and_then(|builder| builder.build())
-                    })
+                let scan = PlanBuilder::scan(
+                    &db_name,
+                    &table_name,
+                    schema.as_ref(),
+                    None,
+                    table_args,
+                    None,
+                )
+                .and_then(|builder| builder.build());
+
+                // The selection is pushed down to the tables which prune with it, e.g. the partitioned
+                // tables, it is still applied by the filter on top of the scan. A selection that can
+                // not be resolved with the table columns only, e.g., referencing an alias, is not
+                // pushed down.
+                let filters = match (table.prunes_by_filters(), selection) {
+                    (true, Some(expr)) => self
+                        .sql_to_rex(expr, schema.as_ref(), None)
+                        .ok()
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                    _ => vec![],
                 };
 
                 // TODO: Move ReadSourcePlan to SelectInterpreter
                 let partitions = self.ctx.get_settings().get_max_threads()? as usize;
                 scan.and_then(|scan| match scan {
-                    PlanNode::Scan(ref scan) => table
-                        .read_plan(self.ctx.clone(), scan, partitions)
-                        .map(PlanNode::ReadSource),
+                    PlanNode::Scan(mut scan) => {
+                        scan.push_downs.filters = filters;
+                        table
+                            .read_plan(self.ctx.clone(), &scan, partitions)
+                            .map(PlanNode::ReadSource)
+                    }
                     _unreachable_plan => panic!("Logical error: Cannot downcast to scan plan"),
                 })
             }
             TableFactor::Derived { subquery, .. } => self.query_to_plan(subquery),
             TableFactor::NestedJoin(table_with_joins) => {
-                self.plan_table_with_joins(table_with_joins)
+                self.plan_table_with_joins(table_with_joins, selection, select)
             }
             TableFactor::TableFunction { .. } => {
                 Result::Err(ErrorCode::UnImplement("Unsupported table function"))
             }
         }
     }
+
+    /// The server variable `@@name` or `@@session.name`, the column is named as it is written.
+    fn process_variable(&self, ids: &[Ident]) -> Result<Expression> {
+        let column_name = ids
+            .iter()
+            .map(|id| id.value.clone())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let name = match ids {
+            [id] => &id.value[2..],
+            [scope, id] => match scope.value.to_lowercase().as_str() {
+                "@@session" | "@@global" | "@@local" => id.value.as_str(),
+                _ => {
+                    return Err(ErrorCode::UnImplement(format!(
+                        "Unsupported variable scope '{}'",
+                        scope.value
+                    )))
+                }
+            },
+            _ => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Unsupported variable '{}'",
+                    column_name
+                )))
+            }
+        };
+
+        Ok(Expression::Literal {
+            value: ContextVariable::get(name, self.ctx.clone())?,
+            column_name: Some(column_name),
+        })
+    }
+
+    /// The column `table.column` of the relations of the query, it is the renamed column
+    /// `table.column` if the column is renamed by a join, see `qualify_join_columns`.
     fn process_compound_ident(
         &self,
         ids: &[Ident],
+        schema: &DataSchema,
         select: Option<&sqlparser::ast::Select>,
     ) -> Result<Expression> {
         let mut var_names = vec![];
@@ -740,62 +1421,31 @@ impl PlanParser {
 
         let table_name = &var_names[0];
         let from = &select.unwrap().from;
-        let obj_table_name = ObjectName(vec![Ident::new(table_name)]);
-
-        match from.len() {
-            0 => Err(ErrorCode::SyntaxException(
+        if from.is_empty() {
+            return Err(ErrorCode::SyntaxException(
                 "Missing table in the select clause",
-            )),
-            1 => match &from[0].relation {
-                TableFactor::Table {
-                    name,
-                    alias,
-                    args: _,
-                    with_hints: _,
-                } => {
-                    if *name == obj_table_name {
-                        return Ok(Expression::Column(var_names.pop().unwrap()));
-                    }
-                    match alias {
-                        Some(a) => {
-                            if a.name == ids[0] {
-                                Ok(Expression::Column(var_names.pop().unwrap()))
-                            } else {
-                                Err(ErrorCode::UnknownTable(format!(
-                                    "Unknown Table '{:?}'",
-                                    &table_name,
-                                )))
-                            }
-                        }
-                        None => Err(ErrorCode::UnknownTable(format!(
-                            "Unknown Table '{:?}'",
-                            &table_name,
-                        ))),
-                    }
-                }
-                TableFactor::Derived {
-                    lateral: _,
-                    subquery: _,
-                    alias,
-                } => match alias {
-                    Some(a) => {
-                        if a.name == ids[0] {
-                            Ok(Expression::Column(var_names.pop().unwrap()))
-                        } else {
-                            Err(ErrorCode::UnknownTable(format!(
-                                "Unknown Table '{:?}'",
-                                &table_name,
-                            )))
-                        }
-                    }
-                    None => Err(ErrorCode::UnknownTable(format!(
-                        "Unknown Table '{:?}'",
-                        &table_name,
-                    ))),
-                },
-                _ => Err(ErrorCode::SyntaxException("Cannot support Nested Join now")),
-            },
-            _ => Err(ErrorCode::SyntaxException("Cannot support JOIN clause")),
+            ));
+        }
+
+        let qualified_name = var_names.join(".");
+        if schema.field_with_name(&qualified_name).is_ok() {
+            return Ok(Expression::Column(qualified_name));
+        }
+
+        let mut qualifiers = vec![];
+        for table_with_joins in from {
+            Self::collect_relation_qualifiers(&table_with_joins.relation, &mut qualifiers);
+            for join in &table_with_joins.joins {
+                Self::collect_relation_qualifiers(&join.relation, &mut qualifiers);
+            }
+        }
+
+        match qualifiers.contains(table_name) {
+            true => Ok(Expression::Column(var_names.pop().unwrap())),
+            false => Err(ErrorCode::UnknownTable(format!(
+                "Unknown Table '{:?}'",
+                &table_name,
+            ))),
         }
     }
 
@@ -839,6 +1489,9 @@ impl PlanParser {
 
         match expr {
             sqlparser::ast::Expr::Value(value) => value_to_rex(value),
+            sqlparser::ast::Expr::Identifier(ref v) if v.value.starts_with("@@") => {
+                self.process_variable(&[v.clone()])
+            }
             sqlparser::ast::Expr::Identifier(ref v) => Ok(Expression::Column(v.clone().value)),
             sqlparser::ast::Expr::BinaryOp { left, op, right } => {
                 Ok(Expression::BinaryExpression {
@@ -856,9 +1509,16 @@ impl PlanParser {
                 args: vec![self.subquery_to_rex(q)?],
             }),
             sqlparser::ast::Expr::Subquery(q) => Ok(self.scalar_subquery_to_rex(q)?),
+            sqlparser::ast::Expr::IsNull(e) => Ok(self.sql_to_rex(e, schema, select)?.is_null()),
+            sqlparser::ast::Expr::IsNotNull(e) => {
+                Ok(self.sql_to_rex(e, schema, select)?.is_not_null())
+            }
             sqlparser::ast::Expr::Nested(e) => self.sql_to_rex(e, schema, select),
+            sqlparser::ast::Expr::CompoundIdentifier(ids) if ids[0].value.starts_with("@@") => {
+                self.process_variable(ids.as_slice())
+            }
             sqlparser::ast::Expr::CompoundIdentifier(ids) => {
-                self.process_compound_ident(ids.as_slice(), select)
+                self.process_compound_ident(ids.as_slice(), schema, select)
             }
             sqlparser::ast::Expr::Function(e) => {
                 let mut args = Vec::with_capacity(e.args.len());
@@ -997,6 +1657,9 @@ impl PlanParser {
             let variable = variable.value.clone();
             let value = match value {
                 sqlparser::ast::SetVariableValue::Ident(v) => v.value.clone(),
+                sqlparser::ast::SetVariableValue::Literal(
+                    sqlparser::ast::Value::SingleQuotedString(v),
+                ) => v.clone(),
                 sqlparser::ast::SetVariableValue::Literal(v) => v.to_string(),
             };
             vars.push(VarValue { variable, value });
@@ -1004,13 +1667,48 @@ impl PlanParser {
         Ok(PlanNode::SetVariable(SettingPlan { vars }))
     }
 
-    /// Apply a filter to the plan
+    /// Apply a filter to the plan, the `IN (subquery)` conjunctions of the predicate are the semi
+    /// joins with the subqueries, and the `NOT IN (subquery)` ones are the anti joins.
     fn filter(
         &self,
         plan: &PlanNode,
         predicate: &Option<sqlparser::ast::Expr>,
         select: Option<&sqlparser::ast::Select>,
     ) -> Result<PlanNode> {
+        let mut conjunctions = vec![];
+        if let Some(predicate_expr) = predicate {
+            Self::split_sql_conjunctions(predicate_expr, &mut conjunctions);
+        }
+        if conjunctions
+            .iter()
+            .any(|expr| matches!(expr, Expr::InSubquery { .. }))
+        {
+            let mut plan = plan.clone();
+            let mut filter_expr: Option<Expression> = None;
+            for conjunction in conjunctions {
+                match conjunction {
+                    Expr::InSubquery {
+                        expr,
+                        subquery,
+                        negated,
+                    } => {
+                        plan = self.in_subquery_to_join(&plan, expr, subquery, *negated, select)?
+                    }
+                    other => {
+                        let expr = self.sql_to_rex(other, &plan.schema(), select)?;
+                        filter_expr = Some(match filter_expr {
+                            Some(filter_expr) => filter_expr.and(expr),
+                            None => expr,
+                        });
+                    }
+                }
+            }
+            return match filter_expr {
+                Some(filter_expr) => PlanBuilder::from(&plan).filter(filter_expr)?.build(),
+                None => Ok(plan),
+            };
+        }
+
         match *predicate {
             Some(ref predicate_expr) => self
                 .sql_to_rex(predicate_expr, &plan.schema(), select)
@@ -1023,6 +1721,72 @@ impl PlanParser {
         }
     }
 
+    fn split_sql_conjunctions<'a>(expr: &'a Expr, conjunctions: &mut Vec<&'a Expr>) {
+        match expr {
+            Expr::BinaryOp {
+                left,
+                op: BinaryOperator::And,
+                right,
+            } => {
+                Self::split_sql_conjunctions(left, conjunctions);
+                Self::split_sql_conjunctions(right, conjunctions);
+            }
+            other => conjunctions.push(other),
+        }
+    }
+
+    /// Semi joins the plan with the subquery by the equality of the expression and the column of
+    /// the subquery, or anti joins them if the IN is negated.
+    fn in_subquery_to_join(
+        &self,
+        plan: &PlanNode,
+        expr: &Expr,
+        subquery: &Query,
+        negated: bool,
+        select: Option<&sqlparser::ast::Select>,
+    ) -> Result<PlanNode> {
+        let left_schema = plan.schema();
+        let right = self.query_to_plan(subquery)?;
+        let right_schema = right.schema();
+        if right_schema.fields().len() != 1 {
+            return Result::Err(ErrorCode::SyntaxException(format!(
+                "The subquery of IN must return one column, but got {} columns: {}",
+                right_schema.fields().len(),
+                subquery
+            )));
+        }
+
+        let left_key = self.sql_to_rex(expr, &left_schema, select)?;
+        let right_field = right_schema.field(0);
+        let condition = left_key.eq(Expression::Column(right_field.name().clone()));
+        let (left_key, right_key) = match Self::join_keys(&condition, &left_schema, &right_schema)?
+        {
+            Some(keys) => keys,
+            None => {
+                return Result::Err(ErrorCode::UnImplement(format!(
+                    "IN subquery of the expression without the columns of the table is not yet implemented: {}",
+                    expr
+                )))
+            }
+        };
+
+        // NOT IN is NULL rather than true if the value or any value of the subquery is NULL, so
+        // it is the anti join only if neither is nullable.
+        let join_type = match negated {
+            false => JoinType::Semi,
+            true if left_key.nullable(&left_schema)? || right_field.is_nullable() => {
+                return Result::Err(ErrorCode::UnImplement(format!(
+                    "NOT IN subquery of the nullable values is not yet implemented: {}",
+                    expr
+                )))
+            }
+            true => JoinType::Anti,
+        };
+        PlanBuilder::from(plan)
+            .join(join_type, &right, &[left_key], &[right_key])?
+            .build()
+    }
+
     /// Apply a having to the plan
     fn having(&self, plan: &PlanNode, expr: Option<Expression>) -> Result<PlanNode> {
         if let Some(expr) = expr {
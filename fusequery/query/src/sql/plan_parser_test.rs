@@ -44,15 +44,33 @@ fn test_plan_parser() -> Result<()> {
         Test {
             name: "create-table-passed",
             sql: "CREATE TABLE t(c1 int, c2 bigint, c3 varchar(255) ) ENGINE = Parquet location = 'foo.parquet' ",
-            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false }, DataField { name: \"c2\", data_type: Int64, nullable: false }, DataField { name: \"c3\", data_type: Utf8, nullable: false }, engine: Parquet, if_not_exists:false, option: {\"location\": \"foo.parquet\"}",
+            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false, default_expr: None, comment: None, collation: None }, DataField { name: \"c2\", data_type: Int64, nullable: false, default_expr: None, comment: None, collation: None }, DataField { name: \"c3\", data_type: Utf8, nullable: false, default_expr: None, comment: None, collation: None }, engine: Parquet, if_not_exists:false, option: {\"location\": \"foo.parquet\"}",
             error: "",
         },
         Test {
             name: "create-table-if-not-exists-passed",
             sql: "CREATE TABLE IF NOT EXISTS t(c1 int, c2 bigint, c3 varchar(255) ) ENGINE = Parquet location = 'foo.parquet' ",
-            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false }, DataField { name: \"c2\", data_type: Int64, nullable: false }, DataField { name: \"c3\", data_type: Utf8, nullable: false }, engine: Parquet, if_not_exists:true, option: {\"location\": \"foo.parquet\"}",
+            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false, default_expr: None, comment: None, collation: None }, DataField { name: \"c2\", data_type: Int64, nullable: false, default_expr: None, comment: None, collation: None }, DataField { name: \"c3\", data_type: Utf8, nullable: false, default_expr: None, comment: None, collation: None }, engine: Parquet, if_not_exists:true, option: {\"location\": \"foo.parquet\"}",
             error: "",
         },
+        Test {
+            name: "create-table-collate-passed",
+            sql: "CREATE TABLE t(c1 int, c2 varchar COLLATE utf8mb4_general_ci) ENGINE = Memory",
+            expect: "Create table default.t DataField { name: \"c1\", data_type: Int32, nullable: false, default_expr: None, comment: None, collation: None }, DataField { name: \"c2\", data_type: Utf8, nullable: false, default_expr: None, comment: None, collation: Some(\"utf8_general_ci\") }, engine: Memory, if_not_exists:false, option: {}",
+            error: "",
+        },
+        Test {
+            name: "create-table-collate-unknown-collation",
+            sql: "CREATE TABLE t(c1 varchar COLLATE latin1_swedish_ci) ENGINE = Memory",
+            expect: "",
+            error: "Code: 6, displayText = Unknown collation: latin1_swedish_ci.",
+        },
+        Test {
+            name: "create-table-collate-not-string",
+            sql: "CREATE TABLE t(c1 int COLLATE utf8_general_ci) ENGINE = Memory",
+            expect: "",
+            error: "Code: 6, displayText = COLLATE is only allowed for the string columns, but column c1 is Int32.",
+        },
         Test {
             name: "drop-table-passed",
             sql: "DROP TABLE t1",
@@ -101,6 +119,18 @@ fn test_plan_parser() -> Result<()> {
             expect: "",
             error: "Code: 26, displayText = Column `number` is not under aggregate function and not in GROUP BY: While processing [(number + 1), (number + 3)].",
         },
+        Test {
+            name: "grouping-without-group-by",
+            sql: "select grouping(number) from numbers(10)",
+            expect: "",
+            error: "Code: 26, displayText = grouping(number) is only allowed with GROUP BY.",
+        },
+        Test {
+            name: "grouping-not-in-group-by",
+            sql: "select number % 3 as id, grouping(id, number) from numbers(10) group by id",
+            expect: "",
+            error: "Code: 26, displayText = Argument `number` of grouping is not in GROUP BY.",
+        },
         Test {
             name: "unsupported-function",
             sql: "select unsupported()",
@@ -138,7 +168,7 @@ fn test_plan_parser() -> Result<()> {
             error: "Code: 25, displayText = Unknown table: 't'.",
         },
         Test {
-            name: "insert-subquery-not-supported",
+            name: "insert-select-unknown-table",
             sql: "insert into t select * from t",
             expect: "",
             error: "Code: 25, displayText = Unknown table: 't'.",
@@ -159,7 +189,191 @@ fn test_plan_parser() -> Result<()> {
             \n                  ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
             error: "",
         },
+        Test {
+            name: "select-join",
+            sql: "select a.number, b.number from numbers(10) as a join numbers(10) as b on a.number = b.number and b.number > 1",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Filter: (b.number > 1)\
+            \n    Join: INNER, on: [number = b.number]\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n      Projection: number as b.number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "select-join-using",
+            sql: "select * from numbers(10) as a join numbers(10) as b using (number)",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Join: INNER, on: [number = b.number]\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "select-left-join",
+            sql: "select a.number, b.number from numbers(10) as a left join numbers(5) as b on a.number = b.number",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Join: LEFT OUTER, on: [number = b.number]\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 5, read_bytes: 40]",
+            error: "",
+        },
+        Test {
+            name: "select-full-join-using",
+            sql: "select * from numbers(10) as a full outer join numbers(10) as b using (number)",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Join: FULL OUTER, on: [number = b.number]\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "select-right-join-with-predicates",
+            sql: "select * from numbers(10) as a right join numbers(10) as b on a.number = b.number and b.number > 1",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Join: RIGHT OUTER, condition: ((number = b.number) and (b.number > 1))\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "select-join-without-equality",
+            sql: "select * from numbers(10) as a join numbers(10) as b on a.number > b.number",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Join: INNER, condition: (number > b.number)\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]",
+            error: "",
+        },
+        Test {
+            name: "select-cross-join",
+            sql: "select a.number, b.number from numbers(10) as a cross join numbers(5) as b",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Join: INNER, condition: true\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number as b.number:UInt64\
+            \n      ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 5, read_bytes: 40]",
+            error: "",
+        },
+        Test {
+            name: "select-comma-join",
+            sql: "select a.number, b.number from numbers(10) as a, numbers(5) as b where a.number < b.number",
+            expect: "\
+            Projection: number:UInt64, b.number:UInt64\
+            \n  Filter: (number < b.number)\
+            \n    Join: INNER, condition: true\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n      Projection: number as b.number:UInt64\
+            \n        ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 5, read_bytes: 40]",
+            error: "",
+        },
+        Test {
+            name: "select-join-with-non-boolean-condition",
+            sql: "select * from numbers(10) as a join numbers(10) as b on a.number + b.number",
+            expect: "",
+            error: "Code: 6, displayText = The condition of JOIN must be Boolean, but got (number + b.number) UInt64.",
+        },
+        Test {
+            name: "select-join-unknown-table",
+            sql: "select c.number from numbers(10) as a join numbers(10) as b on a.number = b.number",
+            expect: "",
+            error: "Code: 25, displayText = Unknown Table '\"c\"'.",
+        },
+        Test {
+            name: "select-in-subquery",
+            sql: "select number from numbers(10) where number in (select number from numbers(5)) and number > 1",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Filter: (number > 1)\
+            \n    Join: LEFT SEMI, on: [number = number]\
+            \n      ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n      Projection: number:UInt64\
+            \n        ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 5, read_bytes: 40]",
+            error: "",
+        },
+        Test {
+            name: "select-not-in-subquery",
+            sql: "select number from numbers(10) where number not in (select number from numbers(5))",
+            expect: "\
+            Projection: number:UInt64\
+            \n  Join: LEFT ANTI, on: [number = number]\
+            \n    ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n    Projection: number:UInt64\
+            \n      ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 5, read_bytes: 40]",
+            error: "",
+        },
+        Test {
+            name: "select-in-subquery-of-columns",
+            sql: "select number from numbers(10) where number in (select number, number + 1 from numbers(5))",
+            expect: "",
+            error: "Code: 5, displayText = The subquery of IN must return one column, but got 2 columns: SELECT number, number + 1 FROM numbers(5).",
+        },
+        Test {
+            name: "select-union-all",
+            sql: "select number from numbers(10) union all select number + 1 as number from numbers(5) order by number limit 3",
+            expect: "\
+            Limit: 3\
+            \n  Sort: number:UInt64\
+            \n    Union All\
+            \n      Projection: number:UInt64\
+            \n        ReadDataSource: scan partitions: [8], scan schema: [number:UInt64], statistics: [read_rows: 10, read_bytes: 80]\
+            \n      Projection: (number + 1) as number:UInt64\
+            \n        Expression: (number + 1):UInt64 (Before Projection)\
+            \n          ReadDataSource: scan partitions: [1], scan schema: [number:UInt64], statistics: [read_rows: 5, read_bytes: 40]",
+            error: "",
+        },
+        Test {
+            name: "select-union-distinct",
+            sql: "select 1 union select 2",
+            expect: "\
+            AggregatorFinal: groupBy=[[1]], aggr=[[]]\
+            \n  AggregatorPartial: groupBy=[[1]], aggr=[[]]\
+            \n    Union All\
+            \n      Projection: 1:UInt8\
+            \n        Expression: 1:UInt8 (Before Projection)\
+            \n          ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]\
+            \n      Projection: 2:UInt8\
+            \n        Expression: 2:UInt8 (Before Projection)\
+            \n          ReadDataSource: scan partitions: [1], scan schema: [dummy:UInt8], statistics: [read_rows: 1, read_bytes: 1]",
+            error: "",
+        },
+        Test {
+            name: "select-union-all-columns-mismatch",
+            sql: "select 1, 2 union all select 3",
+            expect: "",
+            error: "Code: 6, displayText = The inputs of UNION must have the same number of columns, but 2 != 1.",
+        },
 
+        Test {
+            name: "select-unknown-variable",
+            sql: "select @@not_exists",
+            expect: "",
+            error: "Code: 20, displayText = Unknown variable: \"not_exists\".",
+        },
+        Test {
+            name: "select-variable-unknown-scope",
+            sql: "select @@persist.version",
+            expect: "",
+            error: "Code: 2, displayText = Unsupported variable scope '@@persist'.",
+        },
+        Test {
+            name: "show-keys-unknown-table",
+            sql: "show keys from not_exists",
+            expect: "",
+            error: "Code: 25, displayText = Unknown table: 'not_exists'.",
+        },
         Test {
             name: "unimplemented-cte",
             sql: "with t as ( select sum(number) n from system.numbers_mt(1000) )select * from t",
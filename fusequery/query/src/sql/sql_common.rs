@@ -5,8 +5,11 @@
 use std::str::FromStr;
 
 use common_arrow::arrow::datatypes::TimeUnit;
+use common_datavalues::check_decimal_type;
 use common_datavalues::DataType;
 use common_datavalues::DataValue;
+use common_datavalues::DECIMAL_DEFAULT_PRECISION;
+use common_datavalues::DECIMAL_DEFAULT_SCALE;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_planners::Expression;
@@ -27,7 +30,12 @@ impl SQLCommon {
             SQLDataType::Varchar(_) => Ok(DataType::Utf8),
             SQLDataType::String => Ok(DataType::Utf8),
             SQLDataType::Text => Ok(DataType::Utf8),
-            SQLDataType::Decimal(_, _) => Ok(DataType::Float64),
+            SQLDataType::Decimal(precision, scale) => {
+                let precision = precision.map_or(DECIMAL_DEFAULT_PRECISION, |p| p as usize);
+                let scale = scale.map_or(DECIMAL_DEFAULT_SCALE, |s| s as usize);
+                check_decimal_type(precision, scale)?;
+                Ok(DataType::Decimal(precision, scale))
+            }
             SQLDataType::Float(_) => Ok(DataType::Float32),
             SQLDataType::Real | SQLDataType::Double => Ok(DataType::Float64),
             SQLDataType::Boolean => Ok(DataType::Boolean),
@@ -51,6 +59,7 @@ impl SQLCommon {
                     "FLOAT32" => Ok(DataType::Float32),
                     "FLOAT64" => Ok(DataType::Float64),
                     "STRING" => Ok(DataType::Utf8),
+                    "DATETIME" => Ok(DataType::Date64),
 
                     _ => Result::Err(ErrorCode::IllegalDataType(format!(
                         "The SQL data type {:?} is not implemented",
@@ -65,7 +74,7 @@ impl SQLCommon {
         }
     }
 
-    /// INTERVAL '3' MONTH
+    /// INTERVAL '3' MONTH or INTERVAL '3 MONTH'
     /// type: Value(Interval { value: "3", leading_field: Some(Month), leading_precision: None, last_field: None, fractional_seconds_precision: None })
     pub fn make_sql_interval_to_literal(
         value: &str,
@@ -74,12 +83,12 @@ impl SQLCommon {
         last_field: &Option<DateTimeField>,
         fractional_seconds_precision: &Option<u64>,
     ) -> Result<Expression> {
-        if leading_field.is_some() {
-            return Result::Err(ErrorCode::SyntaxException(format!(
-                "Unsupported Interval Expression with leading_field {:?}",
-                leading_field
-            )));
-        }
+        // INTERVAL '3' MONTH is INTERVAL '3 MONTH'
+        let value = match leading_field {
+            Some(field) => format!("{} {}", value, field),
+            None => value.to_string(),
+        };
+        let value = value.as_str();
 
         if leading_precision.is_some() {
             return Result::Err(ErrorCode::SyntaxException(format!(
@@ -147,6 +156,7 @@ impl SQLCommon {
             match interval_type.to_lowercase().as_str() {
                 "year" => Ok(align_interval_parts(interval_period * 12_f32, 0.0, 0.0)),
                 "month" => Ok(align_interval_parts(interval_period, 0.0, 0.0)),
+                "week" | "weeks" => Ok(align_interval_parts(0.0, interval_period * 7_f32, 0.0)),
                 "day" | "days" => Ok(align_interval_parts(0.0, interval_period, 0.0)),
                 "hour" | "hours" => {
                     Ok((0, 0, interval_period * SECONDS_PER_HOUR * MILLIS_PER_SECOND))
@@ -0,0 +1,106 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_infallible::Mutex;
+
+use crate::pipelines::transforms::ExpressionExecutor;
+use crate::sessions::FuseQueryContextRef;
+use crate::sql::DfParser;
+use crate::sql::PlanParser;
+
+/// Builds the values of the columns absent from a block, e.g., columns omitted by an INSERT,
+/// or columns added after a part was written.
+///
+/// A column takes the value of its DEFAULT expression, or NULL if it has no DEFAULT and is nullable.
+/// The DEFAULT expression of a column is evaluated once, the blocks only repeat its value, so a
+/// `DefaultValues` is for the columns of one schema, e.g. of one read or one INSERT.
+pub struct DefaultValues {
+    ctx: FuseQueryContextRef,
+    // The evaluated values of the DEFAULT expressions, cast to the types of the columns.
+    values: Mutex<HashMap<String, DataValue>>,
+}
+
+impl DefaultValues {
+    pub fn create(ctx: FuseQueryContextRef) -> Self {
+        DefaultValues {
+            ctx,
+            values: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Returns a block in `schema` with the absent columns filled with default values.
+    pub fn fill(&self, block: &DataBlock, schema: &DataSchemaRef) -> Result<DataBlock> {
+        let rows = block.num_rows();
+        let mut columns = Vec::with_capacity(schema.fields().len());
+        for field in schema.fields() {
+            let column = match block.try_column_by_name(field.name()) {
+                Ok(column) => column.clone(),
+                Err(_) => self.default_column(field, rows)?,
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(schema.clone(), columns))
+    }
+
+    /// Evaluates the default value of `field` as a constant column of `rows` rows.
+    pub fn default_column(&self, field: &DataField, rows: usize) -> Result<DataColumn> {
+        match field.default_expr() {
+            Some(default_expr) => {
+                let value = self.default_value(field, default_expr)?;
+                Ok(DataColumn::Constant(value, rows))
+            }
+            None if field.is_nullable() => Ok(DataColumn::Constant(
+                DataValue::from(field.data_type()),
+                rows,
+            )),
+            None => Err(ErrorCode::BadArguments(format!(
+                "Column '{}' is not nullable and has no default value",
+                field.name()
+            ))),
+        }
+    }
+
+    fn default_value(&self, field: &DataField, default_expr: &str) -> Result<DataValue> {
+        if let Some(value) = self.values.lock().get(field.name()) {
+            return Ok(value.clone());
+        }
+
+        let value = self.eval_default_expr(default_expr)?;
+        let value = DataColumn::Constant(value, 1)
+            .cast_with_type(field.data_type())?
+            .try_get(0)?;
+        self.values
+            .lock()
+            .insert(field.name().clone(), value.clone());
+        Ok(value)
+    }
+
+    fn eval_default_expr(&self, default_expr: &str) -> Result<DataValue> {
+        let expr = DfParser::parse_expr(default_expr)?;
+        let expr =
+            PlanParser::create(self.ctx.clone()).sql_to_rex(&expr, &DataSchema::empty(), None)?;
+
+        // A default expression is evaluated against a one-row block without any column,
+        // so that an expression referencing a column is rejected.
+        let input_schema =
+            DataSchemaRefExt::create(vec![DataField::new("_dummy", DataType::UInt8, false)]);
+        let input = DataBlock::create_by_array(input_schema.clone(), vec![Series::new(vec![0u8])]);
+        let output_schema = DataSchemaRefExt::create(vec![expr.to_data_field(&input_schema)?]);
+        let executor = ExpressionExecutor::try_create(
+            "default value executor",
+            input_schema,
+            output_schema,
+            vec![expr],
+            false,
+        )?;
+
+        executor.execute(&input)?.column(0).try_get(0)
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::PlanNode;
+use pretty_assertions::assert_eq;
+
+use crate::sql::DefaultValues;
+use crate::sql::PlanParser;
+
+#[test]
+fn test_default_values_create_table() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let plan = PlanParser::create(ctx.clone()).build_from_sql(
+        "create table default.a(a bigint, b int default 1 + 1, c varchar default 'x') Engine = Null",
+    )?;
+    if let PlanNode::CreateTable(plan) = plan {
+        let defaults = plan
+            .schema
+            .fields()
+            .iter()
+            .map(|f| f.default_expr().cloned())
+            .collect::<Vec<_>>();
+        assert_eq!(defaults, vec![
+            None,
+            Some("1 + 1".to_string()),
+            Some("'x'".to_string())
+        ]);
+    } else {
+        assert!(false)
+    }
+
+    // A default expression can not reference a column.
+    let result = PlanParser::create(ctx.clone())
+        .build_from_sql("create table default.a(a bigint, b bigint default a + 1) Engine = Null");
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_default_values_fill() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Int32, false).with_default_expr(Some("1 + 1".to_string())),
+        DataField::new("c", DataType::Utf8, true),
+        DataField::new("d", DataType::UInt8, false),
+    ]);
+
+    let block = DataBlock::create_by_array(
+        DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]),
+        vec![Series::new(vec![1i64, 2])],
+    );
+
+    let default_values = DefaultValues::create(ctx);
+
+    // Column d is not nullable and has no default value.
+    let result = default_values.fill(&block, &schema);
+    assert_eq!(
+        result.err().unwrap().message(),
+        "Column 'd' is not nullable and has no default value"
+    );
+
+    let schema = DataSchemaRefExt::create(schema.fields()[0..3].to_vec());
+    let filled = default_values.fill(&block, &schema)?;
+    assert_eq!(filled.schema(), &schema);
+    assert_eq!(filled.num_rows(), 2);
+    assert_eq!(filled.column(0).try_get(1)?, DataValue::Int64(Some(2)));
+    assert_eq!(filled.column(1).try_get(1)?, DataValue::Int32(Some(2)));
+    assert_eq!(filled.column(2).try_get(1)?, DataValue::Utf8(None));
+
+    Ok(())
+}
+
+#[test]
+fn test_default_values_evaluated_once() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Date64, false).with_default_expr(Some("now()".to_string())),
+    ]);
+    let block = DataBlock::create_by_array(
+        DataSchemaRefExt::create(vec![DataField::new("a", DataType::Int64, false)]),
+        vec![Series::new(vec![1i64, 2])],
+    );
+
+    // The blocks of one read take the value of the first evaluation, not a later now().
+    let default_values = DefaultValues::create(ctx);
+    let first = default_values.fill(&block, &schema)?;
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    let second = default_values.fill(&block, &schema)?;
+    assert_eq!(first.column(1).try_get(0)?, second.column(1).try_get(1)?);
+
+    Ok(())
+}
@@ -8,9 +8,10 @@
 use common_exception::ErrorCode;
 use common_planners::DatabaseEngineType;
 use common_planners::ExplainType;
-use common_planners::TableEngineType;
 use sqlparser::ast::ColumnDef;
+use sqlparser::ast::ColumnOption;
 use sqlparser::ast::ColumnOptionDef;
+use sqlparser::ast::Expr;
 use sqlparser::ast::Ident;
 use sqlparser::ast::SqlOption;
 use sqlparser::ast::TableConstraint;
@@ -24,19 +25,28 @@ use sqlparser::tokenizer::Token;
 use sqlparser::tokenizer::Tokenizer;
 use sqlparser::tokenizer::Whitespace;
 
+use crate::sql::DfCopy;
 use crate::sql::DfCreateDatabase;
 use crate::sql::DfCreateTable;
 use crate::sql::DfDescribeTable;
 use crate::sql::DfDropDatabase;
+use crate::sql::DfDropPartition;
 use crate::sql::DfDropTable;
 use crate::sql::DfExplain;
 use crate::sql::DfHint;
+use crate::sql::DfInsertIntoFunction;
+use crate::sql::DfKillQuery;
 use crate::sql::DfShowCreateTable;
 use crate::sql::DfShowDatabases;
+use crate::sql::DfShowFilter;
+use crate::sql::DfShowKeys;
 use crate::sql::DfShowProcessList;
 use crate::sql::DfShowSettings;
 use crate::sql::DfShowTables;
+use crate::sql::DfShowVariables;
+use crate::sql::DfShowWarnings;
 use crate::sql::DfStatement;
+use crate::sql::DfTruncateTable;
 use crate::sql::DfUseDatabase;
 
 // Use `Parser::expected` instead, if possible
@@ -117,6 +127,19 @@ impl<'a> DfParser<'a> {
         Ok((stmts, hints))
     }
 
+    /// Parse a standalone SQL expression, e.g., the DEFAULT expression of a column
+    pub fn parse_expr(sql: &str) -> Result<Expr, ErrorCode> {
+        let mut parser = DfParser::new(sql)?;
+        let expr = parser.parser.parse_expr()?;
+        if parser.parser.peek_token() != Token::EOF {
+            return Err(ErrorCode::SyntaxException(format!(
+                "Expected end of expression, found: {}",
+                parser.parser.peek_token()
+            )));
+        }
+        Ok(expr)
+    }
+
     /// Report unexpected token
     fn expected<T>(&self, expected: &str, found: Token) -> Result<T, ParserError> {
         parser_err!(format!("Expected {}, found: {}", expected, found))
@@ -125,6 +148,10 @@ impl<'a> DfParser<'a> {
     /// Parse a new expression
     pub fn parse_statement(&mut self) -> Result<DfStatement, ParserError> {
         match self.parser.peek_token() {
+            Token::Word(w) if w.value.to_uppercase() == "KILL" => {
+                self.parser.next_token();
+                self.parse_kill()
+            }
             Token::Word(w) => {
                 match w.keyword {
                     Keyword::CREATE => {
@@ -147,6 +174,32 @@ impl<'a> DfParser<'a> {
                         self.parser.next_token();
                         self.parse_explain()
                     }
+                    Keyword::TRUNCATE => {
+                        self.parser.next_token();
+                        self.parse_truncate()
+                    }
+                    Keyword::ALTER => {
+                        self.parser.next_token();
+                        self.parse_alter()
+                    }
+                    Keyword::INSERT => {
+                        self.parser.next_token();
+                        if self
+                            .parser
+                            .parse_keywords(&[Keyword::INTO, Keyword::FUNCTION])
+                        {
+                            self.parse_insert_into_function()
+                        } else {
+                            // use the native parser
+                            self.parser.prev_token();
+                            Ok(DfStatement::Statement(self.parser.parse_statement()?))
+                        }
+                    }
+
+                    Keyword::COPY => {
+                        self.parser.next_token();
+                        self.parse_copy()
+                    }
 
                     Keyword::SHOW => {
                         self.parser.next_token();
@@ -161,6 +214,19 @@ impl<'a> DfParser<'a> {
                             self.parse_show_create()
                         } else if self.consume_token("PROCESSLIST") {
                             Ok(DfStatement::ShowProcessList(DfShowProcessList))
+                        } else if self.consume_token("WARNINGS") {
+                            Ok(DfStatement::ShowWarnings(DfShowWarnings))
+                        } else if self.consume_token("VARIABLES")
+                            || ((self.consume_token("GLOBAL") || self.consume_token("SESSION"))
+                                && self.consume_token("VARIABLES"))
+                        {
+                            let filter = self.parse_show_filter()?;
+                            Ok(DfStatement::ShowVariables(DfShowVariables { filter }))
+                        } else if self.consume_token("KEYS")
+                            || self.consume_token("INDEX")
+                            || self.consume_token("INDEXES")
+                        {
+                            self.parse_show_keys()
                         } else {
                             self.expected("tables or settings", self.parser.peek_token())
                         }
@@ -191,12 +257,26 @@ impl<'a> DfParser<'a> {
             Token::Word(w) => match w.value.to_uppercase().as_str() {
                 "PIPELINE" => {
                     self.parser.next_token();
-                    ExplainType::Pipeline
+                    match self.parser.peek_token() {
+                        Token::Word(w) if w.value.to_uppercase() == "GRAPH" => {
+                            self.parser.next_token();
+                            ExplainType::PipelineGraph
+                        }
+                        _ => ExplainType::Pipeline,
+                    }
                 }
                 "GRAPH" => {
                     self.parser.next_token();
                     ExplainType::Graph
                 }
+                "STATISTICS" => {
+                    self.parser.next_token();
+                    ExplainType::Statistics
+                }
+                "ANALYZE" => {
+                    self.parser.next_token();
+                    ExplainType::Analyze
+                }
                 _ => ExplainType::Syntax,
             },
             _ => ExplainType::Syntax,
@@ -291,6 +371,16 @@ impl<'a> DfParser<'a> {
                         self.parser.peek_token(),
                     );
                 }
+            } else if self.consume_token("COMMENT") {
+                // COMMENT is not a column option of sqlparser, keep it as a dialect specific one.
+                let comment = self.parser.parse_literal_string()?;
+                options.push(ColumnOptionDef {
+                    name: None,
+                    option: ColumnOption::DialectSpecific(vec![
+                        Token::make_keyword("COMMENT"),
+                        Token::SingleQuotedString(comment),
+                    ]),
+                });
             } else if let Some(option) = self.parser.parse_optional_column_option()? {
                 options.push(ColumnOptionDef { name: None, option });
             } else {
@@ -377,6 +467,89 @@ impl<'a> DfParser<'a> {
         Ok(DfStatement::DropTable(drop))
     }
 
+    /// Truncate table.
+    /// Kill a running query by its id, e.g. KILL QUERY 'query_id'.
+    fn parse_kill(&mut self) -> Result<DfStatement, ParserError> {
+        if !self.consume_token("QUERY") {
+            return self.expected("QUERY", self.parser.peek_token());
+        }
+        let query_id = self.parser.parse_literal_string()?;
+        Ok(DfStatement::KillQuery(DfKillQuery { query_id }))
+    }
+
+    fn parse_truncate(&mut self) -> Result<DfStatement, ParserError> {
+        // The TABLE keyword is optional.
+        self.parser.parse_keyword(Keyword::TABLE);
+        let table_name = self.parser.parse_object_name()?;
+        let truncate = DfTruncateTable { name: table_name };
+        Ok(DfStatement::TruncateTable(truncate))
+    }
+
+    /// Insert the rows of a query into a writable table function, e.g. file(path, format).
+    fn parse_insert_into_function(&mut self) -> Result<DfStatement, ParserError> {
+        let name = self.parser.parse_identifier()?;
+        self.parser.expect_token(&Token::LParen)?;
+        let args = if self.parser.consume_token(&Token::RParen) {
+            vec![]
+        } else {
+            let args = self.parser.parse_comma_separated(Parser::parse_expr)?;
+            self.parser.expect_token(&Token::RParen)?;
+            args
+        };
+        let query = Box::new(self.parser.parse_query()?);
+
+        let insert = DfInsertIntoFunction { name, args, query };
+        Ok(DfStatement::InsertIntoFunction(insert))
+    }
+
+    /// Copy the rows of a file into a table, e.g. COPY INTO t FROM 'file.csv' FORMAT CSV.
+    fn parse_copy(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::INTO)?;
+        let name = self.parser.parse_object_name()?;
+        let columns = if self.parser.consume_token(&Token::LParen) {
+            let columns = self
+                .parser
+                .parse_comma_separated(Parser::parse_identifier)?;
+            self.parser.expect_token(&Token::RParen)?;
+            columns
+        } else {
+            vec![]
+        };
+        self.parser.expect_keyword(Keyword::FROM)?;
+        let location = self.parser.parse_literal_string()?;
+        let format = match self.consume_token("FORMAT") {
+            true => Some(self.parser.parse_identifier()?),
+            false => None,
+        };
+
+        let copy = DfCopy {
+            name,
+            columns,
+            location,
+            format,
+        };
+        Ok(DfStatement::Copy(copy))
+    }
+
+    /// Alter table, only DROP PARTITION is supported.
+    fn parse_alter(&mut self) -> Result<DfStatement, ParserError> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let table_name = self.parser.parse_object_name()?;
+        if !self
+            .parser
+            .parse_keywords(&[Keyword::DROP, Keyword::PARTITION])
+        {
+            return self.expected("DROP PARTITION", self.parser.peek_token());
+        }
+        let partition = self.parser.parse_expr()?;
+
+        let drop = DfDropPartition {
+            name: table_name,
+            partition,
+        };
+        Ok(DfStatement::DropPartition(drop))
+    }
+
     // Parse 'use database' db name.
     fn parse_use_database(&mut self) -> Result<DfStatement, ParserError> {
         if !self.consume_token("USE") {
@@ -414,13 +587,34 @@ impl<'a> DfParser<'a> {
         let engine = self.parse_table_engine()?;
 
         let mut table_properties = vec![];
+        let mut partition_by = None;
+        let mut cluster_by = None;
 
         // parse table options: https://dev.mysql.com/doc/refman/8.0/en/create-table.html
-        if self.consume_token("LOCATION") {
+        loop {
+            if self
+                .parser
+                .parse_keywords(&[Keyword::PARTITION, Keyword::BY])
+            {
+                partition_by = Some(self.parser.parse_expr()?);
+                continue;
+            }
+            if self.consume_token("CLUSTER") {
+                cluster_by = Some(self.parse_cluster_by()?);
+                continue;
+            }
+
+            let name = if self.consume_token("LOCATION") {
+                "LOCATION"
+            } else if self.consume_token("COMMENT") {
+                "COMMENT"
+            } else {
+                break;
+            };
             self.parser.expect_token(&Token::Eq)?;
             let value = self.parse_value()?;
             table_properties.push(SqlOption {
-                name: Ident::new("LOCATION"),
+                name: Ident::new(name),
                 value,
             })
         }
@@ -431,36 +625,38 @@ impl<'a> DfParser<'a> {
             columns,
             engine,
             options: table_properties,
+            partition_by,
+            cluster_by,
         };
 
         Ok(DfStatement::CreateTable(create))
     }
 
-    /// Parses the set of valid formats
-    fn parse_table_engine(&mut self) -> Result<TableEngineType, ParserError> {
+    /// Parses 'BY column INTO buckets BUCKETS' after CLUSTER.
+    fn parse_cluster_by(&mut self) -> Result<(Ident, u64), ParserError> {
+        self.parser.expect_keyword(Keyword::BY)?;
+        let column = self.parser.parse_identifier()?;
+        self.parser.expect_keyword(Keyword::INTO)?;
+        let buckets = self.parser.parse_literal_uint()?;
+        if !self.consume_token("BUCKETS") {
+            return self.expected("BUCKETS", self.parser.peek_token());
+        }
+        Ok((column, buckets))
+    }
+
+    /// Parses the engine name of a table, it is resolved by the table engine registry later. The
+    /// name is empty without ENGINE, the database creates the table with its default engine.
+    fn parse_table_engine(&mut self) -> Result<String, ParserError> {
         // TODO make ENGINE as a keyword
         if !self.consume_token("ENGINE") {
-            return Ok(TableEngineType::Null);
+            return Ok(String::new());
         }
 
         self.parser.expect_token(&Token::Eq)?;
 
         match self.parser.next_token() {
-            Token::Word(w) => match &*w.value {
-                "Parquet" => Ok(TableEngineType::Parquet),
-                "JSONEachRaw" => Ok(TableEngineType::JsonEachRaw),
-                "CSV" => Ok(TableEngineType::Csv),
-                "Null" => Ok(TableEngineType::Null),
-                "Memory" => Ok(TableEngineType::Memory),
-                _ => self.expected(
-                    "Engine must one of Parquet, JSONEachRaw, Null or CSV",
-                    Token::Word(w),
-                ),
-            },
-            unexpected => self.expected(
-                "Engine must one of Parquet, JSONEachRaw, Null or CSV",
-                unexpected,
-            ),
+            Token::Word(w) => Ok(w.value),
+            unexpected => self.expected("table engine name", unexpected),
         }
     }
 
@@ -479,6 +675,40 @@ impl<'a> DfParser<'a> {
         }
     }
 
+    fn parse_show_filter(&mut self) -> Result<Option<DfShowFilter>, ParserError> {
+        if self.parser.parse_keyword(Keyword::LIKE) {
+            match self.parser.next_token() {
+                Token::SingleQuotedString(pattern) => Ok(Some(DfShowFilter::Like(pattern))),
+                unexpected => self.expected("pattern of LIKE", unexpected),
+            }
+        } else if self.parser.parse_keyword(Keyword::WHERE) {
+            Ok(Some(DfShowFilter::Where(self.parser.parse_expr()?)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn parse_show_keys(&mut self) -> Result<DfStatement, ParserError> {
+        if self
+            .parser
+            .parse_one_of_keywords(&[Keyword::FROM, Keyword::IN])
+            .is_none()
+        {
+            return self.expected("FROM or IN", self.parser.peek_token());
+        }
+        let mut name = self.parser.parse_object_name()?;
+
+        if self
+            .parser
+            .parse_one_of_keywords(&[Keyword::FROM, Keyword::IN])
+            .is_some()
+        {
+            let db = self.parser.parse_identifier()?;
+            name.0.insert(0, db);
+        }
+        Ok(DfStatement::ShowKeys(DfShowKeys { name }))
+    }
+
     fn consume_token(&mut self, expected: &str) -> bool {
         if self.parser.peek_token().to_string().to_uppercase() == *expected.to_uppercase() {
             self.parser.next_token();
@@ -6,7 +6,6 @@
 mod tests {
     use common_exception::Result;
     use common_planners::DatabaseEngineType;
-    use common_planners::TableEngineType;
     use sqlparser::ast::*;
 
     use crate::sql::sql_statement::DfDropDatabase;
@@ -132,11 +131,13 @@ mod tests {
             if_not_exists: false,
             name: ObjectName(vec![Ident::new("t")]),
             columns: vec![make_column_def("c1", DataType::Int)],
-            engine: TableEngineType::Csv,
+            engine: "CSV".to_string(),
             options: vec![SqlOption {
                 name: Ident::new("LOCATION".to_string()),
                 value: Value::SingleQuotedString("/data/33.csv".into()),
             }],
+            partition_by: None,
+            cluster_by: None,
         });
         expect_parse_ok(sql, expected)?;
 
@@ -150,20 +151,96 @@ mod tests {
                 make_column_def("c2", DataType::BigInt),
                 make_column_def("c3", DataType::Varchar(Some(255))),
             ],
-            engine: TableEngineType::Parquet,
+            engine: "Parquet".to_string(),
             options: vec![SqlOption {
                 name: Ident::new("LOCATION".to_string()),
                 value: Value::SingleQuotedString("foo.parquet".into()),
             }],
+            partition_by: None,
+            cluster_by: None,
         });
         expect_parse_ok(sql, expected)?;
 
-        // Error cases: Invalid type
-        let sql = "CREATE TABLE t(c1 int) ENGINE = XX location = 'foo.parquet' ";
-        expect_parse_error(
-            sql,
-            "Expected Engine must one of Parquet, JSONEachRaw, Null or CSV, found: XX",
-        )?;
+        // positive case: the database decides the engine without ENGINE
+        let sql = "CREATE TABLE t(c1 int)";
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t")]),
+            columns: vec![make_column_def("c1", DataType::Int)],
+            engine: "".to_string(),
+            options: vec![],
+            partition_by: None,
+            cluster_by: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // positive case: table and column comments
+        let sql = "CREATE TABLE t(c1 int COMMENT 'id of t') ENGINE = Null COMMENT = 'table t' ";
+        let mut column = make_column_def("c1", DataType::Int);
+        column.options.push(ColumnOptionDef {
+            name: None,
+            option: ColumnOption::DialectSpecific(vec![
+                sqlparser::tokenizer::Token::make_keyword("COMMENT"),
+                sqlparser::tokenizer::Token::SingleQuotedString("id of t".into()),
+            ]),
+        });
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t")]),
+            columns: vec![column],
+            engine: "Null".to_string(),
+            options: vec![SqlOption {
+                name: Ident::new("COMMENT".to_string()),
+                value: Value::SingleQuotedString("table t".into()),
+            }],
+            partition_by: None,
+            cluster_by: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // positive case: partition by
+        let sql = "CREATE TABLE t(c1 int) ENGINE = Log PARTITION BY c1 % 10 location = '/data/t' ";
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t")]),
+            columns: vec![make_column_def("c1", DataType::Int)],
+            engine: "Log".to_string(),
+            options: vec![SqlOption {
+                name: Ident::new("LOCATION".to_string()),
+                value: Value::SingleQuotedString("/data/t".into()),
+            }],
+            partition_by: Some(Expr::BinaryOp {
+                left: Box::new(Expr::Identifier(Ident::new("c1"))),
+                op: BinaryOperator::Modulo,
+                right: Box::new(Expr::Value(Value::Number("10".into(), false))),
+            }),
+            cluster_by: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // positive case: cluster by
+        let sql = "CREATE TABLE t(c1 int, c2 int) ENGINE = Parquet CLUSTER BY c1 INTO 8 BUCKETS";
+        let expected = DfStatement::CreateTable(DfCreateTable {
+            if_not_exists: false,
+            name: ObjectName(vec![Ident::new("t")]),
+            columns: vec![
+                make_column_def("c1", DataType::Int),
+                make_column_def("c2", DataType::Int),
+            ],
+            engine: "Parquet".to_string(),
+            options: vec![],
+            partition_by: None,
+            cluster_by: Some((Ident::new("c1"), 8)),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // Error cases: missing BUCKETS
+        let sql = "CREATE TABLE t(c1 int) ENGINE = Parquet CLUSTER BY c1 INTO 8";
+        expect_parse_error(sql, "Expected BUCKETS, found: EOF")?;
+
+        // Error cases: missing engine name
+        let sql = "CREATE TABLE t(c1 int) ENGINE = ";
+        expect_parse_error(sql, "Expected table engine name, found: EOF")?;
 
         Ok(())
     }
@@ -190,6 +267,106 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn truncate_table() -> Result<()> {
+        {
+            let sql = "TRUNCATE TABLE t1";
+            let expected = DfStatement::TruncateTable(DfTruncateTable {
+                name: ObjectName(vec![Ident::new("t1")]),
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+        {
+            let sql = "TRUNCATE db1.t1";
+            let expected = DfStatement::TruncateTable(DfTruncateTable {
+                name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn drop_partition() -> Result<()> {
+        {
+            let sql = "ALTER TABLE t1 DROP PARTITION 202101";
+            let expected = DfStatement::DropPartition(DfDropPartition {
+                name: ObjectName(vec![Ident::new("t1")]),
+                partition: Expr::Value(Value::Number("202101".into(), false)),
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+        {
+            let sql = "ALTER TABLE db1.t1 DROP PARTITION 'cn'";
+            let expected = DfStatement::DropPartition(DfDropPartition {
+                name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+                partition: Expr::Value(Value::SingleQuotedString("cn".into())),
+            });
+            expect_parse_ok(sql, expected)?;
+        }
+
+        // Error cases: only DROP PARTITION is supported
+        let sql = "ALTER TABLE t1 DROP COLUMN c1";
+        expect_parse_error(sql, "Expected DROP PARTITION, found: DROP")?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn insert_into_function() -> Result<()> {
+        let sql = "INSERT INTO FUNCTION file('/tmp/t1.parquet', 'Parquet') SELECT * FROM t1";
+        let query = match DfParser::parse_sql("SELECT * FROM t1")?.0.pop() {
+            Some(DfStatement::Statement(Statement::Query(query))) => query,
+            other => panic!("Expected a query, but got {:?}", other),
+        };
+        let expected = DfStatement::InsertIntoFunction(DfInsertIntoFunction {
+            name: Ident::new("file"),
+            args: vec![
+                Expr::Value(Value::SingleQuotedString("/tmp/t1.parquet".into())),
+                Expr::Value(Value::SingleQuotedString("Parquet".into())),
+            ],
+            query,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        // A plain INSERT is parsed by the native parser.
+        let sql = "INSERT INTO t1 SELECT * FROM t2";
+        let (statements, _) = DfParser::parse_sql(sql)?;
+        assert!(matches!(
+            statements[0],
+            DfStatement::Statement(Statement::Insert { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy() -> Result<()> {
+        let sql = "COPY INTO db1.t1 (a, b) FROM '/tmp/t1.csv' FORMAT CSV";
+        let expected = DfStatement::Copy(DfCopy {
+            name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            columns: vec![Ident::new("a"), Ident::new("b")],
+            location: "/tmp/t1.csv".to_string(),
+            format: Some(Ident::new("CSV")),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "COPY INTO t1 FROM '/tmp/t1.csv'";
+        let expected = DfStatement::Copy(DfCopy {
+            name: ObjectName(vec![Ident::new("t1")]),
+            columns: vec![],
+            location: "/tmp/t1.csv".to_string(),
+            format: None,
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "COPY t1 FROM '/tmp/t1.csv'";
+        expect_parse_error(sql, "Expected INTO, found: t1")?;
+
+        Ok(())
+    }
+
     #[test]
     fn describe_table() -> Result<()> {
         {
@@ -215,6 +392,44 @@ mod tests {
         // positive case
         expect_parse_ok("SHOW TABLES", DfStatement::ShowTables(DfShowTables))?;
         expect_parse_ok("SHOW SETTINGS", DfStatement::ShowSettings(DfShowSettings))?;
+        expect_parse_ok("SHOW WARNINGS", DfStatement::ShowWarnings(DfShowWarnings))?;
+
+        expect_parse_ok(
+            "SHOW VARIABLES",
+            DfStatement::ShowVariables(DfShowVariables { filter: None }),
+        )?;
+        expect_parse_ok(
+            "SHOW SESSION VARIABLES LIKE 'character_set_%'",
+            DfStatement::ShowVariables(DfShowVariables {
+                filter: Some(DfShowFilter::Like("character_set_%".to_string())),
+            }),
+        )?;
+        expect_parse_ok(
+            "SHOW GLOBAL VARIABLES WHERE Variable_name = 'version'",
+            DfStatement::ShowVariables(DfShowVariables {
+                filter: Some(DfShowFilter::Where(Expr::BinaryOp {
+                    left: Box::new(Expr::Identifier(Ident::new("Variable_name"))),
+                    op: BinaryOperator::Eq,
+                    right: Box::new(Expr::Value(Value::SingleQuotedString(
+                        "version".to_string(),
+                    ))),
+                })),
+            }),
+        )?;
+
+        expect_parse_ok(
+            "SHOW KEYS FROM t1",
+            DfStatement::ShowKeys(DfShowKeys {
+                name: ObjectName(vec![Ident::new("t1")]),
+            }),
+        )?;
+        expect_parse_ok(
+            "SHOW INDEX IN t1 FROM db1",
+            DfStatement::ShowKeys(DfShowKeys {
+                name: ObjectName(vec![Ident::new("db1"), Ident::new("t1")]),
+            }),
+        )?;
+        expect_parse_error("SHOW INDEXES t1", "Expected FROM or IN, found: t1")?;
 
         Ok(())
     }
@@ -237,6 +452,25 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn kill_query_test() -> Result<()> {
+        expect_parse_ok(
+            "KILL QUERY 'query_a'",
+            DfStatement::KillQuery(DfKillQuery {
+                query_id: "query_a".to_string(),
+            }),
+        )?;
+        expect_parse_ok(
+            "kill query 'query_a'",
+            DfStatement::KillQuery(DfKillQuery {
+                query_id: "query_a".to_string(),
+            }),
+        )?;
+        expect_parse_error("KILL 'query_a'", "Expected QUERY")?;
+
+        Ok(())
+    }
+
     #[test]
     fn hint_test() -> Result<()> {
         {
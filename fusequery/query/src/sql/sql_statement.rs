@@ -4,7 +4,6 @@
 
 use common_planners::DatabaseEngineType;
 use common_planners::ExplainType;
-use common_planners::TableEngineType;
 use nom::bytes::complete::tag;
 use nom::bytes::complete::take_till1;
 use nom::character::complete::digit1;
@@ -12,7 +11,10 @@ use nom::character::complete::multispace0;
 use nom::character::complete::multispace1;
 use nom::IResult;
 use sqlparser::ast::ColumnDef;
+use sqlparser::ast::Expr;
+use sqlparser::ast::Ident;
 use sqlparser::ast::ObjectName;
+use sqlparser::ast::Query;
 use sqlparser::ast::SqlOption;
 use sqlparser::ast::Statement as SQLStatement;
 
@@ -28,6 +30,28 @@ pub struct DfShowSettings;
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfShowProcessList;
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfShowWarnings;
+
+/// The LIKE 'pattern' or WHERE expr of the SHOW statements.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DfShowFilter {
+    Like(String),
+    Where(Expr),
+}
+
+/// SHOW [GLOBAL | SESSION] VARIABLES [LIKE 'pattern' | WHERE expr]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfShowVariables {
+    pub filter: Option<DfShowFilter>,
+}
+
+/// SHOW {KEYS | INDEX | INDEXES} {FROM | IN} table [{FROM | IN} db]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfShowKeys {
+    pub name: ObjectName,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfExplain {
     pub typ: ExplainType,
@@ -45,8 +69,11 @@ pub struct DfCreateTable {
     /// Table name
     pub name: ObjectName,
     pub columns: Vec<ColumnDef>,
-    pub engine: TableEngineType,
+    pub engine: String,
     pub options: Vec<SqlOption>,
+    pub partition_by: Option<Expr>,
+    /// CLUSTER BY column INTO buckets BUCKETS
+    pub cluster_by: Option<(Ident, u64)>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -60,6 +87,35 @@ pub struct DfDropTable {
     pub name: ObjectName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfTruncateTable {
+    pub name: ObjectName,
+}
+
+/// ALTER TABLE ... DROP PARTITION ...
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfDropPartition {
+    pub name: ObjectName,
+    pub partition: Expr,
+}
+
+/// INSERT INTO FUNCTION name(args) SELECT ...
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfInsertIntoFunction {
+    pub name: Ident,
+    pub args: Vec<Expr>,
+    pub query: Box<Query>,
+}
+
+/// COPY INTO table [(columns)] FROM 'location' [FORMAT format]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfCopy {
+    pub name: ObjectName,
+    pub columns: Vec<Ident>,
+    pub location: String,
+    pub format: Option<Ident>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct DfCreateDatabase {
     pub if_not_exists: bool,
@@ -79,6 +135,11 @@ pub struct DfUseDatabase {
     pub name: ObjectName,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct DfKillQuery {
+    pub query_id: String,
+}
+
 /// Tokens parsed by `DFParser` are converted into these values.
 #[derive(Debug, Clone, PartialEq)]
 pub enum DfStatement {
@@ -98,12 +159,26 @@ pub enum DfStatement {
     CreateTable(DfCreateTable),
     DescribeTable(DfDescribeTable),
     DropTable(DfDropTable),
+    TruncateTable(DfTruncateTable),
+    DropPartition(DfDropPartition),
+    InsertIntoFunction(DfInsertIntoFunction),
+    Copy(DfCopy),
 
     // Settings.
     ShowSettings(DfShowSettings),
 
     // ProcessList
     ShowProcessList(DfShowProcessList),
+
+    // Kill a running query by its id.
+    KillQuery(DfKillQuery),
+
+    // Warnings of the previous query
+    ShowWarnings(DfShowWarnings),
+
+    // Metadata of the drivers.
+    ShowVariables(DfShowVariables),
+    ShowKeys(DfShowKeys),
 }
 
 /// Comment hints from SQL.
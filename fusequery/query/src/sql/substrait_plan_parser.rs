@@ -0,0 +1,515 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_datavalues::DataValue;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_flights::substrait;
+use common_flights::substrait::aggregate_function::AggregationInvocation;
+use common_flights::substrait::expression::field_reference;
+use common_flights::substrait::expression::literal::LiteralType;
+use common_flights::substrait::expression::reference_segment;
+use common_flights::substrait::expression::RexType;
+use common_flights::substrait::function_argument::ArgType;
+use common_flights::substrait::plan_rel;
+use common_flights::substrait::read_rel::ReadType;
+use common_flights::substrait::rel::RelType;
+use common_flights::substrait::simple_extension_declaration::MappingType;
+use common_flights::substrait::sort_field::SortDirection;
+use common_flights::substrait::sort_field::SortKind;
+use common_planners::expand_aggregate_arg_exprs;
+use common_planners::expr_as_column_expr;
+use common_planners::rebase_expr_from_input;
+use common_planners::sort_to_inner_expr;
+use common_planners::Expression;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::SelectPlan;
+use prost::Message;
+
+use crate::sessions::FuseQueryContextRef;
+
+/// Builds the plan of a Substrait plan, the plan of other front-ends and optimizers.
+///
+/// The plan must have exactly one relation, made of the read of named tables, filter, project,
+/// aggregate of one grouping, sort and fetch. The expressions are the literals, the references
+/// to the fields of the input and the functions, which are resolved by the names declared in the
+/// extensions of the plan.
+pub struct SubstraitPlanParser {
+    ctx: FuseQueryContextRef,
+}
+
+impl SubstraitPlanParser {
+    pub fn create(ctx: FuseQueryContextRef) -> Self {
+        Self { ctx }
+    }
+
+    /// Build the plan of a plan encoded by protobuf.
+    pub fn build_from_bytes(&self, bytes: &[u8]) -> Result<PlanNode> {
+        let plan = substrait::Plan::decode(bytes)
+            .map_err(|e| ErrorCode::BadBytes(format!("Cannot decode the Substrait plan: {}", e)))?;
+        self.build_from_plan(&plan)
+    }
+
+    pub fn build_from_plan(&self, plan: &substrait::Plan) -> Result<PlanNode> {
+        let functions = plan
+            .extensions
+            .iter()
+            .filter_map(|extension| match &extension.mapping_type {
+                Some(MappingType::ExtensionFunction(function)) => {
+                    // The name may be followed by the signature, e.g. "add:i64_i64".
+                    let name = function.name.split(':').next().unwrap_or_default();
+                    Some((function.function_anchor, name.to_string()))
+                }
+                None => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        let plan = match plan.relations.as_slice() {
+            [relation] => match &relation.rel_type {
+                Some(plan_rel::RelType::Rel(rel)) => self.rel_to_plan(rel, &functions)?,
+                Some(plan_rel::RelType::Root(root)) => {
+                    let input = required(&root.input, "RelRoot.input")?;
+                    let plan = self.rel_to_plan(input, &functions)?;
+                    Self::rename(&plan, &root.names)?
+                }
+                None => return Err(missing("PlanRel.rel_type")),
+            },
+            relations => {
+                return Err(ErrorCode::UnImplement(format!(
+                    "Substrait plan must have exactly one relation, but got {}",
+                    relations.len()
+                )))
+            }
+        };
+
+        Ok(PlanNode::Select(SelectPlan {
+            input: Arc::new(plan),
+        }))
+    }
+
+    fn rel_to_plan(
+        &self,
+        rel: &substrait::Rel,
+        functions: &HashMap<u32, String>,
+    ) -> Result<PlanNode> {
+        match &rel.rel_type {
+            Some(RelType::Read(read)) => {
+                let plan = match &read.read_type {
+                    Some(ReadType::NamedTable(table)) => self.read_table(&table.names)?,
+                    None => return Err(missing("ReadRel.read_type")),
+                };
+                match &read.filter {
+                    Some(filter) => {
+                        let predicate = Self::expr_to_rex(filter, &plan, functions)?;
+                        PlanBuilder::from(&plan).filter(predicate)?.build()
+                    }
+                    None => Ok(plan),
+                }
+            }
+            Some(RelType::Filter(filter)) => {
+                let plan =
+                    self.rel_to_plan(required(&filter.input, "FilterRel.input")?, functions)?;
+                let condition = required(&filter.condition, "FilterRel.condition")?;
+                let predicate = Self::expr_to_rex(condition, &plan, functions)?;
+                PlanBuilder::from(&plan).filter(predicate)?.build()
+            }
+            Some(RelType::Project(project)) => {
+                let plan =
+                    self.rel_to_plan(required(&project.input, "ProjectRel.input")?, functions)?;
+
+                // The columns of the input are followed by the expressions.
+                let mut exprs = Self::input_columns(&plan);
+                for expr in &project.expressions {
+                    exprs.push(Self::expr_to_rex(expr, &plan, functions)?);
+                }
+                let plan = Self::expression(&plan, &exprs, "Before Projection")?;
+                Self::project(&plan, &exprs)
+            }
+            Some(RelType::Aggregate(aggregate)) => {
+                let plan =
+                    self.rel_to_plan(required(&aggregate.input, "AggregateRel.input")?, functions)?;
+
+                let group_by_exprs = match aggregate.groupings.as_slice() {
+                    [] => vec![],
+                    [grouping] => grouping
+                        .grouping_expressions
+                        .iter()
+                        .map(|expr| Self::expr_to_rex(expr, &plan, functions))
+                        .collect::<Result<Vec<_>>>()?,
+                    _ => {
+                        return Err(ErrorCode::UnImplement(
+                            "AggregateRel of more than one grouping is not supported",
+                        ))
+                    }
+                };
+                let aggr_exprs = aggregate
+                    .measures
+                    .iter()
+                    .map(|measure| {
+                        let function = required(&measure.measure, "Measure.measure")?;
+                        Self::aggregate_to_rex(function, &plan, functions)
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                Self::aggregate(&plan, &aggr_exprs, &group_by_exprs)
+            }
+            Some(RelType::Sort(sort)) => {
+                let plan = self.rel_to_plan(required(&sort.input, "SortRel.input")?, functions)?;
+
+                let order_by_exprs = sort
+                    .sorts
+                    .iter()
+                    .map(|field| {
+                        let expr = required(&field.expr, "SortField.expr")?;
+                        let (asc, nulls_first) = match field.sort_kind {
+                            Some(SortKind::Direction(direction)) => {
+                                match SortDirection::from_i32(direction) {
+                                    Some(SortDirection::AscNullsFirst) => (true, true),
+                                    Some(SortDirection::AscNullsLast) => (true, false),
+                                    Some(SortDirection::DescNullsFirst) => (false, true),
+                                    Some(SortDirection::DescNullsLast) => (false, false),
+                                    _ => {
+                                        return Err(ErrorCode::UnImplement(format!(
+                                            "Unsupported sort direction {}",
+                                            direction
+                                        )))
+                                    }
+                                }
+                            }
+                            None => return Err(missing("SortField.sort_kind")),
+                        };
+                        Ok(Expression::Sort {
+                            expr: Box::new(Self::expr_to_rex(expr, &plan, functions)?),
+                            asc,
+                            nulls_first,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+
+                let columns = Self::input_columns(&plan);
+                let sort_plan = Self::expression(&plan, &order_by_exprs, "Before OrderBy")?;
+                let order_by_exprs = order_by_exprs
+                    .iter()
+                    .map(|expr| rebase_expr_from_input(expr, &sort_plan.schema()))
+                    .collect::<Result<Vec<_>>>()?;
+                let sort_plan = PlanBuilder::from(&sort_plan)
+                    .sort(&order_by_exprs)?
+                    .build()?;
+                // Remove the columns computed for the sort.
+                Self::project(&sort_plan, &columns)
+            }
+            Some(RelType::Fetch(fetch)) => {
+                let plan =
+                    self.rel_to_plan(required(&fetch.input, "FetchRel.input")?, functions)?;
+                let limit = match fetch.count {
+                    count if count < 0 => None,
+                    count => Some(count as usize),
+                };
+                PlanBuilder::from(&plan)
+                    .limit_offset(limit, fetch.offset.max(0) as usize)?
+                    .build()
+            }
+            None => Err(missing("Rel.rel_type")),
+        }
+    }
+
+    fn read_table(&self, names: &[String]) -> Result<PlanNode> {
+        let (db_name, table_name) = match names {
+            [table] => (self.ctx.get_current_database(), table.clone()),
+            [db, table] => (db.clone(), table.clone()),
+            _ => {
+                return Err(ErrorCode::BadArguments(format!(
+                    "The names of NamedTable must be [table] or [database, table], but got {:?}",
+                    names
+                )))
+            }
+        };
+
+        let table = self.ctx.get_table(&db_name, &table_name)?;
+        let scan = PlanBuilder::scan(&db_name, &table_name, &table.schema()?, None, vec![], None)?
+            .build()?;
+        match scan {
+            PlanNode::Scan(ref scan) => {
+                let partitions = self.ctx.get_settings().get_max_threads()? as usize;
+                table
+                    .read_plan(self.ctx.clone(), scan, partitions)
+                    .map(PlanNode::ReadSource)
+            }
+            _unreachable_plan => panic!("Logical error: Cannot downcast to scan plan"),
+        }
+    }
+
+    fn expr_to_rex(
+        expr: &substrait::Expression,
+        input: &PlanNode,
+        functions: &HashMap<u32, String>,
+    ) -> Result<Expression> {
+        match &expr.rex_type {
+            Some(RexType::Literal(literal)) => {
+                let value = match &literal.literal_type {
+                    Some(LiteralType::Boolean(v)) => DataValue::Boolean(Some(*v)),
+                    Some(LiteralType::I8(v)) => DataValue::Int8(Some(*v as i8)),
+                    Some(LiteralType::I16(v)) => DataValue::Int16(Some(*v as i16)),
+                    Some(LiteralType::I32(v)) => DataValue::Int32(Some(*v)),
+                    Some(LiteralType::I64(v)) => DataValue::Int64(Some(*v)),
+                    Some(LiteralType::Fp32(v)) => DataValue::Float32(Some(*v)),
+                    Some(LiteralType::Fp64(v)) => DataValue::Float64(Some(*v)),
+                    Some(LiteralType::String(v)) => DataValue::Utf8(Some(v.clone())),
+                    None => return Err(missing("Literal.literal_type")),
+                };
+                Ok(Expression::create_literal(value))
+            }
+            Some(RexType::Selection(reference)) => {
+                let index = match &reference.reference_type {
+                    Some(field_reference::ReferenceType::DirectReference(segment)) => {
+                        match &segment.reference_type {
+                            Some(reference_segment::ReferenceType::StructField(field)) => {
+                                field.field
+                            }
+                            None => return Err(missing("ReferenceSegment.reference_type")),
+                        }
+                    }
+                    None => return Err(missing("FieldReference.reference_type")),
+                };
+
+                let schema = input.schema();
+                match schema.fields().get(index as usize) {
+                    Some(field) if index >= 0 => Ok(Expression::Column(field.name().clone())),
+                    _ => Err(ErrorCode::BadArguments(format!(
+                        "Field reference {} is out of the {} fields of the input",
+                        index,
+                        schema.fields().len()
+                    ))),
+                }
+            }
+            Some(RexType::ScalarFunction(function)) => {
+                let name = Self::function_name(function.function_reference, functions)?;
+                let mut args = Self::function_args(&function.args, &function.arguments)
+                    .iter()
+                    .map(|arg| Self::expr_to_rex(arg, input, functions))
+                    .collect::<Result<Vec<_>>>()?;
+
+                let binary_op = match name {
+                    "add" => Some("+"),
+                    "subtract" => Some("-"),
+                    "multiply" => Some("*"),
+                    "divide" => Some("/"),
+                    "modulus" => Some("%"),
+                    "equal" => Some("="),
+                    "not_equal" => Some("!="),
+                    "is_not_distinct_from" => Some("<=>"),
+                    "is_distinct_from" => Some("is distinct from"),
+                    "lt" => Some("<"),
+                    "lte" => Some("<="),
+                    "gt" => Some(">"),
+                    "gte" => Some(">="),
+                    "and" => Some("and"),
+                    "or" => Some("or"),
+                    _ => None,
+                };
+
+                match (binary_op, args.len()) {
+                    (Some(op), 2) => {
+                        let right = args.pop().unwrap();
+                        let left = args.pop().unwrap();
+                        Ok(Expression::BinaryExpression {
+                            op: op.to_string(),
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        })
+                    }
+                    (Some(op), n) => Err(ErrorCode::BadArguments(format!(
+                        "Function {} expects 2 arguments, but got {}",
+                        op, n
+                    ))),
+                    (None, 1) if name == "not" => Ok(Expression::UnaryExpression {
+                        op: "not".to_string(),
+                        expr: Box::new(args.pop().unwrap()),
+                    }),
+                    (None, _) => Ok(Expression::ScalarFunction {
+                        op: name.to_string(),
+                        args,
+                    }),
+                }
+            }
+            None => Err(missing("Expression.rex_type")),
+        }
+    }
+
+    fn aggregate_to_rex(
+        function: &substrait::AggregateFunction,
+        input: &PlanNode,
+        functions: &HashMap<u32, String>,
+    ) -> Result<Expression> {
+        let name = Self::function_name(function.function_reference, functions)?;
+        let mut args = Self::function_args(&function.args, &function.arguments)
+            .iter()
+            .map(|arg| Self::expr_to_rex(arg, input, functions))
+            .collect::<Result<Vec<_>>>()?;
+
+        // count() of no arguments counts the rows, as count(*) of SQL.
+        if name == "count" && args.is_empty() {
+            args.push(common_planners::lit(0i64));
+        }
+
+        let distinct = function.invocation == AggregationInvocation::Distinct as i32;
+        Ok(Expression::AggregateFunction {
+            op: name.to_string(),
+            distinct,
+            args,
+        })
+    }
+
+    fn function_name(reference: u32, functions: &HashMap<u32, String>) -> Result<&str> {
+        functions
+            .get(&reference)
+            .map(|name| name.as_str())
+            .ok_or_else(|| {
+                ErrorCode::BadArguments(format!(
+                    "Function reference {} is not declared in the extensions of the plan",
+                    reference
+                ))
+            })
+    }
+
+    /// The arguments of a function, the deprecated args are used if there is no arguments.
+    fn function_args<'a>(
+        args: &'a [substrait::Expression],
+        arguments: &'a [substrait::FunctionArgument],
+    ) -> Vec<&'a substrait::Expression> {
+        match arguments.is_empty() {
+            true => args.iter().collect(),
+            false => arguments
+                .iter()
+                .filter_map(|argument| match &argument.arg_type {
+                    Some(ArgType::Value(expr)) => {
+                        let expr: &substrait::Expression = expr;
+                        Some(expr)
+                    }
+                    None => None,
+                })
+                .collect(),
+        }
+    }
+
+    fn aggregate(
+        input: &PlanNode,
+        aggr_exprs: &[Expression],
+        group_by_exprs: &[Expression],
+    ) -> Result<PlanNode> {
+        let aggr_projection_exprs = group_by_exprs
+            .iter()
+            .chain(aggr_exprs.iter())
+            .cloned()
+            .collect::<Vec<_>>();
+        let before_aggr_exprs = expand_aggregate_arg_exprs(&aggr_projection_exprs);
+        let input = Self::expression(input, &before_aggr_exprs, "Before GroupBy")?;
+
+        let rebase = |exprs: &[Expression]| {
+            exprs
+                .iter()
+                .map(|expr| rebase_expr_from_input(expr, &input.schema()))
+                .collect::<Result<Vec<_>>>()
+        };
+        let (aggr_exprs, group_by_exprs) = (rebase(aggr_exprs)?, rebase(group_by_exprs)?);
+        let plan = PlanBuilder::from(&input)
+            .aggregate_partial(&aggr_exprs, &group_by_exprs)?
+            .aggregate_final(input.schema(), &aggr_exprs, &group_by_exprs)?
+            .build()?;
+
+        // The output of AggregateRel is the grouping columns followed by the measures.
+        let columns = group_by_exprs
+            .iter()
+            .chain(aggr_exprs.iter())
+            .map(expr_as_column_expr)
+            .collect::<Result<Vec<_>>>()?;
+        Self::project(&plan, &columns)
+    }
+
+    /// Apply a expression against exprs, the same as the expression stages of a SELECT.
+    fn expression(input: &PlanNode, exprs: &[Expression], desc: &str) -> Result<PlanNode> {
+        let mut dedup_exprs = vec![];
+        for expr in exprs {
+            let rebased_expr = rebase_expr_from_input(expr, &input.schema())?;
+            let rebased_expr = sort_to_inner_expr(&rebased_expr);
+
+            if !dedup_exprs.contains(&rebased_expr) {
+                dedup_exprs.push(rebased_expr);
+            }
+        }
+
+        // if all expression is column expression expression, we skip this expression
+        if dedup_exprs
+            .iter()
+            .all(|expr| matches!(expr, Expression::Column(_)))
+        {
+            return Ok(input.clone());
+        }
+
+        PlanBuilder::from(input)
+            .expression(&dedup_exprs, desc)
+            .and_then(|builder| builder.build())
+    }
+
+    fn project(input: &PlanNode, exprs: &[Expression]) -> Result<PlanNode> {
+        let exprs = exprs
+            .iter()
+            .map(|expr| rebase_expr_from_input(expr, &input.schema()))
+            .collect::<Result<Vec<_>>>()?;
+
+        PlanBuilder::from(input)
+            .project(&exprs)
+            .and_then(|builder| builder.build())
+    }
+
+    /// Rename the output columns by the names of the RelRoot.
+    fn rename(input: &PlanNode, names: &[String]) -> Result<PlanNode> {
+        if names.is_empty() {
+            return Ok(input.clone());
+        }
+
+        let schema = input.schema();
+        if names.len() != schema.fields().len() {
+            return Err(ErrorCode::BadArguments(format!(
+                "RelRoot has {} names, but the relation has {} columns",
+                names.len(),
+                schema.fields().len()
+            )));
+        }
+
+        let exprs = schema
+            .fields()
+            .iter()
+            .zip(names.iter())
+            .map(|(field, name)| {
+                Expression::Alias(
+                    name.clone(),
+                    Box::new(Expression::Column(field.name().clone())),
+                )
+            })
+            .collect::<Vec<_>>();
+        PlanBuilder::from(input).project(&exprs)?.build()
+    }
+
+    fn input_columns(input: &PlanNode) -> Vec<Expression> {
+        input
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expression::Column(field.name().clone()))
+            .collect()
+    }
+}
+
+fn required<'a, T>(value: &'a Option<T>, name: &str) -> Result<&'a T> {
+    value.as_ref().ok_or_else(|| missing(name))
+}
+
+fn missing(name: &str) -> ErrorCode {
+    ErrorCode::BadArguments(format!("{} of the Substrait plan is missing", name))
+}
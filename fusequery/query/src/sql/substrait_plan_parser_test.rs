@@ -0,0 +1,299 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_datablocks::assert_blocks_eq;
+use common_exception::Result;
+use common_flights::substrait::aggregate_rel;
+use common_flights::substrait::expression;
+use common_flights::substrait::function_argument::ArgType;
+use common_flights::substrait::plan_rel;
+use common_flights::substrait::read_rel;
+use common_flights::substrait::rel::RelType;
+use common_flights::substrait::simple_extension_declaration;
+use common_flights::substrait::sort_field;
+use common_flights::substrait::*;
+use common_runtime::tokio;
+use futures::TryStreamExt;
+use prost::Message;
+
+use crate::interpreters::InterpreterFactory;
+use crate::sessions::FuseQueryContextRef;
+use crate::sql::PlanParser;
+use crate::sql::SubstraitPlanParser;
+
+const ADD: u32 = 1;
+const GT: u32 = 2;
+const SUM: u32 = 3;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_substrait_plan_parser() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    for query in &[
+        "create table default.t1(a bigint, b bigint) Engine = Memory",
+        "insert into default.t1 select number, number % 2 from numbers(5)",
+    ] {
+        execute(&ctx, PlanParser::create(ctx.clone()).build_from_sql(query)?).await?;
+    }
+
+    // Read, filter, aggregate, sort and the names of the root:
+    // SELECT b, sum(a) AS s FROM t1 WHERE a > 0 GROUP BY b ORDER BY s DESC
+    {
+        let filter = rel(RelType::Filter(
+            FilterRel {
+                input: Some(read(&["t1"]).into()),
+                condition: Some(function(GT, vec![field(0), literal(0)])),
+            }
+            .into(),
+        ));
+        let aggregate = rel(RelType::Aggregate(
+            AggregateRel {
+                input: Some(filter.into()),
+                groupings: vec![aggregate_rel::Grouping {
+                    grouping_expressions: vec![field(1)],
+                }],
+                measures: vec![aggregate_rel::Measure {
+                    measure: Some(AggregateFunction {
+                        function_reference: SUM,
+                        args: vec![],
+                        invocation: 0,
+                        arguments: vec![argument(field(0))],
+                    }),
+                }],
+            }
+            .into(),
+        ));
+        let sort = rel(RelType::Sort(
+            SortRel {
+                input: Some(aggregate.into()),
+                sorts: vec![SortField {
+                    expr: Some(field(1)),
+                    sort_kind: Some(sort_field::SortKind::Direction(
+                        sort_field::SortDirection::DescNullsLast as i32,
+                    )),
+                }],
+            }
+            .into(),
+        ));
+        let plan = create_plan(plan_rel::RelType::Root(RelRoot {
+            input: Some(sort.into()),
+            names: vec![String::from("b"), String::from("s")],
+        }));
+
+        let mut bytes = vec![];
+        plan.encode(&mut bytes).unwrap();
+        let plan = SubstraitPlanParser::create(ctx.clone()).build_from_bytes(&bytes)?;
+        let expected = vec![
+            "+---+---+",
+            "| b | s |",
+            "+---+---+",
+            "| 0 | 6 |",
+            "| 1 | 4 |",
+            "+---+---+",
+        ];
+        assert_blocks_eq(expected, &execute(&ctx, plan).await?);
+    }
+
+    // Project and fetch:
+    // SELECT a, b, a + b FROM t1 LIMIT 2 OFFSET 1
+    {
+        let project = rel(RelType::Project(
+            ProjectRel {
+                input: Some(read(&["default", "t1"]).into()),
+                expressions: vec![function(ADD, vec![field(0), field(1)])],
+            }
+            .into(),
+        ));
+        let fetch = rel(RelType::Fetch(
+            FetchRel {
+                input: Some(project.into()),
+                offset: 1,
+                count: 2,
+            }
+            .into(),
+        ));
+        let plan = create_plan(plan_rel::RelType::Rel(fetch));
+
+        let plan = SubstraitPlanParser::create(ctx.clone()).build_from_plan(&plan)?;
+        let expected = vec![
+            "+---+---+---------+",
+            "| a | b | (a + b) |",
+            "+---+---+---------+",
+            "| 1 | 1 | 2       |",
+            "| 2 | 0 | 2       |",
+            "+---+---+---------+",
+        ];
+        assert_blocks_eq(expected, &execute(&ctx, plan).await?);
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_substrait_plan_parser_errors() -> Result<()> {
+    let ctx = crate::tests::try_create_context()?;
+    let parser = SubstraitPlanParser::create(ctx.clone());
+
+    struct Test {
+        name: &'static str,
+        plan: Plan,
+        error: &'static str,
+    }
+
+    let filter = |condition: Expression| {
+        rel(RelType::Filter(
+            FilterRel {
+                input: Some(read(&["system", "one"]).into()),
+                condition: Some(condition),
+            }
+            .into(),
+        ))
+    };
+
+    let tests = vec![
+        Test {
+            name: "no-relations",
+            plan: Plan {
+                extensions: vec![],
+                relations: vec![],
+            },
+            error: "Code: 2, displayText = Substrait plan must have exactly one relation, but got 0.",
+        },
+        Test {
+            name: "unknown-table",
+            plan: create_plan(plan_rel::RelType::Rel(read(&["system", "not_exists"]))),
+            error: "Code: 25, displayText = Unknown table: 'not_exists'.",
+        },
+        Test {
+            name: "undeclared-function",
+            plan: create_plan(plan_rel::RelType::Rel(filter(function(100, vec![])))),
+            error: "Code: 6, displayText = Function reference 100 is not declared in the extensions of the plan.",
+        },
+        Test {
+            name: "field-out-of-range",
+            plan: create_plan(plan_rel::RelType::Rel(filter(function(GT, vec![
+                field(1),
+                literal(0),
+            ])))),
+            error: "Code: 6, displayText = Field reference 1 is out of the 1 fields of the input.",
+        },
+        Test {
+            name: "missing-condition",
+            plan: create_plan(plan_rel::RelType::Rel(rel(RelType::Filter(
+                FilterRel {
+                    input: Some(read(&["system", "one"]).into()),
+                    condition: None,
+                }
+                .into(),
+            )))),
+            error: "Code: 6, displayText = FilterRel.condition of the Substrait plan is missing.",
+        },
+    ];
+
+    for test in tests {
+        match parser.build_from_plan(&test.plan) {
+            Ok(_) => assert!(false, "{} must be failed", test.name),
+            Err(e) => assert_eq!(test.error, e.to_string(), "{}", test.name),
+        }
+    }
+
+    assert!(parser.build_from_bytes(b"not a plan").is_err());
+    Ok(())
+}
+
+async fn execute(
+    ctx: &FuseQueryContextRef,
+    plan: common_planners::PlanNode,
+) -> Result<Vec<common_datablocks::DataBlock>> {
+    let interpreter = InterpreterFactory::get(ctx.clone(), plan)?;
+    interpreter.execute().await?.try_collect::<Vec<_>>().await
+}
+
+fn create_plan(relation: plan_rel::RelType) -> Plan {
+    let extension = |anchor: u32, name: &str| SimpleExtensionDeclaration {
+        mapping_type: Some(
+            simple_extension_declaration::MappingType::ExtensionFunction(
+                simple_extension_declaration::ExtensionFunction {
+                    extension_uri_reference: 0,
+                    function_anchor: anchor,
+                    name: name.to_string(),
+                },
+            ),
+        ),
+    };
+
+    Plan {
+        extensions: vec![
+            extension(ADD, "add:i64_i64"),
+            extension(GT, "gt"),
+            extension(SUM, "sum"),
+        ],
+        relations: vec![PlanRel {
+            rel_type: Some(relation),
+        }],
+    }
+}
+
+fn rel(rel_type: RelType) -> Rel {
+    Rel {
+        rel_type: Some(rel_type),
+    }
+}
+
+fn read(names: &[&str]) -> Rel {
+    rel(RelType::Read(
+        ReadRel {
+            filter: None,
+            read_type: Some(read_rel::ReadType::NamedTable(read_rel::NamedTable {
+                names: names.iter().map(|name| name.to_string()).collect(),
+            })),
+        }
+        .into(),
+    ))
+}
+
+fn field(index: i32) -> Expression {
+    Expression {
+        rex_type: Some(expression::RexType::Selection(expression::FieldReference {
+            reference_type: Some(expression::field_reference::ReferenceType::DirectReference(
+                expression::ReferenceSegment {
+                    reference_type: Some(
+                        expression::reference_segment::ReferenceType::StructField(
+                            expression::reference_segment::StructField { field: index },
+                        ),
+                    ),
+                },
+            )),
+            root_type: Some(expression::field_reference::RootType::RootReference(
+                expression::field_reference::RootReference {},
+            )),
+        })),
+    }
+}
+
+fn literal(value: i64) -> Expression {
+    Expression {
+        rex_type: Some(expression::RexType::Literal(expression::Literal {
+            literal_type: Some(expression::literal::LiteralType::I64(value)),
+        })),
+    }
+}
+
+fn argument(value: Expression) -> FunctionArgument {
+    FunctionArgument {
+        arg_type: Some(ArgType::Value(value.into())),
+    }
+}
+
+fn function(reference: u32, args: Vec<Expression>) -> Expression {
+    Expression {
+        rex_type: Some(expression::RexType::ScalarFunction(
+            expression::ScalarFunction {
+                function_reference: reference,
+                args: vec![],
+                arguments: args.into_iter().map(argument).collect(),
+            }
+            .into(),
+        )),
+    }
+}
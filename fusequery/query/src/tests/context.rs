@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
 use std::env;
 
 use common_exception::ErrorCode;
@@ -36,6 +37,7 @@ pub struct ClusterNode {
     name: String,
     priority: u8,
     address: String,
+    labels: BTreeMap<String, String>,
 }
 
 impl ClusterNode {
@@ -44,8 +46,17 @@ impl ClusterNode {
             name: name.to_string(),
             priority,
             address: address.to_string(),
+            labels: BTreeMap::new(),
         }
     }
+
+    pub fn with_labels(mut self, labels: &[(&str, &str)]) -> ClusterNode {
+        self.labels = labels
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        self
+    }
 }
 
 pub fn try_create_cluster_context(nodes: &[ClusterNode]) -> Result<FuseQueryContextRef> {
@@ -59,7 +70,12 @@ pub fn try_create_cluster_context(nodes: &[ClusterNode]) -> Result<FuseQueryCont
             let runtime = Runtime::new()
                 .map_err_to_code(ErrorCode::TokioError, || "Cannot create tokio runtime.")?;
 
-            runtime.block_on(cluster.add_node(&node.name, node.priority, &node.address))
+            runtime.block_on(cluster.add_node_with_labels(
+                &node.name,
+                node.priority,
+                &node.address,
+                node.labels,
+            ))
         })
         .join()
         .unwrap()?;
@@ -52,13 +52,17 @@ pub type FlightStream<T> =
 /// StoreFlightImpl provides data access API-s for FuseQuery, in arrow-flight protocol.
 pub struct StoreFlightImpl {
     token: FlightToken,
+    username: String,
+    password: String,
     action_handler: ActionHandler,
 }
 
 impl StoreFlightImpl {
-    pub fn create(_conf: Config, fs: Arc<dyn FileSystem>, meta_node: Arc<MetaNode>) -> Self {
+    pub fn create(conf: Config, fs: Arc<dyn FileSystem>, meta_node: Arc<MetaNode>) -> Self {
         Self {
             token: FlightToken::create(),
+            username: conf.flight_api_username,
+            password: conf.flight_api_password,
             // TODO pass in action handler
             action_handler: ActionHandler::create(fs, meta_node),
         }
@@ -96,8 +100,8 @@ impl FlightService for StoreFlightImpl {
         let auth = BasicAuth::decode(&*payload).map_err(|e| Status::internal(e.to_string()))?;
 
         // Check auth and create token.
-        let user = "root";
-        if auth.username == user {
+        let user = &self.username;
+        if auth.username == *user && (self.password.is_empty() || auth.password == self.password) {
             let claim = FlightClaim {
                 username: user.to_string(),
             };
@@ -114,7 +118,7 @@ impl FlightService for StoreFlightImpl {
             Ok(Response::new(Box::pin(output)))
         } else {
             Err(Status::unauthenticated(format!(
-                "Don't know user {}",
+                "Unknown user or wrong password of user {}",
                 auth.username
             )))
         }
@@ -188,10 +192,23 @@ impl FlightService for StoreFlightImpl {
 
         let (db_name, tbl_name) = common_flights::storage_api_impl::get_meta(meta)
             .map_err(|e| Status::internal(e.to_string()))?;
+        let bucket = common_flights::storage_api_impl::get_bucket_meta(meta)
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let tx_id = common_flights::storage_api_impl::get_tx_meta(meta)
+            .map_err(|e| Status::internal(e.to_string()))?;
+        let deduplicate = common_flights::storage_api_impl::get_deduplicate_meta(meta)
+            .map_err(|e| Status::internal(e.to_string()))?;
 
         let append_res = self
             .action_handler
-            .do_put(db_name, tbl_name, request.into_inner())
+            .do_put(
+                db_name,
+                tbl_name,
+                bucket,
+                tx_id,
+                deduplicate,
+                request.into_inner(),
+            )
             .await
             .map_err(|e| Status::internal(e.to_string()))?;
 
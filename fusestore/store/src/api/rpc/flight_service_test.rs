@@ -94,7 +94,6 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
     use common_planners::CreateDatabasePlan;
     use common_planners::CreateTablePlan;
     use common_planners::DatabaseEngineType;
-    use common_planners::TableEngineType;
 
     tracing::info!("init logging");
 
@@ -138,10 +137,11 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
             db: db_name.to_string(),
             table: tbl_name.to_string(),
             schema: schema.clone(),
-            // TODO check get_table
-            options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
-            // TODO
-            engine: TableEngineType::JsonEachRaw,
+            options: maplit::hashmap! {
+                "opt‐1".into() => "val-1".into(),
+                "comment".into() => "tb2 comment".into(),
+            },
+            engine: "JSON".to_string(),
         };
 
         {
@@ -158,6 +158,9 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
                 db: db_name.into(),
                 name: tbl_name.into(),
                 schema: schema.clone(),
+                comment: "tb2 comment".into(),
+                engine: "JSON".into(),
+                options: plan.options.clone(),
             };
             assert_eq!(want, got, "get created table");
         }
@@ -177,6 +180,9 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
                 db: db_name.into(),
                 name: tbl_name.into(),
                 schema: schema.clone(),
+                comment: "tb2 comment".into(),
+                engine: "JSON".into(),
+                options: plan.options.clone(),
             };
             assert_eq!(want, got, "get created table");
         }
@@ -202,6 +208,9 @@ async fn test_flight_create_get_table() -> anyhow::Result<()> {
                 db: db_name.into(),
                 name: tbl_name.into(),
                 schema: schema.clone(),
+                comment: "tb2 comment".into(),
+                engine: "JSON".into(),
+                options: plan.options.clone(),
             };
             assert_eq!(want, got, "get old table");
         }
@@ -220,7 +229,6 @@ async fn test_do_append() -> anyhow::Result<()> {
     use common_planners::CreateDatabasePlan;
     use common_planners::CreateTablePlan;
     use common_planners::DatabaseEngineType;
-    use common_planners::TableEngineType;
 
     let (_tc, addr) = crate::tests::start_store_server().await?;
 
@@ -238,6 +246,7 @@ async fn test_do_append() -> anyhow::Result<()> {
     let expected_cols = 2;
 
     let block = DataBlock::create_by_array(schema.clone(), vec![col0, col1]);
+    let block_copy = block.clone();
     let batches = vec![block.clone(), block];
     let num_batch = batches.len();
     let stream = futures::stream::iter(batches);
@@ -259,7 +268,7 @@ async fn test_do_append() -> anyhow::Result<()> {
             table: tbl_name.to_string(),
             schema: schema.clone(),
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
-            engine: TableEngineType::Parquet,
+            engine: "Parquet".to_string(),
         };
         client.create_table(plan.clone()).await.unwrap();
     }
@@ -267,7 +276,10 @@ async fn test_do_append() -> anyhow::Result<()> {
         .append_data(
             db_name.to_string(),
             tbl_name.to_string(),
-            schema,
+            schema.clone(),
+            None,
+            None,
+            false,
             Box::pin(stream),
         )
         .await
@@ -280,6 +292,135 @@ async fn test_do_append() -> anyhow::Result<()> {
         assert_eq!(p.rows, expected_rows / num_batch);
         assert_eq!(p.cols, expected_cols);
     });
+
+    // The same block appended again without deduplication is kept.
+    let stream = futures::stream::iter(vec![block_copy.clone()]);
+    let res = client
+        .append_data(
+            db_name.to_string(),
+            tbl_name.to_string(),
+            schema.clone(),
+            None,
+            None,
+            false,
+            Box::pin(stream),
+        )
+        .await?;
+    assert_eq!(res.summary.rows, expected_rows / num_batch);
+    assert_eq!(res.parts.len(), 1);
+    assert_eq!(res.deduplicated_blocks, 0);
+
+    // A retry of the append with deduplication is skipped.
+    let stream = futures::stream::iter(vec![block_copy]);
+    let res = client
+        .append_data(
+            db_name.to_string(),
+            tbl_name.to_string(),
+            schema,
+            None,
+            None,
+            true,
+            Box::pin(stream),
+        )
+        .await?;
+    assert_eq!(res.summary.rows, 0);
+    assert_eq!(res.parts.len(), 0);
+    assert_eq!(res.deduplicated_blocks, 1);
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_do_append_in_tx() -> anyhow::Result<()> {
+    // - Append in two transactions.
+    // - The parts are invisible until the transaction commits.
+    // - The parts of an aborted transaction are discarded.
+    common_tracing::init_default_tracing();
+    use std::sync::Arc;
+
+    use common_planners::CreateTablePlan;
+
+    let (_tc, addr) = crate::tests::start_store_server().await?;
+
+    let schema = Arc::new(DataSchema::new(vec![DataField::new(
+        "col_i",
+        DataType::Int64,
+        false,
+    )]));
+    let db_name = "test_db";
+    let tbl_name = "test_tbl";
+
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    {
+        let plan = CreateDatabasePlan {
+            if_not_exists: false,
+            db: db_name.to_string(),
+            engine: DatabaseEngineType::Local,
+            options: Default::default(),
+        };
+        client.create_database(plan.clone()).await?;
+        let plan = CreateTablePlan {
+            if_not_exists: false,
+            db: db_name.to_string(),
+            table: tbl_name.to_string(),
+            schema: schema.clone(),
+            options: Default::default(),
+            engine: "Parquet".to_string(),
+        };
+        client.create_table(plan.clone()).await?;
+    }
+
+    let scan = ScanPlan::empty();
+    for (tx_id, values) in [("tx-1", vec![1i64, 2]), ("tx-2", vec![3i64, 4])] {
+        let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(values)]);
+        let res = client
+            .append_data(
+                db_name.to_string(),
+                tbl_name.to_string(),
+                schema.clone(),
+                None,
+                Some(tx_id.to_string()),
+                false,
+                Box::pin(futures::stream::iter(vec![block])),
+            )
+            .await?;
+        assert_eq!(res.parts.len(), 1);
+    }
+    let parts = client
+        .read_plan(db_name.to_string(), tbl_name.to_string(), &scan)
+        .await?;
+    assert_eq!(None, parts, "staged parts are invisible");
+
+    let res = client.abort_append("tx-2".to_string()).await?;
+    assert_eq!(res.parts, 1);
+    let res = client.commit_append("tx-1".to_string()).await?;
+    assert_eq!(res.parts, 1);
+    let parts = client
+        .read_plan(db_name.to_string(), tbl_name.to_string(), &scan)
+        .await?
+        .unwrap_or_default();
+    assert_eq!(parts.len(), 1, "only the committed part is visible");
+
+    // committing an aborted transaction does nothing
+    let res = client.commit_append("tx-2".to_string()).await?;
+    assert_eq!(res.parts, 0);
+
+    // a retry of the committed append is deduplicated when its transaction commits
+    let block = DataBlock::create_by_array(schema.clone(), vec![Series::new(vec![1i64, 2])]);
+    let res = client
+        .append_data(
+            db_name.to_string(),
+            tbl_name.to_string(),
+            schema.clone(),
+            None,
+            Some("tx-3".to_string()),
+            true,
+            Box::pin(futures::stream::iter(vec![block])),
+        )
+        .await?;
+    assert_eq!(res.parts.len(), 1);
+    let res = client.commit_append("tx-3".to_string()).await?;
+    assert_eq!(res.parts, 0);
+    assert_eq!(res.deduplicated_blocks, 1);
     Ok(())
 }
 
@@ -293,7 +434,6 @@ async fn test_scan_partition() -> anyhow::Result<()> {
     use common_planners::CreateDatabasePlan;
     use common_planners::CreateTablePlan;
     use common_planners::DatabaseEngineType;
-    use common_planners::TableEngineType;
 
     let (_tc, addr) = crate::tests::start_store_server().await?;
 
@@ -333,7 +473,7 @@ async fn test_scan_partition() -> anyhow::Result<()> {
             table: tbl_name.to_string(),
             schema: schema.clone(),
             options: maplit::hashmap! {"opt‐1".into() => "val-1".into()},
-            engine: TableEngineType::Parquet,
+            engine: "Parquet".to_string(),
         };
         client.create_table(plan.clone()).await?;
     }
@@ -342,6 +482,9 @@ async fn test_scan_partition() -> anyhow::Result<()> {
             db_name.to_string(),
             tbl_name.to_string(),
             schema,
+            None,
+            None,
+            false,
             Box::pin(stream),
         )
         .await?;
@@ -367,6 +510,156 @@ async fn test_scan_partition() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_read_partition() -> anyhow::Result<()> {
+    common_tracing::init_default_tracing();
+    use std::sync::Arc;
+
+    use common_flights::storage_api_impl::ReadAction;
+    use common_planners::col;
+    use common_planners::lit;
+    use common_planners::sum;
+    use common_planners::CreateTablePlan;
+    use common_planners::Extras;
+    use common_planners::PlanBuilder;
+    use common_planners::PlanNode;
+    use common_planners::ReadDataSourcePlan;
+    use futures::TryStreamExt;
+
+    let (_tc, addr) = crate::tests::start_store_server().await?;
+
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("col_i", DataType::Int64, false),
+        DataField::new("col_s", DataType::Utf8, false),
+    ]);
+    let db_name = "test_db";
+    let tbl_name = "test_tbl";
+
+    let mut client = StoreClient::try_create(addr.as_str(), "root", "xxx").await?;
+    {
+        let plan = CreateDatabasePlan {
+            if_not_exists: false,
+            db: db_name.to_string(),
+            engine: DatabaseEngineType::Local,
+            options: Default::default(),
+        };
+        client.create_database(plan.clone()).await?;
+        let plan = CreateTablePlan {
+            if_not_exists: false,
+            db: db_name.to_string(),
+            table: tbl_name.to_string(),
+            schema: schema.clone(),
+            options: Default::default(),
+            engine: "Parquet".to_string(),
+        };
+        client.create_table(plan.clone()).await?;
+    }
+
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![0i64, 1, 2, 3, 4]),
+        Series::new(vec!["s0", "s1", "s2", "s3", "s4"]),
+    ]);
+    client
+        .append_data(
+            db_name.to_string(),
+            tbl_name.to_string(),
+            schema.clone(),
+            None,
+            None,
+            false,
+            Box::pin(futures::stream::iter(vec![block])),
+        )
+        .await?;
+    let parts = client
+        .read_plan(
+            db_name.to_string(),
+            tbl_name.to_string(),
+            &ScanPlan::empty(),
+        )
+        .await?
+        .unwrap_or_default();
+    assert_eq!(parts.len(), 1);
+
+    // Only the first column is read, the rows not passing the filter are not sent, and the blocks
+    // have at most 2 rows: [0, 1] is skipped, [2, 3] and [4] are sent.
+    let projected_schema = DataSchemaRefExt::create(vec![schema.field(0).clone()]);
+    let action = ReadAction {
+        part: parts[0].part.clone(),
+        push_down: PlanNode::ReadSource(ReadDataSourcePlan {
+            db: db_name.to_string(),
+            table: tbl_name.to_string(),
+            schema: projected_schema.clone(),
+            scan_plan: Arc::new(ScanPlan {
+                push_downs: Extras {
+                    projection: Some(vec![0]),
+                    filters: vec![col("col_i").gt(lit(1i64))],
+                    ..Extras::default()
+                },
+                ..ScanPlan::empty()
+            }),
+            remote: true,
+            ..ReadDataSourcePlan::empty()
+        }),
+        batch_size: Some(2),
+        window: Some(1),
+    };
+    let blocks = client
+        .read_partition(projected_schema.clone(), &action)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    let rows = blocks.iter().map(|b| b.num_rows()).collect::<Vec<_>>();
+    assert_eq!(vec![2, 1], rows);
+    assert!(blocks.iter().all(|b| b.num_columns() == 1));
+    let expected = vec![
+        "+-------+",
+        "| col_i |",
+        "+-------+",
+        "| 2     |",
+        "| 3     |",
+        "| 4     |",
+        "+-------+",
+    ];
+    common_datablocks::assert_blocks_eq(expected, &blocks);
+
+    // The store plan is executed on the rows of the part, the state of the partial aggregate is
+    // sent in one block.
+    let read = PlanNode::ReadSource(ReadDataSourcePlan {
+        db: db_name.to_string(),
+        table: tbl_name.to_string(),
+        schema: projected_schema,
+        scan_plan: Arc::new(ScanPlan {
+            push_downs: Extras {
+                projection: Some(vec![0]),
+                ..Extras::default()
+            },
+            ..ScanPlan::empty()
+        }),
+        remote: true,
+        ..ReadDataSourcePlan::empty()
+    });
+    let store_plan = PlanBuilder::from(&read)
+        .filter(col("col_i").gt(lit(1i64)))?
+        .aggregate_partial(&[sum(col("col_i"))], &[])?
+        .build()?;
+    let action = ReadAction {
+        part: parts[0].part.clone(),
+        push_down: store_plan.clone(),
+        batch_size: Some(2),
+        window: Some(1),
+    };
+    let blocks = client
+        .read_partition(store_plan.schema(), &action)
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(1, blocks.len());
+    assert_eq!(1, blocks[0].num_rows());
+    assert_eq!(store_plan.schema(), blocks[0].schema().clone());
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_flight_generic_kv() -> anyhow::Result<()> {
     common_tracing::init_default_tracing();
@@ -31,6 +31,7 @@ impl StoreServer {
             .parse::<std::net::SocketAddr>()?;
 
         tracing::info!("flight addr: {}", addr);
+        let labels = self.conf.node_labels()?;
 
         // TODO(xp): add local fs dir to config and use it.
         let p = tempfile::tempdir()?;
@@ -46,9 +47,20 @@ impl StoreServer {
 
         tracing::info!("boot done");
 
-        let dfs = Dfs::create(fs, mn.clone());
+        // Report the liveness of this node, and replicate the files of the dead nodes.
+        MetaNode::start_node_health(
+            mn.clone(),
+            &self.conf,
+            self.conf.flight_api_address.clone(),
+            labels,
+        )
+        .await;
+        // Publish the raft status to the metrics.
+        MetaNode::start_status_metrics(mn.clone()).await;
+        let dfs = Arc::new(Dfs::create(fs, mn.clone(), &self.conf));
+        Dfs::start_pulling(dfs.clone(), &self.conf).await;
 
-        let flight_impl = StoreFlightImpl::create(self.conf.clone(), Arc::new(dfs), mn);
+        let flight_impl = StoreFlightImpl::create(self.conf.clone(), dfs, mn);
         let flight_srv = FlightServiceServer::new(flight_impl);
 
         Server::builder()
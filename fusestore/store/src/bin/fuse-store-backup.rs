@@ -0,0 +1,78 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+//! A small tool to back up the meta data(databases, tables and their parts) of a FuseStore cluster
+//! to a file and to restore it on a fresh cluster.
+//!
+//! fuse-store-backup --store-address 127.0.0.1:9191 export --file meta.dump
+//! fuse-store-backup --store-address 127.0.0.1:9191 import --file meta.dump
+
+use common_flights::MetaApi;
+use common_flights::StoreClient;
+use common_runtime::tokio;
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+#[structopt(
+    name = "fuse-store-backup",
+    about = "Back up and restore FuseStore meta data"
+)]
+struct Opt {
+    #[structopt(
+        long,
+        env = "FUSE_STORE_FLIGHT_API_ADDRESS",
+        default_value = "127.0.0.1:9191"
+    )]
+    store_address: String,
+
+    #[structopt(long, default_value = "root")]
+    username: String,
+
+    #[structopt(long, default_value = "")]
+    password: String,
+
+    #[structopt(subcommand)]
+    cmd: Command,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Export the meta data of a cluster to a file.
+    Export {
+        #[structopt(long)]
+        file: String,
+    },
+
+    /// Restore the meta data from a file into a cluster with an empty catalog.
+    Import {
+        #[structopt(long)]
+        file: String,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let opt = Opt::from_args();
+
+    let mut client =
+        StoreClient::try_create(opt.store_address.as_str(), &opt.username, &opt.password).await?;
+
+    match opt.cmd {
+        Command::Export { file } => {
+            let res = client.export_meta().await?;
+            std::fs::write(&file, &res.data)?;
+            println!("exported {} bytes of meta data to {}", res.data.len(), file);
+        }
+        Command::Import { file } => {
+            let data = std::fs::read(&file)?;
+            let res = client.import_meta(data).await?;
+            println!(
+                "imported {} databases, {} tables from {}",
+                res.databases, res.tables, file
+            );
+        }
+    }
+
+    Ok(())
+}
@@ -2,6 +2,9 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::collections::BTreeMap;
+
+use common_exception::ErrorCode;
 use lazy_static::lazy_static;
 use structopt::StructOpt;
 use structopt_toml::StructOptToml;
@@ -48,6 +51,22 @@ pub struct Config {
     )]
     pub flight_api_address: String,
 
+    #[structopt(
+        long,
+        env = "FUSE_STORE_FLIGHT_API_USERNAME",
+        default_value = "root",
+        help = "The user of the flight API, by which the nodes also pull the files from each other."
+    )]
+    pub flight_api_username: String,
+
+    #[structopt(
+        long,
+        env = "FUSE_STORE_FLIGHT_API_PASSWORD",
+        default_value = "",
+        help = "The password of the user of the flight API, an empty password is not checked."
+    )]
+    pub flight_api_password: String,
+
     #[structopt(
         long,
         env = "FUSE_STORE_META_API_HOST",
@@ -90,6 +109,32 @@ pub struct Config {
     )]
     pub heartbeat_interval: u64,
 
+    #[structopt(
+        long,
+        env = "FUSE_STORE_NODE_HEARTBEAT_INTERVAL",
+        default_value = "3000",
+        help = "The interval in milli seconds at which a node reports that it is alive to the meta service."
+    )]
+    pub node_heartbeat_interval: u64,
+
+    #[structopt(
+        long,
+        env = "FUSE_STORE_NODE_DEAD_TIMEOUT",
+        default_value = "30000",
+        help = concat!("A node is dead if it has not reported for this many milli seconds,",
+                      " the files of it are re-replicated to the healthy nodes.")
+    )]
+    pub node_dead_timeout: u64,
+
+    #[structopt(
+        long,
+        env = "FUSE_STORE_LABELS",
+        default_value = "",
+        help = concat!("The labels of this node, e.g. `zone=z1,rack=r1`. The copies of a file are",
+                      " placed in the different values of the failure domain label of the placement policy.")
+    )]
+    pub labels: String,
+
     #[structopt(
         long,
         env = "FUSE_STORE_BOOT",
@@ -111,4 +156,26 @@ impl Config {
     pub fn meta_api_addr(&self) -> String {
         format!("{}:{}", self.meta_api_host, self.meta_api_port)
     }
+
+    /// Parses the `labels` of the form `k1=v1,k2=v2` to the label map of this node.
+    pub fn node_labels(&self) -> common_exception::Result<BTreeMap<String, String>> {
+        let mut labels = BTreeMap::new();
+        for label in self.labels.split(',').map(str::trim) {
+            if label.is_empty() {
+                continue;
+            }
+            match label.split_once('=') {
+                Some((k, v)) if !k.trim().is_empty() => {
+                    labels.insert(k.trim().to_string(), v.trim().to_string());
+                }
+                _ => {
+                    return Err(ErrorCode::InvalidConfig(format!(
+                        "invalid label: {}, expects key=value",
+                        label
+                    )))
+                }
+            }
+        }
+        Ok(labels)
+    }
 }
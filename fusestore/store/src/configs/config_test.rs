@@ -8,3 +8,22 @@ fn test_fuse_commit_version() -> anyhow::Result<()> {
     assert!(v.len() > 0);
     Ok(())
 }
+
+#[test]
+fn test_node_labels() -> anyhow::Result<()> {
+    let mut conf = crate::configs::Config::empty();
+    assert!(conf.node_labels()?.is_empty());
+
+    conf.labels = "zone=z1, rack = r1,".to_string();
+    let labels = conf.node_labels()?;
+    assert_eq!(2, labels.len());
+    assert_eq!(Some(&"z1".to_string()), labels.get("zone"));
+    assert_eq!(Some(&"r1".to_string()), labels.get("rack"));
+
+    conf.labels = "zone".to_string();
+    assert_eq!(
+        "Code: 2301, displayText = invalid label: zone, expects key=value.",
+        conf.node_labels().unwrap_err().to_string()
+    );
+    Ok(())
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 //
 
+use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::sync::Arc;
 
@@ -15,8 +16,11 @@ use common_arrow::arrow_flight::FlightData;
 use common_arrow::parquet::arrow::ArrowWriter;
 use common_arrow::parquet::file::writer::InMemoryWriteableCursor;
 use common_datablocks::DataBlock;
+use common_flights::storage_api_impl::part_file_name;
 use common_flights::storage_api_impl::AppendResult;
+use common_flights::storage_api_impl::ColumnStatistics;
 use futures::StreamExt;
+use sha2::Digest;
 use uuid::Uuid;
 
 use crate::fs::FileSystem;
@@ -35,23 +39,35 @@ impl Appender {
     /// Assumes
     /// - upstream caller has properly batched data
     /// - first element of the incoming stream is a properly serialized schema
-    pub async fn append_data(&self, path: String, mut stream: InputData) -> Result<AppendResult> {
+    /// - all the rows are in the `bucket` if it is given, the parts are named with the bucket
+    ///
+    /// The parts are stamped with the hashes of their blocks, by which the meta deduplicates them.
+    pub async fn append_data(
+        &self,
+        path: String,
+        bucket: Option<u64>,
+        mut stream: InputData,
+    ) -> Result<AppendResult> {
         if let Some(flight_data) = stream.next().await {
             let arrow_schema = ArrowSchema::try_from(&flight_data)?;
             let arrow_schema_ref = Arc::new(arrow_schema);
 
             let mut result = AppendResult::default();
             while let Some(flight_data) = stream.next().await {
+                let hash = block_hash(&flight_data);
                 let batch =
                     flight_data_to_arrow_batch(&flight_data, arrow_schema_ref.clone(), &[])?;
                 let block = DataBlock::try_from(batch)?;
                 let (rows, cols, wire_bytes) =
                     (block.num_rows(), block.num_columns(), block.memory_size());
-                let part_uuid = Uuid::new_v4().to_simple().to_string() + ".parquet";
-                let location = format!("{}/{}", path, part_uuid);
+                let part_uuid = Uuid::new_v4().to_simple().to_string();
+                let location = format!("{}/{}", path, part_file_name(bucket, &part_uuid));
+                let col_stats = column_statistics(&block);
                 let buffer = write_in_memory(block)?;
 
-                result.append_part(&location, rows, cols, wire_bytes, buffer.len());
+                result
+                    .append_part(&location, rows, cols, wire_bytes, buffer.len(), &hash)
+                    .col_stats = col_stats;
 
                 self.fs.add(&location, &buffer).await?;
             }
@@ -62,6 +78,32 @@ impl Appender {
     }
 }
 
+/// The content hash of a block, computed on the serialized data.
+pub(crate) fn block_hash(flight_data: &FlightData) -> String {
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(&flight_data.data_header);
+    hasher.update(&flight_data.data_body);
+    format!("{:x}", hasher.finalize())
+}
+
+/// The minimum and the maximum of the columns of a block, the columns of the types without an
+/// order and the columns of only null values are left out.
+pub(crate) fn column_statistics(block: &DataBlock) -> HashMap<String, ColumnStatistics> {
+    let mut col_stats = HashMap::new();
+    for (field, column) in block.schema().fields().iter().zip(block.columns()) {
+        let series = match column.to_array() {
+            Ok(series) => series,
+            Err(_) => continue,
+        };
+        if let (Ok(min), Ok(max)) = (series.min(), series.max()) {
+            if !min.is_null() && !max.is_null() {
+                col_stats.insert(field.name().clone(), ColumnStatistics { min, max });
+            }
+        }
+    }
+    col_stats
+}
+
 pub(crate) fn write_in_memory(block: DataBlock) -> Result<Vec<u8>> {
     let cursor = InMemoryWriteableCursor::default();
     {
@@ -78,9 +78,74 @@ mod test {
             flight_data_from_arrow_batch(&batch, &default_ipc_write_opt).1, // ignore dict
         ]);
         let r = appender
-            .append_data("test_tbl".to_string(), Box::pin(req))
+            .append_data("test_tbl".to_string(), None, Box::pin(req))
             .await;
         assert!(r.is_ok());
+
+        // The parts of a bucket are named with the bucket.
+        let req = futures::stream::iter(vec![
+            SchemaAsIpc::new(&schema, &default_ipc_write_opt).into(),
+            flight_data_from_arrow_batch(&batch, &default_ipc_write_opt).1,
+        ]);
+        let r = appender
+            .append_data("test_tbl".to_string(), Some(3), Box::pin(req))
+            .await?;
+        assert_eq!(r.parts.len(), 1);
+        assert!(r.parts[0].location.starts_with("test_tbl/bucket-3-"));
+
+        // The parts have the minimum and the maximum of their columns.
+        let col_stats = &r.parts[0].col_stats;
+        assert_eq!(col_stats["col0"].min, DataValue::Int64(Some(0)));
+        assert_eq!(col_stats["col0"].max, DataValue::Int64(Some(2)));
+        assert_eq!(
+            col_stats["col1"].min,
+            DataValue::Utf8(Some("str1".to_string()))
+        );
+        assert_eq!(
+            col_stats["col1"].max,
+            DataValue::Utf8(Some("str3".to_string()))
+        );
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_append_block_hash() -> anyhow::Result<()> {
+        let col0: ArrayRef = Arc::new(Int64Array::from(vec![0, 1, 2]));
+        let batch = RecordBatch::try_from_iter(vec![("col0", col0)])?;
+        let schema = batch.schema();
+
+        let p = tempfile::tempdir()?;
+        let fs = LocalFS::try_create(p.path().to_str().unwrap().to_string())?;
+        let appender = Appender::new(Arc::new(fs));
+        let opt = IpcWriteOptions::default();
+
+        let req = futures::stream::iter(vec![
+            SchemaAsIpc::new(&schema, &opt).into(),
+            flight_data_from_arrow_batch(&batch, &opt).1,
+        ]);
+        let r = appender
+            .append_data("test_tbl".to_string(), None, Box::pin(req))
+            .await?;
+        assert_eq!(r.parts.len(), 1);
+        assert_eq!(r.deduplicated_blocks, 0);
+        assert_eq!(
+            r.parts[0].block_hash,
+            block_hash(&flight_data_from_arrow_batch(&batch, &opt).1)
+        );
+
+        // A retry of the append writes a new part of the same hash, the meta deduplicates it.
+        let first = r.parts[0].clone();
+        let req = futures::stream::iter(vec![
+            SchemaAsIpc::new(&schema, &opt).into(),
+            flight_data_from_arrow_batch(&batch, &opt).1,
+        ]);
+        let r = appender
+            .append_data("test_tbl".to_string(), None, Box::pin(req))
+            .await?;
+        assert_eq!(r.parts.len(), 1);
+        assert_eq!(r.deduplicated_blocks, 0);
+        assert_eq!(r.parts[0].block_hash, first.block_hash);
+        assert_ne!(r.parts[0].location, first.location);
         Ok(())
     }
 }
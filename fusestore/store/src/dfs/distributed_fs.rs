@@ -5,12 +5,16 @@
 use std::ops::Bound::Included;
 use std::ops::Bound::Unbounded;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_trait::async_trait;
 use common_exception::exception;
 use common_exception::ErrorCode;
+use common_flights::StoreClient;
+use common_runtime::tokio;
 use common_tracing::tracing;
 
+use crate::configs::Config;
 use crate::fs::FileSystem;
 use crate::fs::ListResult;
 use crate::localfs::LocalFS;
@@ -21,7 +25,9 @@ use crate::meta_service::MetaNode;
 /// DFS is a distributed file system impl.
 /// When a file is added, it stores it locally, commit the this action into distributed meta data(something like a raft group).
 /// Then notifies client Ok.
-/// The replication is done by 2 other nodes, by subscribing meta data changes, and pulling the file.
+/// The replication is done by the other nodes of the slot of the file, which pull the files they
+/// do not have from the healthy nodes. When a node is dead, the meta service replaces it in the
+/// slots with the healthy nodes, which then pull the files of the dead node.
 /// TODO: There is a chance the node receiving the upload fails before replication is done, which results in a data loss.
 ///       A synchronous quorum write is required to solve this.
 pub struct Dfs {
@@ -29,18 +35,86 @@ pub struct Dfs {
     /// The distributed fs is a cluster of local-fs organized with a meta data service.
     pub local_fs: LocalFS,
     pub meta_node: Arc<MetaNode>,
+    /// The user and the password to pull the files from the flight API of the other nodes.
+    username: String,
+    password: String,
 }
 
 impl Dfs {
-    pub fn create(local_fs: LocalFS, meta_node: Arc<MetaNode>) -> Dfs {
+    pub fn create(local_fs: LocalFS, meta_node: Arc<MetaNode>, conf: &Config) -> Dfs {
         Dfs {
             local_fs,
             meta_node,
+            username: conf.flight_api_username.clone(),
+            password: conf.flight_api_password.clone(),
         }
     }
 }
 
-impl Dfs {}
+impl Dfs {
+    /// Pull the copies of the files this node stores by the placement but does not have, e.g.
+    /// the files of a slot which a dead node is replaced by this node in.
+    /// Returns the number of the pulled files.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn pull_missing_files(&self, now_ms: u64, timeout_ms: u64) -> usize {
+        let files = self.meta_node.get_files_of_node(now_ms, timeout_ms).await;
+
+        let mut pulled = 0;
+        for (key, addresses) in files.iter() {
+            if self.local_fs.exists(key) {
+                continue;
+            }
+
+            for address in addresses.iter() {
+                match self.pull_file(address, key).await {
+                    Ok(data) => {
+                        match self.local_fs.add(key, &data).await {
+                            Ok(_) => pulled += 1,
+                            Err(e) => tracing::warn!("fail to add pulled file {}: {}", key, e),
+                        }
+                        break;
+                    }
+                    Err(e) => tracing::info!("fail to pull {} from {}: {}", key, address, e),
+                }
+            }
+        }
+        pulled
+    }
+
+    async fn pull_file(&self, address: &str, key: &str) -> exception::Result<Vec<u8>> {
+        let mut client = StoreClient::try_create(address, &self.username, &self.password)
+            .await
+            .map_err(|e| ErrorCode::CannotConnectNode(e.to_string()))?;
+        client.pull_file(key).await
+    }
+
+    /// Spawn a task that pulls the missing files every `node_heartbeat_interval`.
+    pub async fn start_pulling(dfs: Arc<Dfs>, config: &Config) {
+        let interval = Duration::from_millis(config.node_heartbeat_interval);
+        let timeout_ms = config.node_dead_timeout;
+        let mut running_rx = dfs.meta_node.running_rx.clone();
+        let mut jh = dfs.meta_node.join_handles.lock().await;
+
+        let h = tokio::task::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = running_rx.changed() => {
+                        return Ok::<(), ErrorCode>(());
+                    }
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                // The heartbeats are stamped by the leader, they are compared with its clock.
+                let now_ms = dfs.meta_node.get_heartbeat_clock().await;
+                let pulled = dfs.pull_missing_files(now_ms, timeout_ms).await;
+                if pulled > 0 {
+                    tracing::info!("pulled {} files", pulled);
+                }
+            }
+        });
+        jh.push(h);
+    }
+}
 
 #[async_trait]
 impl FileSystem for Dfs {
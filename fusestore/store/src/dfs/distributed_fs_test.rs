@@ -126,7 +126,7 @@ async fn bring_up_dfs(
 
     assert_meta_connection(&meta_addr).await?;
 
-    let dfs = Dfs::create(fs, mn);
+    let dfs = Dfs::create(fs, mn, &tc.config);
     for (key, content) in files.iter() {
         dfs.add((*key).into(), (*content).as_bytes()).await?;
         tracing::debug!("dfs added file: {} {:?}", *key, *content);
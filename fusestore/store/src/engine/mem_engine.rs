@@ -36,12 +36,13 @@ impl MemEngine {
         Arc::new(Mutex::new(e))
     }
 
+    /// Creates a database, the engine and the options of the database are kept as the cmd has
+    /// them.
     pub fn create_database(
         &mut self,
         cmd: CmdCreateDatabase,
         if_not_exists: bool,
     ) -> common_exception::Result<i64> {
-        // TODO: support plan.engine plan.options
         let curr = self.dbs.get(&cmd.db_name);
         if let Some(curr) = curr {
             return if if_not_exists {
@@ -93,15 +94,14 @@ impl MemEngine {
         Ok(x.clone())
     }
 
-    // Create a table. It generates a table id and fill it.
+    // Create a table. It generates a table id and fill it, the engine and the options of the
+    // table are kept as the cmd has them.
     #[allow(dead_code)]
     pub fn create_table(
         &mut self,
         cmd: CmdCreateTable,
         if_not_exists: bool,
     ) -> common_exception::Result<i64> {
-        // TODO: support plan.engine plan.options
-
         let table_id = self
             .dbs
             .get(&cmd.db_name)
@@ -205,8 +205,10 @@ impl MemEngine {
                         part: Part {
                             name: loc.clone(),
                             version: 0,
+                            locations: vec![],
                         },
-                        stats: Statistics::new_exact(p.disk_bytes, p.rows),
+                        stats: Statistics::new_exact(p.rows, p.disk_bytes),
+                        col_stats: p.col_stats.clone(),
                     }
                 })
                 .collect::<Vec<_>>()
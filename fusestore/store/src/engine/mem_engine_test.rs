@@ -26,6 +26,8 @@ fn test_mem_engine_create_database() -> anyhow::Result<()> {
             ver: -1,
             table_name_to_id: HashMap::new(),
             tables: HashMap::new(),
+            engine: "Remote".into(),
+            options: maplit::hashmap! {"key".into() => "val".into()},
         }),
     };
     let cmdbar = CmdCreateDatabase {
@@ -35,6 +37,8 @@ fn test_mem_engine_create_database() -> anyhow::Result<()> {
             ver: -1,
             table_name_to_id: HashMap::new(),
             tables: HashMap::new(),
+            engine: "".into(),
+            options: HashMap::new(),
         }),
     };
 
@@ -47,7 +51,9 @@ fn test_mem_engine_create_database() -> anyhow::Result<()> {
                 db_id: 0,
                 ver: 0,
                 table_name_to_id: HashMap::new(),
-                tables: HashMap::new()
+                tables: HashMap::new(),
+                engine: "Remote".into(),
+                options: maplit::hashmap! {"key".into() => "val".into()}
             },
             eng.get_database("foo".into()).unwrap()
         );
@@ -62,7 +68,9 @@ fn test_mem_engine_create_database() -> anyhow::Result<()> {
                 db_id: 1,
                 ver: 1,
                 table_name_to_id: HashMap::new(),
-                tables: HashMap::new()
+                tables: HashMap::new(),
+                engine: "".into(),
+                options: HashMap::new()
             },
             eng.get_database("bar".into()).unwrap()
         );
@@ -77,7 +85,9 @@ fn test_mem_engine_create_database() -> anyhow::Result<()> {
                 db_id: 1,
                 ver: 1,
                 table_name_to_id: HashMap::new(),
-                tables: HashMap::new()
+                tables: HashMap::new(),
+                engine: "".into(),
+                options: HashMap::new()
             },
             eng.get_database("bar".into()).unwrap()
         );
@@ -95,7 +105,9 @@ fn test_mem_engine_create_database() -> anyhow::Result<()> {
                 db_id: 1,
                 ver: 1,
                 table_name_to_id: HashMap::new(),
-                tables: HashMap::new()
+                tables: HashMap::new(),
+                engine: "".into(),
+                options: HashMap::new()
             },
             eng.get_database("bar".into()).unwrap(),
             "got the previous bar"
@@ -118,6 +130,8 @@ fn test_mem_engine_create_get_table() -> anyhow::Result<()> {
             ver: -1,
             table_name_to_id: HashMap::new(),
             tables: HashMap::new(),
+            engine: "".into(),
+            options: HashMap::new(),
         }),
     };
 
@@ -128,6 +142,7 @@ fn test_mem_engine_create_get_table() -> anyhow::Result<()> {
             table_id: -1,
             ver: -1,
             schema: vec![1, 2, 3],
+            engine: "Fuse".into(),
             options: maplit::hashmap! {"key".into() => "val".into()},
             placement_policy: vec![1, 2, 3],
         }),
@@ -154,6 +169,7 @@ fn test_mem_engine_create_get_table() -> anyhow::Result<()> {
                 table_id: 1,
                 ver: 1,
                 schema: vec![1, 2, 3],
+                engine: "Fuse".into(),
                 options: maplit::hashmap! {"key".into() => "val".into()},
                 placement_policy: vec![1, 2, 3]
             },
@@ -196,6 +212,8 @@ fn test_mem_engine_drop_database() -> anyhow::Result<()> {
             ver: -1,
             table_name_to_id: HashMap::new(),
             tables: HashMap::new(),
+            engine: "".into(),
+            options: HashMap::new(),
         }),
     };
     let _ = eng.create_database(cmd.clone(), false).unwrap();
@@ -226,6 +244,8 @@ fn test_mem_engine_drop_table() -> anyhow::Result<()> {
             ver: -1,
             table_name_to_id: HashMap::new(),
             tables: HashMap::new(),
+            engine: "".into(),
+            options: HashMap::new(),
         }),
     };
 
@@ -236,6 +256,7 @@ fn test_mem_engine_drop_table() -> anyhow::Result<()> {
             table_id: -1,
             ver: -1,
             schema: vec![1, 2, 3],
+            engine: "Fuse".into(),
             options: maplit::hashmap! {"key".into() => "val".into()},
             placement_policy: vec![1, 2, 3],
         }),
@@ -2,30 +2,44 @@
 //
 // SPDX-License-Identifier: Apache-2.0.
 
+use std::convert::TryFrom;
 use std::pin::Pin;
 use std::sync::Arc;
 
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
 use common_arrow::arrow::ipc::writer::IpcWriteOptions;
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_arrow::arrow::record_batch::RecordBatchReader;
 use common_arrow::arrow_flight::utils::flight_data_from_arrow_batch;
 use common_arrow::arrow_flight::FlightData;
+use common_arrow::arrow_flight::SchemaAsIpc;
 use common_arrow::parquet::arrow::ArrowReader;
 use common_arrow::parquet::arrow::ParquetFileArrowReader;
 use common_arrow::parquet::file::reader::SerializedFileReader;
 use common_arrow::parquet::file::serialized_reader::SliceableCursor;
+use common_datablocks::DataBlock;
+use common_datavalues::DataSchema;
 use common_exception::ErrorCode;
 use common_flights::storage_api_impl::AppendResult;
 use common_flights::storage_api_impl::ReadAction;
 use common_flights::RequestFor;
 use common_flights::StoreDoAction;
 use common_planners::PlanNode;
+use common_planners::ReadDataSourcePlan;
+use common_runtime::tokio;
 use common_runtime::tokio::sync::mpsc::Sender;
 use futures::Stream;
 use serde::Serialize;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
 use tonic::Status;
 use tonic::Streaming;
 
 use crate::data_part::appender::Appender;
+use crate::executor::read_filter::ReadFilter;
+use crate::executor::read_projection::part_projection;
+use crate::executor::schema_checker::check_schema_evolution;
+use crate::executor::store_plan::StorePlan;
 use crate::fs::FileSystem;
 use crate::meta_service::MetaNode;
 
@@ -44,6 +58,12 @@ pub struct ActionHandler {
     fs: Arc<dyn FileSystem>,
 }
 
+/// The rows of the blocks sent by a read, if the reader does not tell.
+const DEFAULT_READ_BATCH_SIZE: usize = 2048;
+/// The blocks sent ahead of the reader of a read, if the reader does not tell.
+const DEFAULT_READ_WINDOW: usize = 2;
+const MAX_READ_WINDOW: usize = 1024;
+
 // TODO did this already defined somewhere?
 type DoGetStream =
     Pin<Box<dyn Stream<Item = Result<FlightData, tonic::Status>> + Send + Sync + 'static>>;
@@ -115,9 +135,24 @@ impl ActionHandler {
             StoreDoAction::CreateTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::DropTable(a) => s.serialize(self.handle(a).await?),
             StoreDoAction::GetTable(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::GetMetaVersion(a) => s.serialize(self.handle(a).await?),
+
+            // meta backup
+            StoreDoAction::ExportMeta(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::ImportMeta(a) => s.serialize(self.handle(a).await?),
+
+            // placement
+            StoreDoAction::SetPlacementPolicy(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::GetPlacementPolicy(a) => s.serialize(self.handle(a).await?),
+
+            // meta status
+            StoreDoAction::GetMetaStatus(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::SetMetaReadOnly(a) => s.serialize(self.handle(a).await?),
 
             // part
             StoreDoAction::ReadPlan(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::CommitAppend(a) => s.serialize(self.handle(a).await?),
+            StoreDoAction::AbortAppend(a) => s.serialize(self.handle(a).await?),
 
             // general-purpose kv
             StoreDoAction::UpsertKV(a) => s.serialize(self.handle(a).await?),
@@ -132,30 +167,81 @@ impl ActionHandler {
         &self,
         db_name: String,
         table_name: String,
+        bucket: Option<u64>,
+        tx_id: Option<String>,
+        deduplicate: bool,
         parts: Streaming<FlightData>,
     ) -> common_exception::Result<AppendResult> {
-        {
-            // TODO:  Validates the schema of input stream:
-            // The schema of `parts` should be a subset of
-            // table's current schema (or following the evolution rules of table schema)
-        }
+        let mut parts = Box::pin(
+            parts
+                .take_while(|item| item.is_ok())
+                .map(|item| item.unwrap()),
+        );
+
+        // The first element of the stream is the schema of the data to append.
+        // Parts written with it must be readable with the table's current schema.
+        let schema_data = parts
+            .next()
+            .await
+            .ok_or_else(|| ErrorCode::IllegalSchema("schema of input data must be provided"))?;
+        let input_schema = ArrowSchema::try_from(&schema_data)
+            .map_err(|e| ErrorCode::IllegalSchema(format!("invalid schema: {}", e)))?;
+        let table_schema = self.get_table_arrow_schema(&db_name, &table_name).await?;
+        check_schema_evolution(&input_schema, &table_schema)?;
 
+        let parts = futures::stream::once(async move { schema_data }).chain(parts);
         let appender = Appender::new(self.fs.clone());
-        let parts = parts
-            .take_while(|item| item.is_ok())
-            .map(|item| item.unwrap());
 
         let res = appender
-            .append_data(format!("{}/{}", &db_name, &table_name), Box::pin(parts))
+            .append_data(
+                format!("{}/{}", &db_name, &table_name),
+                bucket,
+                Box::pin(parts),
+            )
             .await?;
 
         // let mut meta = self.meta.lock(); //todo(ariesdevil): change to meta_node
         // meta.append_data_parts(&db_name, &table_name, &res);
         // Ok(res)
-        self.meta_node
-            .append_data_parts(&db_name, &table_name, &res)
-            .await;
-        Ok(res)
+        // The parts of a transaction are invisible until it commits, they are discarded if it aborts.
+        // The blocks appended recently, e.g. resent by a retry of an insert, are skipped with
+        // `deduplicate` when the parts become visible.
+        match tx_id {
+            Some(tx_id) => {
+                self.meta_node
+                    .stage_data_parts(&tx_id, &db_name, &table_name, &res, deduplicate)
+                    .await;
+                Ok(res)
+            }
+            None => Ok(self
+                .meta_node
+                .append_data_parts(&db_name, &table_name, &res, deduplicate)
+                .await),
+        }
+    }
+
+    async fn get_table_arrow_schema(
+        &self,
+        db_name: &str,
+        table_name: &str,
+    ) -> common_exception::Result<ArrowSchema> {
+        let db = self.meta_node.get_database(db_name).await.ok_or_else(|| {
+            ErrorCode::UnknownDatabase(format!("database not found: {:}", db_name))
+        })?;
+        let table_id = db
+            .tables
+            .get(table_name)
+            .ok_or_else(|| ErrorCode::UnknownTable(format!("table not found: {:}", table_name)))?;
+        let table =
+            self.meta_node.get_table(table_id).await.ok_or_else(|| {
+                ErrorCode::UnknownTable(format!("table not found: {:}", table_name))
+            })?;
+
+        ArrowSchema::try_from(&FlightData {
+            data_header: table.schema,
+            ..Default::default()
+        })
+        .map_err(|e| ErrorCode::IllegalSchema(format!("invalid schema: {:}", e.to_string())))
     }
 
     pub async fn read_partition(
@@ -165,43 +251,119 @@ impl ActionHandler {
         log::info!("entering read");
         let part_file = action.part.name;
 
-        let plan = if let PlanNode::ReadSource(read_source_plan) = action.push_down {
-            read_source_plan
-        } else {
-            return Err(ErrorCode::IllegalScanPlan("invalid PlanNode passed in"));
+        // The plan above the read of the part is executed on the rows read, see `StorePlan`.
+        let (plan, store_plan) = match action.push_down {
+            PlanNode::ReadSource(read_source_plan) => (read_source_plan, None),
+            push_down => {
+                let store_plan = StorePlan::try_create(push_down)?;
+                (store_plan.read.clone(), Some(store_plan))
+            }
         };
 
+        // The columns the query asks for by their indices are resolved by the table schema.
+        let table_schema = match &plan.scan_plan.push_downs.projection {
+            Some(_) => Some(self.get_table_arrow_schema(&plan.db, &plan.table).await?),
+            None => None,
+        };
         let content = self.fs.read_all(&part_file).await?;
-        let cursor = SliceableCursor::new(content);
 
+        // The blocks are read one by one, and at most `window` of them wait for the reader: the
+        // reading waits for a slow reader instead of holding all the blocks of the part.
+        let batch_size = action.batch_size.unwrap_or(DEFAULT_READ_BATCH_SIZE).max(1);
+        let window = action
+            .window
+            .unwrap_or(DEFAULT_READ_WINDOW)
+            .max(1)
+            .min(MAX_READ_WINDOW);
+        let (tx, rx) = tokio::sync::mpsc::channel(window);
+        tokio::task::spawn_blocking(move || {
+            let read = Self::read_part(content, plan, store_plan, table_schema, batch_size, &tx);
+            if let Err(e) = read {
+                let _ = tx.blocking_send(Err(Status::internal(e.to_string())));
+            }
+        });
+        Ok(Box::pin(ReceiverStream::new(rx)))
+    }
+
+    /// Sends the schema of the blocks of the part, and then the blocks, until the reader is gone.
+    /// With a store plan, the blocks are the results of the plan on the rows of the part.
+    fn read_part(
+        content: Vec<u8>,
+        plan: ReadDataSourcePlan,
+        store_plan: Option<StorePlan>,
+        table_schema: Option<ArrowSchema>,
+        batch_size: usize,
+        tx: &Sender<Result<FlightData, tonic::Status>>,
+    ) -> common_exception::Result<()> {
+        let cursor = SliceableCursor::new(content);
         let file_reader = SerializedFileReader::new(cursor)
             .map_err(|pe| ErrorCode::ReadFileError(format!("parquet error: {}", pe.to_string())))?;
         let mut arrow_reader = ParquetFileArrowReader::new(Arc::new(file_reader));
 
-        // before push_down is passed in, we returns all the columns
-        let schema = plan.schema;
-        let projection = (0..schema.fields().len()).collect::<Vec<_>>();
-
-        // TODO config
-        let batch_size = 2048;
+        // Only the columns the query asks for are read, by their indices in the table schema, or
+        // by the names of the columns of the plan if the query sends no indices.
+        // A part written before some columns were added to the table does not have them,
+        // they are filled with default values by the reader.
+        let part_schema = arrow_reader
+            .get_schema()
+            .map_err(|pe| ErrorCode::ReadFileError(format!("parquet error: {}", pe.to_string())))?;
+        let projection = match (&plan.scan_plan.push_downs.projection, &table_schema) {
+            (Some(projection), Some(table_schema)) => {
+                part_projection(projection, table_schema, &part_schema)?
+            }
+            _ => plan
+                .schema
+                .fields()
+                .iter()
+                .filter_map(|f| part_schema.index_of(f.name()).ok())
+                .collect::<Vec<_>>(),
+        };
 
         let batch_reader = arrow_reader
             .get_record_reader_by_columns(projection, batch_size)
             .map_err(|pe| ErrorCode::ReadFileError(format!("parquet error: {}", pe.to_string())))?;
 
-        // For simplicity, we do the conversion in-memory, to be optimized later
         // TODO consider using `parquet_table` and `stream_parquet`
         let write_opt = IpcWriteOptions::default();
-        let flights =
-            batch_reader
-                .into_iter()
-                .map(|batch| {
-                    batch.map(
-                    |b| flight_data_from_arrow_batch(&b, &write_opt).1, /*dictionary ignored*/
-                ).map_err(|arrow_err| Status::internal(arrow_err.to_string()))
-                })
-                .collect::<Vec<_>>();
-        let stream = futures::stream::iter(flights);
-        Ok(Box::pin(stream))
+
+        if let Some(store_plan) = store_plan {
+            let schema_flight: FlightData =
+                SchemaAsIpc::new(&store_plan.schema().to_arrow(), &write_opt).into();
+            if tx.blocking_send(Ok(schema_flight)).is_err() {
+                return Ok(());
+            }
+
+            let blocks = batch_reader.map(|batch| DataBlock::try_from(batch?));
+            return store_plan.execute(blocks, |block| {
+                let (_, flight) =
+                    flight_data_from_arrow_batch(&RecordBatch::try_from(block)?, &write_opt);
+                Ok(tx.blocking_send(Ok(flight)).is_ok())
+            });
+        }
+
+        // The first message is the schema of the batches that follow.
+        let batch_schema = batch_reader.schema();
+        let schema_flight: FlightData = SchemaAsIpc::new(&batch_schema, &write_opt).into();
+        if tx.blocking_send(Ok(schema_flight)).is_err() {
+            return Ok(());
+        }
+
+        // The rows which can not pass the filters pushed down are not sent, the batches without
+        // rows are skipped.
+        let filter = ReadFilter::create(
+            &plan.scan_plan.push_downs.filters,
+            &DataSchema::from(batch_schema.as_ref()),
+        );
+        for batch in batch_reader {
+            if let Some(batch) = filter.filter(batch.map_err(ErrorCode::from)?)? {
+                // The dictionaries are ignored.
+                let (_, flight) = flight_data_from_arrow_batch(&batch, &write_opt);
+                if tx.blocking_send(Ok(flight)).is_err() {
+                    // The reader is gone.
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
     }
 }
@@ -19,16 +19,26 @@ use common_flights::meta_api_impl::DropDatabaseAction;
 use common_flights::meta_api_impl::DropDatabaseActionResult;
 use common_flights::meta_api_impl::DropTableAction;
 use common_flights::meta_api_impl::DropTableActionResult;
+use common_flights::meta_api_impl::ExportMetaAction;
 use common_flights::meta_api_impl::GetDatabaseAction;
 use common_flights::meta_api_impl::GetDatabaseActionResult;
+use common_flights::meta_api_impl::GetMetaStatusAction;
+use common_flights::meta_api_impl::GetPlacementPolicyAction;
+use common_flights::meta_api_impl::GetPlacementPolicyActionResult;
 use common_flights::meta_api_impl::GetTableAction;
 use common_flights::meta_api_impl::GetTableActionResult;
+use common_flights::meta_api_impl::ImportMetaAction;
+use common_flights::meta_api_impl::ImportMetaActionResult;
+use common_flights::meta_api_impl::PlacementPolicy;
+use common_flights::meta_api_impl::SetMetaReadOnlyAction;
+use common_flights::meta_api_impl::SetMetaReadOnlyActionResult;
+use common_flights::meta_api_impl::SetPlacementPolicyAction;
+use common_flights::meta_api_impl::SetPlacementPolicyActionResult;
 use common_planners::CreateDatabasePlan;
 use common_planners::CreateTablePlan;
 use common_planners::DatabaseEngineType;
 use common_planners::DropDatabasePlan;
 use common_planners::DropTablePlan;
-use common_planners::TableEngineType;
 use common_runtime::tokio;
 use common_runtime::tokio::sync::mpsc::Receiver;
 use common_runtime::tokio::sync::mpsc::Sender;
@@ -353,7 +363,7 @@ async fn test_action_handler_create_table() -> anyhow::Result<()> {
             db: db_name.to_string(),
             table: table_name.to_string(),
             schema: schema.clone(),
-            engine: TableEngineType::JsonEachRaw,
+            engine: "JSON".to_string(),
             options: Default::default(),
         };
         let want = match want {
@@ -452,6 +462,9 @@ async fn test_action_handler_get_table() -> anyhow::Result<()> {
                 db: db_name.to_string(),
                 name: table_name.to_string(),
                 schema: schema.clone(),
+                comment: "".to_string(),
+                engine: "JSON".to_string(),
+                options: hashmap! {"location".to_string() => "/tmp/foo_t1".to_string()},
             }),
             Err(err_str) => Err(ErrorCode::UnknownTable(err_str)),
         };
@@ -498,8 +511,8 @@ async fn test_action_handler_get_table() -> anyhow::Result<()> {
                 db: "foo".to_string(),
                 table: "foo_t1".to_string(),
                 schema: schema.clone(),
-                engine: TableEngineType::JsonEachRaw,
-                options: Default::default(),
+                engine: "JSON".to_string(),
+                options: hashmap! {"location".to_string() => "/tmp/foo_t1".to_string()},
             };
             let cta = CreateTableAction { plan };
             hdlr.handle(cta).await?;
@@ -608,8 +621,8 @@ async fn test_action_handler_drop_table() -> anyhow::Result<()> {
                 db: "foo".to_string(),
                 table: "foo_t1".to_string(),
                 schema: schema.clone(),
-                engine: TableEngineType::JsonEachRaw,
-                options: Default::default(),
+                engine: "JSON".to_string(),
+                options: hashmap! {"location".to_string() => "/tmp/foo_t1".to_string()},
             };
             let cta = CreateTableAction { plan };
             hdlr.handle(cta).await?;
@@ -648,6 +661,225 @@ async fn test_action_handler_drop_table() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_action_handler_export_import_meta() -> anyhow::Result<()> {
+    // - Bring up an ActionHandler, add a database and a table.
+    // - Export meta.
+    // - Bring up another ActionHandler, import the dump.
+    // - Assert the database and table are restored with the same ids.
+    // - Assert importing into a non-empty catalog is refused.
+
+    common_tracing::init_default_tracing();
+
+    let schema = Arc::new(DataSchema::new(vec![DataField::new(
+        "number",
+        DataType::UInt64,
+        false,
+    )]));
+
+    let data = {
+        let dir = tempdir()?;
+        let root = dir.path();
+        let (_tc, hdlr) = bring_up_dfs_action_handler(root, hashmap! {}).await?;
+
+        let plan = CreateDatabasePlan {
+            db: "foo".to_string(),
+            if_not_exists: false,
+            engine: DatabaseEngineType::Local,
+            options: Default::default(),
+        };
+        hdlr.handle(CreateDatabaseAction { plan }).await?;
+
+        let plan = CreateTablePlan {
+            if_not_exists: false,
+            db: "foo".to_string(),
+            table: "foo_t1".to_string(),
+            schema: schema.clone(),
+            engine: "JSON".to_string(),
+            options: Default::default(),
+        };
+        hdlr.handle(CreateTableAction { plan }).await?;
+
+        hdlr.handle(ExportMetaAction {}).await?.data
+    };
+
+    {
+        let dir = tempdir()?;
+        let root = dir.path();
+        let (_tc, hdlr) = bring_up_dfs_action_handler(root, hashmap! {}).await?;
+
+        let rst = hdlr.handle(ImportMetaAction { data: data.clone() }).await?;
+        assert_eq!(
+            ImportMetaActionResult {
+                databases: 1,
+                tables: 1
+            },
+            rst
+        );
+
+        let got = hdlr
+            .handle(GetTableAction {
+                db: "foo".to_string(),
+                table: "foo_t1".to_string(),
+            })
+            .await?;
+        assert_eq!(
+            GetTableActionResult {
+                table_id: 1,
+                db: "foo".to_string(),
+                name: "foo_t1".to_string(),
+                schema: schema.clone(),
+                comment: "".to_string(),
+                engine: "JSON".to_string(),
+                options: Default::default(),
+            },
+            got
+        );
+
+        // new ids do not collide with the restored ones.
+        let plan = CreateDatabasePlan {
+            db: "bar".to_string(),
+            if_not_exists: false,
+            engine: DatabaseEngineType::Local,
+            options: Default::default(),
+        };
+        let rst = hdlr.handle(CreateDatabaseAction { plan }).await?;
+        assert_eq!(CreateDatabaseActionResult { database_id: 2 }, rst);
+
+        // the catalog is not empty any more.
+        let rst = hdlr.handle(ImportMetaAction { data }).await;
+        let err = rst.unwrap_err();
+        assert_eq!(ErrorCode::IllegalMetaState("").code(), err.code());
+
+        // garbage input
+        let rst = hdlr
+            .handle(ImportMetaAction {
+                data: b"foo".to_vec(),
+            })
+            .await;
+        let err = rst.unwrap_err();
+        assert_eq!(
+            ErrorCode::IllegalMetaOperationArgument("").code(),
+            err.code()
+        );
+    }
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_action_handler_placement_policy() -> anyhow::Result<()> {
+    // - Bring up an ActionHandler.
+    // - Assert the policy has no failure domain by default.
+    // - Set the policy and assert the previous one is returned.
+    // - Get the policy that is set.
+
+    common_tracing::init_default_tracing();
+
+    let dir = tempdir()?;
+    let root = dir.path();
+    let (_tc, hdlr) = bring_up_dfs_action_handler(root, hashmap! {}).await?;
+
+    let rst = hdlr.handle(GetPlacementPolicyAction {}).await?;
+    assert_eq!(
+        GetPlacementPolicyActionResult {
+            policy: PlacementPolicy::default()
+        },
+        rst
+    );
+
+    let policy = PlacementPolicy {
+        failure_domain: "zone".to_string(),
+    };
+    let rst = hdlr
+        .handle(SetPlacementPolicyAction {
+            policy: policy.clone(),
+        })
+        .await?;
+    assert_eq!(
+        SetPlacementPolicyActionResult {
+            prev: PlacementPolicy::default()
+        },
+        rst
+    );
+
+    let rst = hdlr.handle(GetPlacementPolicyAction {}).await?;
+    assert_eq!(GetPlacementPolicyActionResult { policy }, rst);
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_action_handler_get_meta_status() -> anyhow::Result<()> {
+    // - Bring up an ActionHandler of a single node cluster.
+    // - Assert the node is the leader and has applied its logs.
+
+    common_tracing::init_default_tracing();
+
+    let dir = tempdir()?;
+    let root = dir.path();
+    let (_tc, hdlr) = bring_up_dfs_action_handler(root, hashmap! {}).await?;
+
+    let status = hdlr.handle(GetMetaStatusAction {}).await?;
+    assert_eq!(0, status.id);
+    assert_eq!("Leader", status.state);
+    assert_eq!(Some(0), status.current_leader);
+    assert!(status.current_term >= 1);
+    assert!(status.last_applied >= 1);
+    assert!(status.last_log_index >= status.last_applied);
+    assert!(status.peer_lags.is_empty());
+
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_action_handler_meta_read_only() -> anyhow::Result<()> {
+    // - Bring up an ActionHandler, put the meta service into the read-only mode.
+    // - Assert the DDL is rejected and the reads are served.
+    // - Assert the DDL is accepted again out of the read-only mode.
+
+    common_tracing::init_default_tracing();
+
+    let dir = tempdir()?;
+    let root = dir.path();
+    let (_tc, hdlr) = bring_up_dfs_action_handler(root, hashmap! {}).await?;
+
+    let create_db = |db: &str| CreateDatabaseAction {
+        plan: CreateDatabasePlan {
+            db: db.to_string(),
+            if_not_exists: false,
+            engine: DatabaseEngineType::Local,
+            options: Default::default(),
+        },
+    };
+    hdlr.handle(create_db("foo")).await?;
+
+    let rst = hdlr
+        .handle(SetMetaReadOnlyAction { read_only: true })
+        .await?;
+    assert_eq!(SetMetaReadOnlyActionResult { prev: false }, rst);
+    assert!(hdlr.handle(GetMetaStatusAction {}).await?.read_only);
+
+    let e = hdlr.handle(create_db("bar")).await.unwrap_err();
+    assert_eq!(ErrorCode::MetaServiceReadOnly("").code(), e.code());
+
+    let rst = hdlr
+        .handle(GetDatabaseAction {
+            db: "foo".to_string(),
+        })
+        .await?;
+    assert_eq!("foo", rst.db);
+
+    let rst = hdlr
+        .handle(SetMetaReadOnlyAction { read_only: false })
+        .await?;
+    assert_eq!(SetMetaReadOnlyActionResult { prev: true }, rst);
+    assert!(!hdlr.handle(GetMetaStatusAction {}).await?.read_only);
+    hdlr.handle(create_db("bar")).await?;
+
+    Ok(())
+}
+
 // Start an ActionHandler backed with a dfs.
 // And feed files into dfs.
 async fn bring_up_dfs_action_handler(
@@ -661,7 +893,7 @@ async fn bring_up_dfs_action_handler(
     let mn = MetaNode::boot(0, &tc.config).await?;
     tc.meta_nodes.push(mn.clone());
 
-    let dfs = Dfs::create(fs, mn.clone());
+    let dfs = Dfs::create(fs, mn.clone(), &tc.config);
 
     for (key, content) in files.iter() {
         dfs.add((*key).into(), (*content).as_bytes()).await?;
@@ -19,22 +19,40 @@ use common_flights::meta_api_impl::DropDatabaseAction;
 use common_flights::meta_api_impl::DropDatabaseActionResult;
 use common_flights::meta_api_impl::DropTableAction;
 use common_flights::meta_api_impl::DropTableActionResult;
+use common_flights::meta_api_impl::ExportMetaAction;
+use common_flights::meta_api_impl::ExportMetaActionResult;
 use common_flights::meta_api_impl::GetDatabaseAction;
 use common_flights::meta_api_impl::GetDatabaseActionResult;
+use common_flights::meta_api_impl::GetMetaStatusAction;
+use common_flights::meta_api_impl::GetMetaStatusActionResult;
+use common_flights::meta_api_impl::GetMetaVersionAction;
+use common_flights::meta_api_impl::GetMetaVersionActionResult;
+use common_flights::meta_api_impl::GetPlacementPolicyAction;
+use common_flights::meta_api_impl::GetPlacementPolicyActionResult;
 use common_flights::meta_api_impl::GetTableAction;
 use common_flights::meta_api_impl::GetTableActionResult;
+use common_flights::meta_api_impl::ImportMetaAction;
+use common_flights::meta_api_impl::ImportMetaActionResult;
+use common_flights::meta_api_impl::SetMetaReadOnlyAction;
+use common_flights::meta_api_impl::SetMetaReadOnlyActionResult;
+use common_flights::meta_api_impl::SetPlacementPolicyAction;
+use common_flights::meta_api_impl::SetPlacementPolicyActionResult;
 use common_metatypes::Database;
 use common_metatypes::Table;
 use log::info;
 
 use crate::executor::action_handler::RequestHandler;
+use crate::executor::schema_checker::check_table_schema;
 use crate::executor::ActionHandler;
 use crate::meta_service::cmd::Cmd::CreateDatabase;
 use crate::meta_service::cmd::Cmd::CreateTable;
 use crate::meta_service::cmd::Cmd::DropDatabase;
 use crate::meta_service::cmd::Cmd::DropTable;
+use crate::meta_service::cmd::Cmd::ImportMeta;
+use crate::meta_service::state_machine::META_DUMP_VERSION;
 use crate::meta_service::AppliedState;
 use crate::meta_service::LogEntry;
+use crate::meta_service::MetaDump;
 
 // Db
 #[async_trait::async_trait]
@@ -43,6 +61,8 @@ impl RequestHandler<CreateDatabaseAction> for ActionHandler {
         &self,
         act: CreateDatabaseAction,
     ) -> common_exception::Result<CreateDatabaseActionResult> {
+        self.meta_node.check_writable().await?;
+
         let plan = act.plan;
         let db_name = &plan.db;
         let if_not_exists = plan.if_not_exists;
@@ -118,6 +138,8 @@ impl RequestHandler<DropDatabaseAction> for ActionHandler {
         &self,
         act: DropDatabaseAction,
     ) -> common_exception::Result<DropDatabaseActionResult> {
+        self.meta_node.check_writable().await?;
+
         let db_name = &act.plan.db;
         let if_exists = act.plan.if_exists;
         let cr = LogEntry {
@@ -156,6 +178,8 @@ impl RequestHandler<CreateTableAction> for ActionHandler {
         &self,
         act: CreateTableAction,
     ) -> common_exception::Result<CreateTableActionResult> {
+        self.meta_node.check_writable().await?;
+
         let plan = act.plan;
         let db_name = &plan.db;
         let table_name = &plan.table;
@@ -163,14 +187,20 @@ impl RequestHandler<CreateTableAction> for ActionHandler {
 
         info!("create table: {:}: {:?}", &db_name, &table_name);
 
+        let arrow_schema = plan.schema.to_arrow();
+        check_table_schema(&arrow_schema)?;
+
         let options = common_arrow::arrow::ipc::writer::IpcWriteOptions::default();
         let flight_data: FlightData =
-            arrow_flight::SchemaAsIpc::new(&plan.schema.to_arrow(), &options).into();
+            arrow_flight::SchemaAsIpc::new(&arrow_schema, &options).into();
 
         let table = Table {
             table_id: 0,
             schema: flight_data.data_header,
             parts: Default::default(),
+            comment: plan.options.get("comment").cloned().unwrap_or_default(),
+            engine: plan.engine.clone(),
+            options: plan.options.clone(),
         };
 
         let cr = LogEntry {
@@ -219,6 +249,8 @@ impl RequestHandler<DropTableAction> for ActionHandler {
         &self,
         act: DropTableAction,
     ) -> common_exception::Result<DropTableActionResult> {
+        self.meta_node.check_writable().await?;
+
         let db_name = &act.plan.db;
         let table_name = &act.plan.table;
         let if_exists = act.plan.if_exists;
@@ -285,6 +317,9 @@ impl RequestHandler<GetTableAction> for ActionHandler {
                     db: db_name.clone(),
                     name: table_name.clone(),
                     schema: Arc::new(arrow_schema.into()),
+                    comment: table.comment,
+                    engine: table.engine,
+                    options: table.options,
                 };
                 Ok(rst)
             }
@@ -292,3 +327,114 @@ impl RequestHandler<GetTableAction> for ActionHandler {
         }
     }
 }
+
+#[async_trait::async_trait]
+impl RequestHandler<GetMetaVersionAction> for ActionHandler {
+    async fn handle(
+        &self,
+        _act: GetMetaVersionAction,
+    ) -> common_exception::Result<GetMetaVersionActionResult> {
+        let meta_ver = self.meta_node.get_meta_version().await;
+        Ok(GetMetaVersionActionResult { meta_ver })
+    }
+}
+
+// meta backup
+#[async_trait::async_trait]
+impl RequestHandler<ExportMetaAction> for ActionHandler {
+    async fn handle(
+        &self,
+        _act: ExportMetaAction,
+    ) -> common_exception::Result<ExportMetaActionResult> {
+        let dump = self.meta_node.export_meta().await;
+        let data = serde_json::to_vec(&dump)?;
+        Ok(ExportMetaActionResult { data })
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<ImportMetaAction> for ActionHandler {
+    async fn handle(
+        &self,
+        act: ImportMetaAction,
+    ) -> common_exception::Result<ImportMetaActionResult> {
+        self.meta_node.check_writable().await?;
+
+        let dump: MetaDump = serde_json::from_slice(&act.data).map_err(|e| {
+            ErrorCode::IllegalMetaOperationArgument(format!("invalid meta dump: {}", e))
+        })?;
+
+        if dump.version != META_DUMP_VERSION {
+            return Err(ErrorCode::IllegalMetaOperationArgument(format!(
+                "unsupported meta dump version: {}, expect: {}",
+                dump.version, META_DUMP_VERSION
+            )));
+        }
+
+        let cr = LogEntry {
+            txid: None,
+            cmd: ImportMeta { dump },
+        };
+
+        let rst = self
+            .meta_node
+            .write(cr)
+            .await
+            .map_err(|e| ErrorCode::MetaNodeInternalError(e.to_string()))?;
+
+        match rst {
+            AppliedState::ImportMeta { result } => match result {
+                Some((databases, tables)) => Ok(ImportMetaActionResult { databases, tables }),
+                None => Err(ErrorCode::IllegalMetaState(
+                    "can not import meta into a non-empty catalog",
+                )),
+            },
+            _ => Err(ErrorCode::MetaNodeInternalError("not an ImportMeta result")),
+        }
+    }
+}
+
+// placement
+#[async_trait::async_trait]
+impl RequestHandler<SetPlacementPolicyAction> for ActionHandler {
+    async fn handle(
+        &self,
+        act: SetPlacementPolicyAction,
+    ) -> common_exception::Result<SetPlacementPolicyActionResult> {
+        let prev = self.meta_node.set_placement_policy(act.policy).await?;
+        Ok(SetPlacementPolicyActionResult { prev })
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<GetPlacementPolicyAction> for ActionHandler {
+    async fn handle(
+        &self,
+        _act: GetPlacementPolicyAction,
+    ) -> common_exception::Result<GetPlacementPolicyActionResult> {
+        let policy = self.meta_node.get_placement_policy().await;
+        Ok(GetPlacementPolicyActionResult { policy })
+    }
+}
+
+// status
+#[async_trait::async_trait]
+impl RequestHandler<GetMetaStatusAction> for ActionHandler {
+    async fn handle(
+        &self,
+        _act: GetMetaStatusAction,
+    ) -> common_exception::Result<GetMetaStatusActionResult> {
+        Ok(self.meta_node.get_status().await)
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<SetMetaReadOnlyAction> for ActionHandler {
+    async fn handle(
+        &self,
+        act: SetMetaReadOnlyAction,
+    ) -> common_exception::Result<SetMetaReadOnlyActionResult> {
+        let prev = self.meta_node.set_read_only(act.read_only).await?;
+        Ok(SetMetaReadOnlyActionResult { prev })
+    }
+}
@@ -9,6 +9,19 @@ pub use action_handler::ReplySerializer;
 
 #[cfg(test)]
 mod action_handler_test;
+#[cfg(test)]
+mod read_filter_test;
+#[cfg(test)]
+mod read_projection_test;
+#[cfg(test)]
+mod schema_checker_test;
+#[cfg(test)]
+mod store_plan_test;
+
 mod kv_handlers;
 mod meta_handlers;
+mod read_filter;
+mod read_projection;
+mod schema_checker;
 mod storage_handlers;
+mod store_plan;
@@ -0,0 +1,115 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryFrom;
+
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_functions::scalars::FunctionFactory;
+use common_planners::Expression;
+use common_planners::RewriteHelper;
+
+/// The filters pushed down with a read, evaluated by the store to skip the rows before they are
+/// sent to the query.
+///
+/// Only the conjunctions comparing a column of the part with a literal are evaluated, the others
+/// are ignored: the query still applies all the filters to the rows it receives, the store only
+/// cuts the rows which can not pass.
+pub struct ReadFilter {
+    conjunctions: Vec<ReadFilterConjunction>,
+}
+
+struct ReadFilterConjunction {
+    op: String,
+    column: String,
+    value: DataValue,
+    // Whether the column is on the left of the comparison.
+    column_first: bool,
+}
+
+impl ReadFilter {
+    pub fn create(filters: &[Expression], schema: &DataSchema) -> ReadFilter {
+        let conjunctions = filters
+            .iter()
+            .flat_map(RewriteHelper::split_conjunctions)
+            .filter_map(|conjunction| Self::comparison(&conjunction, schema))
+            .collect();
+        ReadFilter { conjunctions }
+    }
+
+    fn comparison(expr: &Expression, schema: &DataSchema) -> Option<ReadFilterConjunction> {
+        let (left, op, right) = match expr {
+            Expression::BinaryExpression { left, op, right } => (left, op.to_lowercase(), right),
+            _ => return None,
+        };
+        if !matches!(op.as_str(), "=" | "!=" | "<>" | "<" | "<=" | ">" | ">=") {
+            return None;
+        }
+
+        let (column, value, column_first) = match (left.as_ref(), right.as_ref()) {
+            (Expression::Column(column), Expression::Literal { value, .. }) => {
+                (column, value, true)
+            }
+            (Expression::Literal { value, .. }, Expression::Column(column)) => {
+                (column, value, false)
+            }
+            _ => return None,
+        };
+
+        // The comparisons between the different kinds of types are left to the query.
+        let column_type = schema.field_with_name(column).ok()?.data_type();
+        let value_type = value.data_type();
+        if value.is_null()
+            || (column_type != &value_type && !(is_numeric(column_type) && is_numeric(&value_type)))
+        {
+            return None;
+        }
+
+        Some(ReadFilterConjunction {
+            op,
+            column: column.clone(),
+            value: value.clone(),
+            column_first,
+        })
+    }
+
+    /// Returns the rows of the batch passing all the conjunctions, or None if no row passes.
+    pub fn filter(&self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        if self.conjunctions.is_empty() {
+            return Ok(Some(batch));
+        }
+
+        let block = DataBlock::try_from(batch.clone())?;
+        let rows = block.num_rows();
+        let mut passed = vec![true; rows];
+        for conjunction in &self.conjunctions {
+            let column = block.try_column_by_name(&conjunction.column)?.clone();
+            let value = DataColumn::Constant(conjunction.value.clone(), rows);
+            let args = match conjunction.column_first {
+                true => [column, value],
+                false => [value, column],
+            };
+
+            let result = FunctionFactory::get(&conjunction.op)?.eval(&args, rows)?;
+            let result = result.to_array()?;
+            for (row, v) in result.bool()?.downcast_iter().enumerate() {
+                passed[row] &= v == Some(true);
+            }
+        }
+
+        let indices = (0..rows as u32)
+            .filter(|row| passed[*row as usize])
+            .collect::<Vec<_>>();
+        match indices.len() {
+            0 => Ok(None),
+            n if n == rows => Ok(Some(batch)),
+            _ => {
+                let block = DataBlock::block_take_by_indices(&block, &[], &indices)?;
+                Ok(Some(RecordBatch::try_from(block)?))
+            }
+        }
+    }
+}
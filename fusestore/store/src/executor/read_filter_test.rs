@@ -0,0 +1,75 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::convert::TryFrom;
+
+use common_arrow::arrow::record_batch::RecordBatch;
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_planners::col;
+use common_planners::lit;
+
+use crate::executor::read_filter::ReadFilter;
+
+#[test]
+fn test_read_filter() -> anyhow::Result<()> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Utf8, false),
+    ]);
+    let block = DataBlock::create_by_array(schema.clone(), vec![
+        Series::new(vec![1i64, 2, 3, 4, 5]),
+        Series::new(vec!["b1", "b2", "b3", "b4", "b5"]),
+    ]);
+    let batch = RecordBatch::try_from(block)?;
+
+    // The comparisons with a literal, on both sides of the column, are evaluated.
+    {
+        let filters = vec![col("a").gt(lit(1i64)).and(lit(4i64).gt(col("a")))];
+        let filter = ReadFilter::create(&filters, &schema);
+        let filtered = filter.filter(batch.clone())?.unwrap();
+        let expected = vec![
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 2 | b2 |",
+            "| 3 | b3 |",
+            "+---+----+",
+        ];
+        assert_blocks_eq(expected, &[DataBlock::try_from(filtered)?]);
+    }
+
+    // The other conjunctions are left to the query.
+    {
+        let filters = vec![col("b").eq(lit("b5")).and(col("a").eq(col("a")))];
+        let filter = ReadFilter::create(&filters, &schema);
+        let filtered = filter.filter(batch.clone())?.unwrap();
+        let expected = vec![
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 5 | b5 |",
+            "+---+----+",
+        ];
+        assert_blocks_eq(expected, &[DataBlock::try_from(filtered)?]);
+    }
+
+    // The comparisons of a column not read or of the different kinds of types are ignored.
+    {
+        let filters = vec![col("c").eq(lit(1i64)), col("b").eq(lit(1i64))];
+        let filter = ReadFilter::create(&filters, &schema);
+        let filtered = filter.filter(batch.clone())?.unwrap();
+        assert_eq!(5, filtered.num_rows());
+    }
+
+    // No row passes.
+    {
+        let filters = vec![col("a").gt(lit(5i64))];
+        let filter = ReadFilter::create(&filters, &schema);
+        assert!(filter.filter(batch)?.is_none());
+    }
+
+    Ok(())
+}
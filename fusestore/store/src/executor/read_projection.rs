@@ -0,0 +1,33 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Returns the indices of the columns of the part to read, by the indices of the columns in the
+/// schema of the table.
+///
+/// A part written before some columns were added to the table does not have them, they are
+/// skipped and filled with default values by the reader.
+pub fn part_projection(
+    projection: &[usize],
+    table_schema: &ArrowSchema,
+    part_schema: &ArrowSchema,
+) -> Result<Vec<usize>> {
+    let mut columns = Vec::with_capacity(projection.len());
+    for index in projection {
+        let field = table_schema.fields().get(*index).ok_or_else(|| {
+            ErrorCode::IllegalScanPlan(format!(
+                "column index {} out of the {} columns of the table",
+                index,
+                table_schema.fields().len()
+            ))
+        })?;
+        if let Ok(column) = part_schema.index_of(field.name()) {
+            columns.push(column);
+        }
+    }
+    Ok(columns)
+}
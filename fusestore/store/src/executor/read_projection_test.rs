@@ -0,0 +1,45 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+
+use crate::executor::read_projection::part_projection;
+
+fn schema(names: &[&str]) -> ArrowSchema {
+    ArrowSchema::new(
+        names
+            .iter()
+            .map(|name| ArrowField::new(name, ArrowDataType::UInt64, true))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_part_projection() -> anyhow::Result<()> {
+    let table_schema = schema(&["a", "b", "c", "d"]);
+
+    // The part has all the columns of the table.
+    assert_eq!(
+        vec![1, 3],
+        part_projection(&[1, 3], &table_schema, &table_schema)?
+    );
+
+    // The columns added after the part was written are skipped.
+    let part_schema = schema(&["a", "b"]);
+    assert_eq!(
+        vec![1],
+        part_projection(&[1, 3], &table_schema, &part_schema)?
+    );
+
+    // The index out of the table.
+    let res = part_projection(&[4], &table_schema, &part_schema);
+    assert_eq!(
+        "Code: 5000, displayText = column index 4 out of the 4 columns of the table.",
+        res.unwrap_err().to_string()
+    );
+
+    Ok(())
+}
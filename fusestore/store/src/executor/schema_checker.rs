@@ -0,0 +1,103 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashSet;
+
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::datatypes::IntervalUnit;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_datavalues::DataField;
+use common_exception::ErrorCode;
+use common_exception::Result;
+
+/// Checks a table schema before it is stored in meta:
+/// - there is at least one column,
+/// - column names are unique,
+/// - every column type is one that can be stored in a data part.
+pub fn check_table_schema(schema: &ArrowSchema) -> Result<()> {
+    if schema.fields().is_empty() {
+        return Err(ErrorCode::IllegalSchema(
+            "table must have at least one column",
+        ));
+    }
+
+    let mut names = HashSet::new();
+    for field in schema.fields() {
+        if !names.insert(field.name().as_str()) {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "duplicate column name: {}",
+                field.name()
+            )));
+        }
+        check_field_type(field)?;
+    }
+    Ok(())
+}
+
+/// Checks that `new` is a compatible evolution of `old`, i.e., data parts written with `old` are
+/// still readable with `new`:
+/// - no column in `old` is removed or changes its type,
+/// - a column added in `new` must be nullable or have a default value, since old parts have no value for it.
+pub fn check_schema_evolution(old: &ArrowSchema, new: &ArrowSchema) -> Result<()> {
+    check_table_schema(new)?;
+
+    for old_field in old.fields() {
+        let new_field = new.field_with_name(old_field.name()).map_err(|_| {
+            ErrorCode::IllegalSchema(format!("column {} can not be dropped", old_field.name()))
+        })?;
+
+        if new_field.data_type() != old_field.data_type() {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "type of column {} can not be changed from {:?} to {:?}",
+                old_field.name(),
+                old_field.data_type(),
+                new_field.data_type()
+            )));
+        }
+    }
+
+    for new_field in new.fields() {
+        if old.field_with_name(new_field.name()).is_ok() || new_field.is_nullable() {
+            continue;
+        }
+        if DataField::from(new_field).default_expr().is_none() {
+            return Err(ErrorCode::IllegalSchema(format!(
+                "new column {} must be nullable or have a default value",
+                new_field.name()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_field_type(field: &ArrowField) -> Result<()> {
+    match field.data_type() {
+        ArrowDataType::Boolean
+        | ArrowDataType::UInt8
+        | ArrowDataType::UInt16
+        | ArrowDataType::UInt32
+        | ArrowDataType::UInt64
+        | ArrowDataType::Int8
+        | ArrowDataType::Int16
+        | ArrowDataType::Int32
+        | ArrowDataType::Int64
+        | ArrowDataType::Float32
+        | ArrowDataType::Float64
+        | ArrowDataType::Decimal(_, _)
+        | ArrowDataType::Utf8
+        | ArrowDataType::Binary
+        | ArrowDataType::Date32
+        | ArrowDataType::Date64
+        | ArrowDataType::Timestamp(_, _)
+        | ArrowDataType::Interval(IntervalUnit::YearMonth)
+        | ArrowDataType::Interval(IntervalUnit::DayTime) => Ok(()),
+        ArrowDataType::List(f) => check_field_type(f),
+        other => Err(ErrorCode::IllegalSchema(format!(
+            "unsupported type of column {}: {:?}",
+            field.name(),
+            other
+        ))),
+    }
+}
@@ -0,0 +1,160 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::datatypes::Schema as ArrowSchema;
+use common_datavalues::DataField;
+use common_datavalues::DataType;
+use common_exception::ErrorCode;
+
+use crate::executor::schema_checker::check_schema_evolution;
+use crate::executor::schema_checker::check_table_schema;
+
+fn schema(fields: Vec<(&str, ArrowDataType, bool)>) -> ArrowSchema {
+    ArrowSchema::new(
+        fields
+            .into_iter()
+            .map(|(name, typ, nullable)| ArrowField::new(name, typ, nullable))
+            .collect(),
+    )
+}
+
+#[test]
+fn test_check_table_schema() -> anyhow::Result<()> {
+    struct T {
+        name: &'static str,
+        schema: ArrowSchema,
+        want: Result<(), &'static str>,
+    }
+
+    let cases = vec![
+        T {
+            name: "ok",
+            schema: schema(vec![
+                ("a", ArrowDataType::UInt64, false),
+                ("b", ArrowDataType::Utf8, true),
+            ]),
+            want: Ok(()),
+        },
+        T {
+            name: "decimal",
+            schema: schema(vec![
+                ("a", ArrowDataType::Decimal(10, 2), false),
+                ("b", ArrowDataType::Decimal(38, 0), true),
+            ]),
+            want: Ok(()),
+        },
+        T {
+            name: "empty",
+            schema: schema(vec![]),
+            want: Err("table must have at least one column"),
+        },
+        T {
+            name: "duplicate",
+            schema: schema(vec![
+                ("a", ArrowDataType::UInt64, false),
+                ("a", ArrowDataType::Utf8, true),
+            ]),
+            want: Err("duplicate column name: a"),
+        },
+        T {
+            name: "unsupported",
+            schema: schema(vec![("a", ArrowDataType::Float16, false)]),
+            want: Err("unsupported type of column a: Float16"),
+        },
+    ];
+
+    for c in cases {
+        let got = check_table_schema(&c.schema);
+        match c.want {
+            Ok(_) => assert!(got.is_ok(), "{}", c.name),
+            Err(msg) => {
+                let e = got.unwrap_err();
+                assert_eq!(ErrorCode::IllegalSchema("").code(), e.code(), "{}", c.name);
+                assert_eq!(msg, e.message(), "{}", c.name);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_check_schema_evolution() -> anyhow::Result<()> {
+    let old = schema(vec![
+        ("a", ArrowDataType::UInt64, false),
+        ("b", ArrowDataType::Utf8, true),
+    ]);
+
+    struct T {
+        name: &'static str,
+        new: ArrowSchema,
+        want: Result<(), &'static str>,
+    }
+
+    let cases = vec![
+        T {
+            name: "same",
+            new: old.clone(),
+            want: Ok(()),
+        },
+        T {
+            name: "add nullable column",
+            new: schema(vec![
+                ("a", ArrowDataType::UInt64, false),
+                ("b", ArrowDataType::Utf8, true),
+                ("c", ArrowDataType::Int32, true),
+            ]),
+            want: Ok(()),
+        },
+        T {
+            name: "add non-nullable column",
+            new: schema(vec![
+                ("a", ArrowDataType::UInt64, false),
+                ("b", ArrowDataType::Utf8, true),
+                ("c", ArrowDataType::Int32, false),
+            ]),
+            want: Err("new column c must be nullable or have a default value"),
+        },
+        T {
+            name: "add defaulted column",
+            new: ArrowSchema::new(vec![
+                ArrowField::new("a", ArrowDataType::UInt64, false),
+                ArrowField::new("b", ArrowDataType::Utf8, true),
+                DataField::new("c", DataType::Int32, false)
+                    .with_default_expr(Some("0".to_string()))
+                    .to_arrow(),
+            ]),
+            want: Ok(()),
+        },
+        T {
+            name: "drop column",
+            new: schema(vec![("a", ArrowDataType::UInt64, false)]),
+            want: Err("column b can not be dropped"),
+        },
+        T {
+            name: "change type",
+            new: schema(vec![
+                ("a", ArrowDataType::Int64, false),
+                ("b", ArrowDataType::Utf8, true),
+            ]),
+            want: Err("type of column a can not be changed from UInt64 to Int64"),
+        },
+    ];
+
+    for c in cases {
+        let got = check_schema_evolution(&old, &c.new);
+        match c.want {
+            Ok(_) => assert!(got.is_ok(), "{}", c.name),
+            Err(msg) => {
+                let e = got.unwrap_err();
+                assert_eq!(ErrorCode::IllegalSchema("").code(), e.code(), "{}", c.name);
+                assert_eq!(msg, e.message(), "{}", c.name);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -3,6 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0.
 //
 
+use common_flights::storage_api_impl::AbortAppendAction;
+use common_flights::storage_api_impl::AppendTxResult;
+use common_flights::storage_api_impl::CommitAppendAction;
 use common_flights::storage_api_impl::ReadPlanAction;
 use common_flights::storage_api_impl::ReadPlanResult;
 use log::debug;
@@ -23,3 +26,22 @@ impl RequestHandler<ReadPlanAction> for ActionHandler {
         Ok(self.meta_node.get_data_parts(db_name, tbl_name).await)
     }
 }
+
+#[async_trait::async_trait]
+impl RequestHandler<CommitAppendAction> for ActionHandler {
+    async fn handle(&self, act: CommitAppendAction) -> common_exception::Result<AppendTxResult> {
+        Ok(self.meta_node.commit_data_parts(&act.tx_id).await)
+    }
+}
+
+#[async_trait::async_trait]
+impl RequestHandler<AbortAppendAction> for ActionHandler {
+    async fn handle(&self, act: AbortAppendAction) -> common_exception::Result<AppendTxResult> {
+        // The files of the discarded parts are left unreferenced.
+        let parts = self.meta_node.abort_data_parts(&act.tx_id).await;
+        Ok(AppendTxResult {
+            parts,
+            ..AppendTxResult::default()
+        })
+    }
+}
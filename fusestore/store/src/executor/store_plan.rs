@@ -0,0 +1,295 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::HashMap;
+
+use bumpalo::Bump;
+use common_datablocks::DataBlock;
+use common_datavalues::arrays::BinaryArrayBuilder;
+use common_datavalues::prelude::*;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_functions::aggregates::AggregateFunctionRef;
+use common_functions::aggregates::StateAddr;
+use common_planners::ExpressionAction;
+use common_planners::ExpressionChain;
+use common_planners::PlanNode;
+use common_planners::ReadDataSourcePlan;
+
+/// The plan which the store executes on the rows of a part, see `Extras::store_plan`: the
+/// filters and the expressions above the read of the part, and a partial aggregate without
+/// group by on top of them.
+///
+/// The filters are exact, unlike the filters pushed down with a read, and the aggregate sends
+/// one block of the states of the part, merged by the query.
+pub struct StorePlan {
+    /// The read of the part at the bottom of the plan.
+    pub read: ReadDataSourcePlan,
+    // The steps above the read, the innermost first.
+    steps: Vec<StorePlanStep>,
+    aggregate: Option<StorePlanAggregate>,
+    schema: DataSchemaRef,
+}
+
+enum StorePlanStep {
+    Filter {
+        column_name: String,
+        chain: ExpressionChain,
+    },
+    Expression {
+        chain: ExpressionChain,
+        schema: DataSchemaRef,
+    },
+}
+
+struct StorePlanAggregate {
+    funcs: Vec<AggregateFunctionRef>,
+    arg_names: Vec<Vec<String>>,
+}
+
+impl StorePlan {
+    pub fn try_create(plan: PlanNode) -> Result<StorePlan> {
+        let schema = plan.schema();
+
+        // The nodes above the read, the outermost first.
+        let mut nodes = vec![];
+        let mut node = plan;
+        let read = loop {
+            match node {
+                PlanNode::ReadSource(read) => break read,
+                PlanNode::Filter(_) | PlanNode::Expression(_) => {}
+                PlanNode::AggregatorPartial(ref plan)
+                    if nodes.is_empty() && plan.group_expr.is_empty() => {}
+                _ => {
+                    return Err(ErrorCode::IllegalScanPlan(format!(
+                        "the store can not execute the plan node {}",
+                        node.name()
+                    )))
+                }
+            }
+            let input = node.input(0).as_ref().clone();
+            nodes.push(node);
+            node = input;
+        };
+
+        let mut steps = vec![];
+        let mut aggregate = None;
+        for node in nodes.iter().rev() {
+            match node {
+                PlanNode::Filter(plan) => steps.push(StorePlanStep::Filter {
+                    column_name: plan.predicate.column_name(),
+                    chain: ExpressionChain::try_create(
+                        plan.input.schema(),
+                        std::slice::from_ref(&plan.predicate),
+                    )?,
+                }),
+                PlanNode::Expression(plan) => steps.push(StorePlanStep::Expression {
+                    chain: ExpressionChain::try_create(plan.input.schema(), &plan.exprs)?,
+                    schema: plan.schema.clone(),
+                }),
+                PlanNode::AggregatorPartial(plan) => {
+                    let schema_before_group_by = plan.input.schema();
+                    aggregate = Some(StorePlanAggregate {
+                        funcs: plan
+                            .aggr_expr
+                            .iter()
+                            .map(|expr| expr.to_aggregate_function(&schema_before_group_by))
+                            .collect::<Result<Vec<_>>>()?,
+                        arg_names: plan
+                            .aggr_expr
+                            .iter()
+                            .map(|expr| expr.to_aggregate_function_names())
+                            .collect::<Result<Vec<_>>>()?,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        Ok(StorePlan {
+            read,
+            steps,
+            aggregate,
+            schema,
+        })
+    }
+
+    /// The schema of the blocks which the plan returns.
+    pub fn schema(&self) -> DataSchemaRef {
+        self.schema.clone()
+    }
+
+    /// Executes the plan on the blocks read from a part, the blocks of the results are passed to
+    /// `send` until it returns false.
+    pub fn execute(
+        &self,
+        blocks: impl Iterator<Item = Result<DataBlock>>,
+        mut send: impl FnMut(DataBlock) -> Result<bool>,
+    ) -> Result<()> {
+        let arena = Bump::new();
+        let places: Vec<StateAddr> = match &self.aggregate {
+            None => vec![],
+            Some(aggregate) => aggregate
+                .funcs
+                .iter()
+                .map(|func| func.allocate_state(&arena))
+                .collect(),
+        };
+
+        for block in blocks {
+            let block = match self.execute_steps(self.fill_default_values(&block?)?)? {
+                Some(block) => block,
+                None => continue,
+            };
+            match &self.aggregate {
+                Some(aggregate) => aggregate.accumulate(&places, &block)?,
+                None if !send(block)? => return Ok(()),
+                None => {}
+            }
+        }
+
+        if let Some(aggregate) = &self.aggregate {
+            send(aggregate.states(&places, self.schema.clone())?)?;
+        }
+        Ok(())
+    }
+
+    // A part written before some columns were added to the table does not have them, they take
+    // the default values sent by the query, or NULL.
+    fn fill_default_values(&self, block: &DataBlock) -> Result<DataBlock> {
+        let rows = block.num_rows();
+        let default_values = &self.read.scan_plan.push_downs.default_values;
+        let mut columns = Vec::with_capacity(self.read.schema.fields().len());
+        for field in self.read.schema.fields() {
+            let column = match block.try_column_by_name(field.name()) {
+                Ok(column) => column.clone(),
+                Err(_) => match default_values.iter().find(|(name, _)| name == field.name()) {
+                    Some((_, value)) => DataColumn::Constant(value.clone(), rows),
+                    None if field.is_nullable() => {
+                        DataColumn::Constant(DataValue::from(field.data_type()), rows)
+                    }
+                    None => {
+                        return Err(ErrorCode::BadArguments(format!(
+                            "Column '{}' is not nullable and has no default value",
+                            field.name()
+                        )))
+                    }
+                },
+            };
+            columns.push(column);
+        }
+        Ok(DataBlock::create(self.read.schema.clone(), columns))
+    }
+
+    // Returns None if no row of the block passes the filters.
+    fn execute_steps(&self, mut block: DataBlock) -> Result<Option<DataBlock>> {
+        for step in &self.steps {
+            block = match step {
+                StorePlanStep::Filter { column_name, chain } => {
+                    let columns = Self::execute_chain(chain, &block)?;
+                    let filter = Self::column(&columns, column_name)?.to_array()?;
+                    let passed = filter
+                        .bool()?
+                        .downcast_iter()
+                        .enumerate()
+                        .filter_map(|(row, v)| match v {
+                            Some(true) => Some(row as u32),
+                            _ => None,
+                        })
+                        .collect::<Vec<_>>();
+                    if passed.is_empty() {
+                        return Ok(None);
+                    }
+                    match passed.len() == block.num_rows() {
+                        true => block,
+                        false => DataBlock::block_take_by_indices(&block, &[], &passed)?,
+                    }
+                }
+                StorePlanStep::Expression { chain, schema } => {
+                    let columns = Self::execute_chain(chain, &block)?;
+                    let projected = schema
+                        .fields()
+                        .iter()
+                        .map(|f| Self::column(&columns, f.name()).cloned())
+                        .collect::<Result<Vec<_>>>()?;
+                    DataBlock::create(schema.clone(), projected)
+                }
+            };
+        }
+        Ok(Some(block))
+    }
+
+    // Returns the columns of the block and of the actions of the chain by their names.
+    fn execute_chain(
+        chain: &ExpressionChain,
+        block: &DataBlock,
+    ) -> Result<HashMap<String, DataColumn>> {
+        let rows = block.num_rows();
+        let mut columns = HashMap::new();
+        for f in block.schema().fields() {
+            columns.insert(
+                f.name().clone(),
+                block.try_column_by_name(f.name())?.clone(),
+            );
+        }
+
+        for action in &chain.actions {
+            if columns.contains_key(action.column_name()) {
+                continue;
+            }
+            let column = match action {
+                ExpressionAction::Input(input) => block.try_column_by_name(&input.name)?.clone(),
+                ExpressionAction::Constant(constant) => {
+                    DataColumn::Constant(constant.value.clone(), rows)
+                }
+                ExpressionAction::Alias(alias) => Self::column(&columns, &alias.arg_name)?.clone(),
+                ExpressionAction::Function(f) => {
+                    let args = f
+                        .arg_names
+                        .iter()
+                        .map(|arg| Self::column(&columns, arg).cloned())
+                        .collect::<Result<Vec<_>>>()?;
+                    f.to_function()?.eval(&args, rows)?
+                }
+            };
+            columns.insert(action.column_name().to_string(), column);
+        }
+        Ok(columns)
+    }
+
+    fn column<'a>(columns: &'a HashMap<String, DataColumn>, name: &str) -> Result<&'a DataColumn> {
+        columns.get(name).ok_or_else(|| {
+            ErrorCode::LogicalError(format!(
+                "Column {} must be evaluated before it is used",
+                name
+            ))
+        })
+    }
+}
+
+impl StorePlanAggregate {
+    fn accumulate(&self, places: &[StateAddr], block: &DataBlock) -> Result<()> {
+        let rows = block.num_rows();
+        for (idx, func) in self.funcs.iter().enumerate() {
+            let mut arg_columns = vec![];
+            for name in self.arg_names[idx].iter() {
+                arg_columns.push(block.try_column_by_name(name)?.clone());
+            }
+            func.accumulate(places[idx], &arg_columns, rows)?;
+        }
+        Ok(())
+    }
+
+    fn states(&self, places: &[StateAddr], schema: DataSchemaRef) -> Result<DataBlock> {
+        let mut columns: Vec<Series> = vec![];
+        for (idx, func) in self.funcs.iter().enumerate() {
+            let mut writer = vec![];
+            func.serialize_state(places[idx], &mut writer)?;
+            let mut array_builder = BinaryArrayBuilder::new(4);
+            array_builder.append_value(writer);
+            columns.push(array_builder.finish().into_series());
+        }
+        Ok(DataBlock::create_by_array(schema, columns))
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+
+use common_datablocks::assert_blocks_eq;
+use common_datablocks::DataBlock;
+use common_datavalues::prelude::*;
+use common_exception::Result;
+use common_planners::add;
+use common_planners::col;
+use common_planners::lit;
+use common_planners::sum;
+use common_planners::Extras;
+use common_planners::PlanBuilder;
+use common_planners::PlanNode;
+use common_planners::ReadDataSourcePlan;
+use common_planners::ScanPlan;
+
+use crate::executor::store_plan::StorePlan;
+
+fn read_plan() -> PlanNode {
+    PlanNode::ReadSource(ReadDataSourcePlan {
+        schema: DataSchemaRefExt::create(vec![
+            DataField::new("a", DataType::Int64, false),
+            DataField::new("b", DataType::Int64, false),
+            DataField::new("c", DataType::Int64, false),
+        ]),
+        scan_plan: Arc::new(ScanPlan {
+            push_downs: Extras {
+                default_values: vec![("c".to_string(), DataValue::Int64(Some(100)))],
+                ..Extras::default()
+            },
+            ..ScanPlan::empty()
+        }),
+        remote: true,
+        ..ReadDataSourcePlan::empty()
+    })
+}
+
+// The part was written before the column c was added.
+fn part_blocks() -> Vec<Result<DataBlock>> {
+    let schema = DataSchemaRefExt::create(vec![
+        DataField::new("a", DataType::Int64, false),
+        DataField::new("b", DataType::Int64, false),
+    ]);
+    vec![
+        Ok(DataBlock::create_by_array(schema.clone(), vec![
+            Series::new(vec![1i64, 2, 3]),
+            Series::new(vec![10i64, 20, 30]),
+        ])),
+        Ok(DataBlock::create_by_array(schema, vec![
+            Series::new(vec![0i64, 4]),
+            Series::new(vec![40i64, 50]),
+        ])),
+    ]
+}
+
+#[test]
+fn test_store_plan_filter_expression() -> Result<()> {
+    let plan = PlanBuilder::from(&read_plan())
+        .filter(col("a").gt(lit(1i64)))?
+        .expression(&[add(col("b"), col("c"))], "")?
+        .build()?;
+    let store_plan = StorePlan::try_create(plan)?;
+
+    let mut blocks = vec![];
+    store_plan.execute(part_blocks().into_iter(), |block| {
+        blocks.push(block);
+        Ok(true)
+    })?;
+    let expected = vec![
+        "+---+----+-----+---------+",
+        "| a | b  | c   | (b + c) |",
+        "+---+----+-----+---------+",
+        "| 2 | 20 | 100 | 120     |",
+        "| 3 | 30 | 100 | 130     |",
+        "| 4 | 50 | 100 | 150     |",
+        "+---+----+-----+---------+",
+    ];
+    assert_blocks_eq(expected, &blocks);
+
+    // The blocks are not sent once the reader is gone.
+    let mut sent = 0;
+    store_plan.execute(part_blocks().into_iter(), |_| {
+        sent += 1;
+        Ok(false)
+    })?;
+    assert_eq!(1, sent);
+
+    Ok(())
+}
+
+#[test]
+fn test_store_plan_aggregate() -> Result<()> {
+    let aggr_expr = vec![sum(col("b"))];
+    let plan = PlanBuilder::from(&read_plan())
+        .filter(col("a").gt(lit(1i64)))?
+        .aggregate_partial(&aggr_expr, &[])?
+        .build()?;
+    let store_plan = StorePlan::try_create(plan.clone())?;
+    assert_eq!(plan.schema(), store_plan.schema());
+
+    let mut blocks = vec![];
+    store_plan.execute(part_blocks().into_iter(), |block| {
+        blocks.push(block);
+        Ok(true)
+    })?;
+    assert_eq!(1, blocks.len());
+
+    // The state of the part is merged by the query.
+    let func = aggr_expr[0].to_aggregate_function(&read_plan().schema())?;
+    let arena = bumpalo::Bump::new();
+    let place = func.allocate_state(&arena);
+    let states = blocks[0].column(0).to_array()?;
+    func.deserialize_state(place, states.binary()?.downcast_ref().value(0))?;
+    assert_eq!(DataValue::Int64(Some(100)), func.merge_result(place)?);
+
+    Ok(())
+}
+
+#[test]
+fn test_store_plan_illegal() -> Result<()> {
+    // The group by is not executed by the store.
+    let plan = PlanBuilder::from(&read_plan())
+        .aggregate_partial(&[sum(col("b"))], &[col("a")])?
+        .build()?;
+    let res = StorePlan::try_create(plan);
+    assert_eq!(
+        "Code: 5000, displayText = the store can not execute the plan node AggregatorPartialPlan.",
+        res.err().unwrap().to_string()
+    );
+
+    Ok(())
+}
@@ -28,6 +28,11 @@ impl LocalFS {
         };
         Ok(f)
     }
+
+    /// Returns true if the file exists.
+    pub fn exists(&self, path: &str) -> bool {
+        Path::new(self.root.as_path()).join(path).is_file()
+    }
 }
 
 #[async_trait]
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use async_raft::AppDataResponse;
+use common_flights::meta_api_impl::PlacementPolicy;
 use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::SeqValue;
@@ -11,8 +12,10 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::meta_service::Node;
+use crate::meta_service::NodeHeartbeat;
 use crate::meta_service::RaftMes;
 use crate::meta_service::RetryableError;
+use crate::meta_service::Slot;
 
 /// The state of an applied raft log.
 /// Normally it includes two fields: the state before applying and the state after applying the log.
@@ -34,6 +37,26 @@ pub enum AppliedState {
         result: Option<Node>,
     },
 
+    NodeHeartbeat {
+        prev: Option<NodeHeartbeat>,
+        result: Option<NodeHeartbeat>,
+    },
+
+    Slot {
+        prev: Option<Slot>,
+        result: Option<Slot>,
+    },
+
+    PlacementPolicy {
+        prev: PlacementPolicy,
+        result: PlacementPolicy,
+    },
+
+    ReadOnly {
+        prev: bool,
+        result: bool,
+    },
+
     DataBase {
         prev: Option<Database>,
         result: Option<Database>,
@@ -53,6 +76,12 @@ pub enum AppliedState {
         prev: Option<SeqValue>,
         result: Option<SeqValue>,
     },
+
+    /// The number of restored databases and tables.
+    /// `None` if the dump is not imported because the catalog is not empty.
+    ImportMeta {
+        result: Option<(u64, u64)>,
+    },
 }
 
 impl AppDataResponse for AppliedState {}
@@ -83,6 +112,42 @@ impl From<(Option<Node>, Option<Node>)> for AppliedState {
     }
 }
 
+impl From<(Option<NodeHeartbeat>, Option<NodeHeartbeat>)> for AppliedState {
+    fn from(v: (Option<NodeHeartbeat>, Option<NodeHeartbeat>)) -> Self {
+        AppliedState::NodeHeartbeat {
+            prev: v.0,
+            result: v.1,
+        }
+    }
+}
+
+impl From<(Option<Slot>, Option<Slot>)> for AppliedState {
+    fn from(v: (Option<Slot>, Option<Slot>)) -> Self {
+        AppliedState::Slot {
+            prev: v.0,
+            result: v.1,
+        }
+    }
+}
+
+impl From<(PlacementPolicy, PlacementPolicy)> for AppliedState {
+    fn from(v: (PlacementPolicy, PlacementPolicy)) -> Self {
+        AppliedState::PlacementPolicy {
+            prev: v.0,
+            result: v.1,
+        }
+    }
+}
+
+impl From<(bool, bool)> for AppliedState {
+    fn from(v: (bool, bool)) -> Self {
+        AppliedState::ReadOnly {
+            prev: v.0,
+            result: v.1,
+        }
+    }
+}
+
 impl From<(Option<Database>, Option<Database>)> for AppliedState {
     fn from(v: (Option<Database>, Option<Database>)) -> Self {
         AppliedState::DataBase {
@@ -5,13 +5,16 @@
 use std::fmt;
 
 use async_raft::NodeId;
+use common_flights::meta_api_impl::PlacementPolicy;
 use common_metatypes::Database;
 use common_metatypes::MatchSeq;
 use common_metatypes::Table;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::meta_service::state_machine::MetaDump;
 use crate::meta_service::Node;
+use crate::meta_service::NodeHeartbeat;
 
 /// A Cmd describes what a user want to do to raft state machine
 /// and is the essential part of a raft log.
@@ -40,6 +43,28 @@ pub enum Cmd {
         node: Node,
     },
 
+    /// Record the latest heartbeat of a node.
+    NodeHeartbeat {
+        node_id: NodeId,
+        heartbeat: NodeHeartbeat,
+    },
+
+    /// Replace the nodes of a slot, e.g. to replace the dead nodes of it.
+    SetSlotNodes {
+        slot_index: u64,
+        node_ids: Vec<NodeId>,
+    },
+
+    /// Set the placement policy of the cluster, by which the slots are repaired.
+    SetPlacementPolicy {
+        policy: PlacementPolicy,
+    },
+
+    /// Put the meta service into the read-only mode or out of it, the DDL is rejected in it.
+    SetReadOnly {
+        read_only: bool,
+    },
+
     /// Add a database if absent
     CreateDatabase {
         // TODO(ariesdevil): add `seq` for distinguish between the results of the execution of
@@ -89,6 +114,11 @@ pub enum Cmd {
         key: String,
         seq: MatchSeq,
     },
+
+    /// Restore the catalog from a dump. It takes effect only when the catalog is empty.
+    ImportMeta {
+        dump: MetaDump,
+    },
 }
 
 impl fmt::Display for Cmd {
@@ -106,6 +136,29 @@ impl fmt::Display for Cmd {
             Cmd::AddNode { node_id, node } => {
                 write!(f, "add_node:{}={}", node_id, node)
             }
+            Cmd::NodeHeartbeat { node_id, heartbeat } => {
+                write!(
+                    f,
+                    "node_heartbeat:{}={}@{}",
+                    node_id, heartbeat.flight_address, heartbeat.time_ms
+                )
+            }
+            Cmd::SetSlotNodes {
+                slot_index,
+                node_ids,
+            } => {
+                write!(f, "set_slot_nodes:{}={:?}", slot_index, node_ids)
+            }
+            Cmd::SetPlacementPolicy { policy } => {
+                write!(
+                    f,
+                    "set_placement_policy:failure_domain={}",
+                    policy.failure_domain
+                )
+            }
+            Cmd::SetReadOnly { read_only } => {
+                write!(f, "set_read_only:{}", read_only)
+            }
             Cmd::CreateDatabase {
                 name,
                 if_not_exists,
@@ -149,6 +202,15 @@ impl fmt::Display for Cmd {
             Cmd::DeleteKVByKey { key, seq } => {
                 write!(f, "delete_by_key_kv: {}({:?})", key, seq)
             }
+            Cmd::ImportMeta { dump } => {
+                write!(
+                    f,
+                    "import_meta: version:{}, databases:{}, tables:{}",
+                    dump.version,
+                    dump.databases.len(),
+                    dump.tables.len()
+                )
+            }
         }
     }
 }
@@ -0,0 +1,107 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_flights::meta_api_impl::GetMetaStatusActionResult;
+use common_runtime::tokio;
+use metrics::gauge;
+
+use crate::meta_service::node_health::now_ms;
+use crate::meta_service::MetaNode;
+
+pub static METRIC_META_RAFT_TERM: &str = "meta.raft_term";
+pub static METRIC_META_RAFT_IS_LEADER: &str = "meta.raft_is_leader";
+pub static METRIC_META_RAFT_LAST_LOG_INDEX: &str = "meta.raft_last_log_index";
+pub static METRIC_META_RAFT_LAST_APPLIED: &str = "meta.raft_last_applied";
+pub static METRIC_META_SNAPSHOT_AGE_MS: &str = "meta.snapshot_age_ms";
+pub static METRIC_META_PEER_LAG: &str = "meta.peer_lag";
+
+/// The interval at which the raft status is published to the metrics, besides the changes of it.
+const STATUS_METRICS_INTERVAL: Duration = Duration::from_secs(1);
+
+impl MetaNode {
+    /// Returns the raft status of this node.
+    /// The lags of the peers are by their heartbeats, see `StateMachine::peer_lags`.
+    pub async fn get_status(&self) -> GetMetaStatusActionResult {
+        let metrics = self.metrics_rx.borrow().clone();
+
+        let (snapshot_last_log_index, snapshot_age_ms) =
+            match &*self.sto.current_snapshot.read().await {
+                Some(snapshot) => (
+                    Some(snapshot.meta.last_log_id.index),
+                    Some(now_ms().saturating_sub(snapshot.time_ms)),
+                ),
+                None => (None, None),
+            };
+
+        let (peer_lags, read_only) = {
+            let sm = self.sto.state_machine.read().await;
+            (
+                sm.peer_lags(self.sto.id, metrics.last_log_index),
+                sm.read_only,
+            )
+        };
+
+        GetMetaStatusActionResult {
+            id: self.sto.id,
+            state: format!("{:?}", metrics.state),
+            current_term: metrics.current_term,
+            current_leader: metrics.current_leader,
+            last_log_index: metrics.last_log_index,
+            last_applied: metrics.last_applied,
+            snapshot_last_log_index,
+            snapshot_age_ms,
+            peer_lags,
+            read_only,
+        }
+    }
+
+    /// Spawn a task that publishes the raft status of this node to the metrics whenever it
+    /// changes, and every `STATUS_METRICS_INTERVAL` for the age of the snapshot.
+    pub async fn start_status_metrics(mn: Arc<MetaNode>) {
+        let mut running_rx = mn.running_rx.clone();
+        let mut metrics_rx = mn.metrics_rx.clone();
+        let mut jh = mn.join_handles.lock().await;
+
+        let mn = mn.clone();
+        let h = tokio::task::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = running_rx.changed() => {
+                        return Ok::<(), common_exception::ErrorCode>(());
+                    }
+                    changed = metrics_rx.changed() => {
+                        if changed.is_err() {
+                            // The raft is shut down.
+                            return Ok(());
+                        }
+                    }
+                    _ = tokio::time::sleep(STATUS_METRICS_INTERVAL) => {}
+                }
+
+                let status = mn.get_status().await;
+                gauge!(METRIC_META_RAFT_TERM, status.current_term as f64);
+                let is_leader = status.current_leader == Some(status.id);
+                gauge!(
+                    METRIC_META_RAFT_IS_LEADER,
+                    if is_leader { 1.0 } else { 0.0 }
+                );
+                gauge!(
+                    METRIC_META_RAFT_LAST_LOG_INDEX,
+                    status.last_log_index as f64
+                );
+                gauge!(METRIC_META_RAFT_LAST_APPLIED, status.last_applied as f64);
+                if let Some(age) = status.snapshot_age_ms {
+                    gauge!(METRIC_META_SNAPSHOT_AGE_MS, age as f64);
+                }
+                for (peer, lag) in status.peer_lags.iter() {
+                    gauge!(METRIC_META_PEER_LAG, *lag as f64, "peer" => peer.to_string());
+                }
+            }
+        });
+        jh.push(h);
+    }
+}
@@ -7,7 +7,9 @@ pub mod cmd;
 pub mod errors;
 pub mod log_entry;
 pub mod meta_service_impl;
+pub mod meta_status;
 pub mod network;
+pub mod node_health;
 pub mod placement;
 pub mod raft_log;
 pub mod raft_state;
@@ -34,7 +36,9 @@ pub use raftmeta::MetaNode;
 pub use raftmeta::MetaStore;
 pub use sled_serde::SledSerde;
 pub use snapshot::Snapshot;
+pub use state_machine::MetaDump;
 pub use state_machine::Node;
+pub use state_machine::NodeHeartbeat;
 pub use state_machine::Slot;
 pub use state_machine::StateMachine;
 
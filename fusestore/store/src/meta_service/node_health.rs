@@ -0,0 +1,169 @@
+// Copyright 2020-2021 The Datafuse Authors.
+//
+// SPDX-License-Identifier: Apache-2.0.
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use common_flights::meta_api_impl::PlacementPolicy;
+use common_runtime::tokio;
+use common_tracing::tracing;
+
+use crate::configs;
+use crate::meta_service::AppliedState;
+use crate::meta_service::Cmd;
+use crate::meta_service::LogEntry;
+use crate::meta_service::MetaNode;
+use crate::meta_service::NodeHeartbeat;
+use crate::meta_service::NodeId;
+
+/// Returns the unix time in milli seconds.
+pub fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl MetaNode {
+    /// Spawn a task that reports the heartbeats of this node with its labels every
+    /// `node_heartbeat_interval`. On the leader it also replaces the dead nodes of the slots and
+    /// the nodes out of the placement policy with the healthy ones, whose files are then pulled
+    /// by the nodes newly assigned to the slots.
+    pub async fn start_node_health(
+        mn: Arc<MetaNode>,
+        config: &configs::Config,
+        flight_address: String,
+        labels: BTreeMap<String, String>,
+    ) {
+        let interval = Duration::from_millis(config.node_heartbeat_interval);
+        let timeout_ms = config.node_dead_timeout;
+        let mut running_rx = mn.running_rx.clone();
+        let mut jh = mn.join_handles.lock().await;
+
+        let mn = mn.clone();
+        let h = tokio::task::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = running_rx.changed() => {
+                        return Ok::<(), common_exception::ErrorCode>(());
+                    }
+                    _ = tokio::time::sleep(interval) => {}
+                }
+
+                let heartbeat = NodeHeartbeat {
+                    flight_address: flight_address.clone(),
+                    // Stamped by the leader when it receives the heartbeat.
+                    time_ms: 0,
+                    labels: labels.clone(),
+                    last_applied: mn.metrics_rx.borrow().last_applied,
+                };
+                if let Err(e) = mn.send_heartbeat(mn.sto.id, heartbeat).await {
+                    tracing::warn!("fail to send heartbeat: my id={}, {}", mn.sto.id, e);
+                }
+
+                if mn.metrics_rx.borrow().current_leader == Some(mn.sto.id) {
+                    if let Err(e) = mn.repair_slots(now_ms(), timeout_ms).await {
+                        tracing::warn!("fail to repair slots: {}", e);
+                    }
+                }
+            }
+        });
+        jh.push(h);
+    }
+
+    /// Record the heartbeat of a node in the meta service.
+    pub async fn send_heartbeat(
+        &self,
+        node_id: NodeId,
+        heartbeat: NodeHeartbeat,
+    ) -> common_exception::Result<()> {
+        self.write(LogEntry {
+            txid: None,
+            cmd: Cmd::NodeHeartbeat { node_id, heartbeat },
+        })
+        .await?;
+        Ok(())
+    }
+
+    /// Replace the dead nodes of the slots and fill the slots up to the replication, with the
+    /// healthy nodes. Returns the number of the changed slots.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn repair_slots(
+        &self,
+        now_ms: u64,
+        timeout_ms: u64,
+    ) -> common_exception::Result<usize> {
+        let repairs = {
+            let sm = self.sto.state_machine.read().await;
+            sm.plan_slot_repairs(now_ms, timeout_ms)
+        };
+
+        for (slot_index, node_ids) in repairs.iter() {
+            tracing::info!("repair slot {}: nodes {:?}", slot_index, node_ids);
+            self.write(LogEntry {
+                txid: None,
+                cmd: Cmd::SetSlotNodes {
+                    slot_index: *slot_index,
+                    node_ids: node_ids.clone(),
+                },
+            })
+            .await?;
+        }
+        Ok(repairs.len())
+    }
+
+    /// Set the placement policy of the cluster. Returns the policy before it.
+    pub async fn set_placement_policy(
+        &self,
+        policy: PlacementPolicy,
+    ) -> common_exception::Result<PlacementPolicy> {
+        let rst = self
+            .write(LogEntry {
+                txid: None,
+                cmd: Cmd::SetPlacementPolicy { policy },
+            })
+            .await?;
+        match rst {
+            AppliedState::PlacementPolicy { prev, .. } => Ok(prev),
+            _ => Err(common_exception::ErrorCode::MetaNodeInternalError(
+                "not a PlacementPolicy result",
+            )),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_placement_policy(&self) -> PlacementPolicy {
+        // inconsistent get: from local state machine
+
+        let sm = self.sto.state_machine.read().await;
+        sm.placement_policy.clone()
+    }
+
+    /// Returns the time of the latest heartbeat, which is the clock of the leader as of the last
+    /// applied log. A node other than the leader judges the liveness by it instead of its own clock.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_heartbeat_clock(&self) -> u64 {
+        // inconsistent get: from local state machine
+
+        let sm = self.sto.state_machine.read().await;
+        sm.latest_heartbeat_ms()
+    }
+
+    /// Returns the files this node stores by the placement, with the flight addresses of the
+    /// healthy nodes to pull them from.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_files_of_node(
+        &self,
+        now_ms: u64,
+        timeout_ms: u64,
+    ) -> Vec<(String, Vec<String>)> {
+        // inconsistent get: from local state machine
+
+        let sm = self.sto.state_machine.read().await;
+        sm.files_of_node(self.sto.id, now_ms, timeout_ms)
+    }
+}
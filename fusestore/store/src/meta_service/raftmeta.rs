@@ -26,6 +26,7 @@ use async_raft::SnapshotPolicy;
 use common_exception::prelude::ErrorCode;
 use common_exception::prelude::ToErrorCode;
 use common_flights::storage_api_impl::AppendResult;
+use common_flights::storage_api_impl::AppendTxResult;
 use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::SeqValue;
@@ -39,12 +40,14 @@ use common_runtime::tokio::task::JoinHandle;
 use common_tracing::tracing;
 
 use crate::configs;
+use crate::meta_service::node_health::now_ms;
 use crate::meta_service::raft_log::RaftLog;
 use crate::meta_service::raft_state::RaftState;
 use crate::meta_service::sled_serde::SledOrderedSerde;
 use crate::meta_service::AppliedState;
 use crate::meta_service::Cmd;
 use crate::meta_service::LogEntry;
+use crate::meta_service::MetaDump;
 use crate::meta_service::MetaServiceClient;
 use crate::meta_service::MetaServiceImpl;
 use crate::meta_service::MetaServiceServer;
@@ -360,6 +363,7 @@ impl RaftStorage<LogEntry, AppliedState> for MetaStore {
             let snapshot = Snapshot {
                 meta: meta.clone(),
                 data: data.clone(),
+                time_ms: now_ms(),
             };
             self.log
                 .insert(&Entry::new_snapshot_pointer(&snapshot.meta))
@@ -394,6 +398,7 @@ impl RaftStorage<LogEntry, AppliedState> for MetaStore {
         let new_snapshot = Snapshot {
             meta: meta.clone(),
             data: snapshot.into_inner(),
+            time_ms: now_ms(),
         };
 
         {
@@ -865,6 +870,48 @@ impl MetaNode {
         sm.get_table(tid)
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn get_meta_version(&self) -> u64 {
+        // inconsistent get: from local state machine
+
+        let sm = self.sto.state_machine.read().await;
+        sm.get_meta_version()
+    }
+
+    /// Put the meta service into the read-only mode or out of it. Returns whether it was
+    /// read-only before.
+    pub async fn set_read_only(&self, read_only: bool) -> common_exception::Result<bool> {
+        let rst = self
+            .write(LogEntry {
+                txid: None,
+                cmd: Cmd::SetReadOnly { read_only },
+            })
+            .await?;
+        match rst {
+            AppliedState::ReadOnly { prev, .. } => Ok(prev),
+            _ => Err(ErrorCode::MetaNodeInternalError("not a ReadOnly result")),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn is_read_only(&self) -> bool {
+        // inconsistent get: from local state machine
+
+        let sm = self.sto.state_machine.read().await;
+        sm.read_only
+    }
+
+    /// Returns `MetaServiceReadOnly` if the meta service is read-only, the DDL checks it before
+    /// it is written.
+    pub async fn check_writable(&self) -> common_exception::Result<()> {
+        match self.is_read_only().await {
+            true => Err(ErrorCode::MetaServiceReadOnly(
+                "the meta service is read-only, DDL is rejected until it is set writable",
+            )),
+            false => Ok(()),
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     pub async fn get_data_parts(
         &self,
@@ -881,9 +928,35 @@ impl MetaNode {
         db_name: &str,
         table_name: &str,
         append_res: &AppendResult,
+        deduplicate: bool,
+    ) -> AppendResult {
+        let mut sm = self.sto.state_machine.write().await;
+        sm.append_data_parts(db_name, table_name, append_res, deduplicate)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn stage_data_parts(
+        &self,
+        tx_id: &str,
+        db_name: &str,
+        table_name: &str,
+        append_res: &AppendResult,
+        deduplicate: bool,
     ) {
         let mut sm = self.sto.state_machine.write().await;
-        sm.append_data_parts(db_name, table_name, append_res)
+        sm.stage_data_parts(tx_id, db_name, table_name, append_res, deduplicate)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn commit_data_parts(&self, tx_id: &str) -> AppendTxResult {
+        let mut sm = self.sto.state_machine.write().await;
+        sm.commit_data_parts(tx_id)
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn abort_data_parts(&self, tx_id: &str) -> usize {
+        let mut sm = self.sto.state_machine.write().await;
+        sm.abort_data_parts(tx_id)
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
@@ -923,6 +996,14 @@ impl MetaNode {
         sm.prefix_list_kv(prefix)
     }
 
+    /// Take a portable dump of the catalog from local meta state machine.
+    #[tracing::instrument(level = "debug", skip(self))]
+    pub async fn export_meta(&self) -> MetaDump {
+        // inconsistent get: from local state machine
+        let sm = self.sto.state_machine.read().await;
+        sm.export_meta()
+    }
+
     /// Submit a write request to the known leader. Returns the response after applying the request.
     #[tracing::instrument(level = "info", skip(self))]
     pub async fn write(&self, req: LogEntry) -> common_exception::Result<AppliedState> {
@@ -988,11 +1069,21 @@ impl MetaNode {
     /// Write a meta log through local raft node.
     /// It works only when this node is the leader,
     /// otherwise it returns ClientWriteError::ForwardToLeader error indicating the latest leader.
+    ///
+    /// A node heartbeat is stamped with the time the leader receives it, so that the liveness of
+    /// the nodes is judged by the clock of the leader instead of the clocks of the nodes.
     #[tracing::instrument(level = "info", skip(self))]
     pub async fn write_to_local_leader(
         &self,
-        req: LogEntry,
+        mut req: LogEntry,
     ) -> common_exception::Result<Result<AppliedState, RetryableError>> {
+        if let Cmd::NodeHeartbeat {
+            ref mut heartbeat, ..
+        } = req.cmd
+        {
+            heartbeat.time_ms = now_ms();
+        }
+
         let write_rst = self.raft.client_write(ClientWriteRequest::new(req)).await;
 
         tracing::debug!("raft.client_write rst: {:?}", write_rst);
@@ -14,10 +14,12 @@ use maplit::hashset;
 use pretty_assertions::assert_eq;
 
 use crate::configs;
+use crate::meta_service::node_health::now_ms;
 use crate::meta_service::AppliedState;
 use crate::meta_service::Cmd;
 use crate::meta_service::LogEntry;
 use crate::meta_service::MetaNode;
+use crate::meta_service::NodeHeartbeat;
 use crate::meta_service::NodeId;
 use crate::meta_service::RaftTxId;
 use crate::meta_service::RetryableError;
@@ -260,6 +262,41 @@ async fn test_meta_node_write_to_local_leader() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_meta_node_heartbeat_stamped_by_leader() -> anyhow::Result<()> {
+    // - Start a leader and a follower;
+    // - Send a heartbeat with a time far ahead from the follower.
+    // - Assert that the heartbeat is stamped with the time the leader receives it.
+
+    common_tracing::init_default_tracing();
+
+    let (mut _nlog, tcs) = setup_cluster(hashset![0, 1], hashset![]).await?;
+    let all = test_context_nodes(&tcs);
+
+    let leader_id = all[0].raft.metrics().borrow().current_leader.unwrap();
+    let follower = all.iter().find(|mn| mn.sto.id != leader_id).unwrap();
+
+    let before = now_ms();
+    follower
+        .send_heartbeat(follower.sto.id, NodeHeartbeat {
+            time_ms: u64::MAX,
+            ..Default::default()
+        })
+        .await?;
+    let after = now_ms();
+
+    let leader = &all[leader_id as usize];
+    let time_ms = leader.sto.state_machine.read().await.node_heartbeats[&follower.sto.id].time_ms;
+    assert!(
+        before <= time_ms && time_ms <= after,
+        "time_ms: {}",
+        time_ms
+    );
+    assert_eq!(time_ms, leader.get_heartbeat_clock().await);
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_meta_node_set_file() -> anyhow::Result<()> {
     // - Start a leader, 2 followers and 2 non-voter;
@@ -13,4 +13,8 @@ pub struct Snapshot {
 
     /// The data of the state machine at the time of this snapshot.
     pub data: Vec<u8>,
+
+    /// The unix time in milli seconds this node built or installed the snapshot.
+    #[serde(default)]
+    pub time_ms: u64,
 }
@@ -4,13 +4,17 @@
 
 use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fmt;
 use std::fmt::Display;
 use std::fmt::Formatter;
 
 use async_raft::LogId;
 use common_exception::prelude::ErrorCode;
+use common_flights::meta_api_impl::PlacementPolicy;
 use common_flights::storage_api_impl::AppendResult;
+use common_flights::storage_api_impl::AppendTxResult;
 use common_flights::storage_api_impl::DataPartInfo;
 use common_metatypes::Database;
 use common_metatypes::MatchSeqExt;
@@ -35,6 +39,27 @@ const SEQ_GENERIC_KV: &str = "generic_kv";
 const SEQ_DATABASE_ID: &str = "database_id";
 /// seq number key to generate table id
 const SEQ_TABLE_ID: &str = "table_id";
+/// seq number key of the catalog version, which is bumped every time a database or table is created or dropped.
+const SEQ_META_VER: &str = "meta_ver";
+/// seq number key prefix of the data version of a table, which is bumped every time the parts
+/// of the table change. The parts are stamped with the version that makes them visible.
+const SEQ_DATA_VER: &str = "data_ver";
+
+/// The format version of a `MetaDump`.
+/// Bump it whenever the layout of `MetaDump` changes incompatibly.
+pub const META_DUMP_VERSION: u64 = 1;
+
+/// The number of the latest appended blocks of a table whose hashes are kept for deduplication.
+pub const BLOCK_HASH_WINDOW: usize = 100;
+
+/// The parts of an append staged in a transaction, they are deduplicated when it commits.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StagedAppend {
+    pub db_name: String,
+    pub table_name: String,
+    pub append_res: AppendResult,
+    pub deduplicate: bool,
+}
 
 /// Replication defines the replication strategy.
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -72,8 +97,20 @@ pub struct StateMachine {
     pub slots: Vec<Slot>,
     pub nodes: HashMap<NodeId, Node>,
 
+    /// The latest heartbeats of the nodes, by which the dead nodes are detected.
+    #[serde(default)]
+    pub node_heartbeats: HashMap<NodeId, NodeHeartbeat>,
+
     pub replication: Replication,
 
+    /// The rules to place the nodes of the slots in the failure domains.
+    #[serde(default)]
+    pub placement_policy: PlacementPolicy,
+
+    /// The DDL is rejected if it is true, e.g. during an upgrade or a backup.
+    #[serde(default)]
+    pub read_only: bool,
+
     /// db name to database mapping
     pub databases: BTreeMap<String, Database>,
 
@@ -83,12 +120,37 @@ pub struct StateMachine {
     /// table parts， db -> (table -> data parts)
     pub tbl_parts: HashMap<String, HashMap<String, Vec<DataPartInfo>>>,
 
+    /// hashes of the latest appended blocks, db -> (table -> block hashes)
+    #[serde(default)]
+    pub tbl_block_hashes: HashMap<String, HashMap<String, VecDeque<String>>>,
+
+    /// parts staged by the appends of a transaction, which are invisible until the transaction commits,
+    /// tx id -> [staged append]
+    #[serde(default)]
+    pub pending_parts: HashMap<String, Vec<StagedAppend>>,
+
     /// A kv store of all other general purpose information.
     /// The value is tuple of a monotonic sequence number and userdata value in string.
     /// The sequence number is guaranteed to increment(by some value greater than 0) everytime the record changes.
     pub kv: BTreeMap<String, (u64, Vec<u8>)>,
 }
 
+/// A portable dump of the catalog in a state machine, used to back up meta data of a cluster and
+/// restore it on another one.
+///
+/// Raft states(`last_applied_log`, `client_last_resp`) and cluster topology(`nodes`, `slots`) are not included,
+/// since they belong to the cluster the dump is taken from.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct MetaDump {
+    /// Format version, see `META_DUMP_VERSION`.
+    pub version: u64,
+    pub sequences: BTreeMap<String, u64>,
+    pub databases: BTreeMap<String, Database>,
+    pub tables: BTreeMap<u64, Table>,
+    pub tbl_parts: HashMap<String, HashMap<String, Vec<DataPartInfo>>>,
+    pub kv: BTreeMap<String, (u64, Vec<u8>)>,
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct StateMachineBuilder {
     /// The number of slots to allocated.
@@ -121,10 +183,15 @@ impl StateMachineBuilder {
             sequences: BTreeMap::new(),
             slots: Vec::with_capacity(initial_slots as usize),
             nodes: HashMap::new(),
+            node_heartbeats: HashMap::new(),
             replication,
+            placement_policy: PlacementPolicy::default(),
+            read_only: false,
             databases: BTreeMap::new(),
             tables: BTreeMap::new(),
             tbl_parts: HashMap::new(),
+            tbl_block_hashes: HashMap::new(),
+            pending_parts: HashMap::new(),
             kv: BTreeMap::new(),
         };
         for _i in 0..initial_slots {
@@ -221,6 +288,45 @@ impl StateMachine {
                 }
             }
 
+            Cmd::NodeHeartbeat {
+                ref node_id,
+                ref heartbeat,
+            } => {
+                let prev = self.node_heartbeats.insert(*node_id, heartbeat.clone());
+                tracing::debug!("applied NodeHeartbeat: {}={:?}", node_id, heartbeat);
+                Ok((prev, Some(heartbeat.clone())).into())
+            }
+
+            Cmd::SetSlotNodes {
+                slot_index,
+                ref node_ids,
+            } => match self.slots.get_mut(slot_index as usize) {
+                Some(slot) => {
+                    let prev = slot.clone();
+                    slot.node_ids = node_ids.clone();
+                    tracing::info!(
+                        "applied SetSlotNodes: {}: {:?} -> {:?}",
+                        slot_index,
+                        prev.node_ids,
+                        node_ids
+                    );
+                    Ok((Some(prev), Some(slot.clone())).into())
+                }
+                None => Ok((None::<Slot>, None::<Slot>).into()),
+            },
+
+            Cmd::SetPlacementPolicy { ref policy } => {
+                let prev = std::mem::replace(&mut self.placement_policy, policy.clone());
+                tracing::info!("applied SetPlacementPolicy: {:?} -> {:?}", prev, policy);
+                Ok((prev, policy.clone()).into())
+            }
+
+            Cmd::SetReadOnly { read_only } => {
+                let prev = std::mem::replace(&mut self.read_only, read_only);
+                tracing::info!("applied SetReadOnly: {} -> {}", prev, read_only);
+                Ok((prev, read_only).into())
+            }
+
             Cmd::CreateDatabase { ref name, .. } => {
                 // - If the db present, return it.
                 // - Otherwise, create a new one with next seq number as database id, and add it in to store.
@@ -234,6 +340,7 @@ impl StateMachine {
                     };
 
                     self.databases.insert(name.clone(), db.clone());
+                    self.incr_seq(SEQ_META_VER);
                     tracing::debug!("applied CreateDatabase: {}={:?}", name, db);
 
                     Ok((None, Some(db)).into())
@@ -244,6 +351,7 @@ impl StateMachine {
                 let prev = self.databases.get(name).cloned();
                 if prev.is_some() {
                     self.databases.remove(name);
+                    self.incr_seq(SEQ_META_VER);
                     tracing::debug!("applied DropDatabase: {}", name);
                     Ok((prev, None).into())
                 } else {
@@ -269,10 +377,14 @@ impl StateMachine {
                         table_id: self.incr_seq(SEQ_TABLE_ID),
                         schema: table.schema.clone(),
                         parts: table.parts.clone(),
+                        comment: table.comment.clone(),
+                        engine: table.engine.clone(),
+                        options: table.options.clone(),
                     };
                     db.tables.insert(table_name.clone(), table.table_id);
                     self.databases.insert(db_name.clone(), db);
                     self.tables.insert(table.table_id, table.clone());
+                    self.incr_seq(SEQ_META_VER);
                     tracing::debug!("applied CreateTable: {}={:?}", table_name, table);
 
                     Ok((None, Some(table)).into())
@@ -290,6 +402,7 @@ impl StateMachine {
                     let tbl_id = tbl_id.to_owned();
                     db.tables.remove(table_name);
                     let prev = self.tables.remove(&tbl_id);
+                    self.incr_seq(SEQ_META_VER);
 
                     Ok((prev, None).into())
                 } else {
@@ -326,9 +439,59 @@ impl StateMachine {
                 tracing::debug!("applied DeleteByKeyKV: {} {}", key, seq);
                 Ok((prev, None).into())
             }
+
+            Cmd::ImportMeta { ref dump } => {
+                // A dump is only allowed to be restored into an empty catalog,
+                // otherwise ids in the dump would collide with the existing ones.
+                if !self.is_catalog_empty() {
+                    return Ok(AppliedState::ImportMeta { result: None });
+                }
+
+                self.import_meta(dump.clone());
+                self.incr_seq(SEQ_META_VER);
+                tracing::info!(
+                    "applied ImportMeta: databases: {}, tables: {}",
+                    self.databases.len(),
+                    self.tables.len()
+                );
+
+                Ok(AppliedState::ImportMeta {
+                    result: Some((self.databases.len() as u64, self.tables.len() as u64)),
+                })
+            }
         }
     }
 
+    /// Returns true if there is no database, table or kv record in this state machine.
+    pub fn is_catalog_empty(&self) -> bool {
+        self.databases.is_empty() && self.tables.is_empty() && self.kv.is_empty()
+    }
+
+    /// Take a portable dump of the catalog.
+    pub fn export_meta(&self) -> MetaDump {
+        MetaDump {
+            version: META_DUMP_VERSION,
+            sequences: self.sequences.clone(),
+            databases: self.databases.clone(),
+            tables: self.tables.clone(),
+            tbl_parts: self.tbl_parts.clone(),
+            kv: self.kv.clone(),
+        }
+    }
+
+    /// Replace the catalog with the one in `dump`.
+    /// Sequences are merged so that ids generated after restoring never reuse an id in the dump.
+    fn import_meta(&mut self, dump: MetaDump) {
+        for (k, v) in dump.sequences {
+            let seq = self.sequences.entry(k).or_insert(0);
+            *seq = std::cmp::max(*seq, v);
+        }
+        self.databases = dump.databases;
+        self.tables = dump.tables;
+        self.tbl_parts = dump.tbl_parts;
+        self.kv = dump.kv;
+    }
+
     /// Initialize slots by assign nodes to everyone of them randomly, according to replicationn config.
     pub fn init_slots(&mut self) -> common_exception::Result<()> {
         for i in 0..self.slots.len() {
@@ -361,6 +524,199 @@ impl StateMachine {
         Ok(())
     }
 
+    /// Returns the nodes whose latest heartbeat is older than `timeout_ms` at `now_ms`.
+    /// The nodes that never sent a heartbeat are neither dead nor healthy.
+    /// The heartbeats are stamped by the leader, `now_ms` is expected by the clock of the leader.
+    pub fn dead_nodes(&self, now_ms: u64, timeout_ms: u64) -> HashSet<NodeId> {
+        self.node_heartbeats
+            .iter()
+            .filter(|(node_id, hb)| {
+                self.nodes.contains_key(*node_id) && hb.time_ms.saturating_add(timeout_ms) < now_ms
+            })
+            .map(|(node_id, _)| *node_id)
+            .collect()
+    }
+
+    /// Returns the time of the latest heartbeat of the nodes, 0 if there is none.
+    pub fn latest_heartbeat_ms(&self) -> u64 {
+        self.node_heartbeats
+            .values()
+            .map(|hb| hb.time_ms)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the nodes whose latest heartbeat is within `timeout_ms` at `now_ms`, sorted by id.
+    pub fn healthy_nodes(&self, now_ms: u64, timeout_ms: u64) -> Vec<NodeId> {
+        let mut node_ids = self
+            .node_heartbeats
+            .iter()
+            .filter(|(node_id, hb)| {
+                self.nodes.contains_key(*node_id) && hb.time_ms.saturating_add(timeout_ms) >= now_ms
+            })
+            .map(|(node_id, _)| *node_id)
+            .collect::<Vec<_>>();
+        node_ids.sort_unstable();
+        node_ids
+    }
+
+    /// Returns the failure domain of a node, which is the value of the label of the placement
+    /// policy of it. None if the policy has no failure domain or the node has no such label.
+    pub fn failure_domain_of(&self, node_id: NodeId) -> Option<&str> {
+        if self.placement_policy.failure_domain.is_empty() {
+            return None;
+        }
+        self.node_heartbeats
+            .get(&node_id)?
+            .labels
+            .get(&self.placement_policy.failure_domain)
+            .map(String::as_str)
+    }
+
+    /// Plans the new nodes of the slots to restore the replication: the dead nodes are removed
+    /// from the slots, and the slots with fewer nodes than the replication are assigned the
+    /// healthy nodes not in them, the ones in the fewest slots first.
+    ///
+    /// With a failure domain in the placement policy, the nodes of the domains with the fewest
+    /// nodes of a slot are assigned to it first, and the nodes sharing a domain with another node
+    /// of a slot are replaced with the healthy nodes of the domains not in the slot, if any. The
+    /// nodes without the label of the failure domain are each in a domain of their own.
+    /// Returns the (slot index, node ids) of the slots to change.
+    pub fn plan_slot_repairs(&self, now_ms: u64, timeout_ms: u64) -> Vec<(u64, Vec<NodeId>)> {
+        let n = match self.replication {
+            Replication::Mirror(x) => x,
+        } as usize;
+        let dead = self.dead_nodes(now_ms, timeout_ms);
+
+        // The number of slots of every healthy node, which is updated as the slots are planned.
+        let mut loads = self
+            .healthy_nodes(now_ms, timeout_ms)
+            .into_iter()
+            .map(|node_id| (node_id, 0_usize))
+            .collect::<BTreeMap<_, _>>();
+        for node_id in self.slots.iter().flat_map(|slot| slot.node_ids.iter()) {
+            if let Some(load) = loads.get_mut(node_id) {
+                *load += 1;
+            }
+        }
+
+        let mut repairs = vec![];
+        for (slot_index, slot) in self.slots.iter().enumerate() {
+            let mut node_ids = slot
+                .node_ids
+                .iter()
+                .filter(|node_id| !dead.contains(*node_id))
+                .cloned()
+                .collect::<Vec<_>>();
+            if node_ids.is_empty() && !slot.node_ids.is_empty() {
+                tracing::warn!(
+                    "all the nodes of slot {} are dead: {:?}",
+                    slot_index,
+                    slot.node_ids
+                );
+            }
+
+            // The domains of the healthy nodes that no node of the slot is in, as many nodes
+            // sharing a domain are removed from the slot to be replaced with the nodes of them.
+            let slot_domains = node_ids
+                .iter()
+                .filter_map(|node_id| self.failure_domain_of(*node_id))
+                .collect::<HashSet<_>>();
+            let free_domains = loads
+                .keys()
+                .filter_map(|node_id| self.failure_domain_of(*node_id))
+                .filter(|domain| !slot_domains.contains(domain))
+                .collect::<HashSet<_>>();
+            let mut replaceable = free_domains.len();
+            let mut seen_domains = HashSet::new();
+            node_ids.retain(|node_id| match self.failure_domain_of(*node_id) {
+                Some(domain) if !seen_domains.insert(domain) && replaceable > 0 => {
+                    replaceable -= 1;
+                    if let Some(load) = loads.get_mut(node_id) {
+                        *load -= 1;
+                    }
+                    false
+                }
+                _ => true,
+            });
+
+            while node_ids.len() < n {
+                let candidate = loads
+                    .iter()
+                    .filter(|(node_id, _)| !node_ids.contains(*node_id))
+                    .min_by_key(|(node_id, load)| {
+                        let shared = match self.failure_domain_of(**node_id) {
+                            None => 0,
+                            Some(domain) => node_ids
+                                .iter()
+                                .filter(|id| self.failure_domain_of(**id) == Some(domain))
+                                .count(),
+                        };
+                        (shared, **load, **node_id)
+                    })
+                    .map(|(node_id, _)| *node_id);
+                match candidate {
+                    Some(node_id) => {
+                        node_ids.push(node_id);
+                        *loads.entry(node_id).or_default() += 1;
+                    }
+                    None => break,
+                }
+            }
+
+            if node_ids != slot.node_ids {
+                repairs.push((slot_index as u64, node_ids));
+            }
+        }
+        repairs
+    }
+
+    /// Returns the number of logs the nodes other than `node_id` are behind `last_log_index`, by
+    /// the last applied logs in their latest heartbeats.
+    pub fn peer_lags(&self, node_id: NodeId, last_log_index: u64) -> BTreeMap<NodeId, u64> {
+        self.node_heartbeats
+            .iter()
+            .filter(|(id, _)| **id != node_id && self.nodes.contains_key(*id))
+            .map(|(id, hb)| (*id, last_log_index.saturating_sub(hb.last_applied)))
+            .collect()
+    }
+
+    /// Returns the files `node_id` stores by the placement, with the flight addresses of the
+    /// other healthy nodes to pull their copies from, the nodes of the slot of a file first.
+    pub fn files_of_node(
+        &self,
+        node_id: NodeId,
+        now_ms: u64,
+        timeout_ms: u64,
+    ) -> Vec<(String, Vec<String>)> {
+        if self.slots.is_empty() {
+            return vec![];
+        }
+
+        let healthy = self.healthy_nodes(now_ms, timeout_ms);
+        let mut files = vec![];
+        for key in self.keys.keys() {
+            let slot = self.get_slot(self.slot_index_for_key(key));
+            if !slot.node_ids.contains(&node_id) {
+                continue;
+            }
+
+            let (mut sources, others): (Vec<NodeId>, Vec<NodeId>) = healthy
+                .iter()
+                .filter(|id| **id != node_id)
+                .cloned()
+                .partition(|id| slot.node_ids.contains(id));
+            sources.extend(others);
+            let addresses = sources
+                .iter()
+                .filter_map(|id| self.node_heartbeats.get(id))
+                .map(|hb| hb.flight_address.clone())
+                .collect();
+            files.push((key.clone(), addresses));
+        }
+        files
+    }
+
     #[tracing::instrument(level = "info", skip(self))]
     pub fn get_file(&self, key: &str) -> Option<String> {
         tracing::info!("meta::get_file: {}", key);
@@ -384,34 +740,136 @@ impl StateMachine {
         x.cloned()
     }
 
+    /// Returns the current catalog version.
+    /// A query node compares it with the version it has seen to tell whether its cached catalog is stale.
+    pub fn get_meta_version(&self) -> u64 {
+        self.sequences.get(SEQ_META_VER).cloned().unwrap_or(0)
+    }
+
     pub fn get_kv(&self, key: &str) -> Option<SeqValue> {
         let x = self.kv.get(key);
         x.cloned()
     }
 
+    /// Returns the data version of a table, 0 if no parts are ever appended.
+    pub fn get_data_version(&self, db_name: &str, table_name: &str) -> u64 {
+        let key = Self::data_version_key(db_name, table_name);
+        self.sequences.get(&key).cloned().unwrap_or(0)
+    }
+
+    fn data_version_key(db_name: &str, table_name: &str) -> String {
+        format!("{}/{}/{}", SEQ_DATA_VER, db_name, table_name)
+    }
+
+    /// Returns the parts of a table, with the labels of the nodes which hold their copies.
     pub fn get_data_parts(&self, db_name: &str, table_name: &str) -> Option<Vec<DataPartInfo>> {
         let parts = self.tbl_parts.get(db_name);
-        parts.and_then(|m| m.get(table_name)).map(Clone::clone)
+        parts.and_then(|m| m.get(table_name)).map(|parts| {
+            parts
+                .iter()
+                .map(|part| {
+                    let mut part = part.clone();
+                    part.part.locations = self.part_locations(&part.part.name);
+                    part
+                })
+                .collect()
+        })
+    }
+
+    // The labels of the nodes of the slot of the part, the nodes without labels are skipped.
+    fn part_locations(&self, key: &str) -> Vec<BTreeMap<String, String>> {
+        if self.slots.is_empty() {
+            return vec![];
+        }
+        let slot = self.get_slot(self.slot_index_for_key(key));
+        slot.node_ids
+            .iter()
+            .filter_map(|id| self.node_heartbeats.get(id))
+            .filter(|hb| !hb.labels.is_empty())
+            .map(|hb| hb.labels.clone())
+            .collect()
+    }
+
+    /// Returns the hashes of the latest appended blocks of a table.
+    pub fn get_block_hashes(&self, db_name: &str, table_name: &str) -> HashSet<String> {
+        self.tbl_block_hashes
+            .get(db_name)
+            .and_then(|m| m.get(table_name))
+            .map(|hashes| hashes.iter().cloned().collect())
+            .unwrap_or_default()
     }
 
+    /// Makes the parts of an append visible, and records the hashes of their blocks.
+    ///
+    /// With `deduplicate`, the parts whose block is among the latest appended blocks of the table
+    /// are skipped, the blocks are checked and recorded at once so that two concurrent retries
+    /// can not both pass the check. The identical blocks in one append are all kept, the files of
+    /// the skipped parts are left unreferenced.
+    ///
+    /// Returns the append result of the visible parts.
     pub fn append_data_parts(
         &mut self,
         db_name: &str,
         table_name: &str,
         append_res: &AppendResult,
-    ) {
+        deduplicate: bool,
+    ) -> AppendResult {
+        let appended_hashes = if deduplicate {
+            self.get_block_hashes(db_name, table_name)
+        } else {
+            HashSet::new()
+        };
+
+        let mut res = AppendResult {
+            session_id: append_res.session_id.clone(),
+            tx_id: append_res.tx_id.clone(),
+            ..AppendResult::default()
+        };
+        for p in append_res.parts.iter() {
+            if appended_hashes.contains(&p.block_hash) {
+                res.deduplicated_blocks += 1;
+                continue;
+            }
+            res.append_part(
+                &p.location,
+                p.rows,
+                p.cols,
+                p.wire_bytes,
+                p.disk_bytes,
+                &p.block_hash,
+            )
+            .col_stats = p.col_stats.clone();
+        }
+
+        let hashes = self
+            .tbl_block_hashes
+            .entry(db_name.to_string())
+            .or_default()
+            .entry(table_name.to_string())
+            .or_default();
+        for part in res.parts.iter() {
+            if !part.block_hash.is_empty() {
+                hashes.push_back(part.block_hash.clone());
+            }
+        }
+        while hashes.len() > BLOCK_HASH_WINDOW {
+            hashes.pop_front();
+        }
+
+        let version = self.incr_seq(&Self::data_version_key(db_name, table_name));
         let part_info = || {
-            append_res
-                .parts
+            res.parts
                 .iter()
                 .map(|p| {
                     let loc = &p.location;
                     DataPartInfo {
                         part: Part {
                             name: loc.clone(),
-                            version: 0,
+                            version,
+                            locations: vec![],
                         },
-                        stats: Statistics::new_exact(p.disk_bytes, p.rows),
+                        stats: Statistics::new_exact(p.rows, p.disk_bytes),
+                        col_stats: p.col_stats.clone(),
                     }
                 })
                 .collect::<Vec<_>>()
@@ -429,16 +887,76 @@ impl StateMachine {
                     .cloned()
                     .collect()
             });
+        res
+    }
+
+    /// Stages the parts of an append in transaction `tx_id`, they are not visible to readers until
+    /// `commit_data_parts` is called.
+    pub fn stage_data_parts(
+        &mut self,
+        tx_id: &str,
+        db_name: &str,
+        table_name: &str,
+        append_res: &AppendResult,
+        deduplicate: bool,
+    ) {
+        self.pending_parts
+            .entry(tx_id.to_string())
+            .or_default()
+            .push(StagedAppend {
+                db_name: db_name.to_string(),
+                table_name: table_name.to_string(),
+                append_res: append_res.clone(),
+                deduplicate,
+            });
+    }
+
+    /// Makes all the parts staged in transaction `tx_id` visible at once, the appends with
+    /// deduplication are deduplicated against the blocks appended before the commit.
+    /// Returns the number of parts committed and the number of blocks skipped.
+    pub fn commit_data_parts(&mut self, tx_id: &str) -> AppendTxResult {
+        let appends = self.pending_parts.remove(tx_id).unwrap_or_default();
+        let mut tx_res = AppendTxResult::default();
+        for append in appends.iter() {
+            let res = self.append_data_parts(
+                &append.db_name,
+                &append.table_name,
+                &append.append_res,
+                append.deduplicate,
+            );
+            tx_res.parts += res.parts.len();
+            tx_res.deduplicated_blocks += res.deduplicated_blocks;
+        }
+        tx_res
+    }
+
+    /// Discards the parts staged in transaction `tx_id`.
+    /// Returns the number of parts discarded.
+    pub fn abort_data_parts(&mut self, tx_id: &str) -> usize {
+        self.pending_parts
+            .remove(tx_id)
+            .map(|appends| {
+                appends
+                    .iter()
+                    .map(|append| append.append_res.parts.len())
+                    .sum()
+            })
+            .unwrap_or_default()
     }
 
     pub fn remove_table_data_parts(&mut self, db_name: &str, table_name: &str) {
+        self.incr_seq(&Self::data_version_key(db_name, table_name));
         self.tbl_parts
             .remove(db_name)
             .and_then(|mut t| t.remove(table_name));
+        self.tbl_block_hashes
+            .get_mut(db_name)
+            .and_then(|t| t.remove(table_name));
     }
 
     pub fn remove_db_data_parts(&mut self, db_name: &str) {
         self.tbl_parts.remove(db_name);
+        self.tbl_block_hashes.remove(db_name);
     }
 
     pub fn mget_kv(&self, keys: &[impl AsRef<str>]) -> Vec<Option<SeqValue>> {
@@ -459,7 +977,7 @@ impl StateMachine {
 /// A slot is a virtual and intermediate allocation unit in a distributed storage.
 /// The key of an object is mapped to a slot by some hashing algo.
 /// A slot is assigned to several physical servers(normally 3 for durability).
-#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
 pub struct Slot {
     pub node_ids: Vec<NodeId>,
 }
@@ -470,6 +988,23 @@ pub struct Node {
     pub address: String,
 }
 
+/// The latest heartbeat of a node.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct NodeHeartbeat {
+    /// The address of the flight API of the node, from which the other nodes pull the files.
+    pub flight_address: String,
+    /// The unix time in milli seconds when the leader received the heartbeat, by the clock of the
+    /// leader. The time of the node sending it is replaced by the leader.
+    pub time_ms: u64,
+    /// The labels of the node, e.g. `zone` and `rack`, which the placement policy places the
+    /// nodes of the slots by.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// The index of the last log applied by the node when the heartbeat is sent.
+    #[serde(default)]
+    pub last_applied: u64,
+}
+
 impl Display for Node {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{}={}", self.name, self.address)
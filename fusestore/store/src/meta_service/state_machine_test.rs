@@ -3,9 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0.
 
 use async_raft::LogId;
+use common_flights::meta_api_impl::PlacementPolicy;
+use common_flights::storage_api_impl::AppendResult;
+use common_flights::storage_api_impl::AppendTxResult;
 use common_metatypes::Database;
 use common_metatypes::MatchSeq;
 use common_metatypes::SeqValue;
+use common_metatypes::Table;
 use common_runtime::tokio;
 use pretty_assertions::assert_eq;
 
@@ -14,6 +18,7 @@ use crate::meta_service::AppliedState;
 use crate::meta_service::Cmd;
 use crate::meta_service::LogEntry;
 use crate::meta_service::Node;
+use crate::meta_service::NodeHeartbeat;
 use crate::meta_service::Slot;
 use crate::meta_service::StateMachine;
 
@@ -442,3 +447,563 @@ async fn test_state_machine_apply_set_file() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_state_machine_export_import_meta() -> anyhow::Result<()> {
+    let mut m = StateMachine::builder().build()?;
+
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::CreateDatabase {
+            name: "foo".to_string(),
+            if_not_exists: true,
+            db: Default::default(),
+        },
+    })?;
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::CreateTable {
+            db_name: "foo".to_string(),
+            table_name: "t1".to_string(),
+            if_not_exists: true,
+            table: Table::default(),
+        },
+    })?;
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::UpsertKV {
+            key: "k1".to_string(),
+            seq: MatchSeq::Any,
+            value: b"v1".to_vec(),
+        },
+    })?;
+
+    let dump = m.export_meta();
+    assert_eq!(1, dump.databases.len());
+    assert_eq!(1, dump.tables.len());
+    assert_eq!(1, dump.kv.len());
+
+    // import into an empty state machine
+
+    let mut m2 = StateMachine::builder().build()?;
+    let resp = m2.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::ImportMeta { dump: dump.clone() },
+    })?;
+    assert_eq!(
+        AppliedState::ImportMeta {
+            result: Some((1, 1))
+        },
+        resp
+    );
+    let got = m2.export_meta();
+    assert_eq!(dump.databases, got.databases);
+    assert_eq!(dump.tables, got.tables);
+    assert_eq!(dump.kv, got.kv);
+    assert!(m2.get_meta_version() > m.get_meta_version());
+
+    // import into a non-empty state machine is refused
+
+    let resp = m2.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::ImportMeta { dump },
+    })?;
+    assert_eq!(AppliedState::ImportMeta { result: None }, resp);
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_stage_commit_abort_data_parts() -> anyhow::Result<()> {
+    // - Stage the parts of two transactions.
+    // - The staged parts are invisible until committed.
+    // - The parts of an aborted transaction are discarded.
+
+    let mut m = StateMachine::builder().build()?;
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p1", 1, 1, 1, 1, "h1");
+    res.append_part("db/t1/p2", 1, 1, 1, 1, "h2");
+    m.stage_data_parts("tx1", "db", "t1", &res, false);
+    m.stage_data_parts("tx2", "db", "t1", &res, false);
+    assert_eq!(None, m.get_data_parts("db", "t1"));
+    assert!(m.get_block_hashes("db", "t1").is_empty());
+
+    assert_eq!(2, m.commit_data_parts("tx1").parts);
+    let parts = m.get_data_parts("db", "t1").unwrap();
+    assert_eq!(2, parts.len());
+    assert_eq!(2, m.get_block_hashes("db", "t1").len());
+
+    assert_eq!(2, m.abort_data_parts("tx2"));
+    assert_eq!(2, m.get_data_parts("db", "t1").unwrap().len());
+
+    // a finished transaction has nothing to commit
+    assert_eq!(0, m.commit_data_parts("tx2").parts);
+    assert_eq!(0, m.commit_data_parts("tx1").parts);
+    assert_eq!(2, m.get_data_parts("db", "t1").unwrap().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_deduplicate_data_parts() -> anyhow::Result<()> {
+    // - The same blocks appended without deduplication are all kept.
+    // - With deduplication, the blocks appended before are skipped and counted.
+    // - Two transactions staging the same block: only the one committed first keeps it.
+
+    let mut m = StateMachine::builder().build()?;
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p1", 1, 1, 1, 1, "h1");
+    res.append_part("db/t1/p2", 1, 1, 1, 1, "h1");
+    let appended = m.append_data_parts("db", "t1", &res, true);
+    assert_eq!(
+        2,
+        appended.parts.len(),
+        "identical blocks in one append are kept"
+    );
+    assert_eq!(0, appended.deduplicated_blocks);
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p3", 1, 1, 1, 1, "h1");
+    res.append_part("db/t1/p4", 2, 1, 1, 1, "h2");
+    let appended = m.append_data_parts("db", "t1", &res, false);
+    assert_eq!(2, appended.parts.len());
+    assert_eq!(0, appended.deduplicated_blocks);
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p5", 1, 1, 1, 1, "h1");
+    res.append_part("db/t1/p6", 3, 1, 1, 1, "h3");
+    let appended = m.append_data_parts("db", "t1", &res, true);
+    assert_eq!(1, appended.parts.len());
+    assert_eq!("db/t1/p6", appended.parts[0].location);
+    assert_eq!(3, appended.summary.rows);
+    assert_eq!(1, appended.deduplicated_blocks);
+    assert_eq!(5, m.get_data_parts("db", "t1").unwrap().len());
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p7", 1, 1, 1, 1, "h4");
+    m.stage_data_parts("tx1", "db", "t1", &res, true);
+    m.stage_data_parts("tx2", "db", "t1", &res, true);
+    assert_eq!(
+        AppendTxResult {
+            parts: 1,
+            deduplicated_blocks: 0
+        },
+        m.commit_data_parts("tx1")
+    );
+    assert_eq!(
+        AppendTxResult {
+            parts: 0,
+            deduplicated_blocks: 1
+        },
+        m.commit_data_parts("tx2")
+    );
+    assert_eq!(6, m.get_data_parts("db", "t1").unwrap().len());
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_data_part_locations() -> anyhow::Result<()> {
+    // - The only slot is held by nodes 1,2,3, node 3 has no labels.
+    // - Assert that the parts are located by the labels of nodes 1,2.
+
+    let mut m = StateMachine {
+        slots: vec![Slot {
+            node_ids: vec![1, 2, 3],
+        }],
+        ..Default::default()
+    };
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p1", 1, 1, 1, 1, "h1");
+    m.append_data_parts("db", "t1", &res, false);
+    let parts = m.get_data_parts("db", "t1").unwrap();
+    assert!(parts[0].part.locations.is_empty());
+
+    for (node_id, zone) in [(1, Some("a")), (2, Some("b")), (3, None)].iter() {
+        let labels = match zone {
+            Some(zone) => maplit::btreemap! {"zone".to_string() => zone.to_string()},
+            None => Default::default(),
+        };
+        m.node_heartbeats.insert(*node_id, NodeHeartbeat {
+            labels,
+            ..Default::default()
+        });
+    }
+
+    let parts = m.get_data_parts("db", "t1").unwrap();
+    assert_eq!(
+        vec![
+            maplit::btreemap! {"zone".to_string() => "a".to_string()},
+            maplit::btreemap! {"zone".to_string() => "b".to_string()},
+        ],
+        parts[0].part.locations
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_data_version() -> anyhow::Result<()> {
+    // - Every commit of parts bumps the data version of the table.
+    // - The parts are stamped with the version that makes them visible.
+    // - The exact rows of the parts are their statistics.
+
+    let mut m = StateMachine::builder().build()?;
+    assert_eq!(0, m.get_data_version("db", "t1"));
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p1", 3, 2, 100, 50, "h1");
+    m.append_data_parts("db", "t1", &res, false);
+    assert_eq!(1, m.get_data_version("db", "t1"));
+
+    let mut res = AppendResult::default();
+    res.append_part("db/t1/p2", 5, 2, 200, 80, "h2");
+    m.stage_data_parts("tx1", "db", "t1", &res, false);
+    assert_eq!(1, m.get_data_version("db", "t1"));
+    m.commit_data_parts("tx1");
+    assert_eq!(2, m.get_data_version("db", "t1"));
+    assert_eq!(0, m.get_data_version("db", "t2"));
+
+    let parts = m.get_data_parts("db", "t1").unwrap();
+    let summary = parts
+        .iter()
+        .map(|p| {
+            (
+                p.part.version,
+                p.stats.read_rows,
+                p.stats.read_bytes,
+                p.stats.is_exact,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(vec![(1, 3, 50, true), (2, 5, 80, true)], summary);
+
+    // truncate
+    m.remove_table_data_parts("db", "t1");
+    assert_eq!(3, m.get_data_version("db", "t1"));
+    assert_eq!(None, m.get_data_parts("db", "t1"));
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_meta_version() -> anyhow::Result<()> {
+    // The catalog version is bumped only when the catalog is actually changed.
+
+    let mut m = StateMachine::builder().build()?;
+    assert_eq!(0, m.get_meta_version());
+
+    let create_db = LogEntry {
+        txid: None,
+        cmd: Cmd::CreateDatabase {
+            name: "foo".to_string(),
+            if_not_exists: true,
+            db: Default::default(),
+        },
+    };
+
+    m.apply_non_dup(&create_db)?;
+    assert_eq!(1, m.get_meta_version());
+
+    // database already exists
+    m.apply_non_dup(&create_db)?;
+    assert_eq!(1, m.get_meta_version());
+
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::CreateTable {
+            db_name: "foo".to_string(),
+            table_name: "t1".to_string(),
+            if_not_exists: true,
+            table: Table::default(),
+        },
+    })?;
+    assert_eq!(2, m.get_meta_version());
+
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::DropTable {
+            db_name: "foo".to_string(),
+            table_name: "t1".to_string(),
+            if_exists: true,
+        },
+    })?;
+    assert_eq!(3, m.get_meta_version());
+
+    // kv does not belong to catalog
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::UpsertKV {
+            key: "k1".to_string(),
+            seq: MatchSeq::Any,
+            value: b"v1".to_vec(),
+        },
+    })?;
+    assert_eq!(3, m.get_meta_version());
+
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::DropDatabase {
+            name: "foo".to_string(),
+        },
+    })?;
+    assert_eq!(4, m.get_meta_version());
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_node_health() -> anyhow::Result<()> {
+    // - Nodes 1,2,3 send heartbeats, node 4 never does, node 2 dies.
+    // - Assert that node 2 is replaced in the slots by the healthy node in the fewest slots.
+    // - Assert that the files are pulled from the healthy nodes.
+
+    let mut m = StateMachine {
+        slots: vec![
+            Slot {
+                node_ids: vec![1, 2],
+            },
+            Slot {
+                node_ids: vec![2, 4],
+            },
+            Slot {
+                node_ids: vec![1, 3],
+            },
+            Slot::default(),
+        ],
+        nodes: maplit::hashmap! {
+            1=> Node{..Default::default()},
+            2=> Node{..Default::default()},
+            3=> Node{..Default::default()},
+            4=> Node{..Default::default()},
+        },
+        replication: Replication::Mirror(2),
+        ..Default::default()
+    };
+
+    for (node_id, time_ms) in [(1, 1000), (2, 100), (3, 1000)].iter() {
+        let heartbeat = NodeHeartbeat {
+            flight_address: format!("node{}:9191", node_id),
+            time_ms: *time_ms,
+            labels: Default::default(),
+            last_applied: 0,
+        };
+        let resp = m.apply_non_dup(&LogEntry {
+            txid: None,
+            cmd: Cmd::NodeHeartbeat {
+                node_id: *node_id,
+                heartbeat: heartbeat.clone(),
+            },
+        })?;
+        assert_eq!(
+            AppliedState::NodeHeartbeat {
+                prev: None,
+                result: Some(heartbeat)
+            },
+            resp
+        );
+    }
+
+    assert_eq!(1000, m.latest_heartbeat_ms());
+
+    // All are healthy before the timeout.
+    assert!(m.dead_nodes(1000, 1000).is_empty());
+    assert_eq!(vec![1, 2, 3], m.healthy_nodes(1000, 1000));
+    assert_eq!(vec![(3, vec![3, 1])], m.plan_slot_repairs(1000, 1000));
+
+    let dead = m.dead_nodes(1500, 1000);
+    assert_eq!(maplit::hashset! {2}, dead);
+    assert_eq!(vec![1, 3], m.healthy_nodes(1500, 1000));
+
+    // Node 4 sends no heartbeat, it is kept in its slot but is not assigned new slots.
+    let repairs = m.plan_slot_repairs(1500, 1000);
+    assert_eq!(
+        vec![(0, vec![1, 3]), (1, vec![4, 1]), (3, vec![3, 1])],
+        repairs
+    );
+
+    for (slot_index, node_ids) in repairs.iter() {
+        let prev = m.slots[*slot_index as usize].clone();
+        let resp = m.apply_non_dup(&LogEntry {
+            txid: None,
+            cmd: Cmd::SetSlotNodes {
+                slot_index: *slot_index,
+                node_ids: node_ids.clone(),
+            },
+        })?;
+        assert_eq!(
+            AppliedState::Slot {
+                prev: Some(prev),
+                result: Some(Slot {
+                    node_ids: node_ids.clone()
+                })
+            },
+            resp
+        );
+    }
+    assert!(m.plan_slot_repairs(1500, 1000).is_empty());
+
+    let resp = m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::SetSlotNodes {
+            slot_index: 10,
+            node_ids: vec![1],
+        },
+    })?;
+    assert_eq!(
+        AppliedState::Slot {
+            prev: None,
+            result: None
+        },
+        resp
+    );
+
+    // The files of the slots of node 3 are pulled from node 1, the other healthy node.
+    // "c" is in slot 0, "d" in slot 1 and "foo" in slot 3.
+    for key in ["c", "d", "foo"].iter() {
+        m.keys.insert(key.to_string(), "".to_string());
+    }
+    let files = m.files_of_node(3, 1500, 1000);
+    assert_eq!(
+        vec![
+            ("c".to_string(), vec!["node1:9191".to_string()]),
+            ("foo".to_string(), vec!["node1:9191".to_string()]),
+        ],
+        files
+    );
+    assert!(m.files_of_node(2, 1500, 1000).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_placement_policy() -> anyhow::Result<()> {
+    // - Nodes 1,2 are in zone a, 3 in b, 4 in c, 5 has no zone.
+    // - Assert that the slots are not changed without a failure domain.
+    // - Set the failure domain to zone.
+    // - Assert that a node sharing a zone in a slot is replaced with a node of another zone.
+    // - Nodes 3,4 die, assert that the nodes of the other zones are assigned first.
+
+    let mut m = StateMachine {
+        slots: vec![
+            Slot {
+                node_ids: vec![1, 2],
+            },
+            Slot {
+                node_ids: vec![3, 4],
+            },
+            Slot {
+                node_ids: vec![2, 5],
+            },
+        ],
+        nodes: maplit::hashmap! {
+            1=> Node{..Default::default()},
+            2=> Node{..Default::default()},
+            3=> Node{..Default::default()},
+            4=> Node{..Default::default()},
+            5=> Node{..Default::default()},
+        },
+        replication: Replication::Mirror(2),
+        ..Default::default()
+    };
+
+    for (node_id, time_ms, zone) in [
+        (1, 1000, Some("a")),
+        (2, 1000, Some("a")),
+        (3, 100, Some("b")),
+        (4, 100, Some("c")),
+        (5, 1000, None),
+    ]
+    .iter()
+    {
+        let mut labels = maplit::btreemap! {"rack".to_string() => format!("r{}", node_id)};
+        if let Some(zone) = zone {
+            labels.insert("zone".to_string(), zone.to_string());
+        }
+        m.apply_non_dup(&LogEntry {
+            txid: None,
+            cmd: Cmd::NodeHeartbeat {
+                node_id: *node_id,
+                heartbeat: NodeHeartbeat {
+                    flight_address: format!("node{}:9191", node_id),
+                    time_ms: *time_ms,
+                    labels,
+                    last_applied: 0,
+                },
+            },
+        })?;
+    }
+
+    assert_eq!(None, m.failure_domain_of(1));
+    assert!(m.plan_slot_repairs(1000, 1000).is_empty());
+
+    let policy = PlacementPolicy {
+        failure_domain: "zone".to_string(),
+    };
+    let resp = m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::SetPlacementPolicy {
+            policy: policy.clone(),
+        },
+    })?;
+    assert_eq!(
+        AppliedState::PlacementPolicy {
+            prev: PlacementPolicy::default(),
+            result: policy
+        },
+        resp
+    );
+    assert_eq!(Some("a"), m.failure_domain_of(1));
+    assert_eq!(None, m.failure_domain_of(5));
+
+    // Node 2 shares zone a with node 1 in slot 0, it is replaced with node 3 of zone b.
+    let repairs = m.plan_slot_repairs(1000, 1000);
+    assert_eq!(vec![(0, vec![1, 3])], repairs);
+    m.apply_non_dup(&LogEntry {
+        txid: None,
+        cmd: Cmd::SetSlotNodes {
+            slot_index: 0,
+            node_ids: vec![1, 3],
+        },
+    })?;
+    assert!(m.plan_slot_repairs(1000, 1000).is_empty());
+
+    // Nodes 3,4 die, only zone a and node 5 are left: node 5 is assigned before the other node
+    // of zone a, which is assigned only if there is no other node.
+    assert_eq!(
+        vec![(0, vec![1, 5]), (1, vec![1, 5])],
+        m.plan_slot_repairs(1500, 1000)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_state_machine_peer_lags() -> anyhow::Result<()> {
+    // - Nodes 1,2,3 send heartbeats with their last applied logs, node 4 is not a node any more.
+    // - Assert the lags of the other nodes are behind the last log of node 1.
+
+    let mut m = StateMachine {
+        nodes: maplit::hashmap! {
+            1=> Node{..Default::default()},
+            2=> Node{..Default::default()},
+            3=> Node{..Default::default()},
+        },
+        ..Default::default()
+    };
+
+    for (node_id, last_applied) in [(1, 10), (2, 7), (3, 12), (4, 1)].iter() {
+        m.node_heartbeats.insert(*node_id, NodeHeartbeat {
+            last_applied: *last_applied,
+            ..Default::default()
+        });
+    }
+
+    assert_eq!(maplit::btreemap! {2 => 3, 3 => 0}, m.peer_lags(1, 10));
+    assert!(StateMachine::default().peer_lags(1, 10).is_empty());
+
+    Ok(())
+}
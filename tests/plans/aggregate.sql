@@ -0,0 +1,5 @@
+SELECT number FROM numbers_local(100);
+SELECT SUM(number) FROM numbers_local(100);
+SELECT SUM(number) FROM numbers_local(100) GROUP BY number % 3;
+SELECT SUM(number) FROM numbers_local(100) GROUP BY number % 3, number % 2;
+select sum(number+1)+2, number%3 as id from numbers(10) where number>1 group by id having id>1 order by id desc limit 3;
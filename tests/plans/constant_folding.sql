@@ -0,0 +1,7 @@
+-- The constant expressions are folded before the projection, the names of the columns are kept.
+SELECT 1 + 2 + 3;
+SELECT dummy + 1 + 2 + 3;
+SELECT 1 + 2 + 3 + dummy;
+SELECT 1 = 1 AND 2 > 1;
+SELECT CAST(1 AS bigint);
+SELECT SUBSTRING('1234567890' FROM 3 FOR 3);
@@ -0,0 +1,4 @@
+-- The filters are pushed below the projections and merged, but not below a limit.
+SELECT * FROM (SELECT number + 1 AS a FROM numbers_mt(10)) WHERE a > 5;
+SELECT * FROM (SELECT number AS a FROM numbers_mt(10) WHERE number > 1) WHERE a < 5;
+SELECT * FROM (SELECT number AS a FROM numbers_mt(10) LIMIT 3) WHERE a > 1;
@@ -0,0 +1,3 @@
+-- The scans only read the columns of the queries.
+select max(value) as c1, name as c2 from system.settings group by c2;
+select a.value, b.value from system.settings as a join system.settings as b on a.name = b.name;
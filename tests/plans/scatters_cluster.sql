@@ -0,0 +1,10 @@
+-- The plans of the cluster tables are scattered to the nodes of the cluster.
+SELECT number FROM numbers(100000000);
+SELECT SUM(number) FROM numbers(100000000) GROUP BY number % 3;
+SELECT SUM(number) FROM numbers(100000000);
+-- The distinct values are grouped across the cluster before they are counted.
+SELECT COUNT(DISTINCT number) FROM numbers(100000000) GROUP BY number % 3;
+-- The HAVING filter runs after the groups are converged.
+SELECT SUM(number) AS s FROM numbers(100000000) GROUP BY number % 3 HAVING s > 10;
+-- Every node sorts its top N rows before they are converged.
+SELECT number FROM numbers(100000000) ORDER BY number DESC LIMIT 3;
@@ -0,0 +1,2 @@
+SELECT * FROM remote('127.0.0.1:9090', 'system', 'one');
+SELECT database, name FROM remote('127.0.0.1:9090', 'system', 'tables') WHERE name = 'tables';
@@ -0,0 +1,6 @@
+SELECT formatDateTime(number * 86400, '%Y-%m-%d') FROM numbers(3);
+SELECT formatDateTime(1000000000, '%Y-%m-%d %H:%M:%S');
+SELECT formatDateTime(1000000000, '%a %d %b %Y');
+SELECT formatReadableSize(number * 1024) FROM numbers(3);
+SELECT formatReadableSize(1536);
+SELECT formatReadableSize(1073741824);
@@ -0,0 +1,5 @@
+SELECT groupBitmap(number % 10) FROM numbers(100);
+SELECT bitmapCardinality(bitmapBuild(number)) FROM numbers(100);
+SELECT bitmapCardinality(bitmapAnd(a, b)), bitmapCardinality(bitmapOr(a, b)), bitmapCardinality(bitmapXor(a, b)), bitmapCardinality(bitmapAndnot(a, b)) FROM (SELECT bitmapBuild(number) AS a, bitmapBuild(number + 50) AS b FROM numbers(100));
+SELECT groupBitmapOr(b), groupBitmapAnd(b) FROM (SELECT bitmapBuild(number) AS b FROM numbers(100) GROUP BY number % 3);
+SELECT groupBitmapAnd(b) FROM (SELECT bitmapBuild(number % 5) AS b FROM numbers(100) GROUP BY number % 3);
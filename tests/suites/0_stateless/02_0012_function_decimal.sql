@@ -0,0 +1,13 @@
+SELECT CAST('12.345' AS DECIMAL(10, 2));
+SELECT CAST(-1.25 AS DECIMAL(10, 1));
+SELECT CAST(3 AS DECIMAL(5, 2));
+SELECT CAST('abc' AS DECIMAL(10, 2));
+SELECT toTypeName(CAST(1 AS DECIMAL(10, 2)));
+SELECT toTypeName(CAST(1 AS DECIMAL));
+
+SELECT CAST('1.25' AS DECIMAL(10, 2)) + CAST('2.5' AS DECIMAL(10, 1)), CAST('1.25' AS DECIMAL(10, 2)) * CAST('2.5' AS DECIMAL(10, 1)), CAST('10' AS DECIMAL(10, 2)) / 3;
+SELECT toTypeName(CAST('1.25' AS DECIMAL(10, 2)) * CAST('2.5' AS DECIMAL(10, 1)));
+SELECT CAST('1.5' AS DECIMAL(10, 1)) > 1, CAST('1.5' AS DECIMAL(10, 1)) = 1.5;
+
+SELECT SUM(CAST(number AS DECIMAL(10, 2))), AVG(CAST(number AS DECIMAL(10, 2))) FROM numbers(4);
+SELECT MIN(CAST(number AS DECIMAL(10, 2))), MAX(CAST(number AS DECIMAL(10, 2))) FROM numbers(4);
@@ -0,0 +1,4 @@
+SELECT CAST(greatCircleDistance(0, 0, 0, 1) AS UInt64), CAST(greatCircleDistance(0, 0, 180, 0) AS UInt64);
+SELECT geohashEncode(-5.60302734375, 42.593994140625), geohashEncode(-5.60302734375, 42.593994140625, 5);
+SELECT pointInPolygon(2, 2, 0, 0, 4, 0, 4, 4, 0, 4), pointInPolygon(5, 2, 0, 0, 4, 0, 4, 4, 0, 4);
+SELECT count() FROM numbers(10) WHERE pointInPolygon(number, number, 0, 0, 4, 0, 4, 4, 0, 4);
@@ -0,0 +1,5 @@
+SELECT retention(number = 1, number < 3, number > 7) FROM numbers(10);
+SELECT retention(number = 20, number < 3) FROM numbers(10);
+SELECT number % 2 AS k, retention(number = 0, number = 2, number = 5) FROM numbers(10) GROUP BY k ORDER BY k;
+SELECT uniqUpTo(5, number % 3), uniqUpTo(2, number % 3), uniqUpTo(0, number) FROM numbers(10);
+SELECT number % 2 AS k, uniqUpTo(3, number) FROM numbers(10) GROUP BY k ORDER BY k;
@@ -0,0 +1,7 @@
+SELECT toDate('2021-01-31'), toDateTime('2021-01-01 12:30:15'), toDate(18628), toDateTime(1609504215), toDate('x');
+SELECT toDate('2021-01-31') + INTERVAL '1' MONTH, toDate('2021-01-31') - INTERVAL '1 day', INTERVAL '1 year' + toDate('2020-02-29');
+SELECT toDateTime('2021-01-01 12:30:15') + INTERVAL '90 minutes', toDateTime('2021-01-01 12:30:15') - INTERVAL '1' HOUR;
+SELECT toDate('2021-03-01') - toDate('2021-01-01');
+SELECT toDate('2021-01-02') > '2021-01-01', toDateTime('2021-01-01 00:00:01') > toDate('2021-01-01'), toDate('2021-01-01') = '2021-01-01';
+SELECT count() FROM numbers(10) WHERE toDate(number) + INTERVAL '1' DAY < toDate('1970-01-05');
+SELECT today() = toDate(now());
@@ -0,0 +1,16 @@
+SELECT NULL IS NULL, 1 IS NULL, NULL IS NOT NULL, 1 IS NOT NULL;
+SELECT toDate('x') IS NULL, toDate('2021-01-01') IS NOT NULL, isNull(toDate('x'));
+SELECT count() FROM numbers(10) WHERE (number > 5) IS NOT NULL;
+SELECT min(number) IS NULL, max(number) IS NOT NULL FROM numbers(10) WHERE 1 = 2;
+
+DROP TABLE IF EXISTS a;
+DROP TABLE IF EXISTS b;
+CREATE TABLE a(id bigint, name varchar) Engine = Memory;
+CREATE TABLE b(id int, value bigint) Engine = Memory;
+INSERT INTO a(id,name) VALUES(1, 'a1'),(2,'a2'),(3,'a3');
+INSERT INTO b(id,value) VALUES(2, 20),(3,30),(3,31),(4,40);
+
+SELECT a.id, value IS NULL, value IS NOT NULL FROM a LEFT JOIN b ON a.id = b.id ORDER BY a.id, value;
+
+DROP TABLE a;
+DROP TABLE b;
@@ -0,0 +1,5 @@
+SELECT any(number), anyLast(number) FROM numbers(1);
+SELECT anyHeavy(number % 3 = 0) FROM numbers(10);
+SELECT anyHeavy(number > 2), anyHeavy(number < 2) FROM numbers(10);
+SELECT number % 2 AS k, any(number % 2 + 10), anyHeavy(number % 2 * 2) FROM numbers(10) GROUP BY number % 2 ORDER BY k;
+SELECT any(number), anyLast(number), anyHeavy(number) FROM numbers(10) WHERE 1 = 2;
@@ -0,0 +1,3 @@
+SELECT groupArraySorted(number, 10 - number, 3) FROM numbers(10);
+SELECT groupArraySorted(number, number % 3 * 10 + number) FROM numbers(6);
+SELECT number % 2 AS k, groupArraySortedDesc(number, number, 3) FROM numbers(10) GROUP BY number % 2 ORDER BY k;
@@ -0,0 +1,3 @@
+SELECT groupArray(number) FROM numbers(5);
+SELECT groupArray(number, 3) FROM numbers(10);
+SELECT number % 2 AS k, groupArray(number) FROM numbers(10) GROUP BY number % 2 ORDER BY k;
@@ -0,0 +1,3 @@
+SELECT uniqHLL(number % 3), approx_count_distinct(number % 3, number % 5) FROM numbers(100);
+SELECT uniqHLL(number) > 98000, uniqHLL(number) < 102000 FROM numbers(100000);
+SELECT number % 2 AS k, uniqHLL(number) FROM numbers(10) GROUP BY number % 2 ORDER BY k;
@@ -0,0 +1,5 @@
+SELECT median(number), quantile(0.9, number) FROM numbers(5);
+SELECT quantiles(0, 0.5, 1, number) FROM numbers(4);
+SELECT number % 2 AS k, median(number) FROM numbers(10) GROUP BY number % 2 ORDER BY k;
+SELECT median(number) FROM numbers(10) WHERE number > 100;
+SELECT quantile(0.99, number) > 98000, quantile(0.99, number) < 100000 FROM numbers(100000);
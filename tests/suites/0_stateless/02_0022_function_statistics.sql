@@ -0,0 +1,6 @@
+SELECT varSamp(number), stddevSamp(number), variance(number), stddev(number) FROM numbers(3);
+SELECT varPop(number), stddevPop(number), var_pop(number), stddev_pop(number) FROM numbers(2);
+SELECT covarSamp(number, number * 2), covarPop(number, number * 2), corr(number, number * 2) FROM numbers(2);
+SELECT number % 2 AS k, varSamp(number) FROM numbers(10) GROUP BY number % 2 ORDER BY k;
+SELECT varSamp(number), covar_samp(number, number), corr(number, number) FROM numbers(1);
+SELECT varPop(number) > 833333333, varPop(number) < 833333334, corr(number, number * 2) > 0.999999 FROM numbers_mt(100000);
@@ -4,3 +4,5 @@ SELECT * FROM numbers_mt (3) having 1=2;
 SELECT MAX(number) AS max FROM numbers_mt(10) GROUP BY number%3 HAVING max>8;
 SELECT MAX(number) AS max FROM numbers_mt(10) GROUP BY number%3 HAVING max>7 ORDER BY max;
 SELECT MAX(number) AS max FROM numbers_mt(10) GROUP BY number%3 HAVING max<7;
+SELECT number % 3 AS k FROM numbers_mt(10) GROUP BY k HAVING count() > 3;
+SELECT number % 3 AS k, SUM(number) AS s FROM numbers_mt(10) GROUP BY k HAVING s > 12 ORDER BY k;
@@ -0,0 +1,5 @@
+SET max_result_rows = 3;
+SET result_overflow_mode = 'break';
+SELECT number FROM numbers(10);
+SET max_result_rows = 0;
+SELECT count() FROM numbers(10);
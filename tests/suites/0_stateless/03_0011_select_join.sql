@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS a;
+DROP TABLE IF EXISTS b;
+CREATE TABLE a(id bigint, name varchar) Engine = Memory;
+CREATE TABLE b(id int, value bigint) Engine = Memory;
+INSERT INTO a(id,name) VALUES(1, 'a1'),(2,'a2'),(3,'a3');
+INSERT INTO b(id,value) VALUES(2, 20),(3,30),(3,31),(4,40);
+
+SELECT * FROM a JOIN b ON a.id = b.id ORDER BY value;
+SELECT a.name, b.value FROM a INNER JOIN b ON b.id = a.id AND b.value > 20 ORDER BY value;
+SELECT name, value FROM a JOIN b USING (id) WHERE value < 31 ORDER BY value;
+SELECT count(*), sum(value) FROM a AS x JOIN b AS y ON x.id = y.id;
+
+DROP TABLE a;
+DROP TABLE b;
@@ -0,0 +1,13 @@
+DROP TABLE IF EXISTS a;
+DROP TABLE IF EXISTS b;
+CREATE TABLE a(id bigint, name varchar) Engine = Memory;
+CREATE TABLE b(id int, value bigint) Engine = Memory;
+INSERT INTO a(id,name) VALUES(1, 'a1'),(2,'a2'),(3,'a3');
+INSERT INTO b(id,value) VALUES(2, 20),(3,30),(3,31),(4,40);
+
+SELECT a.id, name, value FROM a LEFT JOIN b ON a.id = b.id ORDER BY a.id, value;
+SELECT name, b.id, value FROM a RIGHT OUTER JOIN b ON a.id = b.id ORDER BY value;
+SELECT count(*), sum(a.id), sum(value) FROM a FULL OUTER JOIN b USING (id);
+
+DROP TABLE a;
+DROP TABLE b;
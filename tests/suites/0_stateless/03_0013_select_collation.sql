@@ -0,0 +1,10 @@
+DROP TABLE IF EXISTS c;
+CREATE TABLE c(id bigint, name varchar COLLATE utf8_general_ci) Engine = Memory;
+INSERT INTO c(id,name) VALUES(1, 'b'),(2,'A'),(3,'a'),(4,'B'),(5,'c');
+
+SELECT id FROM c WHERE name = 'a' ORDER BY id;
+SELECT id FROM c WHERE name > 'B' ORDER BY id;
+SELECT id, name FROM c ORDER BY name, id;
+SELECT count(*) AS n, max(id) AS m FROM c GROUP BY name ORDER BY m;
+
+DROP TABLE c;
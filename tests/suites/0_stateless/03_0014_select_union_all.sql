@@ -0,0 +1,5 @@
+SELECT number FROM numbers(3) UNION ALL SELECT number + 10 AS number FROM numbers(2) ORDER BY number;
+SELECT count(*) FROM (SELECT number FROM numbers(5) UNION ALL SELECT number FROM numbers_mt(5));
+SELECT number FROM (SELECT number FROM numbers(3) UNION SELECT number FROM numbers(5)) ORDER BY number;
+SELECT number, name FROM (SELECT 0 AS number, 'a' AS name UNION ALL SELECT number + 1, 'b' FROM numbers(2)) ORDER BY number;
+SELECT number FROM numbers(3) UNION ALL SELECT number FROM numbers(3) ORDER BY number DESC LIMIT 2;
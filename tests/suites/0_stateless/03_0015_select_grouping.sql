@@ -0,0 +1,3 @@
+SELECT number % 3 AS k, grouping(k), grouping_id(), count() FROM numbers(10) GROUP BY k ORDER BY k;
+SELECT number % 2 AS k, sum(number) FROM numbers(10) GROUP BY k HAVING grouping(k) = 0 ORDER BY k;
+SELECT number % 2 AS a, number % 3 AS b, grouping(a, b) FROM numbers(6) GROUP BY a, b ORDER BY grouping(b, a), a, b;
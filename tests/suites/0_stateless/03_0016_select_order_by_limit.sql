@@ -0,0 +1,3 @@
+SELECT number FROM numbers_mt(100) ORDER BY number DESC LIMIT 3 OFFSET 2;
+SELECT * FROM (SELECT number FROM numbers_mt(100) ORDER BY number DESC) WHERE number % 2 = 0 LIMIT 3;
+SELECT number % 7 AS a, number FROM numbers_mt(1000) ORDER BY a DESC, number LIMIT 3;
@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS a;
+DROP TABLE IF EXISTS b;
+CREATE TABLE a(id bigint, name varchar) Engine = Memory;
+CREATE TABLE b(id int, value bigint) Engine = Memory;
+INSERT INTO a(id,name) VALUES(1, 'a1'),(2,'a2'),(3,'a3');
+INSERT INTO b(id,value) VALUES(2, 20),(3,30),(3,31),(4,40);
+
+SELECT name FROM a WHERE id IN (SELECT id FROM b) ORDER BY name;
+SELECT name FROM a WHERE id NOT IN (SELECT id FROM b) ORDER BY name;
+SELECT name FROM a WHERE id IN (SELECT id FROM b WHERE value > 30) AND name != 'a1' ORDER BY name;
+SELECT count(*) FROM numbers(10) WHERE number NOT IN (SELECT number * 2 AS n FROM numbers(5));
+
+DROP TABLE a;
+DROP TABLE b;
@@ -0,0 +1,15 @@
+DROP TABLE IF EXISTS a;
+DROP TABLE IF EXISTS b;
+CREATE TABLE a(id bigint, name varchar) Engine = Memory;
+CREATE TABLE b(id int, value bigint) Engine = Memory;
+INSERT INTO a(id,name) VALUES(1, 'a1'),(2,'a2'),(3,'a3');
+INSERT INTO b(id,value) VALUES(2, 20),(3,30),(3,31),(4,40);
+
+SELECT a.number, b.number FROM numbers(3) AS a CROSS JOIN numbers(2) AS b ORDER BY a.number, b.number;
+SELECT count(*) FROM numbers(10) AS a, numbers(10) AS b WHERE a.number < b.number;
+SELECT name, value FROM a JOIN b ON a.id < b.id AND value > 30 ORDER BY name, value;
+SELECT name, value FROM a LEFT JOIN b ON a.id = b.id AND value > 30 ORDER BY name, value;
+SELECT name, b.id FROM a RIGHT JOIN b ON a.id > b.id ORDER BY b.id, name;
+
+DROP TABLE a;
+DROP TABLE b;
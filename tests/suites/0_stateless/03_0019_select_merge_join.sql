@@ -0,0 +1,6 @@
+SELECT a, b FROM (SELECT number AS a FROM numbers(5) ORDER BY a) AS x JOIN (SELECT number + 2 AS b FROM numbers(5) ORDER BY b) AS y ON x.a = y.b;
+SELECT a, b FROM (SELECT number AS a FROM numbers(5) ORDER BY a) AS x LEFT JOIN (SELECT number + 2 AS b FROM numbers(5) ORDER BY b) AS y ON x.a = y.b ORDER BY a;
+SELECT a, b FROM (SELECT number AS a FROM numbers(5) ORDER BY a) AS x RIGHT JOIN (SELECT number + 2 AS b FROM numbers(5) ORDER BY b) AS y ON x.a = y.b ORDER BY b;
+SELECT count(), sum(a), sum(b) FROM (SELECT number AS a FROM numbers(5) ORDER BY a) AS x FULL JOIN (SELECT number + 2 AS b FROM numbers(5) ORDER BY b) AS y ON x.a = y.b;
+SELECT count() FROM (SELECT number % 3 AS a FROM numbers(9) ORDER BY a) AS x JOIN (SELECT number % 3 AS b FROM numbers(6) ORDER BY b) AS y ON x.a = y.b;
+SELECT count(), sum(a) FROM (SELECT number AS a FROM numbers_mt(100000) ORDER BY a) AS x JOIN (SELECT number AS b FROM numbers_mt(100000) ORDER BY b) AS y ON x.a = y.b;
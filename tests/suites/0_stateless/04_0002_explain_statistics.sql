@@ -0,0 +1,2 @@
+set max_threads = 16;
+explain statistics select number from numbers_mt(80000) where (number+1)=4 limit 1;
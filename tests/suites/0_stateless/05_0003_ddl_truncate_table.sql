@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS t1;
+CREATE TABLE t1(a bigint, b varchar) Engine = Memory;
+INSERT INTO t1(a,b) VALUES(1, 'v1'),(2,'v2');
+SELECT count(*) FROM t1;
+
+TRUNCATE TABLE t1;
+SELECT count(*) FROM t1;
+
+INSERT INTO t1(a,b) VALUES(3, 'v3');
+SELECT * FROM t1;
+
+DROP TABLE t1;
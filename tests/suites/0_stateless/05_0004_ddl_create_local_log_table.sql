@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS default.test_log;
+
+CREATE TABLE default.test_log(a bigint, b varchar) Engine = Log location = '/tmp/fuse_test_log_table';
+TRUNCATE TABLE default.test_log;
+
+INSERT INTO default.test_log(a,b) VALUES(1, 'v1'),(2,'v2');
+INSERT INTO default.test_log(a,b) VALUES(3, 'v3');
+SELECT count(*) FROM default.test_log;
+SELECT * FROM default.test_log ORDER BY a;
+
+TRUNCATE TABLE default.test_log;
+SELECT count(*) FROM default.test_log;
+
+DROP TABLE IF EXISTS default.test_log;
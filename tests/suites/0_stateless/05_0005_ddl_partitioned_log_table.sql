@@ -0,0 +1,13 @@
+DROP TABLE IF EXISTS default.test_partitioned_log;
+
+CREATE TABLE default.test_partitioned_log(a bigint, b bigint) Engine = Log PARTITION BY a % 3 location = '/tmp/fuse_test_partitioned_log_table';
+TRUNCATE TABLE default.test_partitioned_log;
+
+INSERT INTO default.test_partitioned_log(a,b) VALUES(1, 1),(2, 2),(3, 3),(4, 4);
+SELECT count(*) FROM default.test_partitioned_log;
+SELECT * FROM default.test_partitioned_log WHERE a % 3 = 1 ORDER BY a;
+
+ALTER TABLE default.test_partitioned_log DROP PARTITION 1;
+SELECT * FROM default.test_partitioned_log ORDER BY a;
+
+DROP TABLE IF EXISTS default.test_partitioned_log;
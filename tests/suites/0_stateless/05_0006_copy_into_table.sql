@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS default.test_copy;
+
+INSERT INTO FUNCTION file('/tmp/05_0006_copy_into_table.csv') SELECT number, number * 2 FROM numbers(5);
+
+CREATE TABLE default.test_copy (a bigint, b bigint, c int default 1) Engine = Memory;
+COPY INTO default.test_copy (a, b) FROM '/tmp/05_0006_copy_into_table.csv' FORMAT CSV;
+SELECT * FROM default.test_copy ORDER BY a;
+COPY INTO default.test_copy (a, b) FROM '/tmp/05_0006_copy_into_table.csv';
+SELECT count(*), sum(b), sum(c) FROM default.test_copy;
+
+DROP TABLE IF EXISTS default.test_copy;
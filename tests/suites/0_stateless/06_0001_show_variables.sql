@@ -0,0 +1,5 @@
+SELECT @@auto_increment_increment, @@session.character_set_client;
+SHOW VARIABLES LIKE 'tx_%';
+SHOW SESSION VARIABLES WHERE Variable_name = 'max_block_size';
+SET autocommit = 1;
+SHOW KEYS FROM tables FROM system;
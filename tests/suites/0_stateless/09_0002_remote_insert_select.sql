@@ -0,0 +1,15 @@
+DROP DATABASE IF EXISTS db1;
+CREATE DATABASE db1;
+USE db1;
+
+CREATE TABLE IF NOT EXISTS t1(a bigint, b bigint);
+INSERT INTO t1 SELECT number, number * 2 FROM numbers(3);
+SELECT * FROM t1 ORDER BY a;
+
+CREATE TABLE IF NOT EXISTS t2(a bigint, b bigint);
+INSERT INTO t2(b, a) SELECT a, b FROM t1 WHERE a > 0;
+SELECT * FROM t2 ORDER BY a;
+
+DROP TABLE t1;
+DROP TABLE t2;
+DROP DATABASE db1;